@@ -0,0 +1,489 @@
+//! Traits for adjusting the lightness, saturation and hue of colors
+//!
+//! * [`Adjust`](trait.Adjust.html): lighten, darken, saturate and desaturate
+//! * [`RotateHue`](trait.RotateHue.html): rotate the hue around the color wheel
+
+use crate::alpha::Alpha;
+use crate::channel::{AngularChannelScalar, FreeChannelScalar, PosNormalChannelScalar};
+use crate::color::Color;
+use crate::convert::FromColor;
+use crate::ehsi::eHsi;
+use crate::hsi::Hsi;
+use crate::hsl::Hsl;
+use crate::hsv::Hsv;
+use crate::hwb::{Hwb, HwbBoundedChannelTraits};
+use crate::lchab::Lchab;
+use crate::lchuv::Lchuv;
+use crate::oklch::Oklch;
+use crate::rgb::Rgb;
+use crate::white_point::WhitePoint;
+use angle::{Angle, Deg, FromAngle, Turns};
+use num_traits::{cast, Float};
+
+/// A color whose lightness and saturation can be adjusted
+///
+/// Each adjustment comes in two variants:
+///
+/// * Relative (`lighten`, `darken`, `saturate`, `desaturate`): `factor` is a fraction of the
+///   distance to the extreme. `lighten(0.5)` moves the lightness halfway to white, while
+///   `darken(0.5)` halves it. Repeated relative adjustments approach, but never reach, the
+///   extreme.
+/// * Absolute (`lighten_fixed`, etc.): `amount` is a fraction of the full range of the channel,
+///   which is added or subtracted directly. `lighten_fixed(0.1)` raises an `Hsl` lightness by
+///   0.1 and an `Lchab` lightness by 10.
+///
+/// Results are clamped to the valid range of each channel. Chroma has no upper limit, so for
+/// `Lchab` and `Oklch` the "full range" of saturation is a nominal maximum chroma, which is
+/// returned by `max_saturation`.
+///
+/// `Adjust` is implemented for `Rgb` by converting to and from `Hsl`.
+///
+/// ## Examples:
+///
+/// ```rust
+/// #[macro_use] extern crate approx;
+/// # extern crate prisma;
+/// extern crate angular_units as angle;
+///
+/// use prisma::{Adjust, Hsl};
+/// use angle::Deg;
+///
+/// let color = Hsl::new(Deg(120.0), 0.5, 0.4);
+/// assert_relative_eq!(color.lighten(0.5), Hsl::new(Deg(120.0), 0.5, 0.7));
+/// assert_relative_eq!(color.lighten_fixed(0.1), Hsl::new(Deg(120.0), 0.5, 0.5));
+/// assert_relative_eq!(color.desaturate(0.5), Hsl::new(Deg(120.0), 0.25, 0.4));
+/// ```
+pub trait Adjust: Sized {
+    /// The scalar type of the lightness and saturation channels
+    type Scalar: Float;
+
+    /// Returns the lightness and the saturation or chroma of `self`
+    fn lightness_saturation(&self) -> (Self::Scalar, Self::Scalar);
+    /// Returns `self` with its lightness and saturation replaced
+    ///
+    /// Implementations clamp the values to the valid range of their channels.
+    fn with_lightness_saturation(&self, lightness: Self::Scalar, saturation: Self::Scalar) -> Self;
+    /// Returns the lightness of white
+    fn max_lightness() -> Self::Scalar;
+    /// Returns the saturation that relative saturation adjustments move toward
+    fn max_saturation() -> Self::Scalar;
+
+    /// Move the lightness toward white by `factor` of the remaining distance
+    fn lighten(&self, factor: Self::Scalar) -> Self {
+        let (l, s) = self.lightness_saturation();
+        let max = Self::max_lightness();
+        self.with_lightness_saturation(
+            l + (max - l).max(<Self::Scalar as num_traits::Zero>::zero()) * factor,
+            s,
+        )
+    }
+    /// Increase the lightness by `amount` of its full range
+    fn lighten_fixed(&self, amount: Self::Scalar) -> Self {
+        let (l, s) = self.lightness_saturation();
+        self.with_lightness_saturation(l + Self::max_lightness() * amount, s)
+    }
+    /// Move the lightness toward black by `factor` of the remaining distance
+    fn darken(&self, factor: Self::Scalar) -> Self {
+        let (l, s) = self.lightness_saturation();
+        self.with_lightness_saturation(l - l * factor, s)
+    }
+    /// Decrease the lightness by `amount` of its full range
+    fn darken_fixed(&self, amount: Self::Scalar) -> Self {
+        let (l, s) = self.lightness_saturation();
+        self.with_lightness_saturation(l - Self::max_lightness() * amount, s)
+    }
+    /// Move the saturation toward the maximum by `factor` of the remaining distance
+    fn saturate(&self, factor: Self::Scalar) -> Self {
+        let (l, s) = self.lightness_saturation();
+        let max = Self::max_saturation();
+        self.with_lightness_saturation(
+            l,
+            s + (max - s).max(<Self::Scalar as num_traits::Zero>::zero()) * factor,
+        )
+    }
+    /// Increase the saturation by `amount` of its full range
+    fn saturate_fixed(&self, amount: Self::Scalar) -> Self {
+        let (l, s) = self.lightness_saturation();
+        self.with_lightness_saturation(l, s + Self::max_saturation() * amount)
+    }
+    /// Move the saturation toward gray by `factor` of the remaining distance
+    fn desaturate(&self, factor: Self::Scalar) -> Self {
+        let (l, s) = self.lightness_saturation();
+        self.with_lightness_saturation(l, s - s * factor)
+    }
+    /// Decrease the saturation by `amount` of its full range
+    fn desaturate_fixed(&self, amount: Self::Scalar) -> Self {
+        let (l, s) = self.lightness_saturation();
+        self.with_lightness_saturation(l, s - Self::max_saturation() * amount)
+    }
+}
+
+fn clamp<T: Float>(value: T, max: T) -> T {
+    value.max(T::zero()).min(max)
+}
+
+impl<T, A> Adjust for Hsl<T, A>
+where
+    T: PosNormalChannelScalar + Float,
+    A: AngularChannelScalar,
+{
+    type Scalar = T;
+
+    fn lightness_saturation(&self) -> (T, T) {
+        (self.lightness(), self.saturation())
+    }
+    fn with_lightness_saturation(&self, lightness: T, saturation: T) -> Self {
+        Hsl::new(
+            self.hue(),
+            clamp(saturation, T::one()),
+            clamp(lightness, T::one()),
+        )
+    }
+    fn max_lightness() -> T {
+        T::one()
+    }
+    fn max_saturation() -> T {
+        T::one()
+    }
+}
+
+impl<T, A> Adjust for Hsv<T, A>
+where
+    T: PosNormalChannelScalar + Float,
+    A: AngularChannelScalar,
+{
+    type Scalar = T;
+
+    /// The value channel is used as the lightness
+    fn lightness_saturation(&self) -> (T, T) {
+        (self.value(), self.saturation())
+    }
+    fn with_lightness_saturation(&self, lightness: T, saturation: T) -> Self {
+        Hsv::new(
+            self.hue(),
+            clamp(saturation, T::one()),
+            clamp(lightness, T::one()),
+        )
+    }
+    fn max_lightness() -> T {
+        T::one()
+    }
+    fn max_saturation() -> T {
+        T::one()
+    }
+}
+
+impl<T, W, A> Adjust for Lchab<T, W, A>
+where
+    T: FreeChannelScalar,
+    W: WhitePoint<T>,
+    A: AngularChannelScalar,
+{
+    type Scalar = T;
+
+    fn lightness_saturation(&self) -> (T, T) {
+        (self.L(), self.chroma())
+    }
+    fn with_lightness_saturation(&self, lightness: T, saturation: T) -> Self {
+        Lchab::new_with_whitepoint(
+            clamp(lightness, Self::max_lightness()),
+            saturation.max(T::zero()),
+            self.hue(),
+            self.white_point().clone(),
+        )
+    }
+    fn max_lightness() -> T {
+        cast(100.0).unwrap()
+    }
+    /// A nominal maximum chroma, approximately the highest chroma of colors in sRGB
+    fn max_saturation() -> T {
+        cast(130.0).unwrap()
+    }
+}
+
+impl<T, A> Adjust for Oklch<T, A>
+where
+    T: FreeChannelScalar,
+    A: AngularChannelScalar,
+{
+    type Scalar = T;
+
+    fn lightness_saturation(&self) -> (T, T) {
+        (self.L(), self.chroma())
+    }
+    fn with_lightness_saturation(&self, lightness: T, saturation: T) -> Self {
+        Oklch::new(
+            clamp(lightness, T::one()),
+            saturation.max(T::zero()),
+            self.hue(),
+        )
+    }
+    fn max_lightness() -> T {
+        T::one()
+    }
+    /// A nominal maximum chroma, approximately the highest chroma of colors in Display P3
+    fn max_saturation() -> T {
+        cast(0.4).unwrap()
+    }
+}
+
+impl<T> Adjust for Rgb<T>
+where
+    T: PosNormalChannelScalar + Float,
+{
+    type Scalar = T;
+
+    fn lightness_saturation(&self) -> (T, T) {
+        Hsl::<T, Turns<T>>::from_color(self).lightness_saturation()
+    }
+    fn with_lightness_saturation(&self, lightness: T, saturation: T) -> Self {
+        let hsl = Hsl::<T, Turns<T>>::from_color(self);
+        Rgb::from_color(&hsl.with_lightness_saturation(lightness, saturation))
+    }
+    fn max_lightness() -> T {
+        T::one()
+    }
+    fn max_saturation() -> T {
+        T::one()
+    }
+}
+
+/// A color with a hue that can be rotated around the color wheel
+///
+/// `rotate_hue` accepts an angle in any unit, which is converted to the unit of the color's hue.
+/// The resulting hue is normalized into its unit's range, so rotating by a negative angle or by
+/// more than a full turn is well defined.
+///
+/// `RotateHue` is implemented for every polar color, for `Alpha` wrapping one, and for `Rgb` by
+/// converting to and from `Hsl`.
+///
+/// ## Examples:
+///
+/// ```rust
+/// #[macro_use] extern crate approx;
+/// # extern crate prisma;
+/// extern crate angular_units as angle;
+///
+/// use prisma::{Hsv, RotateHue};
+/// use angle::{Deg, Turns};
+///
+/// let color = Hsv::new(Deg(300.0), 0.5, 0.5);
+/// assert_relative_eq!(color.rotate_hue(Deg(90.0)), Hsv::new(Deg(30.0), 0.5, 0.5));
+/// assert_relative_eq!(color.rotate_hue(Turns(-0.5)), Hsv::new(Deg(120.0), 0.5, 0.5));
+/// ```
+pub trait RotateHue: Sized {
+    /// The angular type of the hue
+    type Hue: AngularChannelScalar;
+
+    /// Returns the hue of `self`
+    fn hue_angle(&self) -> Self::Hue;
+    /// Returns `self` with its hue replaced by `hue`
+    fn with_hue(&self, hue: Self::Hue) -> Self;
+
+    /// Rotate the hue by `amount`
+    fn rotate_hue<U>(&self, amount: U) -> Self
+    where
+        U: Angle,
+        Self::Hue: FromAngle<U>,
+    {
+        let hue = self.hue_angle() + Self::Hue::from_angle(amount);
+        self.with_hue(AngularChannelScalar::normalize(hue))
+    }
+}
+
+macro_rules! impl_rotate_hue {
+    ($Color: ty, [$($param: ident),*], [$($bounds: tt)*]) => {
+        impl<$($param),*> RotateHue for $Color
+        where
+            $($bounds)*
+        {
+            type Hue = A;
+
+            fn hue_angle(&self) -> A {
+                self.hue()
+            }
+            fn with_hue(&self, hue: A) -> Self {
+                let mut out = self.clone();
+                out.set_hue(hue);
+                out
+            }
+        }
+    };
+}
+
+impl_rotate_hue!(Hsl<T, A>, [T, A], [
+    T: PosNormalChannelScalar + Float,
+    A: AngularChannelScalar,
+]);
+impl_rotate_hue!(Hsv<T, A>, [T, A], [
+    T: PosNormalChannelScalar,
+    A: AngularChannelScalar,
+]);
+impl_rotate_hue!(Hwb<T, A>, [T, A], [
+    T: HwbBoundedChannelTraits,
+    A: AngularChannelScalar,
+]);
+impl_rotate_hue!(Hsi<T, A>, [T, A], [
+    T: PosNormalChannelScalar + Float,
+    A: AngularChannelScalar + Angle<Scalar = T>,
+]);
+impl_rotate_hue!(eHsi<T, A>, [T, A], [
+    T: PosNormalChannelScalar + Float,
+    A: AngularChannelScalar + Angle<Scalar = T>,
+]);
+impl_rotate_hue!(Lchab<T, W, A>, [T, W, A], [
+    T: FreeChannelScalar,
+    W: WhitePoint<T>,
+    A: AngularChannelScalar,
+]);
+impl_rotate_hue!(Lchuv<T, W, A>, [T, W, A], [
+    T: FreeChannelScalar,
+    W: WhitePoint<T>,
+    A: AngularChannelScalar,
+]);
+impl_rotate_hue!(Oklch<T, A>, [T, A], [
+    T: FreeChannelScalar,
+    A: AngularChannelScalar,
+]);
+
+impl<T, InnerColor> RotateHue for Alpha<T, InnerColor>
+where
+    T: PosNormalChannelScalar,
+    InnerColor: Color + RotateHue,
+{
+    type Hue = InnerColor::Hue;
+
+    fn hue_angle(&self) -> Self::Hue {
+        self.color().hue_angle()
+    }
+    fn with_hue(&self, hue: Self::Hue) -> Self {
+        Alpha::new(self.color().with_hue(hue), self.alpha())
+    }
+}
+
+impl<T> RotateHue for Rgb<T>
+where
+    T: PosNormalChannelScalar + Float,
+{
+    type Hue = Deg<T>;
+
+    fn hue_angle(&self) -> Deg<T> {
+        Hsl::<T, Deg<T>>::from_color(self).hue()
+    }
+    fn with_hue(&self, hue: Deg<T>) -> Self {
+        let hsl = Hsl::<T, Deg<T>>::from_color(self);
+        Rgb::from_color(&hsl.with_hue(hue))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::alpha::{Hsla, Rgba};
+    use crate::white_point::D65;
+    use angle::Rad;
+    use approx::*;
+
+    #[test]
+    fn test_hsl() {
+        let c1 = Hsl::new(Deg(200.0), 0.6, 0.4);
+        assert_relative_eq!(c1.lighten(0.5), Hsl::new(Deg(200.0), 0.6, 0.7));
+        assert_relative_eq!(c1.lighten_fixed(0.2), Hsl::new(Deg(200.0), 0.6, 0.6));
+        assert_relative_eq!(c1.darken(0.5), Hsl::new(Deg(200.0), 0.6, 0.2));
+        assert_relative_eq!(c1.darken_fixed(0.1), Hsl::new(Deg(200.0), 0.6, 0.3));
+        assert_relative_eq!(c1.saturate(0.5), Hsl::new(Deg(200.0), 0.8, 0.4));
+        assert_relative_eq!(c1.saturate_fixed(0.1), Hsl::new(Deg(200.0), 0.7, 0.4));
+        assert_relative_eq!(c1.desaturate(0.25), Hsl::new(Deg(200.0), 0.45, 0.4));
+        assert_relative_eq!(c1.desaturate_fixed(0.6), Hsl::new(Deg(200.0), 0.0, 0.4));
+
+        assert_relative_eq!(c1.lighten_fixed(0.8), Hsl::new(Deg(200.0), 0.6, 1.0));
+        assert_relative_eq!(c1.darken_fixed(0.8), Hsl::new(Deg(200.0), 0.6, 0.0));
+        assert_relative_eq!(c1.saturate_fixed(0.8), Hsl::new(Deg(200.0), 1.0, 0.4));
+        assert_relative_eq!(c1.desaturate_fixed(0.8), Hsl::new(Deg(200.0), 0.0, 0.4));
+    }
+
+    #[test]
+    fn test_hsv() {
+        let c1 = Hsv::new(Deg(10.0), 0.5, 0.5);
+        assert_relative_eq!(c1.lighten(0.5), Hsv::new(Deg(10.0), 0.5, 0.75));
+        assert_relative_eq!(c1.darken_fixed(0.25), Hsv::new(Deg(10.0), 0.5, 0.25));
+        assert_relative_eq!(c1.saturate(0.5), Hsv::new(Deg(10.0), 0.75, 0.5));
+    }
+
+    #[test]
+    fn test_lch() {
+        let c1 = Lchab::<f64, D65>::new(50.0, 40.0, Deg(30.0));
+        assert_relative_eq!(c1.lighten(0.5), Lchab::new(75.0, 40.0, Deg(30.0)));
+        assert_relative_eq!(c1.lighten_fixed(0.1), Lchab::new(60.0, 40.0, Deg(30.0)));
+        assert_relative_eq!(c1.darken_fixed(0.6), Lchab::new(0.0, 40.0, Deg(30.0)));
+        assert_relative_eq!(c1.saturate(0.5), Lchab::new(50.0, 85.0, Deg(30.0)));
+        assert_relative_eq!(c1.desaturate(0.5), Lchab::new(50.0, 20.0, Deg(30.0)));
+        assert_relative_eq!(c1.saturate_fixed(1.0), Lchab::new(50.0, 170.0, Deg(30.0)));
+        let c2 = Lchab::<f64, D65>::new(50.0, 150.0, Deg(30.0));
+        assert_relative_eq!(c2.saturate(0.5), c2);
+
+        let c3 = Oklch::new(0.6, 0.1, Deg(140.0));
+        assert_relative_eq!(c3.darken(0.5), Oklch::new(0.3, 0.1, Deg(140.0)));
+        assert_relative_eq!(c3.saturate_fixed(0.25), Oklch::new(0.6, 0.2, Deg(140.0)));
+        assert_relative_eq!(c3.desaturate_fixed(1.0), Oklch::new(0.6, 0.0, Deg(140.0)));
+    }
+
+    #[test]
+    fn test_rgb() {
+        let c1 = Rgb::new(0.8, 0.4, 0.2);
+        let hsl = Hsl::<f64, Deg<f64>>::from_color(&c1);
+        assert_relative_eq!(
+            c1.lighten(0.5),
+            Rgb::from_color(&hsl.lighten(0.5)),
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(c1.desaturate(1.0), Rgb::new(0.5, 0.5, 0.5), epsilon = 1e-9);
+        assert_relative_eq!(c1.darken(1.0), Rgb::new(0.0, 0.0, 0.0), epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_rotate_hue() {
+        let c1 = Hsl::new(Deg(300.0), 0.5, 0.5);
+        assert_relative_eq!(c1.rotate_hue(Deg(90.0)), Hsl::new(Deg(30.0), 0.5, 0.5));
+        assert_relative_eq!(c1.rotate_hue(Deg(-330.0)), Hsl::new(Deg(330.0), 0.5, 0.5));
+        assert_relative_eq!(c1.rotate_hue(Deg(780.0)), Hsl::new(Deg(0.0), 0.5, 0.5));
+        assert_relative_eq!(
+            c1.rotate_hue(Rad(std::f64::consts::PI)),
+            Hsl::new(Deg(120.0), 0.5, 0.5),
+            epsilon = 1e-9
+        );
+
+        let c2 = Lchab::<f64, D65, Turns<f64>>::new(50.0, 30.0, Turns(0.75));
+        assert_relative_eq!(
+            c2.rotate_hue(Deg(180.0)),
+            Lchab::new(50.0, 30.0, Turns(0.25))
+        );
+        let c3 = Oklch::new(0.5, 0.1, Rad(1.0));
+        assert_relative_eq!(c3.rotate_hue(Rad(-0.5)).hue(), Rad(0.5));
+        assert_relative_eq!(c3.rotate_hue(Rad(-0.5)).chroma(), 0.1);
+
+        let c4 = Hsla::new(Hsl::new(Deg(10.0), 0.5, 0.5), 0.25);
+        assert_relative_eq!(
+            c4.rotate_hue(Deg(20.0)),
+            Hsla::new(Hsl::new(Deg(30.0), 0.5, 0.5), 0.25)
+        );
+    }
+
+    #[test]
+    fn test_rotate_rgb() {
+        let red = Rgb::new(1.0, 0.0, 0.0);
+        assert_relative_eq!(red.hue_angle(), Deg(0.0));
+        assert_relative_eq!(
+            red.rotate_hue(Deg(120.0)),
+            Rgb::new(0.0, 1.0, 0.0),
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            red.rotate_hue(Turns(-1.0 / 3.0)),
+            Rgb::new(0.0, 0.0, 1.0),
+            epsilon = 1e-9
+        );
+        let gray = Rgba::new(Rgb::new(0.4, 0.4, 0.4), 0.5);
+        assert_relative_eq!(gray.rotate_hue(Deg(75.0)), gray, epsilon = 1e-9);
+    }
+}
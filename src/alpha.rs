@@ -2,12 +2,12 @@
 #![allow(non_camel_case_types)]
 
 use crate::channel::{
-    AngularChannelScalar, ColorChannel, NormalChannelScalar, PosNormalBoundedChannel,
-    PosNormalChannelScalar,
+    AngularChannelScalar, ChannelCast, ChannelFormatCast, ColorChannel, NormalChannelScalar,
+    PosNormalBoundedChannel, PosNormalChannelScalar, SaturatingChannelScalar,
 };
 use crate::color::{
-    Bounded, Broadcast, Color, Color3, Color4, Flatten, FromTuple, HomogeneousColor, Invert, Lerp,
-    PolarColor,
+    Bounded, BoundedArithmetic, Broadcast, ChannelAccess, ChannelOrder, Color, Color3, Color4,
+    Flatten, FromTuple, HomogeneousColor, HueMode, Invert, Lerp, PolarColor, PolarLerp,
 };
 use crate::convert::{FromColor, FromHsi, FromYCbCr};
 use crate::encoding::EncodableColor;
@@ -24,13 +24,16 @@ use std::ops::{Deref, DerefMut};
 use std::slice;
 
 use crate::lms::Lms;
-use crate::{eHsi, Hsl, Hsv, Hwb, Lab, Lchab, Lchuv, Luv, Rgb, Rgi, XyY, Xyz};
+use crate::{eHsi, Hsl, Hsv, Hwb, Lab, Lchab, Lchuv, Luma, Luv, Oklab, Oklch, Rgb, Rgi, XyY, Xyz};
 
 /// A wrapper around a color with an alpha channel
 ///
 /// `Alpha<T>` makes it easy to add an alpha channel to any other color and share code between
 /// all color types. `Alpha<T>` implements `Deref` and `DerefMut`, making it able to act like the
 /// underlying color in many situations.
+///
+/// `Alpha` is `Eq`, `Hash` and `Ord` whenever the inner color and `T` are, as with `Rgba<u8>`.
+/// Colors are ordered by the inner color first, then by alpha.
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Eq, Ord, PartialEq, PartialOrd, Hash)]
 pub struct Alpha<T, InnerColor> {
@@ -79,6 +82,139 @@ where
     pub fn set_alpha(&mut self, alpha: T) {
         self.alpha.0 = alpha
     }
+
+    /// Construct an `Alpha` from a tuple of the inner color and alpha value
+    ///
+    /// This is the inverse of [`split`](#method.split).
+    pub fn from_parts(parts: (InnerColor, T)) -> Self {
+        Alpha::new(parts.0, parts.1)
+    }
+    /// Break apart an `Alpha` into the inner color and alpha value
+    ///
+    /// This is the same as [`decompose`](#method.decompose), and the inverse of
+    /// [`from_parts`](#method.from_parts).
+    pub fn split(self) -> (InnerColor, T) {
+        self.decompose()
+    }
+
+    /// Return an `Alpha` with `f` applied to the inner color, keeping the alpha value
+    ///
+    /// `f` may return a different color type, for example to convert the inner color.
+    pub fn map_color<F, OutColor>(self, f: F) -> Alpha<T, OutColor>
+    where
+        F: FnOnce(InnerColor) -> OutColor,
+        OutColor: Color,
+    {
+        Alpha {
+            color: f(self.color),
+            alpha: self.alpha,
+        }
+    }
+    /// Return an `Alpha` with `f` applied to the alpha value, keeping the inner color
+    pub fn map_alpha<F>(self, f: F) -> Self
+    where
+        F: FnOnce(T) -> T,
+    {
+        Alpha::new(self.color, f(self.alpha.0))
+    }
+}
+
+impl<T, InnerColor> Alpha<T, InnerColor>
+where
+    T: PosNormalChannelScalar + num_traits::Float,
+    InnerColor: ChannelAccess<ChannelFormat = T>,
+{
+    /// Return the color with each channel of the inner color multiplied by alpha
+    ///
+    /// Premultiplied colors are the usual input to compositing, as blending them needs fewer
+    /// operations and filtering them doesn't bleed the color of transparent pixels into their
+    /// neighbors. The alpha value itself is unchanged. Colors with integer channels can be
+    /// premultiplied by casting them to floating point first.
+    pub fn premultiply(self) -> Self {
+        let alpha = self.alpha.0;
+        self.map_color(|color| color.map_channels(|c| c * alpha))
+    }
+    /// Return the color with each channel of the inner color divided by alpha
+    ///
+    /// This is the inverse of [`premultiply`](#method.premultiply). A fully transparent color
+    /// carries no color information once premultiplied, so it is returned unchanged.
+    pub fn unpremultiply(self) -> Self {
+        let alpha = self.alpha.0;
+        if alpha == T::zero() {
+            self
+        } else {
+            self.map_color(|color| color.map_channels(|c| c / alpha))
+        }
+    }
+}
+
+macro_rules! impl_alpha_color_cast_square {
+    ($name:ident) => {
+        impl<T> Alpha<T, $name<T>>
+        where
+            T: PosNormalChannelScalar,
+        {
+            /// Convert the channel scalar format of the color and alpha channel
+            pub fn color_cast<TOut>(&self) -> Alpha<TOut, $name<TOut>>
+            where
+                T: ChannelFormatCast<TOut>,
+                TOut: PosNormalChannelScalar,
+            {
+                Alpha {
+                    color: self.color.color_cast(),
+                    alpha: self.alpha.clone().channel_cast(),
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_alpha_color_cast_angular {
+    ($name:ident) => {
+        impl<T, A> Alpha<T, $name<T, A>>
+        where
+            T: PosNormalChannelScalar,
+            A: AngularChannelScalar,
+        {
+            /// Convert the channel scalar format of the color and alpha channel
+            pub fn color_cast<TOut, AOut>(&self) -> Alpha<TOut, $name<TOut, AOut>>
+            where
+                T: ChannelFormatCast<TOut>,
+                A: ChannelFormatCast<AOut>,
+                TOut: PosNormalChannelScalar,
+                AOut: AngularChannelScalar,
+            {
+                Alpha {
+                    color: self.color.color_cast(),
+                    alpha: self.alpha.clone().channel_cast(),
+                }
+            }
+        }
+    };
+}
+
+impl_alpha_color_cast_square!(Rgb);
+impl_alpha_color_cast_square!(Luma);
+impl_alpha_color_cast_angular!(Hsv);
+impl_alpha_color_cast_angular!(Hsl);
+impl_alpha_color_cast_angular!(Hwb);
+
+impl<T, M> Alpha<T, YCbCr<T, M>>
+where
+    T: NormalChannelScalar + PosNormalChannelScalar,
+    M: YCbCrModel<T>,
+{
+    /// Convert the channel scalar format of the color and alpha channel
+    pub fn color_cast<TOut>(&self) -> Alpha<TOut, YCbCr<TOut, M>>
+    where
+        T: ChannelFormatCast<TOut>,
+        TOut: NormalChannelScalar + PosNormalChannelScalar,
+    {
+        Alpha {
+            color: self.color.color_cast(),
+            alpha: self.alpha.clone().channel_cast(),
+        }
+    }
 }
 
 impl<T, InnerColor> Color for Alpha<T, InnerColor>
@@ -143,6 +279,21 @@ where
     }
 }
 
+impl<T, InnerColor> PolarLerp for Alpha<T, InnerColor>
+where
+    T: PosNormalChannelScalar + Lerp<Position = InnerColor::Position>,
+    InnerColor: Color + PolarLerp,
+{
+    fn lerp_with_hue_mode(&self, right: &Self, pos: Self::Position, mode: HueMode) -> Self {
+        Alpha {
+            color: self
+                .color
+                .lerp_with_hue_mode(&right.color, pos.clone(), mode),
+            alpha: self.alpha.lerp(&right.alpha, pos),
+        }
+    }
+}
+
 impl<T, InnerColor> Bounded for Alpha<T, InnerColor>
 where
     T: PosNormalChannelScalar,
@@ -159,6 +310,37 @@ where
     }
 }
 
+impl<T, InnerColor> BoundedArithmetic for Alpha<T, InnerColor>
+where
+    T: PosNormalChannelScalar + SaturatingChannelScalar,
+    InnerColor: Color + BoundedArithmetic,
+{
+    fn saturating_add(&self, rhs: &Self) -> Self {
+        Alpha {
+            color: self.color.saturating_add(&rhs.color),
+            alpha: self.alpha.clone().saturating_add(rhs.alpha.clone()),
+        }
+    }
+    fn saturating_sub(&self, rhs: &Self) -> Self {
+        Alpha {
+            color: self.color.saturating_sub(&rhs.color),
+            alpha: self.alpha.clone().saturating_sub(rhs.alpha.clone()),
+        }
+    }
+    fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        Some(Alpha {
+            color: self.color.checked_add(&rhs.color)?,
+            alpha: self.alpha.clone().checked_add(rhs.alpha.clone())?,
+        })
+    }
+    fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        Some(Alpha {
+            color: self.color.checked_sub(&rhs.color)?,
+            alpha: self.alpha.clone().checked_sub(rhs.alpha.clone())?,
+        })
+    }
+}
+
 impl<T, InnerColor> HomogeneousColor for Alpha<T, InnerColor>
 where
     T: PosNormalChannelScalar,
@@ -173,6 +355,71 @@ where
     }
 }
 
+macro_rules! impl_rgba_swizzle {
+    ($($name:ident => ($r:ident, $g:ident, $b:ident, $a:ident)),*) => {
+        $(
+            #[doc = concat!(
+                "Returns the color with its channels reordered, as `Rgba::new(Rgb::new(",
+                stringify!($r), ", ", stringify!($g), ", ", stringify!($b), "), ",
+                stringify!($a), ")`"
+            )]
+            pub fn $name(&self) -> Rgba<T> {
+                Rgba::new(Rgb::new(self.$r(), self.$g(), self.$b()), self.$a())
+            }
+        )*
+    };
+}
+
+impl<T> Rgba<T>
+where
+    T: PosNormalChannelScalar,
+{
+    impl_rgba_swizzle!(
+        rbga => (red, blue, green, alpha),
+        grba => (green, red, blue, alpha),
+        gbra => (green, blue, red, alpha),
+        brga => (blue, red, green, alpha),
+        bgra => (blue, green, red, alpha),
+        argb => (alpha, red, green, blue),
+        abgr => (alpha, blue, green, red)
+    );
+}
+
+impl<T, InnerColor> ChannelOrder for Alpha<T, InnerColor>
+where
+    T: PosNormalChannelScalar,
+    InnerColor: ChannelOrder,
+{
+    fn channel_name(index: usize) -> Option<&'static str> {
+        if index == InnerColor::num_channels() as usize {
+            Some("alpha")
+        } else {
+            InnerColor::channel_name(index)
+        }
+    }
+}
+
+impl<T, InnerColor> ChannelAccess for Alpha<T, InnerColor>
+where
+    T: PosNormalChannelScalar,
+    InnerColor: ChannelAccess + HomogeneousColor<ChannelFormat = T>,
+{
+    fn channel(&self, index: usize) -> Option<&T> {
+        if index == InnerColor::num_channels() as usize {
+            Some(&self.alpha.0)
+        } else {
+            self.color.channel(index)
+        }
+    }
+    fn channel_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index == InnerColor::num_channels() as usize {
+            Some(&mut self.alpha.0)
+        } else {
+            self.color.channel_mut(index)
+        }
+    }
+}
+
 impl<T, InnerColor> Broadcast for Alpha<T, InnerColor>
 where
     T: PosNormalChannelScalar,
@@ -328,6 +575,8 @@ where
 
 /// An `Rgb` value with an alpha channel
 pub type Rgba<T> = Alpha<T, Rgb<T>>;
+/// A `Luma` value with an alpha channel
+pub type LumaA<T> = Alpha<T, Luma<T>>;
 /// An `Rgi` value with an alpha channel
 pub type Rgia<T> = Alpha<T, Rgi<T>>;
 /// An `Hsl` value with an alpha channel
@@ -356,6 +605,10 @@ pub type Lchaba<T, W, A = Deg<T>> = Alpha<T, Lchab<T, W, A>>;
 pub type Lchauv<T, W, A = Deg<T>> = Alpha<T, Lchuv<T, W, A>>;
 /// An `Lmsa` value with an alpha channel
 pub type Lmsa<T, M> = Alpha<T, Lms<T, M>>;
+/// An `Oklab` value with an alpha channel
+pub type Oklaba<T> = Alpha<T, Oklab<T>>;
+/// An `Oklch` value with an alpha channel
+pub type Oklcha<T, A = Deg<T>> = Alpha<T, Oklch<T, A>>;
 
 #[cfg(test)]
 mod test {
@@ -429,6 +682,20 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_lerp_with_hue_mode() {
+        let c1 = Hsva::new(Hsv::new(Deg(60.0), 0.25, 0.55), 0.95);
+        let c2 = Hsva::new(Hsv::new(Deg(340.0), 0.95, 0.0), 0.25);
+        assert_relative_eq!(
+            c1.lerp_with_hue_mode(&c2, 0.25, HueMode::Shorter),
+            c1.lerp(&c2, 0.25)
+        );
+        assert_relative_eq!(
+            c1.lerp_with_hue_mode(&c2, 0.25, HueMode::Increasing),
+            Hsva::new(Hsv::new(Deg(130.0), 0.425, 0.41250), 0.7750)
+        );
+    }
+
     #[test]
     fn test_flatten() {
         let c1 = Rgba::new(Rgb::new(100u8, 50, 175), 254);
@@ -448,4 +715,106 @@ mod test {
         c1.set_red(100);
         assert_eq!(c1.red(), 100);
     }
+
+    #[test]
+    fn test_color_cast() {
+        let c1 = Rgba::new(Rgb::new(255u8, 128, 0), 51u8);
+        let c2: Rgba<u16> = c1.color_cast();
+        assert_eq!(c2, Rgba::new(Rgb::new(65535u16, 32896, 0), 13107));
+        assert_eq!(c2.color_cast::<u8>(), c1);
+        assert_eq!(c2.color_cast::<u32>().color_cast::<u16>(), c2);
+
+        let c3 = Hsva::new(Hsv::new(Deg(90.0f32), 1.0f32, 0.5), 0.2f32);
+        let c4 = c3.color_cast::<u16, Deg<f32>>();
+        assert_eq!(c4.alpha(), 13107);
+        assert_relative_eq!(c4.color_cast(), c3, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn test_bounded_arithmetic() {
+        let c1 = Rgba::new(Rgb::new(200u8, 10, 0), 250u8);
+        let c2 = Rgba::new(Rgb::new(100u8, 10, 0), 10u8);
+        assert_eq!(c1.saturating_add(&c2), Rgba::new(Rgb::new(255, 20, 0), 255));
+        assert_eq!(
+            c1.checked_sub(&c2),
+            Some(Rgba::new(Rgb::new(100, 0, 0), 240))
+        );
+        assert_eq!(c2.checked_sub(&c1), None);
+        let opaque = Rgba::new(Rgb::new(0u8, 0, 0), 255u8);
+        assert_eq!(c2.checked_add(&opaque), None);
+    }
+
+    #[test]
+    fn test_channel_access() {
+        use crate::white_point::D65;
+        use crate::ycbcr::YCbCr;
+
+        assert_eq!(
+            Rgba::<u8>::channel_names(),
+            vec!["red", "green", "blue", "alpha"]
+        );
+        assert_eq!(Hsla::<f32, Deg<f32>>::channel_names()[2], "lightness");
+        assert_eq!(Lchaba::<f64, D65>::channel_index("hue"), Some(2));
+        assert_eq!(XyY::<f32>::channel_index("Y"), Some(2));
+        assert_eq!(Rgb::<u8>::channel_index("alpha"), None);
+
+        let mut c1 = Rgba::new(Rgb::new(10u8, 20, 30), 40);
+        let values: Vec<_> = (0..4).map(|i| *c1.channel(i).unwrap()).collect();
+        assert_eq!(values, vec![10, 20, 30, 40]);
+        assert_eq!(c1.channel(4), None);
+        *c1.channel_mut(0).unwrap() = 50;
+        *c1.channel_by_name_mut("alpha").unwrap() = 60;
+        assert_eq!(c1, Rgba::new(Rgb::new(50, 20, 30), 60));
+        assert_eq!(c1.channel_by_name("green"), Some(&20));
+        assert_eq!(c1.channel_by_name_mut("hue"), None);
+
+        let mut c2 = YCbCr::<f32>::new(0.5, -0.25, 0.25);
+        *c2.channel_by_name_mut("cr").unwrap() = 0.0;
+        assert_eq!(c2.channel(1), Some(&-0.25));
+        assert_eq!(c2.cr(), 0.0);
+    }
+
+    #[test]
+    fn test_swizzle() {
+        let c1 = Rgba::new(Rgb::new(10u8, 20, 30), 40);
+        assert_eq!(c1.bgra(), Rgba::new(Rgb::new(30, 20, 10), 40));
+        assert_eq!(c1.argb(), Rgba::new(Rgb::new(40, 10, 20), 30));
+        assert_eq!(c1.abgr(), Rgba::new(Rgb::new(40, 30, 20), 10));
+        assert_eq!(c1.gbra(), Rgba::new(Rgb::new(20, 30, 10), 40));
+        assert_eq!(c1.argb().argb().argb().argb(), c1);
+        assert_eq!(c1.abgr().abgr(), c1);
+        assert_eq!(c1.bgr(), Rgb::new(30, 20, 10));
+        assert_eq!(c1.rgba(50).argb(), Rgba::new(Rgb::new(50, 10, 20), 30));
+    }
+
+    #[test]
+    fn test_combinators() {
+        let c1 = Rgba::new(Rgb::new(0.5, 0.25, 1.0), 0.5);
+        let (color, alpha) = c1.split();
+        assert_eq!(color, Rgb::new(0.5, 0.25, 1.0));
+        assert_eq!(alpha, 0.5);
+        assert_eq!(Rgba::from_parts(c1.split()), c1);
+
+        assert_eq!(c1.map_alpha(|a| a * 0.5).alpha(), 0.25);
+        assert_eq!(c1.map_color(|c| c.bgr()).color(), &Rgb::new(1.0, 0.25, 0.5));
+        let luma = c1.map_color(|c| Luma::new(c.red()));
+        assert_eq!(luma, LumaA::new(Luma::new(0.5), 0.5));
+    }
+
+    #[test]
+    fn test_premultiply() {
+        let c1 = Rgba::new(Rgb::new(0.5, 0.25, 1.0), 0.5);
+        let premultiplied = c1.premultiply();
+        assert_relative_eq!(premultiplied, Rgba::new(Rgb::new(0.25, 0.125, 0.5), 0.5));
+        assert_relative_eq!(premultiplied.unpremultiply(), c1);
+
+        let c2 = LumaA::new(Luma::new(0.8f32), 0.0);
+        assert_eq!(c2.premultiply(), LumaA::new(Luma::new(0.0), 0.0));
+        assert_eq!(
+            c2.premultiply().unpremultiply(),
+            LumaA::new(Luma::new(0.0), 0.0)
+        );
+        let c3 = Rgba::new(Rgb::new(0.25f32, 0.5, 0.75), 1.0);
+        assert_eq!(c3.premultiply(), c3);
+    }
 }
@@ -0,0 +1,343 @@
+//! Conversions between colors and arrays of their channels
+//!
+//! Every color with a fixed number of channels converts to and from `[T; N]` with `From`, with the
+//! channels in the order `new` takes them. `Alpha` colors append the alpha channel to the array of
+//! their inner color. Hues are stored in the array as the scalar of their angle type, so these
+//! conversions only exist for polar colors when the angle's scalar is the channel type.
+//!
+//! Cartesian colors also implement `AsRef<[T; N]>`, borrowing their channels in place.
+//!
+//! With the `mint` feature enabled, the same conversions exist for `mint::Vector2`, `Vector3` and
+//! `Vector4`, with `IntoMint` naming the vector type a color converts to.
+
+#![allow(non_snake_case)]
+
+use crate::alpha::Alpha;
+use crate::channel::{
+    AngularChannelScalar, FreeChannelScalar, NormalChannelScalar, PosNormalChannelScalar,
+};
+use crate::color::{Color, FromTuple};
+use crate::ehsi::eHsi;
+use crate::hsi::Hsi;
+use crate::hsl::Hsl;
+use crate::hsv::Hsv;
+use crate::hwb::{Hwb, HwbBoundedChannelTraits};
+use crate::lab::Lab;
+use crate::lchab::Lchab;
+use crate::lchuv::Lchuv;
+use crate::lms::{Lms, LmsModel};
+use crate::luma::Luma;
+use crate::luv::Luv;
+use crate::oklab::Oklab;
+use crate::oklch::Oklch;
+use crate::rgb::Rgb;
+use crate::rgi::Rgi;
+use crate::white_point::UnitWhitePoint;
+use crate::xyy::XyY;
+use crate::xyz::Xyz;
+use crate::ycbcr::{BareYCbCr, UnitModel, YCbCr, YCbCrModel};
+use angle::Angle;
+use num_traits::Float;
+use std::mem;
+
+macro_rules! array_channel {
+    (from chan $c: ident) => {
+        $c
+    };
+    (from hue $c: ident) => {
+        A::new($c)
+    };
+    (into chan $c: ident) => {
+        $c
+    };
+    (into hue $c: ident) => {
+        $c.scalar()
+    };
+}
+
+macro_rules! impl_color_array {
+    ($name: ident<$($param: ident),*> = [T; $n: tt] {$($kind: ident $c: ident),*},
+        where $($bounds: tt)*) =>
+    {
+        impl<$($param),*> From<[T; $n]> for $name<$($param),*>
+        where
+            $($bounds)*
+        {
+            fn from(array: [T; $n]) -> Self {
+                let [$($c),*] = array;
+                $name::from_tuple(($(array_channel!(from $kind $c),)*))
+            }
+        }
+
+        impl<$($param),*> From<$name<$($param),*>> for [T; $n]
+        where
+            $($bounds)*
+        {
+            fn from(color: $name<$($param),*>) -> Self {
+                let ($($c,)*) = color.to_tuple();
+                [$(array_channel!(into $kind $c)),*]
+            }
+        }
+
+        #[cfg(feature = "mint")]
+        impl_color_array!(@mint $name<$($param),*> = [T; $n], where $($bounds)*);
+    };
+    ($name: ident<$($param: ident),*> = [T; $n: tt] {$($c: ident),*}, as_ref,
+        where $($bounds: tt)*) =>
+    {
+        impl_color_array!($name<$($param),*> = [T; $n] {$(chan $c),*}, where $($bounds)*);
+
+        impl<$($param),*> AsRef<[T; $n]> for $name<$($param),*>
+        where
+            $($bounds)*
+        {
+            fn as_ref(&self) -> &[T; $n] {
+                // Colors are `repr(C)` and start with their channels, which are `repr(transparent)`
+                unsafe { &*(self as *const Self as *const [T; $n]) }
+            }
+        }
+    };
+    (@mint $name: ident<$($param: ident),*> = [T; 1], where $($bounds: tt)*) => {};
+    (@mint $name: ident<$($param: ident),*> = [T; 3], where $($bounds: tt)*) => {
+        impl<$($param),*> From<mint::Vector3<T>> for $name<$($param),*>
+        where
+            $($bounds)*
+        {
+            fn from(vector: mint::Vector3<T>) -> Self {
+                <[T; 3]>::from(vector).into()
+            }
+        }
+
+        impl<$($param),*> From<$name<$($param),*>> for mint::Vector3<T>
+        where
+            $($bounds)*
+        {
+            fn from(color: $name<$($param),*>) -> Self {
+                <[T; 3]>::from(color).into()
+            }
+        }
+
+        impl<$($param),*> mint::IntoMint for $name<$($param),*>
+        where
+            $($bounds)*
+        {
+            type MintType = mint::Vector3<T>;
+        }
+    };
+}
+
+impl_color_array!(Rgb<T> = [T; 3] {red, green, blue}, as_ref,
+    where T: PosNormalChannelScalar);
+impl_color_array!(Luma<T> = [T; 1] {luma}, as_ref,
+    where T: PosNormalChannelScalar);
+impl_color_array!(Rgi<T> = [T; 3] {red, green, intensity}, as_ref,
+    where T: PosNormalChannelScalar + Float);
+impl_color_array!(BareYCbCr<T> = [T; 3] {luma, cb, cr}, as_ref,
+    where T: NormalChannelScalar + PosNormalChannelScalar);
+impl_color_array!(YCbCr<T, M> = [T; 3] {luma, cb, cr}, as_ref,
+    where T: NormalChannelScalar + PosNormalChannelScalar, M: YCbCrModel<T> + UnitModel<T>);
+impl_color_array!(Xyz<T> = [T; 3] {x, y, z}, as_ref,
+    where T: FreeChannelScalar);
+impl_color_array!(XyY<T> = [T; 3] {x, y, Y}, as_ref,
+    where T: FreeChannelScalar + PosNormalChannelScalar + Float);
+impl_color_array!(Lms<T, M> = [T; 3] {l, m, s}, as_ref,
+    where T: FreeChannelScalar, M: LmsModel<T>);
+impl_color_array!(Lab<T, W> = [T; 3] {L, a, b}, as_ref,
+    where T: FreeChannelScalar, W: UnitWhitePoint<T>);
+impl_color_array!(Luv<T, W> = [T; 3] {L, u, v}, as_ref,
+    where T: FreeChannelScalar, W: UnitWhitePoint<T>);
+impl_color_array!(Oklab<T> = [T; 3] {L, a, b}, as_ref,
+    where T: FreeChannelScalar);
+
+impl_color_array!(Hsv<T, A> = [T; 3] {hue h, chan s, chan v},
+    where T: PosNormalChannelScalar + Float, A: AngularChannelScalar + Angle<Scalar = T>);
+impl_color_array!(Hsl<T, A> = [T; 3] {hue h, chan s, chan l},
+    where T: PosNormalChannelScalar + Float, A: AngularChannelScalar + Angle<Scalar = T>);
+impl_color_array!(Hwb<T, A> = [T; 3] {hue h, chan w, chan b},
+    where T: HwbBoundedChannelTraits, A: AngularChannelScalar + Angle<Scalar = T>);
+impl_color_array!(Hsi<T, A> = [T; 3] {hue h, chan s, chan i},
+    where T: PosNormalChannelScalar + Float, A: AngularChannelScalar + Angle<Scalar = T>);
+impl_color_array!(eHsi<T, A> = [T; 3] {hue h, chan s, chan i},
+    where T: PosNormalChannelScalar + Float, A: AngularChannelScalar + Angle<Scalar = T>);
+impl_color_array!(Lchab<T, W, A> = [T; 3] {chan L, chan c, hue h},
+    where T: FreeChannelScalar, W: UnitWhitePoint<T>,
+        A: AngularChannelScalar + Angle<Scalar = T>);
+impl_color_array!(Lchuv<T, W, A> = [T; 3] {chan L, chan c, hue h},
+    where T: FreeChannelScalar, W: UnitWhitePoint<T>,
+        A: AngularChannelScalar + Angle<Scalar = T>);
+impl_color_array!(Oklch<T, A> = [T; 3] {chan L, chan c, hue h},
+    where T: FreeChannelScalar, A: AngularChannelScalar + Angle<Scalar = T>);
+
+macro_rules! impl_alpha_array {
+    ([T; $n: tt] {$($c: ident),*} => [T; $alpha_n: tt]) => {
+        impl<T, InnerColor> From<[T; $alpha_n]> for Alpha<T, InnerColor>
+        where
+            T: PosNormalChannelScalar,
+            InnerColor: Color + From<[T; $n]>,
+        {
+            fn from(array: [T; $alpha_n]) -> Self {
+                let [$($c,)* alpha] = array;
+                Alpha::new(InnerColor::from([$($c),*]), alpha)
+            }
+        }
+
+        impl<T, InnerColor> From<Alpha<T, InnerColor>> for [T; $alpha_n]
+        where
+            T: PosNormalChannelScalar,
+            InnerColor: Color + Into<[T; $n]>,
+        {
+            fn from(color: Alpha<T, InnerColor>) -> Self {
+                let (color, alpha) = color.decompose();
+                let [$($c),*] = color.into();
+                [$($c,)* alpha]
+            }
+        }
+
+        impl<T, InnerColor> AsRef<[T; $alpha_n]> for Alpha<T, InnerColor>
+        where
+            T: PosNormalChannelScalar,
+            InnerColor: Color + AsRef<[T; $n]>,
+        {
+            fn as_ref(&self) -> &[T; $alpha_n] {
+                // The alpha channel only follows the inner color's channels directly when they
+                // are the only thing it holds
+                assert_eq!(mem::size_of::<InnerColor>(), mem::size_of::<[T; $n]>());
+                unsafe { &*(self as *const Self as *const [T; $alpha_n]) }
+            }
+        }
+    };
+}
+
+impl_alpha_array!([T; 1] {luma} => [T; 2]);
+impl_alpha_array!([T; 3] {c1, c2, c3} => [T; 4]);
+
+#[cfg(feature = "mint")]
+impl<T, InnerColor> From<mint::Vector2<T>> for Alpha<T, InnerColor>
+where
+    T: PosNormalChannelScalar,
+    InnerColor: Color + From<[T; 1]>,
+{
+    fn from(vector: mint::Vector2<T>) -> Self {
+        <[T; 2]>::from(vector).into()
+    }
+}
+
+#[cfg(feature = "mint")]
+impl<T, InnerColor> From<Alpha<T, InnerColor>> for mint::Vector2<T>
+where
+    T: PosNormalChannelScalar,
+    InnerColor: Color + Into<[T; 1]>,
+{
+    fn from(color: Alpha<T, InnerColor>) -> Self {
+        <[T; 2]>::from(color).into()
+    }
+}
+
+#[cfg(feature = "mint")]
+impl<T, InnerColor> From<mint::Vector4<T>> for Alpha<T, InnerColor>
+where
+    T: PosNormalChannelScalar,
+    InnerColor: Color + From<[T; 3]>,
+{
+    fn from(vector: mint::Vector4<T>) -> Self {
+        <[T; 4]>::from(vector).into()
+    }
+}
+
+#[cfg(feature = "mint")]
+impl<T, InnerColor> From<Alpha<T, InnerColor>> for mint::Vector4<T>
+where
+    T: PosNormalChannelScalar,
+    InnerColor: Color + Into<[T; 3]>,
+{
+    fn from(color: Alpha<T, InnerColor>) -> Self {
+        <[T; 4]>::from(color).into()
+    }
+}
+
+#[cfg(feature = "mint")]
+impl<T, InnerColor> mint::IntoMint for Alpha<T, InnerColor>
+where
+    T: PosNormalChannelScalar,
+    InnerColor: Color + Into<[T; 3]>,
+{
+    type MintType = mint::Vector4<T>;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::alpha::{Hsva, LumaA, Rgba};
+    use crate::white_point::D65;
+    use crate::ycbcr::JpegModel;
+    use angle::{Deg, Turns};
+
+    #[test]
+    fn test_cartesian_arrays() {
+        let rgb = Rgb::new(10u8, 20, 30);
+        assert_eq!(<[u8; 3]>::from(rgb), [10, 20, 30]);
+        assert_eq!(Rgb::from([10u8, 20, 30]), rgb);
+        assert_eq!(rgb.as_ref(), &[10, 20, 30]);
+
+        let luma = Luma::new(0.25f32);
+        assert_eq!(<[f32; 1]>::from(luma), [0.25]);
+        assert_eq!(luma.as_ref(), &[0.25]);
+
+        let lab: Lab<f64, D65> = [50.0, -10.0, 20.0].into();
+        assert_eq!(lab, Lab::new(50.0, -10.0, 20.0));
+        assert_eq!(lab.as_ref(), &[50.0, -10.0, 20.0]);
+
+        let ycbcr: YCbCr<f32, JpegModel> = YCbCr::from([0.5, -0.25, 0.125]);
+        assert_eq!(ycbcr.as_ref(), &[0.5, -0.25, 0.125]);
+        assert_eq!(<[f32; 3]>::from(ycbcr), [0.5, -0.25, 0.125]);
+    }
+
+    #[test]
+    fn test_polar_arrays() {
+        let hsv: Hsv<f64> = [120.0, 0.5, 0.25].into();
+        assert_eq!(hsv, Hsv::new(Deg(120.0), 0.5, 0.25));
+        assert_eq!(<[f64; 3]>::from(hsv), [120.0, 0.5, 0.25]);
+
+        let oklch: Oklch<f32, Turns<f32>> = Oklch::from([0.5, 0.1, 0.75]);
+        assert_eq!(oklch, Oklch::new(0.5, 0.1, Turns(0.75)));
+        assert_eq!(<[f32; 3]>::from(oklch), [0.5, 0.1, 0.75]);
+    }
+
+    #[test]
+    fn test_alpha_arrays() {
+        let rgba = Rgba::new(Rgb::new(1u8, 2, 3), 4);
+        assert_eq!(<[u8; 4]>::from(rgba), [1, 2, 3, 4]);
+        assert_eq!(Rgba::from([1u8, 2, 3, 4]), rgba);
+        assert_eq!(rgba.as_ref(), &[1, 2, 3, 4]);
+
+        let lumaa = LumaA::new(Luma::new(0.5f32), 0.75);
+        assert_eq!(<[f32; 2]>::from(lumaa), [0.5, 0.75]);
+        assert_eq!(LumaA::from([0.5f32, 0.75]), lumaa);
+        assert_eq!(lumaa.as_ref(), &[0.5, 0.75]);
+
+        let hsva: Hsva<f64, Deg<f64>> = [90.0, 0.5, 1.0, 0.25].into();
+        assert_eq!(hsva, Hsva::new(Hsv::new(Deg(90.0), 0.5, 1.0), 0.25));
+        assert_eq!(<[f64; 4]>::from(hsva), [90.0, 0.5, 1.0, 0.25]);
+    }
+
+    #[cfg(feature = "mint")]
+    #[test]
+    fn test_mint() {
+        use mint::IntoMint;
+
+        let xyz = Xyz::new(0.25f32, 0.5, 0.75);
+        let vector: mint::Vector3<f32> = xyz.into();
+        assert_eq!(vector, mint::Vector3 { x: 0.25, y: 0.5, z: 0.75 });
+        assert_eq!(Xyz::from(vector), xyz);
+
+        let rgba = Rgba::new(Rgb::new(0.1f64, 0.2, 0.3), 0.4);
+        let vector: <Rgba<f64> as IntoMint>::MintType = rgba.into();
+        assert_eq!(vector, mint::Vector4 { x: 0.1, y: 0.2, z: 0.3, w: 0.4 });
+        assert_eq!(Rgba::from(vector), rgba);
+
+        let lumaa = LumaA::new(Luma::new(1u8), 2);
+        let vector = mint::Vector2::from(lumaa);
+        assert_eq!(vector, mint::Vector2 { x: 1, y: 2 });
+        assert_eq!(LumaA::from(vector), lumaa);
+    }
+}
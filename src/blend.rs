@@ -0,0 +1,303 @@
+//! Separable blend modes
+//!
+//! A blend mode combines the color of a source with the color of the backdrop it is drawn on,
+//! channel by channel, before the result is composited. The modes in
+//! [`BlendMode`](enum.BlendMode.html) follow the separable blend modes of the W3C Compositing and
+//! Blending specification, which are the same as those of CSS and PDF.
+//!
+//! The formulas only describe what is done to the channel values, not what those values mean.
+//! CSS and most image editors blend the encoded, usually sRGB, channel values directly, which is
+//! what users of those tools expect to see. Blending linear values instead treats the channels as
+//! amounts of light, which gives physically plausible results for modes like
+//! [`Multiply`](enum.BlendMode.html#variant.Multiply) and
+//! [`Screen`](enum.BlendMode.html#variant.Screen), but looks noticeably different. The
+//! [`blend`](fn.blend.html) and [`blend_rgba`](fn.blend_rgba.html) functions work on whichever
+//! values they are given, while [`blend_encoded`](fn.blend_encoded.html) takes colors with a
+//! known encoding and lets the caller choose with [`BlendSpace`](enum.BlendSpace.html).
+//!
+//! ## Examples:
+//!
+//! ```rust
+//! #[macro_use] extern crate approx;
+//! # extern crate prisma;
+//!
+//! use prisma::Rgb;
+//! use prisma::blend::{blend, blend_encoded, BlendMode, BlendSpace};
+//! use prisma::encoding::EncodableColor;
+//!
+//! let backdrop = Rgb::new(1.0, 0.5, 0.0);
+//! let source = Rgb::new(0.5, 0.5, 0.5);
+//! assert_relative_eq!(blend(&backdrop, &source, BlendMode::Multiply), Rgb::new(0.5, 0.25, 0.0));
+//! assert_relative_eq!(blend(&backdrop, &source, BlendMode::Screen), Rgb::new(1.0, 0.75, 0.5));
+//!
+//! // An encoded 50% gray is much darker than half of white in linear light, so screening it
+//! // onto itself lightens less when blending linearly.
+//! let gray = Rgb::new(0.5, 0.5, 0.5).srgb_encoded();
+//! let css = blend_encoded(&gray, &gray, BlendMode::Screen, BlendSpace::Encoded);
+//! let linear = blend_encoded(&gray, &gray, BlendMode::Screen, BlendSpace::Linear);
+//! assert_relative_eq!(*css.color(), Rgb::new(0.75, 0.75, 0.75));
+//! assert!(linear.color().red() < 0.7);
+//! ```
+
+use crate::alpha::{Alpha, Rgba};
+use crate::channel::{ChannelFormatCast, PosNormalChannelScalar};
+use crate::color::HomogeneousColor;
+use crate::composite;
+use crate::encoding::{ColorEncoding, EncodedColor, LinearEncoding};
+use crate::rgb::Rgb;
+use num_traits::Float;
+
+/// A separable blend mode
+///
+/// Each mode is described by its function $`B(C_b, C_s)`$ of the backdrop channel $`C_b`$ and the
+/// source channel $`C_s`$.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum BlendMode {
+    /// The source replaces the backdrop: $`C_s`$
+    Normal,
+    /// The channels are multiplied, always darkening: $`C_b C_s`$
+    Multiply,
+    /// The inverted channels are multiplied, always lightening: $`C_b + C_s - C_b C_s`$
+    Screen,
+    /// Multiplies or screens depending on the backdrop; the reverse of `HardLight`
+    Overlay,
+    /// The smaller of the two channels is kept
+    Darken,
+    /// The larger of the two channels is kept
+    Lighten,
+    /// The backdrop is brightened to reflect the source
+    ColorDodge,
+    /// The backdrop is darkened to reflect the source
+    ColorBurn,
+    /// Multiplies or screens depending on the source
+    HardLight,
+    /// Darkens or lightens depending on the source, like a diffuse spotlight
+    SoftLight,
+    /// The absolute difference of the channels: $`|C_b - C_s|`$
+    Difference,
+    /// Like `Difference`, but with lower contrast: $`C_b + C_s - 2 C_b C_s`$
+    Exclusion,
+}
+
+impl BlendMode {
+    /// Blend a single `source` channel onto a `backdrop` channel
+    ///
+    /// Both channels are expected to be in the range $`[0, 1]`$.
+    pub fn blend_channel<T>(self, backdrop: T, source: T) -> T
+    where
+        T: Float,
+    {
+        let one = T::one();
+        let two = one + one;
+        let half = one / two;
+        match self {
+            BlendMode::Normal => source,
+            BlendMode::Multiply => backdrop * source,
+            BlendMode::Screen => backdrop + source - backdrop * source,
+            BlendMode::Overlay => BlendMode::HardLight.blend_channel(source, backdrop),
+            BlendMode::Darken => backdrop.min(source),
+            BlendMode::Lighten => backdrop.max(source),
+            BlendMode::ColorDodge => {
+                if backdrop == T::zero() {
+                    T::zero()
+                } else if source >= one {
+                    one
+                } else {
+                    (backdrop / (one - source)).min(one)
+                }
+            }
+            BlendMode::ColorBurn => {
+                if backdrop >= one {
+                    one
+                } else if source == T::zero() {
+                    T::zero()
+                } else {
+                    one - ((one - backdrop) / source).min(one)
+                }
+            }
+            BlendMode::HardLight => {
+                if source <= half {
+                    BlendMode::Multiply.blend_channel(backdrop, two * source)
+                } else {
+                    BlendMode::Screen.blend_channel(backdrop, two * source - one)
+                }
+            }
+            BlendMode::SoftLight => {
+                if source <= half {
+                    backdrop - (one - two * source) * backdrop * (one - backdrop)
+                } else {
+                    let quarter = half * half;
+                    let d = if backdrop <= quarter {
+                        let sixteen = two * two * two * two;
+                        let twelve = two * two * (two + one);
+                        ((sixteen * backdrop - twelve) * backdrop + two * two) * backdrop
+                    } else {
+                        backdrop.sqrt()
+                    };
+                    backdrop + (two * source - one) * (d - backdrop)
+                }
+            }
+            BlendMode::Difference => (backdrop - source).abs(),
+            BlendMode::Exclusion => backdrop + source - two * backdrop * source,
+        }
+    }
+}
+
+/// The values a blend mode is applied to
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum BlendSpace {
+    /// Blend the encoded channel values, as CSS and most image editors do
+    Encoded,
+    /// Decode the colors and blend the linear channel values
+    Linear,
+}
+
+/// Blend `source` onto `backdrop` with `mode`
+///
+/// The channels are blended as they are, so the result depends on whether the colors are linear
+/// or encoded. See the [module documentation](index.html) for the difference.
+pub fn blend<T>(backdrop: &Rgb<T>, source: &Rgb<T>, mode: BlendMode) -> Rgb<T>
+where
+    T: PosNormalChannelScalar + Float,
+{
+    backdrop
+        .clone()
+        .zip_channels(source, |b, s| mode.blend_channel(b, s))
+}
+
+/// Blend the encoded `source` onto the encoded `backdrop` with `mode` in `space`
+///
+/// The result has the encoding of `backdrop`. `source` is expected to share that encoding when
+/// blending in [`BlendSpace::Encoded`](enum.BlendSpace.html#variant.Encoded).
+pub fn blend_encoded<T, E>(
+    backdrop: &EncodedColor<Rgb<T>, E>,
+    source: &EncodedColor<Rgb<T>, E>,
+    mode: BlendMode,
+    space: BlendSpace,
+) -> EncodedColor<Rgb<T>, E>
+where
+    T: PosNormalChannelScalar + Float + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+    E: ColorEncoding,
+{
+    let encoding = backdrop.encoding().clone();
+    match space {
+        BlendSpace::Encoded => {
+            EncodedColor::new(blend(backdrop.color(), source.color(), mode), encoding)
+        }
+        BlendSpace::Linear => {
+            let linear_backdrop = backdrop.clone().decode();
+            let linear_source = source.clone().decode();
+            let result = blend(linear_backdrop.color(), linear_source.color(), mode);
+            EncodedColor::new(result, LinearEncoding::new()).encode(encoding)
+        }
+    }
+}
+
+/// Blend the transparent `source` onto the transparent `backdrop` with `mode`
+///
+/// Where the backdrop is transparent, the source color shows through unchanged, so the blended
+/// color is $`(1 - \alpha_b) C_s + \alpha_b B(C_b, C_s)`$. It is then placed over the backdrop
+/// with [`composite::over`](../composite/fn.over.html). As with [`blend`](fn.blend.html), the
+/// channels are used as they are given.
+pub fn blend_rgba<T>(backdrop: &Rgba<T>, source: &Rgba<T>, mode: BlendMode) -> Rgba<T>
+where
+    T: PosNormalChannelScalar + Float,
+{
+    let backdrop_alpha = backdrop.alpha();
+    let blended = backdrop
+        .color()
+        .clone()
+        .zip_channels(source.color(), |b, s| {
+            (T::one() - backdrop_alpha) * s + backdrop_alpha * mode.blend_channel(b, s)
+        });
+    composite::over(&Alpha::new(blended, source.alpha()), backdrop)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::encoding::{EncodableColor, SrgbEncoding};
+    use approx::*;
+
+    #[test]
+    fn test_blend_channel() {
+        let expect = |mode: BlendMode, backdrop: f64, source: f64, result: f64| {
+            assert_relative_eq!(
+                mode.blend_channel(backdrop, source),
+                result,
+                epsilon = 1e-12
+            );
+        };
+
+        expect(BlendMode::Normal, 0.25, 0.75, 0.75);
+        expect(BlendMode::Multiply, 0.5, 0.5, 0.25);
+        expect(BlendMode::Screen, 0.5, 0.5, 0.75);
+        expect(BlendMode::Overlay, 0.25, 0.5, 0.25);
+        expect(BlendMode::Overlay, 0.75, 0.5, 0.75);
+        expect(BlendMode::Overlay, 0.75, 0.25, 0.625);
+        expect(BlendMode::Darken, 0.25, 0.75, 0.25);
+        expect(BlendMode::Lighten, 0.25, 0.75, 0.75);
+        expect(BlendMode::ColorDodge, 0.0, 1.0, 0.0);
+        expect(BlendMode::ColorDodge, 0.25, 1.0, 1.0);
+        expect(BlendMode::ColorDodge, 0.25, 0.5, 0.5);
+        expect(BlendMode::ColorDodge, 0.75, 0.5, 1.0);
+        expect(BlendMode::ColorBurn, 1.0, 0.0, 1.0);
+        expect(BlendMode::ColorBurn, 0.75, 0.0, 0.0);
+        expect(BlendMode::ColorBurn, 0.75, 0.5, 0.5);
+        expect(BlendMode::ColorBurn, 0.25, 0.5, 0.0);
+        expect(BlendMode::HardLight, 0.5, 0.25, 0.25);
+        expect(BlendMode::HardLight, 0.5, 0.75, 0.75);
+        expect(BlendMode::SoftLight, 0.5, 0.5, 0.5);
+        expect(BlendMode::SoftLight, 0.5, 0.0, 0.25);
+        expect(BlendMode::SoftLight, 0.25, 1.0, 0.5);
+        expect(BlendMode::SoftLight, 0.125, 1.0, 0.34375);
+        expect(BlendMode::Difference, 0.25, 0.75, 0.5);
+        expect(BlendMode::Exclusion, 0.5, 0.5, 0.5);
+        expect(BlendMode::Exclusion, 1.0, 0.25, 0.75);
+    }
+
+    #[test]
+    fn test_blend() {
+        let backdrop = Rgb::new(0.2f32, 0.5, 0.8);
+        let white = Rgb::new(1.0, 1.0, 1.0);
+        let black = Rgb::new(0.0, 0.0, 0.0);
+        assert_relative_eq!(blend(&backdrop, &white, BlendMode::Multiply), backdrop);
+        assert_relative_eq!(blend(&backdrop, &black, BlendMode::Screen), backdrop);
+        assert_relative_eq!(blend(&backdrop, &black, BlendMode::Difference), backdrop);
+        assert_relative_eq!(
+            blend(&backdrop, &white, BlendMode::Difference),
+            Rgb::new(0.8, 0.5, 0.2)
+        );
+
+        let srgb = backdrop.srgb_encoded();
+        let encoded = blend_encoded(
+            &srgb,
+            &white.srgb_encoded(),
+            BlendMode::Normal,
+            BlendSpace::Linear,
+        );
+        assert_eq!(*encoded.encoding(), SrgbEncoding::new());
+        assert_relative_eq!(*encoded.color(), white, epsilon = 1e-6);
+
+        let gray = Rgb::new(0.5f32, 0.5, 0.5).srgb_encoded();
+        let css = blend_encoded(&srgb, &gray, BlendMode::Screen, BlendSpace::Encoded);
+        let linear = blend_encoded(&srgb, &gray, BlendMode::Screen, BlendSpace::Linear);
+        assert_relative_eq!(*css.color(), Rgb::new(0.6, 0.75, 0.9), epsilon = 1e-6);
+        assert!(linear.color().red() < css.color().red());
+    }
+
+    #[test]
+    fn test_blend_rgba() {
+        let backdrop = Rgba::new(Rgb::new(0.5, 0.5, 0.5), 1.0);
+        let source = Rgba::new(Rgb::new(0.5, 1.0, 0.0), 0.5);
+        let result = blend_rgba(&backdrop, &source, BlendMode::Multiply);
+        assert_relative_eq!(result, Rgba::new(Rgb::new(0.375, 0.5, 0.25), 1.0));
+
+        let clear = Rgba::new(Rgb::new(0.0, 0.0, 0.0), 0.0);
+        let result = blend_rgba(&clear, &source, BlendMode::Multiply);
+        assert_relative_eq!(result, source);
+        let result = blend_rgba(&backdrop, &clear, BlendMode::Screen);
+        assert_relative_eq!(result, backdrop);
+    }
+}
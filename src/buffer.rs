@@ -0,0 +1,350 @@
+//! Conversion of whole buffers of colors
+//!
+//! Converting an image means converting every pixel in it. The functions here take a slice of
+//! colors and convert each one, either into a newly allocated `Vec` or into a slice provided by the
+//! caller, so that a buffer can be reused between frames without allocating.
+//!
+//! [`convert`](fn.convert.html) and [`convert_into`](fn.convert_into.html) convert between color
+//! models using [`FromColor`](../trait.FromColor.html). [`map`](fn.map.html) and
+//! [`map_into`](fn.map_into.html) accept any per-pixel function, for operations like `color_cast`
+//! or conversions that need a color space or other parameters. [`encode`](fn.encode.html) and
+//! [`decode`](fn.decode.html) change the encoding of each color.
+//!
+//! When the result has the same type as the source, such as when changing the encoding of an
+//! `Rgb<f32>` buffer, [`map_in_place`](fn.map_in_place.html),
+//! [`encode_in_place`](fn.encode_in_place.html) and [`decode_in_place`](fn.decode_in_place.html)
+//! overwrite the source instead of allocating a second buffer. Reordering channels in place is done
+//! by the functions in the [`order`](../order/index.html) module.
+//!
+//! With the `rayon` feature enabled, each function has a `par_` counterpart that splits the
+//! buffer into chunks of at least [`PAR_CHUNK_SIZE`](constant.PAR_CHUNK_SIZE.html) colors and
+//! converts them on the rayon thread pool. The results are identical to the sequential versions.
+//!
+//! ## Examples:
+//!
+//! ```rust
+//! # extern crate prisma;
+//! extern crate angular_units as angle;
+//!
+//! use angle::Deg;
+//! use prisma::{Hsv, Rgb};
+//! use prisma::buffer;
+//!
+//! let pixels = vec![Rgb::new(1.0f32, 0.0, 0.0), Rgb::new(0.0, 0.0, 0.5)];
+//! let hsv: Vec<Hsv<f32, Deg<f32>>> = buffer::convert(&pixels);
+//! assert_eq!(hsv[0], Hsv::new(Deg(0.0), 1.0, 1.0));
+//!
+//! let mut bytes = vec![Rgb::new(0u8, 0, 0); pixels.len()];
+//! buffer::map_into(&pixels, &mut bytes, |rgb| rgb.color_cast());
+//! assert_eq!(bytes[0], Rgb::new(255, 0, 0));
+//! ```
+
+use crate::convert::FromColor;
+use crate::encoding::{ChannelDecoder, ChannelEncoder, TranscodableColor};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// The minimum number of colors converted by one task in the `par_` functions
+#[cfg(feature = "rayon")]
+pub const PAR_CHUNK_SIZE: usize = 4096;
+
+/// Convert each color in `src` to the color model `D`
+pub fn convert<S, D>(src: &[S]) -> Vec<D>
+where
+    D: FromColor<S>,
+{
+    map(src, D::from_color)
+}
+
+/// Convert each color in `src` to the color model `D`, writing the results to `dst`
+///
+/// # Panics
+/// If `src` and `dst` have different lengths.
+pub fn convert_into<S, D>(src: &[S], dst: &mut [D])
+where
+    D: FromColor<S>,
+{
+    map_into(src, dst, D::from_color)
+}
+
+/// Apply `f` to each color in `src`, collecting the results
+pub fn map<S, D, F>(src: &[S], f: F) -> Vec<D>
+where
+    F: FnMut(&S) -> D,
+{
+    src.iter().map(f).collect()
+}
+
+/// Apply `f` to each color in `src`, writing the results to `dst`
+///
+/// # Panics
+/// If `src` and `dst` have different lengths.
+pub fn map_into<S, D, F>(src: &[S], dst: &mut [D], mut f: F)
+where
+    F: FnMut(&S) -> D,
+{
+    assert_eq!(
+        src.len(),
+        dst.len(),
+        "source and destination buffers must have the same length"
+    );
+    for (out, color) in dst.iter_mut().zip(src) {
+        *out = f(color);
+    }
+}
+
+/// Encode each linear color in `src` with `encoder`
+pub fn encode<C, E>(src: &[C], encoder: &E) -> Vec<C>
+where
+    C: TranscodableColor + Clone,
+    E: ChannelEncoder,
+{
+    map(src, |color| color.clone().encode_color(encoder))
+}
+
+/// Decode each color in `src` to linear with `decoder`
+pub fn decode<C, D>(src: &[C], decoder: &D) -> Vec<C>
+where
+    C: TranscodableColor + Clone,
+    D: ChannelDecoder,
+{
+    map(src, |color| color.clone().decode_color(decoder))
+}
+
+/// Apply `f` to each color in `colors`, replacing it with the result
+pub fn map_in_place<C, F>(colors: &mut [C], mut f: F)
+where
+    F: FnMut(&C) -> C,
+{
+    for color in colors.iter_mut() {
+        *color = f(color);
+    }
+}
+
+/// Encode each linear color in `colors` with `encoder` in place
+pub fn encode_in_place<C, E>(colors: &mut [C], encoder: &E)
+where
+    C: TranscodableColor + Clone,
+    E: ChannelEncoder,
+{
+    map_in_place(colors, |color| color.clone().encode_color(encoder))
+}
+
+/// Decode each color in `colors` to linear with `decoder` in place
+pub fn decode_in_place<C, D>(colors: &mut [C], decoder: &D)
+where
+    C: TranscodableColor + Clone,
+    D: ChannelDecoder,
+{
+    map_in_place(colors, |color| color.clone().decode_color(decoder))
+}
+
+/// Convert each color in `src` to the color model `D` in parallel
+#[cfg(feature = "rayon")]
+pub fn par_convert<S, D>(src: &[S]) -> Vec<D>
+where
+    S: Sync,
+    D: FromColor<S> + Send,
+{
+    par_map(src, D::from_color)
+}
+
+/// Convert each color in `src` to the color model `D` in parallel, writing the results to `dst`
+///
+/// # Panics
+/// If `src` and `dst` have different lengths.
+#[cfg(feature = "rayon")]
+pub fn par_convert_into<S, D>(src: &[S], dst: &mut [D])
+where
+    S: Sync,
+    D: FromColor<S> + Send,
+{
+    par_map_into(src, dst, D::from_color)
+}
+
+/// Apply `f` to each color in `src` in parallel, collecting the results
+#[cfg(feature = "rayon")]
+pub fn par_map<S, D, F>(src: &[S], f: F) -> Vec<D>
+where
+    S: Sync,
+    D: Send,
+    F: Fn(&S) -> D + Sync,
+{
+    src.par_iter()
+        .with_min_len(PAR_CHUNK_SIZE)
+        .map(&f)
+        .collect()
+}
+
+/// Apply `f` to each color in `src` in parallel, writing the results to `dst`
+///
+/// # Panics
+/// If `src` and `dst` have different lengths.
+#[cfg(feature = "rayon")]
+pub fn par_map_into<S, D, F>(src: &[S], dst: &mut [D], f: F)
+where
+    S: Sync,
+    D: Send,
+    F: Fn(&S) -> D + Sync,
+{
+    assert_eq!(
+        src.len(),
+        dst.len(),
+        "source and destination buffers must have the same length"
+    );
+    dst.par_chunks_mut(PAR_CHUNK_SIZE)
+        .zip(src.par_chunks(PAR_CHUNK_SIZE))
+        .for_each(|(dst, src)| map_into(src, dst, &f));
+}
+
+/// Encode each linear color in `src` with `encoder` in parallel
+#[cfg(feature = "rayon")]
+pub fn par_encode<C, E>(src: &[C], encoder: &E) -> Vec<C>
+where
+    C: TranscodableColor + Clone + Send + Sync,
+    E: ChannelEncoder + Sync,
+{
+    par_map(src, |color| color.clone().encode_color(encoder))
+}
+
+/// Decode each color in `src` to linear with `decoder` in parallel
+#[cfg(feature = "rayon")]
+pub fn par_decode<C, D>(src: &[C], decoder: &D) -> Vec<C>
+where
+    C: TranscodableColor + Clone + Send + Sync,
+    D: ChannelDecoder + Sync,
+{
+    par_map(src, |color| color.clone().decode_color(decoder))
+}
+
+/// Apply `f` to each color in `colors` in parallel, replacing it with the result
+#[cfg(feature = "rayon")]
+pub fn par_map_in_place<C, F>(colors: &mut [C], f: F)
+where
+    C: Send,
+    F: Fn(&C) -> C + Sync,
+{
+    colors
+        .par_chunks_mut(PAR_CHUNK_SIZE)
+        .for_each(|chunk| map_in_place(chunk, &f));
+}
+
+/// Encode each linear color in `colors` with `encoder` in place, in parallel
+#[cfg(feature = "rayon")]
+pub fn par_encode_in_place<C, E>(colors: &mut [C], encoder: &E)
+where
+    C: TranscodableColor + Clone + Send,
+    E: ChannelEncoder + Sync,
+{
+    par_map_in_place(colors, |color| color.clone().encode_color(encoder))
+}
+
+/// Decode each color in `colors` to linear with `decoder` in place, in parallel
+#[cfg(feature = "rayon")]
+pub fn par_decode_in_place<C, D>(colors: &mut [C], decoder: &D)
+where
+    C: TranscodableColor + Clone + Send,
+    D: ChannelDecoder + Sync,
+{
+    par_map_in_place(colors, |color| color.clone().decode_color(decoder))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::encoding::SrgbEncoding;
+    use crate::hsl::Hsl;
+    use crate::rgb::Rgb;
+    use angle::Deg;
+    use approx::*;
+
+    #[test]
+    fn test_convert() {
+        let pixels = vec![
+            Rgb::new(1.0f64, 0.0, 0.0),
+            Rgb::new(0.5, 0.5, 0.5),
+            Rgb::new(0.0, 1.0, 1.0),
+        ];
+        let hsl: Vec<Hsl<f64, Deg<f64>>> = convert(&pixels);
+        assert_eq!(hsl.len(), 3);
+        for (h, p) in hsl.iter().zip(&pixels) {
+            assert_eq!(*h, Hsl::from_color(p));
+        }
+
+        let mut out = vec![Rgb::new(0.0, 0.0, 0.0); 3];
+        convert_into(&hsl, &mut out);
+        for (o, p) in out.iter().zip(&pixels) {
+            assert_relative_eq!(*o, *p, epsilon = 1e-6);
+        }
+
+        let bytes = map(&pixels, |rgb| rgb.color_cast::<u8>());
+        assert_eq!(bytes[2], Rgb::new(0, 255, 255));
+    }
+
+    #[test]
+    fn test_encoding() {
+        let pixels = vec![Rgb::new(1.0f32, 0.5, 0.0), Rgb::new(0.2, 0.4, 0.6)];
+        let encoded = encode(&pixels, &SrgbEncoding::new());
+        assert_eq!(
+            encoded[1],
+            pixels[1].clone().encode_color(&SrgbEncoding::new())
+        );
+        let decoded = decode(&encoded, &SrgbEncoding::new());
+        for (d, p) in decoded.iter().zip(&pixels) {
+            assert_relative_eq!(*d, *p, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_in_place() {
+        let pixels = vec![Rgb::new(1.0f32, 0.5, 0.0), Rgb::new(0.2, 0.4, 0.6)];
+        let mut buffer = pixels.clone();
+        decode_in_place(&mut buffer, &SrgbEncoding::new());
+        assert_eq!(buffer, decode(&pixels, &SrgbEncoding::new()));
+        encode_in_place(&mut buffer, &SrgbEncoding::new());
+        for (b, p) in buffer.iter().zip(&pixels) {
+            assert_relative_eq!(*b, *p, epsilon = 1e-6);
+        }
+        map_in_place(&mut buffer, |rgb| {
+            Rgb::new(rgb.blue(), rgb.green(), rgb.red())
+        });
+        assert_relative_eq!(buffer[1], Rgb::new(0.6, 0.4, 0.2), epsilon = 1e-6);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parallel() {
+        let pixels: Vec<_> = (0..3 * PAR_CHUNK_SIZE + 17)
+            .map(|i| Rgb::new((i % 256) as u8, (i / 256 % 256) as u8, (i % 7) as u8))
+            .collect();
+        let float: Vec<Rgb<f32>> = par_map(&pixels, |rgb| rgb.color_cast());
+        assert_eq!(float, map(&pixels, |rgb| rgb.color_cast()));
+
+        let hsl: Vec<Hsl<f32, Deg<f32>>> = par_convert(&float);
+        assert_eq!(hsl, convert::<_, Hsl<f32, Deg<f32>>>(&float));
+        let mut back = vec![Rgb::new(0.0, 0.0, 0.0); float.len()];
+        par_convert_into(&hsl, &mut back);
+        let mut expected = back.clone();
+        convert_into(&hsl, &mut expected);
+        assert_eq!(back, expected);
+
+        let decoded = par_decode(&float, &SrgbEncoding::new());
+        assert_eq!(decoded, decode(&float, &SrgbEncoding::new()));
+        let encoded = par_encode(&decoded, &SrgbEncoding::new());
+        assert_eq!(encoded, encode(&decoded, &SrgbEncoding::new()));
+
+        let mut buffer = float.clone();
+        par_decode_in_place(&mut buffer, &SrgbEncoding::new());
+        assert_eq!(buffer, decoded);
+        par_encode_in_place(&mut buffer, &SrgbEncoding::new());
+        assert_eq!(buffer, encoded);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_length_mismatch() {
+        let pixels = vec![Rgb::new(1.0f64, 0.0, 0.0); 2];
+        let mut out = vec![Hsl::<f64, Deg<f64>>::new(Deg(0.0), 0.0, 0.0); 3];
+        convert_into(&pixels, &mut out);
+    }
+}
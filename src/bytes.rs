@@ -0,0 +1,180 @@
+//! Zero-copy views of byte buffers as colors
+//!
+//! Image decoders and graphics APIs hand out pixel data as raw bytes. The functions here view a
+//! `&[u8]` as a slice of colors and back without copying, checking that the length is a whole
+//! number of colors and that the bytes are suitably aligned for the color's channel type.
+//!
+//! Only types implementing [`Pod`](trait.Pod.html) can be viewed this way. It is implemented for
+//! `Rgb`, `Luma` and their `Alpha` forms, along with the types of the [`order`](../order/index.html)
+//! module, whenever the channel is a primitive integer or float.
+//!
+//! ## Examples:
+//!
+//! ```rust
+//! # extern crate prisma;
+//!
+//! use prisma::{Rgb, Rgba};
+//! use prisma::bytes::{self, ByteCastError};
+//!
+//! let row = [255u8, 0, 0, 0, 255, 0, 0, 0, 255];
+//! let pixels: &[Rgb<u8>] = bytes::cast_slice(&row).unwrap();
+//! assert_eq!(pixels[1], Rgb::new(0, 255, 0));
+//! assert_eq!(bytes::as_bytes(pixels), &row[..]);
+//!
+//! let result: Result<&[Rgba<u8>], _> = bytes::cast_slice(&row);
+//! assert_eq!(result, Err(ByteCastError::InvalidLength(9)));
+//! ```
+
+use crate::alpha::Alpha;
+use crate::channel::PosNormalChannelScalar;
+use crate::luma::Luma;
+use crate::order::{Abgr, Argb, Bgr, Bgra};
+use crate::rgb::Rgb;
+use std::error::Error;
+use std::fmt;
+use std::mem;
+use std::slice;
+
+/// A type that can be safely viewed as bytes and constructed from any bytes
+///
+/// # Safety
+/// Implementors must have no padding bytes and must be valid for every bit pattern.
+pub unsafe trait Pod: Copy + 'static {}
+
+unsafe impl Pod for u8 {}
+unsafe impl Pod for u16 {}
+unsafe impl Pod for u32 {}
+unsafe impl Pod for f32 {}
+unsafe impl Pod for f64 {}
+
+// Each of these is `repr(C)` with every field holding a `T` directly or through a
+// `repr(transparent)` channel, so there is never any padding.
+unsafe impl<T> Pod for Rgb<T> where T: Pod + PosNormalChannelScalar {}
+unsafe impl<T> Pod for Luma<T> where T: Pod + PosNormalChannelScalar {}
+unsafe impl<T> Pod for Alpha<T, Rgb<T>> where T: Pod + PosNormalChannelScalar {}
+unsafe impl<T> Pod for Alpha<T, Luma<T>> where T: Pod + PosNormalChannelScalar {}
+unsafe impl<T> Pod for Bgr<T> where T: Pod {}
+unsafe impl<T> Pod for Bgra<T> where T: Pod {}
+unsafe impl<T> Pod for Abgr<T> where T: Pod {}
+unsafe impl<T> Pod for Argb<T> where T: Pod {}
+
+/// An error returned when a byte slice could not be viewed as a slice of colors
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ByteCastError {
+    /// The length of the slice is not a multiple of the size of the color. Holds the length.
+    InvalidLength(usize),
+    /// The slice does not start at an address aligned for the color
+    Misaligned,
+}
+
+impl fmt::Display for ByteCastError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ByteCastError::InvalidLength(len) => {
+                write!(
+                    f,
+                    "a slice of {} bytes is not a whole number of colors",
+                    len
+                )
+            }
+            ByteCastError::Misaligned => write!(f, "the slice is not aligned for the color type"),
+        }
+    }
+}
+
+impl Error for ByteCastError {}
+
+fn check_cast<C>(bytes: &[u8]) -> Result<usize, ByteCastError>
+where
+    C: Pod,
+{
+    let size = mem::size_of::<C>();
+    if size == 0 || !bytes.len().is_multiple_of(size) {
+        return Err(ByteCastError::InvalidLength(bytes.len()));
+    }
+    if !(bytes.as_ptr() as usize).is_multiple_of(mem::align_of::<C>()) {
+        return Err(ByteCastError::Misaligned);
+    }
+    Ok(bytes.len() / size)
+}
+
+/// View a byte slice as a slice of colors
+pub fn cast_slice<C>(bytes: &[u8]) -> Result<&[C], ByteCastError>
+where
+    C: Pod,
+{
+    let len = check_cast::<C>(bytes)?;
+    Ok(unsafe { slice::from_raw_parts(bytes.as_ptr() as *const C, len) })
+}
+
+/// View a mutable byte slice as a mutable slice of colors
+pub fn cast_slice_mut<C>(bytes: &mut [u8]) -> Result<&mut [C], ByteCastError>
+where
+    C: Pod,
+{
+    let len = check_cast::<C>(bytes)?;
+    Ok(unsafe { slice::from_raw_parts_mut(bytes.as_mut_ptr() as *mut C, len) })
+}
+
+/// View a slice of colors as bytes
+pub fn as_bytes<C>(colors: &[C]) -> &[u8]
+where
+    C: Pod,
+{
+    let len = mem::size_of_val(colors);
+    unsafe { slice::from_raw_parts(colors.as_ptr() as *const u8, len) }
+}
+
+/// View a mutable slice of colors as mutable bytes
+pub fn as_bytes_mut<C>(colors: &mut [C]) -> &mut [u8]
+where
+    C: Pod,
+{
+    let len = mem::size_of_val(colors);
+    unsafe { slice::from_raw_parts_mut(colors.as_mut_ptr() as *mut u8, len) }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::alpha::Rgba;
+
+    #[test]
+    fn test_cast_u8() {
+        let mut data = [10u8, 20, 30, 40, 50, 60, 70, 80];
+        let pixels: &[Rgba<u8>] = cast_slice(&data).unwrap();
+        assert_eq!(pixels.len(), 2);
+        assert_eq!(pixels[1], Rgba::new(Rgb::new(50, 60, 70), 80));
+        let pixels: &[Bgra<u8>] = cast_slice(&data).unwrap();
+        assert_eq!(pixels[0].into_array(), [10, 20, 30, 40]);
+        let gray: &[Luma<u8>] = cast_slice(&data).unwrap();
+        assert_eq!(gray.len(), 8);
+
+        let pixels: &mut [Rgba<u8>] = cast_slice_mut(&mut data).unwrap();
+        pixels[0].set_alpha(0);
+        as_bytes_mut(pixels)[1] = 0;
+        assert_eq!(data, [10, 0, 30, 0, 50, 60, 70, 80]);
+
+        assert_eq!(
+            cast_slice::<Rgb<u8>>(&data),
+            Err(ByteCastError::InvalidLength(8))
+        );
+    }
+
+    #[test]
+    fn test_cast_alignment() {
+        let colors = [Rgb::new(1.0f32, 0.5, 0.25), Rgb::new(0.0, 0.0, 1.0)];
+        let bytes = as_bytes(&colors);
+        assert_eq!(bytes.len(), 24);
+        assert_eq!(cast_slice::<Rgb<f32>>(bytes).unwrap(), &colors[..]);
+        assert_eq!(cast_slice::<Rgb<f32>>(&bytes[12..]).unwrap(), &colors[1..]);
+        assert_eq!(
+            cast_slice::<Luma<f32>>(&bytes[1..5]),
+            Err(ByteCastError::Misaligned)
+        );
+        assert_eq!(
+            cast_slice::<Luma<f32>>(&bytes[..6]),
+            Err(ByteCastError::InvalidLength(6))
+        );
+    }
+}
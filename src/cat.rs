@@ -0,0 +1,152 @@
+//! Chromatic adaptation transforms between white points
+//!
+//! A color seen under one light source appears the same as a different color seen under another
+//! light source, because the eye adapts to the color of the light. A chromatic adaptation
+//! transform (CAT) predicts this, mapping an `Xyz` color relative to one white point to the `Xyz`
+//! color that appears the same relative to another white point. This is needed whenever colors
+//! move between color spaces or profiles with different white points, such as D50 ProPhoto RGB
+//! and D65 sRGB.
+//!
+//! All of the transforms here are von Kries transforms: the white points are converted into a
+//! cone response space and each cone response is scaled independently. They differ in the
+//! [`LmsModel`](../lms/trait.LmsModel.html) used as the cone response space:
+//!
+//! * [`Bradford`](struct.Bradford.html): The most widely used transform, and the one used by ICC
+//!   profiles and CSS.
+//! * [`VonKries`](struct.VonKries.html): The classic transform using the Hunt-Pointer-Estevez
+//!   cone responses.
+//! * [`Cat02`](type.Cat02.html): The transform of the CIECAM02 color appearance model.
+//! * [`Cat16`](struct.Cat16.html): The transform of the CAM16 color appearance model, fixing
+//!   some problems of CAT02 with highly saturated colors.
+//!
+//! A model can be picked for conversions between RGB color spaces with
+//! [`SpaceConverter::with_adaptation`](../color_space/struct.SpaceConverter.html#method.with_adaptation).
+
+use crate::channel::FreeChannelScalar;
+use crate::color::Color;
+use crate::linalg::Matrix3;
+use crate::lms::{chromatic_adaptation_transform, LmsModel};
+use crate::xyz::Xyz;
+use std::marker::PhantomData;
+
+pub use crate::lms::{Bradford, Cat16, VonKries};
+
+/// The CAT02 transform, which uses the cone responses of [`CieCam2002`](../lms/struct.CieCam2002.html)
+pub type Cat02 = crate::lms::CieCam2002;
+
+/// A chromatic adaptation from one white point to another using the cone space of `Model`
+///
+/// The adaptation matrix is computed once on construction.
+///
+/// ## Examples:
+///
+/// ```rust
+/// #[macro_use] extern crate approx;
+/// # extern crate prisma;
+///
+/// use prisma::Xyz;
+/// use prisma::cat::{Bradford, ChromaticAdaptation};
+/// use prisma::white_point::{WhitePoint, D50, D65};
+///
+/// let adaptation = ChromaticAdaptation::<f64, Bradford>::new(&D50.get_xyz(), &D65.get_xyz());
+/// let white = adaptation.adapt(&D50.get_xyz());
+/// assert_relative_eq!(white, D65.get_xyz(), epsilon=1e-4);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChromaticAdaptation<T, Model> {
+    src_white: Xyz<T>,
+    dst_white: Xyz<T>,
+    transform: Matrix3<T>,
+    model: PhantomData<Model>,
+}
+
+impl<T, Model> ChromaticAdaptation<T, Model>
+where
+    T: FreeChannelScalar,
+    Model: LmsModel<T>,
+{
+    /// Construct a new `ChromaticAdaptation` from `src_white` to `dst_white`
+    pub fn new(src_white: &Xyz<T>, dst_white: &Xyz<T>) -> Self {
+        ChromaticAdaptation {
+            src_white: src_white.clone(),
+            dst_white: dst_white.clone(),
+            transform: chromatic_adaptation_transform::<T, Model>(src_white, dst_white),
+            model: PhantomData,
+        }
+    }
+
+    /// Returns the white point colors are adapted from
+    pub fn src_white(&self) -> &Xyz<T> {
+        &self.src_white
+    }
+    /// Returns the white point colors are adapted to
+    pub fn dst_white(&self) -> &Xyz<T> {
+        &self.dst_white
+    }
+    /// Returns the adaptation matrix, which operates on `Xyz` values
+    pub fn transform(&self) -> &Matrix3<T> {
+        &self.transform
+    }
+    /// Returns the adaptation in the opposite direction
+    pub fn inverse(&self) -> Self {
+        Self::new(&self.dst_white, &self.src_white)
+    }
+
+    /// Adapt `color` from the source white point to the destination white point
+    pub fn adapt(&self, color: &Xyz<T>) -> Xyz<T> {
+        let (x, y, z) = self.transform.transform_vector(color.clone().to_tuple());
+        Xyz::new(x, y, z)
+    }
+}
+
+/// Adapt `color` from `src_white` to `dst_white` using the cone space of `Model`
+///
+/// This is a shorthand for building a [`ChromaticAdaptation`](struct.ChromaticAdaptation.html)
+/// and using it once.
+pub fn adapt<T, Model>(color: &Xyz<T>, src_white: &Xyz<T>, dst_white: &Xyz<T>) -> Xyz<T>
+where
+    T: FreeChannelScalar,
+    Model: LmsModel<T>,
+{
+    ChromaticAdaptation::<T, Model>::new(src_white, dst_white).adapt(color)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::white_point::{WhitePoint, A, D50, D65};
+    use approx::*;
+
+    fn check_model<Model: LmsModel<f64>>(expected: Xyz<f64>) {
+        let adaptation = ChromaticAdaptation::<f64, Model>::new(&D50.get_xyz(), &D65.get_xyz());
+        assert_relative_eq!(
+            adaptation.adapt(&D50.get_xyz()),
+            D65.get_xyz(),
+            epsilon = 1e-4
+        );
+        let color = Xyz::new(0.5, 0.4, 0.3);
+        let adapted = adaptation.adapt(&color);
+        assert_relative_eq!(adapted, expected, epsilon = 1e-4);
+        assert_relative_eq!(adaptation.inverse().adapt(&adapted), color, epsilon = 1e-4);
+        assert_relative_eq!(
+            adapt::<_, Model>(&color, &D50.get_xyz(), &D65.get_xyz()),
+            adapted
+        );
+    }
+
+    #[test]
+    fn test_models() {
+        check_model::<Bradford>(Xyz::new(0.487_522, 0.396_134, 0.396_929));
+        check_model::<VonKries>(Xyz::new(0.490_694, 0.399_278, 0.395_837));
+        check_model::<Cat02>(Xyz::new(0.487_947, 0.396_794, 0.396_204));
+        check_model::<Cat16>(Xyz::new(0.491_976, 0.399_437, 0.396_326));
+    }
+
+    #[test]
+    fn test_same_white() {
+        let adaptation = ChromaticAdaptation::<f64, Cat16>::new(&A.get_xyz(), &A.get_xyz());
+        assert_relative_eq!(*adaptation.transform(), Matrix3::identity(), epsilon = 1e-6);
+        assert_eq!(*adaptation.src_white(), A.get_xyz());
+        assert_eq!(*adaptation.dst_white(), A.get_xyz());
+    }
+}
@@ -6,8 +6,9 @@ use approx;
 
 use crate::channel::{ChannelCast, ChannelFormatCast, ColorChannel};
 use crate::color;
-use crate::color::Lerp;
+use crate::color::{HueMode, Lerp};
 use num_traits;
+use num_traits::Zero;
 use std::fmt;
 use std::ops;
 
@@ -25,8 +26,33 @@ where
 {
     /// Construct a new `AngularChannel`
     pub fn new(val: T) -> Self {
+        #[cfg(feature = "debug-validate")]
+        debug_assert!(
+            num_traits::Float::is_finite(val.scalar()),
+            "hue is NaN or infinite"
+        );
         AngularChannel(val)
     }
+
+    /// Interpolate between `self` and `right` along the path around the circle given by `mode`
+    pub fn lerp_with_hue_mode(&self, right: &Self, pos: T::Scalar, mode: HueMode) -> Self {
+        let zero = T::Scalar::zero();
+        let period = T::period();
+        let half_period = T::half_turn().scalar();
+        let start = self.0.clone().normalize().scalar();
+        let diff = right.0.clone().normalize().scalar() - start;
+
+        let diff = match mode {
+            HueMode::Shorter if diff > half_period => diff - period,
+            HueMode::Shorter if diff < -half_period => diff + period,
+            HueMode::Longer if diff > zero && diff < half_period => diff - period,
+            HueMode::Longer if diff > -half_period && diff <= zero => diff + period,
+            HueMode::Increasing if diff < zero => diff + period,
+            HueMode::Decreasing if diff > zero => diff - period,
+            _ => diff,
+        };
+        AngularChannel(T::new(start + diff * pos).normalize())
+    }
 }
 
 impl<T> ColorChannel for AngularChannel<T>
@@ -64,7 +90,7 @@ where
         AngularChannel(T::new(value))
     }
     fn new(value: T) -> Self {
-        AngularChannel(value)
+        AngularChannel::new(value)
     }
 }
 
@@ -194,3 +220,40 @@ where
         )
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use angle::{Deg, Turns};
+    use approx::*;
+
+    #[test]
+    fn test_lerp_with_hue_mode() {
+        let start = AngularChannel::new(Deg(350.0));
+        let end = AngularChannel::new(Deg(10.0));
+        let lerp = |pos, mode| start.lerp_with_hue_mode(&end, pos, mode).0;
+
+        assert_relative_eq!(lerp(0.5, HueMode::Shorter), Deg(0.0));
+        assert_relative_eq!(lerp(0.25, HueMode::Shorter), Deg(355.0));
+        assert_relative_eq!(lerp(0.75, HueMode::Increasing), Deg(5.0));
+        assert_relative_eq!(lerp(0.5, HueMode::Longer), Deg(180.0));
+        assert_relative_eq!(lerp(0.5, HueMode::Decreasing), Deg(180.0));
+        assert_relative_eq!(
+            end.lerp_with_hue_mode(&start, 0.5, HueMode::Decreasing).0,
+            Deg(0.0)
+        );
+
+        let start = AngularChannel::new(Turns(0.9f32));
+        let end = AngularChannel::new(Turns(0.3f32));
+        assert_relative_eq!(
+            start.lerp_with_hue_mode(&end, 0.5, HueMode::Shorter).0,
+            Turns(0.1),
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(
+            start.lerp_with_hue_mode(&end, 0.5, HueMode::Longer).0,
+            Turns(0.6),
+            epsilon = 1e-6
+        );
+    }
+}
@@ -3,7 +3,7 @@
 //! Bounded channels are the only to support integer scalars
 
 use super::cast::ChannelFormatCast;
-use super::scalar::{NormalChannelScalar, PosNormalChannelScalar};
+use super::scalar::{NormalChannelScalar, PosNormalChannelScalar, SaturatingChannelScalar};
 use super::traits::ColorChannel;
 use super::ChannelCast;
 use crate::color;
@@ -56,10 +56,42 @@ macro_rules! impl_bounded_channel_type {
                 $name(value)
             }
             fn new(value: T) -> Self {
+                #[cfg(feature = "debug-validate")]
+                debug_assert!(value.is_finite_scalar(), "color channel is NaN or infinite");
                 $name(value)
             }
         }
 
+        impl<T> $name<T>
+        where
+            T: $scalar_type + SaturatingChannelScalar,
+        {
+            /// Add `rhs`, clamping the result to the bounds of the channel
+            pub fn saturating_add(self, rhs: Self) -> Self {
+                $name(self.0.saturating_add(rhs.0)).clamp(T::min_bound(), T::max_bound())
+            }
+            /// Subtract `rhs`, clamping the result to the bounds of the channel
+            pub fn saturating_sub(self, rhs: Self) -> Self {
+                $name(self.0.saturating_sub(rhs.0)).clamp(T::min_bound(), T::max_bound())
+            }
+            /// Add `rhs`, returning `None` if the result is outside the bounds of the channel
+            pub fn checked_add(self, rhs: Self) -> Option<Self> {
+                self.0.checked_add(rhs.0).and_then(Self::checked_bounds)
+            }
+            /// Subtract `rhs`, returning `None` if the result is outside the bounds of the channel
+            pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+                self.0.checked_sub(rhs.0).and_then(Self::checked_bounds)
+            }
+
+            fn checked_bounds(value: T) -> Option<Self> {
+                if value >= T::min_bound() && value <= T::max_bound() {
+                    Some($name(value))
+                } else {
+                    None
+                }
+            }
+        }
+
         impl<T> color::Invert for $name<T>
         where
             T: $scalar_type,
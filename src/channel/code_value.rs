@@ -0,0 +1,313 @@
+//! Scalars for 10-bit and 12-bit video code values
+//!
+//! Video is commonly stored with 10 or 12 bits per channel. [`U10`](struct.U10.html) and
+//! [`U12`](struct.U12.html) hold such code values in a `u16`, with a maximum of 1023 and 4095
+//! respectively, so they scale correctly when cast to and from the other channel formats. They
+//! are both `PosNormalChannelScalar`s and `NormalChannelScalar`s, so they can be used in `Rgb`
+//! and `YCbCr` as well as any other bounded color.
+//!
+//! Casting between integer formats replicates the high bits into the low bits when widening and
+//! truncates when narrowing, as the primitive formats do, while casting from floats rounds to the
+//! nearest code value.
+//!
+//! ## Examples:
+//!
+//! ```rust
+//! # extern crate prisma;
+//!
+//! use prisma::Rgb;
+//! use prisma::channel::U10;
+//!
+//! let rgb = Rgb::new(U10::new(1023), U10::new(512), U10::new(0));
+//! assert_eq!(rgb.color_cast::<u8>(), Rgb::new(255u8, 128, 0));
+//! assert_eq!(rgb.color_cast::<f32>().color_cast::<U10>(), rgb);
+//! assert_eq!(Rgb::new(255u8, 128, 0).color_cast::<U10>(), Rgb::new(U10::new(1023), U10::new(514), U10::new(0)));
+//! ```
+
+use crate::channel::{
+    BoundedChannelScalar, ChannelFormatCast, NormalChannelScalar, PosNormalChannelScalar,
+    SaturatingChannelScalar,
+};
+use crate::color;
+use num_traits::Zero;
+use std::fmt;
+use std::ops;
+
+macro_rules! impl_code_value {
+    ($name: ident, $bits: expr, $doc: expr) => {
+        #[doc = $doc]
+        ///
+        /// Addition and subtraction saturate at the bounds, and multiplication treats both values
+        /// as fractions of the maximum code value.
+        #[repr(transparent)]
+        #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name(u16);
+
+        impl $name {
+            /// The number of bits in a code value
+            pub const BITS: u32 = $bits;
+            /// The maximum code value
+            pub const MAX: $name = $name((1 << $bits) - 1);
+
+            /// Construct a new code value
+            ///
+            /// # Panics
+            /// If `value` is greater than the maximum code value
+            pub fn new(value: u16) -> Self {
+                assert!(
+                    value <= Self::MAX.0,
+                    concat!("code value out of range for ", stringify!($name))
+                );
+                $name(value)
+            }
+
+            /// Returns the code value
+            pub fn value(self) -> u16 {
+                self.0
+            }
+
+            fn from_u16(value: u16) -> Self {
+                $name(value >> (16 - $bits))
+            }
+
+            fn to_u16(self) -> u16 {
+                (self.0 << (16 - $bits)) | (self.0 >> (2 * $bits - 16))
+            }
+        }
+
+        impl ops::Add for $name {
+            type Output = $name;
+            fn add(self, rhs: $name) -> $name {
+                $name((self.0 + rhs.0).min(Self::MAX.0))
+            }
+        }
+
+        impl ops::Sub for $name {
+            type Output = $name;
+            fn sub(self, rhs: $name) -> $name {
+                $name(self.0.saturating_sub(rhs.0))
+            }
+        }
+
+        impl ops::Mul for $name {
+            type Output = $name;
+            fn mul(self, rhs: $name) -> $name {
+                let max = u32::from(Self::MAX.0);
+                $name(((u32::from(self.0) * u32::from(rhs.0) + max / 2) / max) as u16)
+            }
+        }
+
+        impl Zero for $name {
+            fn zero() -> Self {
+                $name(0)
+            }
+            fn is_zero(&self) -> bool {
+                self.0 == 0
+            }
+        }
+
+        impl BoundedChannelScalar for $name {}
+
+        impl SaturatingChannelScalar for $name {
+            fn saturating_add(self, rhs: Self) -> Self {
+                self + rhs
+            }
+            fn saturating_sub(self, rhs: Self) -> Self {
+                self - rhs
+            }
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                Some(self.0 + rhs.0)
+                    .filter(|&v| v <= Self::MAX.0)
+                    .map($name)
+            }
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                self.0.checked_sub(rhs.0).map($name)
+            }
+        }
+
+        impl PosNormalChannelScalar for $name {
+            #[inline]
+            fn min_bound() -> Self {
+                $name(0)
+            }
+            #[inline]
+            fn max_bound() -> Self {
+                Self::MAX
+            }
+            #[inline]
+            fn is_normalized(&self) -> bool {
+                self.0 <= Self::MAX.0
+            }
+            #[inline]
+            fn normalize(self) -> Self {
+                $name(self.0.min(Self::MAX.0))
+            }
+        }
+
+        impl NormalChannelScalar for $name {
+            #[inline]
+            fn min_bound() -> Self {
+                $name(0)
+            }
+            #[inline]
+            fn max_bound() -> Self {
+                Self::MAX
+            }
+            #[inline]
+            fn is_normalized(&self) -> bool {
+                self.0 <= Self::MAX.0
+            }
+            #[inline]
+            fn normalize(self) -> Self {
+                $name(self.0.min(Self::MAX.0))
+            }
+        }
+
+        impl color::Lerp for $name {
+            type Position = f64;
+            #[inline]
+            fn lerp(&self, right: &Self, pos: f64) -> Self {
+                let left = f64::from(self.0);
+                let right = f64::from(right.0);
+                $name((left * (1.0 - pos) + right * pos).round() as u16)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+
+        impl ChannelFormatCast<$name> for $name {
+            fn cast(self) -> $name {
+                self
+            }
+        }
+
+        impl_code_value!(@int $name, u8);
+        impl_code_value!(@int $name, u16);
+        impl_code_value!(@int $name, u32);
+        impl_code_value!(@float $name, f32);
+        impl_code_value!(@float $name, f64);
+    };
+    (@int $name: ident, $int: ty) => {
+        impl ChannelFormatCast<$int> for $name {
+            fn cast(self) -> $int {
+                self.to_u16().cast()
+            }
+        }
+        impl ChannelFormatCast<$name> for $int {
+            fn cast(self) -> $name {
+                $name::from_u16(self.cast())
+            }
+        }
+    };
+    (@float $name: ident, $float: ty) => {
+        impl ChannelFormatCast<$float> for $name {
+            fn cast(self) -> $float {
+                <$float>::from(self.0) / <$float>::from($name::MAX.0)
+            }
+            fn cast_with_rescale(self, min: f64, max: f64) -> $float {
+                let out: $float = self.cast();
+                out * (max - min) as $float + min as $float
+            }
+        }
+        impl ChannelFormatCast<$name> for $float {
+            fn cast(self) -> $name {
+                let max = <$float>::from($name::MAX.0);
+                $name((self * max).round().max(0.0).min(max) as u16)
+            }
+            fn cast_with_rescale(self, min: f64, max: f64) -> $name {
+                ((self - min as $float) / (max - min) as $float).cast()
+            }
+        }
+    };
+}
+
+impl_code_value!(U10, 10, "A 10-bit code value between 0 and 1023");
+impl_code_value!(U12, 12, "A 12-bit code value between 0 and 4095");
+
+macro_rules! impl_code_value_cast {
+    ($from: ident, $to: ident) => {
+        impl ChannelFormatCast<$to> for $from {
+            fn cast(self) -> $to {
+                $to::from_u16(self.to_u16())
+            }
+        }
+    };
+}
+
+impl_code_value_cast!(U10, U12);
+impl_code_value_cast!(U12, U10);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::color::Lerp;
+    use crate::rgb::Rgb;
+    use crate::ycbcr::YCbCrJpeg;
+
+    #[test]
+    fn test_bounds() {
+        assert_eq!(U10::MAX.value(), 1023);
+        assert_eq!(U12::MAX.value(), 4095);
+        assert_eq!(U10::new(1000) + U10::new(100), U10::MAX);
+        assert_eq!(U10::new(10) - U10::new(100), U10::new(0));
+        assert_eq!(U12::MAX * U12::new(2048), U12::new(2048));
+        assert_eq!(U10::new(512).lerp(&U10::new(1023), 0.5), U10::new(768));
+        assert!(!PosNormalChannelScalar::is_normalized(&U10(1024)));
+        assert_eq!(PosNormalChannelScalar::normalize(U12(5000)), U12::MAX);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_out_of_range() {
+        U10::new(1024);
+    }
+
+    #[test]
+    fn test_cast() {
+        let max_u8: u8 = U10::MAX.cast();
+        assert_eq!(max_u8, 255);
+        let max_u16: u16 = U12::MAX.cast();
+        assert_eq!(max_u16, 0xFFFF);
+        let max_u32: u32 = U10::MAX.cast();
+        assert_eq!(max_u32, 0xFFFFFFFF);
+        assert_eq!(ChannelFormatCast::<U10>::cast(0xFFu8), U10::MAX);
+        assert_eq!(ChannelFormatCast::<U12>::cast(0x80u8), U12::new(0x808));
+        assert_eq!(ChannelFormatCast::<U10>::cast(0xFFFFu16), U10::MAX);
+
+        let x: f32 = U10::new(341).cast();
+        assert_eq!(x, 341.0 / 1023.0);
+        assert_eq!(ChannelFormatCast::<U12>::cast(0.5f64), U12::new(2048));
+        assert_eq!(ChannelFormatCast::<U10>::cast(1.5f64), U10::MAX);
+        assert_eq!(ChannelFormatCast::<U10>::cast(-0.5f32), U10::new(0));
+
+        assert_eq!(ChannelFormatCast::<U12>::cast(U10::MAX), U12::MAX);
+        assert_eq!(
+            ChannelFormatCast::<U12>::cast(U10::new(256)),
+            U12::new(1025)
+        );
+        assert_eq!(
+            ChannelFormatCast::<U10>::cast(U12::new(1027)),
+            U10::new(256)
+        );
+    }
+
+    #[test]
+    fn test_colors() {
+        let rgb = Rgb::new(U12::new(4095), U12::new(1024), U12::new(0));
+        assert_eq!(rgb.to_string(), "Rgb(4095, 1024, 0)");
+        let rgb_f: Rgb<f64> = rgb.color_cast();
+        assert_eq!(rgb_f, Rgb::new(1.0, 1024.0 / 4095.0, 0.0));
+        assert_eq!(rgb_f.color_cast::<U12>(), rgb);
+
+        let ycbcr = YCbCrJpeg::new(0.5f32, -1.0, 1.0);
+        let coded: YCbCrJpeg<U10> = ycbcr.color_cast();
+        assert_eq!(coded, YCbCrJpeg::new(U10::new(512), U10::new(0), U10::MAX));
+        let out: YCbCrJpeg<f32> = coded.color_cast();
+        assert_eq!(out.cb(), -1.0);
+        assert_eq!(out.cr(), 1.0);
+    }
+}
@@ -52,6 +52,8 @@ where
     }
 
     fn new(value: T) -> Self {
+        #[cfg(feature = "debug-validate")]
+        debug_assert!(value.is_finite(), "color channel is NaN or infinite");
         PosFreeChannel(value)
     }
 }
@@ -196,6 +198,8 @@ where
     }
 
     fn new(value: T) -> Self {
+        #[cfg(feature = "debug-validate")]
+        debug_assert!(value.is_finite(), "color channel is NaN or infinite");
         FreeChannel(value)
     }
 }
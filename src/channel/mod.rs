@@ -28,7 +28,9 @@
 //!     except without negative values.
 //!
 //! The bounded scalars support integral and floating point primitives, whereas the angular and free
-//! channels only support floats.
+//! channels only support floats. `PosNormalChannelScalar` is also implemented by the fixed-point
+//! [`Fixed16`](../fixed/struct.Fixed16.html), and the bounded scalars by the 10-bit and 12-bit video
+//! code values in [`code_value`](code_value/index.html).
 //!
 //! ## Channel:
 //!
@@ -47,6 +49,7 @@
 pub mod angular_channel;
 pub mod bounded_channel;
 pub mod cast;
+pub mod code_value;
 pub mod free_channel;
 pub mod scalar;
 pub mod traits;
@@ -54,9 +57,10 @@ pub mod traits;
 pub use self::angular_channel::AngularChannel;
 pub use self::bounded_channel::{NormalBoundedChannel, PosNormalBoundedChannel};
 pub use self::cast::ChannelFormatCast;
+pub use self::code_value::{U10, U12};
 pub use self::free_channel::{FreeChannel, PosFreeChannel};
 pub use self::scalar::{
     AngularChannelScalar, BoundedChannelScalar, FreeChannelScalar, NormalChannelScalar,
-    PosNormalChannelScalar,
+    PosNormalChannelScalar, SaturatingChannelScalar,
 };
 pub use self::traits::{ChannelCast, ColorChannel};
@@ -24,13 +24,97 @@ pub trait BoundedChannelScalar:
     + ops::Sub<Self, Output = Self>
     + ops::Mul<Self, Output = Self>
 {
+    /// Returns false if the value is NaN or infinite
+    ///
+    /// Integer and fixed point scalars are always finite.
+    fn is_finite_scalar(&self) -> bool {
+        true
+    }
 }
 
 impl BoundedChannelScalar for u8 {}
 impl BoundedChannelScalar for u16 {}
 impl BoundedChannelScalar for u32 {}
-impl BoundedChannelScalar for f32 {}
-impl BoundedChannelScalar for f64 {}
+impl BoundedChannelScalar for f32 {
+    fn is_finite_scalar(&self) -> bool {
+        self.is_finite()
+    }
+}
+impl BoundedChannelScalar for f64 {
+    fn is_finite_scalar(&self) -> bool {
+        self.is_finite()
+    }
+}
+
+/// A bounded scalar with addition and subtraction that saturate or report overflow
+///
+/// For integers these are the inherent methods of the same name, so results stop at the limits of
+/// the type instead of wrapping or panicking. Floating point values cannot overflow, so the
+/// saturating methods simply add and subtract, and the checked methods only fail when the result
+/// is not finite. Colors additionally limit the results to the bounds of each channel.
+pub trait SaturatingChannelScalar: BoundedChannelScalar {
+    /// Add `rhs`, saturating at the limits of the type
+    fn saturating_add(self, rhs: Self) -> Self;
+    /// Subtract `rhs`, saturating at the limits of the type
+    fn saturating_sub(self, rhs: Self) -> Self;
+    /// Add `rhs`, returning `None` on overflow
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    /// Subtract `rhs`, returning `None` on overflow
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_saturating_scalar_int {
+    ($($name: ident),*) => {
+        $(
+            impl SaturatingChannelScalar for $name {
+                #[inline]
+                fn saturating_add(self, rhs: Self) -> Self {
+                    $name::saturating_add(self, rhs)
+                }
+                #[inline]
+                fn saturating_sub(self, rhs: Self) -> Self {
+                    $name::saturating_sub(self, rhs)
+                }
+                #[inline]
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    $name::checked_add(self, rhs)
+                }
+                #[inline]
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    $name::checked_sub(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_saturating_scalar_float {
+    ($($name: ident),*) => {
+        $(
+            impl SaturatingChannelScalar for $name {
+                #[inline]
+                fn saturating_add(self, rhs: Self) -> Self {
+                    self + rhs
+                }
+                #[inline]
+                fn saturating_sub(self, rhs: Self) -> Self {
+                    self - rhs
+                }
+                #[inline]
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    Some(self + rhs).filter(|v| v.is_finite())
+                }
+                #[inline]
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    Some(self - rhs).filter(|v| v.is_finite())
+                }
+            }
+        )*
+    };
+}
+
+impl_saturating_scalar_int!(u8, u16, u32);
+impl_saturating_scalar_float!(f32, f64);
 
 /// A scalar for periodic, angular channels
 pub trait AngularChannelScalar:
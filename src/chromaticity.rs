@@ -1,7 +1,16 @@
+//! Chromaticity coordinates and measures derived from the spectral locus
+
 use crate::convert::{GetChroma, GetHue};
 use angle::{Angle, FromAngle, Rad};
 use num_traits;
 
+mod locus;
+
+pub use self::locus::{
+    complementary_wavelength, dominant_wavelength, excitation_purity, is_inside_spectral_locus,
+    spectral_chromaticity, spectral_locus, spectral_tristimulus, DominantWavelength, SpectralLocus,
+};
+
 // TODO: Improve this module
 #[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Hash)]
 /// A pair of chromaticity coordinates $`\alpha`$ and $`\beta`$
@@ -0,0 +1,446 @@
+//! The spectral locus and measures derived from it
+
+use crate::channel::{FreeChannelScalar, PosNormalChannelScalar};
+use crate::spectrum::{Cie1931, ColorMatchingFunctions};
+use crate::xyy::XyY;
+use crate::xyz::Xyz;
+use num_traits::{cast, Float};
+use std::marker::PhantomData;
+
+/// Compute the tristimulus values of a monochromatic light of `wavelength` nanometers
+///
+/// This is the value of the [`Cie1931`](../spectrum/struct.Cie1931.html) color matching functions
+/// at `wavelength`, linearly interpolated from 5nm samples. Returns `None` if `wavelength` is
+/// outside of the range [380, 780].
+pub fn spectral_tristimulus<T>(wavelength: T) -> Option<Xyz<T>>
+where
+    T: Float + FreeChannelScalar,
+{
+    Cie1931.sample(wavelength)
+}
+
+/// Compute the point of the spectral locus at `wavelength` nanometers
+///
+/// The `x` and `y` channels hold the chromaticity of a monochromatic light, while `Y` holds its
+/// relative luminance, so converting the result to `Xyz` gives the same value as
+/// [`spectral_tristimulus`](fn.spectral_tristimulus.html). Returns `None` if `wavelength` is
+/// outside of the range [380, 780].
+///
+/// ## Examples:
+///
+/// ```rust
+/// #[macro_use] extern crate approx;
+/// # extern crate prisma;
+///
+/// use prisma::chromaticity::spectral_chromaticity;
+///
+/// let green = spectral_chromaticity(520.0).unwrap();
+/// assert_relative_eq!(green.x(), 0.0743, epsilon=1e-4);
+/// assert_relative_eq!(green.y(), 0.8338, epsilon=1e-4);
+/// assert_eq!(spectral_chromaticity(300.0), None);
+/// ```
+pub fn spectral_chromaticity<T>(wavelength: T) -> Option<XyY<T>>
+where
+    T: Float + FreeChannelScalar + PosNormalChannelScalar,
+{
+    let [x, y, z] = Cie1931.interpolate(cast(wavelength).unwrap())?;
+    let sum = x + y + z;
+    Some(XyY::new(
+        cast(x / sum).unwrap(),
+        cast(y / sum).unwrap(),
+        cast(y).unwrap(),
+    ))
+}
+
+/// Returns an iterator over the sampled points of the spectral locus
+///
+/// Each item is a wavelength in nanometers paired with the value of
+/// [`spectral_chromaticity`](fn.spectral_chromaticity.html) at that wavelength, from 380nm to
+/// 780nm in steps of 5nm. Joining the points and closing the shape with the purple boundary gives
+/// the outline of the chromaticity diagram.
+pub fn spectral_locus<T>() -> SpectralLocus<T> {
+    SpectralLocus {
+        index: 0,
+        _scalar: PhantomData,
+    }
+}
+
+/// Returns whether the chromaticity of `color` lies inside of the spectral locus
+///
+/// Chromaticities outside of the region enclosed by the spectral locus and the purple boundary
+/// cannot be produced by any physical light. Points on the boundary are considered to be inside.
+pub fn is_inside_spectral_locus<T>(color: &XyY<T>) -> bool
+where
+    T: Float + FreeChannelScalar + PosNormalChannelScalar,
+{
+    let point: (f64, f64) = (cast(color.x()).unwrap(), cast(color.y()).unwrap());
+    let mut inside = false;
+
+    for i in 0..Cie1931.table().len() {
+        let (start, end) = boundary_edge(i);
+        let edge = (end.0 - start.0, end.1 - start.1);
+        let offset = (point.0 - start.0, point.1 - start.1);
+
+        let length_sq = edge.0 * edge.0 + edge.1 * edge.1;
+        let s = if length_sq > 0.0 {
+            ((offset.0 * edge.0 + offset.1 * edge.1) / length_sq).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let (dx, dy) = (offset.0 - s * edge.0, offset.1 - s * edge.1);
+        if dx * dx + dy * dy < 1e-18 {
+            return true;
+        }
+
+        if (start.1 > point.1) != (end.1 > point.1) {
+            let x = start.0 + (point.1 - start.1) / edge.1 * edge.0;
+            if point.0 < x {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+/// An iterator over the sampled points of the spectral locus
+///
+/// Created by [`spectral_locus`](fn.spectral_locus.html).
+#[derive(Clone, Debug)]
+pub struct SpectralLocus<T> {
+    index: usize,
+    _scalar: PhantomData<T>,
+}
+
+impl<T> Iterator for SpectralLocus<T>
+where
+    T: Float + FreeChannelScalar + PosNormalChannelScalar,
+{
+    type Item = (T, XyY<T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= Cie1931.table().len() {
+            return None;
+        }
+        let wavelength = Cie1931.start_wavelength() + self.index as f64 * Cie1931.wavelength_step();
+        self.index += 1;
+        let wavelength: T = cast(wavelength).unwrap();
+        spectral_chromaticity(wavelength).map(|xy| (wavelength, xy))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = Cie1931.table().len().saturating_sub(self.index);
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for SpectralLocus<T> where
+    T: Float + FreeChannelScalar + PosNormalChannelScalar
+{
+}
+
+/// The dominant wavelength of a color relative to a white point
+///
+/// Colors in the purple region of the chromaticity diagram, between the violet and red ends of the
+/// spectral locus, have no dominant wavelength. They are instead described by the complementary
+/// wavelength, the point of the spectral locus opposite of the color through the white point.
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub enum DominantWavelength<T> {
+    /// The color lies on the line from the white point to this wavelength of the spectral locus
+    Spectral(T),
+    /// The color is a purple, lying opposite of this wavelength of the spectral locus
+    Complementary(T),
+}
+
+impl<T> DominantWavelength<T>
+where
+    T: Clone,
+{
+    /// Returns the wavelength in nanometers, whether it is dominant or complementary
+    pub fn wavelength(&self) -> T {
+        match *self {
+            DominantWavelength::Spectral(ref w) | DominantWavelength::Complementary(ref w) => {
+                w.clone()
+            }
+        }
+    }
+    /// Returns whether the wavelength is complementary rather than dominant
+    pub fn is_complementary(&self) -> bool {
+        match *self {
+            DominantWavelength::Spectral(_) => false,
+            DominantWavelength::Complementary(_) => true,
+        }
+    }
+}
+
+/// Compute the dominant wavelength of `color` relative to `white`
+///
+/// The dominant wavelength is the spectral color that, mixed with the white, matches `color`. It is
+/// found by extending the line from `white` through `color` until it meets the CIE 1931 spectral
+/// locus. The `Y` channel of both colors is ignored; `Xyz` colors can be converted with
+/// `XyY::from_color`.
+///
+/// Returns `None` if `color` has the same chromaticity as `white`.
+///
+/// ## Examples:
+///
+/// ```rust
+/// #[macro_use] extern crate approx;
+/// # extern crate prisma;
+///
+/// use prisma::chromaticity::{dominant_wavelength, DominantWavelength};
+/// use prisma::white_point::{WhitePoint, D65};
+/// use prisma::XyY;
+///
+/// let red = XyY::new(0.64, 0.33, 0.2126);
+/// let wavelength = dominant_wavelength(&red, &D65.get_xy_chromaticity()).unwrap();
+/// assert!(!wavelength.is_complementary());
+/// assert_relative_eq!(wavelength.wavelength(), 611.37, epsilon=1e-2);
+/// ```
+pub fn dominant_wavelength<T>(color: &XyY<T>, white: &XyY<T>) -> Option<DominantWavelength<T>>
+where
+    T: Float + FreeChannelScalar + PosNormalChannelScalar,
+{
+    let (origin, dir) = ray(color, white)?;
+    match intersect_boundary(origin, dir)?.1 {
+        Some(wavelength) => Some(DominantWavelength::Spectral(cast(wavelength).unwrap())),
+        None => intersect_boundary(origin, (-dir.0, -dir.1))?
+            .1
+            .map(|wavelength| DominantWavelength::Complementary(cast(wavelength).unwrap())),
+    }
+}
+
+/// Compute the complementary wavelength of `color` relative to `white`
+///
+/// The complementary wavelength is the spectral color on the opposite side of `white` from
+/// `color`, found by extending the line from `color` through `white` until it meets the spectral
+/// locus.
+///
+/// Returns `None` if `color` has the same chromaticity as `white`, or if the line meets the
+/// purple boundary instead, as happens for most greens.
+pub fn complementary_wavelength<T>(color: &XyY<T>, white: &XyY<T>) -> Option<T>
+where
+    T: Float + FreeChannelScalar + PosNormalChannelScalar,
+{
+    let (origin, dir) = ray(color, white)?;
+    intersect_boundary(origin, (-dir.0, -dir.1))?
+        .1
+        .map(|wavelength| cast(wavelength).unwrap())
+}
+
+/// Compute the excitation purity of `color` relative to `white`
+///
+/// The excitation purity is the distance from `white` to `color` on the chromaticity diagram as a
+/// fraction of the distance from `white` to the boundary of the diagram in the same direction.
+/// It is 0 for the white point itself and 1 for colors on the spectral locus or the purple
+/// boundary.
+///
+/// Returns `None` if `white` lies outside of the spectral locus.
+pub fn excitation_purity<T>(color: &XyY<T>, white: &XyY<T>) -> Option<T>
+where
+    T: Float + FreeChannelScalar + PosNormalChannelScalar,
+{
+    match ray(color, white) {
+        Some((origin, dir)) => intersect_boundary(origin, dir).map(|(t, _)| cast(1.0 / t).unwrap()),
+        None => Some(T::zero()),
+    }
+}
+
+fn ray<T>(color: &XyY<T>, white: &XyY<T>) -> Option<((f64, f64), (f64, f64))>
+where
+    T: Float + FreeChannelScalar + PosNormalChannelScalar,
+{
+    let origin: (f64, f64) = (cast(white.x()).unwrap(), cast(white.y()).unwrap());
+    let dir = (
+        cast::<_, f64>(color.x()).unwrap() - origin.0,
+        cast::<_, f64>(color.y()).unwrap() - origin.1,
+    );
+    if dir.0 == 0.0 && dir.1 == 0.0 {
+        None
+    } else {
+        Some((origin, dir))
+    }
+}
+
+fn locus_xy(index: usize) -> (f64, f64) {
+    let [x, y, z] = Cie1931.table()[index];
+    let sum = x + y + z;
+    (x / sum, y / sum)
+}
+
+/// Returns an edge of the chromaticity diagram boundary, with the last edge being the purple
+/// boundary
+fn boundary_edge(index: usize) -> ((f64, f64), (f64, f64)) {
+    let last = Cie1931.table().len() - 1;
+    if index < last {
+        (locus_xy(index), locus_xy(index + 1))
+    } else {
+        (locus_xy(last), locus_xy(0))
+    }
+}
+
+fn cross(a: (f64, f64), b: (f64, f64)) -> f64 {
+    a.0 * b.1 - a.1 * b.0
+}
+
+/// Find the nearest intersection of a ray with the boundary of the chromaticity diagram
+///
+/// Returns the distance along the ray in multiples of `dir`, and the wavelength of the
+/// intersection or `None` if the ray meets the purple boundary.
+fn intersect_boundary(origin: (f64, f64), dir: (f64, f64)) -> Option<(f64, Option<f64>)> {
+    let last = Cie1931.table().len() - 1;
+    let mut nearest: Option<(f64, Option<f64>)> = None;
+
+    for i in 0..=last {
+        let (start, end) = boundary_edge(i);
+        let edge = (end.0 - start.0, end.1 - start.1);
+        let denom = cross(dir, edge);
+        if denom.abs() < 1e-12 {
+            continue;
+        }
+        let offset = (start.0 - origin.0, start.1 - origin.1);
+        let t = cross(offset, edge) / denom;
+        let s = cross(offset, dir) / denom;
+        if t <= 0.0 || !(0.0..=1.0).contains(&s) {
+            continue;
+        }
+        if let Some((best, _)) = nearest {
+            if best <= t {
+                continue;
+            }
+        }
+        let wavelength = if i < last {
+            Some(Cie1931.start_wavelength() + (i as f64 + s) * Cie1931.wavelength_step())
+        } else {
+            None
+        };
+        nearest = Some((t, wavelength));
+    }
+    nearest
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::convert::FromColor;
+    use crate::white_point::{WhitePoint, D65, E};
+    use approx::*;
+
+    #[test]
+    fn test_spectral_tristimulus() {
+        assert_relative_eq!(
+            spectral_tristimulus(555.0).unwrap(),
+            Xyz::new(0.51205, 1.0, 0.00575)
+        );
+        assert_relative_eq!(
+            spectral_tristimulus(552.5).unwrap(),
+            Xyz::new(0.47275, 0.997475, 0.00725),
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            spectral_tristimulus(780.0f32).unwrap(),
+            Xyz::new(0.000042, 0.000015, 0.0)
+        );
+        assert_eq!(spectral_tristimulus(379.9), None);
+        assert_eq!(spectral_tristimulus(780.1), None);
+    }
+
+    #[test]
+    fn test_spectral_chromaticity() {
+        let c1 = spectral_chromaticity(500.0).unwrap();
+        assert_relative_eq!(c1, XyY::new(0.008168, 0.538423, 0.323), epsilon = 1e-6);
+        assert_relative_eq!(
+            Xyz::from_color(&c1),
+            spectral_tristimulus(500.0).unwrap(),
+            epsilon = 1e-9
+        );
+        assert_eq!(spectral_chromaticity(800.0), None);
+    }
+
+    #[test]
+    fn test_spectral_locus() {
+        let points: Vec<_> = spectral_locus::<f64>().collect();
+        assert_eq!(points.len(), 81);
+        assert_eq!(spectral_locus::<f32>().len(), 81);
+        assert_relative_eq!(points[0].0, 380.0);
+        assert_relative_eq!(points[80].0, 780.0);
+        for (wavelength, xy) in points {
+            assert_relative_eq!(xy, spectral_chromaticity(wavelength).unwrap());
+            assert!(is_inside_spectral_locus(&xy));
+        }
+    }
+
+    #[test]
+    fn test_is_inside_spectral_locus() {
+        assert!(is_inside_spectral_locus::<f64>(&D65.get_xy_chromaticity()));
+        assert!(is_inside_spectral_locus(&XyY::new(0.64, 0.33, 1.0)));
+        assert!(is_inside_spectral_locus(&XyY::new(0.3209, 0.1542, 1.0)));
+        // The ProPhoto RGB green and blue primaries are imaginary colors
+        assert!(!is_inside_spectral_locus(&XyY::new(0.1596, 0.8404, 1.0)));
+        assert!(!is_inside_spectral_locus(&XyY::new(0.0366, 0.0001, 1.0)));
+        assert!(!is_inside_spectral_locus(&XyY::new(0.0, 0.0, 1.0)));
+        assert!(!is_inside_spectral_locus(&XyY::new(0.5, 0.1, 1.0)));
+    }
+
+    #[test]
+    fn test_dominant_wavelength() {
+        let white = D65.get_xy_chromaticity();
+        let red = dominant_wavelength(&XyY::new(0.64, 0.33, 1.0), &white).unwrap();
+        assert_relative_eq!(red.wavelength(), 611.375, epsilon = 1e-3);
+        assert!(!red.is_complementary());
+        let green = dominant_wavelength(&XyY::new(0.3, 0.6, 1.0), &white).unwrap();
+        assert_relative_eq!(green.wavelength(), 549.136, epsilon = 1e-3);
+        let blue = dominant_wavelength(&XyY::new(0.15, 0.06, 1.0), &white).unwrap();
+        assert_relative_eq!(blue.wavelength(), 464.204, epsilon = 1e-3);
+
+        let magenta = dominant_wavelength(&XyY::new(0.3209, 0.1542, 1.0), &white).unwrap();
+        assert!(magenta.is_complementary());
+        assert_relative_eq!(magenta.wavelength(), 549.139, epsilon = 1e-3);
+
+        assert_eq!(dominant_wavelength(&white, &white), None);
+    }
+
+    #[test]
+    fn test_complementary_wavelength() {
+        let white = D65.get_xy_chromaticity();
+        let red = complementary_wavelength(&XyY::new(0.64, 0.33, 1.0), &white).unwrap();
+        assert_relative_eq!(red, 491.411, epsilon = 1e-3);
+        let blue = complementary_wavelength(&XyY::new(0.15, 0.06, 1.0), &white).unwrap();
+        assert_relative_eq!(blue, 570.464, epsilon = 1e-3);
+        assert_eq!(
+            complementary_wavelength(&XyY::new(0.3, 0.6, 1.0), &white),
+            None
+        );
+    }
+
+    #[test]
+    fn test_excitation_purity() {
+        let white = D65.get_xy_chromaticity();
+        let red = XyY::new(0.64, 0.33, 1.0);
+        assert_relative_eq!(
+            excitation_purity(&red, &white).unwrap(),
+            0.916806,
+            epsilon = 1e-5
+        );
+        let magenta = XyY::new(0.3209, 0.1542, 1.0);
+        assert_relative_eq!(
+            excitation_purity(&magenta, &white).unwrap(),
+            0.685613,
+            epsilon = 1e-5
+        );
+        assert_eq!(excitation_purity(&white, &white), Some(0.0));
+
+        let locus = locus_xy(40);
+        let spectral = XyY::new(locus.0, locus.1, 1.0);
+        let white = E.get_xy_chromaticity();
+        assert_relative_eq!(
+            excitation_purity(&spectral, &white).unwrap(),
+            1.0,
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            dominant_wavelength(&spectral, &white).unwrap().wavelength(),
+            580.0,
+            epsilon = 1e-6
+        );
+    }
+}
@@ -1,5 +1,6 @@
 //! A collection of traits implemented by the various color types
 
+use crate::iter::Channels;
 use num_traits;
 
 /// The base color trait, representing any color
@@ -41,12 +42,142 @@ pub trait Flatten: HomogeneousColor {
 }
 
 /// A color only having one type of channel
+///
+/// ## Examples:
+///
+/// ```rust
+/// use prisma::{HomogeneousColor, Rgb};
+///
+/// let c1 = Rgb::new(200u8, 50, 120);
+/// let c2 = Rgb::new(100u8, 80, 120);
+/// assert_eq!(c1.map_channels(|c| c / 2), Rgb::new(100, 25, 60));
+/// assert_eq!(c1.zip_channels(&c2, |a, b| a.max(b)), Rgb::new(200, 80, 120));
+/// assert_eq!(
+///     c1.zip_channels(&c2, |a, b| if a > b { a - b } else { b - a }),
+///     Rgb::new(100, 30, 0)
+/// );
+/// ```
 pub trait HomogeneousColor: Color {
     /// The scalar type of each channel
     type ChannelFormat;
 
     /// Clamp the value of each channel between `min` and `max`
     fn clamp(self, min: Self::ChannelFormat, max: Self::ChannelFormat) -> Self;
+
+    /// Return an iterator over the channel scalars of `self`, in the order of `ChannelOrder`
+    ///
+    /// See the [`iter`](iter/index.html) module for more details.
+    fn iter(&self) -> Channels<'_, Self>
+    where
+        Self: ChannelAccess,
+    {
+        Channels::new(self)
+    }
+    /// Return a color with `f` applied to each channel of `self`
+    ///
+    /// The results of `f` are not clamped to the bounds of each channel. Use `normalize` from
+    /// [`Bounded`](trait.Bounded.html) afterwards if they may leave them.
+    fn map_channels<F>(mut self, mut f: F) -> Self
+    where
+        Self: ChannelAccess,
+        Self::ChannelFormat: Clone,
+        F: FnMut(Self::ChannelFormat) -> Self::ChannelFormat,
+    {
+        for index in 0..Self::num_channels() as usize {
+            if let Some(channel) = self.channel_mut(index) {
+                *channel = f(channel.clone());
+            }
+        }
+        self
+    }
+    /// Return a color with `f` applied to each pair of matching channels of `self` and `other`
+    ///
+    /// As with [`map_channels`](#method.map_channels), the results of `f` are not clamped.
+    fn zip_channels<F>(mut self, other: &Self, mut f: F) -> Self
+    where
+        Self: ChannelAccess,
+        Self::ChannelFormat: Clone,
+        F: FnMut(Self::ChannelFormat, Self::ChannelFormat) -> Self::ChannelFormat,
+    {
+        for index in 0..Self::num_channels() as usize {
+            if let (Some(channel), Some(right)) = (self.channel_mut(index), other.channel(index)) {
+                *channel = f(channel.clone(), right.clone());
+            }
+        }
+        self
+    }
+}
+
+/// A color whose channels have names and a fixed order
+///
+/// Channels are indexed in the order `new` takes them, which is also the order of `to_tuple` and
+/// of array conversions. The names are those of the channel accessors, such as `"red"`, `"hue"`
+/// or `"L"`, and `Alpha` colors append `"alpha"` to the names of their inner color. This lets
+/// generic code such as serializers or plotting label each channel without knowing the concrete
+/// color type.
+///
+/// ## Examples:
+///
+/// ```rust
+/// use prisma::{ChannelOrder, Hsv, Rgba};
+///
+/// assert_eq!(Hsv::<f32>::channel_names(), vec!["hue", "saturation", "value"]);
+/// assert_eq!(Rgba::<u8>::channel_index("alpha"), Some(3));
+/// assert_eq!(Rgba::<u8>::channel_name(4), None);
+/// ```
+pub trait ChannelOrder: Color {
+    /// Return the name of the channel at `index`, or `None` if there is no such channel
+    fn channel_name(index: usize) -> Option<&'static str>;
+
+    /// Return the names of every channel, in order
+    fn channel_names() -> Vec<&'static str> {
+        (0..Self::num_channels() as usize)
+            .filter_map(Self::channel_name)
+            .collect()
+    }
+    /// Return the index of the channel called `name`, or `None` if there is no such channel
+    fn channel_index(name: &str) -> Option<usize> {
+        (0..Self::num_channels() as usize).find(|&index| Self::channel_name(index) == Some(name))
+    }
+}
+
+/// A color whose channels can be borrowed by index or by name
+///
+/// Only colors with a single channel type implement `ChannelAccess`. The hue of polar colors is an
+/// angle rather than a scalar, so it is reached through `hue` and `hue_mut` instead.
+///
+/// ## Examples:
+///
+/// ```rust
+/// use prisma::{ChannelAccess, Color, Rgb};
+///
+/// let mut color = Rgb::new(10u8, 20, 30);
+/// assert_eq!(color.channel(1), Some(&20));
+/// assert_eq!(color.channel(3), None);
+///
+/// *color.channel_by_name_mut("blue").unwrap() = 40;
+/// for index in 0..Rgb::<u8>::num_channels() as usize {
+///     *color.channel_mut(index).unwrap() += 1;
+/// }
+/// assert_eq!(color, Rgb::new(11, 21, 41));
+/// ```
+pub trait ChannelAccess: HomogeneousColor + ChannelOrder {
+    /// Return a reference to the channel at `index`, or `None` if there is no such channel
+    fn channel(&self, index: usize) -> Option<&Self::ChannelFormat>;
+    /// Return a mutable reference to the channel at `index`, or `None` if there is no such channel
+    ///
+    /// Writing through the reference does not clamp the channel to its bounds.
+    fn channel_mut(&mut self, index: usize) -> Option<&mut Self::ChannelFormat>;
+
+    /// Return a reference to the channel called `name`, or `None` if there is no such channel
+    fn channel_by_name(&self, name: &str) -> Option<&Self::ChannelFormat> {
+        self.channel(Self::channel_index(name)?)
+    }
+    /// Return a mutable reference to the channel called `name`, or `None` if there is no such
+    /// channel
+    fn channel_by_name_mut(&mut self, name: &str) -> Option<&mut Self::ChannelFormat> {
+        self.channel_mut(Self::channel_index(name)?)
+    }
 }
 
 /// A color that can have all of its channels set from a single value
@@ -72,6 +203,51 @@ pub trait Lerp {
     fn lerp(&self, right: &Self, pos: Self::Position) -> Self;
 }
 
+/// The path taken around the hue circle when interpolating polar colors
+///
+/// These are the hue interpolation methods of CSS Color Level 4. Two hues split the circle into
+/// two arcs, and the mode decides which arc is traveled.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum HueMode {
+    /// Travel along the shorter arc. This is the behavior of [`Lerp`](trait.Lerp.html)
+    Shorter,
+    /// Travel along the longer arc
+    Longer,
+    /// Travel with increasing hue angles, wrapping past the full turn if necessary
+    Increasing,
+    /// Travel with decreasing hue angles, wrapping past zero if necessary
+    Decreasing,
+}
+
+/// A polar color that can be interpolated along a chosen path around the hue circle
+///
+/// `Lerp` on polar colors always takes the shorter path between the two hues.
+/// `lerp_with_hue_mode` makes the path explicit, which is needed for example to draw a full
+/// rainbow gradient between two nearby hues.
+///
+/// ## Examples:
+///
+/// ```rust
+/// #[macro_use] extern crate approx;
+/// # extern crate prisma;
+/// extern crate angular_units as angle;
+///
+/// use prisma::{Hsv, HueMode, Lerp, PolarLerp};
+/// use angle::Deg;
+///
+/// let c1 = Hsv::new(Deg(30.0), 1.0, 1.0);
+/// let c2 = Hsv::new(Deg(330.0), 1.0, 1.0);
+/// assert_relative_eq!(c1.lerp(&c2, 0.5), Hsv::new(Deg(0.0), 1.0, 1.0));
+/// assert_relative_eq!(
+///     c1.lerp_with_hue_mode(&c2, 0.5, HueMode::Increasing),
+///     Hsv::new(Deg(180.0), 1.0, 1.0)
+/// );
+/// ```
+pub trait PolarLerp: Lerp {
+    /// Interpolate between `self` and `right`, moving the hue along the path given by `mode`
+    fn lerp_with_hue_mode(&self, right: &Self, pos: Self::Position, mode: HueMode) -> Self;
+}
+
 /// A value that can be inverted
 pub trait Invert {
     /// Invert `Self`
@@ -86,5 +262,36 @@ pub trait Bounded {
     fn is_normalized(&self) -> bool;
 }
 
+/// A color whose channels can be added and subtracted without leaving their bounds
+///
+/// Each operation works channel by channel. The saturating methods clamp each channel to its
+/// bounds, while the checked methods return `None` if any channel would leave them. For integer
+/// channels, this avoids overflow without casting to a wider type first.
+///
+/// ## Examples:
+///
+/// ```rust
+/// # extern crate prisma;
+///
+/// use prisma::{BoundedArithmetic, Rgb};
+///
+/// let c1 = Rgb::new(200u8, 100, 0);
+/// let c2 = Rgb::new(100u8, 100, 100);
+/// assert_eq!(c1.saturating_add(&c2), Rgb::new(255, 200, 100));
+/// assert_eq!(c1.saturating_sub(&c2), Rgb::new(100, 0, 0));
+/// assert_eq!(c1.checked_add(&c2), None);
+/// assert_eq!(c2.checked_sub(&Rgb::new(50, 0, 100)), Some(Rgb::new(50, 100, 0)));
+/// ```
+pub trait BoundedArithmetic: Sized {
+    /// Add `rhs` to each channel, clamping the results to the channel bounds
+    fn saturating_add(&self, rhs: &Self) -> Self;
+    /// Subtract `rhs` from each channel, clamping the results to the channel bounds
+    fn saturating_sub(&self, rhs: &Self) -> Self;
+    /// Add `rhs` to each channel, returning `None` if any channel leaves its bounds
+    fn checked_add(&self, rhs: &Self) -> Option<Self>;
+    /// Subtract `rhs` from each channel, returning `None` if any channel leaves its bounds
+    fn checked_sub(&self, rhs: &Self) -> Option<Self>;
+}
+
 /// A color which must have a space specified in order to uniquely represent a color
 pub trait DeviceDependentColor: Color {}
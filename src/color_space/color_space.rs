@@ -9,6 +9,7 @@ use crate::encoding::{
     TranscodableColor,
 };
 use crate::linalg::Matrix3;
+use crate::lms::{chromatic_adaptation_transform, LmsModel};
 use crate::rgb::Rgb;
 use crate::xyz::Xyz;
 use num_traits;
@@ -42,6 +43,32 @@ pub trait ColorSpace<T>: Clone {
 
     /// Apply the forward transform to a 3-vector
     fn apply_transform(&self, vec: (T, T, T)) -> (T, T, T);
+
+    /// Returns the RGB -> XYZ matrix, adapted to produce colors relative to `white_point`
+    ///
+    /// This lets colors from spaces with differing white points, such as D50 ProPhoto RGB and D65
+    /// sRGB, be compared in XYZ. The adaptation is done in the cone space of `Model`.
+    fn get_adapted_xyz_transform<Model>(&self, white_point: &Xyz<T>) -> Matrix3<T>
+    where
+        T: FreeChannelScalar,
+        Model: LmsModel<T>,
+    {
+        chromatic_adaptation_transform::<T, Model>(&self.white_point(), white_point)
+            * self.get_xyz_transform()
+    }
+
+    /// Returns the XYZ -> RGB matrix for XYZ colors relative to `white_point`
+    ///
+    /// This is the inverse of
+    /// [`get_adapted_xyz_transform`](#method.get_adapted_xyz_transform).
+    fn get_adapted_inverse_xyz_transform<Model>(&self, white_point: &Xyz<T>) -> Matrix3<T>
+    where
+        T: FreeChannelScalar,
+        Model: LmsModel<T>,
+    {
+        self.get_inverse_xyz_transform()
+            * chromatic_adaptation_transform::<T, Model>(white_point, &self.white_point())
+    }
 }
 
 /// An object that can convert a color into XYZ
@@ -56,6 +83,28 @@ where
 
     /// Convert `color` into the XYZ space
     fn convert_to_xyz(&self, color: &EncodedColor<CIn, EIn>) -> Self::OutputColor;
+
+    /// Convert `color` into the XYZ space, adapted to be relative to `white_point`
+    ///
+    /// The adaptation is done in the cone space of `Model`, such as
+    /// [`Bradford`](../lms/struct.Bradford.html). Use this to bring colors from a space with a
+    /// different white point, such as D50 ProPhoto RGB, into XYZ relative to D65.
+    fn convert_to_xyz_adapted<Model>(
+        &self,
+        color: &EncodedColor<CIn, EIn>,
+        white_point: &Xyz<T>,
+    ) -> Xyz<T>
+    where
+        Self: ConvertToXyz<T, CIn, EIn, OutputColor = Xyz<T>>,
+        T: FreeChannelScalar,
+        Model: LmsModel<T>,
+    {
+        let xyz = self.convert_to_xyz(color);
+        let (x, y, z) =
+            chromatic_adaptation_transform::<T, Model>(&self.white_point(), white_point)
+                .transform_vector(xyz.to_tuple());
+        Xyz::new(x, y, z)
+    }
 }
 /// An object that can convert a color out of XYZ
 pub trait ConvertFromXyz<T: num_traits::Float, In>: ColorSpace<T> + Sized
@@ -117,18 +166,33 @@ where
         white_point: Xyz<T>,
         encoding: E,
     ) -> Self {
+        Self::try_new(red, green, blue, white_point, encoding).expect(
+            "Singular transformation matrix, make sure red, green and blue are \
+             linearly independent",
+        )
+    }
+
+    /// Construct a new `EncodedColorSpace`, returning `None` if the primaries and white point
+    /// don't give an invertible transformation
+    pub(crate) fn try_new(
+        red: RgbPrimary<T>,
+        green: RgbPrimary<T>,
+        blue: RgbPrimary<T>,
+        white_point: Xyz<T>,
+        encoding: E,
+    ) -> Option<Self> {
+        if [&red, &green, &blue].iter().any(|p| p.y.0 == T::zero()) {
+            return None;
+        }
         let forward_transform = Self::build_transform(
             red.clone(),
             green.clone(),
             blue.clone(),
             white_point.clone(),
-        );
-        let inv_transform = forward_transform.clone().inverse().expect(
-            "Singular transformation matrix, make sure red, green and blue are \
-             linearly independent",
-        );
+        )?;
+        let inv_transform = forward_transform.clone().try_inverse()?;
 
-        EncodedColorSpace {
+        Some(EncodedColorSpace {
             red_primary: red,
             green_primary: green,
             blue_primary: blue,
@@ -136,7 +200,7 @@ where
             encoding,
             xyz_transform: forward_transform,
             inv_transform,
-        }
+        })
     }
 
     /// Construct a new `EncodedColorSpace` from primaries, a white point and an encoding as well as transformation matrices
@@ -186,17 +250,17 @@ where
         green_primary: RgbPrimary<T>,
         blue_primary: RgbPrimary<T>,
         white_point: Xyz<T>,
-    ) -> Matrix3<T> {
+    ) -> Option<Matrix3<T>> {
         let (rx, ry, rz) = Self::calc_transform_vector(red_primary.to_tuple());
         let (gx, gy, gz) = Self::calc_transform_vector(green_primary.to_tuple());
         let (bx, by, bz) = Self::calc_transform_vector(blue_primary.to_tuple());
 
         let primary_transform = Matrix3::new([rx, gx, bx, ry, gy, by, rz, gz, bz]);
-        let inv_transform = primary_transform.clone().inverse().unwrap();
+        let inv_transform = primary_transform.try_inverse()?;
 
         let (sr, sg, sb) = inv_transform.transform_vector(white_point.to_tuple());
 
-        Matrix3::new([
+        Some(Matrix3::new([
             sr * rx,
             sg * gx,
             sb * bx,
@@ -206,7 +270,7 @@ where
             sr * rz,
             sg * gz,
             sb * bz,
-        ])
+        ]))
     }
 
     fn calc_transform_vector(primary_vec: (T, T)) -> (T, T, T) {
@@ -476,22 +540,20 @@ where
         Rgb::new(r, g, b)
     }
 }
-/*
-impl<T, E> ConvertFromXyz<T, Rgba<T>> for EncodedColorSpace<T, E>
-    where
-        T: PosNormalChannelScalar + FreeChannelScalar + ChannelFormatCast<f64>,
-        f64: ChannelFormatCast<T>,
-        E: ColorEncoding + PartialEq + Clone,
+impl<T, E> ConvertFromXyz<T, Xyza<T>> for EncodedColorSpace<T, E>
+where
+    T: PosNormalChannelScalar + FreeChannelScalar + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+    E: ColorEncoding + PartialEq + Clone,
 {
-    type InputColor = Xyza<T>;
-    fn convert_from_xyz_raw(&self, color: &Self::InputColor) -> Rgba<T> {
+    type OutputColor = Rgba<T>;
+    fn convert_from_xyz_raw(&self, color: &Xyza<T>) -> Rgba<T> {
         let (r, g, b) = self
             .get_inverse_xyz_transform()
             .transform_vector(color.color().clone().to_tuple());
         Rgba::new(Rgb::new(r, g, b), color.alpha())
     }
 }
-*/
 
 #[cfg(test)]
 mod test {
@@ -499,7 +561,7 @@ mod test {
     use crate::color::*;
     use crate::color_space::named::*;
     use crate::color_space::primary::RgbPrimary;
-    use crate::color_space::WithColorSpace;
+    use crate::color_space::{UnitColorSpace, WithColorSpace};
     use crate::encoding::*;
     use crate::linalg::Matrix3;
     use crate::rgb::Rgb;
@@ -643,6 +705,35 @@ mod test {
         assert_relative_eq!(srgb.convert_to_xyz(&r6), c6, epsilon = 1e-6);
     }
 
+    #[test]
+    fn test_rgba_round_trip() {
+        let srgb = SRgb::<f64>::build_color_space_instance();
+        let linear_srgb = srgb.with_encoding(LinearEncoding::new());
+
+        let r1 = Rgba::new(Rgb::new(0.25, 0.55, 0.89), 0.5).srgb_encoded();
+        let c1 = srgb.convert_to_xyz(&r1);
+        assert_relative_eq!(
+            c1,
+            Xyza::new(Xyz::new(0.253659, 0.254514, 0.761978), 0.5),
+            epsilon = 1e-6
+        );
+        let back = srgb.convert_from_xyz(&c1);
+        assert_relative_eq!(back.color(), &r1, epsilon = 1e-6);
+
+        let linear = linear_srgb.convert_from_xyz(&c1);
+        assert_relative_eq!(linear.color(), &r1.clone().decode(), epsilon = 1e-6);
+        assert_relative_eq!(
+            srgb.convert_from_xyz_raw(&c1),
+            linear.clone().strip(),
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(
+            srgb.convert_from_xyz_linear(&c1).strip_space(),
+            linear.strip_space(),
+            epsilon = 1e-6
+        );
+    }
+
     #[test]
     fn test_build_transform() {
         let space = LinearColorSpace::new_linear_color_space(
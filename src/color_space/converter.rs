@@ -0,0 +1,324 @@
+//! Defines `Converter`, a reusable pipeline converting encoded `Rgb` colors between color spaces
+
+use crate::buffer;
+use crate::channel::{FreeChannelScalar, PosNormalChannelScalar};
+use crate::color_space::{ColorSpace, LinearPipeline};
+use crate::encoding::ColorEncoding;
+use crate::gamut::{GamutMapping, Preserve};
+use crate::linalg::Matrix3;
+use crate::lms::{chromatic_adaptation_transform, Bradford, LmsModel};
+use crate::rgb::Rgb;
+use crate::xyz::Xyz;
+use num_traits;
+
+/// A function computing a chromatic adaptation matrix between two white points
+type AdaptationFn<T> = fn(&Xyz<T>, &Xyz<T>) -> Matrix3<T>;
+
+/// Converts encoded `Rgb` colors from one color space into another with fixed settings
+///
+/// A `Converter` is built once with a [`ConverterBuilder`](struct.ConverterBuilder.html), which
+/// chooses the encoding of the input and output colors, the chromatic adaptation transform and the
+/// gamut mapping used for colors that fall outside of the destination space. Everything that
+/// can be computed ahead of time is, so converting a color only decodes it, applies a single
+/// matrix, maps it into gamut and encodes it again.
+///
+/// By default the encodings are those of the two spaces, colors are adapted with the Bradford
+/// model and out of gamut colors are left as they are.
+///
+/// ## Examples:
+///
+/// ```rust
+/// #[macro_use] extern crate approx;
+/// # extern crate prisma;
+///
+/// use prisma::Rgb;
+/// use prisma::cat::Cat16;
+/// use prisma::color_space::Converter;
+/// use prisma::color_space::named::{ProPhotoRgb, SRgb};
+/// use prisma::encoding::LinearEncoding;
+/// use prisma::gamut::Clip;
+///
+/// let converter = Converter::builder(ProPhotoRgb::new(), SRgb::new())
+///     .source_encoding(LinearEncoding::new())
+///     .adaptation::<Cat16>()
+///     .gamut_mapping(Clip)
+///     .build();
+///
+/// let pixels = vec![Rgb::new(1.0, 1.0, 1.0), Rgb::new(0.0, 1.0, 0.0)];
+/// let converted = converter.convert_slice(&pixels);
+/// assert_relative_eq!(converted[0], Rgb::new(1.0, 1.0, 1.0), epsilon=1e-4);
+/// assert_eq!(converted[1], converter.convert(&pixels[1]));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Converter<T, S, D, SE, DE, M> {
+    source: S,
+    dest: D,
+    source_encoding: SE,
+    dest_encoding: DE,
+    pipeline: LinearPipeline<T>,
+    mapping: M,
+}
+
+/// Configures and builds a [`Converter`](struct.Converter.html)
+///
+/// Returned by [`Converter::builder`](struct.Converter.html#method.builder).
+#[derive(Clone, Debug)]
+pub struct ConverterBuilder<T, S, D, SE, DE, M> {
+    source: S,
+    dest: D,
+    source_encoding: SE,
+    dest_encoding: DE,
+    adaptation: AdaptationFn<T>,
+    mapping: M,
+}
+
+impl<T, S, D> Converter<T, S, D, S::Encoding, D::Encoding, Preserve>
+where
+    T: num_traits::Float + FreeChannelScalar + PosNormalChannelScalar,
+    S: ColorSpace<T>,
+    D: ColorSpace<T>,
+{
+    /// Start building a `Converter` from `source` to `dest`
+    pub fn builder(
+        source: S,
+        dest: D,
+    ) -> ConverterBuilder<T, S, D, S::Encoding, D::Encoding, Preserve> {
+        ConverterBuilder {
+            source_encoding: source.encoding(),
+            dest_encoding: dest.encoding(),
+            source,
+            dest,
+            adaptation: chromatic_adaptation_transform::<T, Bradford>,
+            mapping: Preserve,
+        }
+    }
+}
+
+impl<T, S, D, SE, DE, M> ConverterBuilder<T, S, D, SE, DE, M>
+where
+    T: num_traits::Float + FreeChannelScalar + PosNormalChannelScalar,
+    S: ColorSpace<T>,
+    D: ColorSpace<T>,
+    SE: ColorEncoding,
+    DE: ColorEncoding,
+    M: GamutMapping<T>,
+{
+    /// Set the encoding of the colors passed to the converter
+    pub fn source_encoding<E>(self, encoding: E) -> ConverterBuilder<T, S, D, E, DE, M>
+    where
+        E: ColorEncoding,
+    {
+        ConverterBuilder {
+            source: self.source,
+            dest: self.dest,
+            source_encoding: encoding,
+            dest_encoding: self.dest_encoding,
+            adaptation: self.adaptation,
+            mapping: self.mapping,
+        }
+    }
+    /// Set the encoding of the colors returned by the converter
+    pub fn dest_encoding<E>(self, encoding: E) -> ConverterBuilder<T, S, D, SE, E, M>
+    where
+        E: ColorEncoding,
+    {
+        ConverterBuilder {
+            source: self.source,
+            dest: self.dest,
+            source_encoding: self.source_encoding,
+            dest_encoding: encoding,
+            adaptation: self.adaptation,
+            mapping: self.mapping,
+        }
+    }
+    /// Adapt between the white points of the two spaces with `Model`
+    pub fn adaptation<Model>(mut self) -> Self
+    where
+        Model: LmsModel<T>,
+    {
+        self.adaptation = chromatic_adaptation_transform::<T, Model>;
+        self
+    }
+    /// Map colors outside of the destination space's gamut with `mapping`
+    pub fn gamut_mapping<N>(self, mapping: N) -> ConverterBuilder<T, S, D, SE, DE, N>
+    where
+        N: GamutMapping<T>,
+    {
+        ConverterBuilder {
+            source: self.source,
+            dest: self.dest,
+            source_encoding: self.source_encoding,
+            dest_encoding: self.dest_encoding,
+            adaptation: self.adaptation,
+            mapping,
+        }
+    }
+
+    /// Build the `Converter`, computing its combined transform
+    pub fn build(self) -> Converter<T, S, D, SE, DE, M> {
+        let source_white = self.source.white_point();
+        let dest_white = self.dest.white_point();
+        let mut pipeline = LinearPipeline::new().rgb_to_xyz(&self.source);
+        if source_white != dest_white {
+            pipeline = pipeline.then((self.adaptation)(&source_white, &dest_white));
+        }
+        let pipeline = pipeline.xyz_to_rgb(&self.dest);
+
+        Converter {
+            source: self.source,
+            dest: self.dest,
+            source_encoding: self.source_encoding,
+            dest_encoding: self.dest_encoding,
+            pipeline,
+            mapping: self.mapping,
+        }
+    }
+}
+
+impl<T, S, D, SE, DE, M> Converter<T, S, D, SE, DE, M>
+where
+    T: num_traits::Float + FreeChannelScalar + PosNormalChannelScalar,
+    S: ColorSpace<T>,
+    D: ColorSpace<T>,
+    SE: ColorEncoding,
+    DE: ColorEncoding,
+    M: GamutMapping<T>,
+{
+    /// Returns a reference to the source color space
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+    /// Returns a reference to the destination color space
+    pub fn dest(&self) -> &D {
+        &self.dest
+    }
+    /// Returns a reference to the encoding of the input colors
+    pub fn source_encoding(&self) -> &SE {
+        &self.source_encoding
+    }
+    /// Returns a reference to the encoding of the output colors
+    pub fn dest_encoding(&self) -> &DE {
+        &self.dest_encoding
+    }
+    /// Returns the linear steps from the source space to the destination space
+    pub fn pipeline(&self) -> &LinearPipeline<T> {
+        &self.pipeline
+    }
+    /// Returns a reference to the gamut mapping used in the destination space
+    pub fn mapping(&self) -> &M {
+        &self.mapping
+    }
+
+    /// Convert a color from the source space and encoding to the destination space and encoding
+    pub fn convert(&self, color: &Rgb<T>) -> Rgb<T> {
+        let decode = |c: T| self.source_encoding.decode_channel(c);
+        let linear = Rgb::new(
+            decode(color.red()),
+            decode(color.green()),
+            decode(color.blue()),
+        );
+        let mapped = self
+            .mapping
+            .map_linear(&self.dest, &self.pipeline.apply(&linear));
+        let encode = |c: T| self.dest_encoding.encode_channel(c);
+        Rgb::new(
+            encode(mapped.red()),
+            encode(mapped.green()),
+            encode(mapped.blue()),
+        )
+    }
+
+    /// Convert each color in `colors`, collecting the results
+    pub fn convert_slice(&self, colors: &[Rgb<T>]) -> Vec<Rgb<T>> {
+        buffer::map(colors, |color| self.convert(color))
+    }
+
+    /// Convert each color in `src`, writing the results to `dst`
+    ///
+    /// # Panics
+    /// If `src` and `dst` have different lengths.
+    pub fn convert_slice_into(&self, src: &[Rgb<T>], dst: &mut [Rgb<T>]) {
+        buffer::map_into(src, dst, |color| self.convert(color))
+    }
+
+    /// Convert each color in `colors` in parallel, collecting the results
+    #[cfg(feature = "rayon")]
+    pub fn par_convert_slice(&self, colors: &[Rgb<T>]) -> Vec<Rgb<T>>
+    where
+        Self: Sync,
+        T: Send + Sync,
+    {
+        buffer::par_map(colors, |color| self.convert(color))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cat::Cat16;
+    use crate::color_space::named::{ProPhotoRgb, Rec2020, SRgb};
+    use crate::color_space::SpaceConverter;
+    use crate::encoding::{EncodableColor, LinearEncoding, SrgbEncoding};
+    use crate::gamut::Clip;
+    use approx::*;
+
+    #[test]
+    fn test_defaults() {
+        let converter = Converter::builder(ProPhotoRgb::<f64>::new(), SRgb::new()).build();
+        let reference = SpaceConverter::new(ProPhotoRgb::new(), SRgb::new());
+        assert_relative_eq!(
+            *converter.pipeline().matrix(),
+            *reference.transform(),
+            epsilon = 1e-12
+        );
+
+        let colors = [Rgb::new(0.2, 0.5, 0.7), Rgb::new(0.9, 0.1, 0.3)];
+        for color in colors.iter() {
+            let expected =
+                reference.convert(&color.encoded_as(ProPhotoRgb::<f64>::new().encoding()));
+            assert_relative_eq!(
+                converter.convert(color),
+                *expected.color().color(),
+                epsilon = 1e-9
+            );
+        }
+    }
+
+    #[test]
+    fn test_options() {
+        let converter = Converter::builder(Rec2020::<f64>::new(), SRgb::new())
+            .source_encoding(LinearEncoding::new())
+            .dest_encoding(LinearEncoding::new())
+            .gamut_mapping(Clip)
+            .build();
+        assert_eq!(*converter.source_encoding(), LinearEncoding::new());
+        let green = converter.convert(&Rgb::new(0.0, 1.0, 0.0));
+        assert_eq!(green.green(), 1.0);
+        assert_eq!(green.red(), 0.0);
+        assert_eq!(green.blue(), 0.0);
+
+        let unmapped = Converter::builder(Rec2020::<f64>::new(), SRgb::new())
+            .source_encoding(LinearEncoding::new())
+            .dest_encoding(LinearEncoding::new())
+            .build();
+        assert!(unmapped.convert(&Rgb::new(0.0, 1.0, 0.0)).red() < 0.0);
+
+        let bradford = Converter::builder(ProPhotoRgb::<f64>::new(), SRgb::new()).build();
+        let cat16 = Converter::builder(ProPhotoRgb::<f64>::new(), SRgb::new())
+            .adaptation::<Cat16>()
+            .build();
+        assert_ne!(bradford.pipeline(), cat16.pipeline());
+        assert_eq!(*cat16.dest_encoding(), SrgbEncoding::new());
+    }
+
+    #[test]
+    fn test_slices() {
+        let converter = Converter::builder(SRgb::<f64>::new(), Rec2020::new()).build();
+        let pixels = vec![Rgb::new(0.1, 0.2, 0.3), Rgb::new(1.0, 0.5, 0.0)];
+        let converted = converter.convert_slice(&pixels);
+        let mut into = vec![Rgb::new(0.0, 0.0, 0.0); 2];
+        converter.convert_slice_into(&pixels, &mut into);
+        assert_eq!(converted, into);
+        assert_eq!(converted[1], converter.convert(&pixels[1]));
+    }
+}
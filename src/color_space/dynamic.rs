@@ -0,0 +1,205 @@
+use std::sync::Arc;
+
+use crate::channel::{FreeChannelScalar, PosNormalChannelScalar};
+use crate::color::Color;
+use crate::encoding::{ChannelDecoder, ChannelEncoder, ColorEncoding, SharedEncoding};
+use crate::linalg::Matrix3;
+use crate::rgb::Rgb;
+use crate::xyz::Xyz;
+use num_traits;
+
+use super::color_space::{ColorSpace, EncodedColorSpace};
+use super::primary::RgbPrimary;
+
+/// An object safe form of `ColorSpace`
+///
+/// `ColorSpace` has an associated encoding type and generic methods, so every space with a
+/// different encoding is a different type and none of them can be used as a trait object.
+/// `DynColorSpace` is implemented for every `ColorSpace` and hides the encoding, so spaces chosen
+/// at runtime can be stored as `Box<dyn DynColorSpace<T>>` and kept together in collections.
+///
+/// `Rgb` colors passed to and returned from the conversion methods are in the space's own
+/// encoding.
+///
+/// ## Examples:
+///
+/// ```rust
+/// #[macro_use] extern crate approx;
+/// # extern crate prisma;
+///
+/// use prisma::{Rgb, Xyz};
+/// use prisma::color_space::DynColorSpace;
+/// use prisma::color_space::named::{AdobeRgb, DisplayP3, SRgb};
+///
+/// let spaces: Vec<Box<dyn DynColorSpace<f64>>> = vec![
+///     Box::new(SRgb::new()),
+///     Box::new(AdobeRgb::new()),
+///     Box::new(DisplayP3::new()),
+/// ];
+///
+/// let white = Rgb::new(1.0, 1.0, 1.0);
+/// for space in spaces.iter() {
+///     let xyz = space.rgb_to_xyz(&white);
+///     assert_relative_eq!(xyz, space.reference_white(), epsilon=1e-4);
+///     assert_relative_eq!(space.xyz_to_rgb(&xyz), white, epsilon=1e-4);
+/// }
+/// ```
+pub trait DynColorSpace<T>
+where
+    T: num_traits::Float + FreeChannelScalar + PosNormalChannelScalar,
+{
+    /// Returns the red, green and blue primaries of the color space
+    fn primaries(&self) -> [RgbPrimary<T>; 3];
+    /// Returns the white point of the color space
+    fn reference_white(&self) -> Xyz<T>;
+    /// Returns the linear RGB -> XYZ matrix
+    fn rgb_to_xyz_matrix(&self) -> Matrix3<T>;
+    /// Returns the XYZ -> linear RGB matrix
+    fn xyz_to_rgb_matrix(&self) -> Matrix3<T>;
+    /// Returns the encoding of the color space
+    fn shared_encoding(&self) -> SharedEncoding;
+
+    /// Encode a linear `Rgb` color with the encoding of the color space
+    fn encode_rgb(&self, color: &Rgb<T>) -> Rgb<T>;
+    /// Decode an `Rgb` color in the encoding of the color space to a linear encoding
+    fn decode_rgb(&self, color: &Rgb<T>) -> Rgb<T>;
+
+    /// Convert an `Rgb` color in the encoding of the color space to `Xyz`
+    fn rgb_to_xyz(&self, color: &Rgb<T>) -> Xyz<T> {
+        let (x, y, z) = self.rgb_to_xyz_matrix() * self.decode_rgb(color).to_tuple();
+        Xyz::new(x, y, z)
+    }
+    /// Convert an `Xyz` color to `Rgb`, in the encoding of the color space
+    fn xyz_to_rgb(&self, color: &Xyz<T>) -> Rgb<T> {
+        let (r, g, b) = self.xyz_to_rgb_matrix() * color.clone().to_tuple();
+        self.encode_rgb(&Rgb::new(r, g, b))
+    }
+}
+
+impl<T, S> DynColorSpace<T> for S
+where
+    T: num_traits::Float + FreeChannelScalar + PosNormalChannelScalar,
+    S: ColorSpace<T>,
+    S::Encoding: Send + Sync + 'static,
+{
+    fn primaries(&self) -> [RgbPrimary<T>; 3] {
+        [
+            self.red_primary(),
+            self.green_primary(),
+            self.blue_primary(),
+        ]
+    }
+    fn reference_white(&self) -> Xyz<T> {
+        self.white_point()
+    }
+    fn rgb_to_xyz_matrix(&self) -> Matrix3<T> {
+        self.get_xyz_transform()
+    }
+    fn xyz_to_rgb_matrix(&self) -> Matrix3<T> {
+        self.get_inverse_xyz_transform()
+    }
+    fn shared_encoding(&self) -> SharedEncoding {
+        Arc::new(self.encoding())
+    }
+
+    fn encode_rgb(&self, color: &Rgb<T>) -> Rgb<T> {
+        let encoding = self.encoding();
+        Rgb::new(
+            encoding.encode_channel(color.red()),
+            encoding.encode_channel(color.green()),
+            encoding.encode_channel(color.blue()),
+        )
+    }
+    fn decode_rgb(&self, color: &Rgb<T>) -> Rgb<T> {
+        let encoding = self.encoding();
+        Rgb::new(
+            encoding.decode_channel(color.red()),
+            encoding.decode_channel(color.green()),
+            encoding.decode_channel(color.blue()),
+        )
+    }
+}
+
+impl<T, E> EncodedColorSpace<T, E>
+where
+    T: num_traits::Float + FreeChannelScalar + PosNormalChannelScalar,
+    E: ColorEncoding + Send + Sync + 'static,
+{
+    /// Returns a copy of this `EncodedColorSpace` with its encoding behind a `SharedEncoding`
+    ///
+    /// Spaces with different encodings all become `EncodedColorSpace<T, SharedEncoding>`, so they
+    /// can be stored together while keeping the full `ColorSpace` interface.
+    pub fn with_shared_encoding(&self) -> EncodedColorSpace<T, SharedEncoding> {
+        let encoding: SharedEncoding = Arc::new(self.encoding().clone());
+        self.with_encoding(encoding)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::color_space::named::{AdobeRgb, ProPhotoRgb, SRgb};
+    use crate::color_space::{ConvertToXyz, UnitColorSpace};
+    use crate::encoding::{
+        DynColorEncoding, EncodableColor, GammaEncoding, LinearEncoding, SrgbEncoding,
+    };
+    use approx::*;
+
+    #[test]
+    fn test_dyn_color_space() {
+        let gamma = AdobeRgb::<f64>::build_color_space_instance().with_encoding(GammaEncoding(2.2));
+        let spaces: Vec<Box<dyn DynColorSpace<f64>>> = vec![
+            Box::new(SRgb::new()),
+            Box::new(ProPhotoRgb::new()),
+            Box::new(SRgb::<f64>::build_color_space_instance().with_encoding(LinearEncoding)),
+            Box::new(gamma.with_shared_encoding()),
+        ];
+
+        let color = Rgb::new(0.2, 0.6, 0.9);
+        let expected = SRgb::new().convert_to_xyz(&color.encoded_as(SrgbEncoding));
+        assert_relative_eq!(spaces[0].rgb_to_xyz(&color), expected, epsilon = 1e-9);
+        let expected = gamma.convert_to_xyz(&color.encoded_as(GammaEncoding(2.2)));
+        assert_relative_eq!(spaces[3].rgb_to_xyz(&color), expected, epsilon = 1e-9);
+
+        for space in spaces.iter() {
+            let back = space.xyz_to_rgb(&space.rgb_to_xyz(&color));
+            assert_relative_eq!(back, color, epsilon = 1e-9);
+            let encoding = space.shared_encoding();
+            assert_relative_eq!(
+                encoding.decode_f64(encoding.encode_f64(0.3)),
+                0.3,
+                epsilon = 1e-12
+            );
+        }
+        assert_relative_eq!(spaces[2].encode_rgb(&color), color);
+        assert_eq!(spaces[1].primaries()[0], ProPhotoRgb::new().red_primary());
+    }
+
+    #[test]
+    fn test_shared_encoding() {
+        let encodings: Vec<SharedEncoding> = vec![
+            Arc::new(SrgbEncoding),
+            Arc::new(LinearEncoding),
+            Arc::new(GammaEncoding(2.2)),
+        ];
+        assert_relative_eq!(
+            encodings[0].encode_channel(0.5f32),
+            0.735357,
+            epsilon = 1e-6
+        );
+        assert_eq!(encodings[1].decode_channel(0.5f32), 0.5);
+        assert_relative_eq!(
+            encodings[2].decode_channel(0.5f64),
+            GammaEncoding(2.2).decode_channel(0.5f64)
+        );
+
+        let linear = Rgb::new(0.25f32, 0.5, 1.0).linear();
+        let encoded = linear.clone().encode(encodings[0].clone());
+        assert_relative_eq!(
+            encoded.clone().strip_encoding(),
+            linear.clone().encode(SrgbEncoding).strip_encoding(),
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(encoded.decode(), linear, epsilon = 1e-6);
+    }
+}
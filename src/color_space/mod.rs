@@ -1,15 +1,23 @@
 //! Traits and structures to define color spaces and convert from device-dependent to device-independent spaces
 
 mod color_space;
+mod converter;
+mod dynamic;
 /// Named built-in color spaces
 pub mod named;
+mod pipeline;
 mod primary;
+mod space_converter;
 mod spaced_color;
 
 pub use self::color_space::{
     ColorSpace, ConvertFromXyz, ConvertToXyz, EncodedColorSpace, LinearColorSpace,
 };
+pub use self::converter::{Converter, ConverterBuilder};
+pub use self::dynamic::DynColorSpace;
+pub use self::pipeline::LinearPipeline;
 pub use self::primary::RgbPrimary;
+pub use self::space_converter::{convert_space, ConversionTrace, SpaceConverter};
 pub use self::spaced_color::SpacedColor;
 use crate::encoding::{ColorEncoding, EncodableColor};
 use num_traits;
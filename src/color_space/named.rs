@@ -8,10 +8,13 @@ use crate::alpha::{Rgba, Xyza};
 use crate::channel::{ChannelFormatCast, FreeChannelScalar, PosNormalChannelScalar};
 use crate::color::Color;
 use crate::color_space::{ColorSpace, EncodedColorSpace, RgbPrimary};
-use crate::encoding::{ColorEncoding, EncodedColor, SrgbEncoding};
+use crate::encoding::{
+    AdobeRgbEncoding, ColorEncoding, EncodedColor, LinearEncoding, Rec2020Encoding,
+    Rec709Encoding, RommEncoding, SrgbEncoding,
+};
 use crate::linalg::Matrix3;
 use crate::rgb::Rgb;
-use crate::white_point::{WhitePoint, D65};
+use crate::white_point::{Aces, WhitePoint, D50, D65};
 use crate::xyz::Xyz;
 use num_traits;
 use num_traits::cast;
@@ -33,12 +36,193 @@ impl<T> SRgb<T> {
     }
 }
 
+/// The scRGB color space, a linear extended-range form of sRGB
+///
+/// scRGB shares the primaries and white point of sRGB, but is linearly encoded and gives meaning to
+/// channels outside of [0, 1]. Negative channels describe colors outside the sRGB gamut, and
+/// channels above one describe highlights brighter than the reference white of 80 cd/m², so any
+/// visible color of any brightness can be stored. It is meant for floating point HDR and wide
+/// gamut intermediate math; no conversion into or out of it clips the channels. The functions of
+/// the [`gamut`](../../gamut/index.html) module detect and clip out of range values when the
+/// color needs to be displayed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct ScRgb<T> {
+    _marker: PhantomData<T>,
+}
+
+impl<T> ScRgb<T> {
+    /// Construct a new ScRgb instance
+    pub fn new() -> ScRgb<T> {
+        ScRgb {
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// The Adobe RGB (1998) color space
+///
+/// Adobe RGB has a wider green primary than sRGB, covering most of the colors reproducible by
+/// CMYK printing.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct AdobeRgb<T> {
+    _marker: PhantomData<T>,
+}
+
+impl<T> AdobeRgb<T> {
+    /// Construct a new AdobeRgb instance
+    pub fn new() -> AdobeRgb<T> {
+        AdobeRgb {
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// The Display P3 color space
+///
+/// Display P3 uses the primaries of DCI-P3 with the D65 white point and sRGB encoding. It is the
+/// wide gamut space of most modern displays and of CSS's `display-p3`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct DisplayP3<T> {
+    _marker: PhantomData<T>,
+}
+
+impl<T> DisplayP3<T> {
+    /// Construct a new DisplayP3 instance
+    pub fn new() -> DisplayP3<T> {
+        DisplayP3 {
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// The ITU-R BT.601 color space for 625-line (PAL and SECAM) standard definition video
+///
+/// The primaries are those of EBU Tech. 3213. Standard definition video stored as `YCbCr` should
+/// be converted to `Rgb` and then decoded with this space, rather than assuming sRGB primaries.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct Rec601_625<T> {
+    _marker: PhantomData<T>,
+}
+
+impl<T> Rec601_625<T> {
+    /// Construct a new Rec601_625 instance
+    pub fn new() -> Rec601_625<T> {
+        Rec601_625 {
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// The ITU-R BT.601 color space for 525-line (NTSC) standard definition video
+///
+/// The primaries are those of SMPTE 170M, also known as SMPTE C.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct Rec601_525<T> {
+    _marker: PhantomData<T>,
+}
+
+impl<T> Rec601_525<T> {
+    /// Construct a new Rec601_525 instance
+    pub fn new() -> Rec601_525<T> {
+        Rec601_525 {
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// The ITU-R BT.2020 color space used for ultra high definition television
+///
+/// `Rec2020` uses the standard dynamic range transfer function by default. HDR content encoded
+/// with PQ or HLG can be handled by swapping the encoding on a built instance, for example
+/// `Rec2020::build_color_space_instance().with_encoding(PqEncoding::new())`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct Rec2020<T> {
+    _marker: PhantomData<T>,
+}
+
+impl<T> Rec2020<T> {
+    /// Construct a new Rec2020 instance
+    pub fn new() -> Rec2020<T> {
+        Rec2020 {
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// The ProPhoto RGB (ROMM RGB) color space
+///
+/// ProPhoto RGB has very wide primaries and is commonly used as a working space for photo editing.
+/// Unlike most RGB spaces it uses the D50 white point, so XYZ values produced by `ProPhotoRgb`
+/// must be chromatically adapted before being used with a D65 space.
+/// [`convert_to_xyz_adapted`](../trait.ConvertToXyz.html#method.convert_to_xyz_adapted) converts
+/// colors into XYZ relative to another white point, and
+/// [`get_adapted_xyz_transform`](../trait.ColorSpace.html#method.get_adapted_xyz_transform) builds
+/// the matrix that does so.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct ProPhotoRgb<T> {
+    _marker: PhantomData<T>,
+}
+
+impl<T> ProPhotoRgb<T> {
+    /// Construct a new ProPhotoRgb instance
+    pub fn new() -> ProPhotoRgb<T> {
+        ProPhotoRgb {
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// The ACES2065-1 color space, using the AP0 primaries
+///
+/// ACES2065-1 is the linear interchange space of the Academy Color Encoding System. Its primaries
+/// enclose the entire spectral locus, so any visible color can be stored with positive values.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct Aces2065_1<T> {
+    _marker: PhantomData<T>,
+}
+
+impl<T> Aces2065_1<T> {
+    /// Construct a new Aces2065_1 instance
+    pub fn new() -> Aces2065_1<T> {
+        Aces2065_1 {
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// The ACEScg color space, using the AP1 primaries
+///
+/// ACEScg is the linear working space recommended by ACES for rendering and compositing. The AP1
+/// primaries are close to those of Rec. 2020 while sharing the ACES white point with ACES2065-1.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct AcesCg<T> {
+    _marker: PhantomData<T>,
+}
+
+impl<T> AcesCg<T> {
+    /// Construct a new AcesCg instance
+    pub fn new() -> AcesCg<T> {
+        AcesCg {
+            _marker: PhantomData,
+        }
+    }
+}
+
 /// Use this macro to easily implement a new color space. You need the primaries, white point and
 /// precomputed forward and backward transformation matrices.
+///
+/// The matrices are exposed as the `XYZ_MATRIX` and `INVERSE_XYZ_MATRIX` constants, so the known
+/// spaces never need to invert a matrix at runtime.
 macro_rules! impl_known_color_space {
     ($name:ident primaries=(($rx:expr, $ry:expr), ($gx:expr, $gy:expr), ($bx:expr, $by:expr)),
         wp=$wp:expr, enc=$enc:ident, mat=[$($m:expr),*], mat_inv=[$($m_inv:expr),*]) =>
     {
+        impl<T> $name<T> {
+            /// The linear RGB -> XYZ matrix, in row major order
+            pub const XYZ_MATRIX: [f64; 9] = [$($m),*];
+            /// The XYZ -> linear RGB matrix, in row major order
+            pub const INVERSE_XYZ_MATRIX: [f64; 9] = [$($m_inv),*];
+        }
         impl<T> ColorSpace<T> for $name<T>
             where T: num_traits::Float + FreeChannelScalar + PosNormalChannelScalar
         {
@@ -56,10 +240,20 @@ macro_rules! impl_known_color_space {
                 $wp.get_xyz()
             }
             fn get_xyz_transform(&self) -> Matrix3<T> {
-                Matrix3::new([$(cast($m).unwrap()),*])
+                let m = Self::XYZ_MATRIX;
+                Matrix3::new([
+                    cast(m[0]).unwrap(), cast(m[1]).unwrap(), cast(m[2]).unwrap(),
+                    cast(m[3]).unwrap(), cast(m[4]).unwrap(), cast(m[5]).unwrap(),
+                    cast(m[6]).unwrap(), cast(m[7]).unwrap(), cast(m[8]).unwrap(),
+                ])
             }
             fn get_inverse_xyz_transform(&self) -> Matrix3<T> {
-                Matrix3::new([$(cast($m_inv).unwrap()),*])
+                let m = Self::INVERSE_XYZ_MATRIX;
+                Matrix3::new([
+                    cast(m[0]).unwrap(), cast(m[1]).unwrap(), cast(m[2]).unwrap(),
+                    cast(m[3]).unwrap(), cast(m[4]).unwrap(), cast(m[5]).unwrap(),
+                    cast(m[6]).unwrap(), cast(m[7]).unwrap(), cast(m[8]).unwrap(),
+                ])
             }
             fn encoding(&self) -> Self::Encoding {
                 Self::Encoding::default()
@@ -139,3 +333,324 @@ impl_known_color_space!(SRgb
     mat=[0.41245643908969226, 0.3575760776439089, 0.1804374832663989, 0.21267285140562256, 0.7151521552878178, 0.07217499330655956, 0.019333895582329303, 0.11919202588130294, 0.9503040785363677],
     mat_inv=[3.2404541621141036, -1.537138512797716, -0.49853140955601594, -0.9692660305051867, 1.8760108454466942, 0.04155601753034982, 0.05564343095911471, -0.20402591351675378, 1.0572251882231791]
 );
+
+impl_known_color_space!(ScRgb
+    primaries=((0.6400, 0.3300), (0.300, 0.600), (0.150, 0.060)),
+    wp=D65,
+    enc=LinearEncoding,
+    mat=[0.41245643908969226, 0.3575760776439089, 0.1804374832663989, 0.21267285140562256, 0.7151521552878178, 0.07217499330655956, 0.019333895582329303, 0.11919202588130294, 0.9503040785363677],
+    mat_inv=[3.2404541621141036, -1.537138512797716, -0.49853140955601594, -0.9692660305051867, 1.8760108454466942, 0.04155601753034982, 0.05564343095911471, -0.20402591351675378, 1.0572251882231791]
+);
+
+impl_known_color_space!(AdobeRgb
+    primaries=((0.6400, 0.3300), (0.2100, 0.7100), (0.1500, 0.0600)),
+    wp=D65,
+    enc=AdobeRgbEncoding,
+    mat=[0.5767308871981476, 0.185553950711214, 0.18818516209063835, 0.2973768637115448, 0.6273490714521998, 0.07527406483625534, 0.027034260337413137, 0.07068721931855779, 0.9911085203440287],
+    mat_inv=[2.04136897926008, -0.5649463871751956, -0.3446943843778484, -0.9692660305051864, 1.8760108454466942, 0.041556017530349806, 0.013447387216170281, -0.11838974235412562, 1.015409571950417]
+);
+
+impl_known_color_space!(DisplayP3
+    primaries=((0.6800, 0.3200), (0.2650, 0.6900), (0.1500, 0.0600)),
+    wp=D65,
+    enc=SrgbEncoding,
+    mat=[0.48663265000000006, 0.2656631625, 0.19817418749999996, 0.22900360000000003, 0.6917267249999999, 0.07926967499999998, 0.0, 0.04511261250000004, 1.0437173874999999],
+    mat_inv=[2.4931807553289667, -0.9312655254971399, -0.40265972375888176, -0.829503115821079, 1.762694121119793, 0.02362508874173959, 0.03585362578007171, -0.07618895478265221, 0.9570926215180217]
+);
+
+impl_known_color_space!(Rec601_625
+    primaries=((0.640, 0.330), (0.290, 0.600), (0.150, 0.060)),
+    wp=D65,
+    enc=Rec709Encoding,
+    mat=[0.4306190335097003, 0.34154191225749564, 0.17830905423280421, 0.22203793915343922, 0.7066384391534393, 0.07132362169312169, 0.02018526719576718, 0.12955038051146386, 0.9390943522927689],
+    mat_inv=[3.062897123222695, -1.3931791364936779, -0.4757516712579539, -0.9692660305051863, 1.8760108454466933, 0.0415560175303498, 0.06787750995175175, -0.2288547739903322, 1.069348968256285]
+);
+
+impl_known_color_space!(Rec601_525
+    primaries=((0.630, 0.340), (0.310, 0.595), (0.155, 0.070)),
+    wp=D65,
+    enc=Rec709Encoding,
+    mat=[0.3935890809541021, 0.365249655704132, 0.19163126334176603, 0.21241315480062656, 0.7010436940127694, 0.08654315118660402, 0.018742337188290558, 0.11193134610287912, 0.9581563167088301],
+    mat_inv=[3.505395974670056, -1.7394893606633242, -0.543964026874098, -1.0690722072799321, 1.977824481410004, 0.035172230231857, 0.05632001476714689, -0.19702261221309847, 1.0502026283050325]
+);
+
+impl_known_color_space!(Rec2020
+    primaries=((0.708, 0.292), (0.170, 0.797), (0.131, 0.046)),
+    wp=D65,
+    enc=Rec2020Encoding,
+    mat=[0.637010191411101, 0.1446150273969693, 0.1688447811919299, 0.26272171736164057, 0.677989275502262, 0.05928900713609751, 0.0, 0.02807232884764691, 1.0607576711523532],
+    mat_inv=[1.7165106697619732, -0.3556416699867158, -0.25334554182190716, -0.6666930011826242, 1.6165022083469105, 0.015768750389995007, 0.01764363876745901, -0.04277978166904462, 0.9423050727200184]
+);
+
+impl_known_color_space!(ProPhotoRgb
+    primaries=((0.7347, 0.2653), (0.1596, 0.8404), (0.0366, 0.0001)),
+    wp=D50,
+    enc=RommEncoding,
+    mat=[0.7976749444306044, 0.13519170147409815, 0.031353354095297416, 0.2880402378623102, 0.7118740972357901, 0.00008566490189971971, 0.0, 0.0, 0.82521],
+    mat_inv=[1.3459433009386654, -0.255607509316767, -0.05111176587088496, -0.5445988694587172, 1.5081673177207673, 0.020535141586646915, 0.0, 0.0, 1.2118127506937628]
+);
+
+impl_known_color_space!(Aces2065_1
+    primaries=((0.7347, 0.2653), (0.0, 1.0), (0.0001, -0.0770)),
+    wp=Aces,
+    enc=LinearEncoding,
+    mat=[0.9525523959, 0.0, 0.0000936786, 0.3439664498, 0.7281660966, -0.0721325464, 0.0, 0.0, 1.0088251844],
+    mat_inv=[1.0498110175, 0.0, -0.0000974845, -0.4959030231, 1.3733130458, 0.0982400361, 0.0, 0.0, 0.9912520182]
+);
+
+impl_known_color_space!(AcesCg
+    primaries=((0.713, 0.293), (0.165, 0.830), (0.128, 0.044)),
+    wp=Aces,
+    enc=LinearEncoding,
+    mat=[0.6624541811, 0.1340042065, 0.1561876870, 0.2722287168, 0.6740817658, 0.0536895174, -0.0055746495, 0.0040607335, 1.0103391003],
+    mat_inv=[1.6410233797, -0.3248032942, -0.2364246952, -0.6636628587, 1.6153315917, 0.0167563477, 0.0117218943, -0.0082844420, 0.9883948585]
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::encoding::{EncodableColor, PqEncoding};
+    use crate::color::FromTuple;
+    use crate::lms::Bradford;
+    use approx::*;
+
+    macro_rules! assert_derived_matrices {
+        ($($name:ident),*) => {
+            $(
+                let built = $name::<f64>::build_color_space_instance();
+                assert_relative_eq!(
+                    Matrix3::new($name::<f64>::XYZ_MATRIX),
+                    built.get_xyz_transform(),
+                    epsilon = 1e-6
+                );
+                assert_relative_eq!(
+                    Matrix3::new($name::<f64>::INVERSE_XYZ_MATRIX),
+                    built.get_inverse_xyz_transform(),
+                    epsilon = 1e-6
+                );
+                assert_relative_eq!(
+                    Matrix3::new($name::<f64>::INVERSE_XYZ_MATRIX)
+                        * Matrix3::new($name::<f64>::XYZ_MATRIX),
+                    Matrix3::identity(),
+                    epsilon = 1e-8
+                );
+            )*
+        };
+    }
+
+    #[test]
+    fn test_const_matrices() {
+        assert_derived_matrices!(
+            SRgb, ScRgb, AdobeRgb, DisplayP3, Rec601_625, Rec601_525, Rec2020, ProPhotoRgb,
+            Aces2065_1, AcesCg
+        );
+        assert_eq!(
+            SRgb::<f32>::new().get_xyz_transform(),
+            Matrix3::new(SRgb::<f32>::XYZ_MATRIX.map(|v| v as f32))
+        );
+    }
+
+    #[test]
+    fn test_wide_gamut_spaces() {
+        let white = Rgb::new(1.0, 1.0, 1.0);
+        let adobe = AdobeRgb::<f64>::new();
+        let xyz = adobe.convert_to_xyz(&white.encoded_as(AdobeRgbEncoding::new()));
+        assert_relative_eq!(xyz, D65.get_xyz(), epsilon = 1e-6);
+        let p3 = DisplayP3::<f64>::new();
+        let xyz = p3.convert_to_xyz(&white.srgb_encoded());
+        assert_relative_eq!(xyz, D65.get_xyz(), epsilon = 1e-6);
+
+        let gray = Rgb::new(0.5, 0.5, 0.5).encoded_as(AdobeRgbEncoding::new());
+        let xyz = adobe.convert_to_xyz(&gray);
+        assert_relative_eq!(xyz.y(), 0.5f64.powf(563.0 / 256.0), epsilon = 1e-9);
+
+        let red = SRgb::<f64>::new().convert_to_xyz(&Rgb::new(1.0, 0.0, 0.0).linear());
+        let in_p3 = p3.convert_from_xyz_raw(&red);
+        assert!(in_p3.red() < 1.0 && in_p3.green() > 0.0);
+    }
+
+    #[test]
+    fn test_scrgb() {
+        let scrgb = ScRgb::<f64>::new();
+        let p3_red = DisplayP3::<f64>::new().convert_to_xyz(&Rgb::new(1.0, 0.0, 0.0).linear());
+        let red = scrgb.convert_from_xyz(&p3_red);
+        let linear = red.color().color();
+        assert_eq!(*red.color().encoding(), LinearEncoding);
+        assert!(linear.green() < 0.0 && linear.blue() < 0.0);
+        assert_relative_eq!(scrgb.convert_to_xyz(red.color()), p3_red, epsilon = 1e-9);
+
+        let highlight = Rgb::new(4.0, 2.5, -0.25);
+        let xyz = scrgb.convert_to_xyz(&highlight.linear());
+        assert_relative_eq!(
+            scrgb.convert_from_xyz(&xyz).strip(),
+            highlight,
+            epsilon = 1e-9
+        );
+        let srgb = SRgb::<f64>::new().convert_from_xyz(&xyz);
+        let encoded = srgb.color().color();
+        assert!(encoded.red() > 1.0 && encoded.blue() < 0.0);
+        assert_relative_eq!(
+            srgb.strip_space().decode().strip_encoding(),
+            highlight,
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn test_rec601_transforms() {
+        let pal = Rec601_625::<f64>::new();
+        let ntsc = Rec601_525::<f64>::new();
+        assert_relative_eq!(
+            pal.get_xyz_transform(),
+            Rec601_625::<f64>::build_color_space_instance().get_xyz_transform(),
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(
+            pal.get_inverse_xyz_transform(),
+            Rec601_625::<f64>::build_color_space_instance().get_inverse_xyz_transform(),
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(
+            ntsc.get_xyz_transform(),
+            Rec601_525::<f64>::build_color_space_instance().get_xyz_transform(),
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(
+            ntsc.get_inverse_xyz_transform(),
+            Rec601_525::<f64>::build_color_space_instance().get_inverse_xyz_transform(),
+            epsilon = 1e-6
+        );
+
+        let color = Rgb::new(0.409008, 0.409008, 0.409008).encoded_as(Rec709Encoding);
+        let xyz = ntsc.convert_to_xyz(&color);
+        assert_relative_eq!(xyz.y(), 0.18, epsilon = 1e-6);
+        let back = ntsc.convert_from_xyz(&xyz);
+        assert_relative_eq!(*back.color(), color, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_rec2020_transform() {
+        let space = Rec2020::<f64>::new();
+        let built = Rec2020::<f64>::build_color_space_instance();
+        assert_relative_eq!(
+            space.get_xyz_transform(),
+            built.get_xyz_transform(),
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(
+            space.get_inverse_xyz_transform(),
+            built.get_inverse_xyz_transform(),
+            epsilon = 1e-6
+        );
+
+        let white = space.convert_to_xyz(&Rgb::new(1.0, 1.0, 1.0).encoded_as(Rec2020Encoding));
+        assert_relative_eq!(white, D65.get_xyz(), epsilon = 1e-6);
+        let back = space.convert_from_xyz(&white);
+        assert_relative_eq!(*back.color().color(), Rgb::new(1.0, 1.0, 1.0), epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_rec2020_pq() {
+        let space = Rec2020::<f64>::build_color_space_instance().with_encoding(PqEncoding::new());
+        let color = Rgb::new(0.508078, 0.508078, 0.508078).encoded_as(PqEncoding::new());
+        let xyz = space.convert_to_xyz(&color);
+        assert_relative_eq!(xyz.y(), 0.01, epsilon = 1e-6);
+        let back = space.convert_from_xyz(&xyz);
+        assert_relative_eq!(*back.color(), color, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_prophoto_transform() {
+        let space = ProPhotoRgb::<f64>::new();
+        let built = ProPhotoRgb::<f64>::build_color_space_instance();
+        assert_relative_eq!(
+            space.get_xyz_transform(),
+            built.get_xyz_transform(),
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(
+            space.get_inverse_xyz_transform(),
+            built.get_inverse_xyz_transform(),
+            epsilon = 1e-6
+        );
+
+        let white = space.convert_to_xyz(&Rgb::new(1.0, 1.0, 1.0).encoded_as(RommEncoding));
+        assert_relative_eq!(white, D50.get_xyz(), epsilon = 1e-6);
+
+        let white = space.convert_to_xyz_adapted::<Bradford>(
+            &Rgb::new(1.0, 1.0, 1.0).encoded_as(RommEncoding),
+            &D65.get_xyz(),
+        );
+        assert_relative_eq!(white, D65.get_xyz(), epsilon = 1e-5);
+        let srgb = SRgb::<f64>::new().convert_from_xyz(&white);
+        assert_relative_eq!(
+            *srgb.color().color(),
+            Rgb::new(1.0, 1.0, 1.0),
+            epsilon = 1e-4
+        );
+
+        let gray = Rgb::new(0.5, 0.5, 0.5).linear();
+        let adapted = space.convert_to_xyz_adapted::<Bradford>(&gray, &D65.get_xyz());
+        assert_relative_eq!(adapted, D65.get_xyz() * 0.5, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn test_prophoto_to_srgb() {
+        let prophoto = ProPhotoRgb::<f64>::new();
+        let srgb = SRgb::<f64>::new();
+        let transform = srgb.get_inverse_xyz_transform()
+            * prophoto.get_adapted_xyz_transform::<Bradford>(&D65.get_xyz());
+
+        let gray = transform.transform_vector((0.5, 0.5, 0.5));
+        assert_relative_eq!(Rgb::from_tuple(gray), Rgb::new(0.5, 0.5, 0.5), epsilon = 1e-4);
+        let red = transform.transform_vector((1.0, 0.0, 0.0));
+        assert_relative_eq!(
+            Rgb::from_tuple(red),
+            Rgb::new(2.034062, -0.228804, -0.008572),
+            epsilon = 1e-4
+        );
+
+        let inverse = prophoto.get_adapted_inverse_xyz_transform::<Bradford>(&D65.get_xyz())
+            * srgb.get_xyz_transform();
+        assert_relative_eq!(inverse * transform, Matrix3::identity(), epsilon = 1e-4);
+    }
+
+    #[test]
+    fn test_aces_transforms() {
+        let ap0 = Aces2065_1::<f64>::new();
+        let ap1 = AcesCg::<f64>::new();
+        assert_relative_eq!(
+            ap0.get_xyz_transform(),
+            Aces2065_1::<f64>::build_color_space_instance().get_xyz_transform(),
+            epsilon = 1e-8
+        );
+        assert_relative_eq!(
+            ap1.get_xyz_transform(),
+            AcesCg::<f64>::build_color_space_instance().get_xyz_transform(),
+            epsilon = 1e-8
+        );
+        assert_relative_eq!(
+            ap0.get_inverse_xyz_transform() * ap0.get_xyz_transform(),
+            Matrix3::identity(),
+            epsilon = 1e-8
+        );
+        assert_relative_eq!(
+            ap1.get_inverse_xyz_transform() * ap1.get_xyz_transform(),
+            Matrix3::identity(),
+            epsilon = 1e-8
+        );
+
+        let ap0_to_ap1 = ap1.get_inverse_xyz_transform() * ap0.get_xyz_transform();
+        assert_relative_eq!(
+            ap0_to_ap1,
+            Matrix3::new([
+                1.4514393161, -0.2365107469, -0.2149285693, -0.0765537734, 1.1762296998,
+                -0.0996759264, 0.0083161484, -0.0060324498, 0.9977163014,
+            ]),
+            epsilon = 1e-8
+        );
+
+        let white = ap1.convert_to_xyz(&Rgb::new(1.0, 1.0, 1.0).linear());
+        assert_relative_eq!(white, Aces.get_xyz(), epsilon = 1e-6);
+    }
+}
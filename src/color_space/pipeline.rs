@@ -0,0 +1,197 @@
+//! Defines `LinearPipeline` for combining linear conversion steps into one matrix
+
+use crate::channel::{FreeChannelScalar, PosNormalChannelScalar};
+use crate::color::Color;
+use crate::color_space::ColorSpace;
+use crate::linalg::Matrix3;
+use crate::lms::{chromatic_adaptation_transform, LmsModel};
+use crate::rgb::Rgb;
+use crate::xyz::Xyz;
+use num_traits;
+
+/// A sequence of linear color transforms collapsed into a single matrix
+///
+/// Converting between RGB spaces takes several linear steps: into XYZ, a chromatic adaptation,
+/// and back out of XYZ. Each step added to a `LinearPipeline` is multiplied into the matrix
+/// immediately, so applying the pipeline costs one matrix multiply per color no matter how many
+/// steps it contains. [`SpaceConverter`](struct.SpaceConverter.html) builds its transform with
+/// a `LinearPipeline`, which is returned by
+/// [`SpaceConverter::pipeline`](struct.SpaceConverter.html#method.pipeline) to be extended or
+/// combined with other pipelines.
+///
+/// ## Examples:
+///
+/// ```rust
+/// #[macro_use] extern crate approx;
+/// # extern crate prisma;
+///
+/// use prisma::Rgb;
+/// use prisma::color_space::LinearPipeline;
+/// use prisma::color_space::named::{Rec2020, SRgb};
+///
+/// let pipeline = LinearPipeline::new()
+///     .rgb_to_xyz(&SRgb::new())
+///     .xyz_to_rgb(&Rec2020::new());
+/// assert_eq!(pipeline.steps(), 2);
+///
+/// let round_trip = pipeline.clone().then_pipeline(&pipeline.inverse().unwrap());
+/// let color = Rgb::new(0.2, 0.4, 0.6);
+/// assert_relative_eq!(round_trip.apply(&color), color, epsilon=1e-9);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinearPipeline<T> {
+    matrix: Matrix3<T>,
+    steps: usize,
+}
+
+impl<T> LinearPipeline<T>
+where
+    T: num_traits::Float + FreeChannelScalar,
+{
+    /// Construct an empty pipeline, which leaves colors unchanged
+    pub fn new() -> Self {
+        LinearPipeline {
+            matrix: Matrix3::identity(),
+            steps: 0,
+        }
+    }
+
+    /// Append a linear step, applied after all of the existing steps
+    pub fn then(self, step: Matrix3<T>) -> Self {
+        LinearPipeline {
+            matrix: step * self.matrix,
+            steps: self.steps + 1,
+        }
+    }
+    /// Append all of the steps of `other`
+    pub fn then_pipeline(self, other: &LinearPipeline<T>) -> Self {
+        LinearPipeline {
+            matrix: other.matrix.clone() * self.matrix,
+            steps: self.steps + other.steps,
+        }
+    }
+    /// Append the transform from linear RGB in `space` to XYZ
+    pub fn rgb_to_xyz<S>(self, space: &S) -> Self
+    where
+        S: ColorSpace<T>,
+    {
+        self.then(space.get_xyz_transform())
+    }
+    /// Append the transform from XYZ to linear RGB in `space`
+    pub fn xyz_to_rgb<S>(self, space: &S) -> Self
+    where
+        S: ColorSpace<T>,
+    {
+        self.then(space.get_inverse_xyz_transform())
+    }
+    /// Append a chromatic adaptation in XYZ from `source_white` to `dest_white` using `Model`
+    ///
+    /// Nothing is appended if the two white points are equal.
+    pub fn adapt<Model>(self, source_white: &Xyz<T>, dest_white: &Xyz<T>) -> Self
+    where
+        Model: LmsModel<T>,
+    {
+        if source_white == dest_white {
+            self
+        } else {
+            self.then(chromatic_adaptation_transform::<T, Model>(
+                source_white,
+                dest_white,
+            ))
+        }
+    }
+
+    /// Returns a pipeline undoing this one, or `None` if the combined matrix is singular
+    pub fn inverse(&self) -> Option<Self> {
+        self.matrix.clone().try_inverse().map(|matrix| LinearPipeline {
+            matrix,
+            steps: self.steps,
+        })
+    }
+
+    /// Returns the combined matrix of every step
+    pub fn matrix(&self) -> &Matrix3<T> {
+        &self.matrix
+    }
+    /// Consume the pipeline, returning the combined matrix of every step
+    pub fn into_matrix(self) -> Matrix3<T> {
+        self.matrix
+    }
+    /// Returns the number of steps that were combined into the pipeline
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Apply the pipeline to a 3-vector
+    pub fn apply_vector(&self, vec: (T, T, T)) -> (T, T, T) {
+        self.matrix.transform_vector(vec)
+    }
+    /// Apply the pipeline to the channels of an `Rgb` color
+    pub fn apply(&self, color: &Rgb<T>) -> Rgb<T>
+    where
+        T: PosNormalChannelScalar,
+    {
+        let (r, g, b) = self.apply_vector(color.clone().to_tuple());
+        Rgb::new(r, g, b)
+    }
+}
+
+impl<T> Default for LinearPipeline<T>
+where
+    T: num_traits::Float + FreeChannelScalar,
+{
+    fn default() -> Self {
+        LinearPipeline::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::color_space::named::{ProPhotoRgb, SRgb};
+    use crate::lms::Bradford;
+    use approx::*;
+
+    #[test]
+    fn test_collapse() {
+        let source = ProPhotoRgb::<f64>::new();
+        let dest = SRgb::new();
+        let pipeline = LinearPipeline::new()
+            .rgb_to_xyz(&source)
+            .adapt::<Bradford>(&source.white_point(), &dest.white_point())
+            .xyz_to_rgb(&dest);
+        assert_eq!(pipeline.steps(), 3);
+
+        let color = (0.2, 0.5, 0.7);
+        let xyz = source.get_xyz_transform().transform_vector(color);
+        let adapted = chromatic_adaptation_transform::<f64, Bradford>(
+            &source.white_point(),
+            &dest.white_point(),
+        )
+        .transform_vector(xyz);
+        let expected = dest.get_inverse_xyz_transform().transform_vector(adapted);
+        let actual = pipeline.apply_vector(color);
+        assert_relative_eq!(actual.0, expected.0, epsilon = 1e-12);
+        assert_relative_eq!(actual.1, expected.1, epsilon = 1e-12);
+        assert_relative_eq!(actual.2, expected.2, epsilon = 1e-12);
+
+        let same = LinearPipeline::new()
+            .rgb_to_xyz(&dest)
+            .adapt::<Bradford>(&dest.white_point(), &dest.white_point())
+            .xyz_to_rgb(&dest);
+        assert_eq!(same.steps(), 2);
+        assert_relative_eq!(*same.matrix(), Matrix3::identity(), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_compose() {
+        let scale = LinearPipeline::new().then(Matrix3::identity() * 2.0f64);
+        let combined = scale.clone().then_pipeline(&scale.inverse().unwrap());
+        assert_eq!(combined.steps(), 2);
+        assert_eq!(combined.into_matrix(), Matrix3::identity());
+        assert_eq!(
+            LinearPipeline::<f64>::default().apply(&Rgb::new(0.1, 0.2, 0.3)),
+            Rgb::new(0.1, 0.2, 0.3)
+        );
+    }
+}
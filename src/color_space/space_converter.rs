@@ -0,0 +1,320 @@
+//! Defines `SpaceConverter` for converting colors directly between two RGB color spaces
+
+use crate::channel::{ChannelFormatCast, FreeChannelScalar, PosNormalChannelScalar};
+use crate::color::Color;
+use crate::color_space::{ColorSpace, LinearPipeline, SpacedColor};
+use crate::encoding::{ColorEncoding, EncodableColor, EncodedColor};
+use crate::linalg::Matrix3;
+use crate::lms::{chromatic_adaptation_transform, Bradford, LmsModel};
+use crate::rgb::Rgb;
+use crate::xyz::Xyz;
+use num_traits;
+
+/// Converts `Rgb` colors from one color space into another in a single step
+///
+/// The conversion decodes the input, transforms it into XYZ with the source space, adapts it to
+/// the white point of the destination space, transforms it out of XYZ with the destination space
+/// and finally encodes it with the destination space's encoding. All of the linear steps are
+/// combined into one matrix by a [`LinearPipeline`](struct.LinearPipeline.html) when the
+/// converter is constructed, so a converter is cheap to apply to many colors.
+///
+/// ## Examples:
+///
+/// ```rust
+/// #[macro_use] extern crate approx;
+/// # extern crate prisma;
+///
+/// use prisma::Rgb;
+/// use prisma::color_space::SpaceConverter;
+/// use prisma::color_space::named::{Rec2020, SRgb};
+/// use prisma::encoding::EncodableColor;
+///
+/// let converter = SpaceConverter::new(SRgb::new(), Rec2020::new());
+/// let white = converter.convert(&Rgb::new(1.0, 1.0, 1.0).srgb_encoded());
+/// assert_relative_eq!(*white.color().color(), Rgb::new(1.0, 1.0, 1.0), epsilon=1e-6);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpaceConverter<T, S, D> {
+    source: S,
+    dest: D,
+    adaptation: Matrix3<T>,
+    pipeline: LinearPipeline<T>,
+}
+
+/// The intermediate values of a single conversion done by a `SpaceConverter`
+///
+/// Returned by [`SpaceConverter::explain`](struct.SpaceConverter.html#method.explain). Each stage
+/// is the input to the next, which makes it straightforward to find where a conversion diverges
+/// from a reference implementation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConversionTrace<T, E> {
+    linear: Rgb<T>,
+    xyz: Xyz<T>,
+    adapted_xyz: Xyz<T>,
+    dest_linear: Rgb<T>,
+    encoded: EncodedColor<Rgb<T>, E>,
+}
+
+impl<T, S, D> SpaceConverter<T, S, D>
+where
+    T: num_traits::Float + FreeChannelScalar + PosNormalChannelScalar,
+    S: ColorSpace<T>,
+    D: ColorSpace<T>,
+{
+    /// Construct a new `SpaceConverter`, adapting between white points with the Bradford model
+    pub fn new(source: S, dest: D) -> Self {
+        Self::with_adaptation::<Bradford>(source, dest)
+    }
+
+    /// Construct a new `SpaceConverter`, adapting between white points with `Model`
+    ///
+    /// Any of the transforms in the [`cat`](../cat/index.html) module can be used, e.g.
+    /// `SpaceConverter::with_adaptation::<cat::Cat16>(source, dest)`. No adaptation is done if
+    /// both spaces share the same white point.
+    pub fn with_adaptation<Model>(source: S, dest: D) -> Self
+    where
+        Model: LmsModel<T>,
+    {
+        let source_white = source.white_point();
+        let dest_white = dest.white_point();
+        let adaptation = if source_white == dest_white {
+            Matrix3::identity()
+        } else {
+            chromatic_adaptation_transform::<T, Model>(&source_white, &dest_white)
+        };
+        let pipeline = LinearPipeline::new()
+            .rgb_to_xyz(&source)
+            .then(adaptation.clone())
+            .xyz_to_rgb(&dest);
+
+        SpaceConverter {
+            source,
+            dest,
+            adaptation,
+            pipeline,
+        }
+    }
+
+    /// Returns a reference to the source color space
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+    /// Returns a reference to the destination color space
+    pub fn dest(&self) -> &D {
+        &self.dest
+    }
+    /// Returns the chromatic adaptation applied in XYZ space
+    ///
+    /// This is the identity matrix if both spaces share the same white point.
+    pub fn adaptation(&self) -> &Matrix3<T> {
+        &self.adaptation
+    }
+    /// Returns the combined linear transform from the source space to the destination space
+    pub fn transform(&self) -> &Matrix3<T> {
+        self.pipeline.matrix()
+    }
+    /// Returns the linear steps from the source space to the destination space as a pipeline
+    pub fn pipeline(&self) -> &LinearPipeline<T> {
+        &self.pipeline
+    }
+
+    /// Convert a linear `Rgb` color in the source space to a linear `Rgb` in the destination space
+    pub fn convert_linear(&self, color: &Rgb<T>) -> Rgb<T> {
+        self.pipeline.apply(color)
+    }
+
+    /// Convert an encoded color in the source space to the destination space and its encoding
+    pub fn convert<E>(
+        &self,
+        color: &EncodedColor<Rgb<T>, E>,
+    ) -> SpacedColor<T, Rgb<T>, D::Encoding, D>
+    where
+        T: ChannelFormatCast<f64>,
+        f64: ChannelFormatCast<T>,
+        E: ColorEncoding,
+    {
+        let linear = color.clone().decode();
+        let converted = self.convert_linear(&linear);
+        SpacedColor::new(
+            converted.linear().encode(self.dest.encoding()),
+            self.dest.clone(),
+        )
+    }
+
+    /// Convert `color` one stage at a time, returning the value after each stage
+    ///
+    /// The final encoded color is identical to the color returned by
+    /// [`convert`](#method.convert) up to rounding error. This is slower than `convert` and is
+    /// intended for diagnosing unexpected conversion results.
+    pub fn explain<E>(&self, color: &EncodedColor<Rgb<T>, E>) -> ConversionTrace<T, D::Encoding>
+    where
+        T: ChannelFormatCast<f64>,
+        f64: ChannelFormatCast<T>,
+        E: ColorEncoding,
+    {
+        let linear = color.clone().decode().strip_encoding();
+        let (x, y, z) = self
+            .source
+            .get_xyz_transform()
+            .transform_vector(linear.clone().to_tuple());
+        let xyz = Xyz::new(x, y, z);
+        let (x, y, z) = self.adaptation.transform_vector(xyz.clone().to_tuple());
+        let adapted_xyz = Xyz::new(x, y, z);
+        let (r, g, b) = self
+            .dest
+            .get_inverse_xyz_transform()
+            .transform_vector(adapted_xyz.clone().to_tuple());
+        let dest_linear = Rgb::new(r, g, b);
+        let encoded = dest_linear.clone().linear().encode(self.dest.encoding());
+
+        ConversionTrace {
+            linear,
+            xyz,
+            adapted_xyz,
+            dest_linear,
+            encoded,
+        }
+    }
+}
+
+impl<T, E> ConversionTrace<T, E> {
+    /// Returns the decoded source color in the source space
+    pub fn linear(&self) -> &Rgb<T> {
+        &self.linear
+    }
+    /// Returns the source color in XYZ, relative to the source white point
+    pub fn xyz(&self) -> &Xyz<T> {
+        &self.xyz
+    }
+    /// Returns the color in XYZ, relative to the destination white point
+    pub fn adapted_xyz(&self) -> &Xyz<T> {
+        &self.adapted_xyz
+    }
+    /// Returns the linear color in the destination space
+    pub fn dest_linear(&self) -> &Rgb<T> {
+        &self.dest_linear
+    }
+    /// Returns the final color, encoded with the destination space's encoding
+    pub fn encoded(&self) -> &EncodedColor<Rgb<T>, E> {
+        &self.encoded
+    }
+}
+
+/// Convert `color` from the color space `source` to the color space `dest`
+///
+/// This is a shorthand for building a [`SpaceConverter`](struct.SpaceConverter.html) and using
+/// it once. Prefer the converter when converting many colors.
+pub fn convert_space<T, S, D, E>(
+    source: &S,
+    dest: &D,
+    color: &EncodedColor<Rgb<T>, E>,
+) -> SpacedColor<T, Rgb<T>, D::Encoding, D>
+where
+    T: num_traits::Float + FreeChannelScalar + PosNormalChannelScalar + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+    S: ColorSpace<T>,
+    D: ColorSpace<T>,
+    E: ColorEncoding,
+{
+    SpaceConverter::new(source.clone(), dest.clone()).convert(color)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::color_space::named::{ProPhotoRgb, Rec2020, SRgb};
+    use crate::color_space::{ConvertFromXyz, ConvertToXyz};
+    use crate::encoding::{RommEncoding, SrgbEncoding};
+    use crate::white_point::{WhitePoint, D65};
+    use approx::*;
+
+    #[test]
+    fn test_same_white_point() {
+        let converter = SpaceConverter::new(SRgb::<f64>::new(), Rec2020::new());
+        let colors = [
+            Rgb::new(1.0, 1.0, 1.0),
+            Rgb::new(0.2, 0.4, 0.8),
+            Rgb::new(0.9, 0.1, 0.0),
+        ];
+        for color in colors.iter() {
+            let encoded = color.clone().srgb_encoded();
+            let expected = Rec2020::new().convert_from_xyz(&SRgb::new().convert_to_xyz(&encoded));
+            let converted = converter.convert(&encoded);
+            assert_relative_eq!(converted.color(), expected.color(), epsilon = 1e-6);
+        }
+
+        let red = converter.convert_linear(&Rgb::new(1.0, 0.0, 0.0));
+        assert_relative_eq!(red, Rgb::new(0.627452, 0.069109, 0.016398), epsilon = 1e-5);
+    }
+
+    #[test]
+    fn test_adapted() {
+        let converter = SpaceConverter::new(ProPhotoRgb::<f64>::new(), SRgb::new());
+        let gray = converter.convert(&Rgb::new(0.5, 0.5, 0.5).encoded_as(RommEncoding));
+        let expected = Rgb::new(0.5, 0.5, 0.5)
+            .encoded_as(RommEncoding)
+            .decode()
+            .encode(SrgbEncoding);
+        assert_relative_eq!(*gray.color(), expected, epsilon = 1e-4);
+
+        let back = SpaceConverter::new(SRgb::<f64>::new(), ProPhotoRgb::new());
+        let round_trip = back.convert(gray.color());
+        assert_relative_eq!(
+            *round_trip.color().color(),
+            Rgb::new(0.5, 0.5, 0.5),
+            epsilon = 1e-4
+        );
+        assert_eq!(back.dest().white_point(), ProPhotoRgb::new().white_point());
+        assert_eq!(converter.dest().white_point(), D65.get_xyz());
+    }
+
+    #[test]
+    fn test_explain() {
+        let converter = SpaceConverter::new(ProPhotoRgb::<f64>::new(), SRgb::new());
+        let color = Rgb::new(0.2, 0.5, 0.7).encoded_as(RommEncoding);
+        let trace = converter.explain(&color);
+
+        assert_relative_eq!(*trace.linear(), color.clone().decode().strip_encoding());
+        assert_relative_eq!(
+            *trace.xyz(),
+            ProPhotoRgb::new().convert_to_xyz(&color),
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(trace.xyz().y(), trace.adapted_xyz().y(), epsilon = 1e-2);
+        assert_relative_eq!(
+            *trace.dest_linear(),
+            converter.convert_linear(trace.linear()),
+            epsilon = 1e-6
+        );
+        let converted = converter.convert(&color);
+        assert_relative_eq!(*trace.encoded(), *converted.color(), epsilon = 1e-6);
+
+        let same = SpaceConverter::new(SRgb::<f64>::new(), Rec2020::new());
+        let trace = same.explain(&Rgb::new(0.2, 0.5, 0.7).srgb_encoded());
+        assert_eq!(*same.adaptation(), Matrix3::identity());
+        assert_relative_eq!(*trace.xyz(), *trace.adapted_xyz());
+    }
+
+    #[test]
+    fn test_pipeline() {
+        let source = ProPhotoRgb::<f64>::new();
+        let dest = SRgb::new();
+        let converter = SpaceConverter::new(source.clone(), dest.clone());
+        let expected = dest.get_inverse_xyz_transform()
+            * converter.adaptation().clone()
+            * source.get_xyz_transform();
+        assert_relative_eq!(*converter.transform(), expected, epsilon = 1e-12);
+        assert_eq!(converter.pipeline().steps(), 3);
+
+        let back = SpaceConverter::new(dest, source);
+        let round_trip = converter.pipeline().clone().then_pipeline(back.pipeline());
+        assert_relative_eq!(*round_trip.matrix(), Matrix3::identity(), epsilon = 1e-4);
+    }
+
+    #[test]
+    fn test_convert_space() {
+        let color = Rgb::new(0.3, 0.6, 0.9).srgb_encoded();
+        let converted = convert_space(&SRgb::new(), &SRgb::new(), &color);
+        assert_relative_eq!(*converted.color(), color, epsilon = 1e-6);
+    }
+}
@@ -0,0 +1,205 @@
+//! Porter-Duff compositing of transparent colors
+//!
+//! Compositing places a source color on top of a destination color, using their alpha values to
+//! decide how much of each ends up in the result. The operators defined by Porter and Duff cover
+//! every way of combining the regions where the source, the destination or both are opaque, and
+//! are listed in [`CompositeOp`](enum.CompositeOp.html). The familiar "paint on top" operation is
+//! [`CompositeOp::SourceOver`](enum.CompositeOp.html#variant.SourceOver).
+//!
+//! Each operator multiplies the source by a factor $`F_a`$ and the destination by a factor
+//! $`F_b`$, both depending only on the alpha values:
+//!
+//! ```math
+//! C_o = C_s F_a + C_d F_b
+//! ```
+//!
+//! The same formula gives the resulting alpha. It only holds for premultiplied colors, so
+//! [`composite`](fn.composite.html) premultiplies its inputs and divides the result again, while
+//! [`composite_premultiplied`](fn.composite_premultiplied.html) works directly on colors that were
+//! premultiplied with [`Alpha::premultiply`](../struct.Alpha.html#method.premultiply).
+//! Compositing should usually be done on linear colors, as it models the mixing of light.
+//!
+//! ## Examples:
+//!
+//! ```rust
+//! #[macro_use] extern crate approx;
+//! # extern crate prisma;
+//!
+//! use prisma::{Rgb, Rgba};
+//! use prisma::composite::{composite, over, CompositeOp};
+//!
+//! let red = Rgba::new(Rgb::new(1.0, 0.0, 0.0), 0.5);
+//! let blue = Rgba::new(Rgb::new(0.0, 0.0, 1.0), 1.0);
+//!
+//! assert_relative_eq!(over(&red, &blue), Rgba::new(Rgb::new(0.5, 0.0, 0.5), 1.0));
+//! assert_relative_eq!(
+//!     composite(&red, &blue, CompositeOp::DestinationOut),
+//!     Rgba::new(Rgb::new(0.0, 0.0, 1.0), 0.5)
+//! );
+//! ```
+
+use crate::alpha::Alpha;
+use crate::channel::PosNormalChannelScalar;
+use crate::color::{ChannelAccess, HomogeneousColor};
+use num_traits::Float;
+
+/// A Porter-Duff compositing operator
+///
+/// The descriptions refer to the region covered by the source, the region covered by the
+/// destination and the region covered by both.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CompositeOp {
+    /// Neither the source nor the destination is kept
+    Clear,
+    /// Only the source is kept
+    Source,
+    /// Only the destination is kept
+    Destination,
+    /// The source is placed over the destination
+    SourceOver,
+    /// The destination is placed over the source
+    DestinationOver,
+    /// The part of the source inside the destination is kept
+    SourceIn,
+    /// The part of the destination inside the source is kept
+    DestinationIn,
+    /// The part of the source outside the destination is kept
+    SourceOut,
+    /// The part of the destination outside the source is kept
+    DestinationOut,
+    /// The part of the source inside the destination is placed over the destination
+    SourceAtop,
+    /// The part of the destination inside the source is placed over the source
+    DestinationAtop,
+    /// The parts of the source and destination outside of each other are kept
+    Xor,
+    /// The source and destination are added together, saturating at one
+    Plus,
+}
+
+impl CompositeOp {
+    /// Returns the factors $`(F_a, F_b)`$ multiplying the source and destination
+    pub fn factors<T>(self, src_alpha: T, dst_alpha: T) -> (T, T)
+    where
+        T: Float,
+    {
+        let one = T::one();
+        let zero = T::zero();
+        match self {
+            CompositeOp::Clear => (zero, zero),
+            CompositeOp::Source => (one, zero),
+            CompositeOp::Destination => (zero, one),
+            CompositeOp::SourceOver => (one, one - src_alpha),
+            CompositeOp::DestinationOver => (one - dst_alpha, one),
+            CompositeOp::SourceIn => (dst_alpha, zero),
+            CompositeOp::DestinationIn => (zero, src_alpha),
+            CompositeOp::SourceOut => (one - dst_alpha, zero),
+            CompositeOp::DestinationOut => (zero, one - src_alpha),
+            CompositeOp::SourceAtop => (dst_alpha, one - src_alpha),
+            CompositeOp::DestinationAtop => (one - dst_alpha, src_alpha),
+            CompositeOp::Xor => (one - dst_alpha, one - src_alpha),
+            CompositeOp::Plus => (one, one),
+        }
+    }
+}
+
+/// Composite the premultiplied color `src` onto the premultiplied color `dst` with `op`
+///
+/// The result is premultiplied as well. Each channel of the result is limited to at most one,
+/// which only has an effect for [`CompositeOp::Plus`](enum.CompositeOp.html#variant.Plus).
+pub fn composite_premultiplied<T, C>(
+    src: &Alpha<T, C>,
+    dst: &Alpha<T, C>,
+    op: CompositeOp,
+) -> Alpha<T, C>
+where
+    T: PosNormalChannelScalar + Float,
+    C: ChannelAccess<ChannelFormat = T>,
+{
+    let (src_factor, dst_factor) = op.factors(src.alpha(), dst.alpha());
+    let one = T::one();
+    src.clone()
+        .zip_channels(dst, |s, d| (s * src_factor + d * dst_factor).min(one))
+}
+
+/// Composite `src` onto `dst` with `op`
+///
+/// `src` and `dst` have straight, non-premultiplied alpha, as does the result. If the result is
+/// fully transparent, its color channels are zero.
+pub fn composite<T, C>(src: &Alpha<T, C>, dst: &Alpha<T, C>, op: CompositeOp) -> Alpha<T, C>
+where
+    T: PosNormalChannelScalar + Float,
+    C: ChannelAccess<ChannelFormat = T>,
+{
+    composite_premultiplied(&src.clone().premultiply(), &dst.clone().premultiply(), op)
+        .unpremultiply()
+}
+
+/// Place `src` over `dst`
+///
+/// This is [`composite`](fn.composite.html) with
+/// [`CompositeOp::SourceOver`](enum.CompositeOp.html#variant.SourceOver), the usual way of
+/// drawing a transparent color on top of another.
+pub fn over<T, C>(src: &Alpha<T, C>, dst: &Alpha<T, C>) -> Alpha<T, C>
+where
+    T: PosNormalChannelScalar + Float,
+    C: ChannelAccess<ChannelFormat = T>,
+{
+    composite(src, dst, CompositeOp::SourceOver)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::alpha::{LumaA, Rgba};
+    use crate::luma::Luma;
+    use crate::rgb::Rgb;
+    use approx::*;
+
+    #[test]
+    fn test_composite() {
+        let src = Rgba::new(Rgb::new(1.0, 0.5, 0.0), 0.75);
+        let dst = Rgba::new(Rgb::new(0.0, 0.5, 1.0), 0.5);
+        let expect = |op, alpha: f64, color: Rgb<f64>| {
+            let result = composite(&src, &dst, op);
+            assert_relative_eq!(result.alpha(), alpha, epsilon = 1e-12);
+            assert_relative_eq!(*result.color(), color, epsilon = 1e-12);
+        };
+
+        expect(CompositeOp::Clear, 0.0, Rgb::new(0.0, 0.0, 0.0));
+        expect(CompositeOp::Source, 0.75, Rgb::new(1.0, 0.5, 0.0));
+        expect(CompositeOp::Destination, 0.5, Rgb::new(0.0, 0.5, 1.0));
+        expect(
+            CompositeOp::SourceOver,
+            0.875,
+            Rgb::new(6.0 / 7.0, 0.5, 1.0 / 7.0),
+        );
+        expect(
+            CompositeOp::DestinationOver,
+            0.875,
+            Rgb::new(3.0 / 7.0, 0.5, 4.0 / 7.0),
+        );
+        expect(CompositeOp::SourceIn, 0.375, Rgb::new(1.0, 0.5, 0.0));
+        expect(CompositeOp::DestinationIn, 0.375, Rgb::new(0.0, 0.5, 1.0));
+        expect(CompositeOp::SourceOut, 0.375, Rgb::new(1.0, 0.5, 0.0));
+        expect(CompositeOp::DestinationOut, 0.125, Rgb::new(0.0, 0.5, 1.0));
+        expect(CompositeOp::SourceAtop, 0.5, Rgb::new(0.75, 0.5, 0.25));
+        expect(CompositeOp::DestinationAtop, 0.75, Rgb::new(0.5, 0.5, 0.5));
+        expect(CompositeOp::Xor, 0.5, Rgb::new(0.75, 0.5, 0.25));
+        expect(CompositeOp::Plus, 1.0, Rgb::new(0.75, 0.625, 0.5));
+    }
+
+    #[test]
+    fn test_over() {
+        let opaque = Rgba::new(Rgb::new(0.2, 0.4, 0.6), 1.0);
+        let clear = Rgba::new(Rgb::new(0.0, 0.0, 0.0), 0.0);
+        assert_relative_eq!(over(&opaque, &clear), opaque);
+        assert_relative_eq!(over(&clear, &opaque), opaque);
+        assert_eq!(over(&clear, &clear), clear);
+
+        let src = LumaA::new(Luma::new(1.0f32), 0.5).premultiply();
+        let dst = LumaA::new(Luma::new(0.0), 1.0).premultiply();
+        let result = composite_premultiplied(&src, &dst, CompositeOp::SourceOver);
+        assert_relative_eq!(result, LumaA::new(Luma::new(0.5), 1.0));
+    }
+}
@@ -0,0 +1,290 @@
+//! WCAG contrast ratios and contrast-preserving color adjustment
+//!
+//! The Web Content Accessibility Guidelines measure the legibility of text by the contrast ratio
+//! between the relative luminance of the text and its background, from 1:1 for identical colors
+//! to 21:1 for black on white. Level AA requires a ratio of at least 4.5:1 for body text and 3:1
+//! for large text, while level AAA requires 7:1 and 4.5:1.
+//!
+//! [`contrast_ratio`](fn.contrast_ratio.html) computes the ratio between two colors, and
+//! [`adjust_for_contrast`](fn.adjust_for_contrast.html) finds the smallest lightness change to a
+//! foreground color that reaches a required ratio.
+//!
+//! All colors are sRGB encoded, as they are in CSS.
+//!
+//! ## Examples:
+//!
+//! ```rust
+//! #[macro_use] extern crate approx;
+//! # extern crate prisma;
+//!
+//! use prisma::Rgb;
+//! use prisma::contrast::{adjust_for_contrast, contrast_ratio};
+//!
+//! let white = Rgb::new(255u8, 255, 255);
+//! let gray = Rgb::new(119u8, 119, 119);
+//! assert_relative_eq!(contrast_ratio(&gray, &white), 4.478, epsilon=1e-3);
+//!
+//! let darker = adjust_for_contrast(&gray, &white, 4.5).unwrap();
+//! assert!(contrast_ratio(&darker, &white) >= 4.5);
+//! assert!(darker.green() < 119);
+//! ```
+
+#![allow(non_snake_case)]
+
+use crate::channel::{ChannelFormatCast, PosNormalChannelScalar};
+use crate::color::Color;
+use crate::color_space::named::SRgb;
+use crate::color_space::ColorSpace;
+use crate::convert::FromColor;
+use crate::encoding::{EncodableColor, SrgbEncoding};
+use crate::gamut::{CssOklch, GamutMapping};
+use crate::grayscale::{LumaWeights, ToLuma};
+use crate::oklab::Oklab;
+use crate::oklch::Oklch;
+use crate::rgb::Rgb;
+use crate::xyz::Xyz;
+use std::error::Error;
+use std::fmt;
+
+/// An error returned when no lightness of a color reaches the requested contrast ratio
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ContrastUnreachable {
+    requested: f64,
+    max_ratio: f64,
+}
+
+impl ContrastUnreachable {
+    /// Returns the contrast ratio that was requested
+    pub fn requested(&self) -> f64 {
+        self.requested
+    }
+    /// Returns the highest contrast ratio that can be reached against the background
+    pub fn max_ratio(&self) -> f64 {
+        self.max_ratio
+    }
+}
+
+impl fmt::Display for ContrastUnreachable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "contrast ratio {} is unreachable, the highest possible is {}",
+            self.requested, self.max_ratio
+        )
+    }
+}
+
+impl Error for ContrastUnreachable {}
+
+/// Returns the WCAG relative luminance of an sRGB encoded color
+///
+/// This is the CIE Y value of the color in [0, 1], relative to the sRGB white point.
+pub fn relative_luminance<T>(color: &Rgb<T>) -> f64
+where
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+{
+    color
+        .color_cast::<f64>()
+        .srgb_encoded()
+        .decode()
+        .strip_encoding()
+        .to_luma(LumaWeights::CieY)
+}
+
+/// Returns the WCAG contrast ratio between two sRGB encoded colors
+///
+/// The ratio ranges from 1 to 21, and doesn't depend on the order of the colors.
+pub fn contrast_ratio<T>(color1: &Rgb<T>, color2: &Rgb<T>) -> f64
+where
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+{
+    ratio_of_luminance(relative_luminance(color1), relative_luminance(color2))
+}
+
+/// Adjust the lightness of `foreground` until it has a contrast ratio of at least `ratio` against
+/// `background`
+///
+/// The lightness is changed in `Oklch`, keeping the hue and, where the gamut allows, the chroma of
+/// `foreground`. Both making the color lighter and darker are tried, and the one changing the
+/// lightness least is returned. If `foreground` already has enough contrast it is returned
+/// unchanged.
+///
+/// Returns an error if even black or white don't reach `ratio`, such as for ratios above 21 or
+/// for mid-gray backgrounds with a high `ratio`.
+///
+/// ## Examples:
+///
+/// ```rust
+/// # extern crate prisma;
+///
+/// use prisma::Rgb;
+/// use prisma::contrast::{adjust_for_contrast, contrast_ratio};
+///
+/// let background = Rgb::new(0.1, 0.1, 0.15);
+/// let text = adjust_for_contrast(&Rgb::new(0.3, 0.3, 0.8), &background, 7.0).unwrap();
+/// assert!(contrast_ratio(&text, &background) >= 7.0);
+///
+/// let gray = Rgb::new(0.5, 0.5, 0.5);
+/// assert!(adjust_for_contrast(&Rgb::new(0.4, 0.4, 0.4), &gray, 10.0).is_err());
+/// ```
+pub fn adjust_for_contrast<T>(
+    foreground: &Rgb<T>,
+    background: &Rgb<T>,
+    ratio: f64,
+) -> Result<Rgb<T>, ContrastUnreachable>
+where
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+{
+    if contrast_ratio(foreground, background) >= ratio {
+        return Ok(foreground.clone());
+    }
+
+    let space = SRgb::new();
+    let start: Oklch<f64> = {
+        let linear = foreground.color_cast::<f64>().srgb_encoded().decode();
+        let (x, y, z) = space.apply_transform(linear.strip_encoding().to_tuple());
+        Oklch::from_color(&Oklab::from_color(&Xyz::new(x, y, z)))
+    };
+    let with_lightness = |L: f64| -> Rgb<T> {
+        let mut color = start.clone();
+        color.set_L(L);
+        let xyz = Xyz::from_color(&Oklab::from_color(&color));
+        let (r, g, b) = space
+            .get_inverse_xyz_transform()
+            .transform_vector(xyz.to_tuple());
+        CssOklch
+            .map_linear(&space, &Rgb::new(r, g, b))
+            .linear()
+            .encode(SrgbEncoding)
+            .strip_encoding()
+            .color_cast()
+    };
+
+    // Luminance increases with lightness, so each direction has a single lightness at which the
+    // ratio is first reached
+    let background_luminance = relative_luminance(background);
+    let reaches = |L: f64| {
+        ratio_of_luminance(relative_luminance(&with_lightness(L)), background_luminance) >= ratio
+    };
+    let L = start.L();
+    let lighter = if reaches(1.0) {
+        Some(search_lightness(L, 1.0, &reaches))
+    } else {
+        None
+    };
+    let darker = if reaches(0.0) {
+        Some(search_lightness(L, 0.0, &reaches))
+    } else {
+        None
+    };
+
+    let best = match (lighter, darker) {
+        (Some(lighter), Some(darker)) => {
+            if lighter - L <= L - darker {
+                lighter
+            } else {
+                darker
+            }
+        }
+        (Some(lighter), None) => lighter,
+        (None, Some(darker)) => darker,
+        (None, None) => {
+            return Err(ContrastUnreachable {
+                requested: ratio,
+                max_ratio: ratio_of_luminance(0.0, background_luminance)
+                    .max(ratio_of_luminance(1.0, background_luminance)),
+            })
+        }
+    };
+    Ok(with_lightness(best))
+}
+
+fn ratio_of_luminance(luminance1: f64, luminance2: f64) -> f64 {
+    let (lighter, darker) = if luminance1 > luminance2 {
+        (luminance1, luminance2)
+    } else {
+        (luminance2, luminance1)
+    };
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+/// Returns the lightness closest to `from` toward `to` for which `reaches` is true
+///
+/// `reaches(to)` must be true.
+fn search_lightness<F>(from: f64, to: f64, reaches: &F) -> f64
+where
+    F: Fn(f64) -> bool,
+{
+    const ITERATIONS: usize = 32;
+    let (mut near, mut far) = (from, to);
+    for _ in 0..ITERATIONS {
+        let mid = (near + far) / 2.0;
+        if reaches(mid) {
+            far = mid;
+        } else {
+            near = mid;
+        }
+    }
+    far
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::hsl::Hsl;
+    use angle::Deg;
+    use approx::*;
+
+    #[test]
+    fn test_contrast_ratio() {
+        let black = Rgb::new(0u8, 0, 0);
+        let white = Rgb::new(255u8, 255, 255);
+        assert_relative_eq!(contrast_ratio(&black, &white), 21.0);
+        assert_relative_eq!(contrast_ratio(&white, &black), 21.0);
+        assert_relative_eq!(contrast_ratio(&white, &white), 1.0);
+        assert_relative_eq!(
+            relative_luminance(&Rgb::new(1.0, 0.0, 0.0)),
+            0.2126,
+            epsilon = 1e-4
+        );
+        assert_relative_eq!(
+            contrast_ratio(&Rgb::new(0.0, 0.0, 1.0), &Rgb::new(1.0, 1.0, 1.0)),
+            8.594,
+            epsilon = 1e-3
+        );
+    }
+
+    #[test]
+    fn test_adjust_for_contrast() {
+        let white = Rgb::new(1.0, 1.0, 1.0);
+        let orange = Rgb::new(1.0, 0.6, 0.2);
+        let adjusted = adjust_for_contrast(&orange, &white, 4.5).unwrap();
+        let ratio = contrast_ratio(&adjusted, &white);
+        assert!((4.5..4.51).contains(&ratio));
+        let hue = |c: &Rgb<f64>| Hsl::<f64, Deg<f64>>::from_color(c).hue();
+        assert_relative_eq!(hue(&adjusted), hue(&orange), epsilon = 3.0);
+
+        // A dark foreground on a mid-dark background is made lighter, being closer
+        let background = Rgb::new(0.25, 0.25, 0.25);
+        let adjusted = adjust_for_contrast(&Rgb::new(0.2, 0.3, 0.2), &background, 3.0).unwrap();
+        assert!(adjusted.green() > 0.5);
+        assert!(contrast_ratio(&adjusted, &background) >= 3.0);
+
+        let black = Rgb::new(0u8, 0, 0);
+        assert_eq!(
+            adjust_for_contrast(&black, &white.color_cast(), 7.0),
+            Ok(black)
+        );
+        let adjusted = adjust_for_contrast(&Rgb::new(30u8, 60, 200), &black, 7.0).unwrap();
+        assert!(contrast_ratio(&adjusted, &black) >= 7.0);
+        assert!(adjusted.blue() > adjusted.red());
+
+        let error = adjust_for_contrast(&orange, &white, 22.0).unwrap_err();
+        assert_relative_eq!(error.max_ratio(), 21.0);
+        assert_eq!(error.requested(), 22.0);
+        let gray = Rgb::new(0.5, 0.5, 0.5);
+        let error = adjust_for_contrast(&orange, &gray, 8.0).unwrap_err();
+        assert!(error.max_ratio() < 8.0);
+    }
+}
@@ -1,11 +1,37 @@
-use crate::color::PolarColor;
-use crate::hsi::HsiOutOfGamutMode;
+use crate::alpha::Alpha;
+use crate::channel::{
+    AngularChannelScalar, ChannelFormatCast, FreeChannelScalar, PosNormalChannelScalar,
+};
+use crate::color::{Color, FromTuple, HomogeneousColor, Lerp, PolarColor};
+use crate::color_space::named::SRgb;
+use crate::color_space::{ColorSpace, ConvertFromXyz, ConvertToXyz};
+use crate::ehsi::eHsi;
+use crate::encoding::EncodableColor;
+use crate::hsi::{Hsi, HsiOutOfGamutMode};
+use crate::hsl::Hsl;
+use crate::hsv::Hsv;
+use crate::hwb::Hwb;
+use crate::lab::Lab;
+use crate::lchab::Lchab;
+use crate::lchuv::Lchuv;
+use crate::lms::Lms;
+use crate::luma::Luma;
+use crate::luv::Luv;
+use crate::oklab::Oklab;
+use crate::oklch::Oklch;
+use crate::rgb::Rgb;
+use crate::rgi::Rgi;
+use crate::white_point::UnitWhitePoint;
+use crate::xyy::XyY;
+use crate::xyz::Xyz;
 /// Traits and methods for converting between colors and representations
-use crate::ycbcr::YCbCrOutOfGamutMode;
+use crate::ycbcr::{YCbCr, YCbCrOutOfGamutMode};
 use angle;
 use angle::{Angle, FromAngle};
 use num_traits;
 use num_traits::Float;
+use std::error::Error;
+use std::fmt;
 
 /// Infallibly convert between two color models
 ///
@@ -33,6 +59,698 @@ pub trait FromYCbCr<From> {
     fn from_ycbcr(from: &From, out_of_gamut_mode: YCbCrOutOfGamutMode) -> Self;
 }
 
+/// A color model that converts through a canonical pivot model
+///
+/// Every model can be converted to and from its pivot. Device dependent models, such as `Hsv`,
+/// `Luma` or `YCbCr`, pivot through `Rgb`, and device independent models, such as `Lab` or
+/// `Oklch`, pivot through `Xyz`. Models sharing a pivot can be converted to each other with
+/// [`ConvertColor`](trait.ConvertColor.html), even if no `FromColor` implementation exists for the
+/// pair.
+///
+/// Going between `Rgb` and `Xyz` depends on the color space the `Rgb` values are in. Models of
+/// different families are converted with [`PivotBridge`](trait.PivotBridge.html), which treats
+/// `Rgb` as sRGB in [`convert`](#method.convert), or as any
+/// [`ColorSpace`](color_space/trait.ColorSpace.html) given to [`convert_in`](#method.convert_in).
+pub trait PivotColor: Sized {
+    /// The model conversions are routed through
+    type Pivot;
+    /// Convert `self` to the pivot model
+    fn to_pivot(&self) -> Self::Pivot;
+    /// Construct `Self` from a color in the pivot model
+    fn from_pivot(pivot: &Self::Pivot) -> Self;
+
+    /// Convert `self` to the pivot model, keeping values outside of the pivot's gamut
+    ///
+    /// This is the same as `to_pivot` except for models such as `Hsi` and `YCbCr`, which can
+    /// describe colors the pivot can't. For these, `to_pivot` clips the result while this leaves
+    /// the channels out of range. Integer channels still saturate at the ends of their range.
+    fn to_pivot_unclipped(&self) -> Self::Pivot {
+        self.to_pivot()
+    }
+
+    /// Convert `self` to any model `Dst`
+    ///
+    /// If `Dst` has a different pivot, the `Rgb` values are taken to be in sRGB.
+    fn convert<Dst>(&self) -> Dst
+    where
+        Dst: PivotColor,
+        Self::Pivot: HomogeneousColor
+            + PivotBridge<Dst::Pivot, SRgb<<Self::Pivot as HomogeneousColor>::ChannelFormat>>,
+    {
+        self.convert_in(&SRgb::new())
+    }
+
+    /// Convert `self` to any model `Dst`, using `space` if `Dst` has a different pivot
+    ///
+    /// `space` is ignored when both models share a pivot.
+    fn convert_in<Dst, S>(&self, space: &S) -> Dst
+    where
+        Dst: PivotColor,
+        Self::Pivot: PivotBridge<Dst::Pivot, S>,
+    {
+        Dst::from_pivot(&self.to_pivot().bridge(space))
+    }
+}
+
+/// Convert a pivot color to the pivot `Dst` using the color space `S`
+///
+/// Every pivot converts to itself without using the space. `Rgb` and `Xyz`, and their `Alpha`
+/// forms, convert to each other with the primaries, white point and encoding of the space, taking
+/// the `Rgb` values to be encoded.
+pub trait PivotBridge<Dst, S> {
+    /// Convert `self` to `Dst` using `space`
+    fn bridge(self, space: &S) -> Dst;
+}
+
+impl<P, S> PivotBridge<P, S> for P {
+    fn bridge(self, _space: &S) -> P {
+        self
+    }
+}
+
+impl<T, S> PivotBridge<Xyz<T>, S> for Rgb<T>
+where
+    T: PosNormalChannelScalar + FreeChannelScalar + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+    S: ConvertToXyz<T, Rgb<T>, <S as ColorSpace<T>>::Encoding, OutputColor = Xyz<T>>,
+{
+    fn bridge(self, space: &S) -> Xyz<T> {
+        space.convert_to_xyz(&self.encoded_as(space.encoding()))
+    }
+}
+
+impl<T, S> PivotBridge<Rgb<T>, S> for Xyz<T>
+where
+    T: PosNormalChannelScalar + FreeChannelScalar + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+    S: ConvertFromXyz<T, Xyz<T>, OutputColor = Rgb<T>>,
+{
+    fn bridge(self, space: &S) -> Rgb<T> {
+        space.convert_from_xyz(&self).strip()
+    }
+}
+
+impl<T, S> PivotBridge<Alpha<T, Xyz<T>>, S> for Alpha<T, Rgb<T>>
+where
+    T: PosNormalChannelScalar,
+    Rgb<T>: PivotBridge<Xyz<T>, S> + Color,
+    Xyz<T>: Color,
+{
+    fn bridge(self, space: &S) -> Alpha<T, Xyz<T>> {
+        Alpha::new(self.color().clone().bridge(space), self.alpha())
+    }
+}
+
+impl<T, S> PivotBridge<Alpha<T, Rgb<T>>, S> for Alpha<T, Xyz<T>>
+where
+    T: PosNormalChannelScalar,
+    Xyz<T>: PivotBridge<Rgb<T>, S> + Color,
+    Rgb<T>: Color,
+{
+    fn bridge(self, space: &S) -> Alpha<T, Rgb<T>> {
+        Alpha::new(self.color().clone().bridge(space), self.alpha())
+    }
+}
+
+/// Convert a color to the model `Dst`
+///
+/// This is implemented for every pair of models, by converting `self` to its
+/// [`PivotColor::Pivot`](trait.PivotColor.html#associatedtype.Pivot) and the pivot to `Dst`.
+/// Models of different families are bridged between `Rgb` and `Xyz` in sRGB, as by
+/// [`PivotColor::convert`](trait.PivotColor.html#method.convert). Conversions that may go out of
+/// gamut, such as from `Hsi` or `YCbCr`, clip the result to the pivot's gamut.
+///
+/// `ConvertColor` is meant for bounds in generic code. To choose the destination at the call site,
+/// use [`PivotColor::convert`](trait.PivotColor.html#method.convert), as in
+/// `color.convert::<Lchab<_, D65>>()`.
+///
+/// ## Examples:
+///
+/// ```rust
+/// #[macro_use] extern crate approx;
+/// # extern crate prisma;
+/// extern crate angular_units as angle;
+///
+/// use prisma::{ConvertColor, Hsl, Hwb, Lab, Lchab, Oklch, PivotColor, Rgb, Xyz};
+/// use prisma::white_point::D65;
+/// use angle::Deg;
+///
+/// let hsl: Hsl<f64, Deg<f64>> = Hsl::new(Deg(120.0), 1.0, 0.25);
+/// let hwb = hsl.convert::<Hwb<_, Deg<_>>>();
+/// assert_relative_eq!(hwb.convert::<Rgb<_>>(), Rgb::new(0.0, 0.5, 0.0), epsilon=1e-6);
+///
+/// let lch = Xyz::new(0.3, 0.4, 0.2).convert::<Lchab<f64, D65>>();
+/// let oklch = lch.convert::<Oklch<_, Deg<_>>>();
+/// assert_relative_eq!(oklch.convert::<Lab<_, D65>>(), lch.convert_color(), epsilon=1e-9);
+///
+/// // Models of different families are bridged in sRGB
+/// let hsl_lch: Lchab<f64, D65> = hsl.convert_color();
+/// assert_relative_eq!(hsl_lch.convert::<Hsl<_, Deg<_>>>(), hsl, epsilon=1e-6);
+/// ```
+pub trait ConvertColor<Dst> {
+    /// Convert `self` to `Dst`
+    fn convert_color(&self) -> Dst;
+}
+
+impl<Src, Dst> ConvertColor<Dst> for Src
+where
+    Src: PivotColor,
+    Dst: PivotColor,
+    Src::Pivot: HomogeneousColor
+        + PivotBridge<Dst::Pivot, SRgb<<Src::Pivot as HomogeneousColor>::ChannelFormat>>,
+{
+    fn convert_color(&self) -> Dst {
+        self.convert()
+    }
+}
+
+/// A channel found outside of the valid range of its model
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct OutOfRangeChannel {
+    /// The index of the channel in the color
+    pub index: usize,
+    /// The name of the channel, such as `"red"`
+    pub name: &'static str,
+    /// The value of the channel, normalized for bounded channels
+    pub value: f64,
+    /// How far `value` is past the nearest end of the valid range, always positive
+    pub excess: f64,
+}
+
+/// An error returned when a conversion would leave the valid range of a color model
+#[derive(Clone, Debug, PartialEq)]
+pub struct OutOfRangeError {
+    channels: Vec<OutOfRangeChannel>,
+}
+
+impl OutOfRangeError {
+    /// Construct an `OutOfRangeError` from the channels that are out of range
+    pub fn new(channels: Vec<OutOfRangeChannel>) -> Self {
+        OutOfRangeError { channels }
+    }
+    /// Returns the channels that are out of range
+    pub fn channels(&self) -> &[OutOfRangeChannel] {
+        &self.channels
+    }
+    /// Returns the largest distance of any channel from its valid range
+    pub fn magnitude(&self) -> f64 {
+        self.channels
+            .iter()
+            .fold(0.0, |max, channel| max.max(channel.excess))
+    }
+}
+
+impl fmt::Display for OutOfRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "color is out of range:")?;
+        for (i, channel) in self.channels.iter().enumerate() {
+            let sep = if i == 0 { " " } else { ", " };
+            write!(
+                f,
+                "{}{} = {} ({} past the bound)",
+                sep, channel.name, channel.value, channel.excess
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for OutOfRangeError {}
+
+/// A color whose channels can be checked against the valid range of its model
+pub trait ChannelRange {
+    /// Return every channel that is outside of its valid range
+    ///
+    /// An empty `Vec` means the color is valid.
+    fn out_of_range_channels(&self) -> Vec<OutOfRangeChannel>;
+}
+
+/// Convert a color to the model `Dst`, failing if it would leave the valid range
+///
+/// This is the fallible counterpart of [`ConvertColor`](trait.ConvertColor.html). The color is
+/// converted to the pivot without clipping, and if any channel of the pivot is out of range an
+/// [`OutOfRangeError`](struct.OutOfRangeError.html) is returned describing them, instead of
+/// silently clipping the color or producing invalid channels.
+///
+/// ## Examples:
+///
+/// ```rust
+/// # extern crate prisma;
+///
+/// use prisma::{Rgb, TryConvert};
+/// use prisma::ycbcr::YCbCrJpeg;
+///
+/// let inside = YCbCrJpeg::new(0.5, 0.1, -0.1);
+/// let rgb: Rgb<f64> = inside.try_into_color().unwrap();
+/// assert!(rgb.red() < rgb.blue());
+///
+/// let outside = YCbCrJpeg::new(0.5, 0.5, 0.5);
+/// let err = TryConvert::<Rgb<f64>>::try_into_color(&outside).unwrap_err();
+/// assert_eq!(err.channels()[0].name, "red");
+/// assert_eq!(err.channels().len(), 3);
+/// assert!(err.magnitude() > 0.3);
+/// ```
+pub trait TryConvert<Dst> {
+    /// Convert `self` to `Dst`, or return the channels that would be out of range
+    fn try_into_color(&self) -> Result<Dst, OutOfRangeError>;
+}
+
+impl<Src, Dst> TryConvert<Dst> for Src
+where
+    Src: PivotColor,
+    Src::Pivot: ChannelRange,
+    Dst: PivotColor<Pivot = Src::Pivot>,
+{
+    fn try_into_color(&self) -> Result<Dst, OutOfRangeError> {
+        let pivot = self.to_pivot_unclipped();
+        let channels = pivot.out_of_range_channels();
+        if channels.is_empty() {
+            Ok(Dst::from_pivot(&pivot))
+        } else {
+            Err(OutOfRangeError::new(channels))
+        }
+    }
+}
+
+fn check_channel_range(
+    out: &mut Vec<OutOfRangeChannel>,
+    index: usize,
+    name: &'static str,
+    value: f64,
+    (min, max): (f64, f64),
+) {
+    let excess = if value < min {
+        min - value
+    } else if value > max {
+        value - max
+    } else if value.is_nan() {
+        f64::INFINITY
+    } else {
+        return;
+    };
+    out.push(OutOfRangeChannel {
+        index,
+        name,
+        value,
+        excess,
+    });
+}
+
+impl<T> ChannelRange for Rgb<T>
+where
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+{
+    fn out_of_range_channels(&self) -> Vec<OutOfRangeChannel> {
+        let mut out = Vec::new();
+        check_channel_range(&mut out, 0, "red", self.red().cast(), (0.0, 1.0));
+        check_channel_range(&mut out, 1, "green", self.green().cast(), (0.0, 1.0));
+        check_channel_range(&mut out, 2, "blue", self.blue().cast(), (0.0, 1.0));
+        out
+    }
+}
+
+impl<T> ChannelRange for Xyz<T>
+where
+    T: FreeChannelScalar,
+{
+    fn out_of_range_channels(&self) -> Vec<OutOfRangeChannel> {
+        let mut out = Vec::new();
+        let cast = |v: T| num_traits::cast::<_, f64>(v).unwrap();
+        let range = (0.0, f64::INFINITY);
+        check_channel_range(&mut out, 0, "x", cast(self.x()), range);
+        check_channel_range(&mut out, 1, "y", cast(self.y()), range);
+        check_channel_range(&mut out, 2, "z", cast(self.z()), range);
+        out
+    }
+}
+
+impl<T, InnerColor> ChannelRange for Alpha<T, InnerColor>
+where
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+    InnerColor: Color + ChannelRange,
+{
+    fn out_of_range_channels(&self) -> Vec<OutOfRangeChannel> {
+        let mut out = self.color().out_of_range_channels();
+        let index = InnerColor::num_channels() as usize;
+        check_channel_range(&mut out, index, "alpha", self.alpha().cast(), (0.0, 1.0));
+        out
+    }
+}
+
+fn check_hue_range<A>(out: &mut Vec<OutOfRangeChannel>, index: usize, hue: A)
+where
+    A: AngularChannelScalar,
+    A::Scalar: Float,
+{
+    let period = num_traits::cast::<_, f64>(A::period()).unwrap();
+    let value = num_traits::cast::<_, f64>(hue.scalar()).unwrap();
+    check_channel_range(out, index, "hue", value, (0.0, period));
+}
+
+impl<T> ChannelRange for Luma<T>
+where
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+{
+    fn out_of_range_channels(&self) -> Vec<OutOfRangeChannel> {
+        let mut out = Vec::new();
+        check_channel_range(&mut out, 0, "luma", self.luma().cast(), (0.0, 1.0));
+        out
+    }
+}
+
+macro_rules! impl_channel_range_hue {
+    (<$($gen:ident),*> $name:ty where { $($bounds:tt)* } ($first:ident, $second:ident)) => {
+        impl<$($gen),*> ChannelRange for $name
+        where
+            $($bounds)*
+        {
+            fn out_of_range_channels(&self) -> Vec<OutOfRangeChannel> {
+                let mut out = Vec::new();
+                check_hue_range(&mut out, 0, self.hue());
+                let (first, second) = (self.$first().cast(), self.$second().cast());
+                check_channel_range(&mut out, 1, stringify!($first), first, (0.0, 1.0));
+                check_channel_range(&mut out, 2, stringify!($second), second, (0.0, 1.0));
+                out
+            }
+        }
+    };
+}
+
+impl_channel_range_hue!(<T, A> Hsv<T, A> where {
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+    A: AngularChannelScalar,
+    A::Scalar: Float,
+} (saturation, value));
+impl_channel_range_hue!(<T, A> Hsl<T, A> where {
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+    A: AngularChannelScalar,
+    A::Scalar: Float,
+} (saturation, lightness));
+impl_channel_range_hue!(<T, A> Hwb<T, A> where {
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+    A: AngularChannelScalar,
+    A::Scalar: Float,
+} (whiteness, blackness));
+impl_channel_range_hue!(<T, A> Hsi<T, A> where {
+    T: PosNormalChannelScalar + Float + ChannelFormatCast<f64>,
+    A: AngularChannelScalar + Angle<Scalar = T>,
+} (saturation, intensity));
+impl_channel_range_hue!(<T, A> eHsi<T, A> where {
+    T: PosNormalChannelScalar + Float + ChannelFormatCast<f64>,
+    A: AngularChannelScalar + Angle<Scalar = T>,
+} (saturation, intensity));
+
+macro_rules! impl_channel_range_lch {
+    (<$($gen:ident),*> $name:ty where { $($bounds:tt)* }) => {
+        impl<$($gen),*> ChannelRange for $name
+        where
+            $($bounds)*
+        {
+            fn out_of_range_channels(&self) -> Vec<OutOfRangeChannel> {
+                let mut out = Vec::new();
+                let cast = |v: T| num_traits::cast::<_, f64>(v).unwrap();
+                let range = (0.0, f64::INFINITY);
+                check_channel_range(&mut out, 0, "L", cast(self.L()), range);
+                check_channel_range(&mut out, 1, "chroma", cast(self.chroma()), range);
+                check_hue_range(&mut out, 2, self.hue());
+                out
+            }
+        }
+    };
+}
+
+impl_channel_range_lch!(<T, W, A> Lchab<T, W, A> where {
+    T: FreeChannelScalar,
+    W: UnitWhitePoint<T>,
+    A: AngularChannelScalar,
+    A::Scalar: Float,
+});
+impl_channel_range_lch!(<T, W, A> Lchuv<T, W, A> where {
+    T: FreeChannelScalar,
+    W: UnitWhitePoint<T>,
+    A: AngularChannelScalar,
+    A::Scalar: Float,
+});
+impl_channel_range_lch!(<T, A> Oklch<T, A> where {
+    T: FreeChannelScalar,
+    A: AngularChannelScalar,
+    A::Scalar: Float,
+});
+
+/// A color that can be constructed from untrusted channel values
+///
+/// `try_from_tuple` constructs the color like [`FromTuple`](trait.FromTuple.html), then checks
+/// its channels with [`ChannelRange`](trait.ChannelRange.html), returning an
+/// [`OutOfRangeError`](struct.OutOfRangeError.html) describing every channel outside of the valid
+/// range of the model instead of a color that would misbehave later. Hues must lie within one
+/// period of their angular unit, such as `[0, 360]` degrees.
+///
+/// It is implemented for every color implementing both traits. Most colors also have an inherent
+/// `try_from_channels` constructor taking the same arguments as `new`.
+///
+/// Note that with the `debug-validate` feature, NaN and infinite channels still panic in debug
+/// builds before they can be checked.
+///
+/// ## Examples:
+///
+/// ```rust
+/// # extern crate prisma;
+/// extern crate angular_units as angle;
+///
+/// use prisma::{Hsv, Rgb, TryFromTuple};
+/// use angle::Deg;
+///
+/// assert_eq!(Rgb::try_from_tuple((0.5, 0.25, 1.0)), Ok(Rgb::new(0.5, 0.25, 1.0)));
+///
+/// let err = Hsv::try_from_channels(Deg(400.0), 0.5, 1.5).unwrap_err();
+/// assert_eq!(err.channels()[0].name, "hue");
+/// assert_eq!(err.channels()[0].excess, 40.0);
+/// assert_eq!(err.channels()[1].name, "value");
+/// ```
+pub trait TryFromTuple: FromTuple + ChannelRange {
+    /// Construct `Self` from a tuple of channel values, or return the channels that are out of
+    /// range
+    fn try_from_tuple(values: Self::ChannelsTuple) -> Result<Self, OutOfRangeError> {
+        let color = Self::from_tuple(values);
+        let channels = color.out_of_range_channels();
+        if channels.is_empty() {
+            Ok(color)
+        } else {
+            Err(OutOfRangeError::new(channels))
+        }
+    }
+}
+
+impl<C> TryFromTuple for C where C: FromTuple + ChannelRange {}
+
+macro_rules! impl_try_from_channels {
+    (<$($gen:ident),*> $name:ident<$($param:ty),*> where { $($bounds:tt)* }
+        ($($arg:ident: $arg_ty:ty),*)) => {
+        impl<$($gen),*> $name<$($param),*>
+        where
+            $($bounds)*
+        {
+            /// Construct a new color, or return the channels that are out of range
+            ///
+            /// This is the checked counterpart of `new`, see
+            /// [`TryFromTuple`](trait.TryFromTuple.html).
+            #[allow(non_snake_case)]
+            pub fn try_from_channels($($arg: $arg_ty),*) -> Result<Self, OutOfRangeError> {
+                Self::try_from_tuple(($($arg,)*))
+            }
+        }
+    };
+}
+
+impl_try_from_channels!(<T> Rgb<T> where { T: PosNormalChannelScalar + ChannelFormatCast<f64> }
+    (red: T, green: T, blue: T));
+impl_try_from_channels!(<T> Luma<T> where { T: PosNormalChannelScalar + ChannelFormatCast<f64> }
+    (luma: T));
+impl_try_from_channels!(<T, A> Hsv<T, A> where {
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+    A: AngularChannelScalar,
+    A::Scalar: Float,
+} (hue: A, saturation: T, value: T));
+impl_try_from_channels!(<T, A> Hsl<T, A> where {
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+    A: AngularChannelScalar,
+    A::Scalar: Float,
+} (hue: A, saturation: T, lightness: T));
+impl_try_from_channels!(<T, A> Hwb<T, A> where {
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+    A: AngularChannelScalar,
+    A::Scalar: Float,
+} (hue: A, whiteness: T, blackness: T));
+impl_try_from_channels!(<T, A> Hsi<T, A> where {
+    T: PosNormalChannelScalar + Float + ChannelFormatCast<f64>,
+    A: AngularChannelScalar + Angle<Scalar = T>,
+} (hue: A, saturation: T, intensity: T));
+impl_try_from_channels!(<T, A> eHsi<T, A> where {
+    T: PosNormalChannelScalar + Float + ChannelFormatCast<f64>,
+    A: AngularChannelScalar + Angle<Scalar = T>,
+} (hue: A, saturation: T, intensity: T));
+impl_try_from_channels!(<T> Xyz<T> where { T: FreeChannelScalar } (x: T, y: T, z: T));
+impl_try_from_channels!(<T, W, A> Lchab<T, W, A> where {
+    T: FreeChannelScalar,
+    W: UnitWhitePoint<T>,
+    A: AngularChannelScalar,
+    A::Scalar: Float,
+} (L: T, chroma: T, hue: A));
+impl_try_from_channels!(<T, W, A> Lchuv<T, W, A> where {
+    T: FreeChannelScalar,
+    W: UnitWhitePoint<T>,
+    A: AngularChannelScalar,
+    A::Scalar: Float,
+} (L: T, chroma: T, hue: A));
+impl_try_from_channels!(<T, A> Oklch<T, A> where {
+    T: FreeChannelScalar,
+    A: AngularChannelScalar,
+    A::Scalar: Float,
+} (L: T, chroma: T, hue: A));
+
+macro_rules! impl_pivot_color {
+    (<$($gen:ident),*> $name:ty => $pivot:ty) => {
+        impl<$($gen),*> PivotColor for $name
+        where
+            $name: FromColor<$pivot>,
+            $pivot: FromColor<$name>,
+        {
+            type Pivot = $pivot;
+            fn to_pivot(&self) -> $pivot {
+                <$pivot>::from_color(self)
+            }
+            fn from_pivot(pivot: &$pivot) -> Self {
+                <$name>::from_color(pivot)
+            }
+        }
+    };
+    (<$($gen:ident),*> $name:ty => $via:ty => $pivot:ty) => {
+        impl<$($gen),*> PivotColor for $name
+        where
+            $name: FromColor<$via>,
+            $via: FromColor<$name> + PivotColor<Pivot = $pivot>,
+        {
+            type Pivot = $pivot;
+            fn to_pivot(&self) -> $pivot {
+                <$via>::from_color(self).to_pivot()
+            }
+            fn to_pivot_unclipped(&self) -> $pivot {
+                <$via>::from_color(self).to_pivot_unclipped()
+            }
+            fn from_pivot(pivot: &$pivot) -> Self {
+                <$name>::from_color(&<$via>::from_pivot(pivot))
+            }
+        }
+    };
+}
+
+impl_pivot_color!(<T> Rgb<T> => Rgb<T>);
+impl_pivot_color!(<T> Luma<T> => Rgb<T>);
+impl_pivot_color!(<T> Rgi<T> => Rgb<T>);
+impl_pivot_color!(<T, A> Hsv<T, A> => Rgb<T>);
+impl_pivot_color!(<T, A> Hsl<T, A> => Rgb<T>);
+impl_pivot_color!(<T, A> Hwb<T, A> => Rgb<T>);
+impl_pivot_color!(<T, A> eHsi<T, A> => Rgb<T>);
+impl_pivot_color!(<T> XyY<T> => Xyz<T>);
+impl_pivot_color!(<T> Oklab<T> => Xyz<T>);
+impl_pivot_color!(<T, M> Lms<T, M> => Xyz<T>);
+impl_pivot_color!(<T, A> Oklch<T, A> => Oklab<T> => Xyz<T>);
+impl_pivot_color!(<T, W, A> Lchab<T, W, A> => Lab<T, W> => Xyz<T>);
+impl_pivot_color!(<T, W, A> Lchuv<T, W, A> => Luv<T, W> => Xyz<T>);
+
+impl<T, A> PivotColor for Hsi<T, A>
+where
+    Hsi<T, A>: FromColor<Rgb<T>>,
+    Rgb<T>: FromHsi<Hsi<T, A>>,
+{
+    type Pivot = Rgb<T>;
+    fn to_pivot(&self) -> Rgb<T> {
+        Rgb::from_hsi(self, HsiOutOfGamutMode::Clip)
+    }
+    fn to_pivot_unclipped(&self) -> Rgb<T> {
+        Rgb::from_hsi(self, HsiOutOfGamutMode::Preserve)
+    }
+    fn from_pivot(pivot: &Rgb<T>) -> Self {
+        Hsi::from_color(pivot)
+    }
+}
+
+impl<T, M> PivotColor for YCbCr<T, M>
+where
+    YCbCr<T, M>: FromColor<Rgb<T>>,
+    Rgb<T>: FromYCbCr<YCbCr<T, M>>,
+{
+    type Pivot = Rgb<T>;
+    fn to_pivot(&self) -> Rgb<T> {
+        Rgb::from_ycbcr(self, YCbCrOutOfGamutMode::Clip)
+    }
+    fn to_pivot_unclipped(&self) -> Rgb<T> {
+        Rgb::from_ycbcr(self, YCbCrOutOfGamutMode::Preserve)
+    }
+    fn from_pivot(pivot: &Rgb<T>) -> Self {
+        YCbCr::from_color(pivot)
+    }
+}
+
+impl<T> PivotColor for Xyz<T>
+where
+    T: FreeChannelScalar,
+{
+    type Pivot = Xyz<T>;
+    fn to_pivot(&self) -> Xyz<T> {
+        self.clone()
+    }
+    fn from_pivot(pivot: &Xyz<T>) -> Self {
+        pivot.clone()
+    }
+}
+
+impl<T, W> PivotColor for Lab<T, W>
+where
+    T: FreeChannelScalar,
+    W: UnitWhitePoint<T>,
+{
+    type Pivot = Xyz<T>;
+    fn to_pivot(&self) -> Xyz<T> {
+        self.to_xyz()
+    }
+    fn from_pivot(pivot: &Xyz<T>) -> Self {
+        Lab::from_xyz(pivot, W::default())
+    }
+}
+
+impl<T, W> PivotColor for Luv<T, W>
+where
+    T: FreeChannelScalar + fmt::Display,
+    W: UnitWhitePoint<T>,
+{
+    type Pivot = Xyz<T>;
+    fn to_pivot(&self) -> Xyz<T> {
+        self.to_xyz()
+    }
+    fn from_pivot(pivot: &Xyz<T>) -> Self {
+        Luv::from_xyz(pivot, W::default())
+    }
+}
+
+impl<T, InnerColor> PivotColor for Alpha<T, InnerColor>
+where
+    T: PosNormalChannelScalar,
+    InnerColor: Color + PivotColor,
+    InnerColor::Pivot: Color,
+{
+    type Pivot = Alpha<T, InnerColor::Pivot>;
+    fn to_pivot(&self) -> Self::Pivot {
+        Alpha::new(self.color().to_pivot(), self.alpha())
+    }
+    fn to_pivot_unclipped(&self) -> Self::Pivot {
+        Alpha::new(self.color().to_pivot_unclipped(), self.alpha())
+    }
+    fn from_pivot(pivot: &Self::Pivot) -> Self {
+        Alpha::new(InnerColor::from_pivot(pivot.color()), pivot.alpha())
+    }
+}
+
 /// Return the chroma of a color
 pub trait GetChroma {
     /// The type of the returned chroma value
@@ -66,3 +784,219 @@ where
 
     (num_traits::cast(hue_seg).unwrap(), scaled_hue - hue_seg)
 }
+
+/// Mix two colors by interpolating them in the color model `Space`
+///
+/// This mirrors the CSS `color-mix()` function: `color1` and `color2` are converted to `Space`,
+/// interpolated with [`Lerp`](../trait.Lerp.html) at `pos`, and the result is converted back. The
+/// model used for mixing changes the result considerably. Mixing in `Rgb` blends channels
+/// directly, mixing in a polar model such as `Hsl` travels around the hue circle, and mixing in a
+/// perceptually uniform model such as `Oklab` gives the most even perceived transitions.
+///
+/// ## Examples:
+///
+/// ```rust
+/// #[macro_use] extern crate approx;
+/// # extern crate prisma;
+/// extern crate angular_units as angle;
+///
+/// use prisma::{mix_in, Hsl, Lerp, Rgb};
+/// use angle::Deg;
+///
+/// let red = Rgb::new(1.0, 0.0, 0.0);
+/// let yellow = Rgb::new(1.0, 1.0, 0.0);
+/// assert_relative_eq!(red.lerp(&yellow, 0.5), Rgb::new(1.0, 0.5, 0.0));
+///
+/// // Mixing in Hsl keeps the saturation and lightness of the inputs
+/// let mixed = mix_in::<Hsl<f64, Deg<f64>>, _>(&red, &Rgb::new(0.0, 1.0, 0.0), 0.5);
+/// assert_relative_eq!(mixed, yellow, epsilon=1e-6);
+/// ```
+pub fn mix_in<Space, C>(color1: &C, color2: &C, pos: Space::Position) -> C
+where
+    Space: FromColor<C> + Lerp,
+    C: FromColor<Space>,
+{
+    let mixed = Space::from_color(color1).lerp(&Space::from_color(color2), pos);
+    C::from_color(&mixed)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::alpha::{Hsla, Rgba};
+    use crate::white_point::D65;
+    use crate::ycbcr::YCbCrJpeg;
+    use angle::{Deg, Rad};
+    use approx::*;
+
+    #[test]
+    fn test_mix_in() {
+        let red = Rgb::new(1.0, 0.0, 0.0);
+        let blue = Rgb::new(0.0, 0.0, 1.0);
+        assert_relative_eq!(
+            mix_in::<Hsv<f64, Deg<f64>>, _>(&red, &blue, 0.5),
+            Rgb::new(1.0, 0.0, 1.0),
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(
+            mix_in::<Hsv<f64, Deg<f64>>, _>(&red, &blue, 0.0),
+            red,
+            epsilon = 1e-6
+        );
+        let red_a = Rgba::new(red, 1.0);
+        let blue_a = Rgba::new(blue, 0.0);
+        assert_relative_eq!(
+            mix_in::<Hsla<f64, Deg<f64>>, _>(&red_a, &blue_a, 0.5),
+            Rgba::new(Rgb::new(1.0, 0.0, 1.0), 0.5),
+            epsilon = 1e-6
+        );
+
+        let c1 = Xyz::new(0.4, 0.3, 0.2);
+        let c2 = Xyz::new(0.1, 0.2, 0.5);
+        let mid = mix_in::<Oklab<f64>, _>(&c1, &c2, 0.5);
+        let expected = Oklab::from_color(&c1).lerp(&Oklab::from_color(&c2), 0.5);
+        assert_relative_eq!(Oklab::from_color(&mid), expected, epsilon = 1e-9);
+        let mid_lch = mix_in::<Oklch<f64, Deg<f64>>, _>(&Oklab::from_color(&c1), &expected, 1.0);
+        assert_relative_eq!(mid_lch, expected, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_convert_via_pivot() {
+        let hsv = Hsv::new(Deg(200.0), 0.5, 0.75);
+        let rgb = Rgb::from_color(&hsv);
+        assert_relative_eq!(hsv.convert::<Rgb<_>>(), rgb, epsilon = 1e-9);
+        assert_relative_eq!(
+            hsv.convert::<Hsl<_, Deg<_>>>(),
+            Hsl::from_color(&rgb),
+            epsilon = 1e-9
+        );
+        let ycbcr: YCbCrJpeg<f64> = hsv.convert();
+        assert_relative_eq!(ycbcr.convert::<Hsv<_, Deg<_>>>(), hsv, epsilon = 1e-3);
+        let luma: Luma<f64> = Hsi::new(Deg(0.0), 0.0, 0.5).convert();
+        assert_relative_eq!(luma, Luma::new(0.5), epsilon = 1e-9);
+
+        let hsla = Hsla::new(Hsl::new(Deg(30.0), 0.8, 0.4), 0.25);
+        let rgba: Rgba<f64> = hsla.convert();
+        assert_relative_eq!(rgba, Rgba::from_color(&hsla), epsilon = 1e-9);
+
+        let xyz = Xyz::new(0.4, 0.3, 0.2);
+        let oklch: Oklch<f64, Deg<f64>> = xyz.convert_color();
+        assert_relative_eq!(
+            oklch,
+            Oklch::from_color(&Oklab::from_color(&xyz)),
+            epsilon = 1e-9
+        );
+        let luv = oklch.convert::<Luv<_, D65>>();
+        assert_relative_eq!(luv, Luv::from_xyz(&xyz, D65), epsilon = 1e-6);
+        assert_relative_eq!(
+            luv.convert::<XyY<_>>().convert::<Xyz<_>>(),
+            xyz,
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn test_convert_between_families() {
+        use crate::color_space::named::{AdobeRgb, SRgb};
+        use crate::encoding::EncodableColor;
+
+        let rgb = Rgb::new(0.8, 0.4, 0.1);
+        let xyz = SRgb::new().convert_to_xyz(&rgb.srgb_encoded());
+        assert_relative_eq!(rgb.convert::<Xyz<_>>(), xyz, epsilon = 1e-9);
+        assert_relative_eq!(xyz.convert::<Rgb<_>>(), rgb, epsilon = 1e-9);
+
+        let hsv = Hsv::new(Deg(200.0), 0.5, 0.75);
+        let lch: Lchab<f64, D65, Deg<f64>> = hsv.convert_color();
+        assert_relative_eq!(
+            lch,
+            Lchab::from_color(&Lab::from_xyz(&hsv.convert::<Xyz<_>>(), D65)),
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(lch.convert::<Hsv<_, Deg<_>>>(), hsv, epsilon = 1e-6);
+
+        let adobe = AdobeRgb::new();
+        let adobe_xyz = adobe.convert_to_xyz(&rgb.encoded_as(adobe.encoding()));
+        assert_relative_eq!(
+            rgb.convert_in::<Xyz<_>, _>(&adobe),
+            adobe_xyz,
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            adobe_xyz.convert_in::<Rgb<_>, _>(&adobe),
+            rgb,
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            hsv.convert_in::<Hsl<_, Deg<_>>, _>(&adobe),
+            hsv.convert::<Hsl<_, Deg<_>>>(),
+            epsilon = 1e-9
+        );
+
+        let rgba = Rgba::new(rgb, 0.25);
+        let oklab: Alpha<f64, Oklab<f64>> = rgba.convert();
+        assert_relative_eq!(*oklab.color(), Oklab::from_color(&xyz), epsilon = 1e-9);
+        assert_relative_eq!(oklab.alpha(), 0.25);
+        assert_relative_eq!(oklab.convert::<Rgba<_>>(), rgba, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_try_convert() {
+        let hsi = Hsi::new(Deg(0.0), 1.0, 0.6);
+        let err = TryConvert::<Hsv<f64, Deg<f64>>>::try_into_color(&hsi).unwrap_err();
+        assert_eq!(err.channels().len(), 1);
+        assert_eq!(err.channels()[0].index, 0);
+        assert_relative_eq!(err.channels()[0].value, 1.8, epsilon = 1e-9);
+        assert_relative_eq!(err.magnitude(), 0.8, epsilon = 1e-9);
+        assert!(err.to_string().starts_with("color is out of range: red = "));
+
+        let dim = Hsi::new(Deg(0.0), 1.0, 0.2);
+        let hsv: Hsv<f64, Deg<f64>> = dim.try_into_color().unwrap();
+        assert_relative_eq!(hsv, dim.convert(), epsilon = 1e-9);
+
+        let rgba = Rgba::new(Rgb::new(0.5, 0.5, 0.5), 1.5);
+        let err = TryConvert::<Hsla<f64, Deg<f64>>>::try_into_color(&rgba).unwrap_err();
+        assert_eq!(err.channels()[0].name, "alpha");
+        assert_eq!(err.channels()[0].index, 3);
+
+        let negative = Xyz::new(-0.1, 0.5, 0.5);
+        let result: Result<Lab<f64, D65>, _> = negative.try_into_color();
+        assert_eq!(result.unwrap_err().channels()[0].name, "x");
+        let lab: Lab<f64, D65> = Oklab::new(0.5, 0.1, 0.0).try_into_color().unwrap();
+        assert!(lab.a() > 0.0);
+    }
+
+    #[test]
+    fn test_try_from_channels() {
+        assert_eq!(
+            Rgb::try_from_channels(255u8, 0, 128),
+            Ok(Rgb::new(255, 0, 128))
+        );
+        let err = Rgb::try_from_channels(1.25, 0.5, -0.5).unwrap_err();
+        assert_eq!(err.channels().len(), 2);
+        assert_eq!(err.channels()[0].name, "red");
+        assert_relative_eq!(err.channels()[1].excess, 0.5);
+        assert_eq!(err.channels()[1].index, 2);
+
+        assert!(Hsl::try_from_channels(Deg(360.0), 0.5, 1.0).is_ok());
+        let err = Hsl::try_from_channels(Rad(-1.0), 0.5, 0.5).unwrap_err();
+        assert_eq!(err.channels()[0].name, "hue");
+        assert_relative_eq!(err.channels()[0].excess, 1.0);
+        if !cfg!(feature = "debug-validate") {
+            let nan = Hwb::try_from_channels(Deg(10.0), f64::NAN, 0.0).unwrap_err();
+            assert_eq!(nan.channels()[0].name, "whiteness");
+        }
+
+        let lch = Lchab::<f64, D65, _>::try_from_channels(50.0, -2.0, Deg(720.0)).unwrap_err();
+        let names: Vec<_> = lch.channels().iter().map(|c| c.name).collect();
+        assert_eq!(names, vec!["chroma", "hue"]);
+        assert_relative_eq!(lch.magnitude(), 360.0);
+        assert!(Luma::try_from_channels(0.5f32).is_ok());
+
+        let rgba = Rgba::try_from_tuple(((0.5, 0.5, 0.5), 2.0)).unwrap_err();
+        assert_eq!(rgba.channels()[0].name, "alpha");
+        assert_eq!(
+            Oklch::try_from_tuple((0.5, 0.1, Deg(90.0))),
+            Ok(Oklch::new(0.5, 0.1, Deg(90.0)))
+        );
+    }
+}
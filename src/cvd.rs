@@ -0,0 +1,387 @@
+//! Simulation and correction of color vision deficiencies
+//!
+//! About 8% of men and 0.5% of women have some form of color vision deficiency (CVD), where one
+//! type of cone in the eye is missing or has shifted sensitivity. [`simulate`](fn.simulate.html)
+//! shows how a color appears to someone with a deficiency, while
+//! [`daltonize`](fn.daltonize.html) adjusts a color so that the contrast lost to the deficiency is
+//! moved into channels that remain visible.
+//!
+//! [`check_palette`](fn.check_palette.html) verifies that the colors of a palette stay
+//! distinguishable for viewers with each deficiency.
+//!
+//! Simulation uses the model of Machado, Oliveira and Fernandes (2009). All functions operate on
+//! linear `Rgb` values with sRGB primaries; decode encoded colors first.
+
+use crate::channel::{FreeChannelScalar, PosNormalChannelScalar};
+use crate::color::{Bounded, Color};
+use crate::color_space::named::SRgb;
+use crate::color_space::ColorSpace;
+use crate::convert::FromColor;
+use crate::linalg::Matrix3;
+use crate::oklab::Oklab;
+use crate::rgb::Rgb;
+use crate::xyz::Xyz;
+use num_traits::{cast, Float};
+
+/// A type of color vision deficiency
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Deficiency {
+    /// Missing or anomalous long-wavelength (red) cones
+    Protanopia,
+    /// Missing or anomalous medium-wavelength (green) cones
+    Deuteranopia,
+    /// Missing or anomalous short-wavelength (blue) cones
+    Tritanopia,
+}
+
+impl Deficiency {
+    /// Every type of deficiency
+    pub const ALL: [Deficiency; 3] = [
+        Deficiency::Protanopia,
+        Deficiency::Deuteranopia,
+        Deficiency::Tritanopia,
+    ];
+
+    /// Returns the simulation matrix for full severity, operating on linear sRGB
+    fn simulation_matrix<T>(self) -> Matrix3<T>
+    where
+        T: Float,
+    {
+        let values = match self {
+            Deficiency::Protanopia => [
+                0.152286, 1.052583, -0.204868, 0.114503, 0.786281, 0.099216, -0.003882, -0.048116,
+                1.051998,
+            ],
+            Deficiency::Deuteranopia => [
+                0.367322, 0.860646, -0.227968, 0.280085, 0.672501, 0.047413, -0.011820, 0.042940,
+                0.968881,
+            ],
+            Deficiency::Tritanopia => [
+                1.255528, -0.076749, -0.178779, -0.078411, 0.930809, 0.147602, 0.004733, 0.691367,
+                0.303900,
+            ],
+        };
+        Matrix3::new(cast_matrix(values))
+    }
+
+    /// Returns the matrix moving the error of a simulation into channels that remain visible
+    fn correction_matrix<T>(self) -> Matrix3<T>
+    where
+        T: Float,
+    {
+        let values = match self {
+            Deficiency::Protanopia | Deficiency::Deuteranopia => {
+                [0.0, 0.0, 0.0, 0.7, 1.0, 0.0, 0.7, 0.0, 1.0]
+            }
+            Deficiency::Tritanopia => [1.0, 0.0, 0.7, 0.0, 1.0, 0.7, 0.0, 0.0, 0.0],
+        };
+        Matrix3::new(cast_matrix(values))
+    }
+}
+
+/// Simulate how a linear `Rgb` color appears to someone with `deficiency`
+///
+/// `severity` ranges from 0, for normal color vision, to 1, for a complete lack of the affected
+/// cone type. Intermediate values model anomalous trichromacy. The result is clamped to [0, 1].
+///
+/// ## Examples:
+///
+/// ```rust
+/// #[macro_use] extern crate approx;
+/// # extern crate prisma;
+///
+/// use prisma::Rgb;
+/// use prisma::cvd::{simulate, Deficiency};
+///
+/// // Red and green look alike without red cones
+/// let red = simulate(&Rgb::new(0.8, 0.2, 0.1), Deficiency::Protanopia, 1.0);
+/// let green = simulate(&Rgb::new(0.2, 0.287, 0.102), Deficiency::Protanopia, 1.0);
+/// assert_relative_eq!(red, green, epsilon=1e-3);
+/// ```
+pub fn simulate<T>(color: &Rgb<T>, deficiency: Deficiency, severity: T) -> Rgb<T>
+where
+    T: Float + PosNormalChannelScalar,
+{
+    simulate_unclamped(color, deficiency, severity).normalize()
+}
+
+/// Adjust a linear `Rgb` color to be easier to distinguish for someone with `deficiency`
+///
+/// This is the daltonization algorithm of Fidaner, Lin and Ozguven: the difference between
+/// `color` and its simulation is the information that is lost, and is added back to the color in
+/// channels the deficiency doesn't affect. `strength` scales the correction, with 0 returning
+/// `color` unchanged and 1 applying the full correction. Neutral colors are never changed. The
+/// result is clamped to [0, 1].
+///
+/// ## Examples:
+///
+/// ```rust
+/// # extern crate prisma;
+///
+/// use prisma::Rgb;
+/// use prisma::cvd::{daltonize, Deficiency};
+///
+/// let red = daltonize(&Rgb::new(1.0, 0.0, 0.0), Deficiency::Protanopia, 1.0);
+/// assert!(red.blue() > 0.5);
+/// ```
+pub fn daltonize<T>(color: &Rgb<T>, deficiency: Deficiency, strength: T) -> Rgb<T>
+where
+    T: Float + PosNormalChannelScalar,
+{
+    let simulated = simulate_unclamped(color, deficiency, T::one());
+    let error = (
+        color.red() - simulated.red(),
+        color.green() - simulated.green(),
+        color.blue() - simulated.blue(),
+    );
+    let (r, g, b) = deficiency.correction_matrix::<T>().transform_vector(error);
+    Rgb::new(
+        color.red() + r * strength,
+        color.green() + g * strength,
+        color.blue() + b * strength,
+    )
+    .normalize()
+}
+
+/// How distinguishable the colors of a palette are under one type of color vision
+///
+/// Returned by [`check_palette`](fn.check_palette.html).
+#[derive(Clone, Debug, PartialEq)]
+pub struct VisionReport<T> {
+    deficiency: Option<Deficiency>,
+    closest_pair: Option<(usize, usize)>,
+    min_distance: T,
+    indistinguishable: Vec<(usize, usize)>,
+}
+
+impl<T> VisionReport<T>
+where
+    T: Clone,
+{
+    /// Returns the simulated deficiency, or `None` for normal color vision
+    pub fn deficiency(&self) -> Option<Deficiency> {
+        self.deficiency
+    }
+    /// Returns the indices of the two palette colors closest together, if there are at least two
+    pub fn closest_pair(&self) -> Option<(usize, usize)> {
+        self.closest_pair
+    }
+    /// Returns the smallest $`\Delta E_{OK}`$ between any two colors, or infinity if the palette
+    /// has fewer than two colors
+    pub fn min_distance(&self) -> T {
+        self.min_distance.clone()
+    }
+    /// Returns the indices of every pair of colors closer together than the threshold
+    pub fn indistinguishable(&self) -> &[(usize, usize)] {
+        &self.indistinguishable
+    }
+    /// Returns true if every pair of colors is at least the threshold apart
+    pub fn is_distinguishable(&self) -> bool {
+        self.indistinguishable.is_empty()
+    }
+}
+
+/// Check how distinguishable the linear `Rgb` colors of `palette` are with each deficiency
+///
+/// Every color is simulated at full severity for each deficiency, and the $`\Delta E_{OK}`$ is
+/// measured between every pair of simulated colors. Pairs closer together than `threshold` are
+/// reported as indistinguishable. A difference of 0.02 is about the smallest that can be seen side
+/// by side; colors that must be told apart at a glance, such as chart series, need several times
+/// that.
+///
+/// The first report is for normal color vision, followed by one for each of
+/// [`Deficiency::ALL`](enum.Deficiency.html#associatedconstant.ALL) in order.
+///
+/// ## Examples:
+///
+/// ```rust
+/// # extern crate prisma;
+///
+/// use prisma::Rgb;
+/// use prisma::cvd::{check_palette, Deficiency};
+///
+/// let palette = [Rgb::new(0.8, 0.1, 0.1), Rgb::new(0.15, 0.3, 0.05), Rgb::new(0.1, 0.2, 0.9)];
+/// let reports = check_palette(&palette, 0.1);
+/// assert!(reports[0].is_distinguishable());
+///
+/// let protanopia = &reports[1];
+/// assert_eq!(protanopia.deficiency(), Some(Deficiency::Protanopia));
+/// assert_eq!(protanopia.indistinguishable(), &[(0, 1)]);
+/// ```
+pub fn check_palette<T>(palette: &[Rgb<T>], threshold: T) -> Vec<VisionReport<T>>
+where
+    T: Float + PosNormalChannelScalar + FreeChannelScalar,
+{
+    let mut reports = vec![vision_report(palette, None, threshold)];
+    for &deficiency in Deficiency::ALL.iter() {
+        let simulated: Vec<_> = palette
+            .iter()
+            .map(|c| simulate(c, deficiency, T::one()))
+            .collect();
+        reports.push(vision_report(&simulated, Some(deficiency), threshold));
+    }
+    reports
+}
+
+fn vision_report<T>(
+    palette: &[Rgb<T>],
+    deficiency: Option<Deficiency>,
+    threshold: T,
+) -> VisionReport<T>
+where
+    T: Float + PosNormalChannelScalar + FreeChannelScalar,
+{
+    let space = SRgb::new();
+    let labs: Vec<_> = palette
+        .iter()
+        .map(|c| {
+            let (x, y, z) = space.apply_transform(c.clone().to_tuple());
+            Oklab::from_color(&Xyz::new(x, y, z))
+        })
+        .collect();
+
+    let mut report = VisionReport {
+        deficiency,
+        closest_pair: None,
+        min_distance: T::infinity(),
+        indistinguishable: Vec::new(),
+    };
+    for (i, lab1) in labs.iter().enumerate() {
+        for (j, lab2) in labs.iter().enumerate().skip(i + 1) {
+            let distance = lab1.distance(lab2);
+            if distance < report.min_distance {
+                report.min_distance = distance;
+                report.closest_pair = Some((i, j));
+            }
+            if distance < threshold {
+                report.indistinguishable.push((i, j));
+            }
+        }
+    }
+    report
+}
+
+fn simulate_unclamped<T>(color: &Rgb<T>, deficiency: Deficiency, severity: T) -> Rgb<T>
+where
+    T: Float + PosNormalChannelScalar,
+{
+    let (r, g, b) = deficiency
+        .simulation_matrix::<T>()
+        .transform_vector(color.clone().to_tuple());
+    Rgb::new(
+        color.red() + (r - color.red()) * severity,
+        color.green() + (g - color.green()) * severity,
+        color.blue() + (b - color.blue()) * severity,
+    )
+}
+
+fn cast_matrix<T: Float>(values: [f64; 9]) -> [T; 9] {
+    let mut out = [T::zero(); 9];
+    for (o, v) in out.iter_mut().zip(values.iter()) {
+        *o = cast(*v).unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use approx::*;
+
+    #[test]
+    fn test_simulate() {
+        let red = Rgb::new(1.0, 0.0, 0.0);
+        assert_relative_eq!(
+            simulate(&red, Deficiency::Protanopia, 1.0),
+            Rgb::new(0.152286, 0.114503, 0.0),
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(
+            simulate(&red, Deficiency::Deuteranopia, 1.0),
+            Rgb::new(0.367322, 0.280085, 0.0),
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(
+            simulate(&red, Deficiency::Tritanopia, 1.0),
+            Rgb::new(1.0, 0.0, 0.004733),
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(
+            simulate(&red, Deficiency::Protanopia, 0.5),
+            Rgb::new(0.576143, 0.0572515, 0.0),
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(simulate(&red, Deficiency::Deuteranopia, 0.0), red);
+
+        let gray = Rgb::new(0.4, 0.4, 0.4);
+        for &d in &[
+            Deficiency::Protanopia,
+            Deficiency::Deuteranopia,
+            Deficiency::Tritanopia,
+        ] {
+            assert_relative_eq!(simulate(&gray, d, 1.0), gray, epsilon = 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_daltonize() {
+        let red = Rgb::new(1.0, 0.0, 0.0);
+        assert_relative_eq!(
+            daltonize(&red, Deficiency::Protanopia, 1.0),
+            Rgb::new(1.0, 0.478897, 0.597282),
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(
+            daltonize(&red, Deficiency::Protanopia, 0.5),
+            Rgb::new(1.0, 0.2394485, 0.298641),
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(daltonize(&red, Deficiency::Deuteranopia, 0.0), red);
+
+        let blue = Rgb::new(0.0, 0.0, 1.0f32);
+        let corrected = daltonize(&blue, Deficiency::Tritanopia, 1.0);
+        assert!(corrected.red() > 0.0 && corrected.green() > 0.0);
+        assert!(corrected.is_normalized());
+
+        let gray = Rgb::new(0.4, 0.4, 0.4);
+        assert_relative_eq!(
+            daltonize(&gray, Deficiency::Tritanopia, 1.0),
+            gray,
+            epsilon = 1e-5
+        );
+    }
+
+    #[test]
+    fn test_check_palette() {
+        let palette = [
+            Rgb::new(1.0, 0.0, 0.0),
+            Rgb::new(0.0, 0.5, 0.0),
+            Rgb::new(0.0, 0.0, 1.0),
+            Rgb::new(1.0, 1.0, 0.0),
+        ];
+        let reports = check_palette(&palette, 0.08);
+        assert_eq!(reports.len(), 4);
+        assert_eq!(reports[0].deficiency(), None);
+        assert!(reports[0].is_distinguishable());
+        assert!(reports[0].min_distance() > 0.2);
+        for (report, &deficiency) in reports[1..].iter().zip(Deficiency::ALL.iter()) {
+            assert_eq!(report.deficiency(), Some(deficiency));
+            assert!(report.min_distance() < reports[0].min_distance());
+        }
+
+        let deuteranopia = &reports[2];
+        let (i, j) = deuteranopia.closest_pair().unwrap();
+        assert!(deuteranopia.indistinguishable().contains(&(i, j)));
+        assert_eq!((i, j), (0, 1));
+        assert!(!deuteranopia.is_distinguishable());
+
+        let grays = [Rgb::new(0.1f32, 0.1, 0.1), Rgb::new(0.9, 0.9, 0.9)];
+        assert!(check_palette(&grays, 0.5)
+            .iter()
+            .all(|r| r.is_distinguishable()));
+
+        let single = check_palette(&[Rgb::new(0.5, 0.2, 0.1)], 0.1);
+        assert_eq!(single[3].closest_pair(), None);
+        assert_eq!(single[3].min_distance(), f64::INFINITY);
+    }
+}
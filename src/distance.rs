@@ -0,0 +1,304 @@
+//! Measures of the perceived difference between colors
+//!
+//! Color differences are conventionally written $`\Delta E`$. The simplest measures are the
+//! euclidean distances in the perceptually uniform `Lab` and `Luv` spaces defined by CIE in 1976.
+//! They are cheap to compute and good enough for many purposes, but overstate differences between
+//! saturated colors, where `Lab` is the least uniform.
+//!
+//! Both colors must be relative to the same white point for the difference to be meaningful.
+//!
+//! Each measure is also available as a type implementing
+//! [`ColorDistance`](trait.ColorDistance.html), so that code such as palette matching can be
+//! written generically over the metric.
+
+#![allow(non_snake_case)]
+
+use crate::channel::FreeChannelScalar;
+use crate::color::Flatten;
+use crate::lab::Lab;
+use crate::luv::Luv;
+use crate::oklab::Oklab;
+use crate::white_point::WhitePoint;
+use num_traits::Float;
+
+/// A metric for the difference between two colors of type `C`
+///
+/// Metrics are small, usually zero-sized, values so that they can be passed to and stored by
+/// generic code. A smaller distance always means more similar colors, and identical colors have a
+/// distance of zero.
+///
+/// ## Examples:
+///
+/// ```rust
+/// # extern crate prisma;
+///
+/// use prisma::Lab;
+/// use prisma::distance::{ColorDistance, Cie76};
+/// use prisma::white_point::D65;
+///
+/// fn nearest<'a, C, D: ColorDistance<C, Output = f64>>(metric: &D, color: &C, palette: &'a [C])
+///     -> Option<&'a C>
+/// {
+///     palette.iter().min_by(|a, b| {
+///         metric.distance(color, a).partial_cmp(&metric.distance(color, b)).unwrap()
+///     })
+/// }
+///
+/// let palette = [Lab::<f64, D65>::new(20.0, 0.0, 0.0), Lab::new(80.0, 0.0, 0.0)];
+/// let color = Lab::new(70.0, 10.0, 10.0);
+/// assert_eq!(nearest(&Cie76, &color, &palette), Some(&palette[1]));
+/// ```
+pub trait ColorDistance<C> {
+    /// The type of the computed distance
+    type Output;
+
+    /// Compute the distance between `color1` and `color2`
+    fn distance(&self, color1: &C, color2: &C) -> Self::Output;
+}
+
+/// The euclidean distance between the channels of two colors
+///
+/// `Euclidean` is implemented for every color that can be flattened into a slice of channels.
+/// It is the default metric when nothing better is known about a color space, but it is only
+/// perceptually meaningful for uniform spaces.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct Euclidean;
+
+/// The CIE76 color difference, $`\Delta E^*_{ab}`$ for `Lab` and $`\Delta E^*_{uv}`$ for `Luv`
+///
+/// See [`delta_e_76`](fn.delta_e_76.html) and [`delta_e_uv`](fn.delta_e_uv.html).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct Cie76;
+
+/// The $`\Delta E_{OK}`$ color difference between `Oklab` colors used by CSS Color Level 4
+///
+/// See [`Oklab::distance`](../struct.Oklab.html#method.distance).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct DeltaEOk;
+
+/// Measures the distance between colors after converting them with a function
+///
+/// `Via` lets a metric defined on one model measure colors of another, such as taking the
+/// $`\Delta E^*_{ab}`$ of `Xyz` or `Rgb` colors by converting them to `Lab` first.
+///
+/// ## Examples:
+///
+/// ```rust
+/// #[macro_use] extern crate approx;
+/// # extern crate prisma;
+///
+/// use prisma::{Lab, Xyz};
+/// use prisma::distance::{ColorDistance, Cie76, Via};
+/// use prisma::white_point::D65;
+///
+/// let metric = Via::new(|xyz: &Xyz<f64>| Lab::from_xyz(xyz, D65), Cie76);
+/// let c1 = Xyz::new(0.3, 0.4, 0.5);
+/// let c2 = Xyz::new(0.31, 0.4, 0.5);
+/// let expected = Cie76.distance(&Lab::from_xyz(&c1, D65), &Lab::from_xyz(&c2, D65));
+/// assert_relative_eq!(metric.distance(&c1, &c2), expected);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct Via<F, M> {
+    convert: F,
+    metric: M,
+}
+
+impl<F, M> Via<F, M> {
+    /// Construct a new `Via` measuring with `metric` after applying `convert`
+    pub fn new(convert: F, metric: M) -> Self {
+        Via { convert, metric }
+    }
+    /// Returns the conversion applied before measuring
+    pub fn convert(&self) -> &F {
+        &self.convert
+    }
+    /// Returns the metric used on the converted colors
+    pub fn metric(&self) -> &M {
+        &self.metric
+    }
+}
+
+impl<C, D, F, M> ColorDistance<C> for Via<F, M>
+where
+    F: Fn(&C) -> D,
+    M: ColorDistance<D>,
+{
+    type Output = M::Output;
+
+    fn distance(&self, color1: &C, color2: &C) -> Self::Output {
+        self.metric
+            .distance(&(self.convert)(color1), &(self.convert)(color2))
+    }
+}
+
+impl<C> ColorDistance<C> for Euclidean
+where
+    C: Flatten,
+    C::ChannelFormat: Float,
+{
+    type Output = C::ChannelFormat;
+
+    fn distance(&self, color1: &C, color2: &C) -> Self::Output {
+        color1
+            .as_slice()
+            .iter()
+            .zip(color2.as_slice().iter())
+            .fold(
+                <C::ChannelFormat as num_traits::Zero>::zero(),
+                |sum, (&a, &b)| sum + (a - b) * (a - b),
+            )
+            .sqrt()
+    }
+}
+
+impl<T, W> ColorDistance<Lab<T, W>> for Cie76
+where
+    T: FreeChannelScalar,
+    W: WhitePoint<T>,
+{
+    type Output = T;
+
+    fn distance(&self, color1: &Lab<T, W>, color2: &Lab<T, W>) -> T {
+        delta_e_76(color1, color2)
+    }
+}
+
+impl<T, W> ColorDistance<Luv<T, W>> for Cie76
+where
+    T: FreeChannelScalar,
+    W: WhitePoint<T>,
+{
+    type Output = T;
+
+    fn distance(&self, color1: &Luv<T, W>, color2: &Luv<T, W>) -> T {
+        delta_e_uv(color1, color2)
+    }
+}
+
+impl<T> ColorDistance<Oklab<T>> for DeltaEOk
+where
+    T: FreeChannelScalar,
+{
+    type Output = T;
+
+    fn distance(&self, color1: &Oklab<T>, color2: &Oklab<T>) -> T {
+        color1.distance(color2)
+    }
+}
+
+/// Compute the CIE76 color difference $`\Delta E^*_{ab}`$ between two `Lab` colors
+///
+/// This is the euclidean distance in `Lab`:
+///
+/// ```math
+/// \Delta E^*_{ab} = \sqrt{(L_2 - L_1)^2 + (a_2 - a_1)^2 + (b_2 - b_1)^2}
+/// ```
+///
+/// A difference of about 2.3 is a just noticeable difference.
+///
+/// ## Examples:
+///
+/// ```rust
+/// #[macro_use] extern crate approx;
+/// # extern crate prisma;
+///
+/// use prisma::Lab;
+/// use prisma::distance::delta_e_76;
+/// use prisma::white_point::D65;
+///
+/// let c1 = Lab::<f64, D65>::new(50.0, 10.0, -10.0);
+/// let c2 = Lab::<f64, D65>::new(52.0, 13.0, -4.0);
+/// assert_relative_eq!(delta_e_76(&c1, &c2), 7.0);
+/// ```
+pub fn delta_e_76<T, W>(color1: &Lab<T, W>, color2: &Lab<T, W>) -> T
+where
+    T: FreeChannelScalar,
+    W: WhitePoint<T>,
+{
+    let dL = color2.L() - color1.L();
+    let da = color2.a() - color1.a();
+    let db = color2.b() - color1.b();
+    (dL * dL + da * da + db * db).sqrt()
+}
+
+/// Compute the CIE76 color difference $`\Delta E^*_{uv}`$ between two `Luv` colors
+///
+/// This is the euclidean distance in `Luv`:
+///
+/// ```math
+/// \Delta E^*_{uv} = \sqrt{(L_2 - L_1)^2 + (u_2 - u_1)^2 + (v_2 - v_1)^2}
+/// ```
+pub fn delta_e_uv<T, W>(color1: &Luv<T, W>, color2: &Luv<T, W>) -> T
+where
+    T: FreeChannelScalar,
+    W: WhitePoint<T>,
+{
+    let dL = color2.L() - color1.L();
+    let du = color2.u() - color1.u();
+    let dv = color2.v() - color1.v();
+    (dL * dL + du * du + dv * dv).sqrt()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rgb::Rgb;
+    use crate::white_point::D65;
+    use crate::xyz::Xyz;
+    use crate::Rgba;
+    use approx::*;
+
+    #[test]
+    fn test_delta_e_76() {
+        let c1 = Lab::<f64, D65>::new(50.0, 2.6772, -79.7751);
+        let c2 = Lab::<f64, D65>::new(50.0, 0.0, -82.7485);
+        assert_relative_eq!(delta_e_76(&c1, &c2), 4.0011, epsilon = 1e-4);
+        assert_relative_eq!(delta_e_76(&c2, &c1), delta_e_76(&c1, &c2));
+        assert_relative_eq!(delta_e_76(&c1, &c1), 0.0);
+
+        let white = Lab::<f64, D65>::new(100.0, 0.0, 0.0);
+        let black = Lab::<f64, D65>::new(0.0, 0.0, 0.0);
+        assert_relative_eq!(delta_e_76(&white, &black), 100.0);
+    }
+
+    #[test]
+    fn test_delta_e_uv() {
+        let c1 = Luv::<f64, D65>::new(60.0, 20.0, -5.0);
+        let c2 = Luv::<f64, D65>::new(62.0, 17.0, 1.0);
+        assert_relative_eq!(delta_e_uv(&c1, &c2), 7.0);
+        assert_relative_eq!(delta_e_uv(&c1, &c1), 0.0);
+
+        let white: Xyz<f64> = D65.get_xyz();
+        let gray1 = Luv::from_xyz(
+            &Xyz::new(white.x() * 0.2, white.y() * 0.2, white.z() * 0.2),
+            D65,
+        );
+        let gray2 = Luv::from_xyz(&white, D65);
+        assert_relative_eq!(
+            delta_e_uv(&gray1, &gray2),
+            gray2.L() - gray1.L(),
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn test_color_distance() {
+        let c1 = Rgb::new(0.2, 0.4, 0.6);
+        let c2 = Rgb::new(0.5, 0.8, 0.6);
+        assert_relative_eq!(Euclidean.distance(&c1, &c2), 0.5, epsilon = 1e-9);
+        assert_relative_eq!(Euclidean.distance(&c1, &c1), 0.0);
+        let a1 = Rgba::new(c1, 1.0);
+        let a2 = Rgba::new(c2, 0.0);
+        assert_relative_eq!(Euclidean.distance(&a1, &a2), 1.25f64.sqrt(), epsilon = 1e-9);
+
+        let l1 = Lab::<f64, D65>::new(50.0, 10.0, -10.0);
+        let l2 = Lab::<f64, D65>::new(52.0, 13.0, -4.0);
+        assert_relative_eq!(Cie76.distance(&l1, &l2), delta_e_76(&l1, &l2));
+        let u1 = Luv::<f64, D65>::new(60.0, 20.0, -5.0);
+        let u2 = Luv::<f64, D65>::new(62.0, 17.0, 1.0);
+        assert_relative_eq!(Cie76.distance(&u1, &u2), delta_e_uv(&u1, &u2));
+        let o1 = Oklab::new(0.5, 0.1, 0.0);
+        let o2 = Oklab::new(0.5, -0.2, 0.4);
+        assert_relative_eq!(DeltaEOk.distance(&o1, &o2), 0.5);
+    }
+}
@@ -0,0 +1,387 @@
+//! Dithering when reducing bit depth or mapping to a palette
+//!
+//! Rounding each pixel to the nearest representable color produces visible bands in smooth
+//! gradients. Dithering trades the bands for fine noise that the eye averages out. Two families of
+//! dithering are provided, each able to target either 8-bit channels or a fixed palette:
+//!
+//! * Error diffusion ([`diffuse_to_u8`](fn.diffuse_to_u8.html),
+//!   [`diffuse_to_palette`](fn.diffuse_to_palette.html)): The rounding error of each pixel is
+//!   spread onto its unprocessed neighbors using a
+//!   [`DiffusionKernel`](struct.DiffusionKernel.html). Floyd–Steinberg is the most common kernel,
+//!   but any kernel can be supplied.
+//! * Ordered dithering ([`ordered_to_u8`](fn.ordered_to_u8.html),
+//!   [`ordered_to_palette`](fn.ordered_to_palette.html)): A threshold from a tiled Bayer matrix is
+//!   added to each pixel before rounding. Each pixel is independent, so the result is stable and
+//!   easy to parallelize, but shows a regular pattern.
+//!
+//! Images are passed as a row-major slice of pixels together with the image width. Channels are
+//! dithered as given, without decoding; palette matching uses the euclidean distance between the
+//! channel values.
+//!
+//! ## Examples:
+//!
+//! ```rust
+//! # extern crate prisma;
+//!
+//! use prisma::Rgb;
+//! use prisma::dither::{diffuse_to_u8, DiffusionKernel};
+//!
+//! // A flat gray between two 8-bit levels becomes an even mix of both levels
+//! let image = vec![Rgb::new(100.5f32 / 255.0, 0.0, 0.0); 16];
+//! let dithered = diffuse_to_u8(&image, 4, &DiffusionKernel::FLOYD_STEINBERG);
+//! let high = dithered.iter().filter(|c| c.red() == 101).count();
+//! assert_eq!(high, 8);
+//! ```
+
+use crate::channel::{ChannelFormatCast, PosNormalChannelScalar};
+use crate::rgb::Rgb;
+
+/// An error diffusion kernel
+///
+/// Each entry is `(dx, dy, weight)`: the fraction `weight` of a pixel's error is added to the
+/// pixel `dx` columns to the right and `dy` rows below. Entries must only point to pixels that
+/// come later in row-major order, meaning `dy > 0` or `dx > 0`. Weights usually sum to 1.
+///
+/// ## Examples:
+///
+/// ```rust
+/// # extern crate prisma;
+///
+/// use prisma::dither::DiffusionKernel;
+///
+/// // Diffuse all of the error to the next pixel in the row
+/// const NEXT_PIXEL: DiffusionKernel = DiffusionKernel::new(&[(1, 0, 1.0)]);
+/// assert_eq!(NEXT_PIXEL.entries(), &[(1, 0, 1.0)]);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DiffusionKernel<'a> {
+    entries: &'a [(isize, usize, f64)],
+}
+
+impl<'a> DiffusionKernel<'a> {
+    /// The Floyd–Steinberg kernel, spreading error over 4 neighbors
+    pub const FLOYD_STEINBERG: DiffusionKernel<'static> = DiffusionKernel::new(&[
+        (1, 0, 7.0 / 16.0),
+        (-1, 1, 3.0 / 16.0),
+        (0, 1, 5.0 / 16.0),
+        (1, 1, 1.0 / 16.0),
+    ]);
+    /// The Jarvis, Judice and Ninke kernel, spreading error over 12 neighbors in 3 rows
+    pub const JARVIS_JUDICE_NINKE: DiffusionKernel<'static> = DiffusionKernel::new(&[
+        (1, 0, 7.0 / 48.0),
+        (2, 0, 5.0 / 48.0),
+        (-2, 1, 3.0 / 48.0),
+        (-1, 1, 5.0 / 48.0),
+        (0, 1, 7.0 / 48.0),
+        (1, 1, 5.0 / 48.0),
+        (2, 1, 3.0 / 48.0),
+        (-2, 2, 1.0 / 48.0),
+        (-1, 2, 3.0 / 48.0),
+        (0, 2, 5.0 / 48.0),
+        (1, 2, 3.0 / 48.0),
+        (2, 2, 1.0 / 48.0),
+    ]);
+    /// The Sierra Lite kernel, a cheap approximation of Floyd–Steinberg
+    pub const SIERRA_LITE: DiffusionKernel<'static> =
+        DiffusionKernel::new(&[(1, 0, 2.0 / 4.0), (-1, 1, 1.0 / 4.0), (0, 1, 1.0 / 4.0)]);
+    /// The Atkinson kernel, which only diffuses 3/4 of the error for higher contrast
+    pub const ATKINSON: DiffusionKernel<'static> = DiffusionKernel::new(&[
+        (1, 0, 1.0 / 8.0),
+        (2, 0, 1.0 / 8.0),
+        (-1, 1, 1.0 / 8.0),
+        (0, 1, 1.0 / 8.0),
+        (1, 1, 1.0 / 8.0),
+        (0, 2, 1.0 / 8.0),
+    ]);
+
+    /// Construct a new `DiffusionKernel` from `(dx, dy, weight)` entries
+    pub const fn new(entries: &'a [(isize, usize, f64)]) -> Self {
+        DiffusionKernel { entries }
+    }
+
+    /// Returns the `(dx, dy, weight)` entries of the kernel
+    pub fn entries(&self) -> &'a [(isize, usize, f64)] {
+        self.entries
+    }
+}
+
+/// Convert an image to 8-bit channels using error diffusion
+///
+/// # Panics
+/// If `width` is 0 or does not evenly divide the number of pixels.
+pub fn diffuse_to_u8<T>(pixels: &[Rgb<T>], width: usize, kernel: &DiffusionKernel) -> Vec<Rgb<u8>>
+where
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+{
+    diffuse(pixels, width, kernel, |c| {
+        let quantized = c.map(|v| (v * 255.0).round().clamp(0.0, 255.0));
+        let out = Rgb::new(quantized[0] as u8, quantized[1] as u8, quantized[2] as u8);
+        (out, quantized.map(|v| v / 255.0))
+    })
+}
+
+/// Map an image to the nearest colors of `palette` using error diffusion
+///
+/// Returns the palette index of each pixel.
+///
+/// # Panics
+/// If `palette` is empty, or if `width` is 0 or does not evenly divide the number of pixels.
+pub fn diffuse_to_palette<T>(
+    pixels: &[Rgb<T>],
+    width: usize,
+    palette: &[Rgb<T>],
+    kernel: &DiffusionKernel,
+) -> Vec<usize>
+where
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+{
+    let palette = palette_channels(palette);
+    diffuse(pixels, width, kernel, |c| {
+        let index = nearest(&palette, &c);
+        (index, palette[index])
+    })
+}
+
+/// Convert an image to 8-bit channels using ordered dithering with a Bayer matrix
+///
+/// The matrix is `2^level` pixels on each side. Larger matrices give more distinct levels of
+/// dithering, while `level` 0 disables dithering.
+///
+/// # Panics
+/// If `width` is 0 or does not evenly divide the number of pixels.
+pub fn ordered_to_u8<T>(pixels: &[Rgb<T>], width: usize, level: u32) -> Vec<Rgb<u8>>
+where
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+{
+    ordered(pixels, width, level, 1.0 / 255.0, |c| {
+        let q = c.map(|v| (v * 255.0).round().clamp(0.0, 255.0) as u8);
+        Rgb::new(q[0], q[1], q[2])
+    })
+}
+
+/// Map an image to the nearest colors of `palette` using ordered dithering with a Bayer matrix
+///
+/// `spread` is the amplitude of the thresholds added to each channel, and should be about the
+/// distance between neighboring palette colors. For a palette with `n` evenly spaced levels per
+/// channel, use `1 / (n - 1)`. Returns the palette index of each pixel.
+///
+/// # Panics
+/// If `palette` is empty, or if `width` is 0 or does not evenly divide the number of pixels.
+pub fn ordered_to_palette<T>(
+    pixels: &[Rgb<T>],
+    width: usize,
+    palette: &[Rgb<T>],
+    level: u32,
+    spread: f64,
+) -> Vec<usize>
+where
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+{
+    let palette = palette_channels(palette);
+    ordered(pixels, width, level, spread, |c| nearest(&palette, &c))
+}
+
+/// Returns the thresholds of a `2^level` square Bayer matrix, in `(-0.5, 0.5)`, in row-major order
+pub fn bayer_matrix(level: u32) -> Vec<f64> {
+    let mut matrix = vec![0usize];
+    let mut size = 1;
+    for _ in 0..level {
+        let mut next = vec![0; size * size * 4];
+        for y in 0..size {
+            for x in 0..size {
+                let v = matrix[y * size + x] * 4;
+                next[y * size * 2 + x] = v;
+                next[y * size * 2 + x + size] = v + 2;
+                next[(y + size) * size * 2 + x] = v + 3;
+                next[(y + size) * size * 2 + x + size] = v + 1;
+            }
+        }
+        matrix = next;
+        size *= 2;
+    }
+
+    let count = (size * size) as f64;
+    matrix
+        .into_iter()
+        .map(|v| (v as f64 + 0.5) / count - 0.5)
+        .collect()
+}
+
+fn diffuse<T, Out, F>(
+    pixels: &[Rgb<T>],
+    width: usize,
+    kernel: &DiffusionKernel,
+    mut quantize: F,
+) -> Vec<Out>
+where
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+    F: FnMut([f64; 3]) -> (Out, [f64; 3]),
+{
+    let height = image_height(pixels, width);
+    let mut values: Vec<_> = pixels.iter().map(to_channels).collect();
+    let mut out = Vec::with_capacity(pixels.len());
+
+    for y in 0..height {
+        for x in 0..width {
+            let value = values[y * width + x];
+            let (result, quantized) = quantize(value);
+            out.push(result);
+
+            let error = [
+                value[0] - quantized[0],
+                value[1] - quantized[1],
+                value[2] - quantized[2],
+            ];
+            for &(dx, dy, weight) in kernel.entries() {
+                let nx = x as isize + dx;
+                let ny = y + dy;
+                if nx < 0 || nx >= width as isize || ny >= height {
+                    continue;
+                }
+                let target = &mut values[ny * width + nx as usize];
+                for (t, e) in target.iter_mut().zip(&error) {
+                    *t += e * weight;
+                }
+            }
+        }
+    }
+    out
+}
+
+fn ordered<T, Out, F>(
+    pixels: &[Rgb<T>],
+    width: usize,
+    level: u32,
+    spread: f64,
+    quantize: F,
+) -> Vec<Out>
+where
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+    F: Fn([f64; 3]) -> Out,
+{
+    image_height(pixels, width);
+    let matrix = bayer_matrix(level);
+    let size = 1 << level;
+    pixels
+        .iter()
+        .enumerate()
+        .map(|(i, pixel)| {
+            let (x, y) = (i % width, i / width);
+            let threshold = matrix[(y % size) * size + x % size] * spread;
+            quantize(to_channels(pixel).map(|v| v + threshold))
+        })
+        .collect()
+}
+
+fn image_height<T>(pixels: &[T], width: usize) -> usize {
+    assert!(width > 0, "Image width must not be 0");
+    assert_eq!(
+        pixels.len() % width,
+        0,
+        "Image width must evenly divide the number of pixels"
+    );
+    pixels.len() / width
+}
+
+fn to_channels<T>(color: &Rgb<T>) -> [f64; 3]
+where
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+{
+    let color = color.color_cast::<f64>();
+    [color.red(), color.green(), color.blue()]
+}
+
+fn palette_channels<T>(palette: &[Rgb<T>]) -> Vec<[f64; 3]>
+where
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+{
+    assert!(!palette.is_empty(), "The palette must not be empty");
+    palette.iter().map(to_channels).collect()
+}
+
+fn nearest(palette: &[[f64; 3]], color: &[f64; 3]) -> usize {
+    let distance =
+        |p: &[f64; 3]| -> f64 { p.iter().zip(color).map(|(a, b)| (a - b) * (a - b)).sum() };
+    let mut nearest = 0;
+    let mut nearest_distance = f64::INFINITY;
+    for (i, entry) in palette.iter().enumerate() {
+        let d = distance(entry);
+        if d < nearest_distance {
+            nearest = i;
+            nearest_distance = d;
+        }
+    }
+    nearest
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bayer_matrix() {
+        assert_eq!(bayer_matrix(0), vec![0.0]);
+        assert_eq!(bayer_matrix(1), vec![-0.375, 0.125, 0.375, -0.125]);
+        let m = bayer_matrix(2);
+        let mut sorted: Vec<_> = m
+            .iter()
+            .map(|v| ((v + 0.5) * 16.0 - 0.5) as usize)
+            .collect();
+        assert_eq!(&sorted[..4], &[0, 8, 2, 10]);
+        sorted.sort();
+        assert_eq!(sorted, (0..16).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_diffuse_to_u8() {
+        let image = vec![Rgb::new(100.25f32 / 255.0, 0.0, 1.0); 256];
+        let dithered = diffuse_to_u8(&image, 16, &DiffusionKernel::FLOYD_STEINBERG);
+        let high = dithered.iter().filter(|c| c.red() == 101).count();
+        assert!(dithered.iter().all(|c| c.red() == 100 || c.red() == 101));
+        assert!((60..=68).contains(&high), "{} high pixels", high);
+        assert!(dithered.iter().all(|c| c.green() == 0 && c.blue() == 255));
+
+        let atkinson = diffuse_to_u8(&image, 16, &DiffusionKernel::ATKINSON);
+        let high = atkinson.iter().filter(|c| c.red() == 101).count();
+        assert!(high > 0 && high < 128);
+    }
+
+    #[test]
+    fn test_diffuse_to_palette() {
+        let palette = [Rgb::new(0.0, 0.0, 0.0), Rgb::new(1.0, 1.0, 1.0)];
+        let image = vec![Rgb::new(0.25, 0.25, 0.25); 256];
+        for kernel in &[
+            DiffusionKernel::FLOYD_STEINBERG,
+            DiffusionKernel::JARVIS_JUDICE_NINKE,
+            DiffusionKernel::SIERRA_LITE,
+        ] {
+            let indices = diffuse_to_palette(&image, 16, &palette, kernel);
+            let white = indices.iter().filter(|&&i| i == 1).count();
+            assert!((50..=70).contains(&white), "{} white pixels", white);
+        }
+    }
+
+    #[test]
+    fn test_ordered() {
+        let image = vec![Rgb::new(0.5, 0.0, 1.0); 16];
+        let palette = [Rgb::new(0.0, 0.0, 1.0), Rgb::new(1.0, 0.0, 1.0)];
+        let indices = ordered_to_palette(&image, 4, &palette, 2, 1.0);
+        assert_eq!(indices.iter().filter(|&&i| i == 1).count(), 8);
+        assert_eq!(&indices[..4], &[0, 1, 0, 1]);
+
+        let image = vec![Rgb::new(100.25f32 / 255.0, 0.0, 0.0); 16];
+        let dithered = ordered_to_u8(&image, 4, 2);
+        assert_eq!(dithered.iter().filter(|c| c.red() == 101).count(), 4);
+        assert!(ordered_to_u8(&image, 4, 0).iter().all(|c| c.red() == 100));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bad_width() {
+        diffuse_to_u8(
+            &[Rgb::new(0.0, 0.0, 0.0); 5],
+            2,
+            &DiffusionKernel::SIERRA_LITE,
+        );
+    }
+}
@@ -0,0 +1,501 @@
+//! Colors whose model is chosen at runtime
+//!
+//! Every color type in prisma carries its model in its type, which catches mixing models at
+//! compile time but doesn't help code that only learns the model at runtime, such as a color
+//! picker letting the user switch between Rgb and Hsl, a parser reading arbitrary color strings or
+//! bindings to a scripting language. [`DynamicColor`](enum.DynamicColor.html) wraps any of the
+//! color models in an enum, tagged with a [`ColorKind`](enum.ColorKind.html), and converts
+//! between them with [`convert_to`](enum.DynamicColor.html#method.convert_to).
+//!
+//! The models with extra type parameters are fixed to their most common choice: hues are in
+//! `Deg<T>`, `Lab`, `Lchab`, `Luv` and `Lchuv` use the `D65` white point, `YCbCr` uses the
+//! `JpegModel` and `Lms` uses the `Bradford` model.
+//!
+//! Conversions follow [`PivotColor`](../trait.PivotColor.html): device dependent models convert
+//! through `Rgb` and device independent models through `Xyz`. Converting between the two families
+//! needs a color space, which is sRGB for `convert_to` and can be chosen with
+//! [`convert_to_in`](enum.DynamicColor.html#method.convert_to_in).
+//!
+//! ## Examples:
+//!
+//! ```rust
+//! #[macro_use] extern crate approx;
+//! # extern crate prisma;
+//! extern crate angular_units as angle;
+//!
+//! use std::convert::TryFrom;
+//! use prisma::{Hsl, Rgb};
+//! use prisma::dynamic::{ColorKind, DynamicColor};
+//! use angle::Deg;
+//!
+//! let color = DynamicColor::from(Rgb::new(0.0, 0.5, 0.0));
+//! assert_eq!(color.kind(), ColorKind::Rgb);
+//!
+//! let hsl = color.convert_to(ColorKind::Hsl);
+//! assert_eq!(hsl.kind(), ColorKind::Hsl);
+//! let expected = Hsl::new(Deg(120.0), 1.0, 0.25);
+//! assert_relative_eq!(Hsl::try_from(hsl).unwrap(), expected, epsilon=1e-6);
+//!
+//! let lab = hsl.convert_to(ColorKind::Lab);
+//! assert_eq!(lab.kind(), ColorKind::Lab);
+//! assert_relative_eq!(Rgb::try_from(lab.convert_to(ColorKind::Rgb)).unwrap(),
+//!     Rgb::new(0.0, 0.5, 0.0), epsilon=1e-6);
+//! assert!(Rgb::try_from(hsl).is_err());
+//! ```
+
+use crate::color_space::named::SRgb;
+use crate::convert::{PivotBridge, PivotColor};
+use crate::ehsi::eHsi;
+use crate::hsi::Hsi;
+use crate::hsl::Hsl;
+use crate::hsv::Hsv;
+use crate::hwb::Hwb;
+use crate::lab::Lab;
+use crate::lchab::Lchab;
+use crate::lchuv::Lchuv;
+use crate::lms::{Bradford, Lms};
+use crate::luma::Luma;
+use crate::luv::Luv;
+use crate::oklab::Oklab;
+use crate::oklch::Oklch;
+use crate::rgb::Rgb;
+use crate::rgi::Rgi;
+use crate::white_point::D65;
+use crate::xyy::XyY;
+use crate::xyz::Xyz;
+use crate::ycbcr::YCbCrJpeg;
+use angle::Deg;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+
+/// The color model of a `DynamicColor`
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ColorKind {
+    /// `Rgb`
+    Rgb,
+    /// `Luma`
+    Luma,
+    /// `Rgi`
+    Rgi,
+    /// `Hsv`
+    Hsv,
+    /// `Hsl`
+    Hsl,
+    /// `Hwb`
+    Hwb,
+    /// `Hsi`
+    Hsi,
+    /// `eHsi`
+    EHsi,
+    /// `YCbCr` with the `JpegModel`
+    YCbCr,
+    /// `Xyz`
+    Xyz,
+    /// `XyY`
+    XyY,
+    /// `Lab` with the `D65` white point
+    Lab,
+    /// `Lchab` with the `D65` white point
+    Lchab,
+    /// `Luv` with the `D65` white point
+    Luv,
+    /// `Lchuv` with the `D65` white point
+    Lchuv,
+    /// `Oklab`
+    Oklab,
+    /// `Oklch`
+    Oklch,
+    /// `Lms` with the `Bradford` model
+    Lms,
+}
+
+impl ColorKind {
+    /// Every kind of color, in declaration order
+    pub const ALL: [ColorKind; 18] = [
+        ColorKind::Rgb,
+        ColorKind::Luma,
+        ColorKind::Rgi,
+        ColorKind::Hsv,
+        ColorKind::Hsl,
+        ColorKind::Hwb,
+        ColorKind::Hsi,
+        ColorKind::EHsi,
+        ColorKind::YCbCr,
+        ColorKind::Xyz,
+        ColorKind::XyY,
+        ColorKind::Lab,
+        ColorKind::Lchab,
+        ColorKind::Luv,
+        ColorKind::Lchuv,
+        ColorKind::Oklab,
+        ColorKind::Oklch,
+        ColorKind::Lms,
+    ];
+
+    /// Returns the name of the model, as used by its type
+    pub fn name(self) -> &'static str {
+        match self {
+            ColorKind::Rgb => "Rgb",
+            ColorKind::Luma => "Luma",
+            ColorKind::Rgi => "Rgi",
+            ColorKind::Hsv => "Hsv",
+            ColorKind::Hsl => "Hsl",
+            ColorKind::Hwb => "Hwb",
+            ColorKind::Hsi => "Hsi",
+            ColorKind::EHsi => "eHsi",
+            ColorKind::YCbCr => "YCbCr",
+            ColorKind::Xyz => "Xyz",
+            ColorKind::XyY => "XyY",
+            ColorKind::Lab => "Lab",
+            ColorKind::Lchab => "Lchab",
+            ColorKind::Luv => "Luv",
+            ColorKind::Lchuv => "Lchuv",
+            ColorKind::Oklab => "Oklab",
+            ColorKind::Oklch => "Oklch",
+            ColorKind::Lms => "Lms",
+        }
+    }
+
+    /// Returns true if the model depends on the device, converting through `Rgb`
+    ///
+    /// Otherwise, the model is device independent and converts through `Xyz`.
+    pub fn is_device_dependent(self) -> bool {
+        matches!(
+            self,
+            ColorKind::Rgb
+                | ColorKind::Luma
+                | ColorKind::Rgi
+                | ColorKind::Hsv
+                | ColorKind::Hsl
+                | ColorKind::Hwb
+                | ColorKind::Hsi
+                | ColorKind::EHsi
+                | ColorKind::YCbCr
+        )
+    }
+}
+
+impl fmt::Display for ColorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// A color in a model chosen at runtime
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum DynamicColor<T> {
+    /// An `Rgb` color
+    Rgb(Rgb<T>),
+    /// A `Luma` color
+    Luma(Luma<T>),
+    /// An `Rgi` color
+    Rgi(Rgi<T>),
+    /// An `Hsv` color
+    Hsv(Hsv<T, Deg<T>>),
+    /// An `Hsl` color
+    Hsl(Hsl<T, Deg<T>>),
+    /// An `Hwb` color
+    Hwb(Hwb<T, Deg<T>>),
+    /// An `Hsi` color
+    Hsi(Hsi<T, Deg<T>>),
+    /// An `eHsi` color
+    EHsi(eHsi<T, Deg<T>>),
+    /// A `YCbCr` color
+    YCbCr(YCbCrJpeg<T>),
+    /// An `Xyz` color
+    Xyz(Xyz<T>),
+    /// An `XyY` color
+    XyY(XyY<T>),
+    /// A `Lab` color
+    Lab(Lab<T, D65>),
+    /// An `Lchab` color
+    Lchab(Lchab<T, D65, Deg<T>>),
+    /// A `Luv` color
+    Luv(Luv<T, D65>),
+    /// An `Lchuv` color
+    Lchuv(Lchuv<T, D65, Deg<T>>),
+    /// An `Oklab` color
+    Oklab(Oklab<T>),
+    /// An `Oklch` color
+    Oklch(Oklch<T, Deg<T>>),
+    /// An `Lms` color
+    Lms(Lms<T, Bradford>),
+}
+
+/// An error returned when a `DynamicColor` is not of the requested model
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct WrongKindError {
+    /// The model that was requested
+    pub expected: ColorKind,
+    /// The model of the color
+    pub found: ColorKind,
+}
+
+impl fmt::Display for WrongKindError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "expected a color in {}, found one in {}",
+            self.expected, self.found
+        )
+    }
+}
+
+impl Error for WrongKindError {}
+
+macro_rules! impl_dynamic_variants {
+    ($($kind:ident => $color:ty),*) => {
+        impl<T> DynamicColor<T> {
+            /// Returns the model of the color
+            pub fn kind(&self) -> ColorKind {
+                match *self {
+                    $(DynamicColor::$kind(_) => ColorKind::$kind,)*
+                }
+            }
+        }
+
+        impl<T> fmt::Display for DynamicColor<T>
+        where
+            $($color: fmt::Display,)*
+        {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match *self {
+                    $(DynamicColor::$kind(ref color) => write!(f, "{}", color),)*
+                }
+            }
+        }
+
+        $(
+            impl<T> From<$color> for DynamicColor<T> {
+                fn from(color: $color) -> Self {
+                    DynamicColor::$kind(color)
+                }
+            }
+
+            impl<T> TryFrom<DynamicColor<T>> for $color {
+                type Error = WrongKindError;
+                fn try_from(color: DynamicColor<T>) -> Result<Self, WrongKindError> {
+                    match color {
+                        DynamicColor::$kind(color) => Ok(color),
+                        other => Err(WrongKindError {
+                            expected: ColorKind::$kind,
+                            found: other.kind(),
+                        }),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_dynamic_variants!(
+    Rgb => Rgb<T>,
+    Luma => Luma<T>,
+    Rgi => Rgi<T>,
+    Hsv => Hsv<T, Deg<T>>,
+    Hsl => Hsl<T, Deg<T>>,
+    Hwb => Hwb<T, Deg<T>>,
+    Hsi => Hsi<T, Deg<T>>,
+    EHsi => eHsi<T, Deg<T>>,
+    YCbCr => YCbCrJpeg<T>,
+    Xyz => Xyz<T>,
+    XyY => XyY<T>,
+    Lab => Lab<T, D65>,
+    Lchab => Lchab<T, D65, Deg<T>>,
+    Luv => Luv<T, D65>,
+    Lchuv => Lchuv<T, D65, Deg<T>>,
+    Oklab => Oklab<T>,
+    Oklch => Oklch<T, Deg<T>>,
+    Lms => Lms<T, Bradford>
+);
+
+enum Pivot<T> {
+    Rgb(Rgb<T>),
+    Xyz(Xyz<T>),
+}
+
+impl<T> Pivot<T> {
+    fn into_rgb<S>(self, space: &S) -> Rgb<T>
+    where
+        Xyz<T>: PivotBridge<Rgb<T>, S>,
+    {
+        match self {
+            Pivot::Rgb(rgb) => rgb,
+            Pivot::Xyz(xyz) => xyz.bridge(space),
+        }
+    }
+
+    fn into_xyz<S>(self, space: &S) -> Xyz<T>
+    where
+        Rgb<T>: PivotBridge<Xyz<T>, S>,
+    {
+        match self {
+            Pivot::Rgb(rgb) => rgb.bridge(space),
+            Pivot::Xyz(xyz) => xyz,
+        }
+    }
+}
+
+macro_rules! impl_dynamic_convert {
+    (rgb: [$($rgb_kind:ident => $rgb_color:ty),*], xyz: [$($xyz_kind:ident => $xyz_color:ty),*]) => {
+        impl<T> DynamicColor<T>
+        where
+            $($rgb_color: PivotColor<Pivot = Rgb<T>>,)*
+            $($xyz_color: PivotColor<Pivot = Xyz<T>>,)*
+        {
+            /// Convert the color to the model `kind`
+            ///
+            /// Colors are converted between device dependent and independent models in sRGB. Out
+            /// of gamut `Hsi` and `YCbCr` colors are clipped to the `Rgb` gamut.
+            pub fn convert_to(&self, kind: ColorKind) -> Self
+            where
+                Rgb<T>: PivotBridge<Xyz<T>, SRgb<T>>,
+                Xyz<T>: PivotBridge<Rgb<T>, SRgb<T>>,
+            {
+                self.convert_to_in(kind, &SRgb::new())
+            }
+
+            /// Convert the color to the model `kind`, using `space` between device dependent and
+            /// independent models
+            pub fn convert_to_in<S>(&self, kind: ColorKind, space: &S) -> Self
+            where
+                Rgb<T>: PivotBridge<Xyz<T>, S>,
+                Xyz<T>: PivotBridge<Rgb<T>, S>,
+            {
+                let pivot = match *self {
+                    $(DynamicColor::$rgb_kind(ref c) => Pivot::Rgb(c.to_pivot()),)*
+                    $(DynamicColor::$xyz_kind(ref c) => Pivot::Xyz(c.to_pivot()),)*
+                };
+                match kind {
+                    $(ColorKind::$rgb_kind => {
+                        DynamicColor::$rgb_kind(PivotColor::from_pivot(&pivot.into_rgb(space)))
+                    })*
+                    $(ColorKind::$xyz_kind => {
+                        DynamicColor::$xyz_kind(PivotColor::from_pivot(&pivot.into_xyz(space)))
+                    })*
+                }
+            }
+        }
+    };
+}
+
+impl_dynamic_convert!(
+    rgb: [
+        Rgb => Rgb<T>,
+        Luma => Luma<T>,
+        Rgi => Rgi<T>,
+        Hsv => Hsv<T, Deg<T>>,
+        Hsl => Hsl<T, Deg<T>>,
+        Hwb => Hwb<T, Deg<T>>,
+        Hsi => Hsi<T, Deg<T>>,
+        EHsi => eHsi<T, Deg<T>>,
+        YCbCr => YCbCrJpeg<T>
+    ],
+    xyz: [
+        Xyz => Xyz<T>,
+        XyY => XyY<T>,
+        Lab => Lab<T, D65>,
+        Lchab => Lchab<T, D65, Deg<T>>,
+        Luv => Luv<T, D65>,
+        Lchuv => Lchuv<T, D65, Deg<T>>,
+        Oklab => Oklab<T>,
+        Oklch => Oklch<T, Deg<T>>,
+        Lms => Lms<T, Bradford>
+    ]
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::color_space::named::AdobeRgb;
+    use crate::color_space::{ColorSpace, ConvertToXyz};
+    use crate::convert::FromColor;
+    use crate::encoding::EncodableColor;
+    use approx::*;
+
+    #[test]
+    fn test_kinds() {
+        for &kind in ColorKind::ALL.iter() {
+            let start = if kind.is_device_dependent() {
+                DynamicColor::from(Rgb::new(0.25f64, 0.5, 0.75))
+            } else {
+                DynamicColor::from(Xyz::new(0.25f64, 0.5, 0.75))
+            };
+            let converted = start.convert_to(kind);
+            assert_eq!(converted.kind(), kind);
+            let back = converted.convert_to(start.kind());
+            match (back, start) {
+                (DynamicColor::Rgb(back), DynamicColor::Rgb(start)) if kind == ColorKind::Luma => {
+                    assert_relative_eq!(back.red(), back.blue());
+                    assert!(back != start);
+                }
+                (DynamicColor::Rgb(back), DynamicColor::Rgb(start)) => {
+                    let epsilon = if kind == ColorKind::YCbCr { 1e-3 } else { 1e-9 };
+                    assert_relative_eq!(back, start, epsilon = epsilon)
+                }
+                (DynamicColor::Xyz(back), DynamicColor::Xyz(start)) => {
+                    assert_relative_eq!(back, start, epsilon = 1e-5)
+                }
+                _ => panic!("unexpected kind for {}", kind),
+            }
+        }
+    }
+
+    #[test]
+    fn test_convert_between_families() {
+        let rgb = Rgb::new(0.25f64, 0.5, 0.75);
+        let xyz = SRgb::new().convert_to_xyz(&rgb.srgb_encoded());
+        let color = DynamicColor::from(rgb);
+        for &kind in ColorKind::ALL.iter() {
+            let converted = color.convert_to(kind);
+            assert_eq!(converted.kind(), kind);
+            if !kind.is_device_dependent() {
+                let back = Xyz::try_from(converted.convert_to(ColorKind::Xyz)).unwrap();
+                assert_relative_eq!(back, xyz, epsilon = 1e-5);
+            }
+        }
+
+        let lab = DynamicColor::from(Lab::<f64, D65>::from_xyz(&xyz, D65));
+        let back = Rgb::try_from(lab.convert_to(ColorKind::Rgb)).unwrap();
+        assert_relative_eq!(back, rgb, epsilon = 1e-6);
+
+        let space = AdobeRgb::new();
+        let adobe_xyz = space.convert_to_xyz(&rgb.encoded_as(space.encoding()));
+        let converted = Xyz::try_from(color.convert_to_in(ColorKind::Xyz, &space)).unwrap();
+        assert_relative_eq!(converted, adobe_xyz, epsilon = 1e-9);
+        let back = DynamicColor::from(adobe_xyz).convert_to_in(ColorKind::Hsv, &space);
+        assert_relative_eq!(
+            Hsv::try_from(back).unwrap(),
+            Hsv::from_color(&rgb),
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn test_static_conversions() {
+        let lab = Lab::<f32, D65>::new(50.0, 20.0, -10.0);
+        let color = DynamicColor::from(lab);
+        assert_eq!(color.to_string(), lab.to_string());
+        assert_eq!(Lab::try_from(color), Ok(lab));
+        assert_eq!(
+            Oklab::try_from(color),
+            Err(WrongKindError {
+                expected: ColorKind::Oklab,
+                found: ColorKind::Lab,
+            })
+        );
+
+        let oklch = Oklch::try_from(color.convert_to(ColorKind::Oklch)).unwrap();
+        let expected: Oklch<f32, Deg<f32>> = Oklch::from_color(&Oklab::from_color(&lab.to_xyz()));
+        assert_relative_eq!(oklch, expected, epsilon = 1e-5);
+        assert_eq!(
+            WrongKindError {
+                expected: ColorKind::EHsi,
+                found: ColorKind::Rgb,
+            }
+            .to_string(),
+            "expected a color in eHsi, found one in Rgb"
+        );
+    }
+}
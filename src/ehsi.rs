@@ -5,7 +5,7 @@ use crate::channel::{
     PosNormalBoundedChannel, PosNormalChannelScalar,
 };
 use crate::color;
-use crate::color::{Bounded, Color, FromTuple, Invert, Lerp, PolarColor};
+use crate::color::{Bounded, ChannelOrder, Color, FromTuple, Invert, Lerp, PolarColor, PolarLerp};
 use crate::convert::{decompose_hue_segment, FromColor, GetHue};
 use crate::encoding::EncodableColor;
 use crate::hsi::Hsi;
@@ -167,6 +167,14 @@ where
     }
 }
 
+impl<T, A> ChannelOrder for eHsi<T, A>
+where
+    T: PosNormalChannelScalar,
+    A: AngularChannelScalar,
+{
+    impl_color_channel_order!({hue, saturation, intensity});
+}
+
 impl<T, A> FromTuple for eHsi<T, A>
 where
     T: PosNormalChannelScalar + Float,
@@ -199,6 +207,14 @@ where
     impl_color_lerp_angular!(eHsi<T> {hue, saturation, intensity});
 }
 
+impl<T, A> PolarLerp for eHsi<T, A>
+where
+    T: PosNormalChannelScalar + color::Lerp,
+    A: AngularChannelScalar + color::Lerp,
+{
+    impl_color_lerp_hue_mode!(eHsi<T> {hue, saturation, intensity});
+}
+
 impl<T, A> Bounded for eHsi<T, A>
 where
     T: PosNormalChannelScalar,
@@ -3,12 +3,14 @@
 //! See the [`encoding`](../index.html) module level documentation for more information
 
 use super::EncodableColor;
-use crate::alpha::Rgba;
+use crate::alpha::{LumaA, Rgba};
 use crate::channel::{ChannelFormatCast, PosNormalChannelScalar};
 use crate::color::Color;
+use crate::luma::Luma;
 use crate::rgb::Rgb;
 use num_traits;
 use std::fmt;
+use std::sync::Arc;
 
 /// An object that can encode a color from a linear encoding to a different encoding
 ///
@@ -50,14 +52,66 @@ pub trait TranscodableColor: Color + EncodableColor {
 /// An object able to encode and decode a color
 pub trait ColorEncoding: ChannelEncoder + ChannelDecoder + Sized + Clone {}
 
+/// An object safe form of `ColorEncoding`
+///
+/// The methods of `ColorEncoding` are generic over the channel type, so an encoding can't be used
+/// as a trait object. `DynColorEncoding` is implemented for every `ColorEncoding` and works on `f64`
+/// channels instead, so encodings chosen at runtime can be stored as `Box<dyn DynColorEncoding>`
+/// or as a [`SharedEncoding`](type.SharedEncoding.html), which is itself a `ColorEncoding`.
+pub trait DynColorEncoding {
+    /// Encode a linearly-encoded channel
+    fn encode_f64(&self, val: f64) -> f64;
+    /// Decode a channel into a linear-encoding
+    fn decode_f64(&self, val: f64) -> f64;
+}
+
+impl<E> DynColorEncoding for E
+where
+    E: ColorEncoding,
+{
+    fn encode_f64(&self, val: f64) -> f64 {
+        self.encode_channel(val)
+    }
+    fn decode_f64(&self, val: f64) -> f64 {
+        self.decode_channel(val)
+    }
+}
+
+/// A shared encoding chosen at runtime
+///
+/// `SharedEncoding` can be used anywhere a `ColorEncoding` is expected, such as the encoding of an
+/// `EncodedColorSpace`, so spaces and colors with different encodings can share one type. Channels
+/// are encoded and decoded as `f64`.
+pub type SharedEncoding = Arc<dyn DynColorEncoding + Send + Sync>;
+
+impl ChannelEncoder for SharedEncoding {
+    fn encode_channel<T>(&self, val: T) -> T
+    where
+        T: num_traits::Float,
+    {
+        num_traits::cast((**self).encode_f64(num_traits::cast(val).unwrap())).unwrap()
+    }
+}
+
+impl ChannelDecoder for SharedEncoding {
+    fn decode_channel<T>(&self, val: T) -> T
+    where
+        T: num_traits::Float,
+    {
+        num_traits::cast((**self).decode_f64(num_traits::cast(val).unwrap())).unwrap()
+    }
+}
+
+impl ColorEncoding for SharedEncoding {}
+
 /// An encoding scheme used by the sRGB color space.
 ///
 /// sRGB features a small linear region at the lowest values, and then transitions to
 /// a $`\gamma`$ of 2.4.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct SrgbEncoding;
 /// A linear encoding scheme
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct LinearEncoding;
 /// A gamma encoding scheme with a given value for $`\gamma`$
 #[derive(Clone, Debug, PartialEq)]
@@ -217,6 +271,346 @@ where
     }
 }
 
+/// The transfer function defined by ITU-R BT.709 and shared by BT.601 standard definition video
+///
+/// Linear values below $`0.018`$ are scaled by $`4.5`$, and the rest follow the curve
+/// $`1.099 L^{0.45} - 0.099`$.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Rec709Encoding;
+
+impl Rec709Encoding {
+    /// Construct a new `Rec709Encoding`
+    pub fn new() -> Self {
+        Rec709Encoding {}
+    }
+}
+
+impl ChannelDecoder for Rec709Encoding {
+    fn decode_channel<T>(&self, val: T) -> T
+    where
+        T: num_traits::Float,
+    {
+        let alpha: T = num_traits::cast(1.099).unwrap();
+        let threshold: T = num_traits::cast(0.081).unwrap();
+        let k: T = num_traits::cast(4.5).unwrap();
+        let gamma: T = num_traits::cast(1.0 / 0.45).unwrap();
+        let one: T = num_traits::cast(1.0).unwrap();
+
+        if val.abs() < threshold {
+            val / k
+        } else {
+            val.signum() * ((val.abs() + alpha - one) / alpha).powf(gamma)
+        }
+    }
+}
+
+impl ChannelEncoder for Rec709Encoding {
+    fn encode_channel<T>(&self, val: T) -> T
+    where
+        T: num_traits::Float,
+    {
+        let alpha: T = num_traits::cast(1.099).unwrap();
+        let threshold: T = num_traits::cast(0.018).unwrap();
+        let k: T = num_traits::cast(4.5).unwrap();
+        let exponent: T = num_traits::cast(0.45).unwrap();
+        let one: T = num_traits::cast(1.0).unwrap();
+
+        if val.abs() < threshold {
+            k * val
+        } else {
+            val.signum() * (alpha * val.abs().powf(exponent) - (alpha - one))
+        }
+    }
+}
+
+impl ColorEncoding for Rec709Encoding {}
+
+impl fmt::Display for Rec709Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Rec.709")
+    }
+}
+
+/// The transfer function defined by ITU-R BT.2020 for standard dynamic range content
+///
+/// This is the same curve as BT.709, a linear segment near black followed by a power function with
+/// an exponent of $`0.45`$, but with the higher precision constants required for 12-bit signals.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Rec2020Encoding;
+
+impl Rec2020Encoding {
+    /// Construct a new `Rec2020Encoding`
+    pub fn new() -> Self {
+        Rec2020Encoding {}
+    }
+}
+
+impl ChannelDecoder for Rec2020Encoding {
+    fn decode_channel<T>(&self, val: T) -> T
+    where
+        T: num_traits::Float,
+    {
+        let alpha: T = num_traits::cast(1.09929682680944).unwrap();
+        let beta: T = num_traits::cast(0.018053968510807).unwrap();
+        let k: T = num_traits::cast(4.5).unwrap();
+        let gamma: T = num_traits::cast(1.0 / 0.45).unwrap();
+        let one: T = num_traits::cast(1.0).unwrap();
+
+        if val.abs() < k * beta {
+            val / k
+        } else {
+            val.signum() * ((val.abs() + alpha - one) / alpha).powf(gamma)
+        }
+    }
+}
+
+impl ChannelEncoder for Rec2020Encoding {
+    fn encode_channel<T>(&self, val: T) -> T
+    where
+        T: num_traits::Float,
+    {
+        let alpha: T = num_traits::cast(1.09929682680944).unwrap();
+        let beta: T = num_traits::cast(0.018053968510807).unwrap();
+        let k: T = num_traits::cast(4.5).unwrap();
+        let exponent: T = num_traits::cast(0.45).unwrap();
+        let one: T = num_traits::cast(1.0).unwrap();
+
+        if val.abs() < beta {
+            k * val
+        } else {
+            val.signum() * (alpha * val.abs().powf(exponent) - (alpha - one))
+        }
+    }
+}
+
+impl ColorEncoding for Rec2020Encoding {}
+
+impl fmt::Display for Rec2020Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Rec.2020")
+    }
+}
+
+/// The SMPTE ST 2084 "Perceptual Quantizer" transfer function used for HDR content
+///
+/// Linear values are absolute, with $`1.0`$ representing $`10000 cd/m^2`$. `PqEncoding` is
+/// commonly paired with the BT.2020 primaries, as specified by ITU-R BT.2100.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct PqEncoding;
+
+impl PqEncoding {
+    /// Construct a new `PqEncoding`
+    pub fn new() -> Self {
+        PqEncoding {}
+    }
+}
+
+impl ChannelDecoder for PqEncoding {
+    fn decode_channel<T>(&self, val: T) -> T
+    where
+        T: num_traits::Float,
+    {
+        let m1: T = num_traits::cast(0.1593017578125).unwrap();
+        let m2: T = num_traits::cast(78.84375).unwrap();
+        let c1: T = num_traits::cast(0.8359375).unwrap();
+        let c2: T = num_traits::cast(18.8515625).unwrap();
+        let c3: T = num_traits::cast(18.6875).unwrap();
+        let one: T = num_traits::cast(1.0).unwrap();
+
+        let e = val.abs().powf(one / m2);
+        let num = (e - c1).max(T::zero());
+        val.signum() * (num / (c2 - c3 * e)).powf(one / m1)
+    }
+}
+
+impl ChannelEncoder for PqEncoding {
+    fn encode_channel<T>(&self, val: T) -> T
+    where
+        T: num_traits::Float,
+    {
+        let m1: T = num_traits::cast(0.1593017578125).unwrap();
+        let m2: T = num_traits::cast(78.84375).unwrap();
+        let c1: T = num_traits::cast(0.8359375).unwrap();
+        let c2: T = num_traits::cast(18.8515625).unwrap();
+        let c3: T = num_traits::cast(18.6875).unwrap();
+        let one: T = num_traits::cast(1.0).unwrap();
+
+        let y = val.abs().powf(m1);
+        val.signum() * ((c1 + c2 * y) / (one + c3 * y)).powf(m2)
+    }
+}
+
+impl ColorEncoding for PqEncoding {}
+
+impl fmt::Display for PqEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PQ")
+    }
+}
+
+/// The Hybrid Log-Gamma transfer function defined by ARIB STD-B67 and ITU-R BT.2100
+///
+/// `HlgEncoding` implements the scene-referred OETF and its inverse, with linear values normalized
+/// to `[0, 1]`. The lower half of the signal range follows a square root curve while the upper half
+/// is logarithmic.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct HlgEncoding;
+
+impl HlgEncoding {
+    /// Construct a new `HlgEncoding`
+    pub fn new() -> Self {
+        HlgEncoding {}
+    }
+}
+
+impl ChannelDecoder for HlgEncoding {
+    fn decode_channel<T>(&self, val: T) -> T
+    where
+        T: num_traits::Float,
+    {
+        let a: T = num_traits::cast(0.17883277).unwrap();
+        let b: T = num_traits::cast(0.28466892).unwrap();
+        let c: T = num_traits::cast(0.55991073).unwrap();
+        let half: T = num_traits::cast(0.5).unwrap();
+        let three: T = num_traits::cast(3.0).unwrap();
+        let twelve: T = num_traits::cast(12.0).unwrap();
+
+        let abs_val = val.abs();
+        if abs_val <= half {
+            val.signum() * abs_val * abs_val / three
+        } else {
+            val.signum() * (((abs_val - c) / a).exp() + b) / twelve
+        }
+    }
+}
+
+impl ChannelEncoder for HlgEncoding {
+    fn encode_channel<T>(&self, val: T) -> T
+    where
+        T: num_traits::Float,
+    {
+        let a: T = num_traits::cast(0.17883277).unwrap();
+        let b: T = num_traits::cast(0.28466892).unwrap();
+        let c: T = num_traits::cast(0.55991073).unwrap();
+        let three: T = num_traits::cast(3.0).unwrap();
+        let twelve: T = num_traits::cast(12.0).unwrap();
+
+        let abs_val = val.abs();
+        if abs_val <= twelve.recip() {
+            val.signum() * (three * abs_val).sqrt()
+        } else {
+            val.signum() * (a * (twelve * abs_val - b).ln() + c)
+        }
+    }
+}
+
+impl ColorEncoding for HlgEncoding {}
+
+impl fmt::Display for HlgEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HLG")
+    }
+}
+
+/// The ROMM transfer function used by the ProPhoto RGB color space
+///
+/// ROMM is a $`1.8`$ gamma curve with a short linear segment of slope $`16`$ for linear values below
+/// $`1/512`$.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct RommEncoding;
+
+impl RommEncoding {
+    /// Construct a new `RommEncoding`
+    pub fn new() -> Self {
+        RommEncoding {}
+    }
+}
+
+impl ChannelDecoder for RommEncoding {
+    fn decode_channel<T>(&self, val: T) -> T
+    where
+        T: num_traits::Float,
+    {
+        let threshold: T = num_traits::cast(1.0 / 32.0).unwrap();
+        let slope: T = num_traits::cast(16.0).unwrap();
+        let gamma: T = num_traits::cast(1.8).unwrap();
+
+        if val.abs() < threshold {
+            val / slope
+        } else {
+            val.signum() * val.abs().powf(gamma)
+        }
+    }
+}
+
+impl ChannelEncoder for RommEncoding {
+    fn encode_channel<T>(&self, val: T) -> T
+    where
+        T: num_traits::Float,
+    {
+        let threshold: T = num_traits::cast(1.0 / 512.0).unwrap();
+        let slope: T = num_traits::cast(16.0).unwrap();
+        let inv_gamma: T = num_traits::cast(1.0 / 1.8).unwrap();
+
+        if val.abs() < threshold {
+            slope * val
+        } else {
+            val.signum() * val.abs().powf(inv_gamma)
+        }
+    }
+}
+
+impl ColorEncoding for RommEncoding {}
+
+impl fmt::Display for RommEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ROMM")
+    }
+}
+
+/// The transfer function of the Adobe RGB (1998) color space
+///
+/// Adobe RGB is a pure gamma curve with $`\gamma = 563/256 \approx 2.2`$.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct AdobeRgbEncoding;
+
+impl AdobeRgbEncoding {
+    const GAMMA: f64 = 563.0 / 256.0;
+
+    /// Construct a new `AdobeRgbEncoding`
+    pub fn new() -> Self {
+        AdobeRgbEncoding {}
+    }
+}
+
+impl ChannelDecoder for AdobeRgbEncoding {
+    fn decode_channel<T>(&self, val: T) -> T
+    where
+        T: num_traits::Float,
+    {
+        let gamma: T = num_traits::cast(Self::GAMMA).unwrap();
+        val.signum() * val.abs().powf(gamma)
+    }
+}
+
+impl ChannelEncoder for AdobeRgbEncoding {
+    fn encode_channel<T>(&self, val: T) -> T
+    where
+        T: num_traits::Float,
+    {
+        let inv_gamma: T = num_traits::cast(1.0 / Self::GAMMA).unwrap();
+        val.signum() * val.abs().powf(inv_gamma)
+    }
+}
+
+impl ColorEncoding for AdobeRgbEncoding {}
+
+impl fmt::Display for AdobeRgbEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Adobe RGB")
+    }
+}
+
 impl<T> TranscodableColor for Rgb<T>
 where
     T: PosNormalChannelScalar + ChannelFormatCast<f64>,
@@ -280,6 +674,53 @@ where
     }
 }
 
+impl<T> TranscodableColor for Luma<T>
+where
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+{
+    type IntermediateColor = Luma<f64>;
+    fn encode_color<Encoder>(self, enc: &Encoder) -> Self
+    where
+        Encoder: ChannelEncoder,
+    {
+        let flt_color: Self::IntermediateColor = self.color_cast();
+        Luma::new(enc.encode_channel(flt_color.luma())).color_cast()
+    }
+
+    fn decode_color<Decoder>(self, dec: &Decoder) -> Self
+    where
+        Decoder: ChannelDecoder,
+    {
+        let flt_color: Self::IntermediateColor = self.color_cast();
+        Luma::new(dec.decode_channel(flt_color.luma())).color_cast()
+    }
+}
+
+impl<T> TranscodableColor for LumaA<T>
+where
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+{
+    type IntermediateColor = LumaA<f64>;
+
+    fn encode_color<Encoder>(self, enc: &Encoder) -> Self
+    where
+        Encoder: ChannelEncoder,
+    {
+        let (color, alpha) = self.decompose();
+        LumaA::new(color.encode_color(enc), alpha)
+    }
+
+    fn decode_color<Decoder>(self, dec: &Decoder) -> Self
+    where
+        Decoder: ChannelDecoder,
+    {
+        let (color, alpha) = self.decompose();
+        LumaA::new(color.decode_color(dec), alpha)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -359,4 +800,94 @@ mod test {
         );
         assert_relative_eq!(t6.decode(), c6, epsilon = 1e-6);
     }
+
+    #[test]
+    fn test_rec709_encoding() {
+        let c1 = Rgb::new(0.0, 0.0, 0.0).linear();
+        let t1 = c1.clone().encode(Rec709Encoding::new());
+        assert_relative_eq!(t1.color(), c1.color(), epsilon = 1e-6);
+
+        let c2 = Rgb::new(1.0, 1.0, 1.0).linear();
+        let t2 = c2.clone().encode(Rec709Encoding::new());
+        assert_relative_eq!(t2.color(), c2.color(), epsilon = 1e-6);
+
+        let c3 = Rgb::new(0.01, 0.18, 0.5).linear();
+        let t3 = c3.clone().encode(Rec709Encoding::new());
+        assert_relative_eq!(
+            *t3.color(),
+            Rgb::new(0.045, 0.409008, 0.705515),
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(t3.decode(), c3, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_rec2020_encoding() {
+        let c1 = Rgb::new(0.0, 0.0, 0.0).linear();
+        let t1 = c1.clone().encode(Rec2020Encoding::new());
+        assert_relative_eq!(t1.color(), c1.color(), epsilon = 1e-6);
+
+        let c2 = Rgb::new(1.0, 1.0, 1.0).linear();
+        let t2 = c2.clone().encode(Rec2020Encoding::new());
+        assert_relative_eq!(t2.color(), c2.color(), epsilon = 1e-6);
+
+        let c3 = Rgb::new(0.01, 0.18, 0.5).linear();
+        let t3 = c3.clone().encode(Rec2020Encoding::new());
+        assert_relative_eq!(
+            *t3.color(),
+            Rgb::new(0.045, 0.408848, 0.705436),
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(t3.decode(), c3, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_pq_encoding() {
+        let c1 = Rgb::new(0.0, 0.0, 0.0).linear();
+        let t1 = c1.clone().encode(PqEncoding::new());
+        assert_relative_eq!(*t1.color(), Rgb::broadcast(0.0000007306), epsilon = 1e-6);
+
+        let c2 = Rgb::new(1.0, 1.0, 1.0).linear();
+        let t2 = c2.clone().encode(PqEncoding::new());
+        assert_relative_eq!(t2.color(), c2.color(), epsilon = 1e-6);
+
+        // 100 cd/m^2 and 1000 cd/m^2 land at the well known PQ code values
+        let c3 = Rgb::new(0.01, 0.1, 0.0).linear();
+        let t3 = c3.clone().encode(PqEncoding::new());
+        assert_relative_eq!(t3.red(), 0.508078, epsilon = 1e-5);
+        assert_relative_eq!(t3.green(), 0.751827, epsilon = 1e-5);
+        assert_relative_eq!(t3.decode(), c3, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_hlg_encoding() {
+        let c1 = Rgb::new(0.0, 0.0, 0.0).linear();
+        let t1 = c1.clone().encode(HlgEncoding::new());
+        assert_relative_eq!(t1.color(), c1.color(), epsilon = 1e-6);
+
+        let c2 = Rgb::new(1.0, 1.0, 1.0).linear();
+        let t2 = c2.clone().encode(HlgEncoding::new());
+        assert_relative_eq!(t2.color(), c2.color(), epsilon = 1e-6);
+
+        let c3 = Rgb::new(1.0 / 12.0, 0.05, 0.5).linear();
+        let t3 = c3.clone().encode(HlgEncoding::new());
+        assert_relative_eq!(*t3.color(), Rgb::new(0.5, 0.387298, 0.871643), epsilon = 1e-6);
+        assert_relative_eq!(t3.decode(), c3, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_romm_encoding() {
+        let c1 = Rgb::new(0.0, 0.0, 0.0).linear();
+        let t1 = c1.clone().encode(RommEncoding::new());
+        assert_relative_eq!(t1.color(), c1.color(), epsilon = 1e-6);
+
+        let c2 = Rgb::new(1.0, 1.0, 1.0).linear();
+        let t2 = c2.clone().encode(RommEncoding::new());
+        assert_relative_eq!(t2.color(), c2.color(), epsilon = 1e-6);
+
+        let c3 = Rgb::new(0.001, 0.18, 0.5).linear();
+        let t3 = c3.clone().encode(RommEncoding::new());
+        assert_relative_eq!(*t3.color(), Rgb::new(0.016, 0.385711, 0.680395), epsilon = 1e-6);
+        assert_relative_eq!(t3.decode(), c3, epsilon = 1e-6);
+    }
 }
@@ -7,28 +7,127 @@ use crate::convert::{FromColor, FromHsi, FromYCbCr};
 use crate::encoding::encode::{ColorEncoding, LinearEncoding, TranscodableColor};
 use crate::hsi::{Hsi, HsiOutOfGamutMode};
 use crate::ycbcr::{YCbCr, YCbCrModel, YCbCrOutOfGamutMode};
-use crate::{Bounded, Broadcast, Color, Color3, Color4, FromTuple, Invert, Lerp, PolarColor};
+use crate::{
+    Bounded, Broadcast, ChannelAccess, Color, Color3, Color4, FromTuple, HomogeneousColor, Invert,
+    Lerp, Luma, PolarColor, Rgb,
+};
 use angle::Angle;
 #[cfg(feature = "approx")]
 use approx;
 use num_traits;
 
 use std::fmt;
+use std::ops;
 use std::ops::{Deref, DerefMut};
 
 /// A color decorated with its encoding. This is the primary way to use encodings.
 ///
 /// As most encodings are zero-sized structs except for `GammaEncoding`, there will be no size
 /// penalty for using `EncodedColor`.
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct EncodedColor<C, E> {
     color: C,
     encoding: E,
 }
 
 /// A color with a linear encoding
+///
+/// Light adds linearly, so linear colors with floating point channels implement element-wise `+`,
+/// `-`, `*` and `/`. Linear `Rgb` and `Luma` can also be multiplied and divided by a scalar to
+/// scale their intensity. This makes it straightforward to accumulate and average samples or adjust
+/// exposure. Encoded colors have none of these operations, as the results would be meaningless.
+///
+/// ## Examples:
+///
+/// ```rust
+/// #[macro_use] extern crate approx;
+/// # extern crate prisma;
+///
+/// use prisma::Rgb;
+/// use prisma::encoding::EncodableColor;
+///
+/// let samples = [
+///     Rgb::new(1.0, 0.0, 0.5f32).linear(),
+///     Rgb::new(0.0, 1.0, 0.5).linear(),
+/// ];
+/// let mut sum = Rgb::new(0.0, 0.0, 0.0).linear();
+/// for sample in samples.iter() {
+///     sum += sample.clone();
+/// }
+/// assert_relative_eq!(sum / 2.0, Rgb::new(0.5, 0.5, 0.5).linear());
+///
+/// let exposed = samples[0].clone() * 2.0;
+/// assert_relative_eq!(exposed, Rgb::new(2.0, 0.0, 1.0).linear());
+/// ```
 pub type LinearColor<C> = EncodedColor<C, LinearEncoding>;
 
+macro_rules! impl_linear_color_ops {
+    ($($op:ident $method:ident $op_assign:ident $method_assign:ident $sym:tt),*) => {
+        $(
+            impl<C> ops::$op for EncodedColor<C, LinearEncoding>
+            where
+                C: ChannelAccess,
+                C::ChannelFormat: num_traits::Float,
+            {
+                type Output = Self;
+                fn $method(mut self, rhs: Self) -> Self {
+                    ops::$op_assign::$method_assign(&mut self, rhs);
+                    self
+                }
+            }
+
+            impl<C> ops::$op_assign for EncodedColor<C, LinearEncoding>
+            where
+                C: ChannelAccess,
+                C::ChannelFormat: num_traits::Float,
+            {
+                fn $method_assign(&mut self, rhs: Self) {
+                    self.color = self.color.clone().zip_channels(&rhs.color, |a, b| a $sym b);
+                }
+            }
+        )*
+    };
+}
+
+impl_linear_color_ops!(
+    Add add AddAssign add_assign +,
+    Sub sub SubAssign sub_assign -,
+    Mul mul MulAssign mul_assign *,
+    Div div DivAssign div_assign /
+);
+
+macro_rules! impl_linear_color_scalar_ops {
+    ($($color:ident),*) => {
+        $(
+            impl_linear_color_scalar_ops!(@op $color, Mul mul MulAssign mul_assign *);
+            impl_linear_color_scalar_ops!(@op $color, Div div DivAssign div_assign /);
+        )*
+    };
+    (@op $color:ident, $op:ident $method:ident $op_assign:ident $method_assign:ident $sym:tt) => {
+        impl<T> ops::$op<T> for EncodedColor<$color<T>, LinearEncoding>
+        where
+            T: PosNormalChannelScalar + num_traits::Float,
+        {
+            type Output = Self;
+            fn $method(mut self, rhs: T) -> Self {
+                ops::$op_assign::$method_assign(&mut self, rhs);
+                self
+            }
+        }
+
+        impl<T> ops::$op_assign<T> for EncodedColor<$color<T>, LinearEncoding>
+        where
+            T: PosNormalChannelScalar + num_traits::Float,
+        {
+            fn $method_assign(&mut self, rhs: T) {
+                self.color = self.color.clone().map_channels(|c| c $sym rhs);
+            }
+        }
+    };
+}
+
+impl_linear_color_scalar_ops!(Rgb, Luma);
+
 impl<C, E> EncodedColor<C, E>
 where
     C: Color + EncodableColor,
@@ -348,7 +447,7 @@ where
 mod tests {
     use super::*;
     use crate::test;
-    use crate::{Hsv, Rgb};
+    use crate::{Hsv, Rgba};
     use angle::Deg;
     use approx::*;
 
@@ -399,4 +498,28 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_linear_ops() {
+        let c1 = Rgb::new(0.5, 0.25, 1.0).linear();
+        let c2 = Rgb::new(0.25, 0.25, 0.5).linear();
+        assert_relative_eq!(c1.clone() + c2.clone(), Rgb::new(0.75, 0.5, 1.5).linear());
+        assert_relative_eq!(c1.clone() - c2.clone(), Rgb::new(0.25, 0.0, 0.5).linear());
+        assert_relative_eq!(
+            c1.clone() * c2.clone(),
+            Rgb::new(0.125, 0.0625, 0.5).linear()
+        );
+        assert_relative_eq!(c1.clone() / c2, Rgb::new(2.0, 1.0, 2.0).linear());
+        assert_relative_eq!(c1.clone() * 0.5, Rgb::new(0.25, 0.125, 0.5).linear());
+        assert_relative_eq!(c1 / 0.5, Rgb::new(1.0, 0.5, 2.0).linear());
+
+        let mut c3 = Luma::new(0.25f32).linear();
+        c3 *= 3.0;
+        c3 -= Luma::new(0.5).linear();
+        assert_relative_eq!(c3, Luma::new(0.25).linear());
+
+        let c4 = Rgba::new(Rgb::new(0.25, 0.5, 0.75), 0.5).linear();
+        let c5 = Rgba::new(Rgb::new(0.25, 0.25, 0.25), 0.25).linear();
+        assert_relative_eq!(c4 + c5, Rgba::new(Rgb::new(0.5, 0.75, 1.0), 0.75).linear());
+    }
 }
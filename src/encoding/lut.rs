@@ -0,0 +1,151 @@
+//! Table driven sRGB conversions for 8-bit channels
+
+use super::encode::{ChannelDecoder, ChannelEncoder, SrgbEncoding};
+use crate::rgb::Rgb;
+use std::sync::OnceLock;
+
+/// The number of entries in the encoding table
+const ENCODE_TABLE_SIZE: usize = 4096;
+
+fn decode_table() -> &'static [f32; 256] {
+    static TABLE: OnceLock<[f32; 256]> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut table = [0.0; 256];
+        for (i, entry) in table.iter_mut().enumerate() {
+            *entry = SrgbEncoding.decode_channel(i as f64 / 255.0) as f32;
+        }
+        table
+    })
+}
+
+fn encode_table() -> &'static [u8; ENCODE_TABLE_SIZE] {
+    static TABLE: OnceLock<[u8; ENCODE_TABLE_SIZE]> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut table = [0; ENCODE_TABLE_SIZE];
+        let max = (ENCODE_TABLE_SIZE - 1) as f64;
+        for (i, entry) in table.iter_mut().enumerate() {
+            *entry = (SrgbEncoding.encode_channel(i as f64 / max) * 255.0).round() as u8;
+        }
+        table
+    })
+}
+
+/// The linear values halfway between adjacent 8-bit levels
+///
+/// Entry `i` is the smallest linear value that encodes to level `i + 1`.
+fn threshold_table() -> &'static [f64; 255] {
+    static TABLE: OnceLock<[f64; 255]> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut table = [0.0; 255];
+        for (i, entry) in table.iter_mut().enumerate() {
+            *entry = SrgbEncoding.decode_channel((i as f64 + 0.5) / 255.0);
+        }
+        table
+    })
+}
+
+/// Decode an sRGB encoded 8-bit channel to a linear value in `[0, 1]`
+///
+/// The result is read from a 256 entry table, so it is exact to `f32` precision.
+pub fn srgb_u8_to_linear(value: u8) -> f32 {
+    decode_table()[value as usize]
+}
+
+/// Encode a linear value as an sRGB 8-bit channel
+///
+/// `value` is clamped to `[0, 1]`, with `NaN` encoding to `0`. A 4096 entry table gives a first
+/// guess, which is then corrected against the halfway points between levels, so the result is
+/// always the exact encoding rounded to the nearest level. Every value from
+/// [`srgb_u8_to_linear`](fn.srgb_u8_to_linear.html) encodes back to where it started.
+pub fn linear_to_srgb_u8(value: f32) -> u8 {
+    if value.is_nan() || value <= 0.0 {
+        return 0;
+    }
+    let value = value.min(1.0);
+    let max = (ENCODE_TABLE_SIZE - 1) as f32;
+    let mut level = encode_table()[(value * max + 0.5) as usize] as usize;
+
+    let thresholds = threshold_table();
+    let value = f64::from(value);
+    while level < 255 && value >= thresholds[level] {
+        level += 1;
+    }
+    while level > 0 && value < thresholds[level - 1] {
+        level -= 1;
+    }
+    level as u8
+}
+
+/// Decode an sRGB encoded `Rgb<u8>` to linear `Rgb<f32>` using a lookup table
+pub fn decode_srgb_u8(color: &Rgb<u8>) -> Rgb<f32> {
+    Rgb::new(
+        srgb_u8_to_linear(color.red()),
+        srgb_u8_to_linear(color.green()),
+        srgb_u8_to_linear(color.blue()),
+    )
+}
+
+/// Encode a linear `Rgb<f32>` as sRGB `Rgb<u8>` using a lookup table
+pub fn encode_srgb_u8(color: &Rgb<f32>) -> Rgb<u8> {
+    Rgb::new(
+        linear_to_srgb_u8(color.red()),
+        linear_to_srgb_u8(color.green()),
+        linear_to_srgb_u8(color.blue()),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use approx::*;
+
+    #[test]
+    fn test_decode() {
+        for i in 0..=255u8 {
+            let exact = SrgbEncoding.decode_channel(f32::from(i) / 255.0);
+            assert_relative_eq!(srgb_u8_to_linear(i), exact, epsilon = 1e-6);
+        }
+        assert_eq!(srgb_u8_to_linear(0), 0.0);
+        assert_eq!(srgb_u8_to_linear(255), 1.0);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for i in 0..=255u8 {
+            let linear = srgb_u8_to_linear(i);
+            assert_eq!(linear_to_srgb_u8(linear), i);
+            assert_eq!(
+                encode_srgb_u8(&decode_srgb_u8(&Rgb::new(i, i, i))),
+                Rgb::new(i, i, i)
+            );
+        }
+    }
+
+    #[test]
+    fn test_encode() {
+        let exact =
+            |value: f32| (SrgbEncoding.encode_channel(f64::from(value)) * 255.0).round() as u8;
+        for i in 0..=100_000 {
+            let linear = i as f32 / 100_000.0;
+            assert_eq!(linear_to_srgb_u8(linear), exact(linear));
+        }
+        for (i, threshold) in threshold_table().iter().enumerate() {
+            let below = f32::from_bits((*threshold as f32).to_bits() - 1);
+            let above = f32::from_bits((*threshold as f32).to_bits() + 1);
+            assert_eq!(linear_to_srgb_u8(below), i as u8);
+            assert_eq!(linear_to_srgb_u8(above), i as u8 + 1);
+        }
+        assert_eq!(linear_to_srgb_u8(-0.5), 0);
+        assert_eq!(linear_to_srgb_u8(2.0), 255);
+        assert_eq!(linear_to_srgb_u8(f32::NAN), 0);
+        assert_eq!(linear_to_srgb_u8(f32::INFINITY), 255);
+    }
+
+    #[test]
+    fn test_rgb() {
+        let color = Rgb::new(255u8, 128, 0);
+        let linear = decode_srgb_u8(&color);
+        assert_relative_eq!(linear, Rgb::new(1.0, 0.2158605, 0.0), epsilon = 1e-6);
+        assert_eq!(encode_srgb_u8(&linear), color);
+    }
+}
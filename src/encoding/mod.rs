@@ -30,11 +30,17 @@
 //!
 //! ## Encoding Schemes:
 //!
-//! Prisma provides three different encoding schemes:
+//! Prisma provides the following encoding schemes:
 //!
 //! * [`LinearEncoding`](encode/struct.LinearEncoding.html) A color with no encoding at all, linear in intensity
 //! * [`SrgbEncoding`](encode/struct.SrgbEncoding.html) A modified gamma encoding used specifically with the sRGB color space
 //! * [`GammaEncoding`](encode/struct.GammaEncoding.html) A general gamma encoding with specified value for gamma
+//! * [`Rec709Encoding`](encode/struct.Rec709Encoding.html) The transfer function of BT.709 and BT.601 video
+//! * [`Rec2020Encoding`](encode/struct.Rec2020Encoding.html) The standard dynamic range transfer function of BT.2020
+//! * [`PqEncoding`](encode/struct.PqEncoding.html) The SMPTE ST 2084 perceptual quantizer used for HDR video
+//! * [`HlgEncoding`](encode/struct.HlgEncoding.html) The hybrid log-gamma curve used for HDR broadcast
+//! * [`RommEncoding`](encode/struct.RommEncoding.html) The 1.8 gamma curve of ProPhoto RGB
+//! * [`AdobeRgbEncoding`](encode/struct.AdobeRgbEncoding.html) The 2.2 gamma curve of Adobe RGB (1998)
 //!
 //! A color can have its encoding specified in the type system by wrapping it in [`EncodedColor`](encoded_color/struct.EncodedColor.html).
 //!
@@ -51,6 +57,16 @@
 //! `encoded_as` method of [`DeviceDependentColor`](struct.DeviceDependentColor.html). This does not
 //! do any conversion, it's up to you to ensure the color is actually encoded as you specify.
 //!
+//! ## 8-bit sRGB:
+//!
+//! Decoding and encoding 8-bit sRGB images is common enough to have a fast path that avoids
+//! calling `powf` for every channel. [`srgb_u8_to_linear`](fn.srgb_u8_to_linear.html) reads from
+//! a 256 entry table, and [`linear_to_srgb_u8`](fn.linear_to_srgb_u8.html) from a 4096 entry
+//! table checked against the halfway points between levels, so it always rounds correctly and every
+//! 8-bit value round-trips exactly through `f32`. [`decode_srgb_u8`](fn.decode_srgb_u8.html) and
+//! [`encode_srgb_u8`](fn.encode_srgb_u8.html) apply them to a whole `Rgb` color. The tables are
+//! built the first time they are used.
+//!
 //! Examples:
 //! =========
 //!
@@ -69,12 +85,15 @@ use num_traits::Float;
 
 mod encode;
 mod encoded_color;
+mod lut;
 
 pub use self::encode::{
-    ChannelDecoder, ChannelEncoder, ColorEncoding, GammaEncoding, LinearEncoding, SrgbEncoding,
-    TranscodableColor,
+    AdobeRgbEncoding, ChannelDecoder, ChannelEncoder, ColorEncoding, DynColorEncoding,
+    GammaEncoding, HlgEncoding, LinearEncoding, PqEncoding, Rec2020Encoding, Rec709Encoding,
+    RommEncoding, SharedEncoding, SrgbEncoding, TranscodableColor,
 };
 pub use self::encoded_color::{EncodedColor, LinearColor};
+pub use self::lut::{decode_srgb_u8, encode_srgb_u8, linear_to_srgb_u8, srgb_u8_to_linear};
 
 /// A color that can be stored in an `EncodedColor` object.
 pub trait EncodableColor: crate::Color {
@@ -0,0 +1,348 @@
+//! A fixed-point channel format for targets without floating point hardware
+//!
+//! [`Fixed16`](struct.Fixed16.html) stores a value in [0, 1] in 16 bits, with `0xFFFF` being one
+//! like a `u16` channel. Unlike `u16`, its arithmetic is fixed-point: multiplying two values scales
+//! the result back into range, so `Fixed16::ONE * x == x`. It is a `PosNormalChannelScalar`, so it
+//! can be used as the channel of `Rgb`, `Luma` and `Alpha`, and casts to and from the other
+//! channel formats.
+//!
+//! The conversions between `Rgb` and `Hsv` in the rest of prisma require float channels, and
+//! `Lerp` positions are always floats. This module provides integer-only versions of both:
+//! [`rgb_to_hsv`](fn.rgb_to_hsv.html) and [`hsv_to_rgb`](fn.hsv_to_rgb.html) represent the hue as a
+//! fraction of a turn, and [`lerp`](fn.lerp.html) interpolates any flat color by a `Fixed16`
+//! position.
+//!
+//! ## Examples:
+//!
+//! ```rust
+//! # extern crate prisma;
+//!
+//! use prisma::Rgb;
+//! use prisma::fixed::{self, Fixed16};
+//!
+//! let green = Rgb::new(Fixed16::from_bits(0x3333), Fixed16::ONE, Fixed16::from_bits(0x3333));
+//! let (hue, saturation, value) = fixed::rgb_to_hsv(&green);
+//! // One third of a turn
+//! assert_eq!(hue, Fixed16::from_bits(0x5555));
+//! assert_eq!((saturation, value), (Fixed16::from_bits(0xCCCC), Fixed16::ONE));
+//! assert_eq!(fixed::hsv_to_rgb(hue, saturation, value), green);
+//!
+//! let black = Rgb::new(Fixed16::ZERO, Fixed16::ZERO, Fixed16::ZERO);
+//! let half = fixed::lerp(&black, &green, Fixed16::from_bits(0x8000));
+//! assert_eq!(half.color_cast::<u8>(), Rgb::new(25u8, 128, 25));
+//! ```
+
+use crate::channel::{
+    BoundedChannelScalar, ChannelFormatCast, PosNormalChannelScalar, SaturatingChannelScalar,
+};
+use crate::color::{Flatten, HomogeneousColor, Lerp};
+use crate::rgb::Rgb;
+use std::fmt;
+use std::ops;
+
+const MAX: u32 = 0xFFFF;
+
+/// An unsigned fixed-point number in [0, 1] with 16 bits of precision
+///
+/// `0x0000` is zero and `0xFFFF` is one. Addition and subtraction saturate at the ends of the
+/// range, and multiplication rounds to the nearest value.
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fixed16(u16);
+
+impl Fixed16 {
+    /// Zero
+    pub const ZERO: Fixed16 = Fixed16(0);
+    /// One
+    pub const ONE: Fixed16 = Fixed16(0xFFFF);
+
+    /// Construct a `Fixed16` from its raw representation
+    pub const fn from_bits(bits: u16) -> Self {
+        Fixed16(bits)
+    }
+
+    /// Returns the raw representation
+    pub const fn to_bits(self) -> u16 {
+        self.0
+    }
+
+    /// Divide `self` by `divisor`, saturating at one
+    ///
+    /// Dividing by zero returns one, unless `self` is also zero.
+    pub fn saturating_div(self, divisor: Fixed16) -> Self {
+        if divisor.0 == 0 {
+            return if self.0 == 0 {
+                Fixed16::ZERO
+            } else {
+                Fixed16::ONE
+            };
+        }
+        let divisor = u32::from(divisor.0);
+        let quotient = (u32::from(self.0) * MAX + divisor / 2) / divisor;
+        Fixed16(quotient.min(MAX) as u16)
+    }
+
+    /// Interpolate between `self` and `right` without using floating point
+    pub fn lerp_fixed(self, right: Fixed16, pos: Fixed16) -> Self {
+        let (left, right, pos) = (u32::from(self.0), u32::from(right.0), u32::from(pos.0));
+        Fixed16(((left * (MAX - pos) + right * pos + MAX / 2) / MAX) as u16)
+    }
+}
+
+impl ops::Add for Fixed16 {
+    type Output = Fixed16;
+    fn add(self, rhs: Fixed16) -> Fixed16 {
+        Fixed16(self.0.saturating_add(rhs.0))
+    }
+}
+
+impl ops::Sub for Fixed16 {
+    type Output = Fixed16;
+    fn sub(self, rhs: Fixed16) -> Fixed16 {
+        Fixed16(self.0.saturating_sub(rhs.0))
+    }
+}
+
+impl ops::Mul for Fixed16 {
+    type Output = Fixed16;
+    fn mul(self, rhs: Fixed16) -> Fixed16 {
+        Fixed16(((u32::from(self.0) * u32::from(rhs.0) + MAX / 2) / MAX) as u16)
+    }
+}
+
+impl BoundedChannelScalar for Fixed16 {}
+
+impl SaturatingChannelScalar for Fixed16 {
+    fn saturating_add(self, rhs: Self) -> Self {
+        self + rhs
+    }
+    fn saturating_sub(self, rhs: Self) -> Self {
+        self - rhs
+    }
+    fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Fixed16)
+    }
+    fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(Fixed16)
+    }
+}
+
+impl PosNormalChannelScalar for Fixed16 {
+    #[inline]
+    fn min_bound() -> Self {
+        Fixed16::ZERO
+    }
+    #[inline]
+    fn max_bound() -> Self {
+        Fixed16::ONE
+    }
+    #[inline]
+    fn is_normalized(&self) -> bool {
+        true
+    }
+    #[inline]
+    fn normalize(self) -> Self {
+        self
+    }
+}
+
+impl Lerp for Fixed16 {
+    type Position = f64;
+    fn lerp(&self, right: &Self, pos: f64) -> Self {
+        let left: f64 = self.cast();
+        let right: f64 = right.cast();
+        (left * (1.0 - pos) + right * pos).cast()
+    }
+}
+
+impl fmt::Display for Fixed16 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value: f64 = self.cast();
+        write!(f, "{}", value)
+    }
+}
+
+macro_rules! impl_fixed_cast_via_u16 {
+    ($($to: ty),*) => {
+        $(
+            impl ChannelFormatCast<$to> for Fixed16 {
+                fn cast(self) -> $to {
+                    self.0.cast()
+                }
+            }
+            impl ChannelFormatCast<Fixed16> for $to {
+                fn cast(self) -> Fixed16 {
+                    Fixed16(self.cast())
+                }
+            }
+        )*
+    };
+}
+
+impl_fixed_cast_via_u16!(u8, u16, u32, f32, f64);
+
+impl ChannelFormatCast<Fixed16> for Fixed16 {
+    fn cast(self) -> Fixed16 {
+        self
+    }
+}
+
+/// Interpolate between two colors with `Fixed16` channels without using floating point
+///
+/// Every channel, including alpha, is interpolated linearly.
+pub fn lerp<C>(left: &C, right: &C, pos: Fixed16) -> C
+where
+    C: Flatten + HomogeneousColor<ChannelFormat = Fixed16>,
+{
+    let mut channels = [Fixed16::ZERO; 4];
+    let count = C::num_channels() as usize;
+    for (out, (l, r)) in channels
+        .iter_mut()
+        .zip(left.as_slice().iter().zip(right.as_slice()))
+    {
+        *out = l.lerp_fixed(*r, pos);
+    }
+    C::from_slice(&channels[..count])
+}
+
+/// Convert an `Rgb` color to hue, saturation and value without using floating point
+///
+/// The hue is returned as a fraction of a turn, so that red is zero, green is one third and blue
+/// is two thirds. Gray colors have a hue of zero.
+pub fn rgb_to_hsv(rgb: &Rgb<Fixed16>) -> (Fixed16, Fixed16, Fixed16) {
+    let (red, green, blue) = (rgb.red(), rgb.green(), rgb.blue());
+    let max = red.max(green).max(blue);
+    let min = red.min(green).min(blue);
+    let delta = i64::from((max - min).0);
+    if delta == 0 {
+        return (Fixed16::ZERO, Fixed16::ZERO, max);
+    }
+
+    // The hue in sixths of a turn, scaled by delta
+    let diff = |a: Fixed16, b: Fixed16| i64::from(a.0) - i64::from(b.0);
+    let mut sixths = if max == red {
+        diff(green, blue)
+    } else if max == green {
+        2 * delta + diff(blue, red)
+    } else {
+        4 * delta + diff(red, green)
+    };
+    if sixths < 0 {
+        sixths += 6 * delta;
+    }
+    let hue = (sixths * i64::from(MAX) + 3 * delta) / (6 * delta);
+    let hue = if hue >= i64::from(MAX) { 0 } else { hue as u16 };
+
+    (Fixed16(hue), (max - min).saturating_div(max), max)
+}
+
+/// Convert a hue, saturation and value to an `Rgb` color without using floating point
+///
+/// `hue` is a fraction of a turn, as returned by [`rgb_to_hsv`](fn.rgb_to_hsv.html). A hue of one
+/// is the same as zero.
+pub fn hsv_to_rgb(hue: Fixed16, saturation: Fixed16, value: Fixed16) -> Rgb<Fixed16> {
+    let sixths = u32::from(hue.0) * 6;
+    let segment = (sixths / MAX) % 6;
+    let frac = Fixed16((sixths % MAX) as u16);
+
+    let min = value * (Fixed16::ONE - saturation);
+    let falling = value * (Fixed16::ONE - saturation * frac);
+    let rising = value * (Fixed16::ONE - saturation * (Fixed16::ONE - frac));
+
+    match segment {
+        0 => Rgb::new(value, rising, min),
+        1 => Rgb::new(falling, value, min),
+        2 => Rgb::new(min, value, rising),
+        3 => Rgb::new(min, falling, value),
+        4 => Rgb::new(rising, min, value),
+        _ => Rgb::new(value, min, falling),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::alpha::Rgba;
+    use crate::color::Invert;
+
+    fn fx(value: f64) -> Fixed16 {
+        value.cast()
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let half = Fixed16::from_bits(0x8000);
+        assert_eq!(Fixed16::ONE * half, half);
+        assert_eq!(half * half, Fixed16::from_bits(0x4000));
+        assert_eq!(half + half, Fixed16::from_bits(0xFFFF));
+        assert_eq!(Fixed16::ONE + half, Fixed16::ONE);
+        assert_eq!(Fixed16::ZERO - half, Fixed16::ZERO);
+        assert_eq!(
+            Fixed16::from_bits(0x4000).saturating_div(half),
+            Fixed16::from_bits(0x8000)
+        );
+        assert_eq!(half.saturating_div(Fixed16::ZERO), Fixed16::ONE);
+        assert_eq!(
+            Fixed16::ONE.lerp_fixed(Fixed16::ZERO, half),
+            Fixed16::from_bits(0x7FFF)
+        );
+
+        let x: f64 = Fixed16::ONE.cast();
+        assert_eq!(x, 1.0);
+        let x: u8 = Fixed16::from_bits(0x80FF).cast();
+        assert_eq!(x, 0x80);
+        assert_eq!(ChannelFormatCast::<Fixed16>::cast(0xFFu8), Fixed16::ONE);
+    }
+
+    #[test]
+    fn test_channel() {
+        let rgb = Rgb::new(Fixed16::ONE, Fixed16::from_bits(0x4000), Fixed16::ZERO);
+        assert_eq!(
+            rgb.invert(),
+            Rgb::new(Fixed16::ZERO, Fixed16::from_bits(0xBFFF), Fixed16::ONE)
+        );
+        assert_eq!(rgb.color_cast::<u8>(), Rgb::new(255, 64, 0));
+        assert_eq!(
+            Rgb::new(255u8, 64, 0).color_cast::<Fixed16>(),
+            Rgb::new(Fixed16::ONE, Fixed16::from_bits(0x4040), Fixed16::ZERO)
+        );
+        assert_eq!(rgb.to_string(), "Rgb(1, 0.2500038147554742, 0)");
+
+        let rgba = Rgba::new(rgb, Fixed16::ONE);
+        let out = lerp(&rgba, &Rgba::new(rgb.invert(), Fixed16::ZERO), fx(0.25));
+        assert_eq!(out.color().color_cast::<u8>(), Rgb::new(192, 95, 63));
+        assert_eq!(out.alpha(), Fixed16::from_bits(0xC000));
+    }
+
+    #[test]
+    fn test_hsv() {
+        let cases = [
+            ((1.0, 0.0, 0.0), (0, 1.0, 1.0)),
+            ((0.0, 1.0, 0.0), (0x5555, 1.0, 1.0)),
+            ((0.0, 0.0, 1.0), (0xAAAA, 1.0, 1.0)),
+            ((0.5, 0.5, 0.5), (0, 0.0, 0.5)),
+            ((0.6, 0.2, 0.2), (0, 2.0 / 3.0, 0.6)),
+            ((0.2, 0.6, 0.2), (0x5555, 2.0 / 3.0, 0.6)),
+        ];
+        for &((r, g, b), (h, s, v)) in cases.iter() {
+            let rgb = Rgb::new(fx(r), fx(g), fx(b));
+            let hue = Fixed16::from_bits(h);
+            assert_eq!(rgb_to_hsv(&rgb), (hue, fx(s), fx(v)));
+            assert_eq!(hsv_to_rgb(hue, fx(s), fx(v)), rgb);
+        }
+        assert_eq!(
+            hsv_to_rgb(Fixed16::ONE, Fixed16::ONE, Fixed16::ONE),
+            hsv_to_rgb(Fixed16::ZERO, Fixed16::ONE, Fixed16::ONE)
+        );
+
+        for i in 0..=255u8 {
+            let rgb = Rgb::new(i, 255 - i, i / 3).color_cast::<Fixed16>();
+            let (h, s, v) = rgb_to_hsv(&rgb);
+            let out = hsv_to_rgb(h, s, v);
+            // The hue only has 16 bits, which is magnified six times in the ramping channel
+            for (a, b) in out.as_slice().iter().zip(rgb.as_slice()) {
+                assert!((i32::from(a.to_bits()) - i32::from(b.to_bits())).abs() <= 3);
+            }
+        }
+    }
+}
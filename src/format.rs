@@ -0,0 +1,297 @@
+//! Human readable formatting of channels as percentages and degrees
+//!
+//! The `Display` implementations of the color types write the raw channel values, such as
+//! `Rgb(255, 128, 0)` or `Hsl(2.0944, 0.5, 0.25)`, which round trip through `FromStr` but are
+//! hard to read at a glance. The helpers in this module write normalized channels as percentages
+//! and hues in degrees instead, whatever the channel and angle types are.
+//!
+//! [`Percent`](struct.Percent.html) and [`Degrees`](struct.Degrees.html) format single values,
+//! using the precision of the format string. Colors implementing
+//! [`FormatCssLike`](trait.FormatCssLike.html) can be written in the style of CSS color functions
+//! with `format_css_like`, which rounds each number to a configurable number of decimal places and
+//! drops trailing zeros.
+//!
+//! ## Examples:
+//!
+//! ```rust
+//! # extern crate prisma;
+//! extern crate angular_units as angle;
+//!
+//! use prisma::{Hsl, Rgb, Rgba};
+//! use prisma::format::{FormatCssLike, Percent};
+//! use angle::Rad;
+//!
+//! assert_eq!(format!("{:.1}", Percent(0.25f32)), "25.0%");
+//! assert_eq!(Rgb::new(255u8, 128, 0).format_css_like().to_string(), "rgb(100% 50.2% 0%)");
+//!
+//! let hsl = Hsl::new(Rad(std::f64::consts::FRAC_PI_3), 0.5, 0.25);
+//! assert_eq!(hsl.format_css_like().precision(0).to_string(), "hsl(60deg 50% 25%)");
+//!
+//! let rgba = Rgba::new(Rgb::new(0.0, 0.5, 1.0), 0.75);
+//! assert_eq!(rgba.format_css_like().to_string(), "rgb(0% 50% 100% / 75%)");
+//! ```
+
+use crate::alpha::Alpha;
+use crate::channel::{AngularChannelScalar, ChannelFormatCast, PosNormalChannelScalar};
+use crate::color::Color;
+use crate::hsl::Hsl;
+use crate::hsv::Hsv;
+use crate::hwb::Hwb;
+use crate::luma::Luma;
+use crate::rgb::Rgb;
+use num_traits;
+use std::fmt;
+
+/// The number of decimal places used by `format_css_like` unless another is chosen
+pub const DEFAULT_PRECISION: usize = 1;
+
+/// Displays a normalized channel value as a percentage
+///
+/// `Percent(0.5)` is written as `50%`. The precision of the format string, if any, sets the number
+/// of decimal places of the percentage.
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct Percent<T>(pub T);
+
+/// Displays an angle in degrees
+///
+/// The angle may be in any unit, such as `Rad` or `Turns`, and is converted to degrees before
+/// it is written with a trailing `deg`. The precision of the format string, if any, sets the
+/// number of decimal places.
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct Degrees<A>(pub A);
+
+impl<T> fmt::Display for Percent<T>
+where
+    T: ChannelFormatCast<f64> + Clone,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_with_precision(f, percent(self.0.clone()), f.precision())?;
+        write!(f, "%")
+    }
+}
+
+impl<A> fmt::Display for Degrees<A>
+where
+    A: AngularChannelScalar,
+    A::Scalar: num_traits::Float,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_with_precision(f, degrees(&self.0), f.precision())?;
+        write!(f, "deg")
+    }
+}
+
+/// A color that can be written in the style of a CSS color function
+///
+/// Colors are written as a function name followed by their channels in parentheses and separated
+/// by spaces, such as `hsl(120deg 50% 25%)`, matching the CSS Color Module Level 4 syntax for the
+/// models CSS supports.
+pub trait FormatCssLike {
+    /// Returns the name of the function the color is written as, such as `"rgb"`
+    fn css_function_name(&self) -> &'static str;
+    /// Write the channels of the color, separated by spaces, rounding each to `precision` decimal
+    /// places
+    fn write_css_channels(&self, f: &mut fmt::Formatter, precision: usize) -> fmt::Result;
+
+    /// Returns a value that displays the color with percentages and degrees
+    ///
+    /// Numbers are rounded to [`DEFAULT_PRECISION`](constant.DEFAULT_PRECISION.html) decimal
+    /// places unless changed with [`CssLike::precision`](struct.CssLike.html#method.precision).
+    fn format_css_like(&self) -> CssLike<'_, Self>
+    where
+        Self: Sized,
+    {
+        CssLike {
+            color: self,
+            precision: DEFAULT_PRECISION,
+        }
+    }
+}
+
+/// Displays a color in the style of a CSS color function
+///
+/// Returned by [`FormatCssLike::format_css_like`](trait.FormatCssLike.html#method.format_css_like).
+/// Trailing zeros after the decimal point are not written, so `100%` is written instead of
+/// `100.0%`.
+#[derive(Copy, Clone, Debug)]
+pub struct CssLike<'a, C> {
+    color: &'a C,
+    precision: usize,
+}
+
+impl<'a, C> CssLike<'a, C> {
+    /// Round numbers to `precision` decimal places
+    pub fn precision(self, precision: usize) -> Self {
+        CssLike { precision, ..self }
+    }
+}
+
+impl<'a, C> fmt::Display for CssLike<'a, C>
+where
+    C: FormatCssLike,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}(", self.color.css_function_name())?;
+        self.color.write_css_channels(f, self.precision)?;
+        write!(f, ")")
+    }
+}
+
+impl<T> FormatCssLike for Rgb<T>
+where
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+{
+    fn css_function_name(&self) -> &'static str {
+        "rgb"
+    }
+    fn write_css_channels(&self, f: &mut fmt::Formatter, precision: usize) -> fmt::Result {
+        write_percent(f, self.red(), precision)?;
+        write!(f, " ")?;
+        write_percent(f, self.green(), precision)?;
+        write!(f, " ")?;
+        write_percent(f, self.blue(), precision)
+    }
+}
+
+impl<T> FormatCssLike for Luma<T>
+where
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+{
+    fn css_function_name(&self) -> &'static str {
+        "luma"
+    }
+    fn write_css_channels(&self, f: &mut fmt::Formatter, precision: usize) -> fmt::Result {
+        write_percent(f, self.luma(), precision)
+    }
+}
+
+macro_rules! impl_format_css_like_polar {
+    ($name:ident, $css_name:expr, $first:ident, $second:ident) => {
+        impl<T, A> FormatCssLike for $name<T, A>
+        where
+            T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+            A: AngularChannelScalar,
+            A::Scalar: num_traits::Float,
+        {
+            fn css_function_name(&self) -> &'static str {
+                $css_name
+            }
+            fn write_css_channels(&self, f: &mut fmt::Formatter, precision: usize) -> fmt::Result {
+                write_number(f, degrees(&self.hue()), precision)?;
+                write!(f, "deg ")?;
+                write_percent(f, self.$first(), precision)?;
+                write!(f, " ")?;
+                write_percent(f, self.$second(), precision)
+            }
+        }
+    };
+}
+
+impl_format_css_like_polar!(Hsl, "hsl", saturation, lightness);
+impl_format_css_like_polar!(Hsv, "hsv", saturation, value);
+impl_format_css_like_polar!(Hwb, "hwb", whiteness, blackness);
+
+impl<T, InnerColor> FormatCssLike for Alpha<T, InnerColor>
+where
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+    InnerColor: Color + FormatCssLike,
+{
+    fn css_function_name(&self) -> &'static str {
+        self.color().css_function_name()
+    }
+    fn write_css_channels(&self, f: &mut fmt::Formatter, precision: usize) -> fmt::Result {
+        self.color().write_css_channels(f, precision)?;
+        write!(f, " / ")?;
+        write_percent(f, self.alpha(), precision)
+    }
+}
+
+fn percent<T: ChannelFormatCast<f64>>(value: T) -> f64 {
+    value.cast() * 100.0
+}
+
+fn degrees<A>(angle: &A) -> f64
+where
+    A: AngularChannelScalar,
+    A::Scalar: num_traits::Float,
+{
+    let turns = angle.scalar() / A::period();
+    num_traits::cast::<_, f64>(turns).unwrap() * 360.0
+}
+
+fn write_with_precision(
+    f: &mut fmt::Formatter,
+    value: f64,
+    precision: Option<usize>,
+) -> fmt::Result {
+    match precision {
+        Some(precision) => write!(f, "{:.*}", precision, value),
+        None => write!(f, "{}", value),
+    }
+}
+
+fn write_percent<T: ChannelFormatCast<f64>>(
+    f: &mut fmt::Formatter,
+    value: T,
+    precision: usize,
+) -> fmt::Result {
+    write_number(f, percent(value), precision)?;
+    write!(f, "%")
+}
+
+/// Write `value` rounded to `precision` decimal places, without trailing zeros
+fn write_number(f: &mut fmt::Formatter, value: f64, precision: usize) -> fmt::Result {
+    let rounded = format!("{:.*}", precision, value);
+    let trimmed = if rounded.contains('.') {
+        rounded.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        &rounded
+    };
+    if trimmed == "-0" {
+        write!(f, "0")
+    } else {
+        write!(f, "{}", trimmed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::alpha::Hsva;
+    use angle::{Deg, Turns};
+
+    #[test]
+    fn test_percent_and_degrees() {
+        assert_eq!(Percent(0.5f64).to_string(), "50%");
+        assert_eq!(format!("{:.2}", Percent(1.0f32 / 3.0)), "33.33%");
+        assert_eq!(format!("{:.0}", Percent(255u8)), "100%");
+        assert_eq!(Degrees(Turns(0.25f64)).to_string(), "90deg");
+        assert_eq!(format!("{:.1}", Degrees(Deg(12.345f32))), "12.3deg");
+    }
+
+    #[test]
+    fn test_format_css_like() {
+        let rgb = Rgb::new(1.0f32 / 3.0, 0.0, 1.0);
+        assert_eq!(rgb.format_css_like().to_string(), "rgb(33.3% 0% 100%)");
+        assert_eq!(
+            rgb.format_css_like().precision(3).to_string(),
+            "rgb(33.333% 0% 100%)"
+        );
+        assert_eq!(
+            Rgb::new(65535u16, 0, 32768).format_css_like().to_string(),
+            "rgb(100% 0% 50%)"
+        );
+        assert_eq!(
+            Luma::new(0.123f64).format_css_like().to_string(),
+            "luma(12.3%)"
+        );
+
+        let hwb = Hwb::new(Deg(359.96f64), 0.2, 0.0004);
+        assert_eq!(hwb.format_css_like().to_string(), "hwb(360deg 20% 0%)");
+        let hsva = Hsva::new(Hsv::new(Turns(0.5f32), 0.25, 1.0), 0.5);
+        assert_eq!(
+            hsva.format_css_like().precision(0).to_string(),
+            "hsv(180deg 25% 100% / 50%)"
+        );
+    }
+}
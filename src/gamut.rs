@@ -0,0 +1,794 @@
+//! Mapping of out-of-gamut colors into an RGB color space
+//!
+//! Colors converted into an RGB color space from XYZ, Lab or a wider RGB space often end up with
+//! channels outside of the range [0, 1]. A [`GamutMapping`](trait.GamutMapping.html) decides how
+//! such colors are brought back inside the space. The following strategies are provided:
+//!
+//! * [`Preserve`](struct.Preserve.html): Leave colors unchanged, keeping out-of-gamut values.
+//! * [`Clip`](struct.Clip.html): Clamp each channel independently. Fast, but shifts hue and
+//!   lightness.
+//! * [`ClipNegative`](struct.ClipNegative.html): Clamp negative channels to zero, keeping
+//!   highlights above one for HDR output.
+//! * [`ChromaReduction`](struct.ChromaReduction.html): Reduce chroma toward the neutral axis,
+//!   keeping lightness and hue constant in Lab.
+//! * [`CuspClip`](struct.CuspClip.html): Project toward the point on the neutral axis with the
+//!   same lightness as the gamut's cusp for the color's hue, trading lightness for chroma.
+//! * [`CssOklch`](struct.CssOklch.html): The CSS Color Level 4 algorithm, reducing chroma in
+//!   Oklch until clipping the color changes it by less than a just-noticeable difference.
+//!
+//! Extended-range spaces such as [`ScRgb`](../color_space/named/struct.ScRgb.html) keep out of
+//! range channels on purpose. [`has_negative_channels`](fn.has_negative_channels.html) and
+//! [`exceeds_white`](fn.exceeds_white.html) tell whether a linear color lies outside the gamut of
+//! the primaries or is brighter than white, so the right mapping can be chosen.
+//!
+//! A [`SoftProofer`](struct.SoftProofer.html) uses a mapping to preview how colors will appear
+//! when reproduced in a narrower space.
+#![allow(non_snake_case)]
+
+use crate::channel::{ChannelFormatCast, FreeChannelScalar, PosNormalChannelScalar};
+use crate::color::{Bounded, Color};
+use crate::color_space::{ColorSpace, SpaceConverter, SpacedColor};
+use crate::convert::FromColor;
+use crate::encoding::{ColorEncoding, EncodableColor, EncodedColor};
+use crate::lab::Lab;
+use crate::linalg::Matrix3;
+use crate::lms::{chromatic_adaptation_transform, Bradford};
+use crate::oklab::Oklab;
+use crate::rgb::Rgb;
+use crate::white_point::{WhitePoint, D65};
+use crate::xyz::Xyz;
+use num_traits;
+
+/// A strategy for mapping a linear `Rgb` color into the gamut of a color space
+pub trait GamutMapping<T>
+where
+    T: num_traits::Float + FreeChannelScalar + PosNormalChannelScalar,
+{
+    /// Map a linear `Rgb` color in `space` so that all channels lie within [0, 1]
+    ///
+    /// Colors already inside the gamut are returned unchanged.
+    fn map_linear<S>(&self, space: &S, color: &Rgb<T>) -> Rgb<T>
+    where
+        S: ColorSpace<T>;
+
+    /// Convert an `Xyz` color, relative to the white point of `space`, into an in-gamut linear `Rgb`
+    fn map_xyz<S>(&self, space: &S, color: &Xyz<T>) -> Rgb<T>
+    where
+        S: ColorSpace<T>,
+    {
+        let (r, g, b) = space
+            .get_inverse_xyz_transform()
+            .transform_vector(color.clone().to_tuple());
+        self.map_linear(space, &Rgb::new(r, g, b))
+    }
+}
+
+/// Leave colors unchanged, even when they lie outside of the gamut
+///
+/// This is useful where a mapping is required but the out of range values are meaningful, such as
+/// when converting into a scene-referred space or when the colors are mapped later.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct Preserve;
+
+impl<T> GamutMapping<T> for Preserve
+where
+    T: num_traits::Float + FreeChannelScalar + PosNormalChannelScalar,
+{
+    fn map_linear<S>(&self, _space: &S, color: &Rgb<T>) -> Rgb<T>
+    where
+        S: ColorSpace<T>,
+    {
+        color.clone()
+    }
+}
+
+/// Clamp each channel into [0, 1] independently
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct Clip;
+
+impl<T> GamutMapping<T> for Clip
+where
+    T: num_traits::Float + FreeChannelScalar + PosNormalChannelScalar,
+{
+    fn map_linear<S>(&self, _space: &S, color: &Rgb<T>) -> Rgb<T>
+    where
+        S: ColorSpace<T>,
+    {
+        color.clone().normalize()
+    }
+}
+
+/// Clamp negative channels to zero, leaving channels above one unchanged
+///
+/// This removes the parts of a color outside the gamut of the primaries while keeping highlights,
+/// for output that supports values brighter than white, such as HDR displays.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct ClipNegative;
+
+impl<T> GamutMapping<T> for ClipNegative
+where
+    T: num_traits::Float + FreeChannelScalar + PosNormalChannelScalar,
+{
+    fn map_linear<S>(&self, _space: &S, color: &Rgb<T>) -> Rgb<T>
+    where
+        S: ColorSpace<T>,
+    {
+        let zero = T::zero();
+        Rgb::new(
+            color.red().max(zero),
+            color.green().max(zero),
+            color.blue().max(zero),
+        )
+    }
+}
+
+/// Reduce Lab chroma at constant lightness and hue until the color fits in the gamut
+///
+/// Colors with a lightness at or beyond black or white are mapped to black or white.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ChromaReduction {
+    iterations: u32,
+}
+
+impl ChromaReduction {
+    /// Construct a new `ChromaReduction` with the default precision
+    pub fn new() -> Self {
+        ChromaReduction { iterations: 24 }
+    }
+    /// Construct a new `ChromaReduction` doing `iterations` steps of bisection
+    pub fn with_iterations(iterations: u32) -> Self {
+        ChromaReduction { iterations }
+    }
+    /// Returns the number of bisection steps done per color
+    pub fn iterations(&self) -> u32 {
+        self.iterations
+    }
+}
+
+impl Default for ChromaReduction {
+    fn default() -> Self {
+        ChromaReduction::new()
+    }
+}
+
+impl<T> GamutMapping<T> for ChromaReduction
+where
+    T: num_traits::Float + FreeChannelScalar + PosNormalChannelScalar,
+{
+    fn map_linear<S>(&self, space: &S, color: &Rgb<T>) -> Rgb<T>
+    where
+        S: ColorSpace<T>,
+    {
+        if is_in_gamut(color) {
+            return color.clone();
+        }
+        let (L, a, b) = linear_to_lab(space, color);
+        if let Some(neutral) = neutral_extreme(L) {
+            return neutral;
+        }
+
+        let scale = bisect(self.iterations, |t| {
+            is_in_gamut(&lab_to_linear(space, L, a * t, b * t))
+        });
+        lab_to_linear(space, L, a * scale, b * scale).normalize()
+    }
+}
+
+/// Project toward the neutral axis at the lightness of the gamut's cusp
+///
+/// The cusp is the most chromatic color in the gamut for a given hue. Projecting toward the
+/// neutral color of the same lightness keeps hue constant while retaining more chroma than
+/// [`ChromaReduction`](struct.ChromaReduction.html) for very light and very dark colors, at
+/// the cost of changing their lightness.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CuspClip {
+    iterations: u32,
+}
+
+impl CuspClip {
+    /// Construct a new `CuspClip` with the default precision
+    pub fn new() -> Self {
+        CuspClip { iterations: 24 }
+    }
+    /// Construct a new `CuspClip` doing `iterations` steps for each search
+    pub fn with_iterations(iterations: u32) -> Self {
+        CuspClip { iterations }
+    }
+    /// Returns the number of steps done for each search
+    pub fn iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    /// Find the lightness of the most chromatic in-gamut color of `space` with hue `(a, b)`
+    fn cusp_lightness<T, S>(&self, space: &S, a: T, b: T) -> T
+    where
+        T: num_traits::Float + FreeChannelScalar + PosNormalChannelScalar,
+        S: ColorSpace<T>,
+    {
+        let zero = num_traits::cast(0.0).unwrap();
+        let hundred: T = num_traits::cast(100.0).unwrap();
+        let inv_phi: T = num_traits::cast(0.618_033_988_749_895).unwrap();
+        let max_chroma = |L: T| {
+            let mut upper = T::one();
+            while is_in_gamut(&lab_to_linear(space, L, a * upper, b * upper)) {
+                upper = upper + upper;
+            }
+            upper
+                * bisect(self.iterations, |t| {
+                    is_in_gamut(&lab_to_linear(space, L, a * upper * t, b * upper * t))
+                })
+        };
+
+        let (mut lo, mut hi) = (zero, hundred);
+        for _ in 0..self.iterations {
+            let l1 = hi - (hi - lo) * inv_phi;
+            let l2 = lo + (hi - lo) * inv_phi;
+            if max_chroma(l1) < max_chroma(l2) {
+                lo = l1;
+            } else {
+                hi = l2;
+            }
+        }
+        (lo + hi) / num_traits::cast(2.0).unwrap()
+    }
+}
+
+impl Default for CuspClip {
+    fn default() -> Self {
+        CuspClip::new()
+    }
+}
+
+impl<T> GamutMapping<T> for CuspClip
+where
+    T: num_traits::Float + FreeChannelScalar + PosNormalChannelScalar,
+{
+    fn map_linear<S>(&self, space: &S, color: &Rgb<T>) -> Rgb<T>
+    where
+        S: ColorSpace<T>,
+    {
+        if is_in_gamut(color) {
+            return color.clone();
+        }
+        let (L, a, b) = linear_to_lab(space, color);
+        let chroma = a.hypot(b);
+        if chroma <= T::epsilon() {
+            return neutral_extreme(L).unwrap_or_else(|| color.clone().normalize());
+        }
+
+        let L0 = self.cusp_lightness(space, a / chroma, b / chroma);
+        let point = |t: T| lab_to_linear(space, L0 + (L - L0) * t, a * t, b * t);
+        let t = bisect(self.iterations, |t| is_in_gamut(&point(t)));
+        point(t).normalize()
+    }
+}
+
+/// The gamut mapping algorithm of CSS Color Level 4
+///
+/// Chroma is reduced in Oklch at constant lightness and hue by bisection. At each step the color
+/// is clipped into gamut, and the search stops once the clipped color is within a just-noticeable
+/// difference ($`\Delta E_{OK}`$ of 0.02) of the unclipped color. The result is then the clipped
+/// color, which retains more chroma than an exact constant-hue reduction while remaining visually
+/// indistinguishable from it. This gives the same results as browsers implementing the
+/// specification.
+///
+/// Oklch is defined relative to D65. Spaces with a different white point are adapted with the
+/// Bradford model, as CSS does.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct CssOklch;
+
+impl CssOklch {
+    const JND: f64 = 0.02;
+    const EPSILON: f64 = 0.0001;
+}
+
+impl<T> GamutMapping<T> for CssOklch
+where
+    T: num_traits::Float + FreeChannelScalar + PosNormalChannelScalar,
+{
+    fn map_linear<S>(&self, space: &S, color: &Rgb<T>) -> Rgb<T>
+    where
+        S: ColorSpace<T>,
+    {
+        let to_d65 = if space.white_point() == D65.get_xyz() {
+            Matrix3::identity()
+        } else {
+            chromatic_adaptation_transform::<T, Bradford>(&space.white_point(), &D65.get_xyz())
+        };
+        let to_oklab = |rgb: &Rgb<T>| {
+            let (x, y, z) = (to_d65.clone() * space.get_xyz_transform())
+                .transform_vector(rgb.clone().to_tuple());
+            Oklab::from_color(&Xyz::new(x, y, z))
+        };
+        let from_d65 = to_d65.clone().try_inverse().unwrap();
+        let from_oklab = |lab: &Oklab<T>| {
+            let (r, g, b) = (space.get_inverse_xyz_transform() * from_d65.clone())
+                .transform_vector(Xyz::from_color(lab).to_tuple());
+            Rgb::new(r, g, b)
+        };
+
+        let origin = to_oklab(color);
+        if origin.L() >= T::one() {
+            return Rgb::new(T::one(), T::one(), T::one());
+        }
+        if origin.L() <= T::zero() {
+            return Rgb::new(T::zero(), T::zero(), T::zero());
+        }
+        if is_in_gamut(color) {
+            return color.clone();
+        }
+
+        let jnd = num_traits::cast(Self::JND).unwrap();
+        let epsilon = num_traits::cast(Self::EPSILON).unwrap();
+        let chroma = origin.a().hypot(origin.b());
+        let with_chroma = |c: T| {
+            if chroma > T::zero() {
+                let scale = c / chroma;
+                Oklab::new(origin.L(), origin.a() * scale, origin.b() * scale)
+            } else {
+                origin.clone()
+            }
+        };
+
+        let mut clipped = color.clone().normalize();
+        if to_oklab(&clipped).distance(&origin) < jnd {
+            return clipped;
+        }
+
+        let (mut min, mut max) = (T::zero(), chroma);
+        let mut min_in_gamut = true;
+        let two = num_traits::cast(2.0).unwrap();
+        while max - min > epsilon {
+            let c = (min + max) / two;
+            let current = with_chroma(c);
+            let current_rgb = from_oklab(&current);
+            if min_in_gamut && is_in_gamut(&current_rgb) {
+                min = c;
+                continue;
+            }
+            clipped = current_rgb.normalize();
+            let error = to_oklab(&clipped).distance(&current);
+            if error < jnd {
+                if jnd - error < epsilon {
+                    return clipped;
+                }
+                min_in_gamut = false;
+                min = c;
+            } else {
+                max = c;
+            }
+        }
+        clipped
+    }
+}
+
+/// Returns true if all channels of the linear color `color` lie within [0, 1]
+pub fn is_in_gamut<T>(color: &Rgb<T>) -> bool
+where
+    T: num_traits::Float + PosNormalChannelScalar,
+{
+    let (r, g, b) = color.clone().to_tuple();
+    let zero = T::zero();
+    let one = T::one();
+    r >= zero && r <= one && g >= zero && g <= one && b >= zero && b <= one
+}
+
+/// Returns true if any channel of the linear color `color` is negative
+///
+/// Negative channels mean the color lies outside the triangle of the space's primaries.
+pub fn has_negative_channels<T>(color: &Rgb<T>) -> bool
+where
+    T: num_traits::Float + PosNormalChannelScalar,
+{
+    let zero = T::zero();
+    color.red() < zero || color.green() < zero || color.blue() < zero
+}
+
+/// Returns true if any channel of the linear color `color` is greater than one
+///
+/// Such colors are brighter than the white of the space in at least one channel.
+pub fn exceeds_white<T>(color: &Rgb<T>) -> bool
+where
+    T: num_traits::Float + PosNormalChannelScalar,
+{
+    let one = T::one();
+    color.red() > one || color.green() > one || color.blue() > one
+}
+
+/// Simulates the reproduction of colors from one color space in a narrower color space
+///
+/// Each color is converted into the proof space, mapped into its gamut and converted back into
+/// the source space, giving the color as it would appear when reproduced by a device using the
+/// proof space, such as a printer. The original color is reported as out of gamut if it could
+/// not be represented in the proof space without mapping.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SoftProofer<T, S, P, M> {
+    to_proof: SpaceConverter<T, S, P>,
+    from_proof: SpaceConverter<T, P, S>,
+    mapping: M,
+}
+
+/// The result of soft proofing a color with a [`SoftProofer`](struct.SoftProofer.html)
+#[derive(Clone, Debug, PartialEq)]
+pub struct SoftProof<C> {
+    color: C,
+    out_of_gamut: bool,
+}
+
+impl<C> SoftProof<C> {
+    /// Returns the proofed color, in the source color space
+    pub fn color(&self) -> &C {
+        &self.color
+    }
+    /// Returns true if the original color was outside of the gamut of the proof space
+    pub fn is_out_of_gamut(&self) -> bool {
+        self.out_of_gamut
+    }
+    /// Returns the proofed color, discarding the gamut information
+    pub fn into_color(self) -> C {
+        self.color
+    }
+}
+
+impl<T, S, P, M> SoftProofer<T, S, P, M>
+where
+    T: num_traits::Float + FreeChannelScalar + PosNormalChannelScalar,
+    S: ColorSpace<T>,
+    P: ColorSpace<T>,
+    M: GamutMapping<T>,
+{
+    /// Construct a new `SoftProofer` proofing colors in `source` against `proof`
+    pub fn new(source: S, proof: P, mapping: M) -> Self {
+        SoftProofer {
+            to_proof: SpaceConverter::new(source.clone(), proof.clone()),
+            from_proof: SpaceConverter::new(proof, source),
+            mapping,
+        }
+    }
+
+    /// Returns a reference to the source color space
+    pub fn source(&self) -> &S {
+        self.to_proof.source()
+    }
+    /// Returns a reference to the proof color space
+    pub fn proof(&self) -> &P {
+        self.to_proof.dest()
+    }
+    /// Returns a reference to the gamut mapping used in the proof space
+    pub fn mapping(&self) -> &M {
+        &self.mapping
+    }
+
+    /// Proof a linear `Rgb` color in the source space, returning a linear `Rgb` color
+    pub fn proof_linear(&self, color: &Rgb<T>) -> SoftProof<Rgb<T>> {
+        let proofed = self.to_proof.convert_linear(color);
+        let out_of_gamut = !is_in_gamut(&proofed);
+        let mapped = self.mapping.map_linear(self.proof(), &proofed);
+        SoftProof {
+            color: self.from_proof.convert_linear(&mapped),
+            out_of_gamut,
+        }
+    }
+
+    /// Proof an encoded color in the source space, returning it in the source space's encoding
+    pub fn proof_color<E>(
+        &self,
+        color: &EncodedColor<Rgb<T>, E>,
+    ) -> SoftProof<SpacedColor<T, Rgb<T>, S::Encoding, S>>
+    where
+        T: ChannelFormatCast<f64>,
+        f64: ChannelFormatCast<T>,
+        E: ColorEncoding,
+    {
+        let proof = self.proof_linear(&color.clone().decode());
+        SoftProof {
+            color: SpacedColor::new(
+                proof.color.linear().encode(self.source().encoding()),
+                self.source().clone(),
+            ),
+            out_of_gamut: proof.out_of_gamut,
+        }
+    }
+}
+
+/// Soft proof `color` from the color space `source` against the color space `proof`
+///
+/// This is a shorthand for building a [`SoftProofer`](struct.SoftProofer.html) and using it
+/// once.
+pub fn soft_proof<T, S, P, E, M>(
+    source: &S,
+    proof: &P,
+    color: &EncodedColor<Rgb<T>, E>,
+    mapping: M,
+) -> SoftProof<SpacedColor<T, Rgb<T>, S::Encoding, S>>
+where
+    T: num_traits::Float + FreeChannelScalar + PosNormalChannelScalar + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+    S: ColorSpace<T>,
+    P: ColorSpace<T>,
+    E: ColorEncoding,
+    M: GamutMapping<T>,
+{
+    SoftProofer::new(source.clone(), proof.clone(), mapping).proof_color(color)
+}
+
+/// Convert an `Xyz` color relative to the white point of `space` into `space`, mapping it into gamut
+pub fn convert_xyz_mapped<T, S, M>(
+    space: &S,
+    color: &Xyz<T>,
+    mapping: &M,
+) -> SpacedColor<T, Rgb<T>, S::Encoding, S>
+where
+    T: num_traits::Float + FreeChannelScalar + PosNormalChannelScalar + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+    S: ColorSpace<T>,
+    M: GamutMapping<T>,
+{
+    let linear = mapping.map_xyz(space, color);
+    SpacedColor::new(linear.linear().encode(space.encoding()), space.clone())
+}
+
+/// Convert a `Lab` color into `space`, mapping it into gamut
+///
+/// If the white point of `color` differs from that of `space`, the color is adapted with the
+/// Bradford model before mapping.
+pub fn convert_lab_mapped<T, W, S, M>(
+    space: &S,
+    color: &Lab<T, W>,
+    mapping: &M,
+) -> SpacedColor<T, Rgb<T>, S::Encoding, S>
+where
+    T: num_traits::Float + FreeChannelScalar + PosNormalChannelScalar + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+    W: WhitePoint<T>,
+    S: ColorSpace<T>,
+    M: GamutMapping<T>,
+{
+    let source_white = color.white_point().get_xyz();
+    let dest_white = space.white_point();
+    let xyz = color.to_xyz();
+    let xyz = if source_white == dest_white {
+        xyz
+    } else {
+        let (x, y, z) = chromatic_adaptation_transform::<T, Bradford>(&source_white, &dest_white)
+            .transform_vector(xyz.to_tuple());
+        Xyz::new(x, y, z)
+    };
+    convert_xyz_mapped(space, &xyz, mapping)
+}
+
+/// Returns the largest `t` in [0, 1] for which `inside(t)`, assuming `inside(0)` holds
+fn bisect<T, F>(iterations: u32, inside: F) -> T
+where
+    T: num_traits::Float,
+    F: Fn(T) -> bool,
+{
+    if inside(T::one()) {
+        return T::one();
+    }
+    let two = T::one() + T::one();
+    let (mut lo, mut hi) = (T::zero(), T::one());
+    for _ in 0..iterations {
+        let mid = (lo + hi) / two;
+        if inside(mid) {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+fn neutral_extreme<T>(L: T) -> Option<Rgb<T>>
+where
+    T: num_traits::Float + PosNormalChannelScalar,
+{
+    if L >= num_traits::cast(100.0).unwrap() {
+        Some(Rgb::new(T::one(), T::one(), T::one()))
+    } else if L <= T::zero() {
+        Some(Rgb::new(T::zero(), T::zero(), T::zero()))
+    } else {
+        None
+    }
+}
+
+fn linear_to_lab<T, S>(space: &S, color: &Rgb<T>) -> (T, T, T)
+where
+    T: num_traits::Float + FreeChannelScalar + PosNormalChannelScalar,
+    S: ColorSpace<T>,
+{
+    let (x, y, z) = space.apply_transform(color.clone().to_tuple());
+    let lab = Lab::from_xyz(&Xyz::new(x, y, z), space.white_point());
+    (lab.L(), lab.a(), lab.b())
+}
+
+fn lab_to_linear<T, S>(space: &S, L: T, a: T, b: T) -> Rgb<T>
+where
+    T: num_traits::Float + FreeChannelScalar + PosNormalChannelScalar,
+    S: ColorSpace<T>,
+{
+    let xyz = Lab::new_with_whitepoint(L, a, b, space.white_point()).to_xyz();
+    let (r, g, b) = space
+        .get_inverse_xyz_transform()
+        .transform_vector(xyz.to_tuple());
+    Rgb::new(r, g, b)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::color_space::named::{ProPhotoRgb, Rec2020, SRgb, ScRgb};
+    use crate::white_point::D50;
+    use approx::*;
+
+    fn wide_green() -> Rgb<f64> {
+        let rec2020 = Rec2020::<f64>::new();
+        let (x, y, z) = rec2020.apply_transform((0.0, 1.0, 0.0));
+        let (r, g, b) = SRgb::<f64>::new()
+            .get_inverse_xyz_transform()
+            .transform_vector((x, y, z));
+        Rgb::new(r, g, b)
+    }
+
+    fn hue((_, a, b): (f64, f64, f64)) -> f64 {
+        b.atan2(a)
+    }
+
+    #[test]
+    fn test_in_gamut_unchanged() {
+        let space = SRgb::<f64>::new();
+        let color = Rgb::new(0.2, 0.5, 0.9);
+        assert_eq!(Clip.map_linear(&space, &color), color);
+        assert_eq!(ChromaReduction::new().map_linear(&space, &color), color);
+        assert_eq!(CuspClip::new().map_linear(&space, &color), color);
+    }
+
+    #[test]
+    fn test_clip() {
+        let space = SRgb::<f64>::new();
+        let mapped = Clip.map_linear(&space, &Rgb::new(-0.2, 0.5, 1.3));
+        assert_relative_eq!(mapped, Rgb::new(0.0, 0.5, 1.0));
+    }
+
+    #[test]
+    fn test_extended_range() {
+        let space = ScRgb::<f64>::new();
+        let color = Rgb::new(-0.2, 0.5, 1.3);
+        assert!(!is_in_gamut(&color));
+        assert!(has_negative_channels(&color));
+        assert!(exceeds_white(&color));
+        assert_eq!(Preserve.map_linear(&space, &color), color);
+
+        let mapped = ClipNegative.map_linear(&space, &color);
+        assert_eq!(mapped, Rgb::new(0.0, 0.5, 1.3));
+        assert!(!has_negative_channels(&mapped));
+        assert!(exceeds_white(&mapped));
+        assert!(is_in_gamut(&Clip.map_linear(&space, &mapped)));
+
+        let wide = wide_green();
+        assert!(has_negative_channels(&wide));
+        assert!(!exceeds_white(&Rgb::new(0.0, 1.0, 0.5)));
+    }
+
+    #[test]
+    fn test_chroma_reduction() {
+        let space = SRgb::<f64>::new();
+        let green = wide_green();
+        assert!(!is_in_gamut(&green));
+
+        let mapped = ChromaReduction::new().map_linear(&space, &green);
+        assert!(is_in_gamut(&mapped));
+        let before = linear_to_lab(&space, &green);
+        let after = linear_to_lab(&space, &mapped);
+        assert_relative_eq!(before.0, after.0, epsilon = 1e-3);
+        assert_relative_eq!(hue(before), hue(after), epsilon = 1e-3);
+        assert!(after.1.hypot(after.2) < before.1.hypot(before.2));
+
+        let white = ChromaReduction::new().map_linear(&space, &Rgb::new(1.2, 1.1, 1.3));
+        assert_relative_eq!(white, Rgb::new(1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn test_cusp_clip() {
+        let space = SRgb::<f64>::new();
+        let green = wide_green();
+
+        let mapped = CuspClip::new().map_linear(&space, &green);
+        assert!(is_in_gamut(&mapped));
+        let before = linear_to_lab(&space, &green);
+        let after = linear_to_lab(&space, &mapped);
+        assert_relative_eq!(hue(before), hue(after), epsilon = 1e-3);
+
+        let reduced = linear_to_lab(&space, &ChromaReduction::new().map_linear(&space, &green));
+        assert!(after.1.hypot(after.2) > reduced.1.hypot(reduced.2));
+    }
+
+    #[test]
+    fn test_css_oklch() {
+        let space = SRgb::<f64>::new();
+        let color = Rgb::new(0.2, 0.5, 0.9);
+        assert_eq!(CssOklch.map_linear(&space, &color), color);
+
+        let green = wide_green();
+        let mapped = CssOklch.map_linear(&space, &green);
+        assert!(is_in_gamut(&mapped));
+
+        let to_oklab = |rgb: &Rgb<f64>| {
+            let (x, y, z) = space.apply_transform(rgb.clone().to_tuple());
+            Oklab::from_color(&Xyz::new(x, y, z))
+        };
+        let before = to_oklab(&green);
+        let after = to_oklab(&mapped);
+        assert_relative_eq!(before.L(), after.L(), epsilon = 0.02);
+        assert_relative_eq!(
+            before.b().atan2(before.a()),
+            after.b().atan2(after.a()),
+            epsilon = 0.05
+        );
+        let reduced = Oklab::new(
+            before.L(),
+            after.a().hypot(after.b()) * before.a() / before.a().hypot(before.b()),
+            after.a().hypot(after.b()) * before.b() / before.a().hypot(before.b()),
+        );
+        assert!(after.distance(&reduced) < 0.02);
+
+        let white = CssOklch.map_linear(&space, &Rgb::new(1.1, 1.05, 1.2));
+        assert_relative_eq!(white, Rgb::new(1.0, 1.0, 1.0));
+        let slightly_out = CssOklch.map_linear(&space, &Rgb::new(1.001, 0.5, 0.2));
+        assert_relative_eq!(slightly_out, Rgb::new(1.0, 0.5, 0.2));
+    }
+
+    #[test]
+    fn test_css_oklch_adapted() {
+        let space = ProPhotoRgb::<f64>::new();
+        let color = Rgb::new(1.1, -0.05, 0.3);
+        let mapped = CssOklch.map_linear(&space, &color);
+        assert!(is_in_gamut(&mapped));
+        assert_relative_eq!(
+            CssOklch.map_linear(&space, &Rgb::new(0.5, 0.5, 0.5)),
+            Rgb::new(0.5, 0.5, 0.5)
+        );
+    }
+
+    #[test]
+    fn test_soft_proof() {
+        let proofer = SoftProofer::new(Rec2020::<f64>::new(), SRgb::new(), Clip);
+
+        let inside = proofer.proof_linear(&Rgb::new(0.5, 0.5, 0.5));
+        assert!(!inside.is_out_of_gamut());
+        assert_relative_eq!(*inside.color(), Rgb::new(0.5, 0.5, 0.5), epsilon = 1e-6);
+
+        let green = proofer.proof_linear(&Rgb::new(0.0, 1.0, 0.0));
+        assert!(green.is_out_of_gamut());
+        let (r, g, b) = Rec2020::<f64>::new()
+            .get_inverse_xyz_transform()
+            .transform_vector(SRgb::new().apply_transform((0.0, 1.0, 0.0)));
+        assert_relative_eq!(*green.color(), Rgb::new(r, g, b), epsilon = 1e-6);
+
+        let encoded = Rgb::new(0.0, 1.0, 0.0).encoded_as(proofer.source().encoding());
+        let proofed = soft_proof(
+            &Rec2020::new(),
+            &SRgb::new(),
+            &encoded,
+            ChromaReduction::new(),
+        );
+        assert!(proofed.is_out_of_gamut());
+        assert!(proofed.color().color().color().green() < 1.0);
+    }
+
+    #[test]
+    fn test_convert_lab_mapped() {
+        let space = SRgb::<f64>::new();
+        let lab = Lab::<f64, D50>::new(50.0, 0.0, 0.0);
+        let gray = convert_lab_mapped(&space, &lab, &ChromaReduction::new());
+        assert_relative_eq!(
+            gray.color().color().red(),
+            gray.color().color().blue(),
+            epsilon = 1e-3
+        );
+
+        let vivid = Lab::<f64, D50>::new(60.0, -120.0, 90.0);
+        let mapped = convert_lab_mapped(&space, &vivid, &CuspClip::new());
+        assert!(mapped.color().color().is_normalized());
+    }
+}
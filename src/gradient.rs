@@ -0,0 +1,813 @@
+//! Multi-stop color gradients and gradient mapping
+//!
+//! A [`Gradient`](struct.Gradient.html) is an ordered set of color stops that can be sampled at any
+//! position. Gradients work with any color implementing [`Lerp`](../trait.Lerp.html), so the color
+//! model chosen for the stops also decides how colors between the stops are blended. Each segment
+//! between two stops can also be given an [`Easing`](enum.Easing.html) to make the transition
+//! non-linear.
+//!
+//! [`gradient_map`](fn.gradient_map.html) uses a gradient as a lookup table indexed by the luma of
+//! each input color. Duotone and tritone effects are gradient maps with two or three stops, and can
+//! be built with [`Gradient::duotone`](struct.Gradient.html#method.duotone) and
+//! [`Gradient::tritone`](struct.Gradient.html#method.tritone).
+//!
+//! For data visualization, [`Gradient::perceptual`](struct.Gradient.html#method.perceptual) and
+//! [`Gradient::lightness_ramp`](struct.Gradient.html#method.lightness_ramp) build gradients that
+//! interpolate in `Oklab`, where equal steps look like equal changes.
+//! [`lightness_deviation`](struct.Gradient.html#method.lightness_deviation) measures how far an
+//! existing gradient is from a uniform lightness ramp.
+//!
+//! With the `heapless` feature enabled, [`FixedGradient`](struct.FixedGradient.html) provides the
+//! same sampling with a fixed number of stops and no heap allocation.
+//!
+//! ## Examples:
+//!
+//! ```rust
+//! #[macro_use] extern crate approx;
+//! # extern crate prisma;
+//!
+//! use prisma::Rgb;
+//! use prisma::gradient::{gradient_map, Gradient};
+//!
+//! let duotone = Gradient::duotone(Rgb::new(0.1, 0.0, 0.3), Rgb::new(1.0, 0.9, 0.5));
+//! let image = [Rgb::new(0.0, 0.0, 0.0), Rgb::new(1.0, 1.0, 1.0)];
+//! let mapped = gradient_map(&image, &duotone);
+//! assert_relative_eq!(mapped[0], Rgb::new(0.1, 0.0, 0.3), epsilon=1e-6);
+//! assert_relative_eq!(mapped[1], Rgb::new(1.0, 0.9, 0.5), epsilon=1e-6);
+//! ```
+
+use crate::channel::{FreeChannelScalar, PosNormalChannelScalar};
+use crate::color::Lerp;
+use crate::convert::FromColor;
+use crate::grayscale::{LumaWeights, ToLuma};
+use crate::oklab::Oklab;
+use crate::rgb::Rgb;
+use num_traits;
+use num_traits::cast;
+
+/// A timing function reshaping the interpolation position within a gradient segment
+///
+/// An easing maps the linear position `t` in `[0, 1]` between two stops to the position passed to
+/// [`Lerp`](../trait.Lerp.html). Every easing maps 0 to 0 and 1 to 1, so the stop colors are
+/// unaffected.
+///
+/// ## Examples:
+///
+/// ```rust
+/// #[macro_use] extern crate approx;
+/// # extern crate prisma;
+///
+/// use prisma::gradient::Easing;
+///
+/// assert_relative_eq!(Easing::SmoothStep.apply(0.25), 0.15625);
+/// assert_relative_eq!(Easing::ease_in_out().apply(0.5), 0.5, epsilon=1e-6);
+/// assert_relative_eq!(Easing::Custom(|t: f64| t * t).apply(0.5), 0.25);
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub enum Easing<T> {
+    /// Interpolate linearly
+    Linear,
+    /// The smoothstep polynomial $`3t^2 - 2t^3`$, which eases in and out
+    SmoothStep,
+    /// A cubic bézier curve from (0, 0) to (1, 1) with control points `(x1, y1)` and `(x2, y2)`
+    ///
+    /// This matches the CSS `cubic-bezier(x1, y1, x2, y2)` timing function. `x1` and `x2` must be
+    /// in `[0, 1]`.
+    CubicBezier(T, T, T, T),
+    /// A user supplied function
+    Custom(fn(T) -> T),
+}
+
+impl<T> PartialEq for Easing<T>
+where
+    T: PartialEq,
+{
+    /// Compares the variants and their parameters. `Custom` easings compare by function address,
+    /// which may be shared or duplicated by the compiler.
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Easing::Linear, Easing::Linear) | (Easing::SmoothStep, Easing::SmoothStep) => true,
+            (Easing::CubicBezier(a1, b1, c1, d1), Easing::CubicBezier(a2, b2, c2, d2)) => {
+                a1 == a2 && b1 == b2 && c1 == c2 && d1 == d2
+            }
+            (Easing::Custom(f1), Easing::Custom(f2)) => *f1 as usize == *f2 as usize,
+            _ => false,
+        }
+    }
+}
+
+impl<T> Easing<T>
+where
+    T: num_traits::Float,
+{
+    /// The CSS `ease` timing function
+    pub fn ease() -> Self {
+        Self::bezier(0.25, 0.1, 0.25, 1.0)
+    }
+    /// The CSS `ease-in` timing function
+    pub fn ease_in() -> Self {
+        Self::bezier(0.42, 0.0, 1.0, 1.0)
+    }
+    /// The CSS `ease-out` timing function
+    pub fn ease_out() -> Self {
+        Self::bezier(0.0, 0.0, 0.58, 1.0)
+    }
+    /// The CSS `ease-in-out` timing function
+    pub fn ease_in_out() -> Self {
+        Self::bezier(0.42, 0.0, 0.58, 1.0)
+    }
+
+    /// Apply the easing to `t`, which is clamped to `[0, 1]`
+    pub fn apply(&self, t: T) -> T {
+        let t = t.max(T::zero()).min(T::one());
+        match *self {
+            Easing::Linear => t,
+            Easing::SmoothStep => {
+                t * t * (cast::<_, T>(3.0).unwrap() - cast::<_, T>(2.0).unwrap() * t)
+            }
+            Easing::CubicBezier(x1, y1, x2, y2) => {
+                let s = solve_bezier(x1, x2, t);
+                bezier(y1, y2, s)
+            }
+            Easing::Custom(f) => f(t),
+        }
+    }
+
+    fn bezier(x1: f64, y1: f64, x2: f64, y2: f64) -> Self {
+        Easing::CubicBezier(
+            cast(x1).unwrap(),
+            cast(y1).unwrap(),
+            cast(x2).unwrap(),
+            cast(y2).unwrap(),
+        )
+    }
+}
+
+/// A piecewise gradient between an ordered list of color stops
+///
+/// Each stop is a position paired with a color. Sampling between two stops linearly interpolates
+/// the neighboring colors with [`Lerp`](../trait.Lerp.html), and sampling outside the range of
+/// the stops returns the nearest end color. The position within each segment can be reshaped by
+/// an [`Easing`](enum.Easing.html), which is linear by default.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Gradient<C>
+where
+    C: Lerp,
+{
+    stops: Vec<(C::Position, C)>,
+    easings: Vec<Easing<C::Position>>,
+}
+
+impl<C> Gradient<C>
+where
+    C: Lerp + Clone,
+{
+    /// Construct a new `Gradient` from a list of `(position, color)` stops
+    ///
+    /// The stops do not need to be given in order, they will be sorted by position.
+    ///
+    /// # Panics
+    /// If `stops` is empty or contains a position that is `NaN`.
+    pub fn new(mut stops: Vec<(C::Position, C)>) -> Self {
+        assert!(!stops.is_empty(), "A gradient must have at least one stop");
+        stops.sort_by(|a, b| {
+            a.0.partial_cmp(&b.0)
+                .expect("Gradient stop positions must not be NaN")
+        });
+        Gradient::with_linear_easing(stops)
+    }
+
+    /// Construct a new `Gradient` with `colors` spaced evenly over `[0, 1]`
+    ///
+    /// # Panics
+    /// If `colors` is empty.
+    pub fn from_colors(colors: Vec<C>) -> Self {
+        assert!(!colors.is_empty(), "A gradient must have at least one stop");
+        let last: C::Position = cast(colors.len().saturating_sub(1).max(1)).unwrap();
+        let stops = colors
+            .into_iter()
+            .enumerate()
+            .map(|(i, c)| (cast::<_, C::Position>(i).unwrap() / last, c))
+            .collect();
+        Gradient::with_linear_easing(stops)
+    }
+
+    /// Construct a two stop gradient running from `shadow` at 0 to `highlight` at 1
+    pub fn duotone(shadow: C, highlight: C) -> Self {
+        Gradient::from_colors(vec![shadow, highlight])
+    }
+
+    /// Construct a three stop gradient with `midtone` placed at 0.5
+    pub fn tritone(shadow: C, midtone: C, highlight: C) -> Self {
+        Gradient::from_colors(vec![shadow, midtone, highlight])
+    }
+
+    /// Returns `self` with `easing` applied to every segment
+    pub fn with_easing(mut self, easing: Easing<C::Position>) -> Self {
+        for e in self.easings.iter_mut() {
+            *e = easing;
+        }
+        self
+    }
+
+    /// Set the easing of the segment between stop `segment` and stop `segment + 1`
+    ///
+    /// # Panics
+    /// If `segment + 1` is not a valid stop index.
+    pub fn set_segment_easing(&mut self, segment: usize, easing: Easing<C::Position>) {
+        self.easings[segment] = easing;
+    }
+
+    /// Returns the gradient stops, ordered by position
+    pub fn stops(&self) -> &[(C::Position, C)] {
+        &self.stops
+    }
+
+    /// Returns the easing of each segment, with one fewer element than there are stops
+    pub fn easings(&self) -> &[Easing<C::Position>] {
+        &self.easings
+    }
+
+    /// Returns the color of the gradient at `pos`
+    pub fn sample(&self, pos: C::Position) -> C {
+        sample_stops(&self.stops, pos, |segment, t| {
+            self.easings[segment].apply(t)
+        })
+    }
+
+    /// Measure how far the lightness of the gradient is from a linear ramp
+    ///
+    /// The gradient is sampled at `samples` evenly spaced positions between the first and last
+    /// stop, and each sample's `Oklab` lightness is compared to the straight line between the
+    /// lightness of the end colors. Returns the largest difference, in units of `Oklab` `L`. A
+    /// value of 0 means lightness changes at a constant rate over the whole gradient.
+    ///
+    /// # Panics
+    /// If `samples` is less than 2.
+    pub fn lightness_deviation<T>(&self, samples: usize) -> T
+    where
+        T: FreeChannelScalar,
+        Oklab<T>: FromColor<C>,
+    {
+        let lightness = self.sample_lightness::<T>(samples);
+        let first = lightness[0];
+        let last = lightness[lightness.len() - 1];
+        let steps: T = cast(samples - 1).unwrap();
+        lightness
+            .iter()
+            .enumerate()
+            .map(|(i, &l)| {
+                let expected = first + (last - first) * cast(i).unwrap() / steps;
+                (l - expected).abs()
+            })
+            .fold(T::zero(), T::max)
+    }
+
+    /// Returns whether the `Oklab` lightness of the gradient only increases or only decreases
+    ///
+    /// The gradient is sampled at `samples` evenly spaced positions between the first and last
+    /// stop. Flat sections are allowed.
+    ///
+    /// # Panics
+    /// If `samples` is less than 2.
+    pub fn is_lightness_monotonic<T>(&self, samples: usize) -> bool
+    where
+        T: FreeChannelScalar,
+        Oklab<T>: FromColor<C>,
+    {
+        let lightness = self.sample_lightness::<T>(samples);
+        let tolerance: T = cast(1e-6).unwrap();
+        let increasing = lightness.windows(2).all(|w| w[1] - w[0] >= -tolerance);
+        let decreasing = lightness.windows(2).all(|w| w[0] - w[1] >= -tolerance);
+        increasing || decreasing
+    }
+
+    fn sample_lightness<T>(&self, samples: usize) -> Vec<T>
+    where
+        T: FreeChannelScalar,
+        Oklab<T>: FromColor<C>,
+    {
+        assert!(samples >= 2, "At least 2 samples are required");
+        let start = self.stops[0].0;
+        let end = self.stops[self.stops.len() - 1].0;
+        let steps: C::Position = cast(samples - 1).unwrap();
+        (0..samples)
+            .map(|i| {
+                let pos = start + (end - start) * cast(i).unwrap() / steps;
+                Oklab::from_color(&self.sample(pos)).L()
+            })
+            .collect()
+    }
+
+    fn with_linear_easing(stops: Vec<(C::Position, C)>) -> Self {
+        let easings = vec![Easing::Linear; stops.len() - 1];
+        Gradient { stops, easings }
+    }
+}
+
+impl<T> Gradient<Oklab<T>>
+where
+    T: FreeChannelScalar + Lerp<Position = T>,
+{
+    /// Construct a gradient through `colors` that changes at a perceptually constant rate
+    ///
+    /// The colors are converted to `Oklab` and interpolated there. Instead of being spaced evenly,
+    /// each stop is placed at its distance along the gradient, measured by the `Oklab` color
+    /// difference, so that every part of `[0, 1]` covers the same amount of visible change.
+    ///
+    /// # Panics
+    /// If `colors` is empty.
+    pub fn perceptual<C>(colors: &[C]) -> Self
+    where
+        Oklab<T>: FromColor<C>,
+    {
+        let colors: Vec<_> = colors.iter().map(Oklab::from_color).collect();
+        assert!(!colors.is_empty(), "A gradient must have at least one stop");
+        let mut distances = Vec::with_capacity(colors.len());
+        let mut total = T::zero();
+        distances.push(total);
+        for pair in colors.windows(2) {
+            total = total + pair[0].distance(&pair[1]);
+            distances.push(total);
+        }
+        if total == T::zero() {
+            return Gradient::from_colors(colors);
+        }
+
+        let stops = distances
+            .into_iter()
+            .map(|d| d / total)
+            .zip(colors)
+            .collect();
+        Gradient::with_linear_easing(stops)
+    }
+
+    /// Construct a gradient through `colors` with lightness increasing or decreasing linearly
+    ///
+    /// The colors are converted to `Oklab` and spaced evenly over `[0, 1]`. The lightness of the
+    /// first and last color is kept, while the lightness of every color in between is replaced so
+    /// that lightness steps evenly from one end to the other. The hue and chroma of each color are
+    /// kept, so the result suits sequential color maps where lightness encodes the data. Colors
+    /// whose lightness was moved far may fall outside the gamut of the output device.
+    ///
+    /// # Panics
+    /// If `colors` is empty.
+    pub fn lightness_ramp<C>(colors: &[C]) -> Self
+    where
+        Oklab<T>: FromColor<C>,
+    {
+        let mut colors: Vec<_> = colors.iter().map(Oklab::from_color).collect();
+        assert!(!colors.is_empty(), "A gradient must have at least one stop");
+        let first = colors[0].L();
+        let last = colors[colors.len() - 1].L();
+        let steps: T = cast(colors.len().saturating_sub(1).max(1)).unwrap();
+        for (i, color) in colors.iter_mut().enumerate() {
+            *color.L_mut() = first + (last - first) * cast(i).unwrap() / steps;
+        }
+        Gradient::from_colors(colors)
+    }
+}
+
+impl<T> Gradient<Rgb<T>>
+where
+    T: PosNormalChannelScalar + num_traits::Float,
+    Rgb<T>: Lerp<Position = T>,
+{
+    /// Returns the color of the gradient at the luma of `color`
+    ///
+    /// Luma is computed from the channel values with the Rec. 709 weights.
+    pub fn map_luma(&self, color: &Rgb<T>) -> Rgb<T> {
+        self.sample(color.to_luma(LumaWeights::Rec709))
+    }
+}
+
+/// Map each color in `colors` through `gradient` by luma
+///
+/// Black maps to the start of the gradient and white to its end. Luma is computed from the channel
+/// values as given, so encoded colors produce perceptually even results.
+pub fn gradient_map<T, C>(colors: &[Rgb<T>], gradient: &Gradient<C>) -> Vec<C>
+where
+    T: PosNormalChannelScalar + num_traits::Float,
+    C: Lerp<Position = T> + Clone,
+{
+    colors
+        .iter()
+        .map(|color| gradient.sample(color.to_luma(LumaWeights::Rec709)))
+        .collect()
+}
+
+/// Map each color in `colors` through `gradient` by luma, replacing the original values
+pub fn gradient_map_in_place<T>(colors: &mut [Rgb<T>], gradient: &Gradient<Rgb<T>>)
+where
+    T: PosNormalChannelScalar + num_traits::Float,
+    Rgb<T>: Lerp<Position = T>,
+{
+    for color in colors.iter_mut() {
+        *color = gradient.sample(color.to_luma(LumaWeights::Rec709));
+    }
+}
+
+/// A gradient with a fixed maximum number of stops that does not allocate
+///
+/// `FixedGradient` stores up to `N` stops inline using `heapless`, so building and sampling it
+/// never touches the heap, such as when driving LED strips from a tight loop. It samples
+/// identically to [`Gradient`](struct.Gradient.html).
+///
+/// This does not make prisma usable on `no_std` targets. The crate still needs the standard
+/// library, since its hue types come from `angular-units` and its float math from
+/// `num_traits::Float`, both of which depend on `std`.
+///
+/// Requires the `heapless` feature.
+#[cfg(feature = "heapless")]
+#[derive(Clone, Debug)]
+pub struct FixedGradient<C, const N: usize>
+where
+    C: Lerp,
+{
+    stops: heapless::Vec<(C::Position, C), N>,
+}
+
+#[cfg(feature = "heapless")]
+impl<C, const N: usize> FixedGradient<C, N>
+where
+    C: Lerp + Clone,
+{
+    /// Construct a new `FixedGradient` with a single stop
+    ///
+    /// # Panics
+    /// If `N` is zero or `pos` is `NaN`.
+    pub fn new(pos: C::Position, color: C) -> Self {
+        assert!(
+            !num_traits::Float::is_nan(pos),
+            "Gradient stop positions must not be NaN"
+        );
+        let mut stops = heapless::Vec::new();
+        if stops.push((pos, color)).is_err() {
+            panic!("A FixedGradient must have room for at least one stop");
+        }
+        FixedGradient { stops }
+    }
+
+    /// Construct a new `FixedGradient` from a list of `(position, color)` stops
+    ///
+    /// Returns `None` if `stops` is empty or holds more than `N` stops.
+    ///
+    /// # Panics
+    /// If `stops` contains a position that is `NaN`.
+    pub fn from_stops(stops: &[(C::Position, C)]) -> Option<Self> {
+        let (first, rest) = stops.split_first()?;
+        if stops.len() > N {
+            return None;
+        }
+        let mut gradient = FixedGradient::new(first.0, first.1.clone());
+        for stop in rest {
+            gradient.push_stop(stop.0, stop.1.clone()).ok()?;
+        }
+        Some(gradient)
+    }
+
+    /// Construct a two stop gradient running from `shadow` at 0 to `highlight` at 1
+    ///
+    /// # Panics
+    /// If `N` is less than two.
+    pub fn duotone(shadow: C, highlight: C) -> Self {
+        let zero = num_traits::zero();
+        let one = num_traits::one();
+        Self::from_stops(&[(zero, shadow), (one, highlight)])
+            .expect("A duotone FixedGradient needs room for two stops")
+    }
+
+    /// Construct a three stop gradient with `midtone` placed at 0.5
+    ///
+    /// # Panics
+    /// If `N` is less than three.
+    pub fn tritone(shadow: C, midtone: C, highlight: C) -> Self {
+        let zero = num_traits::zero();
+        let half = cast(0.5).unwrap();
+        let one = num_traits::one();
+        Self::from_stops(&[(zero, shadow), (half, midtone), (one, highlight)])
+            .expect("A tritone FixedGradient needs room for three stops")
+    }
+
+    /// Insert a new stop, keeping the stops ordered by position
+    ///
+    /// If the gradient is full, the stop is handed back in the `Err` variant.
+    ///
+    /// # Panics
+    /// If `pos` is `NaN`.
+    pub fn push_stop(&mut self, pos: C::Position, color: C) -> Result<(), (C::Position, C)> {
+        assert!(
+            !num_traits::Float::is_nan(pos),
+            "Gradient stop positions must not be NaN"
+        );
+        let index = self
+            .stops
+            .iter()
+            .position(|s| s.0 > pos)
+            .unwrap_or_else(|| self.stops.len());
+        self.stops.insert(index, (pos, color))
+    }
+
+    /// Returns the gradient stops, ordered by position
+    pub fn stops(&self) -> &[(C::Position, C)] {
+        &self.stops
+    }
+
+    /// Returns the color of the gradient at `pos`
+    pub fn sample(&self, pos: C::Position) -> C {
+        sample_stops(&self.stops, pos, |_, t| t)
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<T, const N: usize> FixedGradient<Rgb<T>, N>
+where
+    T: PosNormalChannelScalar + num_traits::Float,
+    Rgb<T>: Lerp<Position = T>,
+{
+    /// Returns the color of the gradient at the luma of `color`
+    ///
+    /// Luma is computed from the channel values with the Rec. 709 weights.
+    pub fn map_luma(&self, color: &Rgb<T>) -> Rgb<T> {
+        self.sample(color.to_luma(LumaWeights::Rec709))
+    }
+}
+
+fn sample_stops<C, F>(stops: &[(C::Position, C)], pos: C::Position, ease: F) -> C
+where
+    C: Lerp + Clone,
+    F: Fn(usize, C::Position) -> C::Position,
+{
+    let first = &stops[0];
+    if pos <= first.0 {
+        return first.1.clone();
+    }
+    let last = &stops[stops.len() - 1];
+    if pos >= last.0 {
+        return last.1.clone();
+    }
+
+    let upper = stops
+        .iter()
+        .position(|s| s.0 > pos)
+        .unwrap_or(stops.len() - 1);
+    let (ref left_pos, ref left) = stops[upper - 1];
+    let (ref right_pos, ref right) = stops[upper];
+    let t = (pos - *left_pos) / (*right_pos - *left_pos);
+    left.lerp(right, ease(upper - 1, t))
+}
+
+fn bezier<T: num_traits::Float>(p1: T, p2: T, s: T) -> T {
+    let three: T = cast(3.0).unwrap();
+    let inv = T::one() - s;
+    three * inv * inv * s * p1 + three * inv * s * s * p2 + s * s * s
+}
+
+fn bezier_derivative<T: num_traits::Float>(p1: T, p2: T, s: T) -> T {
+    let three: T = cast(3.0).unwrap();
+    let six: T = cast(6.0).unwrap();
+    let inv = T::one() - s;
+    three * inv * inv * p1 + six * inv * s * (p2 - p1) + three * s * s * (T::one() - p2)
+}
+
+/// Find the bézier parameter whose x coordinate is `x`
+fn solve_bezier<T: num_traits::Float>(x1: T, x2: T, x: T) -> T {
+    let epsilon: T = cast(1e-7).unwrap();
+    let mut s = x;
+    for _ in 0..8 {
+        let error = bezier(x1, x2, s) - x;
+        if error.abs() < epsilon {
+            return s;
+        }
+        let slope = bezier_derivative(x1, x2, s);
+        if slope.abs() < epsilon {
+            break;
+        }
+        s = s - error / slope;
+    }
+
+    // Newton's method failed to converge, fall back to bisection
+    let (mut low, mut high) = (T::zero(), T::one());
+    s = x;
+    for _ in 0..64 {
+        let value = bezier(x1, x2, s);
+        if (value - x).abs() < epsilon {
+            break;
+        }
+        if value < x {
+            low = s;
+        } else {
+            high = s;
+        }
+        s = (low + high) / cast(2.0).unwrap();
+    }
+    s
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::color::Broadcast;
+    use crate::hsv::Hsv;
+    use crate::oklch::Oklch;
+    use crate::xyz::Xyz;
+    use angle::Deg;
+    use approx::*;
+
+    #[test]
+    fn test_sample() {
+        let gradient = Gradient::new(vec![
+            (1.0, Rgb::new(0.0, 0.0, 1.0)),
+            (0.0, Rgb::new(1.0, 0.0, 0.0)),
+            (0.25, Rgb::new(0.0, 1.0, 0.0)),
+        ]);
+        assert_eq!(gradient.stops()[1].0, 0.25);
+        assert_relative_eq!(gradient.sample(-1.0), Rgb::new(1.0, 0.0, 0.0));
+        assert_relative_eq!(gradient.sample(0.0), Rgb::new(1.0, 0.0, 0.0));
+        assert_relative_eq!(gradient.sample(0.125), Rgb::new(0.5, 0.5, 0.0));
+        assert_relative_eq!(gradient.sample(0.25), Rgb::new(0.0, 1.0, 0.0));
+        assert_relative_eq!(gradient.sample(0.625), Rgb::new(0.0, 0.5, 0.5));
+        assert_relative_eq!(gradient.sample(1.5), Rgb::new(0.0, 0.0, 1.0));
+
+        let single = Gradient::from_colors(vec![Rgb::new(0.2, 0.4, 0.6)]);
+        assert_relative_eq!(single.sample(0.5), Rgb::new(0.2, 0.4, 0.6));
+    }
+
+    #[test]
+    fn test_easing() {
+        assert_relative_eq!(Easing::Linear.apply(0.3), 0.3);
+        assert_relative_eq!(Easing::Linear.apply(1.3), 1.0);
+        assert_relative_eq!(Easing::SmoothStep.apply(0.5), 0.5);
+        assert_relative_eq!(Easing::SmoothStep.apply(0.75), 0.84375);
+        assert_relative_eq!(Easing::Custom(|t: f64| t.sqrt()).apply(0.25), 0.5);
+
+        let linear = Easing::CubicBezier(0.25, 0.25, 0.75, 0.75);
+        for &t in &[0.0, 0.1, 0.5, 0.9, 1.0] {
+            assert_relative_eq!(linear.apply(t), t, epsilon = 1e-6);
+        }
+        assert_relative_eq!(Easing::ease().apply(0.5), 0.802_403, epsilon = 1e-5);
+        assert_relative_eq!(Easing::ease_in().apply(0.5), 0.315_356, epsilon = 1e-5);
+        assert_relative_eq!(Easing::ease_out().apply(0.5), 0.684_644, epsilon = 1e-5);
+        assert_relative_eq!(Easing::ease_in_out().apply(0.25), 0.129_161, epsilon = 1e-5);
+        assert_relative_eq!(Easing::<f64>::ease_in_out().apply(1.0), 1.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_sample_eased() {
+        let mut gradient = Gradient::from_colors(vec![
+            Rgb::new(0.0, 0.0, 0.0),
+            Rgb::new(1.0, 1.0, 1.0),
+            Rgb::new(1.0, 0.0, 0.0),
+        ])
+        .with_easing(Easing::SmoothStep);
+        assert_eq!(
+            gradient.easings(),
+            &[Easing::SmoothStep, Easing::SmoothStep]
+        );
+        assert_relative_eq!(gradient.sample(0.125), Rgb::broadcast(0.15625));
+        assert_relative_eq!(gradient.sample(0.5), Rgb::broadcast(1.0));
+
+        gradient.set_segment_easing(1, Easing::Linear);
+        assert_relative_eq!(gradient.sample(0.125), Rgb::broadcast(0.15625));
+        assert_relative_eq!(gradient.sample(0.625), Rgb::new(1.0, 0.75, 0.75));
+        assert_relative_eq!(gradient.sample(2.0), Rgb::new(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_perceptual() {
+        let colors = [
+            Oklab::new(0.2, 0.0, 0.0),
+            Oklab::new(0.3, 0.0, 0.0),
+            Oklab::new(0.54, 0.0, 0.32),
+        ];
+        let gradient = Gradient::perceptual(&colors);
+        assert_relative_eq!(gradient.stops()[0].0, 0.0);
+        assert_relative_eq!(gradient.stops()[1].0, 0.2, epsilon = 1e-12);
+        assert_relative_eq!(gradient.stops()[2].0, 1.0);
+        assert_relative_eq!(
+            gradient.sample(0.6),
+            Oklab::new(0.42, 0.0, 0.16),
+            epsilon = 1e-12
+        );
+
+        let flat = Gradient::perceptual(&[Oklab::new(0.5, 0.1, 0.1); 3]);
+        assert_relative_eq!(flat.stops()[1].0, 0.5);
+
+        let oklch = [
+            Oklch::new(0.3, 0.1, Deg(30.0)),
+            Oklch::new(0.8, 0.1, Deg(120.0)),
+        ];
+        let gradient = Gradient::perceptual(&oklch);
+        assert_relative_eq!(
+            gradient.sample(1.0),
+            Oklab::from_color(&oklch[1]),
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn test_lightness_ramp() {
+        let colors = [
+            Oklab::new(0.2, 0.0, 0.0),
+            Oklab::new(0.95, -0.05, 0.2),
+            Oklab::new(0.45, -0.03, -0.3),
+        ];
+        let naive = Gradient::from_colors(colors.to_vec());
+        assert!(!naive.is_lightness_monotonic::<f64>(11));
+        assert_relative_eq!(naive.lightness_deviation::<f64>(11), 0.625, epsilon = 1e-12);
+
+        let ramp = Gradient::lightness_ramp(&colors);
+        assert_relative_eq!(
+            ramp.stops()[1].1,
+            Oklab::new(0.325, -0.05, 0.2),
+            epsilon = 1e-12
+        );
+        assert!(ramp.is_lightness_monotonic::<f64>(11));
+        assert_relative_eq!(ramp.lightness_deviation::<f64>(11), 0.0, epsilon = 1e-12);
+
+        let xyz = Gradient::duotone(Xyz::new(0.0, 0.0, 0.0), Xyz::new(0.95047, 1.0, 1.08883));
+        assert!(xyz.is_lightness_monotonic::<f64>(16));
+        assert!(xyz.lightness_deviation::<f64>(16) > 0.1);
+    }
+
+    #[test]
+    fn test_sample_polar() {
+        let gradient = Gradient::duotone(
+            Hsv::new(Deg(350.0), 1.0, 1.0),
+            Hsv::new(Deg(30.0), 0.5, 1.0),
+        );
+        assert_relative_eq!(
+            gradient.sample(0.5),
+            Hsv::new(Deg(10.0), 0.75, 1.0),
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn test_gradient_map() {
+        let tritone = Gradient::tritone(
+            Rgb::new(0.0, 0.0, 0.2),
+            Rgb::new(0.8, 0.2, 0.2),
+            Rgb::new(1.0, 1.0, 0.8),
+        );
+        let colors = [
+            Rgb::new(0.0, 0.0, 0.0),
+            Rgb::new(0.5, 0.5, 0.5),
+            Rgb::new(0.25, 0.25, 0.25),
+            Rgb::new(1.0, 1.0, 1.0),
+        ];
+        let mapped = gradient_map(&colors, &tritone);
+        assert_relative_eq!(mapped[0], Rgb::new(0.0, 0.0, 0.2), epsilon = 1e-6);
+        assert_relative_eq!(mapped[1], Rgb::new(0.8, 0.2, 0.2), epsilon = 1e-6);
+        assert_relative_eq!(mapped[2], Rgb::new(0.4, 0.1, 0.2), epsilon = 1e-6);
+        assert_relative_eq!(mapped[3], Rgb::new(1.0, 1.0, 0.8), epsilon = 1e-6);
+
+        let red = Rgb::new(1.0, 0.0, 0.0);
+        let duotone = Gradient::duotone(Rgb::new(0.0, 0.0, 0.0), Rgb::new(1.0, 1.0, 1.0));
+        assert_relative_eq!(
+            duotone.map_luma(&red),
+            Rgb::new(0.2126, 0.2126, 0.2126),
+            epsilon = 1e-6
+        );
+
+        let mut in_place = colors;
+        gradient_map_in_place(&mut in_place, &tritone);
+        assert_eq!(&in_place[..], &mapped[..]);
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn test_fixed_gradient() {
+        let mut gradient: FixedGradient<Rgb<f64>, 3> =
+            FixedGradient::duotone(Rgb::new(0.0, 0.0, 0.0), Rgb::new(1.0, 1.0, 1.0));
+        assert!(gradient.push_stop(0.25, Rgb::new(1.0, 0.0, 0.0)).is_ok());
+        assert!(gradient.push_stop(0.5, Rgb::new(0.0, 1.0, 0.0)).is_err());
+        assert_eq!(gradient.stops()[1].0, 0.25);
+
+        let heap = Gradient::new(gradient.stops().to_vec());
+        for &pos in &[-1.0, 0.0, 0.1, 0.25, 0.6, 1.0, 2.0] {
+            assert_relative_eq!(gradient.sample(pos), heap.sample(pos), epsilon = 1e-6);
+        }
+        assert_relative_eq!(
+            gradient.map_luma(&Rgb::new(0.25, 0.25, 0.25)),
+            Rgb::new(1.0, 0.0, 0.0),
+            epsilon = 1e-6
+        );
+
+        assert!(FixedGradient::<Rgb<f64>, 2>::from_stops(&[]).is_none());
+        assert!(FixedGradient::<Rgb<f64>, 1>::from_stops(&[
+            (0.0, Rgb::new(0.0, 0.0, 0.0)),
+            (1.0, Rgb::new(1.0, 1.0, 1.0)),
+        ])
+        .is_none());
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    #[should_panic(expected = "Gradient stop positions must not be NaN")]
+    fn test_fixed_gradient_nan_stop() {
+        let mut gradient: FixedGradient<Rgb<f64>, 3> =
+            FixedGradient::duotone(Rgb::new(0.0, 0.0, 0.0), Rgb::new(1.0, 1.0, 1.0));
+        let _ = gradient.push_stop(f64::NAN, Rgb::new(1.0, 0.0, 0.0));
+    }
+}
@@ -0,0 +1,315 @@
+//! The `ToLuma` trait for converting colors to grayscale
+
+#![allow(non_snake_case)]
+
+use crate::alpha::Alpha;
+use crate::channel::{AngularChannelScalar, FreeChannelScalar, PosNormalChannelScalar};
+use crate::color::Color;
+use crate::convert::FromColor;
+use crate::ehsi::eHsi;
+use crate::hsl::Hsl;
+use crate::hsv::Hsv;
+use crate::hwb::{Hwb, HwbBoundedChannelTraits};
+use crate::lab::Lab;
+use crate::lchab::Lchab;
+use crate::lchuv::Lchuv;
+use crate::luv::Luv;
+use crate::oklab::Oklab;
+use crate::oklch::Oklch;
+use crate::rgb::Rgb;
+use crate::white_point::WhitePoint;
+use crate::xyy::XyY;
+use crate::xyz::Xyz;
+use angle::Angle;
+use num_traits::{cast, Float};
+use std::fmt;
+
+/// The weights of the red, green and blue channels when computing luma
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum LumaWeights {
+    /// The weights of ITU-R BT.601, used by standard definition video and JPEG
+    Rec601,
+    /// The weights of ITU-R BT.709, used by high definition video
+    Rec709,
+    /// The weights of ITU-R BT.2020, used by ultra high definition video
+    Rec2020,
+    /// The exact CIE Y row of the linear sRGB to `Xyz` transform
+    ///
+    /// For linear sRGB values this is the relative luminance, and it is the value used by WCAG.
+    CieY,
+}
+
+impl LumaWeights {
+    /// Returns the red, green and blue weights, which sum to 1
+    pub fn coefficients<T: Float>(self) -> (T, T, T) {
+        let (kr, kg, kb) = match self {
+            LumaWeights::Rec601 => (0.299, 0.587, 0.114),
+            LumaWeights::Rec709 => (0.2126, 0.7152, 0.0722),
+            LumaWeights::Rec2020 => (0.2627, 0.6780, 0.0593),
+            LumaWeights::CieY => (
+                0.212_672_851_405_622_56,
+                0.715_152_155_287_817_8,
+                0.072_174_993_306_559_56,
+            ),
+        };
+        (cast(kr).unwrap(), cast(kg).unwrap(), cast(kb).unwrap())
+    }
+}
+
+/// A color that can be reduced to a single luma or luminance value
+///
+/// For RGB-based colors, the result is the weighted sum of the red, green and blue channels. The
+/// channel values are used as they are: passing gamma-encoded values gives the luma $`Y'`$ used
+/// by video standards, while passing linear values gives luminance. Other RGB-based models such
+/// as `Hsv` are converted to `Rgb` first.
+///
+/// Colors defined relative to `Xyz`, such as `Lab` and `Oklab`, already have an exact luminance.
+/// They ignore `weights` and return their CIE Y value.
+///
+/// ## Examples:
+///
+/// ```rust
+/// #[macro_use] extern crate approx;
+/// # extern crate prisma;
+///
+/// use prisma::{LumaWeights, Rgb, ToLuma};
+///
+/// let color = Rgb::new(1.0, 0.5, 0.0);
+/// assert_relative_eq!(color.to_luma(LumaWeights::Rec601), 0.5925);
+/// assert_relative_eq!(color.to_luma(LumaWeights::Rec709), 0.5702);
+/// ```
+pub trait ToLuma {
+    /// The scalar type of the computed luma
+    type Scalar;
+
+    /// Compute the luma of `self` using `weights`
+    fn to_luma(&self, weights: LumaWeights) -> Self::Scalar;
+}
+
+impl<T> ToLuma for Rgb<T>
+where
+    T: PosNormalChannelScalar + Float,
+{
+    type Scalar = T;
+
+    fn to_luma(&self, weights: LumaWeights) -> T {
+        let (kr, kg, kb) = weights.coefficients::<T>();
+        kr * self.red() + kg * self.green() + kb * self.blue()
+    }
+}
+
+impl<T, InnerColor> ToLuma for Alpha<T, InnerColor>
+where
+    T: PosNormalChannelScalar,
+    InnerColor: Color + ToLuma,
+{
+    type Scalar = InnerColor::Scalar;
+
+    /// Compute the luma of the color, ignoring alpha
+    fn to_luma(&self, weights: LumaWeights) -> Self::Scalar {
+        self.color().to_luma(weights)
+    }
+}
+
+macro_rules! impl_to_luma_via_rgb {
+    ($Color: ident, [$($bounds: tt)*]) => {
+        impl<T, A> ToLuma for $Color<T, A>
+        where
+            $($bounds)*
+        {
+            type Scalar = T;
+
+            fn to_luma(&self, weights: LumaWeights) -> T {
+                Rgb::from_color(self).to_luma(weights)
+            }
+        }
+    };
+}
+
+impl_to_luma_via_rgb!(Hsl, [
+    T: PosNormalChannelScalar + Float,
+    A: AngularChannelScalar,
+]);
+impl_to_luma_via_rgb!(Hsv, [
+    T: PosNormalChannelScalar + Float,
+    A: AngularChannelScalar,
+]);
+impl_to_luma_via_rgb!(Hwb, [
+    T: HwbBoundedChannelTraits,
+    A: AngularChannelScalar,
+]);
+impl_to_luma_via_rgb!(eHsi, [
+    T: PosNormalChannelScalar + Float,
+    A: AngularChannelScalar + Angle<Scalar = T>,
+]);
+
+impl<T> ToLuma for Xyz<T>
+where
+    T: FreeChannelScalar,
+{
+    type Scalar = T;
+
+    fn to_luma(&self, _weights: LumaWeights) -> T {
+        self.y()
+    }
+}
+
+impl<T> ToLuma for XyY<T>
+where
+    T: FreeChannelScalar + PosNormalChannelScalar + Float,
+{
+    type Scalar = T;
+
+    fn to_luma(&self, _weights: LumaWeights) -> T {
+        self.Y()
+    }
+}
+
+impl<T, W> ToLuma for Lab<T, W>
+where
+    T: FreeChannelScalar,
+    W: WhitePoint<T>,
+{
+    type Scalar = T;
+
+    fn to_luma(&self, _weights: LumaWeights) -> T {
+        self.to_xyz().y()
+    }
+}
+
+impl<T, W> ToLuma for Luv<T, W>
+where
+    T: FreeChannelScalar + fmt::Display,
+    W: WhitePoint<T>,
+{
+    type Scalar = T;
+
+    fn to_luma(&self, _weights: LumaWeights) -> T {
+        self.to_xyz().y()
+    }
+}
+
+impl<T, W, A> ToLuma for Lchab<T, W, A>
+where
+    T: FreeChannelScalar,
+    W: WhitePoint<T>,
+    A: AngularChannelScalar + Angle<Scalar = T>,
+{
+    type Scalar = T;
+
+    fn to_luma(&self, weights: LumaWeights) -> T {
+        Lab::from_color(self).to_luma(weights)
+    }
+}
+
+impl<T, W, A> ToLuma for Lchuv<T, W, A>
+where
+    T: FreeChannelScalar + fmt::Display,
+    W: WhitePoint<T>,
+    A: AngularChannelScalar + Angle<Scalar = T>,
+{
+    type Scalar = T;
+
+    fn to_luma(&self, weights: LumaWeights) -> T {
+        Luv::from_color(self).to_luma(weights)
+    }
+}
+
+impl<T> ToLuma for Oklab<T>
+where
+    T: FreeChannelScalar,
+{
+    type Scalar = T;
+
+    /// Compute the luminance relative to D65
+    fn to_luma(&self, _weights: LumaWeights) -> T {
+        Xyz::from_color(self).y()
+    }
+}
+
+impl<T, A> ToLuma for Oklch<T, A>
+where
+    T: FreeChannelScalar,
+    A: AngularChannelScalar + Angle<Scalar = T>,
+{
+    type Scalar = T;
+
+    /// Compute the luminance relative to D65
+    fn to_luma(&self, weights: LumaWeights) -> T {
+        Oklab::from_color(self).to_luma(weights)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::alpha::Rgba;
+    use crate::white_point::D65;
+    use angle::Deg;
+    use approx::*;
+
+    #[test]
+    fn test_rgb_luma() {
+        let white = Rgb::new(1.0, 1.0, 1.0);
+        let color = Rgb::new(0.2, 0.6, 0.9);
+        for &weights in &[
+            LumaWeights::Rec601,
+            LumaWeights::Rec709,
+            LumaWeights::Rec2020,
+            LumaWeights::CieY,
+        ] {
+            assert_relative_eq!(white.to_luma(weights), 1.0, epsilon = 1e-12);
+        }
+        assert_relative_eq!(color.to_luma(LumaWeights::Rec601), 0.5146, epsilon = 1e-12);
+        assert_relative_eq!(color.to_luma(LumaWeights::Rec709), 0.53662, epsilon = 1e-12);
+        assert_relative_eq!(
+            color.to_luma(LumaWeights::Rec2020),
+            0.51271,
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(
+            color.to_luma(LumaWeights::CieY),
+            color.to_luma(LumaWeights::Rec709),
+            epsilon = 1e-4
+        );
+        assert_relative_eq!(
+            Rgba::new(color, 0.5).to_luma(LumaWeights::Rec601),
+            0.5146,
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn test_polar_luma() {
+        let rgb = Rgb::new(0.2, 0.6, 0.9);
+        let hsv = Hsv::<f64, Deg<f64>>::from_color(&rgb);
+        let hsl = Hsl::<f64, Deg<f64>>::from_color(&rgb);
+        assert_relative_eq!(
+            hsv.to_luma(LumaWeights::Rec709),
+            rgb.to_luma(LumaWeights::Rec709),
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            hsl.to_luma(LumaWeights::Rec601),
+            rgb.to_luma(LumaWeights::Rec601),
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn test_cie_luma() {
+        let xyz = Xyz::new(0.3, 0.4, 0.5);
+        assert_relative_eq!(xyz.to_luma(LumaWeights::Rec601), 0.4);
+        assert_relative_eq!(XyY::from_color(&xyz).to_luma(LumaWeights::CieY), 0.4);
+        let lab = Lab::from_xyz(&xyz, D65);
+        assert_relative_eq!(lab.to_luma(LumaWeights::CieY), 0.4, epsilon = 1e-9);
+        let lch = Lchab::<f64, D65, Deg<f64>>::from_color(&lab);
+        assert_relative_eq!(lch.to_luma(LumaWeights::CieY), 0.4, epsilon = 1e-9);
+        let luv = Luv::from_xyz(&xyz, D65);
+        assert_relative_eq!(luv.to_luma(LumaWeights::CieY), 0.4, epsilon = 1e-9);
+        let oklab = Oklab::from_color(&xyz);
+        assert_relative_eq!(oklab.to_luma(LumaWeights::CieY), 0.4, epsilon = 1e-9);
+        let oklch = Oklch::<f64, Deg<f64>>::from_color(&oklab);
+        assert_relative_eq!(oklch.to_luma(LumaWeights::CieY), 0.4, epsilon = 1e-9);
+    }
+}
@@ -0,0 +1,398 @@
+//! Color harmony palette generation
+//!
+//! A color harmony is a set of colors whose hues sit at fixed angles from each other on a color
+//! wheel. [`palette`](fn.palette.html) builds one from a seed color by rotating its hue, and
+//! [`palette_in`](fn.palette_in.html) does the same in a polar color model chosen by the caller.
+//! Rotating in `Hsl` or `Hsv` uses the familiar RGB color wheel, while rotating in `Oklch` keeps
+//! the perceived lightness and chroma of the palette even.
+//!
+//! [`ryb_palette`](fn.ryb_palette.html) uses the traditional red-yellow-blue painter's wheel
+//! instead, where the complement of red is green and the complement of yellow is purple.
+//!
+//! [`shades_in`](fn.shades_in.html), [`tints_in`](fn.tints_in.html) and
+//! [`tones_in`](fn.tones_in.html) build the other staple of color schemes: variations of a single
+//! color mixed toward black, white and gray respectively.
+//!
+//! ## Examples:
+//!
+//! ```rust
+//! #[macro_use] extern crate approx;
+//! # extern crate prisma;
+//! extern crate angular_units as angle;
+//!
+//! use prisma::{Hsl, Rgb};
+//! use prisma::harmony::{palette_in, Harmony};
+//! use angle::Deg;
+//!
+//! let colors = palette_in::<Hsl<f64, Deg<f64>>, _>(&Rgb::new(1.0, 0.0, 0.0), Harmony::Triadic);
+//! assert_relative_eq!(colors[1], Rgb::new(0.0, 1.0, 0.0), epsilon=1e-6);
+//! assert_relative_eq!(colors[2], Rgb::new(0.0, 0.0, 1.0), epsilon=1e-6);
+//! ```
+
+use crate::adjust::RotateHue;
+use crate::channel::PosNormalChannelScalar;
+use crate::color::Lerp;
+use crate::convert::FromColor;
+use crate::rgb::Rgb;
+use angle::Angle;
+use num_traits::{cast, Float};
+
+/// The corresponding hues of the RYB and RGB color wheels, in degrees
+///
+/// The RYB wheel is stretched between red and yellow and compressed between green and blue
+/// relative to the RGB wheel. Hues in between are linearly interpolated.
+const RYB_WHEEL: [(f64, f64); 7] = [
+    (0.0, 0.0),
+    (60.0, 35.0),
+    (120.0, 60.0),
+    (180.0, 135.0),
+    (240.0, 225.0),
+    (300.0, 275.0),
+    (360.0, 360.0),
+];
+
+/// A scheme of hue offsets making up a harmonious palette
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Harmony {
+    /// The seed and the hue opposite it
+    Complementary,
+    /// The seed and the two hues 30° to either side of its complement
+    SplitComplementary,
+    /// The seed and its two neighbors 30° away
+    Analogous,
+    /// Three hues spaced evenly around the wheel
+    Triadic,
+    /// Two pairs of complements 60° apart, forming a rectangle on the wheel
+    Tetradic,
+    /// Four hues spaced evenly around the wheel
+    Square,
+}
+
+impl Harmony {
+    /// Returns the hue offset of each palette color from the seed, in degrees
+    ///
+    /// The first offset is always 0, for the seed itself.
+    pub fn offsets(self) -> &'static [f64] {
+        match self {
+            Harmony::Complementary => &[0.0, 180.0],
+            Harmony::SplitComplementary => &[0.0, 150.0, 210.0],
+            Harmony::Analogous => &[0.0, -30.0, 30.0],
+            Harmony::Triadic => &[0.0, 120.0, 240.0],
+            Harmony::Tetradic => &[0.0, 60.0, 180.0, 240.0],
+            Harmony::Square => &[0.0, 90.0, 180.0, 270.0],
+        }
+    }
+}
+
+/// Generate a harmonious palette from `seed` by rotating its hue
+///
+/// The hue is rotated in the native hue of `C`, so the palette of an `Hsl` color uses the RGB
+/// color wheel and the palette of an `Oklch` color the `Oklch` one. `Rgb` colors are rotated in
+/// `Hsl`. The first color of the palette is `seed` itself.
+///
+/// ## Examples:
+///
+/// ```rust
+/// #[macro_use] extern crate approx;
+/// # extern crate prisma;
+/// extern crate angular_units as angle;
+///
+/// use prisma::Oklch;
+/// use prisma::harmony::{palette, Harmony};
+/// use angle::Deg;
+///
+/// let seed = Oklch::new(0.7, 0.1, Deg(40.0));
+/// let colors = palette(&seed, Harmony::SplitComplementary);
+/// assert_relative_eq!(colors[1], Oklch::new(0.7, 0.1, Deg(190.0)), epsilon=1e-6);
+/// assert_relative_eq!(colors[2], Oklch::new(0.7, 0.1, Deg(250.0)), epsilon=1e-6);
+/// ```
+pub fn palette<C>(seed: &C, harmony: Harmony) -> Vec<C>
+where
+    C: RotateHue,
+    <C::Hue as Angle>::Scalar: Float,
+{
+    harmony
+        .offsets()
+        .iter()
+        .map(|&offset| seed.rotate_hue(hue_from_degrees::<C::Hue>(offset)))
+        .collect()
+}
+
+/// Generate a harmonious palette from `seed` by rotating its hue in the color model `Space`
+///
+/// `seed` is converted to `Space`, rotated by each of the offsets of `harmony` and converted back,
+/// in the same way [`mix_in`](../fn.mix_in.html) mixes colors in a chosen model.
+///
+/// ## Examples:
+///
+/// ```rust
+/// #[macro_use] extern crate approx;
+/// # extern crate prisma;
+/// extern crate angular_units as angle;
+///
+/// use prisma::{Hsv, Rgb};
+/// use prisma::harmony::{palette_in, Harmony};
+/// use angle::Deg;
+///
+/// let red = Rgb::new(1.0, 0.0, 0.0);
+/// let colors = palette_in::<Hsv<f64, Deg<f64>>, _>(&red, Harmony::Complementary);
+/// assert_relative_eq!(colors[1], Rgb::new(0.0, 1.0, 1.0), epsilon=1e-6);
+/// ```
+pub fn palette_in<Space, C>(seed: &C, harmony: Harmony) -> Vec<C>
+where
+    Space: RotateHue + FromColor<C>,
+    C: FromColor<Space>,
+    <Space::Hue as Angle>::Scalar: Float,
+{
+    palette(&Space::from_color(seed), harmony)
+        .iter()
+        .map(C::from_color)
+        .collect()
+}
+
+/// Generate a harmonious palette from `seed` using the RYB painter's color wheel
+///
+/// This works like [`palette`](fn.palette.html), but the offsets of `harmony` are measured on the
+/// red-yellow-blue wheel used in traditional color theory. The hue of `seed` is mapped onto the
+/// RYB wheel, rotated, and mapped back. `C` should be built on the RGB color wheel, such as `Rgb`,
+/// `Hsl` or `Hsv`.
+///
+/// ## Examples:
+///
+/// ```rust
+/// #[macro_use] extern crate approx;
+/// # extern crate prisma;
+/// extern crate angular_units as angle;
+///
+/// use prisma::Hsl;
+/// use prisma::harmony::{ryb_palette, Harmony};
+/// use angle::Deg;
+///
+/// let red = Hsl::new(Deg(0.0), 1.0, 0.5);
+/// let colors = ryb_palette(&red, Harmony::Complementary);
+/// assert_relative_eq!(colors[1], Hsl::new(Deg(135.0), 1.0, 0.5), epsilon=1e-6);
+/// ```
+pub fn ryb_palette<C>(seed: &C, harmony: Harmony) -> Vec<C>
+where
+    C: RotateHue,
+    <C::Hue as Angle>::Scalar: Float,
+{
+    let period: f64 = cast(C::Hue::period()).unwrap();
+    let hue: f64 = cast(seed.hue_angle().scalar()).unwrap();
+    let ryb_hue = rgb_to_ryb_hue(hue / period * 360.0);
+
+    harmony
+        .offsets()
+        .iter()
+        .map(|&offset| {
+            let rgb_hue = ryb_to_rgb_hue(ryb_hue + offset);
+            seed.with_hue(hue_from_degrees(rgb_hue))
+        })
+        .collect()
+}
+
+/// Returns `count` colors evenly spaced from `from` to `to`, interpolated in the color model `Space`
+///
+/// Neither `from` nor `to` is included: color `i` is at position $`\frac{i + 1}{count + 1}`$.
+/// Colors are interpolated as by [`mix_in`](../fn.mix_in.html).
+///
+/// ## Examples:
+///
+/// ```rust
+/// #[macro_use] extern crate approx;
+/// # extern crate prisma;
+///
+/// use prisma::Oklab;
+/// use prisma::harmony::steps_in;
+///
+/// let steps = steps_in::<Oklab<f64>, _>(&Oklab::new(0.2, 0.1, 0.0), &Oklab::new(0.8, 0.1, 0.0), 2);
+/// assert_relative_eq!(steps[0], Oklab::new(0.4, 0.1, 0.0), epsilon=1e-9);
+/// assert_relative_eq!(steps[1], Oklab::new(0.6, 0.1, 0.0), epsilon=1e-9);
+/// ```
+pub fn steps_in<Space, C>(from: &C, to: &C, count: usize) -> Vec<C>
+where
+    Space: FromColor<C> + Lerp,
+    C: FromColor<Space>,
+{
+    let from = Space::from_color(from);
+    let to = Space::from_color(to);
+    let divisor: Space::Position = cast(count + 1).unwrap();
+    (1..=count)
+        .map(|i| C::from_color(&from.lerp(&to, cast::<_, Space::Position>(i).unwrap() / divisor)))
+        .collect()
+}
+
+/// Returns `count` shades of `color`, mixed toward black in the color model `Space`
+///
+/// Shades get darker through the list. Neither `color` nor black is included, see
+/// [`steps_in`](fn.steps_in.html).
+///
+/// ## Examples:
+///
+/// ```rust
+/// #[macro_use] extern crate approx;
+/// # extern crate prisma;
+///
+/// use prisma::Rgb;
+/// use prisma::harmony::shades_in;
+///
+/// let shades = shades_in::<Rgb<f64>, _>(&Rgb::new(1.0, 0.5, 0.0), 3);
+/// assert_relative_eq!(shades[0], Rgb::new(0.75, 0.375, 0.0));
+/// assert_relative_eq!(shades[2], Rgb::new(0.25, 0.125, 0.0));
+/// ```
+pub fn shades_in<Space, T>(color: &Rgb<T>, count: usize) -> Vec<Rgb<T>>
+where
+    T: PosNormalChannelScalar + Float,
+    Space: FromColor<Rgb<T>> + Lerp,
+    Rgb<T>: FromColor<Space>,
+{
+    steps_in::<Space, _>(color, &Rgb::new(T::zero(), T::zero(), T::zero()), count)
+}
+
+/// Returns `count` tints of `color`, mixed toward white in the color model `Space`
+///
+/// Tints get lighter through the list. Neither `color` nor white is included, see
+/// [`steps_in`](fn.steps_in.html).
+pub fn tints_in<Space, T>(color: &Rgb<T>, count: usize) -> Vec<Rgb<T>>
+where
+    T: PosNormalChannelScalar + Float,
+    Space: FromColor<Rgb<T>> + Lerp,
+    Rgb<T>: FromColor<Space>,
+{
+    steps_in::<Space, _>(color, &Rgb::new(T::one(), T::one(), T::one()), count)
+}
+
+/// Returns `count` tones of `color`, mixed toward middle gray in the color model `Space`
+///
+/// Tones get grayer through the list. Neither `color` nor gray is included, see
+/// [`steps_in`](fn.steps_in.html).
+pub fn tones_in<Space, T>(color: &Rgb<T>, count: usize) -> Vec<Rgb<T>>
+where
+    T: PosNormalChannelScalar + Float,
+    Space: FromColor<Rgb<T>> + Lerp,
+    Rgb<T>: FromColor<Space>,
+{
+    let half = cast(0.5).unwrap();
+    steps_in::<Space, _>(color, &Rgb::new(half, half, half), count)
+}
+
+fn hue_from_degrees<A>(degrees: f64) -> A
+where
+    A: Angle,
+    A::Scalar: Float,
+{
+    A::new(A::period() * cast(degrees / 360.0).unwrap()).normalize()
+}
+
+fn rgb_to_ryb_hue(hue: f64) -> f64 {
+    map_wheel(hue, |&(ryb, rgb)| (rgb, ryb))
+}
+
+fn ryb_to_rgb_hue(hue: f64) -> f64 {
+    map_wheel(hue, |&(ryb, rgb)| (ryb, rgb))
+}
+
+fn map_wheel<F>(hue: f64, point: F) -> f64
+where
+    F: Fn(&(f64, f64)) -> (f64, f64),
+{
+    let hue = hue.rem_euclid(360.0);
+    let points: Vec<_> = RYB_WHEEL.iter().map(point).collect();
+    let upper = points
+        .iter()
+        .position(|p| p.0 > hue)
+        .unwrap_or(points.len() - 1);
+    let (x0, y0) = points[upper - 1];
+    let (x1, y1) = points[upper];
+    y0 + (hue - x0) / (x1 - x0) * (y1 - y0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::hsl::Hsl;
+    use crate::hsv::Hsv;
+    use crate::oklch::Oklch;
+    use crate::rgb::Rgb;
+    use angle::{Deg, Turns};
+    use approx::*;
+
+    #[test]
+    fn test_palette() {
+        let red = Rgb::new(1.0, 0.0, 0.0);
+        let square = palette_in::<Hsv<f64, Deg<f64>>, _>(&red, Harmony::Square);
+        assert_eq!(square.len(), 4);
+        assert_relative_eq!(square[0], red, epsilon = 1e-6);
+        assert_relative_eq!(square[1], Rgb::new(0.5, 1.0, 0.0), epsilon = 1e-6);
+        assert_relative_eq!(square[2], Rgb::new(0.0, 1.0, 1.0), epsilon = 1e-6);
+        assert_relative_eq!(square[3], Rgb::new(0.5, 0.0, 1.0), epsilon = 1e-6);
+
+        let seed = Hsl::new(Turns(0.05), 0.6, 0.4);
+        let analogous = palette(&seed, Harmony::Analogous);
+        assert_relative_eq!(
+            analogous[1],
+            Hsl::new(Turns(0.05 - 1.0 / 12.0 + 1.0), 0.6, 0.4)
+        );
+        assert_relative_eq!(analogous[2], Hsl::new(Turns(0.05 + 1.0 / 12.0), 0.6, 0.4));
+
+        let seed = Oklch::new(0.6, 0.15, Deg(300.0));
+        let tetradic = palette(&seed, Harmony::Tetradic);
+        for (color, &hue) in tetradic.iter().zip(&[300.0, 0.0, 120.0, 180.0]) {
+            assert_relative_eq!(color, &Oklch::new(0.6, 0.15, Deg(hue)), epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_ryb_palette() {
+        let yellow = Hsl::new(Deg(60.0), 1.0, 0.5);
+        let complement = ryb_palette(&yellow, Harmony::Complementary);
+        assert_relative_eq!(
+            complement[1],
+            Hsl::new(Deg(275.0), 1.0, 0.5),
+            epsilon = 1e-9
+        );
+
+        let red = Rgb::new(1.0, 0.0, 0.0);
+        let triad = ryb_palette(&red, Harmony::Triadic);
+        assert_relative_eq!(triad[0], red, epsilon = 1e-6);
+        assert_relative_eq!(triad[1], Rgb::new(1.0, 1.0, 0.0), epsilon = 1e-6);
+        assert_relative_eq!(triad[2], Rgb::new(0.0, 0.25, 1.0), epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_ryb_wheel() {
+        for &hue in &[0.0, 17.5, 35.0, 50.0, 100.0, 200.0, 250.0, 330.0, 359.0] {
+            assert_relative_eq!(ryb_to_rgb_hue(rgb_to_ryb_hue(hue)), hue, epsilon = 1e-9);
+        }
+        assert_relative_eq!(rgb_to_ryb_hue(47.5), 90.0);
+        assert_relative_eq!(ryb_to_rgb_hue(210.0), 180.0);
+    }
+
+    #[test]
+    fn test_shades_tints_tones() {
+        let red = Rgb::new(0.8, 0.2, 0.2);
+        let shades = shades_in::<Rgb<f64>, _>(&red, 3);
+        assert_eq!(shades.len(), 3);
+        assert_relative_eq!(shades[0], Rgb::new(0.6, 0.15, 0.15), epsilon = 1e-9);
+        assert_relative_eq!(shades[1], Rgb::new(0.4, 0.1, 0.1), epsilon = 1e-9);
+        assert_relative_eq!(shades[2], Rgb::new(0.2, 0.05, 0.05), epsilon = 1e-9);
+
+        let tints = tints_in::<Hsl<f64, Deg<f64>>, _>(&red, 4);
+        for (color, expected) in tints.iter().zip(&[0.6, 0.7, 0.8, 0.9]) {
+            let hsl = Hsl::<f64, Deg<f64>>::from_color(color);
+            assert_relative_eq!(hsl.hue(), Deg(0.0), epsilon = 1e-6);
+            assert_relative_eq!(hsl.lightness(), expected, epsilon = 1e-6);
+        }
+
+        let tones = tones_in::<Rgb<f64>, _>(&red, 1);
+        assert_relative_eq!(tones[0], Rgb::new(0.65, 0.35, 0.35), epsilon = 1e-9);
+        let tones = tones_in::<Hsv<f64, Deg<f64>>, _>(&red, 2);
+        let saturation: Vec<_> = tones
+            .iter()
+            .map(|c| Hsv::<f64, Deg<f64>>::from_color(c).saturation())
+            .collect();
+        assert!(saturation[0] < 0.75 && saturation[1] < saturation[0]);
+
+        assert!(shades_in::<Rgb<f64>, _>(&red, 0).is_empty());
+    }
+}
@@ -0,0 +1,503 @@
+//! Parsing and formatting of hexadecimal sRGB color strings
+//!
+//! Hex strings such as `"#ff8000"` are by far the most common way colors are written down, and
+//! they are almost always 8-bit sRGB. [`parse_hex`](fn.parse_hex.html) returns the exact
+//! `Rgb<u8>` value of a string, while [`parse_as`](fn.parse_as.html) also decodes the color and
+//! converts it into any type implementing [`FromSrgb`](trait.FromSrgb.html) in one step.
+//! [`parse_hex_alpha`](fn.parse_hex_alpha.html) also accepts an alpha channel, returning an
+//! `Rgba<u8>`.
+//!
+//! `Rgb<u8>` and `Rgba<u8>` can be written back to hex strings with `to_hex_string`, or with the
+//! `{:x}` and `{:X}` format specifiers. The alternate form `{:#x}` adds a leading `#`.
+//!
+//! ## Examples:
+//!
+//! ```rust
+//! #[macro_use] extern crate approx;
+//! # extern crate prisma;
+//!
+//! use prisma::{Lab, Rgb};
+//! use prisma::hex::{parse_as, parse_hex};
+//! use prisma::white_point::D65;
+//!
+//! assert_eq!(parse_hex("#ff8000"), Ok(Rgb::new(255, 128, 0)));
+//! assert_eq!(format!("{:#x}", Rgb::new(255u8, 128, 0)), "#ff8000");
+//!
+//! let lab = parse_as::<Lab<f32, D65>>("#ffffff").unwrap();
+//! assert_relative_eq!(lab, Lab::new(100.0, 0.0, 0.0), epsilon=1e-2);
+//! ```
+
+use crate::alpha::Rgba;
+use crate::channel::{
+    AngularChannelScalar, ChannelFormatCast, FreeChannelScalar, PosNormalChannelScalar,
+};
+use crate::color::Color;
+use crate::color_space::named::SRgb;
+use crate::color_space::ConvertToXyz;
+use crate::convert::FromColor;
+use crate::ehsi::eHsi;
+use crate::encoding::{EncodableColor, EncodedColor, LinearEncoding, SrgbEncoding};
+use crate::hsi::Hsi;
+use crate::hsl::Hsl;
+use crate::hsv::Hsv;
+use crate::hwb::Hwb;
+use crate::lab::Lab;
+use crate::lchab::Lchab;
+use crate::lchuv::Lchuv;
+use crate::lms::{chromatic_adaptation_transform, Bradford};
+use crate::luv::Luv;
+use crate::rgb::Rgb;
+use crate::rgi::Rgi;
+use crate::white_point::{UnitWhitePoint, WhitePoint, D65};
+use crate::xyy::XyY;
+use crate::xyz::Xyz;
+use angle::{Angle, FromAngle, Rad};
+use num_traits;
+use std::error::Error;
+use std::fmt;
+
+/// An error returned when a hex color string could not be parsed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseHexError {
+    /// The string did not contain a supported number of hex digits. Holds the number of digits
+    /// found.
+    InvalidLength(usize),
+    /// The string contained a character that is not a hex digit
+    InvalidDigit(char),
+}
+
+impl fmt::Display for ParseHexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseHexError::InvalidLength(len) => {
+                write!(f, "unsupported number of hex digits {}", len)
+            }
+            ParseHexError::InvalidDigit(c) => write!(f, "invalid hex digit '{}'", c),
+        }
+    }
+}
+
+impl Error for ParseHexError {}
+
+/// Parse a hex color string into an `Rgb<u8>`
+///
+/// Accepts `"rrggbb"` and the shorthand `"rgb"`, with or without a leading `#`. Digits may be upper
+/// or lower case.
+pub fn parse_hex(s: &str) -> Result<Rgb<u8>, ParseHexError> {
+    let (values, len) = parse_digits(s)?;
+    match len {
+        3 | 6 => Ok(channels_from_digits(&values, len).0),
+        _ => Err(ParseHexError::InvalidLength(len)),
+    }
+}
+
+/// Parse a hex color string with an optional alpha channel into an `Rgba<u8>`
+///
+/// Accepts `"rrggbbaa"` and `"rgba"` as well as the forms accepted by
+/// [`parse_hex`](fn.parse_hex.html), which are fully opaque.
+///
+/// ## Examples:
+///
+/// ```rust
+/// # extern crate prisma;
+///
+/// use prisma::{Rgb, Rgba};
+/// use prisma::hex::parse_hex_alpha;
+///
+/// assert_eq!(parse_hex_alpha("#ff800080"), Ok(Rgba::new(Rgb::new(255, 128, 0), 128)));
+/// assert_eq!(parse_hex_alpha("#f80"), Ok(Rgba::new(Rgb::new(255, 136, 0), 255)));
+/// ```
+pub fn parse_hex_alpha(s: &str) -> Result<Rgba<u8>, ParseHexError> {
+    let (values, len) = parse_digits(s)?;
+    match len {
+        3 | 4 | 6 | 8 => {
+            let (rgb, alpha) = channels_from_digits(&values, len);
+            Ok(Rgba::new(rgb, alpha.unwrap_or(255)))
+        }
+        _ => Err(ParseHexError::InvalidLength(len)),
+    }
+}
+
+/// Returns the value of up to 8 hex digits in `s`, and the total number of digits
+fn parse_digits(s: &str) -> Result<([u8; 8], usize), ParseHexError> {
+    let digits = s.strip_prefix('#').unwrap_or(s);
+    let mut values = [0u8; 8];
+    let mut len = 0;
+    for c in digits.chars() {
+        let digit = c.to_digit(16).ok_or(ParseHexError::InvalidDigit(c))?;
+        if len < values.len() {
+            values[len] = digit as u8;
+        }
+        len += 1;
+    }
+    Ok((values, len))
+}
+
+/// Combine the digits of a 3, 4, 6 or 8 digit string into channels
+fn channels_from_digits(values: &[u8; 8], len: usize) -> (Rgb<u8>, Option<u8>) {
+    let channel = |i: usize| {
+        if len <= 4 {
+            values[i] * 17
+        } else {
+            values[2 * i] * 16 + values[2 * i + 1]
+        }
+    };
+    let alpha = if len == 4 || len == 8 {
+        Some(channel(3))
+    } else {
+        None
+    };
+    (Rgb::new(channel(0), channel(1), channel(2)), alpha)
+}
+
+/// Parse a hex sRGB color string directly into the color type `C`
+///
+/// This is equivalent to calling [`parse_hex`](fn.parse_hex.html) followed by
+/// [`FromSrgb::from_srgb`](trait.FromSrgb.html#tymethod.from_srgb).
+pub fn parse_as<C>(s: &str) -> Result<C, ParseHexError>
+where
+    C: FromSrgb,
+{
+    parse_hex(s).map(|rgb| C::from_srgb(&rgb))
+}
+
+impl Rgb<u8> {
+    /// Parse a hex color string, as [`parse_hex`](hex/fn.parse_hex.html)
+    pub fn from_hex_str(s: &str) -> Result<Self, ParseHexError> {
+        parse_hex(s)
+    }
+    /// Returns the color as a `"#rrggbb"` hex string
+    pub fn to_hex_string(&self) -> String {
+        format!("{:#x}", self)
+    }
+}
+
+impl Rgba<u8> {
+    /// Parse a hex color string with an optional alpha channel, as
+    /// [`parse_hex_alpha`](hex/fn.parse_hex_alpha.html)
+    pub fn from_hex_str(s: &str) -> Result<Self, ParseHexError> {
+        parse_hex_alpha(s)
+    }
+    /// Returns the color as a `"#rrggbbaa"` hex string
+    pub fn to_hex_string(&self) -> String {
+        format!("{:#x}", self)
+    }
+}
+
+impl fmt::LowerHex for Rgb<u8> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("#")?;
+        }
+        write!(
+            f,
+            "{:02x}{:02x}{:02x}",
+            self.red(),
+            self.green(),
+            self.blue()
+        )
+    }
+}
+
+impl fmt::UpperHex for Rgb<u8> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("#")?;
+        }
+        write!(
+            f,
+            "{:02X}{:02X}{:02X}",
+            self.red(),
+            self.green(),
+            self.blue()
+        )
+    }
+}
+
+impl fmt::LowerHex for Rgba<u8> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::LowerHex::fmt(self.color(), f)?;
+        write!(f, "{:02x}", self.alpha())
+    }
+}
+
+impl fmt::UpperHex for Rgba<u8> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::UpperHex::fmt(self.color(), f)?;
+        write!(f, "{:02X}", self.alpha())
+    }
+}
+
+/// A color that can be constructed from an 8-bit sRGB color
+///
+/// Device-dependent models are computed directly from the sRGB encoded values, as is usual for
+/// them. Device-independent colors are decoded and transformed through XYZ, adapting from the D65
+/// white point of sRGB when the destination uses a different white point.
+pub trait FromSrgb: Sized {
+    /// Construct `Self` from an sRGB encoded `Rgb<u8>`
+    fn from_srgb(rgb: &Rgb<u8>) -> Self;
+}
+
+impl<T> FromSrgb for Rgb<T>
+where
+    T: PosNormalChannelScalar,
+    u8: ChannelFormatCast<T>,
+{
+    fn from_srgb(rgb: &Rgb<u8>) -> Self {
+        rgb.color_cast()
+    }
+}
+
+impl<T> FromSrgb for EncodedColor<Rgb<T>, SrgbEncoding>
+where
+    T: PosNormalChannelScalar,
+    u8: ChannelFormatCast<T>,
+{
+    fn from_srgb(rgb: &Rgb<u8>) -> Self {
+        rgb.color_cast::<T>().srgb_encoded()
+    }
+}
+
+impl<T> FromSrgb for EncodedColor<Rgb<T>, LinearEncoding>
+where
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+    u8: ChannelFormatCast<T>,
+{
+    fn from_srgb(rgb: &Rgb<u8>) -> Self {
+        rgb.color_cast::<T>().srgb_encoded().decode()
+    }
+}
+
+macro_rules! impl_from_srgb_polar {
+    ($($name:ident),*) => {
+        $(
+            impl<T, A> FromSrgb for $name<T, A>
+            where
+                T: PosNormalChannelScalar + num_traits::Float,
+                A: AngularChannelScalar,
+                u8: ChannelFormatCast<T>,
+                $name<T, A>: FromColor<Rgb<T>>,
+            {
+                fn from_srgb(rgb: &Rgb<u8>) -> Self {
+                    $name::from_color(&rgb.color_cast::<T>())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_srgb_polar!(Hsv, Hsl, Hwb, Hsi, eHsi);
+
+impl<T> FromSrgb for Rgi<T>
+where
+    T: PosNormalChannelScalar + num_traits::Float,
+    u8: ChannelFormatCast<T>,
+{
+    fn from_srgb(rgb: &Rgb<u8>) -> Self {
+        Rgi::from_color(&rgb.color_cast::<T>())
+    }
+}
+
+impl<T> FromSrgb for Xyz<T>
+where
+    T: PosNormalChannelScalar + FreeChannelScalar + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+    u8: ChannelFormatCast<T>,
+{
+    fn from_srgb(rgb: &Rgb<u8>) -> Self {
+        SRgb::new().convert_to_xyz(&rgb.color_cast::<T>().srgb_encoded())
+    }
+}
+
+impl<T> FromSrgb for XyY<T>
+where
+    T: PosNormalChannelScalar + FreeChannelScalar + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+    u8: ChannelFormatCast<T>,
+{
+    fn from_srgb(rgb: &Rgb<u8>) -> Self {
+        XyY::from_color(&Xyz::from_srgb(rgb))
+    }
+}
+
+impl<T, W> FromSrgb for Lab<T, W>
+where
+    T: PosNormalChannelScalar + FreeChannelScalar + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+    u8: ChannelFormatCast<T>,
+    W: UnitWhitePoint<T>,
+{
+    fn from_srgb(rgb: &Rgb<u8>) -> Self {
+        Lab::from_xyz(&srgb_to_xyz_with_white(rgb, &W::default()), W::default())
+    }
+}
+
+impl<T, W> FromSrgb for Luv<T, W>
+where
+    T: PosNormalChannelScalar + FreeChannelScalar + ChannelFormatCast<f64> + fmt::Display,
+    f64: ChannelFormatCast<T>,
+    u8: ChannelFormatCast<T>,
+    W: UnitWhitePoint<T>,
+{
+    fn from_srgb(rgb: &Rgb<u8>) -> Self {
+        Luv::from_xyz(&srgb_to_xyz_with_white(rgb, &W::default()), W::default())
+    }
+}
+
+impl<T, W, A> FromSrgb for Lchab<T, W, A>
+where
+    T: PosNormalChannelScalar + FreeChannelScalar + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+    u8: ChannelFormatCast<T>,
+    W: UnitWhitePoint<T>,
+    A: AngularChannelScalar + FromAngle<Rad<T>> + Angle,
+{
+    fn from_srgb(rgb: &Rgb<u8>) -> Self {
+        Lchab::from_color(&Lab::<T, W>::from_srgb(rgb))
+    }
+}
+
+impl<T, W, A> FromSrgb for Lchuv<T, W, A>
+where
+    T: PosNormalChannelScalar + FreeChannelScalar + ChannelFormatCast<f64> + fmt::Display,
+    f64: ChannelFormatCast<T>,
+    u8: ChannelFormatCast<T>,
+    W: UnitWhitePoint<T>,
+    A: AngularChannelScalar + FromAngle<Rad<T>> + Angle,
+{
+    fn from_srgb(rgb: &Rgb<u8>) -> Self {
+        Lchuv::from_color(&Luv::<T, W>::from_srgb(rgb))
+    }
+}
+
+fn srgb_to_xyz_with_white<T, W>(rgb: &Rgb<u8>, white: &W) -> Xyz<T>
+where
+    T: PosNormalChannelScalar + FreeChannelScalar + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+    u8: ChannelFormatCast<T>,
+    W: WhitePoint<T>,
+{
+    let xyz = Xyz::from_srgb(rgb);
+    let src_white = D65.get_xyz();
+    let dst_white = white.get_xyz();
+    if src_white == dst_white {
+        xyz
+    } else {
+        let transform = chromatic_adaptation_transform::<T, Bradford>(&src_white, &dst_white);
+        let (x, y, z) = transform.transform_vector(xyz.to_tuple());
+        Xyz::new(x, y, z)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::white_point::D50;
+    use angle::Deg;
+    use approx::*;
+
+    #[test]
+    fn test_parse_hex() {
+        assert_eq!(parse_hex("#ff8000"), Ok(Rgb::new(255, 128, 0)));
+        assert_eq!(parse_hex("1A2b3C"), Ok(Rgb::new(0x1a, 0x2b, 0x3c)));
+        assert_eq!(parse_hex("#f80"), Ok(Rgb::new(255, 136, 0)));
+        assert_eq!(parse_hex("000"), Ok(Rgb::new(0, 0, 0)));
+        assert_eq!(parse_hex("#ff80"), Err(ParseHexError::InvalidLength(4)));
+        assert_eq!(parse_hex(""), Err(ParseHexError::InvalidLength(0)));
+        assert_eq!(parse_hex("#ff8000ff"), Err(ParseHexError::InvalidLength(8)));
+        assert_eq!(parse_hex("#ff80g0"), Err(ParseHexError::InvalidDigit('g')));
+        assert_eq!(parse_hex("##fff"), Err(ParseHexError::InvalidDigit('#')));
+    }
+
+    #[test]
+    fn test_parse_hex_alpha() {
+        let orange = Rgb::new(255, 128, 0);
+        assert_eq!(parse_hex_alpha("#ff8000"), Ok(Rgba::new(orange, 255)));
+        assert_eq!(parse_hex_alpha("ff800040"), Ok(Rgba::new(orange, 0x40)));
+        assert_eq!(
+            parse_hex_alpha("#F80c"),
+            Ok(Rgba::new(Rgb::new(255, 136, 0), 204))
+        );
+        assert_eq!(
+            parse_hex_alpha("#f80"),
+            Ok(Rgba::new(Rgb::new(255, 136, 0), 255))
+        );
+        assert_eq!(
+            parse_hex_alpha("#ff800"),
+            Err(ParseHexError::InvalidLength(5))
+        );
+        assert_eq!(
+            parse_hex_alpha("#ff8000ff00"),
+            Err(ParseHexError::InvalidLength(10))
+        );
+        assert_eq!(
+            parse_hex_alpha("#ff8x"),
+            Err(ParseHexError::InvalidDigit('x'))
+        );
+
+        assert_eq!(Rgb::from_hex_str("#ff8000"), Ok(orange));
+        assert_eq!(Rgba::from_hex_str("#ff800080"), Ok(Rgba::new(orange, 128)));
+        assert_eq!(
+            ParseHexError::InvalidLength(5).to_string(),
+            "unsupported number of hex digits 5"
+        );
+    }
+
+    #[test]
+    fn test_format_hex() {
+        let color = Rgb::new(10u8, 171, 255);
+        assert_eq!(color.to_hex_string(), "#0aabff");
+        assert_eq!(format!("{:x}", color), "0aabff");
+        assert_eq!(format!("{:X}", color), "0AABFF");
+        assert_eq!(format!("{:#X}", color), "#0AABFF");
+
+        let rgba = Rgba::new(color, 0);
+        assert_eq!(rgba.to_hex_string(), "#0aabff00");
+        assert_eq!(format!("{:X}", rgba), "0AABFF00");
+        assert_eq!(Rgba::from_hex_str(&rgba.to_hex_string()), Ok(rgba));
+        assert_eq!(Rgb::from_hex_str(&color.to_hex_string()), Ok(color));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for &(r, g, b) in &[
+            (0u8, 0u8, 0u8),
+            (1, 128, 254),
+            (255, 255, 255),
+            (17, 34, 51),
+        ] {
+            let s = format!("#{:02x}{:02x}{:02x}", r, g, b);
+            let rgb: Rgb<f32> = parse_as(&s).unwrap();
+            assert_eq!(rgb.color_cast::<u8>(), Rgb::new(r, g, b));
+        }
+    }
+
+    #[test]
+    fn test_parse_as() {
+        let rgb = parse_as::<Rgb<f32>>("#ff0000").unwrap();
+        assert_relative_eq!(rgb, Rgb::new(1.0, 0.0, 0.0));
+
+        let hsv = parse_as::<Hsv<f64>>("#00ff00").unwrap();
+        assert_relative_eq!(hsv, Hsv::new(Deg(120.0), 1.0, 1.0), epsilon = 1e-6);
+
+        let linear = parse_as::<EncodedColor<Rgb<f64>, LinearEncoding>>("#808080").unwrap();
+        assert_relative_eq!(linear.red(), 0.215861, epsilon = 1e-6);
+
+        let xyz = parse_as::<Xyz<f64>>("#ffffff").unwrap();
+        assert_relative_eq!(xyz, D65.get_xyz(), epsilon = 1e-6);
+
+        let lab = parse_as::<Lab<f64, D65>>("#ff0000").unwrap();
+        assert_relative_eq!(lab, Lab::new(53.2408, 80.0925, 67.2032), epsilon = 1e-3);
+
+        let lab_d50 = parse_as::<Lab<f64, D50>>("#ffffff").unwrap();
+        assert_relative_eq!(lab_d50.L(), 100.0, epsilon = 1e-2);
+        assert_relative_eq!(lab_d50.a(), 0.0, epsilon = 1e-1);
+        assert_relative_eq!(lab_d50.b(), 0.0, epsilon = 1e-1);
+
+        let lch = parse_as::<Lchab<f64, D65>>("#ff0000").unwrap();
+        assert_relative_eq!(lch.chroma(), 104.5518, epsilon = 1e-3);
+
+        assert!(parse_as::<Hsl<f32>>("#zzz").is_err());
+    }
+}
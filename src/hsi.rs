@@ -5,7 +5,7 @@ use crate::channel::{
     PosNormalBoundedChannel, PosNormalChannelScalar,
 };
 use crate::color;
-use crate::color::{Bounded, Color, FromTuple, Invert, Lerp, PolarColor};
+use crate::color::{Bounded, ChannelOrder, Color, FromTuple, Invert, Lerp, PolarColor, PolarLerp};
 use crate::convert::{FromColor, FromHsi, GetHue};
 use crate::encoding::EncodableColor;
 use crate::rgb::Rgb;
@@ -152,6 +152,14 @@ where
     }
 }
 
+impl<T, A> ChannelOrder for Hsi<T, A>
+where
+    T: PosNormalChannelScalar,
+    A: AngularChannelScalar,
+{
+    impl_color_channel_order!({hue, saturation, intensity});
+}
+
 impl<T, A> FromTuple for Hsi<T, A>
 where
     T: PosNormalChannelScalar + num_traits::Float,
@@ -184,6 +192,14 @@ where
     impl_color_lerp_angular!(Hsi<T> {hue, saturation, intensity});
 }
 
+impl<T, A> PolarLerp for Hsi<T, A>
+where
+    T: PosNormalChannelScalar + color::Lerp,
+    A: AngularChannelScalar + color::Lerp,
+{
+    impl_color_lerp_hue_mode!(Hsi<T> {hue, saturation, intensity});
+}
+
 impl<T, A> Bounded for Hsi<T, A>
 where
     T: PosNormalChannelScalar,
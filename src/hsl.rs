@@ -5,7 +5,7 @@ use crate::channel::{
     PosNormalBoundedChannel, PosNormalChannelScalar,
 };
 use crate::color;
-use crate::color::{Color, FromTuple};
+use crate::color::{ChannelOrder, Color, FromTuple};
 use crate::convert;
 use crate::convert::GetChroma;
 use crate::encoding::EncodableColor;
@@ -123,6 +123,14 @@ where
     }
 }
 
+impl<T, A> ChannelOrder for Hsl<T, A>
+where
+    T: PosNormalChannelScalar,
+    A: AngularChannelScalar,
+{
+    impl_color_channel_order!({hue, saturation, lightness});
+}
+
 impl<T, A> FromTuple for Hsl<T, A>
 where
     T: PosNormalChannelScalar,
@@ -164,6 +172,14 @@ where
     impl_color_lerp_angular!(Hsl<T> {hue, saturation, lightness});
 }
 
+impl<T, A> color::PolarLerp for Hsl<T, A>
+where
+    T: PosNormalChannelScalar + color::Lerp,
+    A: AngularChannelScalar + color::Lerp,
+{
+    impl_color_lerp_hue_mode!(Hsl<T> {hue, saturation, lightness});
+}
+
 impl<T, A> color::Bounded for Hsl<T, A>
 where
     T: PosNormalChannelScalar,
@@ -6,7 +6,7 @@ use crate::channel::{
     PosNormalChannelScalar,
 };
 use crate::color;
-use crate::color::{Bounded, Color, FromTuple, Invert, Lerp, PolarColor};
+use crate::color::{Bounded, ChannelOrder, Color, FromTuple, Invert, Lerp, PolarColor, PolarLerp};
 use crate::convert;
 use crate::encoding::EncodableColor;
 use crate::rgb;
@@ -131,6 +131,14 @@ where
     }
 }
 
+impl<T, A> ChannelOrder for Hsv<T, A>
+where
+    T: PosNormalChannelScalar,
+    A: AngularChannelScalar,
+{
+    impl_color_channel_order!({hue, saturation, value});
+}
+
 impl<T, A> FromTuple for Hsv<T, A>
 where
     T: PosNormalChannelScalar,
@@ -163,6 +171,14 @@ where
     impl_color_lerp_angular!(Hsv<T> {hue, saturation, value});
 }
 
+impl<T, A> PolarLerp for Hsv<T, A>
+where
+    T: PosNormalChannelScalar + color::Lerp,
+    A: AngularChannelScalar + color::Lerp,
+{
+    impl_color_lerp_hue_mode!(Hsv<T> {hue, saturation, value});
+}
+
 impl<T, A> Bounded for Hsv<T, A>
 where
     T: PosNormalChannelScalar,
@@ -296,6 +312,7 @@ where
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::color::HueMode;
     use crate::convert::*;
     use crate::rgb;
     use angle::*;
@@ -346,6 +363,34 @@ mod test {
         assert_ulps_eq!(c3.lerp(&c4, 0.5).normalize(), Hsv::new(Deg(30.0), 0.5, 0.5));
     }
 
+    #[test]
+    fn test_lerp_with_hue_mode() {
+        let c1 = Hsv::new(Deg(320.0), 0.0, 1.0);
+        let c2 = Hsv::new(Deg(100.0), 1.0, 0.0);
+        let mid = |mode| c1.lerp_with_hue_mode(&c2, 0.5, mode);
+        assert_ulps_eq!(mid(HueMode::Shorter), c1.lerp(&c2, 0.5).normalize());
+        assert_ulps_eq!(mid(HueMode::Shorter), Hsv::new(Deg(30.0), 0.5, 0.5));
+        assert_ulps_eq!(mid(HueMode::Longer), Hsv::new(Deg(210.0), 0.5, 0.5));
+        assert_ulps_eq!(mid(HueMode::Increasing), Hsv::new(Deg(30.0), 0.5, 0.5));
+        assert_ulps_eq!(mid(HueMode::Decreasing), Hsv::new(Deg(210.0), 0.5, 0.5));
+        assert_ulps_eq!(
+            c2.lerp_with_hue_mode(&c1, 0.25, HueMode::Decreasing),
+            Hsv::new(Deg(65.0), 0.75, 0.25)
+        );
+        assert_ulps_eq!(
+            c1.lerp_with_hue_mode(&c2, 1.0, HueMode::Longer).normalize(),
+            c2
+        );
+
+        let c3 = Hsv::new(Rad(1.0f32), 0.5f32, 0.5);
+        assert_ulps_eq!(
+            c3.lerp_with_hue_mode(&c3, 0.5, HueMode::Longer),
+            Hsv::new(Rad(1.0 + consts::PI), 0.5, 0.5)
+        );
+        assert_ulps_eq!(c3.lerp_with_hue_mode(&c3, 0.5, HueMode::Shorter), c3);
+        assert_ulps_eq!(c3.lerp_with_hue_mode(&c3, 0.5, HueMode::Increasing), c3);
+    }
+
     #[test]
     fn test_normalize() {
         let c1 = Hsv::new(Deg(-120.0), 0.25, 0.75);
@@ -5,7 +5,7 @@ use crate::channel::{
     PosNormalBoundedChannel, PosNormalChannelScalar,
 };
 use crate::color;
-use crate::color::{Color, FromTuple};
+use crate::color::{ChannelOrder, Color, FromTuple};
 use crate::convert;
 use crate::encoding::EncodableColor;
 use crate::hsv;
@@ -38,14 +38,14 @@ pub struct Hwb<T, A = Deg<T>> {
     blackness: PosNormalBoundedChannel<T>,
 }
 
-/// Combination of traits used to bound `T` in `Hwb`
+/// Combination of traits used to bound `T` in the `Hwb` operations needing floating point math
 pub trait HwbBoundedChannelTraits: PosNormalChannelScalar + num_traits::Float {}
 
 impl<T> HwbBoundedChannelTraits for T where T: PosNormalChannelScalar + num_traits::Float {}
 
 impl<T, A> Hwb<T, A>
 where
-    T: HwbBoundedChannelTraits,
+    T: PosNormalChannelScalar,
     A: AngularChannelScalar,
 {
     /// Construct a `Hwb` instance from hue, whiteness and blackness
@@ -133,7 +133,7 @@ where
 
 impl<T, A> Color for Hwb<T, A>
 where
-    T: HwbBoundedChannelTraits,
+    T: PosNormalChannelScalar,
     A: AngularChannelScalar,
 {
     type Tag = HwbTag;
@@ -147,9 +147,17 @@ where
     }
 }
 
+impl<T, A> ChannelOrder for Hwb<T, A>
+where
+    T: PosNormalChannelScalar,
+    A: AngularChannelScalar,
+{
+    impl_color_channel_order!({hue, whiteness, blackness});
+}
+
 impl<T, A> FromTuple for Hwb<T, A>
 where
-    T: HwbBoundedChannelTraits,
+    T: PosNormalChannelScalar,
     A: AngularChannelScalar,
 {
     fn from_tuple(values: Self::ChannelsTuple) -> Self {
@@ -159,7 +167,7 @@ where
 
 impl<T, A> color::PolarColor for Hwb<T, A>
 where
-    T: HwbBoundedChannelTraits,
+    T: PosNormalChannelScalar,
     A: AngularChannelScalar,
 {
     type Angular = A;
@@ -168,7 +176,7 @@ where
 
 impl<T, A> color::Invert for Hwb<T, A>
 where
-    T: HwbBoundedChannelTraits,
+    T: PosNormalChannelScalar,
     A: AngularChannelScalar,
 {
     impl_color_invert!(Hwb {
@@ -180,7 +188,7 @@ where
 
 impl<T, A> color::Lerp for Hwb<T, A>
 where
-    T: HwbBoundedChannelTraits + color::Lerp,
+    T: PosNormalChannelScalar + color::Lerp,
     A: AngularChannelScalar + color::Lerp,
 {
     type Position = A::Position;
@@ -188,9 +196,17 @@ where
     impl_color_lerp_angular!(Hwb<T> {hue, whiteness, blackness});
 }
 
+impl<T, A> color::PolarLerp for Hwb<T, A>
+where
+    T: PosNormalChannelScalar + color::Lerp,
+    A: AngularChannelScalar + color::Lerp,
+{
+    impl_color_lerp_hue_mode!(Hwb<T> {hue, whiteness, blackness});
+}
+
 impl<T, A> color::Bounded for Hwb<T, A>
 where
-    T: HwbBoundedChannelTraits,
+    T: PosNormalChannelScalar,
     A: AngularChannelScalar,
 {
     impl_color_bounded!(Hwb {
@@ -210,7 +226,7 @@ where
 #[cfg(feature = "approx")]
 impl<T, A> approx::AbsDiffEq for Hwb<T, A>
 where
-    T: HwbBoundedChannelTraits + approx::AbsDiffEq<Epsilon = A::Epsilon>,
+    T: PosNormalChannelScalar + approx::AbsDiffEq<Epsilon = A::Epsilon>,
     A: AngularChannelScalar + approx::AbsDiffEq,
     A::Epsilon: Clone + num_traits::Float,
 {
@@ -219,7 +235,7 @@ where
 #[cfg(feature = "approx")]
 impl<T, A> approx::RelativeEq for Hwb<T, A>
 where
-    T: HwbBoundedChannelTraits + approx::RelativeEq<Epsilon = A::Epsilon>,
+    T: PosNormalChannelScalar + approx::RelativeEq<Epsilon = A::Epsilon>,
     A: AngularChannelScalar + approx::RelativeEq,
     A::Epsilon: Clone + num_traits::Float,
 {
@@ -228,7 +244,7 @@ where
 #[cfg(feature = "approx")]
 impl<T, A> approx::UlpsEq for Hwb<T, A>
 where
-    T: HwbBoundedChannelTraits + approx::UlpsEq<Epsilon = A::Epsilon>,
+    T: PosNormalChannelScalar + approx::UlpsEq<Epsilon = A::Epsilon>,
     A: AngularChannelScalar + approx::UlpsEq,
     A::Epsilon: Clone + num_traits::Float,
 {
@@ -237,7 +253,7 @@ where
 
 impl<T, A> Default for Hwb<T, A>
 where
-    T: HwbBoundedChannelTraits + num_traits::Zero,
+    T: PosNormalChannelScalar + num_traits::Zero,
     A: AngularChannelScalar + num_traits::Zero,
 {
     impl_color_default!(Hwb {
@@ -249,7 +265,7 @@ where
 
 impl<T, A> fmt::Display for Hwb<T, A>
 where
-    T: HwbBoundedChannelTraits + fmt::Display,
+    T: PosNormalChannelScalar + fmt::Display,
     A: AngularChannelScalar + fmt::Display,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -263,7 +279,7 @@ where
 
 impl<T, A> convert::GetHue for Hwb<T, A>
 where
-    T: HwbBoundedChannelTraits,
+    T: PosNormalChannelScalar,
     A: AngularChannelScalar,
 {
     impl_color_get_hue_angular!(Hwb);
@@ -532,5 +548,16 @@ mod test {
             Hwb::new(Turns(0.3333), 0.5, 0.3),
             epsilon = 1e-3
         );
+
+        let c2 = Hwb::new(Deg(120.0_f32), 32768u16, 65535);
+        assert_eq!(
+            c2.color_cast::<u8, Deg<f32>>(),
+            Hwb::new(Deg(120.0), 128u8, 255)
+        );
+        assert_relative_eq!(
+            c2.color_cast::<f32, Deg<f32>>(),
+            Hwb::new(Deg(120.0), 0.500008, 1.0),
+            epsilon = 1e-6
+        );
     }
 }
@@ -0,0 +1,633 @@
+//! Import of matrix/TRC ICC profiles as color spaces
+//!
+//! ICC profiles are the standard way for images and devices to describe their colors. The simplest
+//! and by far most common kind of RGB profile, the matrix/TRC profile, describes an RGB color
+//! space by three colorants in XYZ and a tone reproduction curve for each channel. These map
+//! directly onto an [`EncodedColorSpace`](../color_space/struct.EncodedColorSpace.html).
+//!
+//! Only the data needed to build the color space is read. Profiles using lookup tables (`A2B0`
+//! and friends) instead of colorants are not supported.
+//!
+//! The colorants of a profile are relative to the profile connection space, which always uses
+//! a D50 white. The color spaces built from profiles therefore have a D50 white point, and
+//! produce XYZ values relative to D50.
+//!
+//! This module is only available with the `icc` feature.
+
+use crate::channel::{FreeChannelScalar, PosNormalChannelScalar};
+use crate::color_space::{EncodedColorSpace, RgbPrimary};
+use crate::encoding::{ChannelDecoder, ChannelEncoder, ColorEncoding};
+use crate::xyz::Xyz;
+use std::error::Error;
+use std::fmt;
+
+/// An error produced while reading an ICC profile
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IccError {
+    /// The data ended before the end of the structure being read
+    UnexpectedEnd,
+    /// The data does not contain the `acsp` profile signature
+    InvalidSignature,
+    /// The profile does not describe RGB colors with an XYZ connection space
+    UnsupportedProfile,
+    /// A tag required to build a color space is missing
+    MissingTag([u8; 4]),
+    /// A tag has a type or contents that can't be used
+    InvalidTag([u8; 4]),
+    /// The three channels use different curves, which can't be represented by a single encoding
+    MismatchedCurves,
+}
+
+impl fmt::Display for IccError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            IccError::UnexpectedEnd => write!(f, "unexpected end of ICC profile data"),
+            IccError::InvalidSignature => write!(f, "data is not an ICC profile"),
+            IccError::UnsupportedProfile => {
+                write!(
+                    f,
+                    "ICC profile is not an RGB profile with an XYZ connection space"
+                )
+            }
+            IccError::MissingTag(sig) => {
+                write!(
+                    f,
+                    "ICC profile is missing tag '{}'",
+                    String::from_utf8_lossy(&sig)
+                )
+            }
+            IccError::InvalidTag(sig) => {
+                write!(
+                    f,
+                    "ICC profile tag '{}' is invalid",
+                    String::from_utf8_lossy(&sig)
+                )
+            }
+            IccError::MismatchedCurves => {
+                write!(f, "ICC profile uses different curves for each channel")
+            }
+        }
+    }
+}
+
+impl Error for IccError {}
+
+/// A tone reproduction curve read from an ICC profile
+///
+/// The curve maps encoded device values to linear values, and is used as the encoding of color
+/// spaces built from a profile. Like the other encodings, negative values are mapped symmetrically.
+#[derive(Clone, Debug, PartialEq)]
+pub enum IccCurve {
+    /// No encoding, the device values are linear
+    Identity,
+    /// A pure power function with the given exponent
+    Gamma(f64),
+    /// A sampled curve, with samples evenly spaced over [0, 1]
+    Table(Vec<f64>),
+    /// A parametric curve in the most general ICC form
+    ///
+    /// The curve is $`(aX + b)^g + e`$ for $`X \geq d`$ and $`cX + f`$ otherwise. The simpler
+    /// parametric curve types are converted into this form.
+    Parametric {
+        /// The exponent
+        g: f64,
+        /// The scale of the power segment
+        a: f64,
+        /// The offset of the power segment
+        b: f64,
+        /// The slope of the linear segment
+        c: f64,
+        /// The input at which the power segment begins
+        d: f64,
+        /// The offset added to the power segment
+        e: f64,
+        /// The offset of the linear segment
+        f: f64,
+    },
+}
+
+impl IccCurve {
+    fn decode(&self, x: f64) -> f64 {
+        match *self {
+            IccCurve::Identity => x,
+            IccCurve::Gamma(g) => x.powf(g),
+            IccCurve::Table(ref table) => match table.len() {
+                0 => x,
+                1 => table[0],
+                len => {
+                    let pos = x.clamp(0.0, 1.0) * (len - 1) as f64;
+                    let index = (pos.floor() as usize).min(len - 2);
+                    let t = pos - index as f64;
+                    table[index] + (table[index + 1] - table[index]) * t
+                }
+            },
+            IccCurve::Parametric {
+                g,
+                a,
+                b,
+                c,
+                d,
+                e,
+                f,
+            } => {
+                if x >= d {
+                    (a * x + b).max(0.0).powf(g) + e
+                } else {
+                    c * x + f
+                }
+            }
+        }
+    }
+
+    fn encode(&self, y: f64) -> f64 {
+        match *self {
+            IccCurve::Identity => y,
+            IccCurve::Gamma(g) => y.powf(1.0 / g),
+            IccCurve::Table(ref table) => match table.len() {
+                0 => y,
+                1 => 0.0,
+                len => {
+                    let upper = table.iter().position(|&v| v >= y).unwrap_or(len - 1).max(1);
+                    let (lo, hi) = (table[upper - 1], table[upper]);
+                    let t = if hi > lo { (y - lo) / (hi - lo) } else { 0.0 };
+                    ((upper - 1) as f64 + t.clamp(0.0, 1.0)) / (len - 1) as f64
+                }
+            },
+            IccCurve::Parametric {
+                g,
+                a,
+                b,
+                c,
+                d,
+                e,
+                f,
+            } => {
+                let threshold = (a * d + b).max(0.0).powf(g) + e;
+                if y >= threshold {
+                    ((y - e).max(0.0).powf(1.0 / g) - b) / a
+                } else if c != 0.0 {
+                    (y - f) / c
+                } else {
+                    d
+                }
+            }
+        }
+    }
+}
+
+impl ChannelDecoder for IccCurve {
+    fn decode_channel<T>(&self, val: T) -> T
+    where
+        T: num_traits::Float,
+    {
+        let x: f64 = num_traits::cast(val.abs()).unwrap();
+        let out: T = num_traits::cast(self.decode(x)).unwrap();
+        if val < T::zero() {
+            -out
+        } else {
+            out
+        }
+    }
+}
+
+impl ChannelEncoder for IccCurve {
+    fn encode_channel<T>(&self, val: T) -> T
+    where
+        T: num_traits::Float,
+    {
+        let y: f64 = num_traits::cast(val.abs()).unwrap();
+        let out: T = num_traits::cast(self.encode(y)).unwrap();
+        if val < T::zero() {
+            -out
+        } else {
+            out
+        }
+    }
+}
+
+impl ColorEncoding for IccCurve {}
+
+impl fmt::Display for IccCurve {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            IccCurve::Identity => write!(f, "ICC Identity Curve"),
+            IccCurve::Gamma(g) => write!(f, "ICC Gamma Curve ({})", g),
+            IccCurve::Table(ref table) => write!(f, "ICC Table Curve ({} entries)", table.len()),
+            IccCurve::Parametric { .. } => write!(f, "ICC Parametric Curve"),
+        }
+    }
+}
+
+/// The color space data of a matrix/TRC ICC profile
+#[derive(Clone, Debug, PartialEq)]
+pub struct IccProfile {
+    version: (u8, u8),
+    red_colorant: Xyz<f64>,
+    green_colorant: Xyz<f64>,
+    blue_colorant: Xyz<f64>,
+    media_white_point: Option<Xyz<f64>>,
+    red_curve: IccCurve,
+    green_curve: IccCurve,
+    blue_curve: IccCurve,
+}
+
+impl IccProfile {
+    /// Read a profile from the contents of an ICC file
+    pub fn parse(data: &[u8]) -> Result<Self, IccError> {
+        if data.len() < 132 {
+            return Err(IccError::UnexpectedEnd);
+        }
+        if &data[36..40] != b"acsp" {
+            return Err(IccError::InvalidSignature);
+        }
+        if &data[16..20] != b"RGB " || &data[20..24] != b"XYZ " {
+            return Err(IccError::UnsupportedProfile);
+        }
+        let version = (data[8], data[9] >> 4);
+
+        let tag_count = read_u32(data, 128)? as usize;
+        let table_end = tag_count
+            .checked_mul(12)
+            .and_then(|len| len.checked_add(132))
+            .ok_or(IccError::UnexpectedEnd)?;
+        if table_end > data.len() {
+            return Err(IccError::UnexpectedEnd);
+        }
+        let mut tags = Vec::with_capacity(tag_count);
+        for i in 0..tag_count {
+            let entry = 132 + i * 12;
+            let sig = read_sig(data, entry)?;
+            let offset = read_u32(data, entry + 4)? as usize;
+            let size = read_u32(data, entry + 8)? as usize;
+            let end = offset.checked_add(size).ok_or(IccError::UnexpectedEnd)?;
+            let tag = data.get(offset..end).ok_or(IccError::UnexpectedEnd)?;
+            tags.push((sig, tag));
+        }
+        let find = |sig: &[u8; 4]| {
+            tags.iter()
+                .find(|&&(s, _)| s == *sig)
+                .map(|&(_, tag)| tag)
+                .ok_or(IccError::MissingTag(*sig))
+        };
+
+        Ok(IccProfile {
+            version,
+            red_colorant: parse_xyz(b"rXYZ", find(b"rXYZ")?)?,
+            green_colorant: parse_xyz(b"gXYZ", find(b"gXYZ")?)?,
+            blue_colorant: parse_xyz(b"bXYZ", find(b"bXYZ")?)?,
+            media_white_point: match find(b"wtpt") {
+                Ok(tag) => Some(parse_xyz(b"wtpt", tag)?),
+                Err(_) => None,
+            },
+            red_curve: parse_curve(b"rTRC", find(b"rTRC")?)?,
+            green_curve: parse_curve(b"gTRC", find(b"gTRC")?)?,
+            blue_curve: parse_curve(b"bTRC", find(b"bTRC")?)?,
+        })
+    }
+
+    /// Returns the major and minor version of the profile
+    pub fn version(&self) -> (u8, u8) {
+        self.version
+    }
+    /// Returns the XYZ value of the red colorant, relative to D50
+    pub fn red_colorant(&self) -> &Xyz<f64> {
+        &self.red_colorant
+    }
+    /// Returns the XYZ value of the green colorant, relative to D50
+    pub fn green_colorant(&self) -> &Xyz<f64> {
+        &self.green_colorant
+    }
+    /// Returns the XYZ value of the blue colorant, relative to D50
+    pub fn blue_colorant(&self) -> &Xyz<f64> {
+        &self.blue_colorant
+    }
+    /// Returns the media white point of the profile, if it has one
+    pub fn media_white_point(&self) -> Option<&Xyz<f64>> {
+        self.media_white_point.as_ref()
+    }
+    /// Returns the red, green and blue tone reproduction curves
+    pub fn curves(&self) -> (&IccCurve, &IccCurve, &IccCurve) {
+        (&self.red_curve, &self.green_curve, &self.blue_curve)
+    }
+
+    /// Build a color space from the colorants and curves of the profile
+    ///
+    /// Fails with `MismatchedCurves` if the channels don't share a single curve.
+    pub fn color_space<T>(&self) -> Result<EncodedColorSpace<T, IccCurve>, IccError>
+    where
+        T: num_traits::Float + FreeChannelScalar + PosNormalChannelScalar,
+    {
+        if self.red_curve != self.green_curve || self.red_curve != self.blue_curve {
+            return Err(IccError::MismatchedCurves);
+        }
+        let primary = |xyz: &Xyz<f64>| {
+            let sum = xyz.x() + xyz.y() + xyz.z();
+            RgbPrimary::new(
+                num_traits::cast(xyz.x() / sum).unwrap(),
+                num_traits::cast(xyz.y() / sum).unwrap(),
+            )
+        };
+        let white = |f: fn(&Xyz<f64>) -> f64| -> T {
+            num_traits::cast(
+                f(&self.red_colorant) + f(&self.green_colorant) + f(&self.blue_colorant),
+            )
+            .unwrap()
+        };
+
+        Ok(EncodedColorSpace::new(
+            primary(&self.red_colorant),
+            primary(&self.green_colorant),
+            primary(&self.blue_colorant),
+            Xyz::new(white(Xyz::x), white(Xyz::y), white(Xyz::z)),
+            self.red_curve.clone(),
+        ))
+    }
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16, IccError> {
+    let bytes = data
+        .get(offset..offset + 2)
+        .ok_or(IccError::UnexpectedEnd)?;
+    Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32, IccError> {
+    let bytes = data
+        .get(offset..offset + 4)
+        .ok_or(IccError::UnexpectedEnd)?;
+    Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+fn read_s15_fixed16(data: &[u8], offset: usize) -> Result<f64, IccError> {
+    Ok(f64::from(read_u32(data, offset)? as i32) / 65536.0)
+}
+
+fn read_sig(data: &[u8], offset: usize) -> Result<[u8; 4], IccError> {
+    let bytes = data
+        .get(offset..offset + 4)
+        .ok_or(IccError::UnexpectedEnd)?;
+    Ok([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+fn parse_xyz(sig: &[u8; 4], tag: &[u8]) -> Result<Xyz<f64>, IccError> {
+    if tag.len() < 20 || &tag[0..4] != b"XYZ " {
+        return Err(IccError::InvalidTag(*sig));
+    }
+    Ok(Xyz::new(
+        read_s15_fixed16(tag, 8)?,
+        read_s15_fixed16(tag, 12)?,
+        read_s15_fixed16(tag, 16)?,
+    ))
+}
+
+fn parse_curve(sig: &[u8; 4], tag: &[u8]) -> Result<IccCurve, IccError> {
+    let invalid = |_| IccError::InvalidTag(*sig);
+    match tag.get(0..4) {
+        Some(b"curv") => {
+            let count = read_u32(tag, 8).map_err(invalid)? as usize;
+            match count {
+                0 => Ok(IccCurve::Identity),
+                1 => Ok(IccCurve::Gamma(
+                    f64::from(read_u16(tag, 12).map_err(invalid)?) / 256.0,
+                )),
+                _ => (0..count)
+                    .map(|i| read_u16(tag, 12 + i * 2).map(|v| f64::from(v) / 65535.0))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(IccCurve::Table)
+                    .map_err(invalid),
+            }
+        }
+        Some(b"para") => {
+            let function = read_u16(tag, 8).map_err(invalid)?;
+            let num_params = match function {
+                0 => 1,
+                1 => 3,
+                2 => 4,
+                3 => 5,
+                4 => 7,
+                _ => return Err(IccError::InvalidTag(*sig)),
+            };
+            let mut p = [0.0; 7];
+            for (i, param) in p.iter_mut().enumerate().take(num_params) {
+                *param = read_s15_fixed16(tag, 12 + i * 4).map_err(invalid)?;
+            }
+            let (g, a, b, c, d, e, f) = (p[0], p[1], p[2], p[3], p[4], p[5], p[6]);
+            Ok(match function {
+                0 => IccCurve::Gamma(g),
+                1 => IccCurve::Parametric {
+                    g,
+                    a,
+                    b,
+                    c: 0.0,
+                    d: -b / a,
+                    e: 0.0,
+                    f: 0.0,
+                },
+                2 => IccCurve::Parametric {
+                    g,
+                    a,
+                    b,
+                    c: 0.0,
+                    d: -b / a,
+                    e: c,
+                    f: c,
+                },
+                3 => IccCurve::Parametric {
+                    g,
+                    a,
+                    b,
+                    c,
+                    d,
+                    e: 0.0,
+                    f: 0.0,
+                },
+                _ => IccCurve::Parametric {
+                    g,
+                    a,
+                    b,
+                    c,
+                    d,
+                    e,
+                    f,
+                },
+            })
+        }
+        _ => Err(IccError::InvalidTag(*sig)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::color_space::ColorSpace;
+    use crate::encoding::SrgbEncoding;
+    use approx::*;
+
+    fn s15_fixed16(v: f64) -> [u8; 4] {
+        ((v * 65536.0).round() as i32).to_be_bytes()
+    }
+
+    fn xyz_tag(x: f64, y: f64, z: f64) -> Vec<u8> {
+        let mut tag = b"XYZ \0\0\0\0".to_vec();
+        for v in [x, y, z].iter() {
+            tag.extend_from_slice(&s15_fixed16(*v));
+        }
+        tag
+    }
+
+    fn build_profile(tags: &[(&[u8; 4], Vec<u8>)]) -> Vec<u8> {
+        let mut data = vec![0u8; 128];
+        data[8] = 4;
+        data[9] = 0x30;
+        data[12..16].copy_from_slice(b"mntr");
+        data[16..20].copy_from_slice(b"RGB ");
+        data[20..24].copy_from_slice(b"XYZ ");
+        data[36..40].copy_from_slice(b"acsp");
+        data.extend_from_slice(&(tags.len() as u32).to_be_bytes());
+
+        let mut offset = 132 + tags.len() * 12;
+        let mut body = Vec::new();
+        for &(sig, ref tag) in tags.iter() {
+            data.extend_from_slice(sig);
+            data.extend_from_slice(&(offset as u32).to_be_bytes());
+            data.extend_from_slice(&(tag.len() as u32).to_be_bytes());
+            body.extend_from_slice(tag);
+            offset += tag.len();
+        }
+        data.extend_from_slice(&body);
+        let len = data.len() as u32;
+        data[0..4].copy_from_slice(&len.to_be_bytes());
+        data
+    }
+
+    fn srgb_profile(curve: Vec<u8>) -> Vec<u8> {
+        build_profile(&[
+            (b"wtpt", xyz_tag(0.9505, 1.0, 1.0891)),
+            (b"rXYZ", xyz_tag(0.436_066, 0.222_488, 0.013_916)),
+            (b"gXYZ", xyz_tag(0.385_147, 0.716_873, 0.097_045)),
+            (b"bXYZ", xyz_tag(0.143_066, 0.060_608, 0.714_081)),
+            (b"rTRC", curve.clone()),
+            (b"gTRC", curve.clone()),
+            (b"bTRC", curve),
+        ])
+    }
+
+    fn srgb_para() -> Vec<u8> {
+        let mut tag = b"para\0\0\0\0\0\x03\0\0".to_vec();
+        for v in [2.4, 1.0 / 1.055, 0.055 / 1.055, 1.0 / 12.92, 0.04045].iter() {
+            tag.extend_from_slice(&s15_fixed16(*v));
+        }
+        tag
+    }
+
+    #[test]
+    fn test_parse() {
+        let profile = IccProfile::parse(&srgb_profile(srgb_para())).unwrap();
+        assert_eq!(profile.version(), (4, 3));
+        assert_relative_eq!(
+            *profile.red_colorant(),
+            Xyz::new(0.436_066, 0.222_488, 0.013_916),
+            epsilon = 1e-4
+        );
+        assert_relative_eq!(
+            *profile.media_white_point().unwrap(),
+            Xyz::new(0.9505, 1.0, 1.0891),
+            epsilon = 1e-4
+        );
+        let (red, green, blue) = profile.curves();
+        assert_eq!(red, green);
+        assert_eq!(green, blue);
+    }
+
+    #[test]
+    fn test_color_space() {
+        let profile = IccProfile::parse(&srgb_profile(srgb_para())).unwrap();
+        let space = profile.color_space::<f64>().unwrap();
+
+        let (x, y, z) = space.apply_transform((1.0, 0.0, 0.0));
+        assert_relative_eq!(
+            Xyz::new(x, y, z),
+            Xyz::new(0.436_066, 0.222_488, 0.013_916),
+            epsilon = 1e-4
+        );
+        assert_relative_eq!(
+            space.white_point(),
+            Xyz::new(0.9642, 1.0, 0.8249),
+            epsilon = 1e-3
+        );
+
+        let curve = space.encoding();
+        for &v in [0.0, 0.02, 0.2, 0.5, 0.8, 1.0].iter() {
+            assert_relative_eq!(
+                curve.decode_channel(v),
+                SrgbEncoding.decode_channel(v),
+                epsilon = 1e-4
+            );
+            assert_relative_eq!(
+                curve.encode_channel(curve.decode_channel(v)),
+                v,
+                epsilon = 1e-6
+            );
+        }
+    }
+
+    #[test]
+    fn test_curves() {
+        let gamma = parse_curve(b"rTRC", b"curv\0\0\0\0\0\0\0\x01\x02\x33").unwrap();
+        assert_eq!(gamma, IccCurve::Gamma(2.19921875));
+        assert_relative_eq!(gamma.decode_channel(0.5), 0.5f64.powf(2.19921875));
+        assert_relative_eq!(gamma.decode_channel(-0.5), -(0.5f64.powf(2.19921875)));
+
+        let identity = parse_curve(b"rTRC", b"curv\0\0\0\0\0\0\0\0").unwrap();
+        assert_eq!(identity, IccCurve::Identity);
+
+        let table = parse_curve(b"rTRC", b"curv\0\0\0\0\0\0\0\x03\0\0\x40\0\xff\xff").unwrap();
+        assert_relative_eq!(table.decode_channel(0.25), 0.125, epsilon = 1e-4);
+        assert_relative_eq!(table.decode_channel(0.75), 0.625, epsilon = 1e-4);
+        assert_relative_eq!(table.encode_channel(0.625), 0.75, epsilon = 1e-4);
+
+        assert_eq!(
+            parse_curve(b"rTRC", b"mft2\0\0\0\0"),
+            Err(IccError::InvalidTag(*b"rTRC"))
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(IccProfile::parse(&[0u8; 64]), Err(IccError::UnexpectedEnd));
+        assert_eq!(
+            IccProfile::parse(&[0u8; 200]),
+            Err(IccError::InvalidSignature)
+        );
+
+        let missing = build_profile(&[(b"rXYZ", xyz_tag(0.4, 0.2, 0.0))]);
+        assert_eq!(
+            IccProfile::parse(&missing),
+            Err(IccError::MissingTag(*b"gXYZ"))
+        );
+
+        let mut truncated = srgb_profile(srgb_para());
+        truncated[128..132].copy_from_slice(&u32::MAX.to_be_bytes());
+        assert_eq!(IccProfile::parse(&truncated), Err(IccError::UnexpectedEnd));
+        truncated.truncate(132 + 3 * 12);
+        truncated[128..132].copy_from_slice(&7u32.to_be_bytes());
+        assert_eq!(IccProfile::parse(&truncated), Err(IccError::UnexpectedEnd));
+
+        let mut mismatched = srgb_profile(srgb_para());
+        let profile = {
+            let mut p = IccProfile::parse(&mismatched).unwrap();
+            p.blue_curve = IccCurve::Gamma(2.2);
+            p
+        };
+        assert_eq!(
+            profile.color_space::<f64>(),
+            Err(IccError::MismatchedCurves)
+        );
+
+        mismatched[16..20].copy_from_slice(b"CMYK");
+        assert_eq!(
+            IccProfile::parse(&mismatched),
+            Err(IccError::UnsupportedProfile)
+        );
+    }
+}
@@ -118,6 +118,38 @@ macro_rules! impl_color_bounded {
     }
 }
 
+macro_rules! impl_color_bounded_arithmetic {
+    ($name: ident {$($fields: ident),*}, phantom={$($phantom:ident),*}) => {
+        fn saturating_add(&self, rhs: &Self) -> Self {
+            $name {
+                $($fields: self.$fields.clone().saturating_add(rhs.$fields.clone()),)*
+                $($phantom: PhantomData),*
+            }
+        }
+        fn saturating_sub(&self, rhs: &Self) -> Self {
+            $name {
+                $($fields: self.$fields.clone().saturating_sub(rhs.$fields.clone()),)*
+                $($phantom: PhantomData),*
+            }
+        }
+        fn checked_add(&self, rhs: &Self) -> Option<Self> {
+            Some($name {
+                $($fields: self.$fields.clone().checked_add(rhs.$fields.clone())?,)*
+                $($phantom: PhantomData),*
+            })
+        }
+        fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+            Some($name {
+                $($fields: self.$fields.clone().checked_sub(rhs.$fields.clone())?,)*
+                $($phantom: PhantomData),*
+            })
+        }
+    };
+    ($name: ident {$($fields: ident),*}) => {
+        impl_color_bounded_arithmetic!($name {$($fields),*}, phantom={});
+    };
+}
+
 macro_rules! impl_color_lerp_square {
     ($name:ident {$($fields:ident),*}, copy={$($copy:ident),*}, phantom={$($phantom:ident),*}) => {
         fn lerp(&self, right: &Self, pos: Self::Position) -> Self {
@@ -156,6 +188,26 @@ macro_rules! impl_color_lerp_angular {
     };
 }
 
+macro_rules! impl_color_lerp_hue_mode {
+    ($name: ident<$T: ident> {$ang_field: ident, $($fields: ident),*}) => {
+        impl_color_lerp_hue_mode!($name<$T> {$ang_field, $($fields),*}, copy={});
+    };
+    ($name: ident<$T: ident> {$ang_field: ident, $($fields: ident),*}, copy={$($copy:ident),*}) => {
+
+        fn lerp_with_hue_mode(&self, right: &Self, pos: Self::Position,
+                              mode: $crate::color::HueMode) -> Self {
+            let tpos: $T::Position = num_traits::cast(pos).unwrap();
+            $name {
+                $ang_field: self.$ang_field.lerp_with_hue_mode(&right.$ang_field,
+                    num_traits::cast(pos).unwrap(), mode),
+                $($fields: $crate::color::Lerp::lerp(&self.$fields, &right.$fields,
+                    tpos.clone())),*,
+                $($copy: self.$copy.clone()),*
+            }
+        }
+    };
+}
+
 macro_rules! impl_color_default {
     ($name:ident {$($fields:ident:$ChanType:ident),*}, phantom={$($phantom:ident),*}) => {
         fn default() -> Self {
@@ -234,6 +286,95 @@ macro_rules! impl_color_homogeneous_color_square {
     };
 }
 
+macro_rules! impl_color_channel_order {
+    ({$($fields:ident),*}) => {
+        fn channel_name(index: usize) -> Option<&'static str> {
+            [$(stringify!($fields)),*].get(index).copied()
+        }
+    };
+}
+
+macro_rules! impl_color_channel_access_square {
+    ($T:ident {$($fields:ident),*}) => {
+        fn channel(&self, index: usize) -> Option<&$T> {
+            [$(&self.$fields.0),*].get(index).copied()
+        }
+        fn channel_mut(&mut self, index: usize) -> Option<&mut $T> {
+            IntoIterator::into_iter([$(&mut self.$fields.0),*]).nth(index)
+        }
+    };
+}
+
+macro_rules! impl_color_elementwise_ops {
+    ($name:ident<$($param:ident),*> {$($fields:ident),*} where { $($bounds:tt)* }) => {
+        impl_color_elementwise_ops!(@op $name<$($param),*> {$($fields),*},
+            Add add AddAssign add_assign +, where { $($bounds)* });
+        impl_color_elementwise_ops!(@op $name<$($param),*> {$($fields),*},
+            Sub sub SubAssign sub_assign -, where { $($bounds)* });
+        impl_color_elementwise_ops!(@op $name<$($param),*> {$($fields),*},
+            Mul mul MulAssign mul_assign *, where { $($bounds)* });
+        impl_color_elementwise_ops!(@op $name<$($param),*> {$($fields),*},
+            Div div DivAssign div_assign /, where { $($bounds)* });
+    };
+    (@op $name:ident<$($param:ident),*> {$($fields:ident),*},
+        $op:ident $method:ident $op_assign:ident $method_assign:ident $sym:tt,
+        where { $($bounds:tt)* }) =>
+    {
+        impl<$($param),*> std::ops::$op for $name<$($param),*>
+        where
+            $($bounds)*
+        {
+            type Output = Self;
+            fn $method(mut self, rhs: Self) -> Self {
+                std::ops::$op_assign::$method_assign(&mut self, rhs);
+                self
+            }
+        }
+
+        impl<$($param),*> std::ops::$op_assign for $name<$($param),*>
+        where
+            $($bounds)*
+        {
+            fn $method_assign(&mut self, rhs: Self) {
+                $(self.$fields.0 = self.$fields.0 $sym rhs.$fields.0;)*
+            }
+        }
+    };
+}
+
+macro_rules! impl_color_scalar_ops {
+    ($name:ident<$T:ident $(, $param:ident)*> {$($fields:ident),*} where { $($bounds:tt)* }) => {
+        impl_color_scalar_ops!(@op $name<$T $(, $param)*> {$($fields),*},
+            Mul mul MulAssign mul_assign *, where { $($bounds)* });
+        impl_color_scalar_ops!(@op $name<$T $(, $param)*> {$($fields),*},
+            Div div DivAssign div_assign /, where { $($bounds)* });
+    };
+    (@op $name:ident<$T:ident $(, $param:ident)*> {$($fields:ident),*},
+        $op:ident $method:ident $op_assign:ident $method_assign:ident $sym:tt,
+        where { $($bounds:tt)* }) =>
+    {
+        impl<$T $(, $param)*> std::ops::$op<$T> for $name<$T $(, $param)*>
+        where
+            $($bounds)*
+        {
+            type Output = Self;
+            fn $method(mut self, rhs: $T) -> Self {
+                std::ops::$op_assign::$method_assign(&mut self, rhs);
+                self
+            }
+        }
+
+        impl<$T $(, $param)*> std::ops::$op_assign<$T> for $name<$T $(, $param)*>
+        where
+            $($bounds)*
+        {
+            fn $method_assign(&mut self, rhs: $T) {
+                $(self.$fields.0 = self.$fields.0 $sym rhs;)*
+            }
+        }
+    };
+}
+
 macro_rules! impl_color_broadcast {
     ($name:ident<$T:ident> {$($fields:ident),*}, chan=$chan:ident,
     phantom={$($phantom:ident),*}) =>
@@ -0,0 +1,210 @@
+//! Iterators over the channels of a color
+//!
+//! Every color with a single channel type can be iterated over, yielding its channel scalars in
+//! the order of [`ChannelOrder`](../trait.ChannelOrder.html). Colors implement `IntoIterator`,
+//! producing an [`IntoChannels`](struct.IntoChannels.html), and
+//! [`HomogeneousColor::iter`](../trait.HomogeneousColor.html#method.iter) borrows the color in a
+//! [`Channels`](struct.Channels.html) instead. Both yield the scalars by value, as the channel
+//! accessors of each color do.
+//!
+//! ## Examples:
+//!
+//! ```rust
+//! use prisma::{HomogeneousColor, Rgb, Rgba};
+//!
+//! let color = Rgb::new(10u8, 200, 50);
+//! assert_eq!(color.iter().max(), Some(200));
+//! assert_eq!(color.iter().map(u32::from).sum::<u32>(), 260);
+//!
+//! let channels: Vec<f32> = Rgba::new(Rgb::new(0.25, 0.5, 0.75), 1.0).into_iter().collect();
+//! assert_eq!(channels, vec![0.25, 0.5, 0.75, 1.0]);
+//! ```
+
+use crate::alpha::Alpha;
+use crate::channel::{FreeChannelScalar, NormalChannelScalar, PosNormalChannelScalar};
+use crate::color::ChannelAccess;
+use crate::lab::Lab;
+use crate::lms::{Lms, LmsModel};
+use crate::luma::Luma;
+use crate::luv::Luv;
+use crate::oklab::Oklab;
+use crate::rgb::Rgb;
+use crate::rgi::Rgi;
+use crate::white_point::WhitePoint;
+use crate::xyy::XyY;
+use crate::xyz::Xyz;
+use crate::ycbcr::{BareYCbCr, YCbCr, YCbCrModel};
+use num_traits::Float;
+use std::iter::FusedIterator;
+use std::ops::Range;
+
+/// An iterator over the channels of a borrowed color
+///
+/// Returned by [`HomogeneousColor::iter`](../trait.HomogeneousColor.html#method.iter).
+#[derive(Clone, Debug)]
+pub struct Channels<'a, C> {
+    color: &'a C,
+    indices: Range<usize>,
+}
+
+impl<'a, C> Channels<'a, C>
+where
+    C: ChannelAccess,
+{
+    /// Construct an iterator over the channels of `color`
+    pub fn new(color: &'a C) -> Self {
+        Channels {
+            color,
+            indices: 0..C::num_channels() as usize,
+        }
+    }
+}
+
+/// An iterator over the channels of a color, taken by value
+///
+/// Returned by the `IntoIterator` implementation of each color.
+#[derive(Clone, Debug)]
+pub struct IntoChannels<C> {
+    color: C,
+    indices: Range<usize>,
+}
+
+impl<C> IntoChannels<C>
+where
+    C: ChannelAccess,
+{
+    /// Construct an iterator over the channels of `color`
+    pub fn new(color: C) -> Self {
+        IntoChannels {
+            color,
+            indices: 0..C::num_channels() as usize,
+        }
+    }
+}
+
+macro_rules! impl_channel_iterator {
+    (<$($lt:lifetime),*> $name:ty) => {
+        impl<$($lt,)* C> Iterator for $name
+        where
+            C: ChannelAccess,
+            C::ChannelFormat: Clone,
+        {
+            type Item = C::ChannelFormat;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                let index = self.indices.next()?;
+                self.color.channel(index).cloned()
+            }
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.indices.size_hint()
+            }
+        }
+
+        impl<$($lt,)* C> DoubleEndedIterator for $name
+        where
+            C: ChannelAccess,
+            C::ChannelFormat: Clone,
+        {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                let index = self.indices.next_back()?;
+                self.color.channel(index).cloned()
+            }
+        }
+
+        impl<$($lt,)* C> ExactSizeIterator for $name
+        where
+            C: ChannelAccess,
+            C::ChannelFormat: Clone,
+        {
+        }
+
+        impl<$($lt,)* C> FusedIterator for $name
+        where
+            C: ChannelAccess,
+            C::ChannelFormat: Clone,
+        {
+        }
+    };
+}
+
+impl_channel_iterator!(<'a> Channels<'a, C>);
+impl_channel_iterator!(<> IntoChannels<C>);
+
+macro_rules! impl_into_iterator {
+    (<$($gen:ident),*> $name:ty where { $($bounds:tt)* }) => {
+        impl<$($gen),*> IntoIterator for $name
+        where
+            $($bounds)*
+        {
+            type Item = T;
+            type IntoIter = IntoChannels<Self>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                IntoChannels::new(self)
+            }
+        }
+    };
+}
+
+impl_into_iterator!(<T> Rgb<T> where { T: PosNormalChannelScalar });
+impl_into_iterator!(<T> Luma<T> where { T: PosNormalChannelScalar });
+impl_into_iterator!(<T> Rgi<T> where { T: PosNormalChannelScalar + Float });
+impl_into_iterator!(<T> Xyz<T> where { T: FreeChannelScalar });
+impl_into_iterator!(<T> XyY<T> where { T: FreeChannelScalar + PosNormalChannelScalar + Float });
+impl_into_iterator!(<T, W> Lab<T, W> where { T: FreeChannelScalar, W: WhitePoint<T> });
+impl_into_iterator!(<T, W> Luv<T, W> where { T: FreeChannelScalar, W: WhitePoint<T> });
+impl_into_iterator!(<T, M> Lms<T, M> where { T: FreeChannelScalar, M: LmsModel<T> });
+impl_into_iterator!(<T> Oklab<T> where { T: FreeChannelScalar });
+impl_into_iterator!(<T> BareYCbCr<T> where { T: PosNormalChannelScalar + NormalChannelScalar });
+impl_into_iterator!(<T, M> YCbCr<T, M> where {
+    T: PosNormalChannelScalar + NormalChannelScalar,
+    M: YCbCrModel<T>,
+});
+impl_into_iterator!(<T, C> Alpha<T, C> where {
+    T: PosNormalChannelScalar,
+    C: ChannelAccess<ChannelFormat = T>,
+});
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::alpha::Rgba;
+    use crate::color::HomogeneousColor;
+    use crate::white_point::D65;
+
+    #[test]
+    fn test_iter() {
+        let color = Rgb::new(10u8, 20, 30);
+        let mut iter = color.iter();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next(), Some(10));
+        assert_eq!(iter.next_back(), Some(30));
+        assert_eq!(iter.next(), Some(20));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+
+        let rgba = Rgba::new(color, 40);
+        assert_eq!(rgba.iter().rev().collect::<Vec<_>>(), vec![40, 30, 20, 10]);
+        assert_eq!(rgba.iter().zip(rgba).filter(|(a, b)| a == b).count(), 4);
+
+        let lab = Lab::<f64, D65>::new(50.0, -20.0, 10.0);
+        let sum: f64 = lab.into_iter().sum();
+        assert_eq!(sum, 40.0);
+        assert_eq!(Luma::new(0.5f32).into_iter().len(), 1);
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let mut total = 0u32;
+        for channel in Rgb::new(1u8, 2, 3) {
+            total += u32::from(channel);
+        }
+        assert_eq!(total, 6);
+
+        let ycbcr = YCbCr::<f32>::new(0.5, -0.25, 0.25);
+        let min = ycbcr.into_iter().fold(f32::INFINITY, f32::min);
+        assert_eq!(min, -0.25);
+        let channels: Vec<_> = Xyz::new(0.25, 0.5, 0.75).into_iter().collect();
+        assert_eq!(channels, vec![0.25, 0.5, 0.75]);
+    }
+}
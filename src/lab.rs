@@ -5,7 +5,9 @@
 use crate::channel::{
     ChannelCast, ChannelFormatCast, ColorChannel, FreeChannel, FreeChannelScalar, PosFreeChannel,
 };
-use crate::color::{Bounded, Broadcast, Color, FromTuple, HomogeneousColor, Lerp};
+use crate::color::{
+    Bounded, Broadcast, ChannelAccess, ChannelOrder, Color, FromTuple, HomogeneousColor, Lerp,
+};
 use crate::tags::LabTag;
 use crate::white_point::{UnitWhitePoint, WhitePoint};
 use crate::xyz::Xyz;
@@ -170,6 +172,24 @@ where
     }
 }
 
+impl<T, W> ChannelOrder for Lab<T, W>
+where
+    T: FreeChannelScalar,
+    W: WhitePoint<T>,
+{
+    impl_color_channel_order!({L, a, b});
+}
+
+impl<T, W> ChannelAccess for Lab<T, W>
+where
+    T: FreeChannelScalar,
+    W: WhitePoint<T>,
+{
+    impl_color_channel_access_square!(T {L, a, b});
+}
+
+impl_color_elementwise_ops!(Lab<T, W> {L, a, b} where { T: FreeChannelScalar, W: WhitePoint<T> });
+
 impl<T, W> Broadcast for Lab<T, W>
 where
     T: FreeChannelScalar,
@@ -6,7 +6,7 @@ use crate::channel::{
     AngularChannel, AngularChannelScalar, ChannelCast, ChannelFormatCast, ColorChannel,
     FreeChannelScalar, PosFreeChannel,
 };
-use crate::color::{Bounded, Color, FromTuple, Lerp, PolarColor};
+use crate::color::{Bounded, ChannelOrder, Color, FromTuple, Lerp, PolarColor, PolarLerp};
 use crate::convert::{FromColor, GetChroma, GetHue};
 use crate::lab::Lab;
 use crate::tags::LchabTag;
@@ -151,6 +151,15 @@ where
     }
 }
 
+impl<T, W, A> ChannelOrder for Lchab<T, W, A>
+where
+    T: FreeChannelScalar,
+    A: AngularChannelScalar,
+    W: WhitePoint<T>,
+{
+    impl_color_channel_order!({L, chroma, hue});
+}
+
 impl<T, W, A> PolarColor for Lchab<T, W, A>
 where
     T: FreeChannelScalar,
@@ -183,6 +192,15 @@ where
     impl_color_lerp_angular!(Lchab<T> {hue, L, chroma }, copy={white_point});
 }
 
+impl<T, W, A> PolarLerp for Lchab<T, W, A>
+where
+    T: FreeChannelScalar + Lerp,
+    A: AngularChannelScalar + Lerp,
+    W: WhitePoint<T>,
+{
+    impl_color_lerp_hue_mode!(Lchab<T> {hue, L, chroma }, copy={white_point});
+}
+
 impl<T, W, A> Bounded for Lchab<T, W, A>
 where
     T: FreeChannelScalar,
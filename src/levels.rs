@@ -0,0 +1,284 @@
+//! The levels adjustment of image editors
+//!
+//! A levels adjustment stretches the input range `[in_min, in_max]` of each channel over the
+//! output range `[out_min, out_max]`. Values below `in_min` or above `in_max` are clipped, and an
+//! optional gamma bends the midtones, brightening them for a gamma above one and darkening them
+//! below it. This is the "Levels" dialog found in most image editors, and is commonly used to
+//! stretch the contrast of washed out images or to map full range values into a narrower one.
+//!
+//! [`Levels`](struct.Levels.html) describes one adjustment. Colors implementing
+//! [`RemapLevels`](trait.RemapLevels.html) apply it to every channel with `remap_levels` or
+//! `apply_levels`, or a separate one to each channel with `apply_channel_levels`.
+//! [`apply`](fn.apply.html) and [`apply_in_place`](fn.apply_in_place.html) adjust whole buffers.
+//!
+//! The bounds are given in the channel's own scalar type, so `Rgb<u8>` levels go from 0 to 255
+//! and `Rgb<f32>` levels from 0 to 1.
+//!
+//! ## Examples:
+//!
+//! ```rust
+//! # extern crate prisma;
+//!
+//! use prisma::Rgb;
+//! use prisma::levels::{self, Levels, RemapLevels};
+//!
+//! let color = Rgb::new(16u8, 128, 235);
+//! assert_eq!(color.remap_levels(16, 235, 0, 255), Rgb::new(0, 130, 255));
+//!
+//! let mut pixels = vec![Rgb::new(0.25f32, 0.5, 0.75)];
+//! levels::apply_in_place(&mut pixels, &Levels::new(0.0, 1.0, 0.0, 1.0).with_gamma(2.0));
+//! assert_eq!(pixels[0], Rgb::new(0.5, 0.70710677, 0.8660254));
+//! ```
+
+use crate::alpha::Alpha;
+use crate::channel::{ChannelFormatCast, FreeChannelScalar, PosNormalChannelScalar};
+use crate::color::{Color, Flatten};
+use crate::luma::Luma;
+use crate::rgb::Rgb;
+use crate::xyz::Xyz;
+use std::marker::PhantomData;
+
+/// A levels adjustment, remapping `[in_min, in_max]` to `[out_min, out_max]`
+///
+/// Input values are clipped to `[in_min, in_max]` and scaled to `[0, 1]`, raised to the power of
+/// `1 / gamma`, then scaled to `[out_min, out_max]`. `out_min` may be greater than `out_max`,
+/// which inverts the channel. If `in_min` is not less than `in_max`, the adjustment becomes a
+/// threshold, mapping values below `in_max` to `out_min` and the rest to `out_max`.
+///
+/// The calculation is done on channels cast to normalized `f64` values, and the result is cast
+/// back to `T` with `ChannelFormatCast`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Levels<T> {
+    in_min: f64,
+    in_max: f64,
+    out_min: f64,
+    out_max: f64,
+    gamma: f64,
+    _channel: PhantomData<T>,
+}
+
+impl<T> Levels<T>
+where
+    T: ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+{
+    /// Construct a `Levels` with a gamma of one
+    pub fn new(in_min: T, in_max: T, out_min: T, out_max: T) -> Self {
+        Levels {
+            in_min: in_min.cast(),
+            in_max: in_max.cast(),
+            out_min: out_min.cast(),
+            out_max: out_max.cast(),
+            gamma: 1.0,
+            _channel: PhantomData,
+        }
+    }
+    /// Construct a `Levels` that changes nothing
+    pub fn identity() -> Self
+    where
+        T: PosNormalChannelScalar,
+    {
+        Levels::new(
+            T::min_bound(),
+            T::max_bound(),
+            T::min_bound(),
+            T::max_bound(),
+        )
+    }
+
+    /// Set the midtone gamma
+    ///
+    /// # Panics
+    /// If `gamma` is not greater than zero.
+    pub fn with_gamma(self, gamma: f64) -> Self {
+        assert!(gamma > 0.0, "levels gamma must be greater than zero");
+        Levels { gamma, ..self }
+    }
+    /// Returns the midtone gamma
+    pub fn gamma(&self) -> f64 {
+        self.gamma
+    }
+
+    /// Apply the adjustment to a single channel value
+    pub fn apply(&self, value: T) -> T {
+        let value: f64 = value.cast();
+        let range = self.in_max - self.in_min;
+        let pos = if range > 0.0 {
+            ((value - self.in_min) / range).clamp(0.0, 1.0)
+        } else if value < self.in_max {
+            0.0
+        } else {
+            1.0
+        };
+        let pos = if self.gamma == 1.0 {
+            pos
+        } else {
+            pos.powf(1.0 / self.gamma)
+        };
+        (self.out_min + pos * (self.out_max - self.out_min)).cast()
+    }
+}
+
+/// A color whose channels can be remapped with a levels adjustment
+///
+/// For `Alpha` colors, only the inner color is adjusted and the alpha channel is left unchanged.
+pub trait RemapLevels: Color + Sized {
+    /// The scalar type of the channels
+    type Channel: ChannelFormatCast<f64>;
+
+    /// Apply `levels[i]` to the `i`th channel
+    ///
+    /// # Panics
+    /// If `levels` does not have one element per adjusted channel.
+    fn apply_channel_levels(&self, levels: &[Levels<Self::Channel>]) -> Self;
+
+    /// Apply `levels` to every channel
+    fn apply_levels(&self, levels: &Levels<Self::Channel>) -> Self;
+
+    /// Remap every channel from `[in_min, in_max]` to `[out_min, out_max]`
+    fn remap_levels(
+        &self,
+        in_min: Self::Channel,
+        in_max: Self::Channel,
+        out_min: Self::Channel,
+        out_max: Self::Channel,
+    ) -> Self
+    where
+        f64: ChannelFormatCast<Self::Channel>,
+    {
+        self.apply_levels(&Levels::new(in_min, in_max, out_min, out_max))
+    }
+}
+
+macro_rules! impl_remap_levels_flat {
+    ($name:ident, $chan_trait:ident) => {
+        impl<T> RemapLevels for $name<T>
+        where
+            T: $chan_trait + ChannelFormatCast<f64>,
+            f64: ChannelFormatCast<T>,
+        {
+            type Channel = T;
+
+            fn apply_channel_levels(&self, levels: &[Levels<T>]) -> Self {
+                let values = self.as_slice();
+                assert_eq!(
+                    levels.len(),
+                    values.len(),
+                    "expected one levels adjustment per channel"
+                );
+                let remapped: Vec<T> = values
+                    .iter()
+                    .zip(levels)
+                    .map(|(value, levels)| levels.apply(value.clone()))
+                    .collect();
+                $name::from_slice(&remapped)
+            }
+
+            fn apply_levels(&self, levels: &Levels<T>) -> Self {
+                let remapped: Vec<T> = self
+                    .as_slice()
+                    .iter()
+                    .map(|value| levels.apply(value.clone()))
+                    .collect();
+                $name::from_slice(&remapped)
+            }
+        }
+    };
+}
+
+impl_remap_levels_flat!(Rgb, PosNormalChannelScalar);
+impl_remap_levels_flat!(Luma, PosNormalChannelScalar);
+impl_remap_levels_flat!(Xyz, FreeChannelScalar);
+
+impl<T, InnerColor> RemapLevels for Alpha<T, InnerColor>
+where
+    T: PosNormalChannelScalar,
+    InnerColor: RemapLevels,
+{
+    type Channel = InnerColor::Channel;
+
+    fn apply_channel_levels(&self, levels: &[Levels<Self::Channel>]) -> Self {
+        Alpha::new(self.color().apply_channel_levels(levels), self.alpha())
+    }
+
+    fn apply_levels(&self, levels: &Levels<Self::Channel>) -> Self {
+        Alpha::new(self.color().apply_levels(levels), self.alpha())
+    }
+}
+
+/// Apply `levels` to every channel of each color in `src`
+pub fn apply<C>(src: &[C], levels: &Levels<C::Channel>) -> Vec<C>
+where
+    C: RemapLevels,
+{
+    src.iter().map(|color| color.apply_levels(levels)).collect()
+}
+
+/// Apply `levels` to every channel of each color in `colors` in place
+pub fn apply_in_place<C>(colors: &mut [C], levels: &Levels<C::Channel>)
+where
+    C: RemapLevels,
+{
+    for color in colors.iter_mut() {
+        *color = color.apply_levels(levels);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::alpha::Rgba;
+    use approx::*;
+
+    #[test]
+    fn test_levels() {
+        let levels = Levels::new(0.2f64, 0.6, 0.0, 1.0);
+        assert_relative_eq!(levels.apply(0.1), 0.0);
+        assert_relative_eq!(levels.apply(0.4), 0.5);
+        assert_relative_eq!(levels.apply(0.9), 1.0);
+
+        let inverted = Levels::new(0.0f32, 1.0, 1.0, 0.25);
+        assert_relative_eq!(inverted.apply(0.0), 1.0);
+        assert_relative_eq!(inverted.apply(1.0), 0.25);
+
+        let gamma = Levels::new(0.0f64, 1.0, 0.0, 1.0).with_gamma(0.5);
+        assert_relative_eq!(gamma.apply(0.5), 0.25);
+        assert_eq!(gamma.gamma(), 0.5);
+
+        let threshold = Levels::new(0.5f64, 0.5, 0.0, 1.0);
+        assert_eq!(threshold.apply(0.49), 0.0);
+        assert_eq!(threshold.apply(0.5), 1.0);
+
+        let identity = Levels::<u8>::identity();
+        assert!((0..=255u8).all(|v| identity.apply(v) == v));
+    }
+
+    #[test]
+    fn test_remap_colors() {
+        let color = Rgb::new(0.1f32, 0.5, 0.9);
+        assert_relative_eq!(
+            color.remap_levels(0.1, 0.9, 0.0, 1.0),
+            Rgb::new(0.0, 0.5, 1.0),
+            epsilon = 1e-6
+        );
+        let per_channel = [
+            Levels::new(0.0, 0.5, 0.0, 1.0),
+            Levels::identity(),
+            Levels::new(0.0, 1.0, 1.0, 0.0),
+        ];
+        assert_relative_eq!(
+            color.apply_channel_levels(&per_channel),
+            Rgb::new(0.2, 0.5, 0.1),
+            epsilon = 1e-6
+        );
+
+        let rgba = Rgba::new(Rgb::new(64u8, 128, 192), 10u8);
+        let remapped = rgba.remap_levels(64, 192, 0, 255);
+        assert_eq!(remapped, Rgba::new(Rgb::new(0, 127, 255), 10));
+
+        let lumas = vec![Luma::new(0.0f64), Luma::new(0.5), Luma::new(1.0)];
+        let out = apply(&lumas, &Levels::new(0.0, 1.0, 0.25, 0.75));
+        assert_relative_eq!(out[1], Luma::new(0.5));
+        assert_relative_eq!(out[2], Luma::new(0.75));
+    }
+}
@@ -28,6 +28,7 @@
 //!
 //! #### Device Dependent:
 //! * **[`Rgb`](struct.Rgb.html)** - The standard color model for displays
+//! * **[`Luma`](struct.Luma.html)** - A single channel grayscale model for monochrome images
 //! * **[`Rgi`](struct.Rgi.html)** - A chromaticity model constructed from Rgb that decouples chromaticity and lightness
 //! * **[`Hsv`](struct.Hsv.html)** - Hue, saturation, value: a more intuitive polar Rgb model
 //! * **[`Hsl`](struct.Hsl.html)** - Hue, saturation, lightness: an alternate to Hsv fulfilling similar roles
@@ -43,6 +44,8 @@
 //! * **[`Lchab`](struct.Lchab.html)** - A polar transformation of Lab. A uniform perception analog of Hsl
 //! * **[`Luv`](struct.Luv.html)** - An alternative uniform perception color space useful in lighting calculations
 //! * **[`Lchuv`](struct.Lchuv.html)** - A polar transformation of Luv
+//! * **[`Oklab`](struct.Oklab.html)** - A uniform perception color space with improved hue linearity
+//! * **[`Oklch`](struct.Oklch.html)** - A polar transformation of Oklab
 //!
 //! Prisma also supports these color spaces with an alpha channel via the [`Alpha`](struct.Alpha.html) type.
 //!
@@ -169,18 +172,63 @@ extern crate angular_units as angle;
 #[macro_use]
 mod impl_macros;
 
+pub mod buffer;
+pub mod blend;
+pub mod bytes;
+pub mod cat;
 pub mod channel;
-mod linalg;
+pub mod chromaticity;
+pub mod linalg;
 
 pub mod color_space;
+pub mod composite;
+pub mod contrast;
+pub mod cvd;
+pub mod distance;
+pub mod dither;
+pub mod dynamic;
 pub mod encoding;
+pub mod fixed;
+pub mod format;
+pub mod gamut;
+pub mod gradient;
+pub mod harmony;
+pub mod levels;
+pub mod hex;
+#[cfg(feature = "icc")]
+pub mod icc;
+pub mod iter;
+pub mod lut;
+pub mod named;
+pub mod order;
+pub mod packed;
+pub mod palette;
+pub mod parse;
+pub mod policy;
+pub mod quantize;
+#[cfg(feature = "rand")]
+pub mod random;
+pub mod roundtrip;
+#[cfg(feature = "serde")]
+pub mod serialize;
+#[cfg(feature = "shader")]
+pub mod shader;
+pub mod spectrum;
+#[cfg(feature = "proptest")]
+pub mod strategy;
+pub mod stream;
 pub mod tags;
+#[cfg(feature = "term")]
+pub mod term;
+pub mod validate;
 pub mod white_point;
 
+mod adjust;
 mod alpha;
-mod chromaticity;
+mod array;
 mod color;
 mod convert;
+mod grayscale;
 
 mod ehsi;
 mod hsi;
@@ -191,7 +239,10 @@ mod lab;
 mod lchab;
 mod lchuv;
 pub mod lms;
+mod luma;
 mod luv;
+mod oklab;
+mod oklch;
 mod rgb;
 mod rgi;
 mod xyy;
@@ -202,16 +253,22 @@ pub mod ycbcr;
 pub mod test;
 
 pub use crate::color::{
-    Bounded, Broadcast, Color, Color3, Color4, DeviceDependentColor, Flatten, FromTuple,
-    HomogeneousColor, Invert, Lerp, PolarColor,
+    Bounded, BoundedArithmetic, Broadcast, ChannelAccess, ChannelOrder, Color, Color3, Color4,
+    DeviceDependentColor, Flatten, FromTuple, HomogeneousColor, HueMode, Invert, Lerp, PolarColor,
+    PolarLerp,
 };
 
+pub use crate::adjust::{Adjust, RotateHue};
 pub use crate::alpha::{
-    eHsia, Alpha, Hsia, Hsla, Hsva, Hwba, Laba, Lchaba, Lchauv, Lmsa, Luva, Rgba, Rgia, XyYa, Xyza,
-    YCbCra,
+    eHsia, Alpha, Hsia, Hsla, Hsva, Hwba, Laba, Lchaba, Lchauv, Lmsa, LumaA, Luva, Oklaba, Oklcha, Rgba,
+    Rgia, XyYa, Xyza, YCbCra,
 };
 pub use crate::chromaticity::ChromaticityCoordinates;
-pub use crate::convert::{FromColor, FromHsi, FromYCbCr};
+pub use crate::convert::{
+    mix_in, ChannelRange, ConvertColor, FromColor, FromHsi, FromYCbCr, OutOfRangeChannel,
+    OutOfRangeError, PivotBridge, PivotColor, TryConvert, TryFromTuple,
+};
+pub use crate::grayscale::{LumaWeights, ToLuma};
 pub use crate::ehsi::eHsi;
 pub use crate::hsi::{Hsi, HsiOutOfGamutMode};
 pub use crate::hsl::Hsl;
@@ -220,8 +277,11 @@ pub use crate::hwb::{Hwb, HwbBoundedChannelTraits};
 pub use crate::lab::Lab;
 pub use crate::lchab::Lchab;
 pub use crate::lchuv::Lchuv;
-pub use crate::linalg::Matrix3;
+pub use crate::linalg::{Matrix3, Matrix3x4};
+pub use crate::luma::Luma;
 pub use crate::luv::Luv;
+pub use crate::oklab::Oklab;
+pub use crate::oklch::Oklch;
 pub use crate::rgb::Rgb;
 pub use crate::rgi::Rgi;
 pub use crate::xyy::XyY;
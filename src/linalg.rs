@@ -1,13 +1,46 @@
+//! A small 3x3 matrix type for linear color transformations
+//!
+//! Most color space conversions between linear models are a multiplication by a 3x3 matrix, such
+//! as the Rgb to Xyz matrix of a color space or a chromatic adaptation transform.
+//! [`Matrix3`](struct.Matrix3.html) provides the handful of operations needed to build, combine
+//! and invert such transforms without bringing in a separate linear algebra crate.
+//!
+//! Matrices are stored in row major order and act on column vectors, so `a * b` is the transform
+//! that applies `b` first and then `a`. Transforms that also add a constant offset, such as those
+//! producing digital YCbCr code values, are represented by
+//! [`Matrix3x4`](struct.Matrix3x4.html), which composes and inverts in the same way.
+//!
+//! ## Examples:
+//!
+//! ```rust
+//! # extern crate prisma;
+//!
+//! use prisma::linalg::Matrix3;
+//!
+//! let scale = Matrix3::new([2.0, 0.0, 0.0, 0.0, 4.0, 0.0, 0.0, 0.0, 0.5]);
+//! let swap = Matrix3::new([0.0, 1.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0]);
+//!
+//! assert_eq!(scale * (1.0, 1.0, 1.0), (2.0, 4.0, 0.5));
+//! assert_eq!((swap * scale) * (1.0, 2.0, 3.0), (8.0, 2.0, 1.5));
+//! assert_eq!(scale.determinant(), 4.0);
+//!
+//! let inverse = scale.try_inverse().unwrap();
+//! assert_eq!(inverse * scale, Matrix3::identity());
+//! assert_eq!(Matrix3::<f64>::zero().try_inverse(), None);
+//! ```
+
 #![allow(clippy::many_single_char_names)]
 
 #[cfg(feature = "approx")]
 use approx;
 use num_traits;
 use std::fmt;
-use std::mem;
 use std::ops;
 
 /// A 3x3 matrix used for linear color transformations
+///
+/// The cells are stored in row major order. Multiplying by another `Matrix3` composes the
+/// transforms, and multiplying by a `(T, T, T)` tuple transforms it as a column vector.
 #[derive(Copy, Debug, PartialEq)]
 pub struct Matrix3<T> {
     /// An array containing the cell values
@@ -19,12 +52,7 @@ where
     T: Clone,
 {
     fn clone(&self) -> Self {
-        unsafe {
-            let mut new_arr: [T; 9] = mem::uninitialized();
-            new_arr.clone_from_slice(&self.m);
-
-            Matrix3 { m: new_arr }
-        }
+        Matrix3 { m: self.m.clone() }
     }
 }
 
@@ -108,7 +136,7 @@ where
     ///
     /// None is returned if the matrix is singular (determinant = 0)
     #[inline]
-    pub fn inverse(self) -> Option<Self> {
+    pub fn try_inverse(self) -> Option<Self> {
         let det = self.determinant();
         let (a, b, c, d, e, f, g, h, i) = self.to_tuple();
 
@@ -131,7 +159,22 @@ where
         }
     }
 
+    /// Compute the inverse of the matrix
+    ///
+    /// None is returned if the matrix is singular (determinant = 0)
+    #[deprecated(note = "renamed to `try_inverse`")]
+    #[inline]
+    pub fn inverse(self) -> Option<Self> {
+        self.try_inverse()
+    }
+
     /// Transform a vector using `self`
+    ///
+    /// The components are cast to `T` for the calculation and the results cast back to `U`.
+    /// Multiplying by a `(T, T, T)` tuple is equivalent when no cast is needed.
+    ///
+    /// # Panics
+    /// If a component or result can not be represented by the other type.
     #[inline]
     pub fn transform_vector<U>(&self, vec: (U, U, U)) -> (U, U, U)
     where
@@ -274,6 +317,26 @@ where
         output
     }
 }
+impl<T> ops::Mul<(T, T, T)> for Matrix3<T>
+where
+    T: num_traits::Num + Copy,
+{
+    type Output = (T, T, T);
+    #[inline]
+    fn mul(self, rhs: (T, T, T)) -> (T, T, T) {
+        let (v1, v2, v3) = rhs;
+        let (m1, m2, m3, m4, m5, m6, m7, m8, m9) = (
+            self.m[0], self.m[1], self.m[2], self.m[3], self.m[4], self.m[5], self.m[6], self.m[7],
+            self.m[8],
+        );
+
+        (
+            v1 * m1 + v2 * m2 + v3 * m3,
+            v1 * m4 + v2 * m5 + v3 * m6,
+            v1 * m7 + v2 * m8 + v3 * m9,
+        )
+    }
+}
 impl<T> ops::Mul<T> for Matrix3<T>
 where
     T: num_traits::Num + Copy,
@@ -442,6 +505,197 @@ where
     }
 }
 
+/// A 3x4 affine transform, made of a linear `Matrix3` followed by an offset
+///
+/// A vector `v` is transformed to `linear * v + offset`. Many color conversions add a constant
+/// after a matrix multiplication, such as the footroom and neutral chroma of digital YCbCr code
+/// values, and can't be expressed by a `Matrix3` alone. Like `Matrix3`, affine transforms can be
+/// composed by multiplication, with `a * b` applying `b` first, and inverted.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Matrix3x4<T> {
+    /// The linear part of the transform
+    pub linear: Matrix3<T>,
+    /// The offset added after the linear part
+    pub offset: (T, T, T),
+}
+
+impl<T> Matrix3x4<T>
+where
+    T: num_traits::Num + Copy + num_traits::Zero + num_traits::NumCast,
+{
+    /// Construct a new `Matrix3x4` from a linear part and an offset
+    #[inline]
+    pub fn new(linear: Matrix3<T>, offset: (T, T, T)) -> Self {
+        Matrix3x4 { linear, offset }
+    }
+
+    /// Construct a `Matrix3x4` from a linear transform with no offset
+    #[inline]
+    pub fn from_linear(linear: Matrix3<T>) -> Self {
+        Matrix3x4::new(linear, (T::zero(), T::zero(), T::zero()))
+    }
+
+    /// Construct a `Matrix3x4` that only adds `offset`
+    #[inline]
+    pub fn translation(offset: (T, T, T)) -> Self {
+        Matrix3x4::new(Matrix3::identity(), offset)
+    }
+
+    /// Construct an identity transform
+    #[inline]
+    pub fn identity() -> Self {
+        Matrix3x4::from_linear(Matrix3::identity())
+    }
+
+    /// Return the twelve cells as rows of four, with the offset in the last column
+    #[inline]
+    pub fn to_rows(&self) -> [[T; 4]; 3] {
+        let m = &self.linear.m;
+        [
+            [m[0], m[1], m[2], self.offset.0],
+            [m[3], m[4], m[5], self.offset.1],
+            [m[6], m[7], m[8], self.offset.2],
+        ]
+    }
+
+    /// Compute the determinant of the linear part
+    #[inline]
+    pub fn determinant(&self) -> T {
+        self.linear.determinant()
+    }
+
+    /// Compute the inverse of the transform
+    ///
+    /// None is returned if the linear part is singular (determinant = 0)
+    #[inline]
+    pub fn try_inverse(self) -> Option<Self> {
+        let inverse = self.linear.try_inverse()?;
+        let (o1, o2, o3) = inverse * self.offset;
+        Some(Matrix3x4::new(
+            inverse,
+            (T::zero() - o1, T::zero() - o2, T::zero() - o3),
+        ))
+    }
+}
+
+impl<T> From<Matrix3<T>> for Matrix3x4<T>
+where
+    T: num_traits::Num + Copy + num_traits::Zero + num_traits::NumCast,
+{
+    fn from(linear: Matrix3<T>) -> Self {
+        Matrix3x4::from_linear(linear)
+    }
+}
+
+impl<T> Default for Matrix3x4<T>
+where
+    T: num_traits::Num + Copy + Default,
+{
+    fn default() -> Self {
+        Matrix3x4 {
+            linear: Matrix3::default(),
+            offset: (T::default(), T::default(), T::default()),
+        }
+    }
+}
+
+impl<T> ops::Mul<(T, T, T)> for Matrix3x4<T>
+where
+    T: num_traits::Num + Copy,
+{
+    type Output = (T, T, T);
+    #[inline]
+    fn mul(self, rhs: (T, T, T)) -> (T, T, T) {
+        let (v1, v2, v3) = self.linear * rhs;
+        (v1 + self.offset.0, v2 + self.offset.1, v3 + self.offset.2)
+    }
+}
+
+impl<T> ops::Mul for Matrix3x4<T>
+where
+    T: num_traits::Num + Copy,
+{
+    type Output = Self;
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        Matrix3x4 {
+            linear: self.linear * rhs.linear,
+            offset: self * rhs.offset,
+        }
+    }
+}
+
+impl<T> ops::Mul<Matrix3<T>> for Matrix3x4<T>
+where
+    T: num_traits::Num + Copy,
+{
+    type Output = Self;
+    #[inline]
+    fn mul(self, rhs: Matrix3<T>) -> Self {
+        Matrix3x4 {
+            linear: self.linear * rhs,
+            offset: self.offset,
+        }
+    }
+}
+
+impl<T> ops::Mul<Matrix3x4<T>> for Matrix3<T>
+where
+    T: num_traits::Num + Copy,
+{
+    type Output = Matrix3x4<T>;
+    #[inline]
+    fn mul(self, rhs: Matrix3x4<T>) -> Matrix3x4<T> {
+        Matrix3x4 {
+            linear: self * rhs.linear,
+            offset: self * rhs.offset,
+        }
+    }
+}
+
+#[cfg(feature = "approx")]
+impl<T> approx::AbsDiffEq for Matrix3x4<T>
+where
+    T: num_traits::Num + Copy + approx::AbsDiffEq,
+    T::Epsilon: Clone,
+{
+    type Epsilon = T::Epsilon;
+
+    fn default_epsilon() -> Self::Epsilon {
+        T::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        self.linear.abs_diff_eq(&other.linear, epsilon.clone())
+            && self.offset.0.abs_diff_eq(&other.offset.0, epsilon.clone())
+            && self.offset.1.abs_diff_eq(&other.offset.1, epsilon.clone())
+            && self.offset.2.abs_diff_eq(&other.offset.2, epsilon)
+    }
+}
+#[cfg(feature = "approx")]
+impl<T> approx::RelativeEq for Matrix3x4<T>
+where
+    T: num_traits::Num + Copy + approx::RelativeEq,
+    T::Epsilon: Clone,
+{
+    fn default_max_relative() -> Self::Epsilon {
+        T::default_max_relative()
+    }
+    fn relative_eq(
+        &self,
+        other: &Self,
+        epsilon: Self::Epsilon,
+        max_relative: Self::Epsilon,
+    ) -> bool {
+        let eq = |lhs: &T, rhs: &T| lhs.relative_eq(rhs, epsilon.clone(), max_relative.clone());
+        self.linear
+            .relative_eq(&other.linear, epsilon.clone(), max_relative.clone())
+            && eq(&self.offset.0, &other.offset.0)
+            && eq(&self.offset.1, &other.offset.1)
+            && eq(&self.offset.2, &other.offset.2)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -473,6 +727,7 @@ mod test {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn test_invert() {
         let m1 = Matrix3::<f32>::identity();
         assert_eq!(m1, m1.inverse().unwrap());
@@ -486,4 +741,65 @@ mod test {
         assert_eq!(m3.inverse().unwrap(), m4);
         assert_eq!(m3.inverse().unwrap() * m3, Matrix3::identity());
     }
+
+    #[test]
+    fn test_try_inverse() {
+        let m1 = Matrix3::<f32>::identity();
+        assert_eq!(m1, m1.try_inverse().unwrap());
+        assert_eq!(Matrix3::broadcast(1.0f32).try_inverse(), None);
+
+        let m2 = Matrix3::new([1.0, 2.0, 1.0, 4.0, 2.0, 3.0, 1.0, 3.0, 1.0]);
+        let m3 = Matrix3::new([-7.0, 1.0, 4.0, -1.0, 0.0, 1.0, 10.0, -1.0, -6.0]);
+        assert_eq!(m2.try_inverse(), Some(m3));
+        assert_eq!(m2.try_inverse().unwrap() * m2, Matrix3::identity());
+    }
+
+    #[test]
+    fn test_transform() {
+        let m1 = Matrix3::new([1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(m1 * (1, 0, 0), (1, 4, 7));
+        assert_eq!(m1 * (1, 1, 1), (6, 15, 24));
+        assert_eq!(m1 * (1, 1, 1), m1.transform_vector((1, 1, 1)));
+        assert_eq!(m1.transpose() * (1, 1, 1), (12, 15, 18));
+        assert_eq!(m1.transpose().transpose(), m1);
+        assert_eq!(m1.determinant(), 0);
+
+        let m2 = Matrix3::new([2.0, 0.0, 1.0, 1.0, 1.0, 0.0, 0.0, 3.0, 1.0]);
+        let m3 = Matrix3::new([1.0, 1.0, 0.0, 0.0, 2.0, 0.0, 1.0, 0.0, 1.0]);
+        let v = (1.0, -2.0, 0.5);
+        assert_eq!((m2 * m3) * v, m2 * (m3 * v));
+        assert_eq!(m2.transpose().determinant(), m2.determinant());
+    }
+
+    #[test]
+    fn test_affine() {
+        let scale = Matrix3::new([2.0, 0.0, 0.0, 0.0, 4.0, 0.0, 0.0, 0.0, 0.5]);
+        let m1 = Matrix3x4::new(scale, (1.0, -1.0, 0.5));
+        assert_eq!(m1 * (1.0, 1.0, 2.0), (3.0, 3.0, 1.5));
+        assert_eq!(
+            m1.to_rows(),
+            [
+                [2.0, 0.0, 0.0, 1.0],
+                [0.0, 4.0, 0.0, -1.0],
+                [0.0, 0.0, 0.5, 0.5]
+            ]
+        );
+
+        let m2 = Matrix3x4::translation((0.0, 2.0, 4.0));
+        let v = (0.25, 0.5, -1.0);
+        assert_eq!((m2 * m1) * v, m2 * (m1 * v));
+        assert_eq!((m1 * m2) * v, m1 * (m2 * v));
+        assert_eq!((scale * m2) * v, scale * (m2 * v));
+        assert_eq!((m2 * scale) * v, m2 * (scale * v));
+        assert_eq!(Matrix3x4::from(scale) * v, scale * v);
+
+        let inverse = m1.try_inverse().unwrap();
+        assert_eq!(inverse * (m1 * v), v);
+        assert_eq!(inverse * m1, Matrix3x4::identity());
+        assert_eq!(m1.determinant(), 4.0);
+        assert_eq!(
+            Matrix3x4::new(Matrix3::broadcast(1.0f32), (1.0, 0.0, 0.0)).try_inverse(),
+            None
+        );
+    }
 }
@@ -6,7 +6,10 @@
 use crate::channel::{
     ChannelCast, ChannelFormatCast, ColorChannel, FreeChannel, FreeChannelScalar,
 };
-use crate::color::{Bounded, Broadcast, Color, Flatten, FromTuple, HomogeneousColor, Lerp};
+use crate::color::{
+    Bounded, Broadcast, ChannelAccess, ChannelOrder, Color, Flatten, FromTuple, HomogeneousColor,
+    Lerp,
+};
 use crate::convert::FromColor;
 use crate::linalg::Matrix3;
 use crate::tags::LmsTag;
@@ -58,6 +61,12 @@ pub struct CieCam97s;
 /// The Bradford `LMS` transform
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Bradford;
+/// The Hunt-Pointer-Estevez `LMS` transform used by the classic von Kries adaptation
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct VonKries;
+/// The `LMS` transform defined in the CAM16 color appearance model
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Cat16;
 
 /// An `LMS` space using the [`CieCam2002`](struct.CieCam2002.html) model
 pub type LmsCam2002<T> = Lms<T, CieCam2002>;
@@ -65,6 +74,10 @@ pub type LmsCam2002<T> = Lms<T, CieCam2002>;
 pub type LmsCam97s<T> = Lms<T, CieCam97s>;
 /// An `LMS` space using the [`Bradford`](struct.Bradford.html) model
 pub type LmsBradford<T> = Lms<T, Bradford>;
+/// An `LMS` space using the [`VonKries`](struct.VonKries.html) model
+pub type LmsVonKries<T> = Lms<T, VonKries>;
+/// An `LMS` space using the [`Cat16`](struct.Cat16.html) model
+pub type LmsCat16<T> = Lms<T, Cat16>;
 
 impl<T, Model> Lms<T, Model>
 where
@@ -170,6 +183,29 @@ where
     impl_color_homogeneous_color_square!(Lms<T> {l, m, s}, phantom={model});
 }
 
+impl<T, Model> ChannelOrder for Lms<T, Model>
+where
+    T: FreeChannelScalar,
+    Model: LmsModel<T>,
+{
+    impl_color_channel_order!({l, m, s});
+}
+
+impl<T, Model> ChannelAccess for Lms<T, Model>
+where
+    T: FreeChannelScalar,
+    Model: LmsModel<T>,
+{
+    impl_color_channel_access_square!(T {l, m, s});
+}
+
+impl_color_elementwise_ops!(
+    Lms<T, Model> {l, m, s} where { T: FreeChannelScalar, Model: LmsModel<T> }
+);
+impl_color_scalar_ops!(
+    Lms<T, Model> {l, m, s} where { T: FreeChannelScalar, Model: LmsModel<T> }
+);
+
 impl<T, Model> Bounded for Lms<T, Model>
 where
     T: FreeChannelScalar,
@@ -283,6 +319,38 @@ where
     }
 }
 
+/// Build a von Kries chromatic adaptation matrix in the cone space of `Model`
+///
+/// The returned matrix maps an XYZ color seen under `src_white` to the XYZ color that appears the
+/// same under `dst_white`. The cone responses of both white points are computed with `Model`, and
+/// each cone response is scaled independently. [`Bradford`](struct.Bradford.html) is the most
+/// common choice of model.
+pub fn chromatic_adaptation_transform<T, Model>(
+    src_white: &Xyz<T>,
+    dst_white: &Xyz<T>,
+) -> Matrix3<T>
+where
+    T: FreeChannelScalar,
+    Model: LmsModel<T>,
+{
+    let src = Lms::<T, Model>::from_color(src_white);
+    let dst = Lms::<T, Model>::from_color(dst_white);
+    let zero = T::zero();
+    let scale = Matrix3::new([
+        dst.l() / src.l(),
+        zero,
+        zero,
+        zero,
+        dst.m() / src.m(),
+        zero,
+        zero,
+        zero,
+        dst.s() / src.s(),
+    ]);
+
+    Model::inverse_transform() * scale * Model::forward_transform()
+}
+
 impl<T> LmsModel<T> for CieCam2002
 where
     T: FreeChannelScalar,
@@ -382,6 +450,72 @@ where
     }
 }
 
+impl<T> LmsModel<T> for VonKries
+where
+    T: FreeChannelScalar,
+{
+    fn forward_transform() -> Matrix3<T> {
+        Matrix3::<T>::new([
+            num_traits::cast(0.40024).unwrap(),
+            num_traits::cast(0.7076).unwrap(),
+            num_traits::cast(-0.08081).unwrap(),
+            num_traits::cast(-0.2263).unwrap(),
+            num_traits::cast(1.16532).unwrap(),
+            num_traits::cast(0.0457).unwrap(),
+            num_traits::cast(0.0).unwrap(),
+            num_traits::cast(0.0).unwrap(),
+            num_traits::cast(0.91822).unwrap(),
+        ])
+    }
+
+    fn inverse_transform() -> Matrix3<T> {
+        Matrix3::<T>::new([
+            num_traits::cast(1.8599364).unwrap(),
+            num_traits::cast(-1.1293816).unwrap(),
+            num_traits::cast(0.2198974).unwrap(),
+            num_traits::cast(0.3611914).unwrap(),
+            num_traits::cast(0.6388125).unwrap(),
+            num_traits::cast(-0.0000064).unwrap(),
+            num_traits::cast(0.0).unwrap(),
+            num_traits::cast(0.0).unwrap(),
+            num_traits::cast(1.0890636).unwrap(),
+        ])
+    }
+}
+
+impl<T> LmsModel<T> for Cat16
+where
+    T: FreeChannelScalar,
+{
+    fn forward_transform() -> Matrix3<T> {
+        Matrix3::<T>::new([
+            num_traits::cast(0.401288).unwrap(),
+            num_traits::cast(0.650173).unwrap(),
+            num_traits::cast(-0.051461).unwrap(),
+            num_traits::cast(-0.250268).unwrap(),
+            num_traits::cast(1.204414).unwrap(),
+            num_traits::cast(0.045854).unwrap(),
+            num_traits::cast(-0.002079).unwrap(),
+            num_traits::cast(0.048952).unwrap(),
+            num_traits::cast(0.953127).unwrap(),
+        ])
+    }
+
+    fn inverse_transform() -> Matrix3<T> {
+        Matrix3::<T>::new([
+            num_traits::cast(1.8620679).unwrap(),
+            num_traits::cast(-1.0112546).unwrap(),
+            num_traits::cast(0.1491868).unwrap(),
+            num_traits::cast(0.3875265).unwrap(),
+            num_traits::cast(0.6214474).unwrap(),
+            num_traits::cast(-0.0089740).unwrap(),
+            num_traits::cast(-0.0158415).unwrap(),
+            num_traits::cast(-0.0341229).unwrap(),
+            num_traits::cast(1.0499644).unwrap(),
+        ])
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -460,4 +594,19 @@ mod test {
         assert_relative_eq!(c1.color_cast(), LmsCam2002::new(0.25f32, 0.50f32, 0.75f32));
         assert_relative_eq!(c1.color_cast::<f32>().color_cast(), c1);
     }
+
+    #[test]
+    fn test_chromatic_adaptation() {
+        let d50 = Xyz::new(0.96422, 1.0, 0.82521);
+        let d65 = Xyz::new(0.95047, 1.0, 1.08883);
+        let transform = chromatic_adaptation_transform::<_, Bradford>(&d50, &d65);
+        let (x, y, z) = transform.transform_vector(d50.clone().to_tuple());
+        assert_relative_eq!(Xyz::new(x, y, z), d65, epsilon = 1e-4);
+
+        let (x, y, z) = transform.transform_vector((0.4, 0.3, 0.2));
+        assert_relative_eq!(Xyz::new(x, y, z), Xyz::new(0.387951, 0.295870, 0.264757), epsilon = 1e-3);
+
+        let identity = chromatic_adaptation_transform::<_, CieCam2002>(&d65, &d65);
+        assert_relative_eq!(identity, Matrix3::identity(), epsilon = 1e-3);
+    }
 }
@@ -0,0 +1,338 @@
+//! The single channel grayscale color model.
+//!
+//! Provides the [Luma<T>](struct.Luma.html) type.
+
+use crate::channel::{
+    ChannelCast, ChannelFormatCast, ColorChannel, PosNormalBoundedChannel, PosNormalChannelScalar,
+    SaturatingChannelScalar,
+};
+use crate::color;
+use crate::color::{Broadcast, ChannelAccess, ChannelOrder, Color, FromTuple, HomogeneousColor};
+use crate::convert::FromColor;
+use crate::encoding::EncodableColor;
+use crate::grayscale::{LumaWeights, ToLuma};
+use crate::rgb::Rgb;
+use crate::tags::LumaTag;
+use std::fmt;
+use std::mem;
+use std::slice;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Eq, Ord, Hash)]
+/// The `Luma` device-dependent grayscale color model.
+///
+/// `Luma<T>` has a single channel, which is always positive and in the normalized range `[0, 1]`.
+/// Like `Rgb<T>`, it accepts both integer and float components and can be transmuted directly to
+/// a `&[T; 1]`, making it suitable for grayscale image buffers.
+///
+/// Converting from `Rgb` uses the Rec. 709 weights. Use
+/// [`from_rgb_with_weights`](#method.from_rgb_with_weights) to choose different weights.
+/// Converting to `Rgb` copies the luma into every channel.
+///
+/// With integer channels, `Luma` is `Eq`, `Hash` and `Ord`, ordered from darkest to lightest.
+///
+/// ## Examples:
+///
+/// ```rust
+/// #[macro_use] extern crate approx;
+/// # extern crate prisma;
+///
+/// use prisma::{FromColor, Lerp, Luma, Rgb};
+///
+/// let gray = Luma::from_color(&Rgb::new(1.0, 0.5, 0.0));
+/// assert_relative_eq!(gray, Luma::new(0.5702));
+/// assert_relative_eq!(gray.lerp(&Luma::new(1.0), 0.5), Luma::new(0.7851));
+/// assert_relative_eq!(Rgb::from_color(&gray), Rgb::new(0.5702, 0.5702, 0.5702));
+/// ```
+pub struct Luma<T> {
+    luma: PosNormalBoundedChannel<T>,
+}
+
+impl<T> Luma<T>
+where
+    T: PosNormalChannelScalar,
+{
+    /// Construct a new `Luma` instance with the given channel value
+    pub fn new(luma: T) -> Self {
+        Luma {
+            luma: PosNormalBoundedChannel::new(luma),
+        }
+    }
+
+    impl_color_color_cast_square!(Luma { luma }, chan_traits = { PosNormalChannelScalar });
+
+    /// Returns the luma channel scalar
+    pub fn luma(&self) -> T {
+        self.luma.0.clone()
+    }
+    /// Returns a mutable reference to the luma channel scalar
+    pub fn luma_mut(&mut self) -> &mut T {
+        &mut self.luma.0
+    }
+    /// Set the luma channel value
+    pub fn set_luma(&mut self, val: T) {
+        self.luma.0 = val;
+    }
+}
+
+impl<T> Luma<T>
+where
+    T: PosNormalChannelScalar + num_traits::Float,
+{
+    /// Construct a `Luma` value from an `Rgb` value using `weights`
+    pub fn from_rgb_with_weights(from: &Rgb<T>, weights: LumaWeights) -> Self {
+        Luma::new(from.to_luma(weights))
+    }
+}
+
+impl<T> Color for Luma<T>
+where
+    T: PosNormalChannelScalar,
+{
+    type Tag = LumaTag;
+    type ChannelsTuple = (T,);
+
+    #[inline]
+    fn num_channels() -> u32 {
+        1
+    }
+
+    fn to_tuple(self) -> Self::ChannelsTuple {
+        (self.luma.0,)
+    }
+}
+
+impl<T> FromTuple for Luma<T>
+where
+    T: PosNormalChannelScalar,
+{
+    fn from_tuple(values: Self::ChannelsTuple) -> Self {
+        Luma::new(values.0)
+    }
+}
+
+impl<T> HomogeneousColor for Luma<T>
+where
+    T: PosNormalChannelScalar,
+{
+    type ChannelFormat = T;
+
+    impl_color_homogeneous_color_square!(Luma<T> { luma });
+}
+
+impl<T> ChannelOrder for Luma<T>
+where
+    T: PosNormalChannelScalar,
+{
+    impl_color_channel_order!({luma});
+}
+
+impl<T> ChannelAccess for Luma<T>
+where
+    T: PosNormalChannelScalar,
+{
+    impl_color_channel_access_square!(T {luma});
+}
+
+impl<T> Broadcast for Luma<T>
+where
+    T: PosNormalChannelScalar,
+{
+    impl_color_broadcast!(Luma<T> { luma }, chan = PosNormalBoundedChannel);
+}
+
+impl<T> color::Invert for Luma<T>
+where
+    T: PosNormalChannelScalar,
+{
+    impl_color_invert!(Luma { luma });
+}
+
+impl<T> color::Bounded for Luma<T>
+where
+    T: PosNormalChannelScalar,
+{
+    impl_color_bounded!(Luma { luma });
+}
+
+impl<T> color::BoundedArithmetic for Luma<T>
+where
+    T: PosNormalChannelScalar + SaturatingChannelScalar,
+{
+    impl_color_bounded_arithmetic!(Luma { luma });
+}
+
+impl<T> color::Lerp for Luma<T>
+where
+    T: PosNormalChannelScalar + color::Lerp,
+{
+    type Position = <T as color::Lerp>::Position;
+    impl_color_lerp_square!(Luma { luma });
+}
+
+impl<T> color::Flatten for Luma<T>
+where
+    T: PosNormalChannelScalar,
+{
+    impl_color_as_slice!(T);
+    impl_color_from_slice_square!(Luma<T> {luma:PosNormalBoundedChannel - 0});
+}
+
+impl<T> EncodableColor for Luma<T> where T: PosNormalChannelScalar {}
+
+impl<T> ToLuma for Luma<T>
+where
+    T: PosNormalChannelScalar,
+{
+    type Scalar = T;
+
+    /// Returns the luma channel, ignoring `weights`
+    fn to_luma(&self, _weights: LumaWeights) -> T {
+        self.luma()
+    }
+}
+
+impl<T> FromColor<Rgb<T>> for Luma<T>
+where
+    T: PosNormalChannelScalar + num_traits::Float,
+{
+    /// Construct a `Luma` value from an `Rgb` value using the Rec. 709 weights
+    fn from_color(from: &Rgb<T>) -> Self {
+        Luma::from_rgb_with_weights(from, LumaWeights::Rec709)
+    }
+}
+
+impl<T> FromColor<Luma<T>> for Rgb<T>
+where
+    T: PosNormalChannelScalar,
+{
+    fn from_color(from: &Luma<T>) -> Self {
+        Rgb::broadcast(from.luma())
+    }
+}
+
+#[cfg(feature = "approx")]
+impl<T> approx::AbsDiffEq for Luma<T>
+where
+    T: PosNormalChannelScalar + approx::AbsDiffEq,
+    T::Epsilon: Clone,
+{
+    impl_abs_diff_eq!({ luma });
+}
+#[cfg(feature = "approx")]
+impl<T> approx::RelativeEq for Luma<T>
+where
+    T: PosNormalChannelScalar + approx::RelativeEq,
+    T::Epsilon: Clone,
+{
+    impl_rel_eq!({ luma });
+}
+#[cfg(feature = "approx")]
+impl<T> approx::UlpsEq for Luma<T>
+where
+    T: PosNormalChannelScalar + approx::UlpsEq,
+    T::Epsilon: Clone,
+{
+    impl_ulps_eq!({ luma });
+}
+
+impl<T> Default for Luma<T>
+where
+    T: PosNormalChannelScalar + num_traits::Zero,
+{
+    impl_color_default!(Luma {
+        luma: PosNormalBoundedChannel
+    });
+}
+
+impl<T> fmt::Display for Luma<T>
+where
+    T: PosNormalChannelScalar + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Luma({})", self.luma)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::alpha::{LumaA, Rgba};
+    use crate::color::*;
+    use crate::encoding::{EncodableColor, SrgbEncoding, TranscodableColor};
+    use approx::*;
+
+    #[test]
+    fn test_construct() {
+        let c1 = Luma::new(120u8);
+        assert_eq!(c1.luma(), 120u8);
+        assert_eq!(c1.as_slice(), &[120u8]);
+        assert_eq!(Luma::from_slice(&[40u8]), Luma::new(40u8));
+        assert_eq!(c1.clone().to_tuple(), (120u8,));
+        assert_eq!(Luma::from_tuple((7u8,)), Luma::new(7u8));
+        assert_eq!(Luma::broadcast(0.5), Luma::new(0.5));
+        assert_eq!(Luma::<f32>::default(), Luma::new(0.0));
+        assert_eq!(format!("{}", Luma::new(0.25)), "Luma(0.25)");
+
+        let mut c2 = Luma::new(0.3);
+        c2.set_luma(0.6);
+        assert_eq!(c2.luma(), 0.6);
+        *c2.luma_mut() = 0.9;
+        assert_eq!(c2.luma(), 0.9);
+    }
+
+    #[test]
+    fn test_color_traits() {
+        assert_relative_eq!(Luma::new(0.2).lerp(&Luma::new(0.6), 0.25), Luma::new(0.3));
+        assert_eq!(
+            Luma::new(50u8).lerp(&Luma::new(150u8), 0.5),
+            Luma::new(100u8)
+        );
+        assert_relative_eq!(Luma::new(0.2).invert(), Luma::new(0.8));
+        assert_eq!(Luma::new(1.5).normalize(), Luma::new(1.0));
+        assert!(!Luma::new(-0.1).is_normalized());
+        assert_eq!(Luma::new(0.7).clamp(0.0, 0.5), Luma::new(0.5));
+        assert_eq!(Luma::new(255u8).color_cast::<f32>(), Luma::new(1.0f32));
+    }
+
+    #[test]
+    fn test_convert() {
+        let rgb = Rgb::new(0.2, 0.6, 0.9);
+        assert_relative_eq!(Luma::from_color(&rgb), Luma::new(0.53662), epsilon = 1e-12);
+        assert_relative_eq!(
+            Luma::from_rgb_with_weights(&rgb, LumaWeights::Rec601),
+            Luma::new(0.5146),
+            epsilon = 1e-12
+        );
+        assert_eq!(Rgb::from_color(&Luma::new(0.4)), Rgb::new(0.4, 0.4, 0.4));
+        assert_relative_eq!(
+            Luma::from_color(&Rgb::from_color(&Luma::new(0.4))),
+            Luma::new(0.4),
+            epsilon = 1e-12
+        );
+
+        let rgba = Rgba::new(rgb, 0.5);
+        let lumaa = LumaA::<f64>::from_color(&rgba);
+        assert_relative_eq!(lumaa, LumaA::new(Luma::new(0.53662), 0.5), epsilon = 1e-12);
+        assert_eq!(LumaA::<f64>::num_channels(), 2);
+        assert_eq!(lumaa.as_slice().len(), 2);
+    }
+
+    #[test]
+    fn test_encode() {
+        let linear = Luma::new(0.5);
+        let encoded = linear.clone().encode_color(&SrgbEncoding);
+        assert_relative_eq!(encoded, Luma::new(0.7353569830524495), epsilon = 1e-6);
+        assert_relative_eq!(encoded.decode_color(&SrgbEncoding), linear, epsilon = 1e-6);
+
+        let c1 = Luma::new(128u8).encoded_as(SrgbEncoding);
+        assert_eq!(c1.decode().color().luma(), 55u8);
+
+        let c2 = LumaA::new(Luma::new(0.5), 0.25).encode_color(&SrgbEncoding);
+        assert_relative_eq!(
+            c2,
+            LumaA::new(Luma::new(0.7353569830524495), 0.25),
+            epsilon = 1e-6
+        );
+    }
+}
@@ -0,0 +1,460 @@
+//! Lookup tables for 1D curves and 3D color transforms, and their numerical inverses
+//!
+//! A [`Lut1d`](struct.Lut1d.html) samples a curve at evenly spaced positions over `[0, 1]`,
+//! while a [`Lut3d`](struct.Lut3d.html) samples an `Rgb -> Rgb` transform on an evenly spaced cube.
+//! Both are evaluated with linear (or trilinear) interpolation between samples.
+//!
+//! Often only the forward direction of a look is available. Both tables can build an approximate
+//! inverse with `invert`, which returns a [`LutInversion`](struct.LutInversion.html) holding the
+//! inverse table and the regions where the forward table could not be inverted.
+
+use crate::channel::PosNormalChannelScalar;
+use crate::color::Color;
+use crate::linalg::Matrix3;
+use crate::rgb::Rgb;
+use num_traits;
+use num_traits::cast;
+
+/// The result of inverting a lookup table
+///
+/// `R` describes a region that could not be inverted exactly, and depends on the kind of table
+/// that was inverted.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LutInversion<L, R> {
+    lut: L,
+    non_invertible: Vec<R>,
+}
+
+impl<L, R> LutInversion<L, R> {
+    /// Returns a reference to the inverse table
+    pub fn lut(&self) -> &L {
+        &self.lut
+    }
+    /// Returns the inverse table, discarding the non-invertible regions
+    pub fn into_lut(self) -> L {
+        self.lut
+    }
+    /// Returns the regions that could not be inverted
+    pub fn non_invertible_regions(&self) -> &[R] {
+        &self.non_invertible
+    }
+    /// Returns `true` if the entire table was invertible
+    pub fn is_exact(&self) -> bool {
+        self.non_invertible.is_empty()
+    }
+}
+
+/// A one-dimensional lookup table sampling a curve over `[0, 1]`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lut1d<T> {
+    samples: Vec<T>,
+}
+
+impl<T> Lut1d<T>
+where
+    T: num_traits::Float,
+{
+    /// Construct a new `Lut1d` from samples spaced evenly over `[0, 1]`
+    ///
+    /// # Panics
+    /// If fewer than two samples are given.
+    pub fn new(samples: Vec<T>) -> Self {
+        assert!(samples.len() >= 2, "A Lut1d needs at least two samples");
+        Lut1d { samples }
+    }
+
+    /// Construct a new `Lut1d` by sampling `f` at `size` evenly spaced positions
+    ///
+    /// # Panics
+    /// If `size` is less than two.
+    pub fn from_fn<F>(size: usize, f: F) -> Self
+    where
+        F: Fn(T) -> T,
+    {
+        assert!(size >= 2, "A Lut1d needs at least two samples");
+        let last: T = cast(size - 1).unwrap();
+        Lut1d::new(
+            (0..size)
+                .map(|i| f(cast::<_, T>(i).unwrap() / last))
+                .collect(),
+        )
+    }
+
+    /// Returns the samples of the table
+    pub fn samples(&self) -> &[T] {
+        &self.samples
+    }
+
+    /// Returns the number of samples in the table
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Returns `true` if the table has no samples. This is never the case for a valid table.
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Evaluate the curve at `x`, clamping `x` into `[0, 1]`
+    pub fn apply(&self, x: T) -> T {
+        let last = self.samples.len() - 1;
+        let pos = clamp_unit(x) * cast(last).unwrap();
+        let index = pos.floor().to_usize().unwrap().min(last - 1);
+        let t = pos - cast(index).unwrap();
+        let left = self.samples[index];
+        let right = self.samples[index + 1];
+        left + (right - left) * t
+    }
+
+    /// Build an inverse table with `size` samples
+    ///
+    /// The curve is expected to be monotonic, either increasing or decreasing. Where it is flat or
+    /// runs against its overall direction there is no unique inverse; those input intervals are
+    /// reported as non-invertible and the inverse picks the first crossing instead. Inverse values
+    /// outside of the range of the curve map to the nearest end of the input domain.
+    ///
+    /// # Panics
+    /// If `size` is less than two.
+    pub fn invert(&self, size: usize) -> LutInversion<Lut1d<T>, (T, T)> {
+        assert!(size >= 2, "A Lut1d needs at least two samples");
+        let increasing = self.samples[self.samples.len() - 1] >= self.samples[0];
+        let oriented: Vec<T> = if increasing {
+            self.samples.clone()
+        } else {
+            self.samples.iter().map(|&s| -s).collect()
+        };
+        let last: T = cast(self.samples.len() - 1).unwrap();
+
+        let mut non_invertible: Vec<(T, T)> = Vec::new();
+        for (i, pair) in oriented.windows(2).enumerate() {
+            if pair[1] > pair[0] {
+                continue;
+            }
+            let start = cast::<_, T>(i).unwrap() / last;
+            let end = cast::<_, T>(i + 1).unwrap() / last;
+            match non_invertible.last_mut() {
+                Some(region) if region.1 == start => region.1 = end,
+                _ => non_invertible.push((start, end)),
+            }
+        }
+
+        let inverse = Lut1d::from_fn(size, |y: T| {
+            let y = if increasing { y } else { -y };
+            if y <= oriented[0] {
+                return T::zero();
+            }
+            for (i, pair) in oriented.windows(2).enumerate() {
+                if pair[1] > pair[0] && y >= pair[0] && y <= pair[1] {
+                    let t = (y - pair[0]) / (pair[1] - pair[0]);
+                    return (cast::<_, T>(i).unwrap() + t) / last;
+                }
+            }
+            T::one()
+        });
+
+        LutInversion {
+            lut: inverse,
+            non_invertible,
+        }
+    }
+}
+
+/// A three-dimensional lookup table sampling an `Rgb -> Rgb` transform
+///
+/// Entries are stored with red varying fastest and blue slowest, matching the layout of the common
+/// `.cube` file format.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lut3d<T> {
+    size: usize,
+    entries: Vec<Rgb<T>>,
+}
+
+impl<T> Lut3d<T>
+where
+    T: PosNormalChannelScalar + num_traits::Float,
+{
+    /// Construct a new `Lut3d` with `size` samples along each axis
+    ///
+    /// # Panics
+    /// If `size` is less than two or `entries` does not contain `size^3` colors.
+    pub fn new(size: usize, entries: Vec<Rgb<T>>) -> Self {
+        assert!(size >= 2, "A Lut3d needs at least two samples per axis");
+        assert_eq!(
+            entries.len(),
+            size * size * size,
+            "A Lut3d of size {} needs {} entries",
+            size,
+            size * size * size
+        );
+        Lut3d { size, entries }
+    }
+
+    /// Construct a new `Lut3d` by sampling `f` on an evenly spaced cube with `size` samples per axis
+    ///
+    /// # Panics
+    /// If `size` is less than two.
+    pub fn from_fn<F>(size: usize, f: F) -> Self
+    where
+        F: Fn(&Rgb<T>) -> Rgb<T>,
+    {
+        assert!(size >= 2, "A Lut3d needs at least two samples per axis");
+        let last: T = cast(size - 1).unwrap();
+        let mut entries = Vec::with_capacity(size * size * size);
+        for b in 0..size {
+            for g in 0..size {
+                for r in 0..size {
+                    entries.push(f(&Rgb::new(
+                        cast::<_, T>(r).unwrap() / last,
+                        cast::<_, T>(g).unwrap() / last,
+                        cast::<_, T>(b).unwrap() / last,
+                    )));
+                }
+            }
+        }
+        Lut3d { size, entries }
+    }
+
+    /// Returns the number of samples along each axis
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the table entries
+    pub fn entries(&self) -> &[Rgb<T>] {
+        &self.entries
+    }
+
+    /// Returns the entry at grid position `(r, g, b)`
+    pub fn entry(&self, r: usize, g: usize, b: usize) -> &Rgb<T> {
+        &self.entries[r + self.size * (g + self.size * b)]
+    }
+
+    /// Evaluate the transform at `color` with trilinear interpolation
+    ///
+    /// Channels are clamped into `[0, 1]` before lookup.
+    pub fn apply(&self, color: &Rgb<T>) -> Rgb<T> {
+        let (r0, tr) = self.grid_position(color.red());
+        let (g0, tg) = self.grid_position(color.green());
+        let (b0, tb) = self.grid_position(color.blue());
+
+        let lerp = |a: (T, T, T), b: (T, T, T), t: T| {
+            (
+                a.0 + (b.0 - a.0) * t,
+                a.1 + (b.1 - a.1) * t,
+                a.2 + (b.2 - a.2) * t,
+            )
+        };
+        let corner = |dr: usize, dg: usize, db: usize| {
+            self.entry(r0 + dr, g0 + dg, b0 + db).clone().to_tuple()
+        };
+
+        let c00 = lerp(corner(0, 0, 0), corner(1, 0, 0), tr);
+        let c10 = lerp(corner(0, 1, 0), corner(1, 1, 0), tr);
+        let c01 = lerp(corner(0, 0, 1), corner(1, 0, 1), tr);
+        let c11 = lerp(corner(0, 1, 1), corner(1, 1, 1), tr);
+        let c0 = lerp(c00, c10, tg);
+        let c1 = lerp(c01, c11, tg);
+        let (r, g, b) = lerp(c0, c1, tb);
+
+        Rgb::new(r, g, b)
+    }
+
+    /// Build an inverse table with `size` samples per axis
+    ///
+    /// Each inverse entry is found with Newton's method on the interpolated forward table. Target
+    /// colors that the forward table does not reach within `tolerance`, either because they lie
+    /// outside of its output gamut or because the transform folds over itself, are reported as
+    /// non-invertible. Their inverse entries hold the closest input found.
+    ///
+    /// # Panics
+    /// If `size` is less than two.
+    pub fn invert(&self, size: usize, tolerance: T) -> LutInversion<Lut3d<T>, Rgb<T>> {
+        assert!(size >= 2, "A Lut3d needs at least two samples per axis");
+        let mut non_invertible = Vec::new();
+        let mut entries = Vec::with_capacity(size * size * size);
+        let inverse_grid = Lut3d::from_fn(size, |c| c.clone());
+
+        for target in inverse_grid.entries {
+            let (found, error) = self.solve(&target);
+            if error > tolerance {
+                non_invertible.push(target);
+            }
+            entries.push(found);
+        }
+
+        LutInversion {
+            lut: Lut3d::new(size, entries),
+            non_invertible,
+        }
+    }
+
+    fn solve(&self, target: &Rgb<T>) -> (Rgb<T>, T) {
+        const MAX_ITERATIONS: usize = 32;
+        let step: T = cast(1e-4).unwrap();
+        let (tr, tg, tb) = target.clone().to_tuple();
+
+        let residual = |c: [T; 3]| {
+            let (r, g, b) = self.apply(&Rgb::new(c[0], c[1], c[2])).to_tuple();
+            [r - tr, g - tg, b - tb]
+        };
+        let norm = |v: [T; 3]| v[0].abs().max(v[1].abs()).max(v[2].abs());
+
+        let mut current = [tr, tg, tb];
+        let mut error = residual(current);
+        for _ in 0..MAX_ITERATIONS {
+            if norm(error) <= T::epsilon() {
+                break;
+            }
+
+            // Finite difference Jacobian, stepping away from the upper bound so every sample
+            // stays inside the table
+            let mut jacobian = Matrix3::zero();
+            for axis in 0..3 {
+                let h = if current[axis] + step > T::one() {
+                    -step
+                } else {
+                    step
+                };
+                let mut shifted = current;
+                shifted[axis] = shifted[axis] + h;
+                let shifted_error = residual(shifted);
+                for row in 0..3 {
+                    jacobian.m[row * 3 + axis] = (shifted_error[row] - error[row]) / h;
+                }
+            }
+
+            let delta = match jacobian.try_inverse() {
+                Some(inv) => inv.transform_vector((error[0], error[1], error[2])),
+                None => break,
+            };
+            let next = [
+                clamp_unit(current[0] - delta.0),
+                clamp_unit(current[1] - delta.1),
+                clamp_unit(current[2] - delta.2),
+            ];
+            let next_error = residual(next);
+            if norm(next_error) >= norm(error) {
+                break;
+            }
+            current = next;
+            error = next_error;
+        }
+
+        (Rgb::new(current[0], current[1], current[2]), norm(error))
+    }
+
+    fn grid_position(&self, val: T) -> (usize, T) {
+        let last = self.size - 1;
+        let pos = clamp_unit(val) * cast(last).unwrap();
+        let index = pos.floor().to_usize().unwrap().min(last - 1);
+        (index, pos - cast(index).unwrap())
+    }
+}
+
+fn clamp_unit<T: num_traits::Float>(val: T) -> T {
+    val.max(T::zero()).min(T::one())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use approx::*;
+
+    #[test]
+    fn test_lut1d_apply() {
+        let lut = Lut1d::new(vec![0.0, 0.5, 0.75, 1.0]);
+        assert_eq!(lut.len(), 4);
+        assert_relative_eq!(lut.apply(0.0), 0.0);
+        assert_relative_eq!(lut.apply(1.0 / 6.0), 0.25, epsilon = 1e-6);
+        assert_relative_eq!(lut.apply(0.5), 0.625, epsilon = 1e-6);
+        assert_relative_eq!(lut.apply(1.0), 1.0);
+        assert_relative_eq!(lut.apply(2.0), 1.0);
+        assert_relative_eq!(lut.apply(-1.0), 0.0);
+    }
+
+    #[test]
+    fn test_lut1d_invert() {
+        let lut = Lut1d::from_fn(256, |x: f64| x.powf(2.2));
+        let inversion = lut.invert(256);
+        assert!(inversion.is_exact());
+        for &x in &[0.0, 0.1, 0.3, 0.5, 0.8, 1.0] {
+            assert_relative_eq!(inversion.lut().apply(lut.apply(x)), x, epsilon = 1e-2);
+        }
+
+        let decreasing = Lut1d::new(vec![1.0, 0.6, 0.2, 0.0]);
+        let inversion = decreasing.invert(11);
+        assert!(inversion.is_exact());
+        assert_relative_eq!(inversion.lut().apply(0.6), 1.0 / 3.0, epsilon = 1e-6);
+        assert_relative_eq!(inversion.lut().apply(0.1), 2.5 / 3.0, epsilon = 1e-6);
+
+        let flat = Lut1d::new(vec![0.0, 0.25, 0.25, 0.25, 0.75, 0.5, 1.0]);
+        let inversion = flat.invert(5);
+        assert!(!inversion.is_exact());
+        let regions = inversion.non_invertible_regions();
+        assert_eq!(regions.len(), 2);
+        assert_relative_eq!(regions[0].0, 1.0 / 6.0, epsilon = 1e-6);
+        assert_relative_eq!(regions[0].1, 0.5, epsilon = 1e-6);
+        assert_relative_eq!(regions[1].0, 4.0 / 6.0, epsilon = 1e-6);
+        assert_relative_eq!(regions[1].1, 5.0 / 6.0, epsilon = 1e-6);
+        assert_relative_eq!(inversion.lut().apply(0.25), 1.0 / 6.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_lut3d_apply() {
+        let identity = Lut3d::from_fn(2, |c: &Rgb<f64>| c.clone());
+        let color = Rgb::new(0.2, 0.5, 0.9);
+        assert_relative_eq!(identity.apply(&color), color, epsilon = 1e-6);
+
+        let lut = Lut3d::from_fn(17, |c: &Rgb<f64>| {
+            Rgb::new(c.green(), c.blue() * 0.5, c.red())
+        });
+        assert_eq!(lut.entries().len(), 17 * 17 * 17);
+        assert_relative_eq!(*lut.entry(16, 0, 0), Rgb::new(0.0, 0.0, 1.0));
+        assert_relative_eq!(
+            lut.apply(&Rgb::new(0.3, 0.7, 0.1)),
+            Rgb::new(0.7, 0.05, 0.3),
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn test_lut3d_invert() {
+        let look = |c: &Rgb<f64>| {
+            let (r, g, b) = c.clone().to_tuple();
+            Rgb::new(
+                0.8 * r + 0.1 * g + 0.1 * b.powf(1.5),
+                0.1 * r + 0.8 * g.powf(0.8) + 0.1 * b,
+                0.05 * r + 0.05 * g + 0.9 * b,
+            )
+        };
+        let lut = Lut3d::from_fn(17, look);
+        let inversion = lut.invert(9, 1e-4);
+
+        // The forward look never produces pure saturated primaries
+        assert!(!inversion.is_exact());
+        assert!(inversion
+            .non_invertible_regions()
+            .contains(&Rgb::new(1.0, 0.0, 0.0)));
+
+        for color in &[
+            Rgb::new(0.5, 0.5, 0.5),
+            Rgb::new(0.3, 0.6, 0.2),
+            Rgb::new(0.7, 0.4, 0.55),
+        ] {
+            let round_trip = inversion.lut().apply(&lut.apply(color));
+            assert_relative_eq!(round_trip, *color, epsilon = 1e-2);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "A Lut1d needs at least two samples")]
+    fn test_lut1d_invert_size_one() {
+        Lut1d::new(vec![0.0, 1.0]).invert(1);
+    }
+
+    #[test]
+    #[should_panic(expected = "A Lut3d needs at least two samples per axis")]
+    fn test_lut3d_invert_size_one() {
+        Lut3d::from_fn(2, |c: &Rgb<f64>| c.clone()).invert(1, 1e-4);
+    }
+}
@@ -5,7 +5,9 @@
 use crate::channel::{
     ChannelCast, ChannelFormatCast, ColorChannel, FreeChannel, FreeChannelScalar, PosFreeChannel,
 };
-use crate::color::{Bounded, Broadcast, Color, FromTuple, HomogeneousColor, Lerp};
+use crate::color::{
+    Bounded, Broadcast, ChannelAccess, ChannelOrder, Color, FromTuple, HomogeneousColor, Lerp,
+};
 use crate::tags::LuvTag;
 use crate::xyz::Xyz;
 #[cfg(feature = "approx")]
@@ -165,6 +167,24 @@ where
     }
 }
 
+impl<T, W> ChannelOrder for Luv<T, W>
+where
+    T: FreeChannelScalar,
+    W: WhitePoint<T>,
+{
+    impl_color_channel_order!({L, u, v});
+}
+
+impl<T, W> ChannelAccess for Luv<T, W>
+where
+    T: FreeChannelScalar,
+    W: WhitePoint<T>,
+{
+    impl_color_channel_access_square!(T {L, u, v});
+}
+
+impl_color_elementwise_ops!(Luv<T, W> {L, u, v} where { T: FreeChannelScalar, W: WhitePoint<T> });
+
 impl<T, W> Broadcast for Luv<T, W>
 where
     T: FreeChannelScalar,
@@ -0,0 +1,530 @@
+//! The named colors of CSS Color Level 4
+//!
+//! CSS defines 148 color keywords, most of them inherited from the X11 color names. Each is
+//! available as an 8-bit sRGB [`Rgb`](../struct.Rgb.html) constant, and [`lookup`](fn.lookup.html)
+//! finds a color by its name, as in a stylesheet. The keywords `transparent` and `currentcolor`
+//! are not colors in this sense, and are not included.
+//!
+//! Several colors have two names: every `gray` also has a `grey` spelling, `aqua` is the same as
+//! `cyan` and `fuchsia` the same as `magenta`.
+//!
+//! ## Examples:
+//!
+//! ```rust
+//! # extern crate prisma;
+//!
+//! use prisma::Rgb;
+//! use prisma::named::{self, lookup, name_of};
+//!
+//! assert_eq!(named::REBECCAPURPLE, Rgb::new(102, 51, 153));
+//! assert_eq!(lookup("CornflowerBlue"), Some(named::CORNFLOWERBLUE));
+//! assert_eq!(name_of(&Rgb::new(255, 165, 0)), Some("orange"));
+//! ```
+
+use crate::rgb::Rgb;
+
+/// `aliceblue`: `#f0f8ff`
+pub const ALICEBLUE: Rgb<u8> = Rgb::new_const(240, 248, 255);
+/// `antiquewhite`: `#faebd7`
+pub const ANTIQUEWHITE: Rgb<u8> = Rgb::new_const(250, 235, 215);
+/// `aqua`: `#00ffff`
+pub const AQUA: Rgb<u8> = Rgb::new_const(0, 255, 255);
+/// `aquamarine`: `#7fffd4`
+pub const AQUAMARINE: Rgb<u8> = Rgb::new_const(127, 255, 212);
+/// `azure`: `#f0ffff`
+pub const AZURE: Rgb<u8> = Rgb::new_const(240, 255, 255);
+/// `beige`: `#f5f5dc`
+pub const BEIGE: Rgb<u8> = Rgb::new_const(245, 245, 220);
+/// `bisque`: `#ffe4c4`
+pub const BISQUE: Rgb<u8> = Rgb::new_const(255, 228, 196);
+/// `black`: `#000000`
+pub const BLACK: Rgb<u8> = Rgb::new_const(0, 0, 0);
+/// `blanchedalmond`: `#ffebcd`
+pub const BLANCHEDALMOND: Rgb<u8> = Rgb::new_const(255, 235, 205);
+/// `blue`: `#0000ff`
+pub const BLUE: Rgb<u8> = Rgb::new_const(0, 0, 255);
+/// `blueviolet`: `#8a2be2`
+pub const BLUEVIOLET: Rgb<u8> = Rgb::new_const(138, 43, 226);
+/// `brown`: `#a52a2a`
+pub const BROWN: Rgb<u8> = Rgb::new_const(165, 42, 42);
+/// `burlywood`: `#deb887`
+pub const BURLYWOOD: Rgb<u8> = Rgb::new_const(222, 184, 135);
+/// `cadetblue`: `#5f9ea0`
+pub const CADETBLUE: Rgb<u8> = Rgb::new_const(95, 158, 160);
+/// `chartreuse`: `#7fff00`
+pub const CHARTREUSE: Rgb<u8> = Rgb::new_const(127, 255, 0);
+/// `chocolate`: `#d2691e`
+pub const CHOCOLATE: Rgb<u8> = Rgb::new_const(210, 105, 30);
+/// `coral`: `#ff7f50`
+pub const CORAL: Rgb<u8> = Rgb::new_const(255, 127, 80);
+/// `cornflowerblue`: `#6495ed`
+pub const CORNFLOWERBLUE: Rgb<u8> = Rgb::new_const(100, 149, 237);
+/// `cornsilk`: `#fff8dc`
+pub const CORNSILK: Rgb<u8> = Rgb::new_const(255, 248, 220);
+/// `crimson`: `#dc143c`
+pub const CRIMSON: Rgb<u8> = Rgb::new_const(220, 20, 60);
+/// `cyan`: `#00ffff`
+pub const CYAN: Rgb<u8> = Rgb::new_const(0, 255, 255);
+/// `darkblue`: `#00008b`
+pub const DARKBLUE: Rgb<u8> = Rgb::new_const(0, 0, 139);
+/// `darkcyan`: `#008b8b`
+pub const DARKCYAN: Rgb<u8> = Rgb::new_const(0, 139, 139);
+/// `darkgoldenrod`: `#b8860b`
+pub const DARKGOLDENROD: Rgb<u8> = Rgb::new_const(184, 134, 11);
+/// `darkgray`: `#a9a9a9`
+pub const DARKGRAY: Rgb<u8> = Rgb::new_const(169, 169, 169);
+/// `darkgreen`: `#006400`
+pub const DARKGREEN: Rgb<u8> = Rgb::new_const(0, 100, 0);
+/// `darkgrey`: `#a9a9a9`
+pub const DARKGREY: Rgb<u8> = Rgb::new_const(169, 169, 169);
+/// `darkkhaki`: `#bdb76b`
+pub const DARKKHAKI: Rgb<u8> = Rgb::new_const(189, 183, 107);
+/// `darkmagenta`: `#8b008b`
+pub const DARKMAGENTA: Rgb<u8> = Rgb::new_const(139, 0, 139);
+/// `darkolivegreen`: `#556b2f`
+pub const DARKOLIVEGREEN: Rgb<u8> = Rgb::new_const(85, 107, 47);
+/// `darkorange`: `#ff8c00`
+pub const DARKORANGE: Rgb<u8> = Rgb::new_const(255, 140, 0);
+/// `darkorchid`: `#9932cc`
+pub const DARKORCHID: Rgb<u8> = Rgb::new_const(153, 50, 204);
+/// `darkred`: `#8b0000`
+pub const DARKRED: Rgb<u8> = Rgb::new_const(139, 0, 0);
+/// `darksalmon`: `#e9967a`
+pub const DARKSALMON: Rgb<u8> = Rgb::new_const(233, 150, 122);
+/// `darkseagreen`: `#8fbc8f`
+pub const DARKSEAGREEN: Rgb<u8> = Rgb::new_const(143, 188, 143);
+/// `darkslateblue`: `#483d8b`
+pub const DARKSLATEBLUE: Rgb<u8> = Rgb::new_const(72, 61, 139);
+/// `darkslategray`: `#2f4f4f`
+pub const DARKSLATEGRAY: Rgb<u8> = Rgb::new_const(47, 79, 79);
+/// `darkslategrey`: `#2f4f4f`
+pub const DARKSLATEGREY: Rgb<u8> = Rgb::new_const(47, 79, 79);
+/// `darkturquoise`: `#00ced1`
+pub const DARKTURQUOISE: Rgb<u8> = Rgb::new_const(0, 206, 209);
+/// `darkviolet`: `#9400d3`
+pub const DARKVIOLET: Rgb<u8> = Rgb::new_const(148, 0, 211);
+/// `deeppink`: `#ff1493`
+pub const DEEPPINK: Rgb<u8> = Rgb::new_const(255, 20, 147);
+/// `deepskyblue`: `#00bfff`
+pub const DEEPSKYBLUE: Rgb<u8> = Rgb::new_const(0, 191, 255);
+/// `dimgray`: `#696969`
+pub const DIMGRAY: Rgb<u8> = Rgb::new_const(105, 105, 105);
+/// `dimgrey`: `#696969`
+pub const DIMGREY: Rgb<u8> = Rgb::new_const(105, 105, 105);
+/// `dodgerblue`: `#1e90ff`
+pub const DODGERBLUE: Rgb<u8> = Rgb::new_const(30, 144, 255);
+/// `firebrick`: `#b22222`
+pub const FIREBRICK: Rgb<u8> = Rgb::new_const(178, 34, 34);
+/// `floralwhite`: `#fffaf0`
+pub const FLORALWHITE: Rgb<u8> = Rgb::new_const(255, 250, 240);
+/// `forestgreen`: `#228b22`
+pub const FORESTGREEN: Rgb<u8> = Rgb::new_const(34, 139, 34);
+/// `fuchsia`: `#ff00ff`
+pub const FUCHSIA: Rgb<u8> = Rgb::new_const(255, 0, 255);
+/// `gainsboro`: `#dcdcdc`
+pub const GAINSBORO: Rgb<u8> = Rgb::new_const(220, 220, 220);
+/// `ghostwhite`: `#f8f8ff`
+pub const GHOSTWHITE: Rgb<u8> = Rgb::new_const(248, 248, 255);
+/// `gold`: `#ffd700`
+pub const GOLD: Rgb<u8> = Rgb::new_const(255, 215, 0);
+/// `goldenrod`: `#daa520`
+pub const GOLDENROD: Rgb<u8> = Rgb::new_const(218, 165, 32);
+/// `gray`: `#808080`
+pub const GRAY: Rgb<u8> = Rgb::new_const(128, 128, 128);
+/// `green`: `#008000`
+pub const GREEN: Rgb<u8> = Rgb::new_const(0, 128, 0);
+/// `greenyellow`: `#adff2f`
+pub const GREENYELLOW: Rgb<u8> = Rgb::new_const(173, 255, 47);
+/// `grey`: `#808080`
+pub const GREY: Rgb<u8> = Rgb::new_const(128, 128, 128);
+/// `honeydew`: `#f0fff0`
+pub const HONEYDEW: Rgb<u8> = Rgb::new_const(240, 255, 240);
+/// `hotpink`: `#ff69b4`
+pub const HOTPINK: Rgb<u8> = Rgb::new_const(255, 105, 180);
+/// `indianred`: `#cd5c5c`
+pub const INDIANRED: Rgb<u8> = Rgb::new_const(205, 92, 92);
+/// `indigo`: `#4b0082`
+pub const INDIGO: Rgb<u8> = Rgb::new_const(75, 0, 130);
+/// `ivory`: `#fffff0`
+pub const IVORY: Rgb<u8> = Rgb::new_const(255, 255, 240);
+/// `khaki`: `#f0e68c`
+pub const KHAKI: Rgb<u8> = Rgb::new_const(240, 230, 140);
+/// `lavender`: `#e6e6fa`
+pub const LAVENDER: Rgb<u8> = Rgb::new_const(230, 230, 250);
+/// `lavenderblush`: `#fff0f5`
+pub const LAVENDERBLUSH: Rgb<u8> = Rgb::new_const(255, 240, 245);
+/// `lawngreen`: `#7cfc00`
+pub const LAWNGREEN: Rgb<u8> = Rgb::new_const(124, 252, 0);
+/// `lemonchiffon`: `#fffacd`
+pub const LEMONCHIFFON: Rgb<u8> = Rgb::new_const(255, 250, 205);
+/// `lightblue`: `#add8e6`
+pub const LIGHTBLUE: Rgb<u8> = Rgb::new_const(173, 216, 230);
+/// `lightcoral`: `#f08080`
+pub const LIGHTCORAL: Rgb<u8> = Rgb::new_const(240, 128, 128);
+/// `lightcyan`: `#e0ffff`
+pub const LIGHTCYAN: Rgb<u8> = Rgb::new_const(224, 255, 255);
+/// `lightgoldenrodyellow`: `#fafad2`
+pub const LIGHTGOLDENRODYELLOW: Rgb<u8> = Rgb::new_const(250, 250, 210);
+/// `lightgray`: `#d3d3d3`
+pub const LIGHTGRAY: Rgb<u8> = Rgb::new_const(211, 211, 211);
+/// `lightgreen`: `#90ee90`
+pub const LIGHTGREEN: Rgb<u8> = Rgb::new_const(144, 238, 144);
+/// `lightgrey`: `#d3d3d3`
+pub const LIGHTGREY: Rgb<u8> = Rgb::new_const(211, 211, 211);
+/// `lightpink`: `#ffb6c1`
+pub const LIGHTPINK: Rgb<u8> = Rgb::new_const(255, 182, 193);
+/// `lightsalmon`: `#ffa07a`
+pub const LIGHTSALMON: Rgb<u8> = Rgb::new_const(255, 160, 122);
+/// `lightseagreen`: `#20b2aa`
+pub const LIGHTSEAGREEN: Rgb<u8> = Rgb::new_const(32, 178, 170);
+/// `lightskyblue`: `#87cefa`
+pub const LIGHTSKYBLUE: Rgb<u8> = Rgb::new_const(135, 206, 250);
+/// `lightslategray`: `#778899`
+pub const LIGHTSLATEGRAY: Rgb<u8> = Rgb::new_const(119, 136, 153);
+/// `lightslategrey`: `#778899`
+pub const LIGHTSLATEGREY: Rgb<u8> = Rgb::new_const(119, 136, 153);
+/// `lightsteelblue`: `#b0c4de`
+pub const LIGHTSTEELBLUE: Rgb<u8> = Rgb::new_const(176, 196, 222);
+/// `lightyellow`: `#ffffe0`
+pub const LIGHTYELLOW: Rgb<u8> = Rgb::new_const(255, 255, 224);
+/// `lime`: `#00ff00`
+pub const LIME: Rgb<u8> = Rgb::new_const(0, 255, 0);
+/// `limegreen`: `#32cd32`
+pub const LIMEGREEN: Rgb<u8> = Rgb::new_const(50, 205, 50);
+/// `linen`: `#faf0e6`
+pub const LINEN: Rgb<u8> = Rgb::new_const(250, 240, 230);
+/// `magenta`: `#ff00ff`
+pub const MAGENTA: Rgb<u8> = Rgb::new_const(255, 0, 255);
+/// `maroon`: `#800000`
+pub const MAROON: Rgb<u8> = Rgb::new_const(128, 0, 0);
+/// `mediumaquamarine`: `#66cdaa`
+pub const MEDIUMAQUAMARINE: Rgb<u8> = Rgb::new_const(102, 205, 170);
+/// `mediumblue`: `#0000cd`
+pub const MEDIUMBLUE: Rgb<u8> = Rgb::new_const(0, 0, 205);
+/// `mediumorchid`: `#ba55d3`
+pub const MEDIUMORCHID: Rgb<u8> = Rgb::new_const(186, 85, 211);
+/// `mediumpurple`: `#9370db`
+pub const MEDIUMPURPLE: Rgb<u8> = Rgb::new_const(147, 112, 219);
+/// `mediumseagreen`: `#3cb371`
+pub const MEDIUMSEAGREEN: Rgb<u8> = Rgb::new_const(60, 179, 113);
+/// `mediumslateblue`: `#7b68ee`
+pub const MEDIUMSLATEBLUE: Rgb<u8> = Rgb::new_const(123, 104, 238);
+/// `mediumspringgreen`: `#00fa9a`
+pub const MEDIUMSPRINGGREEN: Rgb<u8> = Rgb::new_const(0, 250, 154);
+/// `mediumturquoise`: `#48d1cc`
+pub const MEDIUMTURQUOISE: Rgb<u8> = Rgb::new_const(72, 209, 204);
+/// `mediumvioletred`: `#c71585`
+pub const MEDIUMVIOLETRED: Rgb<u8> = Rgb::new_const(199, 21, 133);
+/// `midnightblue`: `#191970`
+pub const MIDNIGHTBLUE: Rgb<u8> = Rgb::new_const(25, 25, 112);
+/// `mintcream`: `#f5fffa`
+pub const MINTCREAM: Rgb<u8> = Rgb::new_const(245, 255, 250);
+/// `mistyrose`: `#ffe4e1`
+pub const MISTYROSE: Rgb<u8> = Rgb::new_const(255, 228, 225);
+/// `moccasin`: `#ffe4b5`
+pub const MOCCASIN: Rgb<u8> = Rgb::new_const(255, 228, 181);
+/// `navajowhite`: `#ffdead`
+pub const NAVAJOWHITE: Rgb<u8> = Rgb::new_const(255, 222, 173);
+/// `navy`: `#000080`
+pub const NAVY: Rgb<u8> = Rgb::new_const(0, 0, 128);
+/// `oldlace`: `#fdf5e6`
+pub const OLDLACE: Rgb<u8> = Rgb::new_const(253, 245, 230);
+/// `olive`: `#808000`
+pub const OLIVE: Rgb<u8> = Rgb::new_const(128, 128, 0);
+/// `olivedrab`: `#6b8e23`
+pub const OLIVEDRAB: Rgb<u8> = Rgb::new_const(107, 142, 35);
+/// `orange`: `#ffa500`
+pub const ORANGE: Rgb<u8> = Rgb::new_const(255, 165, 0);
+/// `orangered`: `#ff4500`
+pub const ORANGERED: Rgb<u8> = Rgb::new_const(255, 69, 0);
+/// `orchid`: `#da70d6`
+pub const ORCHID: Rgb<u8> = Rgb::new_const(218, 112, 214);
+/// `palegoldenrod`: `#eee8aa`
+pub const PALEGOLDENROD: Rgb<u8> = Rgb::new_const(238, 232, 170);
+/// `palegreen`: `#98fb98`
+pub const PALEGREEN: Rgb<u8> = Rgb::new_const(152, 251, 152);
+/// `paleturquoise`: `#afeeee`
+pub const PALETURQUOISE: Rgb<u8> = Rgb::new_const(175, 238, 238);
+/// `palevioletred`: `#db7093`
+pub const PALEVIOLETRED: Rgb<u8> = Rgb::new_const(219, 112, 147);
+/// `papayawhip`: `#ffefd5`
+pub const PAPAYAWHIP: Rgb<u8> = Rgb::new_const(255, 239, 213);
+/// `peachpuff`: `#ffdab9`
+pub const PEACHPUFF: Rgb<u8> = Rgb::new_const(255, 218, 185);
+/// `peru`: `#cd853f`
+pub const PERU: Rgb<u8> = Rgb::new_const(205, 133, 63);
+/// `pink`: `#ffc0cb`
+pub const PINK: Rgb<u8> = Rgb::new_const(255, 192, 203);
+/// `plum`: `#dda0dd`
+pub const PLUM: Rgb<u8> = Rgb::new_const(221, 160, 221);
+/// `powderblue`: `#b0e0e6`
+pub const POWDERBLUE: Rgb<u8> = Rgb::new_const(176, 224, 230);
+/// `purple`: `#800080`
+pub const PURPLE: Rgb<u8> = Rgb::new_const(128, 0, 128);
+/// `rebeccapurple`: `#663399`
+pub const REBECCAPURPLE: Rgb<u8> = Rgb::new_const(102, 51, 153);
+/// `red`: `#ff0000`
+pub const RED: Rgb<u8> = Rgb::new_const(255, 0, 0);
+/// `rosybrown`: `#bc8f8f`
+pub const ROSYBROWN: Rgb<u8> = Rgb::new_const(188, 143, 143);
+/// `royalblue`: `#4169e1`
+pub const ROYALBLUE: Rgb<u8> = Rgb::new_const(65, 105, 225);
+/// `saddlebrown`: `#8b4513`
+pub const SADDLEBROWN: Rgb<u8> = Rgb::new_const(139, 69, 19);
+/// `salmon`: `#fa8072`
+pub const SALMON: Rgb<u8> = Rgb::new_const(250, 128, 114);
+/// `sandybrown`: `#f4a460`
+pub const SANDYBROWN: Rgb<u8> = Rgb::new_const(244, 164, 96);
+/// `seagreen`: `#2e8b57`
+pub const SEAGREEN: Rgb<u8> = Rgb::new_const(46, 139, 87);
+/// `seashell`: `#fff5ee`
+pub const SEASHELL: Rgb<u8> = Rgb::new_const(255, 245, 238);
+/// `sienna`: `#a0522d`
+pub const SIENNA: Rgb<u8> = Rgb::new_const(160, 82, 45);
+/// `silver`: `#c0c0c0`
+pub const SILVER: Rgb<u8> = Rgb::new_const(192, 192, 192);
+/// `skyblue`: `#87ceeb`
+pub const SKYBLUE: Rgb<u8> = Rgb::new_const(135, 206, 235);
+/// `slateblue`: `#6a5acd`
+pub const SLATEBLUE: Rgb<u8> = Rgb::new_const(106, 90, 205);
+/// `slategray`: `#708090`
+pub const SLATEGRAY: Rgb<u8> = Rgb::new_const(112, 128, 144);
+/// `slategrey`: `#708090`
+pub const SLATEGREY: Rgb<u8> = Rgb::new_const(112, 128, 144);
+/// `snow`: `#fffafa`
+pub const SNOW: Rgb<u8> = Rgb::new_const(255, 250, 250);
+/// `springgreen`: `#00ff7f`
+pub const SPRINGGREEN: Rgb<u8> = Rgb::new_const(0, 255, 127);
+/// `steelblue`: `#4682b4`
+pub const STEELBLUE: Rgb<u8> = Rgb::new_const(70, 130, 180);
+/// `tan`: `#d2b48c`
+pub const TAN: Rgb<u8> = Rgb::new_const(210, 180, 140);
+/// `teal`: `#008080`
+pub const TEAL: Rgb<u8> = Rgb::new_const(0, 128, 128);
+/// `thistle`: `#d8bfd8`
+pub const THISTLE: Rgb<u8> = Rgb::new_const(216, 191, 216);
+/// `tomato`: `#ff6347`
+pub const TOMATO: Rgb<u8> = Rgb::new_const(255, 99, 71);
+/// `turquoise`: `#40e0d0`
+pub const TURQUOISE: Rgb<u8> = Rgb::new_const(64, 224, 208);
+/// `violet`: `#ee82ee`
+pub const VIOLET: Rgb<u8> = Rgb::new_const(238, 130, 238);
+/// `wheat`: `#f5deb3`
+pub const WHEAT: Rgb<u8> = Rgb::new_const(245, 222, 179);
+/// `white`: `#ffffff`
+pub const WHITE: Rgb<u8> = Rgb::new_const(255, 255, 255);
+/// `whitesmoke`: `#f5f5f5`
+pub const WHITESMOKE: Rgb<u8> = Rgb::new_const(245, 245, 245);
+/// `yellow`: `#ffff00`
+pub const YELLOW: Rgb<u8> = Rgb::new_const(255, 255, 0);
+/// `yellowgreen`: `#9acd32`
+pub const YELLOWGREEN: Rgb<u8> = Rgb::new_const(154, 205, 50);
+
+/// Every named color with its lowercase name, sorted by name
+pub const COLORS: [(&str, Rgb<u8>); 148] = [
+    ("aliceblue", ALICEBLUE),
+    ("antiquewhite", ANTIQUEWHITE),
+    ("aqua", AQUA),
+    ("aquamarine", AQUAMARINE),
+    ("azure", AZURE),
+    ("beige", BEIGE),
+    ("bisque", BISQUE),
+    ("black", BLACK),
+    ("blanchedalmond", BLANCHEDALMOND),
+    ("blue", BLUE),
+    ("blueviolet", BLUEVIOLET),
+    ("brown", BROWN),
+    ("burlywood", BURLYWOOD),
+    ("cadetblue", CADETBLUE),
+    ("chartreuse", CHARTREUSE),
+    ("chocolate", CHOCOLATE),
+    ("coral", CORAL),
+    ("cornflowerblue", CORNFLOWERBLUE),
+    ("cornsilk", CORNSILK),
+    ("crimson", CRIMSON),
+    ("cyan", CYAN),
+    ("darkblue", DARKBLUE),
+    ("darkcyan", DARKCYAN),
+    ("darkgoldenrod", DARKGOLDENROD),
+    ("darkgray", DARKGRAY),
+    ("darkgreen", DARKGREEN),
+    ("darkgrey", DARKGREY),
+    ("darkkhaki", DARKKHAKI),
+    ("darkmagenta", DARKMAGENTA),
+    ("darkolivegreen", DARKOLIVEGREEN),
+    ("darkorange", DARKORANGE),
+    ("darkorchid", DARKORCHID),
+    ("darkred", DARKRED),
+    ("darksalmon", DARKSALMON),
+    ("darkseagreen", DARKSEAGREEN),
+    ("darkslateblue", DARKSLATEBLUE),
+    ("darkslategray", DARKSLATEGRAY),
+    ("darkslategrey", DARKSLATEGREY),
+    ("darkturquoise", DARKTURQUOISE),
+    ("darkviolet", DARKVIOLET),
+    ("deeppink", DEEPPINK),
+    ("deepskyblue", DEEPSKYBLUE),
+    ("dimgray", DIMGRAY),
+    ("dimgrey", DIMGREY),
+    ("dodgerblue", DODGERBLUE),
+    ("firebrick", FIREBRICK),
+    ("floralwhite", FLORALWHITE),
+    ("forestgreen", FORESTGREEN),
+    ("fuchsia", FUCHSIA),
+    ("gainsboro", GAINSBORO),
+    ("ghostwhite", GHOSTWHITE),
+    ("gold", GOLD),
+    ("goldenrod", GOLDENROD),
+    ("gray", GRAY),
+    ("green", GREEN),
+    ("greenyellow", GREENYELLOW),
+    ("grey", GREY),
+    ("honeydew", HONEYDEW),
+    ("hotpink", HOTPINK),
+    ("indianred", INDIANRED),
+    ("indigo", INDIGO),
+    ("ivory", IVORY),
+    ("khaki", KHAKI),
+    ("lavender", LAVENDER),
+    ("lavenderblush", LAVENDERBLUSH),
+    ("lawngreen", LAWNGREEN),
+    ("lemonchiffon", LEMONCHIFFON),
+    ("lightblue", LIGHTBLUE),
+    ("lightcoral", LIGHTCORAL),
+    ("lightcyan", LIGHTCYAN),
+    ("lightgoldenrodyellow", LIGHTGOLDENRODYELLOW),
+    ("lightgray", LIGHTGRAY),
+    ("lightgreen", LIGHTGREEN),
+    ("lightgrey", LIGHTGREY),
+    ("lightpink", LIGHTPINK),
+    ("lightsalmon", LIGHTSALMON),
+    ("lightseagreen", LIGHTSEAGREEN),
+    ("lightskyblue", LIGHTSKYBLUE),
+    ("lightslategray", LIGHTSLATEGRAY),
+    ("lightslategrey", LIGHTSLATEGREY),
+    ("lightsteelblue", LIGHTSTEELBLUE),
+    ("lightyellow", LIGHTYELLOW),
+    ("lime", LIME),
+    ("limegreen", LIMEGREEN),
+    ("linen", LINEN),
+    ("magenta", MAGENTA),
+    ("maroon", MAROON),
+    ("mediumaquamarine", MEDIUMAQUAMARINE),
+    ("mediumblue", MEDIUMBLUE),
+    ("mediumorchid", MEDIUMORCHID),
+    ("mediumpurple", MEDIUMPURPLE),
+    ("mediumseagreen", MEDIUMSEAGREEN),
+    ("mediumslateblue", MEDIUMSLATEBLUE),
+    ("mediumspringgreen", MEDIUMSPRINGGREEN),
+    ("mediumturquoise", MEDIUMTURQUOISE),
+    ("mediumvioletred", MEDIUMVIOLETRED),
+    ("midnightblue", MIDNIGHTBLUE),
+    ("mintcream", MINTCREAM),
+    ("mistyrose", MISTYROSE),
+    ("moccasin", MOCCASIN),
+    ("navajowhite", NAVAJOWHITE),
+    ("navy", NAVY),
+    ("oldlace", OLDLACE),
+    ("olive", OLIVE),
+    ("olivedrab", OLIVEDRAB),
+    ("orange", ORANGE),
+    ("orangered", ORANGERED),
+    ("orchid", ORCHID),
+    ("palegoldenrod", PALEGOLDENROD),
+    ("palegreen", PALEGREEN),
+    ("paleturquoise", PALETURQUOISE),
+    ("palevioletred", PALEVIOLETRED),
+    ("papayawhip", PAPAYAWHIP),
+    ("peachpuff", PEACHPUFF),
+    ("peru", PERU),
+    ("pink", PINK),
+    ("plum", PLUM),
+    ("powderblue", POWDERBLUE),
+    ("purple", PURPLE),
+    ("rebeccapurple", REBECCAPURPLE),
+    ("red", RED),
+    ("rosybrown", ROSYBROWN),
+    ("royalblue", ROYALBLUE),
+    ("saddlebrown", SADDLEBROWN),
+    ("salmon", SALMON),
+    ("sandybrown", SANDYBROWN),
+    ("seagreen", SEAGREEN),
+    ("seashell", SEASHELL),
+    ("sienna", SIENNA),
+    ("silver", SILVER),
+    ("skyblue", SKYBLUE),
+    ("slateblue", SLATEBLUE),
+    ("slategray", SLATEGRAY),
+    ("slategrey", SLATEGREY),
+    ("snow", SNOW),
+    ("springgreen", SPRINGGREEN),
+    ("steelblue", STEELBLUE),
+    ("tan", TAN),
+    ("teal", TEAL),
+    ("thistle", THISTLE),
+    ("tomato", TOMATO),
+    ("turquoise", TURQUOISE),
+    ("violet", VIOLET),
+    ("wheat", WHEAT),
+    ("white", WHITE),
+    ("whitesmoke", WHITESMOKE),
+    ("yellow", YELLOW),
+    ("yellowgreen", YELLOWGREEN),
+];
+
+/// Returns the color with the CSS name `name`, or `None` if there is no such color
+///
+/// Names are matched ignoring ASCII case, as in CSS.
+pub fn lookup(name: &str) -> Option<Rgb<u8>> {
+    COLORS
+        .binary_search_by(|&(n, _)| n.bytes().cmp(name.bytes().map(|b| b.to_ascii_lowercase())))
+        .ok()
+        .map(|i| COLORS[i].1)
+}
+
+/// Returns the name of `color` if it is exactly one of the named colors
+///
+/// For colors with several names, the first in alphabetical order is returned, such as `"aqua"`
+/// rather than `"cyan"` and `"darkgray"` rather than `"darkgrey"`.
+pub fn name_of(color: &Rgb<u8>) -> Option<&'static str> {
+    COLORS
+        .iter()
+        .find(|(_, c)| c == color)
+        .map(|&(name, _)| name)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_lookup() {
+        assert_eq!(lookup("red"), Some(RED));
+        assert_eq!(lookup("Red"), Some(Rgb::new(255, 0, 0)));
+        assert_eq!(
+            lookup("LIGHTGOLDENRODYELLOW"),
+            Some(Rgb::new(250, 250, 210))
+        );
+        assert_eq!(lookup("aliceblue"), Some(ALICEBLUE));
+        assert_eq!(lookup("yellowgreen"), Some(YELLOWGREEN));
+        assert_eq!(lookup("grey"), lookup("gray"));
+        assert_eq!(lookup("notacolor"), None);
+        assert_eq!(lookup(""), None);
+        assert_eq!(lookup("transparent"), None);
+
+        for pair in COLORS.windows(2) {
+            assert!(pair[0].0 < pair[1].0);
+        }
+        for &(name, color) in COLORS.iter() {
+            assert_eq!(lookup(name), Some(color));
+        }
+    }
+
+    #[test]
+    fn test_name_of() {
+        assert_eq!(name_of(&CYAN), Some("aqua"));
+        assert_eq!(name_of(&Rgb::new(0, 0, 0)), Some("black"));
+        assert_eq!(name_of(&SLATEGREY), Some("slategray"));
+        assert_eq!(name_of(&Rgb::new(1, 2, 3)), None);
+    }
+}
@@ -0,0 +1,378 @@
+//! The Oklab perceptually uniform device-independent color space
+
+#![allow(non_snake_case)]
+
+use crate::channel::{
+    ChannelCast, ChannelFormatCast, ColorChannel, FreeChannel, FreeChannelScalar, PosFreeChannel,
+};
+use crate::color::{Bounded, ChannelAccess, ChannelOrder, Color, FromTuple, HomogeneousColor, Lerp};
+use crate::convert::FromColor;
+use crate::linalg::Matrix3;
+use crate::tags::OklabTag;
+use crate::xyz::Xyz;
+use std::fmt;
+
+/// The Oklab perceptually uniform device-independent color space
+///
+/// Oklab was designed by Björn Ottosson as a simple alternative to [`Lab`](struct.Lab.html) with
+/// better hue linearity and more uniform lightness and chroma, particularly for blues. It is the
+/// space used for interpolation and gamut mapping by CSS Color Level 4.
+///
+/// Unlike `Lab`, Oklab has a single fixed reference white: it is defined against XYZ relative to
+/// D65. Converting from XYZ relative to another white point requires chromatic adaptation first.
+///
+/// The `L` value represents lightness in the range [0, 1], while a and b are green vs red and
+/// blue vs yellow respectively. A polar version of `Oklab` exists as [`Oklch`](struct.Oklch.html).
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct Oklab<T> {
+    L: PosFreeChannel<T>,
+    a: FreeChannel<T>,
+    b: FreeChannel<T>,
+}
+
+impl<T> Oklab<T>
+where
+    T: FreeChannelScalar,
+{
+    /// Construct a new `Oklab` value
+    pub fn new(L: T, a: T, b: T) -> Self {
+        Oklab {
+            L: PosFreeChannel::new(L),
+            a: FreeChannel::new(a),
+            b: FreeChannel::new(b),
+        }
+    }
+
+    impl_color_color_cast_square!(Oklab { L, a, b }, chan_traits = { FreeChannelScalar });
+
+    /// Returns the `L` lightness channel scalar
+    pub fn L(&self) -> T {
+        self.L.0.clone()
+    }
+    /// Returns the `a` green-red channel scalar
+    pub fn a(&self) -> T {
+        self.a.0.clone()
+    }
+    /// Returns the `b` yellow-blue channel scalar
+    pub fn b(&self) -> T {
+        self.b.0.clone()
+    }
+    /// Returns a mutable reference to the `L` lightness channel scalar
+    pub fn L_mut(&mut self) -> &mut T {
+        &mut self.L.0
+    }
+    /// Returns a mutable reference to the `a` green-red channel scalar
+    pub fn a_mut(&mut self) -> &mut T {
+        &mut self.a.0
+    }
+    /// Returns a mutable reference to the `b` yellow-blue channel scalar
+    pub fn b_mut(&mut self) -> &mut T {
+        &mut self.b.0
+    }
+    /// Set the `L` channel scalar
+    pub fn set_L(&mut self, val: T) {
+        self.L.0 = val;
+    }
+    /// Set the `a` channel scalar
+    pub fn set_a(&mut self, val: T) {
+        self.a.0 = val;
+    }
+    /// Set the `b` channel scalar
+    pub fn set_b(&mut self, val: T) {
+        self.b.0 = val;
+    }
+
+    /// Returns the euclidean distance between `self` and `other`
+    ///
+    /// This is the $`\Delta E_{OK}`$ color difference used by CSS Color Level 4.
+    pub fn distance(&self, other: &Self) -> T {
+        let dL = self.L() - other.L();
+        let da = self.a() - other.a();
+        let db = self.b() - other.b();
+        (dL * dL + da * da + db * db).sqrt()
+    }
+}
+
+impl<T> Color for Oklab<T>
+where
+    T: FreeChannelScalar,
+{
+    type Tag = OklabTag;
+    type ChannelsTuple = (T, T, T);
+
+    #[inline]
+    fn num_channels() -> u32 {
+        3
+    }
+    fn to_tuple(self) -> Self::ChannelsTuple {
+        (self.L.0, self.a.0, self.b.0)
+    }
+}
+
+impl<T> FromTuple for Oklab<T>
+where
+    T: FreeChannelScalar,
+{
+    fn from_tuple(values: (T, T, T)) -> Self {
+        let (L, a, b) = values;
+        Oklab::new(L, a, b)
+    }
+}
+
+impl<T> HomogeneousColor for Oklab<T>
+where
+    T: FreeChannelScalar,
+{
+    type ChannelFormat = T;
+
+    impl_color_homogeneous_color_square!(Oklab<T> {L, a, b});
+}
+
+impl<T> ChannelOrder for Oklab<T>
+where
+    T: FreeChannelScalar,
+{
+    impl_color_channel_order!({L, a, b});
+}
+
+impl<T> ChannelAccess for Oklab<T>
+where
+    T: FreeChannelScalar,
+{
+    impl_color_channel_access_square!(T {L, a, b});
+}
+
+impl_color_elementwise_ops!(Oklab<T> {L, a, b} where { T: FreeChannelScalar });
+
+impl<T> Bounded for Oklab<T>
+where
+    T: FreeChannelScalar,
+{
+    fn normalize(self) -> Self {
+        Oklab::new(self.L.normalize().0, self.a(), self.b())
+    }
+    fn is_normalized(&self) -> bool {
+        self.L.is_normalized()
+    }
+}
+
+impl<T> Lerp for Oklab<T>
+where
+    T: FreeChannelScalar + Lerp,
+{
+    type Position = <FreeChannel<T> as Lerp>::Position;
+    impl_color_lerp_square!(Oklab { L, a, b });
+}
+
+#[cfg(feature = "approx")]
+impl<T> approx::AbsDiffEq for Oklab<T>
+where
+    T: FreeChannelScalar + approx::AbsDiffEq,
+    T::Epsilon: Clone,
+{
+    impl_abs_diff_eq!({L, a, b});
+}
+#[cfg(feature = "approx")]
+impl<T> approx::RelativeEq for Oklab<T>
+where
+    T: FreeChannelScalar + approx::RelativeEq,
+    T::Epsilon: Clone,
+{
+    impl_rel_eq!({L, a, b});
+}
+#[cfg(feature = "approx")]
+impl<T> approx::UlpsEq for Oklab<T>
+where
+    T: FreeChannelScalar + approx::UlpsEq,
+    T::Epsilon: Clone,
+{
+    impl_ulps_eq!({L, a, b});
+}
+
+impl<T> Default for Oklab<T>
+where
+    T: FreeChannelScalar,
+{
+    impl_color_default!(Oklab {
+        L: PosFreeChannel,
+        a: FreeChannel,
+        b: FreeChannel
+    });
+}
+
+impl<T> fmt::Display for Oklab<T>
+where
+    T: FreeChannelScalar + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Oklab({}, {}, {})", self.L, self.a, self.b)
+    }
+}
+
+impl<T> FromColor<Oklab<T>> for Oklab<T>
+where
+    T: FreeChannelScalar,
+{
+    /// Returns a copy of `from`, so `Oklab` can be passed where a color convertible to `Oklab` is
+    /// expected
+    fn from_color(from: &Oklab<T>) -> Self {
+        from.clone()
+    }
+}
+
+impl<T> FromColor<Xyz<T>> for Oklab<T>
+where
+    T: FreeChannelScalar,
+{
+    /// Construct an `Oklab` value from an `Xyz` value relative to D65
+    fn from_color(from: &Xyz<T>) -> Self {
+        let (l, m, s) = Oklab::<T>::xyz_to_lms().transform_vector(from.clone().to_tuple());
+        let (L, a, b) = Oklab::<T>::lms_to_lab().transform_vector((l.cbrt(), m.cbrt(), s.cbrt()));
+        Oklab::new(L, a, b)
+    }
+}
+
+impl<T> FromColor<Oklab<T>> for Xyz<T>
+where
+    T: FreeChannelScalar,
+{
+    /// Construct an `Xyz` value relative to D65 from an `Oklab` value
+    fn from_color(from: &Oklab<T>) -> Self {
+        let (l, m, s) = Oklab::<T>::lab_to_lms().transform_vector(from.clone().to_tuple());
+        let (x, y, z) =
+            Oklab::<T>::lms_to_xyz().transform_vector((l * l * l, m * m * m, s * s * s));
+        Xyz::new(x, y, z)
+    }
+}
+
+impl<T> Oklab<T>
+where
+    T: FreeChannelScalar,
+{
+    fn xyz_to_lms() -> Matrix3<T> {
+        Matrix3::new(Self::cast_matrix([
+            0.819022437996703,
+            0.3619062600528904,
+            -0.1288737815209879,
+            0.0329836539323885,
+            0.9292868615863434,
+            0.0361446663506424,
+            0.0481771893596242,
+            0.2642395317527308,
+            0.6335478284694309,
+        ]))
+    }
+    fn lms_to_lab() -> Matrix3<T> {
+        Matrix3::new(Self::cast_matrix([
+            0.210454268309314,
+            0.7936177747023054,
+            -0.0040720430116193,
+            1.9779985324311684,
+            -2.42859224204858,
+            0.450593709617411,
+            0.0259040424655478,
+            0.7827717124575296,
+            -0.8086757549230774,
+        ]))
+    }
+    fn lab_to_lms() -> Matrix3<T> {
+        Matrix3::new(Self::cast_matrix([
+            1.0,
+            0.3963377773761749,
+            0.2158037573099136,
+            1.0,
+            -0.1055613458156586,
+            -0.0638541728258133,
+            1.0,
+            -0.0894841775298119,
+            -1.2914855480194092,
+        ]))
+    }
+    fn lms_to_xyz() -> Matrix3<T> {
+        Matrix3::new(Self::cast_matrix([
+            1.2268798758459243,
+            -0.5578149944602171,
+            0.2813910456659647,
+            -0.0405757452148008,
+            1.112286803280317,
+            -0.0717110580655164,
+            -0.0763729366746601,
+            -0.4214933324022432,
+            1.5869240198367816,
+        ]))
+    }
+    fn cast_matrix(values: [f64; 9]) -> [T; 9] {
+        let mut out = [T::default(); 9];
+        for (o, v) in out.iter_mut().zip(values.iter()) {
+            *o = num_traits::cast(*v).unwrap();
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::white_point::{WhitePoint, D65};
+    use approx::*;
+
+    #[test]
+    fn test_construct() {
+        let c1 = Oklab::new(0.5, -0.1, 0.2);
+        assert_eq!(c1.L(), 0.5);
+        assert_eq!(c1.a(), -0.1);
+        assert_eq!(c1.b(), 0.2);
+        assert_eq!(c1.to_tuple(), (0.5, -0.1, 0.2));
+        assert_relative_eq!(Oklab::from_tuple(c1.to_tuple()), c1);
+        assert_relative_eq!(c1.color_cast::<f32>().color_cast(), c1, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_normalize() {
+        let c1 = Oklab::new(0.8, -0.3, 0.3);
+        assert!(c1.is_normalized());
+        assert_relative_eq!(c1.normalize(), c1);
+
+        let c2 = Oklab::new(-0.1, 0.2, -0.2);
+        assert!(!c2.is_normalized());
+        assert_relative_eq!(c2.normalize(), Oklab::new(0.0, 0.2, -0.2));
+    }
+
+    #[test]
+    fn test_lerp() {
+        let c1 = Oklab::new(0.2, 0.1, -0.1);
+        let c2 = Oklab::new(0.6, -0.1, 0.3);
+        assert_relative_eq!(c1.lerp(&c2, 0.0), c1);
+        assert_relative_eq!(c1.lerp(&c2, 1.0), c2);
+        assert_relative_eq!(c1.lerp(&c2, 0.5), Oklab::new(0.4, 0.0, 0.1));
+    }
+
+    #[test]
+    fn test_from_xyz() {
+        let white = Oklab::from_color(&D65.get_xyz());
+        assert_relative_eq!(white, Oklab::new(1.0, 0.0, 0.0), epsilon = 1e-4);
+
+        let c1 = Xyz::new(0.4123908, 0.2126390, 0.0193308);
+        let t1 = Oklab::from_color(&c1);
+        assert_relative_eq!(t1, Oklab::new(0.627955, 0.224863, 0.125846), epsilon = 1e-6);
+        assert_relative_eq!(Xyz::from_color(&t1), c1, epsilon = 1e-6);
+
+        let c2 = Xyz::new(0.2, 0.3, 0.4);
+        let t2 = Oklab::from_color(&c2);
+        assert_relative_eq!(
+            t2,
+            Oklab::new(0.655537, -0.114708, -0.026125),
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(Xyz::from_color(&t2), c2, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_distance() {
+        let c1 = Oklab::new(0.5, 0.1, 0.0);
+        let c2 = Oklab::new(0.5, -0.2, 0.4);
+        assert_relative_eq!(c1.distance(&c2), 0.5);
+        assert_relative_eq!(c1.distance(&c1), 0.0);
+    }
+}
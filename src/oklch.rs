@@ -0,0 +1,348 @@
+//! The Oklch device-independent polar color space
+
+#![allow(non_snake_case)]
+
+use crate::channel::{
+    AngularChannel, AngularChannelScalar, ChannelCast, ChannelFormatCast, ColorChannel,
+    FreeChannelScalar, PosFreeChannel,
+};
+use crate::color::{Bounded, ChannelOrder, Color, FromTuple, Lerp, PolarColor, PolarLerp};
+use crate::convert::{FromColor, GetChroma, GetHue};
+use crate::oklab::Oklab;
+use crate::tags::OklchTag;
+use angle::{Angle, Deg, FromAngle, IntoAngle, Rad};
+use std::fmt;
+
+/// The Oklch device-independent polar color space
+///
+/// `Oklch` is a polar transformation of [`Oklab`](struct.Oklab.html), in the same way that
+/// [`Lchab`](struct.Lchab.html) is of `Lab`. Holding `L` and `h` constant while reducing the
+/// chroma moves a color toward gray with little visible change in hue, which makes it well suited
+/// to gamut mapping and to generating palettes.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct Oklch<T, A = Deg<T>> {
+    L: PosFreeChannel<T>,
+    chroma: PosFreeChannel<T>,
+    hue: AngularChannel<A>,
+}
+
+impl<T, A> Oklch<T, A>
+where
+    T: FreeChannelScalar,
+    A: AngularChannelScalar,
+{
+    /// Construct a new `Oklch` value
+    pub fn new(L: T, chroma: T, hue: A) -> Self {
+        Oklch {
+            L: PosFreeChannel::new(L),
+            chroma: PosFreeChannel::new(chroma),
+            hue: AngularChannel::new(hue),
+        }
+    }
+
+    impl_color_color_cast_angular!(
+        Oklch { L, chroma, hue },
+        chan_traits = { FreeChannelScalar }
+    );
+
+    /// Returns the `L` lightness channel scalar
+    pub fn L(&self) -> T {
+        self.L.0.clone()
+    }
+    /// Returns the `C` chroma channel scalar
+    pub fn chroma(&self) -> T {
+        self.chroma.0.clone()
+    }
+    /// Returns the `h` hue channel scalar
+    pub fn hue(&self) -> A {
+        self.hue.0.clone()
+    }
+    /// Returns a mutable reference to the `L` lightness channel scalar
+    pub fn L_mut(&mut self) -> &mut T {
+        &mut self.L.0
+    }
+    /// Returns a mutable reference to the `C` chroma channel scalar
+    pub fn chroma_mut(&mut self) -> &mut T {
+        &mut self.chroma.0
+    }
+    /// Returns a mutable reference to the `h` hue channel scalar
+    pub fn hue_mut(&mut self) -> &mut A {
+        &mut self.hue.0
+    }
+    /// Sets the `L` channel scalar
+    pub fn set_L(&mut self, val: T) {
+        self.L.0 = val;
+    }
+    /// Sets the `chroma` channel scalar
+    pub fn set_chroma(&mut self, val: T) {
+        self.chroma.0 = val;
+    }
+    /// Sets the `hue` channel scalar
+    pub fn set_hue(&mut self, val: A) {
+        self.hue.0 = val;
+    }
+}
+
+impl<T, A> Color for Oklch<T, A>
+where
+    T: FreeChannelScalar,
+    A: AngularChannelScalar,
+{
+    type Tag = OklchTag;
+    type ChannelsTuple = (T, T, A);
+
+    fn num_channels() -> u32 {
+        3
+    }
+    fn to_tuple(self) -> Self::ChannelsTuple {
+        (self.L.0, self.chroma.0, self.hue.0)
+    }
+}
+
+impl<T, A> ChannelOrder for Oklch<T, A>
+where
+    T: FreeChannelScalar,
+    A: AngularChannelScalar,
+{
+    impl_color_channel_order!({L, chroma, hue});
+}
+
+impl<T, A> PolarColor for Oklch<T, A>
+where
+    T: FreeChannelScalar,
+    A: AngularChannelScalar,
+{
+    type Angular = A;
+    type Cartesian = T;
+}
+
+impl<T, A> FromTuple for Oklch<T, A>
+where
+    T: FreeChannelScalar,
+    A: AngularChannelScalar,
+{
+    fn from_tuple(values: Self::ChannelsTuple) -> Self {
+        Oklch::new(values.0, values.1, values.2)
+    }
+}
+
+impl<T, A> Lerp for Oklch<T, A>
+where
+    T: FreeChannelScalar + Lerp,
+    A: AngularChannelScalar + Lerp,
+{
+    type Position = A::Position;
+
+    impl_color_lerp_angular!(Oklch<T> {hue, L, chroma});
+}
+
+impl<T, A> PolarLerp for Oklch<T, A>
+where
+    T: FreeChannelScalar + Lerp,
+    A: AngularChannelScalar + Lerp,
+{
+    impl_color_lerp_hue_mode!(Oklch<T> {hue, L, chroma});
+}
+
+impl<T, A> Bounded for Oklch<T, A>
+where
+    T: FreeChannelScalar,
+    A: AngularChannelScalar,
+{
+    fn normalize(self) -> Self {
+        Oklch::new(
+            self.L.normalize().0,
+            self.chroma.normalize().0,
+            self.hue.normalize().0,
+        )
+    }
+    fn is_normalized(&self) -> bool {
+        self.L.is_normalized() && self.chroma.is_normalized() && self.hue.is_normalized()
+    }
+}
+
+#[cfg(feature = "approx")]
+impl<T, A> approx::AbsDiffEq for Oklch<T, A>
+where
+    T: FreeChannelScalar + approx::AbsDiffEq<Epsilon = A::Epsilon>,
+    A: AngularChannelScalar + approx::AbsDiffEq,
+    A::Epsilon: Clone + num_traits::Float,
+{
+    impl_abs_diff_eq!({L, chroma, hue});
+}
+#[cfg(feature = "approx")]
+impl<T, A> approx::RelativeEq for Oklch<T, A>
+where
+    T: FreeChannelScalar + approx::RelativeEq<Epsilon = A::Epsilon>,
+    A: AngularChannelScalar + approx::RelativeEq,
+    A::Epsilon: Clone + num_traits::Float,
+{
+    impl_rel_eq!({L, chroma, hue});
+}
+#[cfg(feature = "approx")]
+impl<T, A> approx::UlpsEq for Oklch<T, A>
+where
+    T: FreeChannelScalar + approx::UlpsEq<Epsilon = A::Epsilon>,
+    A: AngularChannelScalar + approx::UlpsEq,
+    A::Epsilon: Clone + num_traits::Float,
+{
+    impl_ulps_eq!({L, chroma, hue});
+}
+
+impl<T, A> Default for Oklch<T, A>
+where
+    T: FreeChannelScalar + num_traits::Zero,
+    A: AngularChannelScalar + num_traits::Zero,
+{
+    fn default() -> Self {
+        Oklch {
+            L: Default::default(),
+            chroma: Default::default(),
+            hue: Default::default(),
+        }
+    }
+}
+
+impl<T, A> fmt::Display for Oklch<T, A>
+where
+    T: FreeChannelScalar + fmt::Display,
+    A: AngularChannelScalar + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Oklch({}, {}, {})", self.L, self.chroma, self.hue)
+    }
+}
+
+impl<T, A> GetChroma for Oklch<T, A>
+where
+    T: FreeChannelScalar,
+    A: AngularChannelScalar,
+{
+    type ChromaType = T;
+    fn get_chroma(&self) -> T {
+        self.chroma()
+    }
+}
+
+impl<T, A> GetHue for Oklch<T, A>
+where
+    T: FreeChannelScalar,
+    A: AngularChannelScalar,
+{
+    impl_color_get_hue_angular!(Oklch);
+}
+
+impl<T, A> FromColor<Oklab<T>> for Oklch<T, A>
+where
+    T: FreeChannelScalar,
+    A: AngularChannelScalar + FromAngle<Rad<T>> + Angle,
+{
+    /// Construct an `Oklch` value from an `Oklab` value
+    fn from_color(from: &Oklab<T>) -> Self {
+        let L = from.L();
+        let chroma = (from.a() * from.a() + from.b() * from.b()).sqrt();
+        let hue = A::from_angle(Rad::atan2(from.b(), from.a()));
+
+        Oklch::new(L, chroma, <A as Angle>::normalize(hue))
+    }
+}
+
+impl<T, A> FromColor<Oklch<T, A>> for Oklab<T>
+where
+    T: FreeChannelScalar,
+    A: AngularChannelScalar + Angle<Scalar = T>,
+{
+    /// Construct an `Oklab` value from an `Oklch` value
+    fn from_color(from: &Oklch<T, A>) -> Self {
+        let L = from.L();
+        let a = from.chroma() * from.hue().cos();
+        let b = from.chroma() * from.hue().sin();
+
+        Oklab::new(L, a, b)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::color::HueMode;
+    use angle::Turns;
+    use approx::*;
+
+    #[test]
+    fn test_construct() {
+        let c1 = Oklch::new(0.55, 0.12, Deg(90.0));
+        assert_relative_eq!(c1.L(), 0.55);
+        assert_relative_eq!(c1.chroma(), 0.12);
+        assert_relative_eq!(c1.hue(), Deg(90.0));
+        assert_eq!(c1.to_tuple(), (0.55, 0.12, Deg(90.0)));
+        assert_relative_eq!(Oklch::from_tuple(c1.to_tuple()), c1);
+        assert_relative_eq!(
+            c1.color_cast::<f64, Turns<f64>>(),
+            Oklch::new(0.55, 0.12, Turns(0.25))
+        );
+    }
+
+    #[test]
+    fn test_lerp() {
+        let c1 = Oklch::new(0.25, 0.3, Deg(300.0));
+        let c2 = Oklch::new(0.75, 0.1, Deg(50.0));
+        assert_relative_eq!(c1.lerp(&c2, 0.0), c1);
+        assert_relative_eq!(c1.lerp(&c2, 1.0), c2);
+        assert_relative_eq!(c1.lerp(&c2, 0.5), Oklch::new(0.5, 0.2, Deg(355.0)));
+    }
+
+    #[test]
+    fn test_lerp_with_hue_mode() {
+        let c1 = Oklch::new(0.25, 0.3, Deg(300.0));
+        let c2 = Oklch::new(0.75, 0.1, Deg(50.0));
+        assert_relative_eq!(
+            c1.lerp_with_hue_mode(&c2, 0.5, HueMode::Shorter),
+            Oklch::new(0.5, 0.2, Deg(355.0))
+        );
+        assert_relative_eq!(
+            c1.lerp_with_hue_mode(&c2, 0.5, HueMode::Longer),
+            Oklch::new(0.5, 0.2, Deg(175.0))
+        );
+        assert_relative_eq!(
+            c1.lerp_with_hue_mode(&c2, 0.5, HueMode::Increasing),
+            Oklch::new(0.5, 0.2, Deg(355.0))
+        );
+        assert_relative_eq!(
+            c1.lerp_with_hue_mode(&c2, 0.5, HueMode::Decreasing),
+            Oklch::new(0.5, 0.2, Deg(175.0))
+        );
+    }
+
+    #[test]
+    fn test_normalize() {
+        let c1 = Oklch::new(0.5, 0.2, Deg(400.0));
+        assert!(!c1.is_normalized());
+        assert_relative_eq!(c1.normalize(), Oklch::new(0.5, 0.2, Deg(40.0)));
+    }
+
+    #[test]
+    fn test_get_hue() {
+        let c1 = Oklch::new(0.2, 0.1, Deg(180.0));
+        assert_eq!(c1.get_chroma(), 0.1);
+        assert_eq!(c1.get_hue::<Turns<_>>(), Turns(0.5));
+    }
+
+    #[test]
+    fn test_from_oklab() {
+        let c1 = Oklab::new(0.655537, -0.114708, -0.026125);
+        let t1 = Oklch::from_color(&c1);
+        assert_relative_eq!(
+            t1,
+            Oklch::new(0.655537, 0.117645, Deg(192.8301)),
+            epsilon = 1e-4
+        );
+        assert_relative_eq!(Oklab::from_color(&t1), c1, epsilon = 1e-6);
+
+        let c2 = Oklab::new(0.5, 0.0, 0.0);
+        let t2 = Oklch::from_color(&c2);
+        assert_relative_eq!(t2, Oklch::new(0.5, 0.0, Deg(0.0)));
+        assert_relative_eq!(Oklab::from_color(&t2), c2);
+    }
+}
@@ -0,0 +1,232 @@
+//! Pixel types storing RGB channels in a non-canonical memory order
+//!
+//! `Rgb` and `Rgba` always lay out their channels as red, green, blue and then alpha. Many
+//! platform buffers store them differently: Win32 DIBs, Direct2D and OpenCV use BGR or BGRA,
+//! while some video and imaging APIs use ARGB or ABGR. The types in this module are `#[repr(C)]`
+//! with their fields declared in memory order, so a slice of pixels in a foreign buffer can be
+//! viewed as a slice of one of these types and each pixel converted to the canonical color with
+//! `From`. The conversions only move fields, so they compile down to a shuffle of the channels.
+//!
+//! Since each type has the same size as its canonical color, a whole buffer can also be reordered
+//! without allocating: functions like [`rgb_to_bgr_in_place`](fn.rgb_to_bgr_in_place.html)
+//! rearrange the channels of every pixel and return the same memory as a slice of the new type.
+//!
+//! | Type | Memory order |
+//! |------|--------------|
+//! | [`Bgr`](struct.Bgr.html) | blue, green, red |
+//! | [`Bgra`](struct.Bgra.html) | blue, green, red, alpha |
+//! | [`Abgr`](struct.Abgr.html) | alpha, blue, green, red |
+//! | [`Argb`](struct.Argb.html) | alpha, red, green, blue |
+//!
+//! ## Examples:
+//!
+//! ```rust
+//! # extern crate prisma;
+//!
+//! use prisma::{Rgb, Rgba};
+//! use prisma::order::Bgra;
+//!
+//! // A pixel as read from a BGRA framebuffer
+//! let pixel = Bgra { blue: 0x30u8, green: 0x20, red: 0x10, alpha: 0xFF };
+//! let color = Rgba::from(pixel);
+//! assert_eq!(color, Rgba::new(Rgb::new(0x10, 0x20, 0x30), 0xFF));
+//! assert_eq!(Bgra::from(color), pixel);
+//! ```
+
+use crate::alpha::Rgba;
+use crate::channel::PosNormalChannelScalar;
+use crate::rgb::Rgb;
+use std::mem;
+use std::ptr;
+use std::slice;
+
+macro_rules! impl_channel_order {
+    ($name: ident, $doc: expr, { $($field: ident),* }) => {
+        #[doc = $doc]
+        #[repr(C)]
+        #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+        pub struct $name<T> {
+            $(
+                #[doc = concat!("The ", stringify!($field), " channel")]
+                pub $field: T,
+            )*
+        }
+
+        impl<T> $name<T> {
+            /// Convert the pixel to an array of its channels in memory order
+            pub fn into_array(self) -> [T; impl_channel_order!(@count $($field)*)] {
+                [$(self.$field),*]
+            }
+        }
+    };
+    (@count $($field: ident)*) => {
+        0 $(+ impl_channel_order!(@one $field))*
+    };
+    (@one $field: ident) => {
+        1
+    };
+}
+
+impl_channel_order!(Bgr, "A pixel with its channels stored as blue, green, red", {
+    blue,
+    green,
+    red
+});
+impl_channel_order!(
+    Bgra,
+    "A pixel with its channels stored as blue, green, red, alpha",
+    { blue, green, red, alpha }
+);
+impl_channel_order!(
+    Abgr,
+    "A pixel with its channels stored as alpha, blue, green, red",
+    { alpha, blue, green, red }
+);
+impl_channel_order!(
+    Argb,
+    "A pixel with its channels stored as alpha, red, green, blue",
+    { alpha, red, green, blue }
+);
+
+impl<T> From<Rgb<T>> for Bgr<T>
+where
+    T: PosNormalChannelScalar,
+{
+    fn from(rgb: Rgb<T>) -> Self {
+        Bgr {
+            blue: rgb.blue(),
+            green: rgb.green(),
+            red: rgb.red(),
+        }
+    }
+}
+
+impl<T> From<Bgr<T>> for Rgb<T>
+where
+    T: PosNormalChannelScalar,
+{
+    fn from(bgr: Bgr<T>) -> Self {
+        Rgb::new(bgr.red, bgr.green, bgr.blue)
+    }
+}
+
+macro_rules! impl_alpha_order_conversions {
+    ($name: ident) => {
+        impl<T> From<Rgba<T>> for $name<T>
+        where
+            T: PosNormalChannelScalar,
+        {
+            fn from(rgba: Rgba<T>) -> Self {
+                let (rgb, alpha) = rgba.decompose();
+                $name {
+                    red: rgb.red(),
+                    green: rgb.green(),
+                    blue: rgb.blue(),
+                    alpha,
+                }
+            }
+        }
+
+        impl<T> From<$name<T>> for Rgba<T>
+        where
+            T: PosNormalChannelScalar,
+        {
+            fn from(pixel: $name<T>) -> Self {
+                Rgba::new(Rgb::new(pixel.red, pixel.green, pixel.blue), pixel.alpha)
+            }
+        }
+    };
+}
+
+impl_alpha_order_conversions!(Bgra);
+impl_alpha_order_conversions!(Abgr);
+impl_alpha_order_conversions!(Argb);
+
+macro_rules! impl_reorder_in_place {
+    ($fn_name: ident, $from: ident, $to: ident) => {
+        #[doc = concat!("Convert a buffer of `", stringify!($from), "` pixels to `",
+                    stringify!($to), "` in place")]
+        pub fn $fn_name<T>(pixels: &mut [$from<T>]) -> &mut [$to<T>]
+        where
+            T: PosNormalChannelScalar,
+        {
+            assert_eq!(mem::size_of::<$from<T>>(), mem::size_of::<$to<T>>());
+            assert_eq!(mem::align_of::<$from<T>>(), mem::align_of::<$to<T>>());
+            let len = pixels.len();
+            let from = pixels.as_mut_ptr();
+            let to = from as *mut $to<T>;
+            for i in 0..len {
+                // The conversion only moves channels and cannot panic, so every pixel is read
+                // exactly once before its slot is overwritten
+                unsafe {
+                    let pixel = ptr::read(from.add(i));
+                    ptr::write(to.add(i), $to::from(pixel));
+                }
+            }
+            unsafe { slice::from_raw_parts_mut(to, len) }
+        }
+    };
+}
+
+impl_reorder_in_place!(rgb_to_bgr_in_place, Rgb, Bgr);
+impl_reorder_in_place!(bgr_to_rgb_in_place, Bgr, Rgb);
+impl_reorder_in_place!(rgba_to_bgra_in_place, Rgba, Bgra);
+impl_reorder_in_place!(bgra_to_rgba_in_place, Bgra, Rgba);
+impl_reorder_in_place!(rgba_to_abgr_in_place, Rgba, Abgr);
+impl_reorder_in_place!(abgr_to_rgba_in_place, Abgr, Rgba);
+impl_reorder_in_place!(rgba_to_argb_in_place, Rgba, Argb);
+impl_reorder_in_place!(argb_to_rgba_in_place, Argb, Rgba);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_reorder() {
+        let rgb = Rgb::new(1u8, 2, 3);
+        let bgr = Bgr::from(rgb);
+        assert_eq!(bgr.into_array(), [3, 2, 1]);
+        assert_eq!(Rgb::from(bgr), rgb);
+
+        let rgba = Rgba::new(Rgb::new(1u8, 2, 3), 4);
+        assert_eq!(Bgra::from(rgba).into_array(), [3, 2, 1, 4]);
+        assert_eq!(Abgr::from(rgba).into_array(), [4, 3, 2, 1]);
+        assert_eq!(Argb::from(rgba).into_array(), [4, 1, 2, 3]);
+        assert_eq!(Rgba::from(Bgra::from(rgba)), rgba);
+        assert_eq!(Rgba::from(Abgr::from(rgba)), rgba);
+        assert_eq!(Rgba::from(Argb::from(rgba)), rgba);
+    }
+
+    #[test]
+    fn test_in_place() {
+        let mut pixels = vec![Rgb::new(1u8, 2, 3), Rgb::new(4, 5, 6)];
+        let bgr = rgb_to_bgr_in_place(&mut pixels);
+        assert_eq!(bgr[1].into_array(), [6, 5, 4]);
+        let rgb = bgr_to_rgb_in_place(bgr);
+        assert_eq!(rgb, [Rgb::new(1, 2, 3), Rgb::new(4, 5, 6)]);
+
+        let mut pixels = vec![Rgba::new(Rgb::new(0.1f32, 0.2, 0.3), 0.4); 3];
+        let argb = rgba_to_argb_in_place(&mut pixels);
+        assert_eq!(argb[2].into_array(), [0.4, 0.1, 0.2, 0.3]);
+        let abgr = rgba_to_abgr_in_place(argb_to_rgba_in_place(argb));
+        assert_eq!(abgr[0].into_array(), [0.4, 0.3, 0.2, 0.1]);
+        let bgra = rgba_to_bgra_in_place(abgr_to_rgba_in_place(abgr));
+        assert_eq!(bgra[1].into_array(), [0.3, 0.2, 0.1, 0.4]);
+        assert_eq!(
+            bgra_to_rgba_in_place(bgra)[0],
+            Rgba::new(Rgb::new(0.1, 0.2, 0.3), 0.4)
+        );
+    }
+
+    #[test]
+    fn test_layout() {
+        assert_eq!(mem::size_of::<Bgr<u8>>(), 3);
+        assert_eq!(mem::size_of::<Bgra<u16>>(), 8);
+        let buffer = [0x30u8, 0x20, 0x10, 0xFF];
+        let pixel: Bgra<u8> = unsafe { mem::transmute(buffer) };
+        assert_eq!(
+            Rgba::from(pixel),
+            Rgba::new(Rgb::new(0x10, 0x20, 0x30), 0xFF)
+        );
+    }
+}
@@ -0,0 +1,310 @@
+//! Packing of colors into `u32` and 16-bit pixel values
+//!
+//! Framebuffers and platform APIs commonly store a color as a single `u32`, one byte per channel.
+//! The order of the channels differs between them, so it is given explicitly with
+//! [`PackedOrder`](enum.PackedOrder.html), read from the most significant byte to the least:
+//!
+//! * `0xAARRGGBB` ([`Argb`](enum.PackedOrder.html#variant.Argb)) is used by Win32 DIBs, Android
+//!   and most software framebuffers.
+//! * `0xRRGGBBAA` ([`Rgba`](enum.PackedOrder.html#variant.Rgba)) matches how colors are written
+//!   in hex.
+//! * `0xAABBGGRR` ([`Abgr`](enum.PackedOrder.html#variant.Abgr)) is what the bytes of a web canvas
+//!   `ImageData` read as on a little-endian machine.
+//! * `0xBBGGRRAA` ([`Bgra`](enum.PackedOrder.html#variant.Bgra)) completes the set.
+//!
+//! `Rgba<u8>` has `from_packed` and `to_packed` taking an order, along with shortcuts for the
+//! two most common ones. `Rgb<u8>` packs into the low three bytes as `0x00RRGGBB`, or as
+//! `0x00BBGGRR` like a Win32 `COLORREF`.
+//!
+//! For embedded displays and older hardware, [`Rgb565`](struct.Rgb565.html),
+//! [`Rgb555`](struct.Rgb555.html) and [`Argb1555`](struct.Argb1555.html) pack a color into 16
+//! bits. Converting from 8 bits rounds each channel to the nearest level, and converting back
+//! replicates the high bits into the low bits, so that every packed color survives a round trip
+//! through `Rgb<u8>` and full intensity stays at 255.
+//!
+//! ## Examples:
+//!
+//! ```rust
+//! # extern crate prisma;
+//!
+//! use prisma::{Rgb, Rgba};
+//! use prisma::packed::{PackedOrder, Rgb565};
+//!
+//! let color = Rgba::new(Rgb::new(0x11u8, 0x22, 0x33), 0x44);
+//! assert_eq!(color.to_packed_argb(), 0x44112233);
+//! assert_eq!(color.to_packed_rgba(), 0x11223344);
+//! assert_eq!(color.to_packed(PackedOrder::Abgr), 0x44332211);
+//! assert_eq!(Rgba::from_packed(0x44332211, PackedOrder::Abgr), color);
+//!
+//! assert_eq!(Rgb::from_packed_rgb(0x00ff8000), Rgb::new(255, 128, 0));
+//! assert_eq!(Rgb::new(255u8, 128, 0).to_packed_bgr(), 0x000080ff);
+//!
+//! let packed = Rgb565::from(Rgb::new(255u8, 128, 0));
+//! assert_eq!(packed.to_bits(), 0b11111_100000_00000);
+//! assert_eq!(Rgb::from(packed), Rgb::new(255, 130, 0));
+//! ```
+
+// Binary literals are grouped by channel
+#![allow(clippy::unusual_byte_groupings)]
+
+use crate::alpha::Rgba;
+use crate::rgb::Rgb;
+
+/// The order of the channels in a packed `u32` color, from the most significant byte
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PackedOrder {
+    /// `0xAARRGGBB`
+    Argb,
+    /// `0xRRGGBBAA`
+    Rgba,
+    /// `0xAABBGGRR`
+    Abgr,
+    /// `0xBBGGRRAA`
+    Bgra,
+}
+
+impl PackedOrder {
+    /// Returns the bit shifts of the red, green, blue and alpha channels
+    fn shifts(self) -> (u32, u32, u32, u32) {
+        match self {
+            PackedOrder::Argb => (16, 8, 0, 24),
+            PackedOrder::Rgba => (24, 16, 8, 0),
+            PackedOrder::Abgr => (0, 8, 16, 24),
+            PackedOrder::Bgra => (8, 16, 24, 0),
+        }
+    }
+}
+
+impl Rgba<u8> {
+    /// Unpack a color from a `u32` with the channels in `order`
+    pub fn from_packed(value: u32, order: PackedOrder) -> Self {
+        let (r, g, b, a) = order.shifts();
+        let byte = |shift: u32| (value >> shift) as u8;
+        Rgba::new(Rgb::new(byte(r), byte(g), byte(b)), byte(a))
+    }
+    /// Pack the color into a `u32` with the channels in `order`
+    pub fn to_packed(&self, order: PackedOrder) -> u32 {
+        let (r, g, b, a) = order.shifts();
+        (u32::from(self.red()) << r)
+            | (u32::from(self.green()) << g)
+            | (u32::from(self.blue()) << b)
+            | (u32::from(self.alpha()) << a)
+    }
+    /// Unpack a color from a `0xAARRGGBB` value
+    pub fn from_packed_argb(value: u32) -> Self {
+        Self::from_packed(value, PackedOrder::Argb)
+    }
+    /// Pack the color into a `0xAARRGGBB` value
+    pub fn to_packed_argb(&self) -> u32 {
+        self.to_packed(PackedOrder::Argb)
+    }
+    /// Unpack a color from a `0xRRGGBBAA` value
+    pub fn from_packed_rgba(value: u32) -> Self {
+        Self::from_packed(value, PackedOrder::Rgba)
+    }
+    /// Pack the color into a `0xRRGGBBAA` value
+    pub fn to_packed_rgba(&self) -> u32 {
+        self.to_packed(PackedOrder::Rgba)
+    }
+}
+
+impl Rgb<u8> {
+    /// Unpack a color from a `0x00RRGGBB` value, ignoring the high byte
+    pub fn from_packed_rgb(value: u32) -> Self {
+        Rgb::new((value >> 16) as u8, (value >> 8) as u8, value as u8)
+    }
+    /// Pack the color into a `0x00RRGGBB` value
+    pub fn to_packed_rgb(&self) -> u32 {
+        (u32::from(self.red()) << 16) | (u32::from(self.green()) << 8) | u32::from(self.blue())
+    }
+    /// Unpack a color from a `0x00BBGGRR` value, ignoring the high byte
+    pub fn from_packed_bgr(value: u32) -> Self {
+        Rgb::new(value as u8, (value >> 8) as u8, (value >> 16) as u8)
+    }
+    /// Pack the color into a `0x00BBGGRR` value
+    pub fn to_packed_bgr(&self) -> u32 {
+        (u32::from(self.blue()) << 16) | (u32::from(self.green()) << 8) | u32::from(self.red())
+    }
+}
+
+/// Reduce an 8-bit channel to `bits` bits, rounding to the nearest level
+fn reduce(value: u8, bits: u32) -> u16 {
+    let max = (1u32 << bits) - 1;
+    ((u32::from(value) * max + 127) / 255) as u16
+}
+
+/// Expand a channel of `bits` bits to 8 bits by replicating its high bits
+fn expand(value: u16, bits: u32) -> u8 {
+    let value = value & ((1 << bits) - 1);
+    ((value << (8 - bits)) | (value >> (2 * bits - 8))) as u8
+}
+
+macro_rules! impl_packed_16 {
+    ($name: ident, $doc: expr, $red: expr, $green: expr, $blue: expr) => {
+        #[doc = $doc]
+        #[repr(transparent)]
+        #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+        pub struct $name(u16);
+
+        impl $name {
+            /// Construct from the packed bits
+            pub const fn from_bits(bits: u16) -> Self {
+                $name(bits)
+            }
+            /// Returns the packed bits
+            pub const fn to_bits(self) -> u16 {
+                self.0
+            }
+        }
+
+        impl From<Rgb<u8>> for $name {
+            fn from(rgb: Rgb<u8>) -> Self {
+                $name(
+                    (reduce(rgb.red(), $red) << ($green + $blue))
+                        | (reduce(rgb.green(), $green) << $blue)
+                        | reduce(rgb.blue(), $blue),
+                )
+            }
+        }
+
+        impl From<$name> for Rgb<u8> {
+            fn from(packed: $name) -> Self {
+                Rgb::new(
+                    expand(packed.0 >> ($green + $blue), $red),
+                    expand(packed.0 >> $blue, $green),
+                    expand(packed.0, $blue),
+                )
+            }
+        }
+    };
+}
+
+impl_packed_16!(
+    Rgb565,
+    "A color packed into 16 bits as `0bRRRRRGGGGGGBBBBB`",
+    5,
+    6,
+    5
+);
+impl_packed_16!(
+    Rgb555,
+    "A color packed into 16 bits as `0b0RRRRRGGGGGBBBBB`",
+    5,
+    5,
+    5
+);
+
+/// A color with a 1-bit alpha packed into 16 bits as `0bARRRRRGGGGGBBBBB`
+///
+/// Converting from `Rgba<u8>` makes the color opaque when its alpha is at least 128.
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Argb1555(u16);
+
+impl Argb1555 {
+    /// Construct from the packed bits
+    pub const fn from_bits(bits: u16) -> Self {
+        Argb1555(bits)
+    }
+    /// Returns the packed bits
+    pub const fn to_bits(self) -> u16 {
+        self.0
+    }
+    /// Returns whether the alpha bit is set
+    pub fn is_opaque(self) -> bool {
+        self.0 & 0x8000 != 0
+    }
+}
+
+impl From<Rgba<u8>> for Argb1555 {
+    fn from(rgba: Rgba<u8>) -> Self {
+        let alpha = if rgba.alpha() >= 128 { 0x8000 } else { 0 };
+        Argb1555(alpha | Rgb555::from(*rgba.color()).0)
+    }
+}
+
+impl From<Argb1555> for Rgba<u8> {
+    fn from(packed: Argb1555) -> Self {
+        let alpha = if packed.is_opaque() { 255 } else { 0 };
+        Rgba::new(Rgb::from(Rgb555(packed.0 & 0x7FFF)), alpha)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_packed_rgba() {
+        let color = Rgba::new(Rgb::new(0xAAu8, 0xBB, 0xCC), 0xDD);
+        let cases = [
+            (PackedOrder::Argb, 0xDDAABBCC),
+            (PackedOrder::Rgba, 0xAABBCCDD),
+            (PackedOrder::Abgr, 0xDDCCBBAA),
+            (PackedOrder::Bgra, 0xCCBBAADD),
+        ];
+        for &(order, packed) in cases.iter() {
+            assert_eq!(color.to_packed(order), packed);
+            assert_eq!(Rgba::from_packed(packed, order), color);
+        }
+        assert_eq!(Rgba::from_packed_argb(0xDDAABBCC), color);
+        assert_eq!(color.to_packed_argb(), 0xDDAABBCC);
+        assert_eq!(Rgba::from_packed_rgba(0xAABBCCDD), color);
+        assert_eq!(color.to_packed_rgba(), 0xAABBCCDD);
+    }
+
+    #[test]
+    fn test_packed_rgb() {
+        let color = Rgb::new(0x12u8, 0x34, 0x56);
+        assert_eq!(color.to_packed_rgb(), 0x00123456);
+        assert_eq!(color.to_packed_bgr(), 0x00563412);
+        assert_eq!(Rgb::from_packed_rgb(0xFF123456), color);
+        assert_eq!(Rgb::from_packed_bgr(0xFF563412), color);
+    }
+
+    #[test]
+    fn test_packed_16() {
+        assert_eq!(Rgb565::from(Rgb::new(255u8, 255, 255)).to_bits(), 0xFFFF);
+        assert_eq!(Rgb555::from(Rgb::new(255u8, 255, 255)).to_bits(), 0x7FFF);
+        assert_eq!(
+            Rgb::from(Rgb565::from_bits(0xFFFF)),
+            Rgb::new(255, 255, 255)
+        );
+        assert_eq!(
+            Rgb::from(Rgb555::from_bits(0x7FFF)),
+            Rgb::new(255, 255, 255)
+        );
+        assert_eq!(
+            Rgb565::from(Rgb::new(0x80u8, 0x40, 0x08)).to_bits(),
+            0b10000_010000_00001
+        );
+        assert_eq!(
+            Rgb::from(Rgb565::from_bits(0b10000_010000_00001)),
+            Rgb::new(0x84, 0x41, 0x08)
+        );
+
+        for bits in 0..=0xFFFFu16 {
+            assert_eq!(
+                Rgb565::from(Rgb::from(Rgb565::from_bits(bits))).to_bits(),
+                bits
+            );
+            let bits = bits & 0x7FFF;
+            assert_eq!(
+                Rgb555::from(Rgb::from(Rgb555::from_bits(bits))).to_bits(),
+                bits
+            );
+        }
+    }
+
+    #[test]
+    fn test_argb1555() {
+        let packed = Argb1555::from(Rgba::new(Rgb::new(255u8, 0, 132), 200));
+        assert_eq!(packed.to_bits(), 0b1_11111_00000_10000);
+        assert!(packed.is_opaque());
+        assert_eq!(Rgba::from(packed), Rgba::new(Rgb::new(255, 0, 132), 255));
+
+        let packed = Argb1555::from(Rgba::new(Rgb::new(255u8, 0, 132), 127));
+        assert!(!packed.is_opaque());
+        assert_eq!(Rgba::from(packed), Rgba::new(Rgb::new(255, 0, 132), 0));
+    }
+}
@@ -0,0 +1,840 @@
+//! Working with fixed palettes of colors
+//!
+//! [`PaletteIndex`](struct.PaletteIndex.html) finds the palette color nearest to a query color.
+//! It pre-builds a k-d tree over the palette, so each query only compares against a small part of
+//! the palette instead of every entry. This speeds up quantizing images to large palettes and
+//! mapping colors to terminal palettes.
+//!
+//! [`DistinctColors`](struct.DistinctColors.html) generates palettes instead, producing an endless
+//! sequence of colors that are easy to tell apart, such as for the series of a chart. When the
+//! number of colors is known up front, [`CategoricalPalette`](struct.CategoricalPalette.html)
+//! searches for the set of colors that are furthest apart, optionally against a background and for
+//! viewers with color vision deficiencies.
+//!
+//! With the `heapless` feature enabled, [`FixedPalette`](struct.FixedPalette.html) answers the
+//! same nearest-color queries as `PaletteIndex` for a fixed number of colors, without heap
+//! allocation.
+
+use crate::channel::{
+    AngularChannelScalar, ChannelFormatCast, FreeChannelScalar, PosNormalChannelScalar,
+};
+use crate::color::Color;
+use crate::color_space::named::SRgb;
+use crate::color_space::ColorSpace;
+use crate::convert::FromColor;
+use crate::cvd::{simulate, Deficiency};
+use crate::distance::ColorDistance;
+use crate::encoding::EncodableColor;
+use crate::oklab::Oklab;
+use crate::oklch::Oklch;
+use crate::rgb::Rgb;
+use crate::xyz::Xyz;
+use num_traits::{cast, Float};
+
+/// A spatial index answering nearest-color queries against a fixed palette
+///
+/// The palette colors are stored in a k-d tree keyed on their three channels, and distances are
+/// measured by the [`ColorDistance`](../distance/trait.ColorDistance.html) metric `D`. Palettes in
+/// a perceptually uniform space, such as `Lab` with [`Cie76`](../distance/struct.Cie76.html) or
+/// `Oklab` with [`DeltaEOk`](../distance/struct.DeltaEOk.html), give matches that look closest.
+///
+/// The tree skips parts of the palette whose channels are too far from the query in a single
+/// channel. This is only exact when the distance between two colors is never less than the
+/// difference of any one of their channels, which holds for every metric based on the euclidean
+/// distance between the channels.
+///
+/// ## Examples:
+///
+/// ```rust
+/// # extern crate prisma;
+///
+/// use prisma::Oklab;
+/// use prisma::distance::DeltaEOk;
+/// use prisma::palette::PaletteIndex;
+///
+/// let palette = vec![
+///     Oklab::new(0.2, 0.0, 0.0),
+///     Oklab::new(0.6, 0.2, 0.1),
+///     Oklab::new(0.9, -0.1, 0.1),
+/// ];
+/// let index = PaletteIndex::new(palette, DeltaEOk);
+/// assert_eq!(index.nearest(&Oklab::new(0.55, 0.15, 0.05)), Some(1));
+/// assert_eq!(index.nearest_color(&Oklab::new(0.1, 0.0, 0.0)), Some(&Oklab::new(0.2, 0.0, 0.0)));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct PaletteIndex<C, D>
+where
+    C: Color,
+{
+    palette: Vec<C>,
+    nodes: Vec<Node<C::ChannelsTuple>>,
+    root: Option<usize>,
+    metric: D,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Node<P> {
+    color: usize,
+    point: P,
+    axis: usize,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+impl<T, C, D> PaletteIndex<C, D>
+where
+    T: Float,
+    C: Color<ChannelsTuple = (T, T, T)> + Clone,
+    D: ColorDistance<C, Output = T>,
+{
+    /// Construct a new `PaletteIndex` over `palette`, using `metric` to compare colors
+    pub fn new(palette: Vec<C>, metric: D) -> Self {
+        let mut nodes = Vec::with_capacity(palette.len());
+        let points: Vec<_> = palette.iter().map(|c| c.clone().to_tuple()).collect();
+        let mut order: Vec<_> = (0..palette.len()).collect();
+        let root = build_tree(&mut nodes, &points, &mut order, 0);
+        PaletteIndex {
+            palette,
+            nodes,
+            root,
+            metric,
+        }
+    }
+
+    /// Returns the palette colors, in the order they were given
+    pub fn palette(&self) -> &[C] {
+        &self.palette
+    }
+
+    /// Returns the metric used to compare colors
+    pub fn metric(&self) -> &D {
+        &self.metric
+    }
+
+    /// Returns the number of colors in the palette
+    pub fn len(&self) -> usize {
+        self.palette.len()
+    }
+
+    /// Returns whether the palette is empty
+    pub fn is_empty(&self) -> bool {
+        self.palette.is_empty()
+    }
+
+    /// Returns the palette index of the color nearest to `color`, or `None` if the palette is empty
+    ///
+    /// If several colors are equally near, any one of them may be returned.
+    pub fn nearest(&self, color: &C) -> Option<usize> {
+        let root = self.root?;
+        let point = color.clone().to_tuple();
+        let mut best = (0, T::infinity());
+        self.search(root, color, &point, &mut best);
+        Some(best.0)
+    }
+
+    /// Returns the palette color nearest to `color`, or `None` if the palette is empty
+    pub fn nearest_color(&self, color: &C) -> Option<&C> {
+        self.nearest(color).map(|i| &self.palette[i])
+    }
+
+    fn search(&self, node: usize, color: &C, point: &(T, T, T), best: &mut (usize, T)) {
+        let node = &self.nodes[node];
+        let distance = self.metric.distance(color, &self.palette[node.color]);
+        if distance < best.1 {
+            *best = (node.color, distance);
+        }
+
+        let diff = axis_value(point, node.axis) - axis_value(&node.point, node.axis);
+        let (near, far) = if diff < T::zero() {
+            (node.left, node.right)
+        } else {
+            (node.right, node.left)
+        };
+        if let Some(near) = near {
+            self.search(near, color, point, best);
+        }
+        if let Some(far) = far {
+            if diff.abs() < best.1 {
+                self.search(far, color, point, best);
+            }
+        }
+    }
+}
+
+fn axis_value<T: Copy>(point: &(T, T, T), axis: usize) -> T {
+    match axis {
+        0 => point.0,
+        1 => point.1,
+        _ => point.2,
+    }
+}
+
+/// Build the subtree over `order`, returning the index of its root node
+fn build_tree<T: Float>(
+    nodes: &mut Vec<Node<(T, T, T)>>,
+    points: &[(T, T, T)],
+    order: &mut [usize],
+    depth: usize,
+) -> Option<usize> {
+    if order.is_empty() {
+        return None;
+    }
+
+    let axis = depth % 3;
+    order.sort_by(|&a, &b| {
+        axis_value(&points[a], axis)
+            .partial_cmp(&axis_value(&points[b], axis))
+            .expect("Palette colors must not contain NaN")
+    });
+    let median = order.len() / 2;
+    let index = nodes.len();
+    nodes.push(Node {
+        color: order[median],
+        point: points[order[median]],
+        axis,
+        left: None,
+        right: None,
+    });
+
+    let (left, rest) = order.split_at_mut(median);
+    let left = build_tree(nodes, points, left, depth + 1);
+    let right = build_tree(nodes, points, &mut rest[1..], depth + 1);
+    nodes[index].left = left;
+    nodes[index].right = right;
+    Some(index)
+}
+
+/// A palette with a fixed maximum number of colors that does not allocate
+///
+/// `FixedPalette` stores up to `N` colors inline using `heapless` and answers the same
+/// nearest-color queries as [`PaletteIndex`](struct.PaletteIndex.html). It compares the query
+/// against every color instead of building a tree, which is fast for the small palettes it is
+/// meant for, such as the colors of an LED strip. Like
+/// [`FixedGradient`](../gradient/struct.FixedGradient.html), it still needs `std` to build.
+///
+/// Requires the `heapless` feature.
+#[cfg(feature = "heapless")]
+#[derive(Clone, Debug)]
+pub struct FixedPalette<C, D, const N: usize> {
+    palette: heapless::Vec<C, N>,
+    metric: D,
+}
+
+#[cfg(feature = "heapless")]
+impl<T, C, D, const N: usize> FixedPalette<C, D, N>
+where
+    T: Float,
+    C: Clone,
+    D: ColorDistance<C, Output = T>,
+{
+    /// Construct a new empty `FixedPalette`, using `metric` to compare colors
+    pub fn new(metric: D) -> Self {
+        FixedPalette {
+            palette: heapless::Vec::new(),
+            metric,
+        }
+    }
+
+    /// Construct a new `FixedPalette` holding `colors`
+    ///
+    /// Returns `None` if `colors` holds more than `N` colors.
+    pub fn from_colors(colors: &[C], metric: D) -> Option<Self> {
+        let palette = heapless::Vec::from_slice(colors).ok()?;
+        Some(FixedPalette { palette, metric })
+    }
+
+    /// Add `color` to the end of the palette
+    ///
+    /// If the palette is full, the color is handed back in the `Err` variant.
+    pub fn push(&mut self, color: C) -> Result<(), C> {
+        self.palette.push(color)
+    }
+
+    /// Returns the palette colors, in the order they were added
+    pub fn palette(&self) -> &[C] {
+        &self.palette
+    }
+
+    /// Returns the metric used to compare colors
+    pub fn metric(&self) -> &D {
+        &self.metric
+    }
+
+    /// Returns the number of colors in the palette
+    pub fn len(&self) -> usize {
+        self.palette.len()
+    }
+
+    /// Returns whether the palette is empty
+    pub fn is_empty(&self) -> bool {
+        self.palette.is_empty()
+    }
+
+    /// Returns the palette index of the color nearest to `color`, or `None` if the palette is empty
+    ///
+    /// If several colors are equally near, the first of them is returned.
+    pub fn nearest(&self, color: &C) -> Option<usize> {
+        let mut best: Option<(usize, T)> = None;
+        for (i, entry) in self.palette.iter().enumerate() {
+            let distance = self.metric.distance(color, entry);
+            let closer = match best {
+                Some((_, best_distance)) => distance < best_distance,
+                None => true,
+            };
+            if closer {
+                best = Some((i, distance));
+            }
+        }
+        best.map(|(i, _)| i)
+    }
+
+    /// Returns the palette color nearest to `color`, or `None` if the palette is empty
+    pub fn nearest_color(&self, color: &C) -> Option<&C> {
+        self.nearest(color).map(|i| &self.palette[i])
+    }
+}
+
+/// An endless sequence of visually distinct colors
+///
+/// Each color is rotated from the previous one in `Oklch` by the golden angle, about 137.5°. This
+/// places every new hue in the largest gap left by the hues before it, so any prefix of the
+/// sequence is spread evenly around the hue wheel without knowing its length in advance. All
+/// colors share the same chroma, and their lightness cycles through a list of levels, which keeps
+/// colors with nearby hues apart once the sequence grows long.
+///
+/// Not every lightness and chroma can be displayed at every hue. Chroma up to about 0.12 at
+/// lightness between 0.5 and 0.8 stays inside the sRGB gamut; other colors can be brought into
+/// gamut with a [`GamutMapping`](../gamut/trait.GamutMapping.html).
+///
+/// ## Examples:
+///
+/// ```rust
+/// #[macro_use] extern crate approx;
+/// # extern crate prisma;
+/// extern crate angular_units as angle;
+///
+/// use prisma::Oklch;
+/// use prisma::palette::DistinctColors;
+/// use angle::Deg;
+///
+/// let colors: Vec<Oklch<f64>> = DistinctColors::new(0.7, 0.1).take(3).collect();
+/// assert_relative_eq!(colors[0], Oklch::new(0.7, 0.1, Deg(0.0)), epsilon=1e-9);
+/// assert_relative_eq!(colors[1], Oklch::new(0.7, 0.1, Deg(137.50776)), epsilon=1e-5);
+/// assert_relative_eq!(colors[2], Oklch::new(0.7, 0.1, Deg(275.01553)), epsilon=1e-5);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct DistinctColors<T, A = angle::Deg<T>> {
+    lightness: Vec<T>,
+    chroma: T,
+    start_hue: A,
+    index: usize,
+}
+
+impl<T, A> DistinctColors<T, A>
+where
+    T: FreeChannelScalar + Float,
+    A: AngularChannelScalar,
+    A::Scalar: Float,
+{
+    /// The fraction of a turn between consecutive hues, $`2 - \varphi`$
+    const GOLDEN_FRACTION: f64 = 0.3819660112501051;
+
+    /// Construct a new `DistinctColors` with a fixed `lightness` and `chroma`, starting at hue 0
+    pub fn new(lightness: T, chroma: T) -> Self {
+        DistinctColors {
+            lightness: vec![lightness],
+            chroma,
+            start_hue: A::zero(),
+            index: 0,
+        }
+    }
+
+    /// Construct a new `DistinctColors` cycling through the lightness `levels`
+    ///
+    /// # Panics
+    /// If `levels` is empty.
+    pub fn with_lightness_levels(levels: Vec<T>, chroma: T) -> Self {
+        assert!(
+            !levels.is_empty(),
+            "At least one lightness level is required"
+        );
+        DistinctColors {
+            lightness: levels,
+            chroma,
+            start_hue: A::zero(),
+            index: 0,
+        }
+    }
+
+    /// Returns `self` with the sequence starting at `hue`
+    pub fn with_start_hue(mut self, hue: A) -> Self {
+        self.start_hue = hue;
+        self
+    }
+
+    /// Returns the lightness levels cycled through
+    pub fn lightness_levels(&self) -> &[T] {
+        &self.lightness
+    }
+    /// Returns the chroma of every color
+    pub fn chroma(&self) -> T {
+        self.chroma
+    }
+    /// Returns the hue of the first color
+    pub fn start_hue(&self) -> A {
+        self.start_hue.clone()
+    }
+
+    /// Returns the color at `index` in the sequence, regardless of how far it has been iterated
+    pub fn color_at(&self, index: usize) -> Oklch<T, A> {
+        // Computing each hue from its index avoids accumulating error over long sequences
+        let turns = (index as f64 * Self::GOLDEN_FRACTION).fract();
+        let offset = A::new(A::period() * cast(turns).unwrap());
+        Oklch::new(
+            self.lightness[index % self.lightness.len()],
+            self.chroma,
+            AngularChannelScalar::normalize(self.start_hue.clone() + offset),
+        )
+    }
+}
+
+impl<T, A> Iterator for DistinctColors<T, A>
+where
+    T: FreeChannelScalar + Float,
+    A: AngularChannelScalar,
+    A::Scalar: Float,
+{
+    type Item = Oklch<T, A>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let color = self.color_at(self.index);
+        self.index += 1;
+        Some(color)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+/// Builds categorical palettes whose colors are as distinct from each other as possible
+///
+/// A categorical palette labels unordered categories, such as the series of a chart, so what
+/// matters is that no two of its colors can be confused. [`generate`](#method.generate) searches a
+/// grid of sRGB colors for the set maximizing the smallest $`\Delta E_{OK}`$ between any two of
+/// its colors. The search starts from a greedy farthest-point selection and then swaps single
+/// colors while that increases their distance to the rest of the palette.
+///
+/// The palette can be constrained to:
+///
+/// * Stand out from a background color, which counts as an extra color the palette must be
+///   distinct from.
+/// * A range of `Oklab` lightness, for example to keep text in every color legible.
+/// * Stay distinct for viewers with color vision deficiencies. Each pair of colors is then as far
+///   apart as their smallest distance under normal vision or any of the simulated deficiencies.
+///
+/// ## Examples:
+///
+/// ```rust
+/// # extern crate prisma;
+///
+/// use prisma::Rgb;
+/// use prisma::cvd::Deficiency;
+/// use prisma::palette::CategoricalPalette;
+///
+/// let palette: Vec<Rgb<u8>> = CategoricalPalette::new()
+///     .with_background(Rgb::new(1.0, 1.0, 1.0))
+///     .with_lightness_range(0.4, 0.8)
+///     .with_deficiencies(&[Deficiency::Deuteranopia])
+///     .generate(5);
+/// assert_eq!(palette.len(), 5);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct CategoricalPalette {
+    background: Option<Rgb<f64>>,
+    min_lightness: f64,
+    max_lightness: f64,
+    deficiencies: Vec<Deficiency>,
+    resolution: usize,
+}
+
+impl CategoricalPalette {
+    /// The maximum number of times every color is considered for a swap
+    const MAX_PASSES: usize = 16;
+
+    /// Construct a new `CategoricalPalette` choosing from all of sRGB, without constraints
+    pub fn new() -> Self {
+        CategoricalPalette {
+            background: None,
+            min_lightness: 0.0,
+            max_lightness: 1.0,
+            deficiencies: Vec::new(),
+            resolution: 16,
+        }
+    }
+
+    /// Returns `self` with palette colors kept distinct from the sRGB encoded `background`
+    pub fn with_background(mut self, background: Rgb<f64>) -> Self {
+        self.background = Some(background);
+        self
+    }
+    /// Returns `self` with palette colors restricted to `Oklab` lightness in [`min`, `max`]
+    pub fn with_lightness_range(mut self, min: f64, max: f64) -> Self {
+        self.min_lightness = min;
+        self.max_lightness = max;
+        self
+    }
+    /// Returns `self` with palette colors kept distinct for viewers with any of `deficiencies`
+    pub fn with_deficiencies(mut self, deficiencies: &[Deficiency]) -> Self {
+        self.deficiencies = deficiencies.to_vec();
+        self
+    }
+    /// Returns `self` choosing colors from a grid with `resolution` levels per sRGB channel
+    ///
+    /// Higher resolutions find slightly more distinct palettes, at a cost growing with the cube of
+    /// the resolution. The default is 16.
+    ///
+    /// # Panics
+    /// If `resolution` is less than 2.
+    pub fn with_resolution(mut self, resolution: usize) -> Self {
+        assert!(resolution >= 2, "The resolution must be at least 2");
+        self.resolution = resolution;
+        self
+    }
+
+    /// Returns the background color, if any
+    pub fn background(&self) -> Option<&Rgb<f64>> {
+        self.background.as_ref()
+    }
+    /// Returns the allowed range of `Oklab` lightness as `(min, max)`
+    pub fn lightness_range(&self) -> (f64, f64) {
+        (self.min_lightness, self.max_lightness)
+    }
+    /// Returns the deficiencies the palette is kept distinct for
+    pub fn deficiencies(&self) -> &[Deficiency] {
+        &self.deficiencies
+    }
+    /// Returns the number of levels per sRGB channel of the candidate grid
+    pub fn resolution(&self) -> usize {
+        self.resolution
+    }
+
+    /// Generate a palette of `count` sRGB encoded colors
+    ///
+    /// Fewer than `count` colors are returned if not enough candidate colors satisfy the
+    /// lightness range.
+    pub fn generate<T>(&self, count: usize) -> Vec<Rgb<T>>
+    where
+        T: PosNormalChannelScalar,
+        f64: ChannelFormatCast<T>,
+    {
+        let steps = (self.resolution - 1) as f64;
+        let mut colors = Vec::new();
+        let mut views = Vec::new();
+        for i in 0..self.resolution.pow(3) {
+            let r = (i / (self.resolution * self.resolution)) as f64 / steps;
+            let g = (i / self.resolution % self.resolution) as f64 / steps;
+            let b = (i % self.resolution) as f64 / steps;
+            let color = Rgb::new(r, g, b);
+            let view = self.views(&color);
+            if (self.min_lightness..=self.max_lightness).contains(&view[0][0]) {
+                colors.push(color);
+                views.push(view);
+            }
+        }
+        let background = self.background.as_ref().map(|b| self.views(b));
+
+        let count = count.min(colors.len());
+        let mut selected: Vec<usize> = Vec::with_capacity(count);
+        while selected.len() < count {
+            let next = (0..colors.len())
+                .filter(|c| !selected.contains(c))
+                .map(|c| (c, min_distance(&views, &background, &selected, c, None)))
+                .fold(
+                    (0, -1.0),
+                    |best, next| if next.1 > best.1 { next } else { best },
+                );
+            selected.push(next.0);
+        }
+
+        for _ in 0..Self::MAX_PASSES {
+            let mut improved = false;
+            for i in 0..selected.len() {
+                let current = min_distance(&views, &background, &selected, selected[i], Some(i));
+                let best = (0..colors.len())
+                    .filter(|c| !selected.contains(c))
+                    .map(|c| (c, min_distance(&views, &background, &selected, c, Some(i))))
+                    .fold((selected[i], current), |best, next| {
+                        if next.1 > best.1 {
+                            next
+                        } else {
+                            best
+                        }
+                    });
+                if best.0 != selected[i] {
+                    selected[i] = best.0;
+                    improved = true;
+                }
+            }
+            if !improved {
+                break;
+            }
+        }
+
+        selected
+            .into_iter()
+            .map(|c| colors[c].color_cast())
+            .collect()
+    }
+
+    /// Returns the `Oklab` channels of `color` as seen normally and with each deficiency
+    fn views(&self, color: &Rgb<f64>) -> Vec<[f64; 3]> {
+        let linear = color.srgb_encoded().decode().strip_encoding();
+        let mut views = vec![linear_to_oklab(&linear)];
+        for &deficiency in self.deficiencies.iter() {
+            views.push(linear_to_oklab(&simulate(&linear, deficiency, 1.0)));
+        }
+        views
+    }
+}
+
+impl Default for CategoricalPalette {
+    fn default() -> Self {
+        CategoricalPalette::new()
+    }
+}
+
+fn linear_to_oklab(color: &Rgb<f64>) -> [f64; 3] {
+    let (x, y, z) = SRgb::new().apply_transform(color.clone().to_tuple());
+    let lab = Oklab::from_color(&Xyz::new(x, y, z));
+    [lab.L(), lab.a(), lab.b()]
+}
+
+/// Returns the distance between two colors: their smallest distance in any of their views
+fn view_distance(a: &[[f64; 3]], b: &[[f64; 3]]) -> f64 {
+    a.iter()
+        .zip(b.iter())
+        .map(|(a, b)| {
+            ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+        })
+        .fold(f64::INFINITY, f64::min)
+}
+
+/// Returns the distance from `candidate` to the nearest selected color, other than the one at
+/// `skip`, or to the background
+fn min_distance(
+    views: &[Vec<[f64; 3]>],
+    background: &Option<Vec<[f64; 3]>>,
+    selected: &[usize],
+    candidate: usize,
+    skip: Option<usize>,
+) -> f64 {
+    let to_background = background
+        .as_ref()
+        .map(|b| view_distance(&views[candidate], b))
+        .unwrap_or(f64::INFINITY);
+    selected
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| Some(i) != skip)
+        .map(|(_, &s)| view_distance(&views[candidate], &views[s]))
+        .fold(to_background, f64::min)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::distance::{Cie76, Euclidean};
+    use crate::lab::Lab;
+    use crate::rgb::Rgb;
+    use crate::white_point::D65;
+    use angle::{Deg, Turns};
+    use approx::*;
+
+    /// A small deterministic generator of values in [0, 1)
+    fn values(seed: u64, count: usize) -> Vec<f64> {
+        let mut state = seed;
+        (0..count)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (state >> 11) as f64 / (1u64 << 53) as f64
+            })
+            .collect()
+    }
+
+    fn brute_force<C, D>(palette: &[C], metric: &D, color: &C) -> usize
+    where
+        D: ColorDistance<C, Output = f64>,
+    {
+        (0..palette.len())
+            .min_by(|&a, &b| {
+                let da = metric.distance(color, &palette[a]);
+                let db = metric.distance(color, &palette[b]);
+                da.partial_cmp(&db).unwrap()
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_nearest_lab() {
+        let v = values(7, 300);
+        let palette: Vec<_> = v
+            .chunks(3)
+            .map(|c| Lab::<f64, D65>::new(c[0] * 100.0, c[1] * 200.0 - 100.0, c[2] * 200.0 - 100.0))
+            .collect();
+        let index = PaletteIndex::new(palette.clone(), Cie76);
+        assert_eq!(index.len(), 100);
+
+        let queries = values(11, 600);
+        for c in queries.chunks(3) {
+            let color = Lab::new(c[0] * 100.0, c[1] * 200.0 - 100.0, c[2] * 200.0 - 100.0);
+            let expected = brute_force(&palette, &Cie76, &color);
+            assert_eq!(index.nearest(&color), Some(expected));
+        }
+        assert_eq!(index.nearest(&palette[42]), Some(42));
+    }
+
+    #[test]
+    fn test_nearest_rgb() {
+        let palette = vec![
+            Rgb::new(0.0, 0.0, 0.0),
+            Rgb::new(1.0, 0.0, 0.0),
+            Rgb::new(0.0, 1.0, 0.0),
+            Rgb::new(0.0, 0.0, 1.0),
+            Rgb::new(1.0, 1.0, 1.0),
+        ];
+        let index = PaletteIndex::new(palette, Euclidean);
+        assert_eq!(index.nearest(&Rgb::new(0.9, 0.2, 0.1)), Some(1));
+        assert_eq!(index.nearest(&Rgb::new(0.2, 0.3, 0.8)), Some(3));
+        assert_eq!(
+            index.nearest_color(&Rgb::new(0.8, 0.9, 0.7)),
+            Some(&Rgb::new(1.0, 1.0, 1.0))
+        );
+
+        let empty = PaletteIndex::<Rgb<f64>, _>::new(Vec::new(), Euclidean);
+        assert!(empty.is_empty());
+        assert_eq!(empty.nearest(&Rgb::new(0.5, 0.5, 0.5)), None);
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn test_fixed_palette() {
+        let v = values(3, 48);
+        let colors: Vec<_> = v
+            .chunks(3)
+            .map(|c| Lab::<f64, D65>::new(c[0] * 100.0, c[1] * 200.0 - 100.0, c[2] * 200.0 - 100.0))
+            .collect();
+        let palette: FixedPalette<_, _, 16> = FixedPalette::from_colors(&colors, Cie76).unwrap();
+        assert_eq!(palette.len(), 16);
+        for c in values(5, 120).chunks(3) {
+            let color = Lab::new(c[0] * 100.0, c[1] * 200.0 - 100.0, c[2] * 200.0 - 100.0);
+            assert_eq!(
+                palette.nearest(&color),
+                Some(brute_force(&colors, &Cie76, &color))
+            );
+        }
+        assert!(FixedPalette::<_, _, 15>::from_colors(&colors, Cie76).is_none());
+
+        let mut small: FixedPalette<Rgb<f64>, _, 2> = FixedPalette::new(Euclidean);
+        assert!(small.is_empty());
+        assert_eq!(small.nearest(&Rgb::new(0.5, 0.5, 0.5)), None);
+        assert!(small.push(Rgb::new(0.0, 0.0, 0.0)).is_ok());
+        assert!(small.push(Rgb::new(1.0, 1.0, 1.0)).is_ok());
+        assert_eq!(
+            small.push(Rgb::new(1.0, 0.0, 0.0)),
+            Err(Rgb::new(1.0, 0.0, 0.0))
+        );
+        assert_eq!(
+            small.nearest_color(&Rgb::new(0.8, 0.9, 0.7)),
+            Some(&Rgb::new(1.0, 1.0, 1.0))
+        );
+    }
+
+    #[test]
+    fn test_distinct_colors() {
+        let colors: Vec<Oklch<f64, Turns<f64>>> = DistinctColors::new(0.65, 0.12)
+            .with_start_hue(Turns(0.25))
+            .take(50)
+            .collect();
+        assert_relative_eq!(colors[0], Oklch::new(0.65, 0.12, Turns(0.25)));
+        assert_relative_eq!(
+            colors[1],
+            Oklch::new(0.65, 0.12, Turns(0.25 + 0.3819660112501051)),
+            epsilon = 1e-12
+        );
+
+        // Every prefix leaves no gap between hues much larger than an even spacing
+        for n in 2..50 {
+            let mut hues: Vec<_> = colors[..n].iter().map(|c| c.hue().0).collect();
+            hues.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let mut widest = 1.0 - hues[n - 1] + hues[0];
+            for pair in hues.windows(2) {
+                widest = f64::max(widest, pair[1] - pair[0]);
+            }
+            assert!(widest < 2.7 / n as f64);
+        }
+
+        let levels = DistinctColors::<f64, Deg<f64>>::with_lightness_levels(vec![0.5, 0.8], 0.1);
+        assert_eq!(levels.lightness_levels(), &[0.5, 0.8]);
+        let mut colors = levels.clone();
+        assert_eq!(colors.next().unwrap().L(), 0.5);
+        assert_eq!(colors.next().unwrap().L(), 0.8);
+        assert_eq!(colors.next(), Some(levels.color_at(2)));
+        assert_relative_eq!(
+            levels.color_at(1000).hue(),
+            Deg((1000.0 * 0.3819660112501051f64).fract() * 360.0),
+            epsilon = 1e-6
+        );
+    }
+
+    fn palette_distance(palette: &CategoricalPalette, colors: &[Rgb<f64>]) -> f64 {
+        let views: Vec<_> = colors.iter().map(|c| palette.views(c)).collect();
+        let mut distance = f64::INFINITY;
+        for (i, a) in views.iter().enumerate() {
+            for b in views[i + 1..].iter() {
+                distance = distance.min(view_distance(a, b));
+            }
+        }
+        distance
+    }
+
+    #[test]
+    fn test_categorical_palette() {
+        let palette = CategoricalPalette::new().with_resolution(8);
+        let two: Vec<Rgb<f64>> = palette.generate(2);
+        assert_eq!(two.len(), 2);
+        // The furthest apart colors in sRGB are black and a near white
+        assert!(two.contains(&Rgb::new(0.0, 0.0, 0.0)));
+        assert!(two
+            .iter()
+            .any(|c| c.red() > 0.8 && c.green() > 0.8 && c.blue() > 0.8));
+
+        let six: Vec<Rgb<f64>> = palette.generate(6);
+        assert_eq!(six.len(), 6);
+        assert!(palette_distance(&palette, &six) > 0.25);
+        assert_eq!(palette.generate::<f64>(6), six);
+
+        let constrained = CategoricalPalette::new()
+            .with_resolution(8)
+            .with_background(Rgb::new(1.0, 1.0, 1.0))
+            .with_lightness_range(0.45, 0.75);
+        let colors: Vec<Rgb<f64>> = constrained.generate(6);
+        for color in colors.iter() {
+            let lightness = constrained.views(color)[0][0];
+            assert!((0.45..=0.75).contains(&lightness));
+        }
+        assert!(palette_distance(&constrained, &colors) > 0.15);
+
+        let deficiencies = [Deficiency::Protanopia, Deficiency::Deuteranopia];
+        let safe = constrained.clone().with_deficiencies(&deficiencies);
+        let safe_colors: Vec<Rgb<f64>> = safe.generate(6);
+        assert!(palette_distance(&safe, &safe_colors) > palette_distance(&safe, &colors));
+
+        let narrow = CategoricalPalette::new()
+            .with_resolution(2)
+            .with_lightness_range(0.98, 1.01);
+        assert_eq!(narrow.generate::<u8>(4), vec![Rgb::new(255, 255, 255)]);
+    }
+}
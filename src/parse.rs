@@ -0,0 +1,396 @@
+//! Parsing of colors from strings with `FromStr`
+//!
+//! The main color types can be read with [`str::parse`], which makes them easy to accept from
+//! configuration files and command line arguments. The syntax is the one produced by each type's
+//! `Display` implementation: the name of the model followed by its channels in parentheses, such
+//! as `Rgb(1, 0.5, 0)` or `Alpha(Hsl(120°, 0.5, 0.5), 0.8)`.
+//!
+//! * Names are matched ignoring case, and short forms are accepted where the displayed name is
+//!   unwieldy: `Lab` for `L*a*b*` and `Lch` for `Lch(ab)`.
+//! * Channels are parsed with the `FromStr` implementation of the channel type, so `Rgb<u8>`
+//!   expects integers and `Rgb<f32>` decimals.
+//! * Hues may carry a unit: `°` or `deg` for degrees, `r` or `rad` for radians, `t` or `turn` for
+//!   turns and `g` or `grad` for gradians. A bare number is in the unit of the hue type.
+//!
+//! ## Examples:
+//!
+//! ```rust
+//! #[macro_use] extern crate approx;
+//! # extern crate prisma;
+//! extern crate angular_units as angle;
+//!
+//! use prisma::{Hsl, Lab, Rgb, Rgba};
+//! use prisma::white_point::D65;
+//! use angle::Deg;
+//!
+//! let rgb: Rgb<u8> = "Rgb(255, 128, 0)".parse().unwrap();
+//! assert_eq!(rgb, Rgb::new(255, 128, 0));
+//!
+//! let hsl: Hsl<f64, Deg<f64>> = "hsl(0.5turn, 1, 0.25)".parse().unwrap();
+//! assert_relative_eq!(hsl, Hsl::new(Deg(180.0), 1.0, 0.25));
+//!
+//! let lab: Lab<f32, D65> = "Lab(50, -20.5, 10)".parse().unwrap();
+//! assert_eq!(lab, Lab::new(50.0, -20.5, 10.0));
+//!
+//! let rgba: Rgba<f32> = "Alpha(Rgb(1, 0, 0), 0.5)".parse().unwrap();
+//! assert_eq!(rgba.to_string(), "Alpha(Rgb(1, 0, 0), 0.5)");
+//! ```
+//!
+//! [`str::parse`]: https://doc.rust-lang.org/std/primitive.str.html#method.parse
+
+use crate::alpha::Alpha;
+use crate::channel::{AngularChannelScalar, FreeChannelScalar, PosNormalChannelScalar};
+use crate::color::Color;
+use crate::hsl::Hsl;
+use crate::hsv::Hsv;
+use crate::hwb::{Hwb, HwbBoundedChannelTraits};
+use crate::lab::Lab;
+use crate::lchab::Lchab;
+use crate::rgb::Rgb;
+use crate::white_point::UnitWhitePoint;
+use crate::xyz::Xyz;
+use angle::{Angle, Deg, FromAngle, Gon, Rad, Turns};
+use num_traits::Float;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// An error returned when a color could not be parsed from a string
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseColorError {
+    /// The string was not of the form `Name(channel, ...)`
+    InvalidSyntax,
+    /// The name of the color model didn't match the type being parsed. Holds the name found.
+    UnexpectedName(String),
+    /// The number of channels was wrong for the type being parsed. Holds the number found.
+    WrongChannelCount(usize),
+    /// A channel could not be parsed as a value of its type. Holds the channel text.
+    InvalidChannel(String),
+}
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseColorError::InvalidSyntax => write!(f, "expected a color as Name(channel, ...)"),
+            ParseColorError::UnexpectedName(ref name) => {
+                write!(f, "unexpected color model name '{}'", name)
+            }
+            ParseColorError::WrongChannelCount(count) => {
+                write!(f, "wrong number of channels {}", count)
+            }
+            ParseColorError::InvalidChannel(ref channel) => {
+                write!(f, "invalid channel value '{}'", channel)
+            }
+        }
+    }
+}
+
+impl Error for ParseColorError {}
+
+/// Split `s` into a name from `names` and exactly `count` top-level channels
+fn parse_function<'a>(
+    s: &'a str,
+    names: &[&str],
+    count: usize,
+) -> Result<Vec<&'a str>, ParseColorError> {
+    let s = s.trim();
+    if !s.ends_with(')') {
+        return Err(ParseColorError::InvalidSyntax);
+    }
+
+    // The arguments start at the parenthesis matching the final one, so names such as
+    // `Lch(ab)` may contain parentheses themselves
+    let mut depth = 0;
+    let mut open = None;
+    for (i, c) in s.char_indices().rev() {
+        match c {
+            ')' => depth += 1,
+            '(' => {
+                depth -= 1;
+                if depth == 0 {
+                    open = Some(i);
+                    break;
+                }
+            }
+            _ => (),
+        }
+    }
+    let open = open.ok_or(ParseColorError::InvalidSyntax)?;
+
+    let name = s[..open].trim();
+    if !names.iter().any(|n| n.eq_ignore_ascii_case(name)) {
+        return Err(ParseColorError::UnexpectedName(name.to_string()));
+    }
+
+    let args = &s[open + 1..s.len() - 1];
+    let mut channels = Vec::with_capacity(count);
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in args.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                channels.push(args[start..i].trim());
+                start = i + 1;
+            }
+            _ => (),
+        }
+    }
+    channels.push(args[start..].trim());
+
+    if channels.len() != count {
+        return Err(ParseColorError::WrongChannelCount(channels.len()));
+    }
+    Ok(channels)
+}
+
+fn parse_channel<T: FromStr>(s: &str) -> Result<T, ParseColorError> {
+    s.parse()
+        .map_err(|_| ParseColorError::InvalidChannel(s.to_string()))
+}
+
+fn parse_hue<A>(s: &str) -> Result<A, ParseColorError>
+where
+    A: Angle
+        + FromAngle<Deg<A::Scalar>>
+        + FromAngle<Rad<A::Scalar>>
+        + FromAngle<Turns<A::Scalar>>
+        + FromAngle<Gon<A::Scalar>>,
+    A::Scalar: Float + FromStr,
+{
+    // Suffixes that end another suffix must come before it
+    const UNITS: [&str; 8] = ["°", "deg", "grad", "rad", "r", "turn", "t", "g"];
+    let unit = UNITS.iter().find(|unit| s.ends_with(*unit));
+    let value = unit.map_or(s, |unit| &s[..s.len() - unit.len()]);
+    let value: A::Scalar = parse_channel(value.trim_end())
+        .map_err(|_| ParseColorError::InvalidChannel(s.to_string()))?;
+    Ok(match unit {
+        Some(&"°") | Some(&"deg") => A::from_angle(Deg(value)),
+        Some(&"rad") | Some(&"r") => A::from_angle(Rad(value)),
+        Some(&"turn") | Some(&"t") => A::from_angle(Turns(value)),
+        Some(_) => A::from_angle(Gon(value)),
+        None => A::new(value),
+    })
+}
+
+impl<T> FromStr for Rgb<T>
+where
+    T: PosNormalChannelScalar + FromStr,
+{
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let c = parse_function(s, &["Rgb"], 3)?;
+        Ok(Rgb::new(
+            parse_channel(c[0])?,
+            parse_channel(c[1])?,
+            parse_channel(c[2])?,
+        ))
+    }
+}
+
+macro_rules! impl_from_str_hue_first {
+    ($name: ident, $bound: ident) => {
+        impl<T, A> FromStr for $name<T, A>
+        where
+            T: $bound + FromStr,
+            A: AngularChannelScalar
+                + FromAngle<Deg<A::Scalar>>
+                + FromAngle<Rad<A::Scalar>>
+                + FromAngle<Turns<A::Scalar>>
+                + FromAngle<Gon<A::Scalar>>,
+            A::Scalar: Float + FromStr,
+        {
+            type Err = ParseColorError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let c = parse_function(s, &[stringify!($name)], 3)?;
+                Ok($name::new(
+                    parse_hue(c[0])?,
+                    parse_channel(c[1])?,
+                    parse_channel(c[2])?,
+                ))
+            }
+        }
+    };
+}
+
+impl_from_str_hue_first!(Hsv, PosNormalChannelScalar);
+impl_from_str_hue_first!(Hsl, PosNormalChannelScalar);
+impl_from_str_hue_first!(Hwb, HwbBoundedChannelTraits);
+
+impl<T, W> FromStr for Lab<T, W>
+where
+    T: FreeChannelScalar + FromStr,
+    W: UnitWhitePoint<T>,
+{
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let c = parse_function(s, &["Lab", "L*a*b*"], 3)?;
+        Ok(Lab::new(
+            parse_channel(c[0])?,
+            parse_channel(c[1])?,
+            parse_channel(c[2])?,
+        ))
+    }
+}
+
+impl<T, W, A> FromStr for Lchab<T, W, A>
+where
+    T: FreeChannelScalar + FromStr,
+    W: UnitWhitePoint<T>,
+    A: AngularChannelScalar
+        + FromAngle<Deg<A::Scalar>>
+        + FromAngle<Rad<A::Scalar>>
+        + FromAngle<Turns<A::Scalar>>
+        + FromAngle<Gon<A::Scalar>>,
+    A::Scalar: Float + FromStr,
+{
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let c = parse_function(s, &["Lch", "Lchab", "Lch(ab)"], 3)?;
+        Ok(Lchab::new(
+            parse_channel(c[0])?,
+            parse_channel(c[1])?,
+            parse_hue(c[2])?,
+        ))
+    }
+}
+
+impl<T> FromStr for Xyz<T>
+where
+    T: FreeChannelScalar + FromStr,
+{
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let c = parse_function(s, &["Xyz"], 3)?;
+        Ok(Xyz::new(
+            parse_channel(c[0])?,
+            parse_channel(c[1])?,
+            parse_channel(c[2])?,
+        ))
+    }
+}
+
+impl<T, InnerColor> FromStr for Alpha<T, InnerColor>
+where
+    T: PosNormalChannelScalar + FromStr,
+    InnerColor: Color + FromStr<Err = ParseColorError>,
+{
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let c = parse_function(s, &["Alpha"], 2)?;
+        Ok(Alpha::new(c[0].parse()?, parse_channel(c[1])?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::alpha::Rgba;
+    use crate::white_point::{D50, D65};
+    use approx::*;
+    use std::f64::consts;
+
+    #[test]
+    fn test_parse_rgb() {
+        assert_eq!("Rgb(255, 128, 0)".parse(), Ok(Rgb::new(255u8, 128, 0)));
+        assert_eq!(
+            "  rgb(0.5,0.25,1) ".parse(),
+            Ok(Rgb::new(0.5f32, 0.25, 1.0))
+        );
+        let color = Rgb::new(0.125, 0.75, 1.0);
+        assert_eq!(color.to_string().parse(), Ok(color));
+
+        assert_eq!(
+            "Rgb(256, 0, 0)".parse::<Rgb<u8>>(),
+            Err(ParseColorError::InvalidChannel("256".to_string()))
+        );
+        assert_eq!(
+            "Rgb(1, 0)".parse::<Rgb<f32>>(),
+            Err(ParseColorError::WrongChannelCount(2))
+        );
+        assert_eq!(
+            "Hsv(1, 0, 0)".parse::<Rgb<f32>>(),
+            Err(ParseColorError::UnexpectedName("Hsv".to_string()))
+        );
+        assert_eq!(
+            "Rgb 1, 0, 0".parse::<Rgb<f32>>(),
+            Err(ParseColorError::InvalidSyntax)
+        );
+        assert_eq!(
+            "Rgb(1, 0, 0))".parse::<Rgb<f32>>(),
+            Err(ParseColorError::InvalidSyntax)
+        );
+    }
+
+    #[test]
+    fn test_parse_polar() {
+        let hsv: Hsv<f64> = "Hsv(120°, 0.5, 1)".parse().unwrap();
+        assert_eq!(hsv, Hsv::new(Deg(120.0), 0.5, 1.0));
+        assert_eq!(hsv.to_string().parse(), Ok(hsv));
+
+        let hsl: Hsl<f64, Turns<f64>> = "HSL(90deg, 0.2, 0.3)".parse().unwrap();
+        assert_relative_eq!(hsl, Hsl::new(Turns(0.25), 0.2, 0.3));
+        let hsl: Hsl<f64, Turns<f64>> = "Hsl(0.25, 0.2, 0.3)".parse().unwrap();
+        assert_relative_eq!(hsl, Hsl::new(Turns(0.25), 0.2, 0.3));
+
+        let hwb: Hwb<f64, Deg<f64>> = "Hwb(3.14159265358979r, 0.1, 0.2)".parse().unwrap();
+        assert_relative_eq!(hwb, Hwb::new(Deg(180.0), 0.1, 0.2), epsilon = 1e-9);
+        let hwb: Hwb<f64, Deg<f64>> = "Hwb(100grad, 0.1, 0.2)".parse().unwrap();
+        assert_relative_eq!(hwb, Hwb::new(Deg(90.0), 0.1, 0.2), epsilon = 1e-9);
+        let hsv: Hsv<f64, Rad<f64>> = "Hsv(200g, 0.5, 1)".parse().unwrap();
+        assert_relative_eq!(hsv, Hsv::new(Rad(consts::PI), 0.5, 1.0), epsilon = 1e-9);
+
+        assert_eq!(
+            "Hsv(12x, 0.5, 1)".parse::<Hsv<f64>>(),
+            Err(ParseColorError::InvalidChannel("12x".to_string()))
+        );
+        assert_eq!(
+            "Hsv(deg, 0.5, 1)".parse::<Hsv<f64>>(),
+            Err(ParseColorError::InvalidChannel("deg".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_cie() {
+        let lab: Lab<f64, D65> = "L*a*b*(50, -20, 30.5)".parse().unwrap();
+        assert_eq!(lab, Lab::new(50.0, -20.0, 30.5));
+        assert_eq!(lab.to_string().parse(), Ok(lab));
+        let lab: Lab<f64, D50> = "lab(1e1, 0, 0)".parse().unwrap();
+        assert_eq!(lab, Lab::new(10.0, 0.0, 0.0));
+
+        let lch: Lchab<f64, D65> = "Lch(ab)(60, 40, 270°)".parse().unwrap();
+        assert_eq!(lch, Lchab::new(60.0, 40.0, Deg(270.0)));
+        assert_eq!(lch.to_string().parse(), Ok(lch));
+        assert_eq!("Lch(60, 40, 270)".parse(), Ok(lch));
+
+        let xyz: Xyz<f32> = "XYZ(0.95, 1, 1.09)".parse().unwrap();
+        assert_eq!(xyz, Xyz::new(0.95, 1.0, 1.09));
+        assert_eq!(xyz.to_string().parse(), Ok(xyz));
+    }
+
+    #[test]
+    fn test_parse_alpha() {
+        let rgba: Rgba<u8> = "Alpha(Rgb(255, 0, 10), 128)".parse().unwrap();
+        assert_eq!(rgba, Rgba::new(Rgb::new(255, 0, 10), 128));
+        assert_eq!(rgba.to_string().parse(), Ok(rgba));
+
+        let hsla: Alpha<f64, Hsl<f64>> = "alpha(hsl(10°, 0.5, 0.5), 0.25)".parse().unwrap();
+        assert_eq!(hsla, Alpha::new(Hsl::new(Deg(10.0), 0.5, 0.5), 0.25));
+
+        assert_eq!(
+            "Alpha(Rgb(1, 0), 0.5)".parse::<Rgba<f32>>(),
+            Err(ParseColorError::WrongChannelCount(2))
+        );
+        assert_eq!(
+            "Alpha(Rgb(1, 0, 0))".parse::<Rgba<f32>>(),
+            Err(ParseColorError::WrongChannelCount(1))
+        );
+    }
+}
@@ -0,0 +1,390 @@
+//! Policies for colors that end up outside of the valid range of their model
+//!
+//! Many operations can produce channels outside of the valid range of a model, most commonly
+//! conversions from models such as `Hsi` and `YCbCr` that can describe colors `Rgb` can't. Without
+//! a policy, what happens to these colors depends on the operation: `PivotColor::convert` clips
+//! them, `TryConvert` fails and the `FromColor` impls often keep them as they are.
+//! A [`RangePolicy`](trait.RangePolicy.html) makes the choice explicit:
+//!
+//! * [`Clamp`](struct.Clamp.html): Clamp each channel to its range, and bring hues within one
+//!   period.
+//! * [`Wrap`](struct.Wrap.html): Wrap each channel around its range, so that `1.25` becomes
+//!   `0.25`. Channels without an upper bound are clamped.
+//! * [`Preserve`](struct.Preserve.html): Keep out of range channels, as needed by wide gamut and
+//!   scene-referred workflows.
+//! * [`Error`](struct.Error.html): Fail with an [`OutOfRangeError`](../struct.OutOfRangeError.html)
+//!   describing the channels, for strict workflows.
+//!
+//! A policy can be chosen for a single conversion with
+//! [`ConvertWithPolicy::convert_with`](trait.ConvertWithPolicy.html#method.convert_with), which
+//! applies it to the pivot of the conversion, or attached to a color with
+//! [`WithPolicy`](struct.WithPolicy.html), which applies it whenever the color is constructed or
+//! converted.
+//!
+//! ## Examples:
+//!
+//! ```rust
+//! # extern crate prisma;
+//!
+//! use prisma::{Luma, Rgb};
+//! use prisma::policy::{self, ConvertWithPolicy, WithPolicy};
+//! use prisma::ycbcr::YCbCrJpeg;
+//!
+//! let ycbcr = YCbCrJpeg::new(0.9, 0.3, 0.3);
+//! let clamped: Rgb<f64> = ycbcr.convert_with(&policy::Clamp).unwrap();
+//! assert_eq!(clamped.red(), 1.0);
+//! let preserved: Rgb<f64> = ycbcr.convert_with(&policy::Preserve).unwrap();
+//! assert!(preserved.red() > 1.0);
+//! assert!(ycbcr.convert_with::<Rgb<f64>, _>(&policy::Error).is_err());
+//!
+//! let strict = WithPolicy::new(Rgb::new(0.5, 0.25, 1.0), policy::Error).unwrap();
+//! assert!(strict.convert::<Luma<f64>>().is_ok());
+//! assert!(WithPolicy::new(Rgb::new(0.5, 1.25, 1.0), policy::Error).is_err());
+//! ```
+
+use crate::alpha::Alpha;
+use crate::channel::{
+    AngularChannelScalar, ChannelFormatCast, FreeChannelScalar, PosNormalChannelScalar,
+};
+use crate::color::{Bounded, Color};
+use crate::convert::{ChannelRange, OutOfRangeError, PivotColor};
+use crate::ehsi::eHsi;
+use crate::hsi::Hsi;
+use crate::hsl::Hsl;
+use crate::hsv::Hsv;
+use crate::hwb::Hwb;
+use crate::luma::Luma;
+use crate::rgb::Rgb;
+use crate::xyz::Xyz;
+use angle::Angle;
+use num_traits::Float;
+
+/// A color whose channels can be wrapped around their range
+pub trait WrapChannels {
+    /// Wrap each channel around its range, leaving channels already in range unchanged
+    ///
+    /// Hues are brought within one period, and channels without an upper bound are clamped.
+    fn wrap_channels(self) -> Self;
+}
+
+/// A color that a [`RangePolicy`](trait.RangePolicy.html) can be applied to
+///
+/// This is implemented for every color implementing the traits the policies use.
+pub trait PolicyColor: Bounded + ChannelRange + WrapChannels + Sized {}
+
+impl<C> PolicyColor for C where C: Bounded + ChannelRange + WrapChannels + Sized {}
+
+/// What to do with colors that are outside of the valid range of their model
+pub trait RangePolicy {
+    /// Apply the policy to `color`
+    ///
+    /// Colors already in range are always returned unchanged.
+    fn apply<C>(&self, color: C) -> Result<C, OutOfRangeError>
+    where
+        C: PolicyColor;
+}
+
+/// Clamp each channel to its range
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct Clamp;
+
+/// Wrap each channel around its range
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct Wrap;
+
+/// Keep channels that are out of range
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct Preserve;
+
+/// Fail if any channel is out of range
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct Error;
+
+impl RangePolicy for Clamp {
+    fn apply<C>(&self, color: C) -> Result<C, OutOfRangeError>
+    where
+        C: PolicyColor,
+    {
+        Ok(color.normalize())
+    }
+}
+
+impl RangePolicy for Wrap {
+    fn apply<C>(&self, color: C) -> Result<C, OutOfRangeError>
+    where
+        C: PolicyColor,
+    {
+        Ok(color.wrap_channels())
+    }
+}
+
+impl RangePolicy for Preserve {
+    fn apply<C>(&self, color: C) -> Result<C, OutOfRangeError>
+    where
+        C: PolicyColor,
+    {
+        Ok(color)
+    }
+}
+
+impl RangePolicy for Error {
+    fn apply<C>(&self, color: C) -> Result<C, OutOfRangeError>
+    where
+        C: PolicyColor,
+    {
+        let channels = color.out_of_range_channels();
+        if channels.is_empty() {
+            Ok(color)
+        } else {
+            Err(OutOfRangeError::new(channels))
+        }
+    }
+}
+
+/// Convert colors with an explicit [`RangePolicy`](trait.RangePolicy.html)
+///
+/// The color is converted to its pivot without clipping, the policy is applied to the pivot, and
+/// the result is converted to `Dst`. This is implemented for every `PivotColor`.
+pub trait ConvertWithPolicy: PivotColor {
+    /// Convert `self` to `Dst`, applying `policy` to out of range colors
+    fn convert_with<Dst, P>(&self, policy: &P) -> Result<Dst, OutOfRangeError>
+    where
+        Self::Pivot: PolicyColor,
+        Dst: PivotColor<Pivot = Self::Pivot>,
+        P: RangePolicy,
+    {
+        let pivot = policy.apply(self.to_pivot_unclipped())?;
+        Ok(Dst::from_pivot(&pivot))
+    }
+}
+
+impl<C> ConvertWithPolicy for C where C: PivotColor {}
+
+/// A color paired with the [`RangePolicy`](trait.RangePolicy.html) applied to it
+///
+/// The policy is applied when the `WithPolicy` is constructed and to every conversion, so a
+/// `WithPolicy<C, Error>` is always in range and a `WithPolicy<C, Preserve>` never loses out of
+/// range channels.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct WithPolicy<C, P> {
+    color: C,
+    policy: P,
+}
+
+impl<C, P> WithPolicy<C, P>
+where
+    C: PolicyColor,
+    P: RangePolicy,
+{
+    /// Construct a new `WithPolicy`, applying `policy` to `color`
+    pub fn new(color: C, policy: P) -> Result<Self, OutOfRangeError> {
+        let color = policy.apply(color)?;
+        Ok(WithPolicy { color, policy })
+    }
+
+    /// Returns the color
+    pub fn color(&self) -> &C {
+        &self.color
+    }
+    /// Returns the policy
+    pub fn policy(&self) -> &P {
+        &self.policy
+    }
+    /// Returns the color, dropping the policy
+    pub fn into_color(self) -> C {
+        self.color
+    }
+
+    /// Convert the color to `Dst`, applying the policy to the pivot and to the result
+    pub fn convert<Dst>(&self) -> Result<WithPolicy<Dst, P>, OutOfRangeError>
+    where
+        C: PivotColor,
+        C::Pivot: PolicyColor,
+        Dst: PolicyColor + PivotColor<Pivot = C::Pivot>,
+        P: Clone,
+    {
+        let color = self.color.convert_with::<Dst, P>(&self.policy)?;
+        WithPolicy::new(color, self.policy.clone())
+    }
+
+    /// Apply `f` to the color, applying the policy to the result
+    pub fn map<F>(self, f: F) -> Result<Self, OutOfRangeError>
+    where
+        F: FnOnce(C) -> C,
+    {
+        WithPolicy::new(f(self.color), self.policy)
+    }
+}
+
+fn wrap_unit<T>(value: T) -> T
+where
+    T: ChannelFormatCast<f64> + Clone,
+    f64: ChannelFormatCast<T>,
+{
+    let normal: f64 = value.clone().cast();
+    if (0.0..=1.0).contains(&normal) || normal.is_nan() {
+        value
+    } else {
+        normal.rem_euclid(1.0).cast()
+    }
+}
+
+impl<T> WrapChannels for Rgb<T>
+where
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+{
+    fn wrap_channels(self) -> Self {
+        Rgb::new(
+            wrap_unit(self.red()),
+            wrap_unit(self.green()),
+            wrap_unit(self.blue()),
+        )
+    }
+}
+
+impl<T> WrapChannels for Luma<T>
+where
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+{
+    fn wrap_channels(self) -> Self {
+        Luma::new(wrap_unit(self.luma()))
+    }
+}
+
+impl<T> WrapChannels for Xyz<T>
+where
+    T: FreeChannelScalar,
+{
+    fn wrap_channels(self) -> Self {
+        self.normalize()
+    }
+}
+
+macro_rules! impl_wrap_channels_hue {
+    ($name:ident, $first:ident, $second:ident) => {
+        impl_wrap_channels_hue!($name, $first, $second, where {
+            T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+            A: AngularChannelScalar,
+            A::Scalar: Float,
+        });
+    };
+    ($name:ident, $first:ident, $second:ident, where { $($bounds:tt)* }) => {
+        impl<T, A> WrapChannels for $name<T, A>
+        where
+            f64: ChannelFormatCast<T>,
+            $($bounds)*
+        {
+            fn wrap_channels(self) -> Self {
+                $name::new(
+                    Angle::normalize(self.hue()),
+                    wrap_unit(self.$first()),
+                    wrap_unit(self.$second()),
+                )
+            }
+        }
+    };
+}
+
+impl_wrap_channels_hue!(Hsv, saturation, value);
+impl_wrap_channels_hue!(Hsl, saturation, lightness);
+impl_wrap_channels_hue!(Hwb, whiteness, blackness);
+impl_wrap_channels_hue!(Hsi, saturation, intensity, where {
+    T: PosNormalChannelScalar + Float + ChannelFormatCast<f64>,
+    A: AngularChannelScalar + Angle<Scalar = T>,
+});
+impl_wrap_channels_hue!(eHsi, saturation, intensity, where {
+    T: PosNormalChannelScalar + Float + ChannelFormatCast<f64>,
+    A: AngularChannelScalar + Angle<Scalar = T>,
+});
+
+impl<T, InnerColor> WrapChannels for Alpha<T, InnerColor>
+where
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+    InnerColor: Color + WrapChannels,
+{
+    fn wrap_channels(self) -> Self {
+        let alpha = wrap_unit(self.alpha());
+        Alpha::new(self.color().clone().wrap_channels(), alpha)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::alpha::Rgba;
+    use crate::lab::Lab;
+    use crate::white_point::D65;
+    use angle::Deg;
+    use approx::*;
+
+    #[test]
+    fn test_policies() {
+        let color = Rgb::new(1.25, -0.25, 0.5);
+        assert_eq!(Clamp.apply(color), Ok(Rgb::new(1.0, 0.0, 0.5)));
+        assert_eq!(Wrap.apply(color), Ok(Rgb::new(0.25, 0.75, 0.5)));
+        assert_eq!(Preserve.apply(color), Ok(color));
+        let err = Error.apply(color).unwrap_err();
+        assert_eq!(err.channels().len(), 2);
+
+        let inside = Rgb::new(0.0, 1.0, 0.5);
+        for result in [
+            Clamp.apply(inside),
+            Wrap.apply(inside),
+            Preserve.apply(inside),
+            Error.apply(inside),
+        ]
+        .iter()
+        {
+            assert_eq!(result, &Ok(inside));
+        }
+
+        let hsv = Hsv::new(Deg(400.0), 1.5, 0.5);
+        let wrapped = Wrap.apply(hsv).unwrap();
+        assert_relative_eq!(wrapped, Hsv::new(Deg(40.0), 0.5, 0.5), epsilon = 1e-9);
+        assert_relative_eq!(
+            Clamp.apply(hsv).unwrap(),
+            Hsv::new(Deg(40.0), 1.0, 0.5),
+            epsilon = 1e-9
+        );
+
+        let rgba = Rgba::new(Rgb::new(0.5, 0.5, 0.5), 1.5);
+        assert_relative_eq!(
+            Wrap.apply(rgba).unwrap(),
+            Rgba::new(Rgb::new(0.5, 0.5, 0.5), 0.5)
+        );
+        assert_eq!(
+            Wrap.apply(Xyz::new(-0.5, 2.0, 0.5)),
+            Ok(Xyz::new(0.0, 2.0, 0.5))
+        );
+    }
+
+    #[test]
+    fn test_convert_with_policy() {
+        let hsi = Hsi::new(Deg(0.0), 1.0, 0.8);
+        let preserved: Rgb<f64> = hsi.convert_with(&Preserve).unwrap();
+        assert!(preserved.red() > 1.0);
+        let clamped: Rgb<f64> = hsi.convert_with(&Clamp).unwrap();
+        assert_eq!(clamped, Clamp.apply(preserved).unwrap());
+        let err = hsi.convert_with::<Hsv<f64, Deg<f64>>, _>(&Error);
+        assert_eq!(err.unwrap_err().channels()[0].name, "red");
+
+        let lab = Lab::<f64, D65>::new(50.0, 20.0, -30.0);
+        let xyz: Xyz<f64> = lab.convert_with(&Error).unwrap();
+        assert_relative_eq!(xyz, lab.to_xyz(), epsilon = 1e-12);
+
+        let wide = WithPolicy::new(hsi, Preserve).unwrap();
+        let rgb = wide.convert::<Rgb<f64>>().unwrap();
+        assert_eq!(rgb.color(), &preserved);
+        assert_eq!(rgb.into_color(), preserved);
+
+        let strict = WithPolicy::new(Rgb::new(0.2, 0.4, 0.6), Error).unwrap();
+        assert!(strict.map(|c| Rgb::new(c.red() * 2.0, 0.4, 0.6)).is_ok());
+        assert!(strict.map(|c| Rgb::new(c.red() * 10.0, 0.4, 0.6)).is_err());
+        let clamped = WithPolicy::new(Rgb::new(1.5, 0.4, 0.6), Clamp).unwrap();
+        assert_eq!(clamped.color(), &Rgb::new(1.0, 0.4, 0.6));
+        assert_eq!(clamped.policy(), &Clamp);
+    }
+}
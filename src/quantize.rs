@@ -0,0 +1,325 @@
+//! Color quantization and palette extraction
+//!
+//! Quantization reduces the colors of an image to a small palette, which is useful both for
+//! indexed image formats and for finding the dominant colors of an image. Two algorithms are
+//! provided:
+//!
+//! * [`median_cut`](fn.median_cut.html): Repeatedly split the box of colors with the largest range
+//!   at the median of its widest channel. Fast and deterministic.
+//! * [`kmeans`](fn.kmeans.html): Refine the median cut palette with k-means clustering in `Lab`,
+//!   so colors are grouped by perceived difference rather than by channel values.
+//!
+//! Both take sRGB encoded pixels, as stored by almost all images, and return a
+//! [`Quantized`](struct.Quantized.html) holding the palette and the palette index of each pixel.
+//!
+//! ## Examples:
+//!
+//! ```rust
+//! # extern crate prisma;
+//!
+//! use prisma::Rgb;
+//! use prisma::quantize::median_cut;
+//!
+//! let pixels = [
+//!     Rgb::new(250u8, 10, 10),
+//!     Rgb::new(10, 10, 250),
+//!     Rgb::new(255, 0, 0),
+//!     Rgb::new(0, 0, 255),
+//! ];
+//! let quantized = median_cut(&pixels, 2);
+//! assert_eq!(quantized.palette(), &[Rgb::new(5, 5, 253), Rgb::new(253, 5, 5)]);
+//! assert_eq!(quantized.indices(), &[1, 0, 1, 0]);
+//! ```
+
+#![allow(non_snake_case)]
+
+use crate::channel::{ChannelFormatCast, PosNormalChannelScalar};
+use crate::color::Bounded;
+use crate::color_space::named::SRgb;
+use crate::color_space::{ConvertFromXyz, ConvertToXyz};
+use crate::encoding::{EncodableColor, SrgbEncoding};
+use crate::lab::Lab;
+use crate::rgb::Rgb;
+use crate::white_point::D65;
+
+/// A quantized image: a palette and the index into it of each pixel
+#[derive(Clone, Debug, PartialEq)]
+pub struct Quantized<T> {
+    palette: Vec<Rgb<T>>,
+    indices: Vec<usize>,
+}
+
+impl<T> Quantized<T> {
+    /// Returns the palette colors
+    pub fn palette(&self) -> &[Rgb<T>] {
+        &self.palette
+    }
+    /// Returns the palette index of each pixel, in the same order as the input pixels
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+    /// Returns the number of pixels mapped to each palette color
+    pub fn counts(&self) -> Vec<usize> {
+        let mut counts = vec![0; self.palette.len()];
+        for &i in self.indices.iter() {
+            counts[i] += 1;
+        }
+        counts
+    }
+    /// Decompose `self` into the palette and the pixel indices
+    pub fn into_parts(self) -> (Vec<Rgb<T>>, Vec<usize>) {
+        (self.palette, self.indices)
+    }
+}
+
+/// Reduce `pixels` to a palette of at most `k` colors using the median cut algorithm
+///
+/// The palette has fewer than `k` colors if `pixels` has fewer than `k` distinct colors. Each
+/// palette color is the average of the pixels mapped to it.
+///
+/// # Panics
+/// If `k` is 0.
+pub fn median_cut<T>(pixels: &[Rgb<T>], k: usize) -> Quantized<T>
+where
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+{
+    let colors: Vec<_> = pixels.iter().map(to_channels).collect();
+    let (palette, indices) = median_cut_channels(&colors, k);
+    Quantized {
+        palette: palette
+            .into_iter()
+            .map(|[r, g, b]| Rgb::new(r, g, b).color_cast())
+            .collect(),
+        indices,
+    }
+}
+
+/// Reduce `pixels` to a palette of at most `k` colors using k-means clustering in `Lab`
+///
+/// Clustering starts from the [`median_cut`](fn.median_cut.html) palette and runs until no pixel
+/// changes cluster or `max_iterations` is reached. Distances are measured in `Lab`, which groups
+/// colors the way they are perceived, at the cost of converting every pixel.
+///
+/// # Panics
+/// If `k` is 0.
+pub fn kmeans<T>(pixels: &[Rgb<T>], k: usize, max_iterations: usize) -> Quantized<T>
+where
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+{
+    let space = SRgb::<f64>::new();
+    let labs: Vec<_> = pixels
+        .iter()
+        .map(|p| {
+            let xyz = space.convert_to_xyz(&p.color_cast::<f64>().srgb_encoded());
+            let lab = Lab::from_xyz(&xyz, D65);
+            [lab.L(), lab.a(), lab.b()]
+        })
+        .collect();
+
+    let (mut centroids, mut indices) = median_cut_channels(&labs, k);
+    for _ in 0..max_iterations {
+        let mut sums = vec![([0.0; 3], 0usize); centroids.len()];
+        for (lab, &i) in labs.iter().zip(indices.iter()) {
+            let sum = &mut sums[i];
+            for (s, v) in sum.0.iter_mut().zip(lab) {
+                *s += v;
+            }
+            sum.1 += 1;
+        }
+        for (centroid, (sum, count)) in centroids.iter_mut().zip(sums) {
+            // A cluster that lost all its pixels keeps its previous centroid
+            if count > 0 {
+                *centroid = [
+                    sum[0] / count as f64,
+                    sum[1] / count as f64,
+                    sum[2] / count as f64,
+                ];
+            }
+        }
+
+        let mut changed = false;
+        for (lab, index) in labs.iter().zip(indices.iter_mut()) {
+            let nearest = nearest_channels(&centroids, lab);
+            if nearest != *index {
+                *index = nearest;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    let palette = centroids
+        .into_iter()
+        .map(|[L, a, b]| {
+            let xyz = Lab::<f64, D65>::new(L, a, b).to_xyz();
+            space
+                .convert_from_xyz_raw(&xyz)
+                .normalize()
+                .linear()
+                .encode(SrgbEncoding)
+                .strip_encoding()
+                .color_cast()
+        })
+        .collect();
+    Quantized { palette, indices }
+}
+
+fn to_channels<T>(color: &Rgb<T>) -> [f64; 3]
+where
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+{
+    let color = color.color_cast::<f64>();
+    [color.red(), color.green(), color.blue()]
+}
+
+fn squared_distance(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    (0..3).map(|c| (a[c] - b[c]) * (a[c] - b[c])).sum()
+}
+
+fn nearest_channels(palette: &[[f64; 3]], color: &[f64; 3]) -> usize {
+    let mut nearest = 0;
+    let mut nearest_distance = f64::INFINITY;
+    for (i, entry) in palette.iter().enumerate() {
+        let distance = squared_distance(entry, color);
+        if distance < nearest_distance {
+            nearest = i;
+            nearest_distance = distance;
+        }
+    }
+    nearest
+}
+
+/// Run median cut on raw channel values, returning the box averages and each color's box
+fn median_cut_channels(colors: &[[f64; 3]], k: usize) -> (Vec<[f64; 3]>, Vec<usize>) {
+    assert!(k > 0, "A palette must have at least one color");
+    if colors.is_empty() {
+        return (Vec::new(), Vec::new());
+    }
+
+    let mut boxes = vec![(0..colors.len()).collect::<Vec<_>>()];
+    while boxes.len() < k {
+        let (widest, channel, range) = boxes
+            .iter()
+            .enumerate()
+            .map(|(i, b)| {
+                let (channel, range) = widest_channel(colors, b);
+                (i, channel, range)
+            })
+            .fold(
+                (0, 0, 0.0),
+                |best, next| if next.2 > best.2 { next } else { best },
+            );
+        if range <= 0.0 {
+            break;
+        }
+
+        let mut members = boxes.swap_remove(widest);
+        members.sort_by(|&a, &b| colors[a][channel].partial_cmp(&colors[b][channel]).unwrap());
+        let upper = members.split_off(members.len() / 2);
+        boxes.push(members);
+        boxes.push(upper);
+    }
+
+    let mut indices = vec![0; colors.len()];
+    let palette = boxes
+        .iter()
+        .enumerate()
+        .map(|(i, members)| {
+            let mut sum = [0.0; 3];
+            for &m in members.iter() {
+                indices[m] = i;
+                for (s, v) in sum.iter_mut().zip(&colors[m]) {
+                    *s += v;
+                }
+            }
+            let count = members.len() as f64;
+            [sum[0] / count, sum[1] / count, sum[2] / count]
+        })
+        .collect();
+    (palette, indices)
+}
+
+/// Returns the channel with the largest range in a box of colors, and that range
+fn widest_channel(colors: &[[f64; 3]], members: &[usize]) -> (usize, f64) {
+    (0..3)
+        .map(|c| {
+            let (min, max) = members
+                .iter()
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |acc, &m| {
+                    (acc.0.min(colors[m][c]), acc.1.max(colors[m][c]))
+                });
+            (c, max - min)
+        })
+        .fold(
+            (0, 0.0),
+            |best, next| if next.1 > best.1 { next } else { best },
+        )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn pixels() -> Vec<Rgb<u8>> {
+        vec![
+            Rgb::new(250, 10, 10),
+            Rgb::new(240, 20, 0),
+            Rgb::new(10, 200, 30),
+            Rgb::new(20, 220, 40),
+            Rgb::new(0, 0, 250),
+            Rgb::new(10, 10, 230),
+            Rgb::new(255, 0, 0),
+            Rgb::new(230, 30, 20),
+        ]
+    }
+
+    #[test]
+    fn test_median_cut() {
+        let quantized = median_cut(&pixels(), 3);
+        assert_eq!(quantized.palette().len(), 3);
+        let indices = quantized.indices();
+        assert_eq!(indices[0], indices[1]);
+        assert_eq!(indices[0], indices[6]);
+        assert_eq!(indices[2], indices[3]);
+        assert_eq!(indices[4], indices[5]);
+        assert_eq!(indices[0], indices[7]);
+        assert_eq!(quantized.palette()[indices[2]], Rgb::new(15, 210, 35));
+        let mut counts = quantized.counts();
+        counts.sort();
+        assert_eq!(counts, vec![2, 2, 4]);
+
+        let single = median_cut(&[Rgb::new(0.5f32, 0.5, 0.5); 4], 8);
+        assert_eq!(single.palette(), &[Rgb::new(0.5, 0.5, 0.5)]);
+        assert_eq!(single.indices(), &[0, 0, 0, 0]);
+        assert!(median_cut::<u8>(&[], 4).palette().is_empty());
+    }
+
+    #[test]
+    fn test_kmeans() {
+        let quantized = kmeans(&pixels(), 3, 10);
+        let (palette, indices) = quantized.into_parts();
+        assert_eq!(palette.len(), 3);
+        assert_eq!(indices[0], indices[1]);
+        assert_eq!(indices[0], indices[6]);
+        assert_eq!(indices[2], indices[3]);
+        assert_eq!(indices[4], indices[5]);
+        assert_ne!(indices[0], indices[2]);
+        assert_ne!(indices[2], indices[4]);
+
+        let red = &palette[indices[0]];
+        assert!(red.red() > 230 && red.green() < 30 && red.blue() < 30);
+
+        let gray = kmeans(
+            &[Rgb::new(0.25f32, 0.25, 0.25), Rgb::new(0.75, 0.75, 0.75)],
+            1,
+            10,
+        );
+        assert_eq!(gray.indices(), &[0, 0]);
+        let mid = &gray.palette()[0];
+        assert!(mid.red() > 0.25 && mid.red() < 0.75);
+    }
+}
@@ -0,0 +1,376 @@
+//! Random color sampling, enabled by the `rand` feature
+//!
+//! Three kinds of sampling are provided:
+//!
+//! * `rand`'s [`Standard`](https://docs.rs/rand/0.8/rand/distributions/struct.Standard.html)
+//!   distribution samples the device dependent colors uniformly within their normalized range.
+//!   Hues are sampled over the full circle.
+//! * [`InGamut`](struct.InGamut.html) samples `Xyz` colors uniformly within the gamut of an RGB
+//!   color space.
+//! * [`UniformOklab`](struct.UniformOklab.html) samples `Oklab` colors uniformly within the gamut of
+//!   an RGB color space, so that the samples are spread evenly by perceived difference.
+//!
+//! ## Examples:
+//!
+//! ```rust
+//! # extern crate prisma;
+//! extern crate rand;
+//!
+//! use prisma::{Bounded, Oklab, Rgb};
+//! use prisma::color_space::named::SRgb;
+//! use prisma::random::UniformOklab;
+//! use rand::Rng;
+//!
+//! let mut rng = rand::thread_rng();
+//! let rgb: Rgb<f32> = rng.gen();
+//! assert!(rgb.is_normalized());
+//!
+//! let oklab: Oklab<f64> = rng.sample(UniformOklab::new(SRgb::new()));
+//! assert!(oklab.L() >= 0.0 && oklab.L() <= 1.0);
+//! ```
+
+#![allow(non_snake_case)]
+
+use crate::alpha::Alpha;
+use crate::channel::{AngularChannelScalar, FreeChannelScalar, PosNormalChannelScalar};
+use crate::color::Color;
+use crate::color_space::ColorSpace;
+use crate::convert::FromColor;
+use crate::gamut::is_in_gamut;
+use crate::hsl::Hsl;
+use crate::hsv::Hsv;
+use crate::hwb::{Hwb, HwbBoundedChannelTraits};
+use crate::linalg::Matrix3;
+use crate::lms::{chromatic_adaptation_transform, Bradford};
+use crate::luma::Luma;
+use crate::oklab::Oklab;
+use crate::rgb::Rgb;
+use crate::white_point::{WhitePoint, D65};
+use crate::xyz::Xyz;
+use num_traits::{cast, Float};
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+impl<T> Distribution<Rgb<T>> for Standard
+where
+    T: PosNormalChannelScalar,
+    Standard: Distribution<T>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Rgb<T> {
+        Rgb::new(rng.gen(), rng.gen(), rng.gen())
+    }
+}
+
+impl<T> Distribution<Luma<T>> for Standard
+where
+    T: PosNormalChannelScalar,
+    Standard: Distribution<T>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Luma<T> {
+        Luma::new(rng.gen())
+    }
+}
+
+impl<T, A> Distribution<Hsv<T, A>> for Standard
+where
+    T: PosNormalChannelScalar,
+    A: AngularChannelScalar,
+    A::Scalar: Float,
+    Standard: Distribution<T> + Distribution<A::Scalar>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Hsv<T, A> {
+        Hsv::new(sample_hue(rng), rng.gen(), rng.gen())
+    }
+}
+
+impl<T, A> Distribution<Hsl<T, A>> for Standard
+where
+    T: PosNormalChannelScalar,
+    A: AngularChannelScalar,
+    A::Scalar: Float,
+    Standard: Distribution<T> + Distribution<A::Scalar>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Hsl<T, A> {
+        Hsl::new(sample_hue(rng), rng.gen(), rng.gen())
+    }
+}
+
+impl<T, A> Distribution<Hwb<T, A>> for Standard
+where
+    T: HwbBoundedChannelTraits,
+    A: AngularChannelScalar,
+    A::Scalar: Float,
+    Standard: Distribution<T> + Distribution<A::Scalar>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Hwb<T, A> {
+        Hwb::new(sample_hue(rng), rng.gen(), rng.gen())
+    }
+}
+
+impl<T, InnerColor> Distribution<Alpha<T, InnerColor>> for Standard
+where
+    T: PosNormalChannelScalar,
+    InnerColor: Color,
+    Standard: Distribution<T> + Distribution<InnerColor>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Alpha<T, InnerColor> {
+        Alpha::new(rng.gen(), rng.gen())
+    }
+}
+
+/// Samples `Xyz` colors uniformly within the gamut of an RGB color space
+///
+/// Linear RGB maps to `Xyz` by a linear transform, so the gamut is sampled evenly by drawing each
+/// linear channel uniformly from [0, 1]. The resulting colors are relative to the white point of
+/// the space.
+///
+/// ## Examples:
+///
+/// ```rust
+/// # extern crate prisma;
+/// extern crate rand;
+///
+/// use prisma::Xyz;
+/// use prisma::color_space::named::SRgb;
+/// use prisma::random::InGamut;
+/// use rand::Rng;
+///
+/// let colors: Vec<Xyz<f64>> = rand::thread_rng()
+///     .sample_iter(InGamut::new(SRgb::new()))
+///     .take(16)
+///     .collect();
+/// assert!(colors.iter().all(|c| c.y() >= 0.0 && c.y() <= 1.0));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct InGamut<S> {
+    space: S,
+}
+
+impl<S> InGamut<S> {
+    /// Construct a new `InGamut` sampling the gamut of `space`
+    pub fn new(space: S) -> Self {
+        InGamut { space }
+    }
+    /// Returns a reference to the sampled color space
+    pub fn space(&self) -> &S {
+        &self.space
+    }
+}
+
+impl<T, S> Distribution<Xyz<T>> for InGamut<S>
+where
+    T: FreeChannelScalar,
+    S: ColorSpace<T>,
+    Standard: Distribution<T>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Xyz<T> {
+        let (x, y, z) = self
+            .space
+            .apply_transform((rng.gen(), rng.gen(), rng.gen()));
+        Xyz::new(x, y, z)
+    }
+}
+
+/// Samples `Oklab` colors uniformly within the gamut of an RGB color space
+///
+/// `Oklab` is close to perceptually uniform, so the samples are spread evenly by how different
+/// they look rather than by their channel values. Colors are drawn uniformly from a box bounding
+/// the gamut and rejected until one falls inside it. The bounds are found when the distribution
+/// is constructed, so a `UniformOklab` should be reused when drawing many samples.
+///
+/// `Oklab` is relative to D65. Spaces with another white point are adapted to D65 with the
+/// Bradford model.
+///
+/// ## Examples:
+///
+/// ```rust
+/// # extern crate prisma;
+/// extern crate rand;
+///
+/// use prisma::{Color, FromColor, Oklab, Xyz};
+/// use prisma::color_space::named::SRgb;
+/// use prisma::color_space::ColorSpace;
+/// use prisma::random::UniformOklab;
+/// use rand::Rng;
+///
+/// let space = SRgb::new();
+/// let oklab: Oklab<f64> = rand::thread_rng().sample(UniformOklab::new(space.clone()));
+/// let xyz = Xyz::from_color(&oklab);
+/// let (r, g, b) = space.get_inverse_xyz_transform().transform_vector(xyz.to_tuple());
+/// assert!(r > -1e-9 && g > -1e-9 && b > -1e-9);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct UniformOklab<T, S> {
+    space: S,
+    from_xyz: Matrix3<T>,
+    min: (T, T, T),
+    max: (T, T, T),
+}
+
+impl<T, S> UniformOklab<T, S>
+where
+    T: Float + FreeChannelScalar + PosNormalChannelScalar,
+    S: ColorSpace<T>,
+{
+    /// The number of steps along each edge of the grid used to find the bounds of the gamut
+    const GRID_STEPS: usize = 16;
+    /// The margin added to the bounds to cover the gamut between grid points
+    const MARGIN: f64 = 0.01;
+
+    /// Construct a new `UniformOklab` sampling the gamut of `space`
+    pub fn new(space: S) -> Self {
+        let to_d65 = if space.white_point() == D65.get_xyz() {
+            Matrix3::identity()
+        } else {
+            chromatic_adaptation_transform::<T, Bradford>(&space.white_point(), &D65.get_xyz())
+        };
+        let to_xyz = to_d65.clone() * space.get_xyz_transform();
+        let from_xyz = space.get_inverse_xyz_transform() * to_d65.try_inverse().unwrap();
+
+        // The gamut boundary is the image of the faces of the RGB cube, so the extreme values of
+        // each channel lie on one of its faces.
+        let steps = Self::GRID_STEPS;
+        let step = |i: usize| -> T { cast::<_, T>(i).unwrap() / cast(steps).unwrap() };
+        let mut min = (T::infinity(), T::infinity(), T::infinity());
+        let mut max = (T::neg_infinity(), T::neg_infinity(), T::neg_infinity());
+        for face in 0..6 {
+            let fixed = if face < 3 { T::zero() } else { T::one() };
+            for i in 0..=steps {
+                for j in 0..=steps {
+                    let rgb = match face % 3 {
+                        0 => (fixed, step(i), step(j)),
+                        1 => (step(i), fixed, step(j)),
+                        _ => (step(i), step(j), fixed),
+                    };
+                    let (x, y, z) = to_xyz.transform_vector(rgb);
+                    let (L, a, b) = Oklab::from_color(&Xyz::new(x, y, z)).to_tuple();
+                    min = (min.0.min(L), min.1.min(a), min.2.min(b));
+                    max = (max.0.max(L), max.1.max(a), max.2.max(b));
+                }
+            }
+        }
+        let margin = cast(Self::MARGIN).unwrap();
+        UniformOklab {
+            space,
+            from_xyz,
+            min: (min.0.max(T::zero()), min.1 - margin, min.2 - margin),
+            max: (max.0, max.1 + margin, max.2 + margin),
+        }
+    }
+
+    /// Returns a reference to the sampled color space
+    pub fn space(&self) -> &S {
+        &self.space
+    }
+
+    /// Returns true if `color` lies within the gamut of the sampled color space
+    pub fn contains(&self, color: &Oklab<T>) -> bool {
+        let (r, g, b) = self
+            .from_xyz
+            .transform_vector(Xyz::from_color(color).to_tuple());
+        is_in_gamut(&Rgb::new(r, g, b))
+    }
+}
+
+impl<T, S> Distribution<Oklab<T>> for UniformOklab<T, S>
+where
+    T: Float + FreeChannelScalar + PosNormalChannelScalar,
+    S: ColorSpace<T>,
+    Standard: Distribution<T>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Oklab<T> {
+        let between = |rng: &mut R, min: T, max: T| min + (max - min) * rng.gen();
+        loop {
+            let color = Oklab::new(
+                between(rng, self.min.0, self.max.0),
+                between(rng, self.min.1, self.max.1),
+                between(rng, self.min.2, self.max.2),
+            );
+            if self.contains(&color) {
+                return color;
+            }
+        }
+    }
+}
+
+fn sample_hue<A, R>(rng: &mut R) -> A
+where
+    A: AngularChannelScalar,
+    A::Scalar: Float,
+    R: Rng + ?Sized,
+    Standard: Distribution<A::Scalar>,
+{
+    A::new(A::period() * rng.gen())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::color::Bounded;
+    use crate::color_space::named::{ProPhotoRgb, SRgb};
+    use crate::hwb::Hwb;
+    use angle::{Deg, Turns};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_standard() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let colors: Vec<Rgb<f64>> = (&mut rng).sample_iter(Standard).take(1000).collect();
+        assert!(colors.iter().all(|c| c.is_normalized()));
+        let mean = colors.iter().map(|c| c.red()).sum::<f64>() / 1000.0;
+        assert!(mean > 0.45 && mean < 0.55);
+
+        let hues: Vec<Hsv<f32, Deg<f32>>> = (&mut rng).sample_iter(Standard).take(1000).collect();
+        assert!(hues.iter().all(|c| c.is_normalized()));
+        assert!(hues.iter().any(|c| c.hue() > Deg(300.0)));
+        assert!(hues.iter().any(|c| c.hue() < Deg(60.0)));
+
+        let hsl: Hsl<f64, Turns<f64>> = rng.gen();
+        assert!(hsl.is_normalized());
+        let hwb: Hwb<f64> = rng.gen();
+        assert!(hwb.is_normalized());
+        let luma: Luma<u8> = rng.gen();
+        assert!(luma.is_normalized());
+        let rgba: Alpha<u8, Rgb<u8>> = rng.gen();
+        assert!(rgba.is_normalized());
+    }
+
+    #[test]
+    fn test_in_gamut() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let space = SRgb::new();
+        let dist = InGamut::new(space.clone());
+        for _ in 0..1000 {
+            let xyz: Xyz<f64> = rng.sample(&dist);
+            let (r, g, b) = space
+                .get_inverse_xyz_transform()
+                .transform_vector(xyz.to_tuple());
+            assert!(r > -1e-9 && r < 1.0 + 1e-9);
+            assert!(g > -1e-9 && g < 1.0 + 1e-9);
+            assert!(b > -1e-9 && b < 1.0 + 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_uniform_oklab() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let dist = UniformOklab::new(SRgb::new());
+        assert!(dist.contains(&Oklab::new(0.5, 0.0, 0.0)));
+        assert!(!dist.contains(&Oklab::new(0.5, 0.3, 0.3)));
+
+        let colors: Vec<Oklab<f64>> = (&mut rng).sample_iter(&dist).take(2000).collect();
+        assert!(colors.iter().all(|c| dist.contains(c)));
+        let dark = colors.iter().filter(|c| c.L() < 0.5).count();
+        let light = colors.iter().filter(|c| c.L() >= 0.5).count();
+        // The sRGB gamut is wider at high lightness, but both halves are well represented
+        assert!(dark > 400 && light > 800);
+        assert!(colors.iter().any(|c| c.a() > 0.15));
+        assert!(colors.iter().any(|c| c.b() < -0.15));
+
+        let wide = UniformOklab::new(ProPhotoRgb::new());
+        let wide_colors: Vec<Oklab<f64>> = (&mut rng).sample_iter(&wide).take(200).collect();
+        assert!(wide_colors.iter().all(|c| wide.contains(c)));
+        assert!(wide_colors.iter().any(|c| !dist.contains(c)));
+    }
+}
@@ -2,13 +2,14 @@
 //!
 //! Provides the [Rgb<T>](struct.Rgb.html) type.
 
+use crate::alpha::Rgba;
 use crate::channel::{
     AngularChannelScalar, ChannelCast, ChannelFormatCast, ColorChannel, PosNormalBoundedChannel,
-    PosNormalChannelScalar,
+    PosNormalChannelScalar, SaturatingChannelScalar,
 };
 use crate::chromaticity::ChromaticityCoordinates;
 use crate::color;
-use crate::color::{Broadcast, Color, FromTuple, HomogeneousColor};
+use crate::color::{Broadcast, ChannelAccess, ChannelOrder, Color, FromTuple, HomogeneousColor};
 use crate::convert;
 use crate::encoding::EncodableColor;
 use crate::hsl;
@@ -40,6 +41,9 @@ use std::slice;
 /// of `Rgb` is not specified or assumed, it is up to you to not mix color spaces improperly or use
 /// an appropriate wrapper.
 ///
+/// With integer channels, `Rgb` is `Eq`, `Hash` and `Ord`, so colors can be used as `HashMap` or
+/// `BTreeMap` keys. Colors are ordered by red, then green, then blue.
+///
 /// ## Examples:
 ///
 /// ```rust
@@ -71,6 +75,15 @@ where
         }
     }
 
+    /// Construct a new `Rgb` instance in a constant, skipping the `debug-validate` checks of `new`
+    pub(crate) const fn new_const(red: T, green: T, blue: T) -> Self {
+        Rgb {
+            red: PosNormalBoundedChannel(red),
+            green: PosNormalBoundedChannel(green),
+            blue: PosNormalBoundedChannel(blue),
+        }
+    }
+
     impl_color_color_cast_square!(
         Rgb { red, green, blue },
         chan_traits = { PosNormalChannelScalar }
@@ -114,6 +127,38 @@ where
     }
 }
 
+macro_rules! impl_rgb_swizzle {
+    ($($name:ident => ($r:ident, $g:ident, $b:ident)),*) => {
+        $(
+            #[doc = concat!(
+                "Returns the color with its channels reordered, as `Rgb::new(",
+                stringify!($r), ", ", stringify!($g), ", ", stringify!($b), ")`"
+            )]
+            pub fn $name(&self) -> Rgb<T> {
+                Rgb::new(self.$r(), self.$g(), self.$b())
+            }
+        )*
+    };
+}
+
+impl<T> Rgb<T>
+where
+    T: PosNormalChannelScalar,
+{
+    impl_rgb_swizzle!(
+        rbg => (red, blue, green),
+        grb => (green, red, blue),
+        gbr => (green, blue, red),
+        brg => (blue, red, green),
+        bgr => (blue, green, red)
+    );
+
+    /// Returns an `Rgba` color with the channels of `self` and the given alpha
+    pub fn rgba(&self, alpha: T) -> Rgba<T> {
+        Rgba::new(self.clone(), alpha)
+    }
+}
+
 impl<T> Rgb<T>
 where
     T: PosNormalChannelScalar + num_traits::Float,
@@ -167,6 +212,20 @@ where
     impl_color_homogeneous_color_square!(Rgb<T> {red, green, blue});
 }
 
+impl<T> ChannelOrder for Rgb<T>
+where
+    T: PosNormalChannelScalar,
+{
+    impl_color_channel_order!({red, green, blue});
+}
+
+impl<T> ChannelAccess for Rgb<T>
+where
+    T: PosNormalChannelScalar,
+{
+    impl_color_channel_access_square!(T {red, green, blue});
+}
+
 impl<T> Broadcast for Rgb<T>
 where
     T: PosNormalChannelScalar,
@@ -190,6 +249,13 @@ where
     impl_color_bounded!(Rgb { red, green, blue });
 }
 
+impl<T> color::BoundedArithmetic for Rgb<T>
+where
+    T: PosNormalChannelScalar + SaturatingChannelScalar,
+{
+    impl_color_bounded_arithmetic!(Rgb { red, green, blue });
+}
+
 impl<T> color::Lerp for Rgb<T>
 where
     T: PosNormalChannelScalar + color::Lerp,
@@ -331,6 +397,17 @@ where
     }
 }
 
+impl<T> convert::FromColor<Rgb<T>> for Rgb<T>
+where
+    T: PosNormalChannelScalar,
+{
+    /// Returns a copy of `from`, so `Rgb` can be passed where a color convertible to `Rgb` is
+    /// expected
+    fn from_color(from: &Rgb<T>) -> Self {
+        from.clone()
+    }
+}
+
 impl<T, A> convert::FromColor<Rgb<T>> for hsv::Hsv<T, A>
 where
     T: PosNormalChannelScalar + num_traits::Float,
@@ -393,6 +470,7 @@ where
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::channel::U10;
     use crate::color::*;
     use crate::convert::*;
     use crate::hsl::Hsl;
@@ -569,4 +647,128 @@ mod test {
             epsilon = 1e-4
         );
     }
+
+    #[test]
+    fn test_bounded_arithmetic() {
+        let c1 = Rgb::new(60000u16, 1000, 30000);
+        let c2 = Rgb::new(10000u16, 2000, 30000);
+        assert_eq!(c1.saturating_add(&c2), Rgb::new(65535, 3000, 60000));
+        assert_eq!(c1.saturating_sub(&c2), Rgb::new(50000, 0, 0));
+        assert_eq!(c1.checked_add(&c2), None);
+        assert_eq!(c1.checked_sub(&c2), None);
+        assert_eq!(c2.checked_add(&c2), Some(Rgb::new(20000, 4000, 60000)));
+
+        let c3 = Rgb::new(0.75f32, 0.25, 0.5);
+        let c4 = Rgb::new(0.5f32, 0.5, 0.25);
+        assert_eq!(c3.saturating_add(&c4), Rgb::new(1.0, 0.75, 0.75));
+        assert_eq!(c4.saturating_sub(&c3), Rgb::new(0.0, 0.25, 0.0));
+        assert_eq!(c3.checked_add(&c4), None);
+        assert_eq!(c3.checked_sub(&c4), None);
+        assert_eq!(c4.checked_add(&c4), Some(Rgb::new(1.0, 1.0, 0.5)));
+
+        let c5 = Rgb::new(U10::new(1000), U10::new(10), U10::new(0));
+        assert_eq!(
+            c5.saturating_add(&c5),
+            Rgb::new(U10::MAX, U10::new(20), U10::new(0))
+        );
+        assert_eq!(
+            c5.checked_sub(&c5),
+            Some(Rgb::new(U10::new(0), U10::new(0), U10::new(0)))
+        );
+    }
+
+    #[test]
+    fn test_integer_keys() {
+        use crate::ycbcr::YCbCr;
+        use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+        let pixels = [
+            Rgb::new(255u8, 0, 0),
+            Rgb::new(0, 255, 0),
+            Rgb::new(255, 0, 0),
+            Rgb::new(0, 0, 255),
+            Rgb::new(255, 0, 0),
+        ];
+        let mut counts = HashMap::new();
+        for pixel in pixels.iter() {
+            *counts.entry(*pixel).or_insert(0) += 1;
+        }
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts[&Rgb::new(255, 0, 0)], 3);
+        assert_eq!(counts[&Rgb::new(0, 0, 255)], 1);
+
+        let sorted: BTreeMap<_, _> = counts.into_iter().collect();
+        let keys: Vec<_> = sorted.keys().cloned().collect();
+        assert_eq!(
+            keys,
+            vec![
+                Rgb::new(0, 0, 255),
+                Rgb::new(0, 255, 0),
+                Rgb::new(255, 0, 0)
+            ]
+        );
+        assert!(Rgb::new(10u8, 200, 200) < Rgb::new(11, 0, 0));
+        assert!(Rgb::new(10u8, 20, 30) < Rgb::new(10, 20, 31));
+
+        let rgba: HashSet<Rgba<u8>> = [
+            Rgba::new(Rgb::new(1, 2, 3), 255),
+            Rgba::new(Rgb::new(1, 2, 3), 128),
+            Rgba::new(Rgb::new(1, 2, 3), 255),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        assert_eq!(rgba.len(), 2);
+        assert!(Rgba::new(Rgb::new(1u8, 2, 3), 255) < Rgba::new(Rgb::new(1, 2, 4), 0));
+
+        let ycbcr: BTreeSet<YCbCr<u8>> = [
+            YCbCr::new(200, 128, 128),
+            YCbCr::new(16, 128, 128),
+            YCbCr::new(200, 128, 128),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        assert_eq!(ycbcr.len(), 2);
+        assert_eq!(ycbcr.iter().next(), Some(&YCbCr::new(16, 128, 128)));
+    }
+
+    #[test]
+    fn test_swizzle() {
+        let c1 = Rgb::new(10u8, 20, 30);
+        assert_eq!(c1.bgr(), Rgb::new(30, 20, 10));
+        assert_eq!(c1.grb(), Rgb::new(20, 10, 30));
+        assert_eq!(c1.gbr(), Rgb::new(20, 30, 10));
+        assert_eq!(c1.brg(), Rgb::new(30, 10, 20));
+        assert_eq!(c1.rbg(), Rgb::new(10, 30, 20));
+        assert_eq!(c1.gbr().gbr().gbr(), c1);
+        assert_eq!(c1.bgr().bgr(), c1);
+        assert_eq!(c1.rgba(40), Rgba::new(c1, 40));
+    }
+
+    #[test]
+    fn test_map_channels() {
+        let c1 = Rgb::new(0.25f32, 0.5, 1.0);
+        assert_relative_eq!(
+            c1.map_channels(|c| c.powf(2.0)),
+            Rgb::new(0.0625, 0.25, 1.0)
+        );
+        let c2 = Rgb::new(0.5f32, 0.25, 0.75);
+        assert_relative_eq!(c1.zip_channels(&c2, f32::min), Rgb::new(0.25, 0.25, 0.75));
+        assert_relative_eq!(
+            c1.zip_channels(&c2, |a, b| (a - b).abs()),
+            Rgb::new(0.25, 0.25, 0.25)
+        );
+
+        let c3 = Rgba::new(Rgb::new(10u8, 20, 30), 40);
+        assert_eq!(
+            c3.map_channels(|c| c + 1),
+            Rgba::new(Rgb::new(11, 21, 31), 41)
+        );
+        let c4 = Rgba::new(Rgb::new(40u8, 10, 30), 20);
+        assert_eq!(
+            c3.zip_channels(&c4, u8::max),
+            Rgba::new(Rgb::new(40, 20, 30), 40)
+        );
+    }
 }
@@ -3,7 +3,10 @@
 use crate::channel::{
     ChannelCast, ChannelFormatCast, ColorChannel, PosNormalBoundedChannel, PosNormalChannelScalar,
 };
-use crate::color::{Bounded, Broadcast, Color, Flatten, FromTuple, HomogeneousColor, Lerp};
+use crate::color::{
+    Bounded, Broadcast, ChannelAccess, ChannelOrder, Color, Flatten, FromTuple, HomogeneousColor,
+    Lerp,
+};
 use crate::convert::FromColor;
 use crate::encoding::EncodableColor;
 use crate::rgb::Rgb;
@@ -202,6 +205,20 @@ where
     impl_color_homogeneous_color_square!(Rgi<T> {red, green, intensity});
 }
 
+impl<T> ChannelOrder for Rgi<T>
+where
+    T: PosNormalChannelScalar + Float,
+{
+    impl_color_channel_order!({red, green, intensity});
+}
+
+impl<T> ChannelAccess for Rgi<T>
+where
+    T: PosNormalChannelScalar + Float,
+{
+    impl_color_channel_access_square!(T {red, green, intensity});
+}
+
 impl<T> Broadcast for Rgi<T>
 where
     T: PosNormalChannelScalar + Float,
@@ -0,0 +1,340 @@
+//! Measuring the accuracy of conversion chains
+//!
+//! Converting a color to another model and back should give the original color, but finite
+//! precision, clipping and quantization make most chains slightly lossy. The functions in this
+//! module run a color through a round trip and report how far the result ended up from where it
+//! started, both per channel and with a [`ColorDistance`](../distance/trait.ColorDistance.html)
+//! metric such as $`\Delta E`$.
+//!
+//! [`measure`](fn.measure.html) measures a single color through any chain given as a closure,
+//! while [`measure_via`](fn.measure_via.html) measures the round trip through the model `B` of
+//! [`PivotColor`](../trait.PivotColor.html) colors. [`measure_all`](fn.measure_all.html)
+//! gathers [`RoundTripStats`](struct.RoundTripStats.html) over many colors, typically the
+//! samples of [`rgb_grid`](fn.rgb_grid.html).
+//!
+//! Channel errors are computed on channels cast to normalized `f64` values with
+//! `ChannelFormatCast`, so an error of one step of an `Rgb<u8>` channel is `1 / 255`.
+//!
+//! ## Examples:
+//!
+//! ```rust
+//! # extern crate prisma;
+//! extern crate angular_units as angle;
+//!
+//! use prisma::{Hsv, Lab, PivotColor, Xyz};
+//! use prisma::distance::{Cie76, Euclidean, Via};
+//! use prisma::roundtrip;
+//! use prisma::white_point::D65;
+//! use angle::Deg;
+//!
+//! let grid = roundtrip::rgb_grid::<f64>(9);
+//! let stats = roundtrip::measure_all(grid, |c| c.convert::<Hsv<f64, Deg<f64>>>().convert(),
+//!     &Euclidean);
+//! assert_eq!(stats.count(), 729);
+//! assert!(stats.max_distance() < 1e-9);
+//!
+//! let xyz = Xyz::new(0.3f32, 0.4, 0.5);
+//! let delta_e = Via::new(|c: &Xyz<f32>| Lab::from_xyz(c, D65), Cie76);
+//! let result = roundtrip::measure_via::<_, Lab<f32, D65>, _>(&xyz, &delta_e);
+//! assert!(result.distance() < 1e-3);
+//! ```
+
+use crate::channel::{ChannelFormatCast, PosNormalChannelScalar};
+use crate::color::Flatten;
+use crate::convert::PivotColor;
+use crate::distance::ColorDistance;
+use crate::rgb::Rgb;
+use num_traits::{self, ToPrimitive};
+
+/// The error of a single color after a round trip
+#[derive(Clone, Debug, PartialEq)]
+pub struct RoundTrip<C> {
+    original: C,
+    result: C,
+    channel_errors: Vec<f64>,
+    distance: f64,
+}
+
+impl<C> RoundTrip<C> {
+    /// Returns the color before the round trip
+    pub fn original(&self) -> &C {
+        &self.original
+    }
+    /// Returns the color after the round trip
+    pub fn result(&self) -> &C {
+        &self.result
+    }
+    /// Returns the absolute error of each normalized channel
+    pub fn channel_errors(&self) -> &[f64] {
+        &self.channel_errors
+    }
+    /// Returns the largest absolute error of any normalized channel
+    pub fn max_channel_error(&self) -> f64 {
+        self.channel_errors.iter().cloned().fold(0.0, f64::max)
+    }
+    /// Returns the distance between the original and result colors
+    pub fn distance(&self) -> f64 {
+        self.distance
+    }
+}
+
+/// Statistics over the round trips of many colors
+///
+/// Returned by [`measure_all`](fn.measure_all.html), or built up one color at a time with
+/// [`add`](#method.add).
+#[derive(Clone, Debug, PartialEq)]
+pub struct RoundTripStats<C> {
+    count: usize,
+    max_channel_errors: Vec<f64>,
+    sum_channel_errors: Vec<f64>,
+    max_distance: f64,
+    sum_distance: f64,
+    sum_squared_distance: f64,
+    worst: Option<RoundTrip<C>>,
+}
+
+impl<C> RoundTripStats<C> {
+    /// Construct an empty `RoundTripStats`
+    pub fn new() -> Self {
+        RoundTripStats {
+            count: 0,
+            max_channel_errors: Vec::new(),
+            sum_channel_errors: Vec::new(),
+            max_distance: 0.0,
+            sum_distance: 0.0,
+            sum_squared_distance: 0.0,
+            worst: None,
+        }
+    }
+
+    /// Add the error of one round trip
+    pub fn add(&mut self, round_trip: RoundTrip<C>) {
+        let channels = round_trip.channel_errors.len();
+        if self.max_channel_errors.len() < channels {
+            self.max_channel_errors.resize(channels, 0.0);
+            self.sum_channel_errors.resize(channels, 0.0);
+        }
+        for (i, &error) in round_trip.channel_errors.iter().enumerate() {
+            self.max_channel_errors[i] = self.max_channel_errors[i].max(error);
+            self.sum_channel_errors[i] += error;
+        }
+        self.count += 1;
+        self.sum_distance += round_trip.distance;
+        self.sum_squared_distance += round_trip.distance * round_trip.distance;
+        if self.worst.is_none() || round_trip.distance > self.max_distance {
+            self.max_distance = round_trip.distance;
+            self.worst = Some(round_trip);
+        }
+    }
+
+    /// Returns the number of colors measured
+    pub fn count(&self) -> usize {
+        self.count
+    }
+    /// Returns the largest error of each normalized channel
+    pub fn max_channel_errors(&self) -> &[f64] {
+        &self.max_channel_errors
+    }
+    /// Returns the mean error of each normalized channel
+    pub fn mean_channel_errors(&self) -> Vec<f64> {
+        self.sum_channel_errors
+            .iter()
+            .map(|&sum| sum / self.count as f64)
+            .collect()
+    }
+    /// Returns the largest distance of any round trip
+    pub fn max_distance(&self) -> f64 {
+        self.max_distance
+    }
+    /// Returns the mean distance, or zero if nothing was measured
+    pub fn mean_distance(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.sum_distance / self.count as f64
+        }
+    }
+    /// Returns the root mean square distance, or zero if nothing was measured
+    pub fn rms_distance(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            (self.sum_squared_distance / self.count as f64).sqrt()
+        }
+    }
+    /// Returns the round trip with the largest distance, if any
+    pub fn worst(&self) -> Option<&RoundTrip<C>> {
+        self.worst.as_ref()
+    }
+}
+
+impl<C> Default for RoundTripStats<C> {
+    fn default() -> Self {
+        RoundTripStats::new()
+    }
+}
+
+/// Measure the error of `color` after applying `round_trip`, with the distance given by `metric`
+///
+/// `round_trip` may be any conversion chain starting and ending in the model of `color`, such as
+/// a conversion to another model and back or a whole `Converter` pipeline.
+pub fn measure<C, F, M>(color: &C, round_trip: F, metric: &M) -> RoundTrip<C>
+where
+    C: Flatten + Clone,
+    C::ChannelFormat: ChannelFormatCast<f64> + Clone,
+    F: FnOnce(&C) -> C,
+    M: ColorDistance<C>,
+    M::Output: num_traits::ToPrimitive,
+{
+    let result = round_trip(color);
+    let channel_errors = color
+        .as_slice()
+        .iter()
+        .zip(result.as_slice().iter())
+        .map(|(a, b)| {
+            let a: f64 = a.clone().cast();
+            let b: f64 = b.clone().cast();
+            (a - b).abs()
+        })
+        .collect();
+    let distance = metric.distance(color, &result).to_f64().unwrap();
+    RoundTrip {
+        original: color.clone(),
+        result,
+        channel_errors,
+        distance,
+    }
+}
+
+/// Measure the error of converting `color` to the model `B` and back
+pub fn measure_via<C, B, M>(color: &C, metric: &M) -> RoundTrip<C>
+where
+    C: Flatten + PivotColor + Clone,
+    C::ChannelFormat: ChannelFormatCast<f64> + Clone,
+    B: PivotColor<Pivot = C::Pivot>,
+    M: ColorDistance<C>,
+    M::Output: num_traits::ToPrimitive,
+{
+    measure(
+        color,
+        |c| C::from_pivot(&B::from_pivot(&c.to_pivot()).to_pivot()),
+        metric,
+    )
+}
+
+/// Measure the round trip errors of every color in `colors`
+pub fn measure_all<C, I, F, M>(colors: I, round_trip: F, metric: &M) -> RoundTripStats<C>
+where
+    C: Flatten + Clone,
+    C::ChannelFormat: ChannelFormatCast<f64> + Clone,
+    I: IntoIterator<Item = C>,
+    F: Fn(&C) -> C,
+    M: ColorDistance<C>,
+    M::Output: num_traits::ToPrimitive,
+{
+    let mut stats = RoundTripStats::new();
+    for color in colors {
+        stats.add(measure(&color, &round_trip, metric));
+    }
+    stats
+}
+
+/// Returns a grid of `steps` evenly spaced values per channel covering the `Rgb` cube
+///
+/// The grid has `steps³` colors, including the black and white corners.
+///
+/// # Panics
+/// If `steps` is less than two.
+pub fn rgb_grid<T>(steps: usize) -> Vec<Rgb<T>>
+where
+    T: PosNormalChannelScalar,
+    f64: ChannelFormatCast<T>,
+{
+    assert!(steps >= 2, "a grid needs at least two steps per channel");
+    let value = |i: usize| -> T { (i as f64 / (steps - 1) as f64).cast() };
+    let mut grid = Vec::with_capacity(steps * steps * steps);
+    for r in 0..steps {
+        for g in 0..steps {
+            for b in 0..steps {
+                grid.push(Rgb::new(value(r), value(g), value(b)));
+            }
+        }
+    }
+    grid
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::distance::Euclidean;
+    use crate::luma::Luma;
+    use crate::ycbcr::YCbCrJpeg;
+    use approx::*;
+
+    #[test]
+    fn test_measure() {
+        let color = Rgb::new(0.25f64, 0.5, 0.75);
+        let exact = measure_via::<_, YCbCrJpeg<f64>, _>(&color, &Euclidean);
+        assert_relative_eq!(exact.max_channel_error(), 0.0, epsilon = 1e-3);
+
+        let lossy = measure_via::<_, Luma<f64>, _>(&color, &Euclidean);
+        assert_eq!(lossy.original(), &color);
+        assert_eq!(lossy.channel_errors().len(), 3);
+        let result = lossy.result().clone();
+        assert_relative_eq!(lossy.channel_errors()[0], (result.red() - 0.25).abs());
+        assert_relative_eq!(lossy.distance(), Euclidean.distance(&color, &result));
+
+        let shift = |c: &Rgb<u8>| Rgb::new(c.red() + 1, c.green(), c.blue() + 2);
+        let quantized = measure(&Rgb::new(10u8, 20, 30), shift, &CodeDistance);
+        assert_relative_eq!(quantized.channel_errors()[0], 1.0 / 255.0);
+        assert_relative_eq!(quantized.max_channel_error(), 2.0 / 255.0);
+        assert_relative_eq!(quantized.distance(), 5.0f64.sqrt());
+    }
+
+    struct CodeDistance;
+    impl ColorDistance<Rgb<u8>> for CodeDistance {
+        type Output = f64;
+        fn distance(&self, c1: &Rgb<u8>, c2: &Rgb<u8>) -> f64 {
+            Euclidean.distance(&c1.color_cast::<f64>(), &c2.color_cast::<f64>()) * 255.0
+        }
+    }
+
+    #[test]
+    fn test_stats() {
+        let grid = rgb_grid::<u8>(3);
+        assert_eq!(grid.len(), 27);
+        assert_eq!(grid[0], Rgb::new(0, 0, 0));
+        assert_eq!(grid[13], Rgb::new(127, 127, 127));
+        assert_eq!(grid[26], Rgb::new(255, 255, 255));
+
+        let colors = vec![Luma::new(0.5f64), Luma::new(0.2), Luma::new(0.9)];
+        let stats = measure_all(
+            colors,
+            |c| Luma::new((c.luma() * 10.0).round() / 10.0),
+            &Euclidean,
+        );
+        assert_eq!(stats.count(), 3);
+        assert_relative_eq!(stats.max_channel_errors()[0], 0.0, epsilon = 1e-12);
+
+        let colors = vec![Luma::new(0.51f64), Luma::new(0.23), Luma::new(0.9)];
+        let stats = measure_all(
+            colors,
+            |c| Luma::new((c.luma() * 10.0).round() / 10.0),
+            &Euclidean,
+        );
+        assert_relative_eq!(stats.max_distance(), 0.03, epsilon = 1e-12);
+        assert_relative_eq!(stats.mean_distance(), 0.04 / 3.0, epsilon = 1e-12);
+        assert_relative_eq!(
+            stats.rms_distance(),
+            (0.001f64 / 3.0).sqrt(),
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(stats.mean_channel_errors()[0], 0.04 / 3.0, epsilon = 1e-12);
+        assert_eq!(stats.worst().unwrap().original(), &Luma::new(0.23));
+
+        let empty = RoundTripStats::<Luma<f64>>::default();
+        assert_eq!(empty.count(), 0);
+        assert_eq!(empty.mean_distance(), 0.0);
+        assert!(empty.worst().is_none());
+    }
+}
@@ -0,0 +1,754 @@
+//! Serialization of colors with serde
+//!
+//! With the `serde` feature, every color type, `Alpha`, `EncodedColor`, the encodings,
+//! `ChromaticityCoordinates` and the color space descriptors implement `Serialize` and
+//! `Deserialize`. This module only holds the implementations and documents their representation.
+//!
+//! ## Representation
+//!
+//! Colors are structs with one field per channel, named after the channel's accessor:
+//!
+//! | Type | Fields |
+//! |------|--------|
+//! | `Rgb` | `red`, `green`, `blue` |
+//! | `Rgi` | `red`, `green`, `intensity` |
+//! | `Luma` | `luma` |
+//! | `Hsv` | `hue`, `saturation`, `value` |
+//! | `Hsl` | `hue`, `saturation`, `lightness` |
+//! | `Hsi`, `eHsi` | `hue`, `saturation`, `intensity` |
+//! | `Hwb` | `hue`, `whiteness`, `blackness` |
+//! | `YCbCr`, `BareYCbCr` | `luma`, `cb`, `cr` |
+//! | `Xyz` | `x`, `y`, `z` |
+//! | `XyY` | `x`, `y`, `Y` |
+//! | `Lms` | `l`, `m`, `s` |
+//! | `Lab`, `Oklab` | `L`, `a`, `b` |
+//! | `Luv` | `L`, `u`, `v` |
+//! | `Lchab`, `Lchuv`, `Oklch` | `L`, `chroma`, `hue` |
+//! | `Alpha` | `color`, `alpha` |
+//! | `EncodedColor` | `color`, `encoding` |
+//! | `ChromaticityCoordinates` | `alpha`, `beta` |
+//! | `RgbPrimary` | `x`, `y` |
+//! | `EncodedColorSpace` | `red`, `green`, `blue`, `white_point`, `encoding` |
+//!
+//! The struct names are the names of the types. In self-describing formats such as JSON, an
+//! `Rgb<f32>` is written as `{"red":1.0,"green":0.5,"blue":0.0}`.
+//!
+//! * Hues are written as a bare number in the unit of the hue type, so a hue of `Deg(120.0)` is
+//!   `120.0`, and reading it back as `Rad` gives `Rad(120.0)`, not the same angle.
+//! * Information held in the type rather than the value is not written. This is the white point
+//!   of `Lab`, `Luv`, `Lchab` and `Lchuv`, the model of `Lms`, and the model of `YCbCr`. These
+//!   types can only be read with white points and models that are unit types.
+//! * Encodings with no parameters and the named color spaces such as `SRgb` are unit structs,
+//!   which JSON writes as `null`. `GammaEncoding` is a newtype struct of its gamma value.
+//! * `EncodedColorSpace` stores only its primaries, white point and encoding. The transformation
+//!   matrices are rebuilt when it is read.
+//!
+//! Channel values are not clamped, so out of range colors survive a round trip. Values that no
+//! constructor would accept, such as `Rgi` ratios summing to more than one, or a color space with
+//! linearly dependent primaries, are errors.
+//!
+//! This module is only available with the `serde` feature.
+//!
+//! ## Examples:
+//!
+//! ```rust
+//! # extern crate prisma;
+//! extern crate serde_json;
+//!
+//! use prisma::{Hsv, Rgb, Rgba};
+//! use angle::Deg;
+//! # extern crate angular_units as angle;
+//!
+//! let rgb = Rgb::new(1.0f32, 0.5, 0.0);
+//! let json = serde_json::to_string(&rgb).unwrap();
+//! assert_eq!(json, r#"{"red":1.0,"green":0.5,"blue":0.0}"#);
+//! assert_eq!(serde_json::from_str::<Rgb<f32>>(&json).unwrap(), rgb);
+//!
+//! let hsv: Hsv<f64> = serde_json::from_str(r#"{"hue":90,"saturation":1,"value":0.5}"#).unwrap();
+//! assert_eq!(hsv, Hsv::new(Deg(90.0), 1.0, 0.5));
+//!
+//! let rgba = Rgba::new(Rgb::new(255u8, 0, 0), 128);
+//! let json = serde_json::to_string(&rgba).unwrap();
+//! assert_eq!(json, r#"{"color":{"red":255,"green":0,"blue":0},"alpha":128}"#);
+//! ```
+
+use crate::alpha::Alpha;
+use crate::channel::{
+    AngularChannelScalar, FreeChannelScalar, NormalChannelScalar, PosNormalChannelScalar,
+};
+use crate::chromaticity::ChromaticityCoordinates;
+use crate::color::Color;
+use crate::color_space::named::{
+    Aces2065_1, AcesCg, ProPhotoRgb, Rec2020, Rec601_525, Rec601_625, SRgb, ScRgb,
+};
+use crate::color_space::{ColorSpace, EncodedColorSpace, RgbPrimary};
+use crate::ehsi::eHsi;
+use crate::encoding::{
+    ColorEncoding, EncodableColor, EncodedColor, GammaEncoding, HlgEncoding, LinearEncoding,
+    PqEncoding, Rec2020Encoding, Rec709Encoding, RommEncoding, SrgbEncoding,
+};
+use crate::hsi::Hsi;
+use crate::hsl::Hsl;
+use crate::hsv::Hsv;
+use crate::hwb::{Hwb, HwbBoundedChannelTraits};
+use crate::lab::Lab;
+use crate::lchab::Lchab;
+use crate::lchuv::Lchuv;
+use crate::lms::{Lms, LmsModel};
+use crate::luma::Luma;
+use crate::luv::Luv;
+use crate::oklab::Oklab;
+use crate::oklch::Oklch;
+use crate::rgb::Rgb;
+use crate::rgi::Rgi;
+use crate::white_point::UnitWhitePoint;
+use crate::xyy::XyY;
+use crate::xyz::Xyz;
+use crate::ycbcr::{BareYCbCr, UnitModel, YCbCr, YCbCrModel};
+use angle::Angle;
+use num_traits::Float;
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// Implement `Serialize` and `Deserialize` for a color through a struct of its channels
+///
+/// `$repr` is the struct with the serialized fields, and `$name::new` is given the fields in
+/// order. Fields marked `hue` are angles written as their scalar value.
+macro_rules! impl_serde_color {
+    (
+        $name:ident<$($param:ident),*>, $sname:literal,
+        $repr:ident<$($rparam:ident = $rarg:ty),*> { $($field:ident: $kind:ident $fty:ident),* },
+        where $($bound:tt)*
+    ) => {
+        #[derive(Serialize, Deserialize)]
+        #[serde(rename = $sname)]
+        #[allow(non_snake_case)]
+        struct $repr<$($rparam),*> {
+            $($field: $fty),*
+        }
+
+        impl<$($param),*> Serialize for $name<$($param),*>
+        where
+            $($bound)*,
+            $repr<$($rarg),*>: Serialize,
+        {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                $repr {
+                    $($field: impl_serde_color!(@get $kind self.$field())),*
+                }
+                .serialize(serializer)
+            }
+        }
+
+        impl<'de, $($param),*> Deserialize<'de> for $name<$($param),*>
+        where
+            $($bound)*,
+            $repr<$($rarg),*>: Deserialize<'de>,
+        {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let channels = $repr::<$($rarg),*>::deserialize(deserializer)?;
+                Ok($name::new($(impl_serde_color!(@set $kind channels.$field)),*))
+            }
+        }
+    };
+    (@get channel $value:expr) => { $value };
+    (@get hue $value:expr) => { $value.scalar() };
+    (@set channel $value:expr) => { $value };
+    (@set hue $value:expr) => { Angle::new($value) };
+}
+
+impl_serde_color!(
+    Rgb<T>, "Rgb", RgbRepr<T = T> {
+        red: channel T, green: channel T, blue: channel T
+    },
+    where T: PosNormalChannelScalar
+);
+impl_serde_color!(
+    Luma<T>, "Luma", LumaRepr<T = T> { luma: channel T },
+    where T: PosNormalChannelScalar
+);
+impl_serde_color!(
+    Hsv<T, A>, "Hsv", HsvRepr<T = T, H = A::Scalar> {
+        hue: hue H, saturation: channel T, value: channel T
+    },
+    where
+        T: PosNormalChannelScalar,
+        A: AngularChannelScalar
+);
+impl_serde_color!(
+    Hsl<T, A>, "Hsl", HslRepr<T = T, H = A::Scalar> {
+        hue: hue H, saturation: channel T, lightness: channel T
+    },
+    where
+        T: PosNormalChannelScalar,
+        A: AngularChannelScalar
+);
+impl_serde_color!(
+    Hsi<T, A>, "Hsi", HsiRepr<T = T, H = A::Scalar> {
+        hue: hue H, saturation: channel T, intensity: channel T
+    },
+    where
+        T: PosNormalChannelScalar + Float,
+        A: AngularChannelScalar + Angle<Scalar = T>
+);
+impl_serde_color!(
+    eHsi<T, A>, "eHsi", EHsiRepr<T = T, H = A::Scalar> {
+        hue: hue H, saturation: channel T, intensity: channel T
+    },
+    where
+        T: PosNormalChannelScalar + Float,
+        A: AngularChannelScalar + Angle<Scalar = T>
+);
+impl_serde_color!(
+    Hwb<T, A>, "Hwb", HwbRepr<T = T, H = A::Scalar> {
+        hue: hue H, whiteness: channel T, blackness: channel T
+    },
+    where
+        T: HwbBoundedChannelTraits,
+        A: AngularChannelScalar
+);
+impl_serde_color!(
+    BareYCbCr<T>, "BareYCbCr", BareYCbCrRepr<T = T> {
+        luma: channel T, cb: channel T, cr: channel T
+    },
+    where T: NormalChannelScalar + PosNormalChannelScalar
+);
+impl_serde_color!(
+    YCbCr<T, M>, "YCbCr", YCbCrRepr<T = T> {
+        luma: channel T, cb: channel T, cr: channel T
+    },
+    where
+        T: NormalChannelScalar + PosNormalChannelScalar,
+        M: YCbCrModel<T> + UnitModel<T>
+);
+impl_serde_color!(
+    Xyz<T>, "Xyz", XyzRepr<T = T> {
+        x: channel T, y: channel T, z: channel T
+    },
+    where T: FreeChannelScalar
+);
+impl_serde_color!(
+    Lms<T, M>, "Lms", LmsRepr<T = T> {
+        l: channel T, m: channel T, s: channel T
+    },
+    where
+        T: FreeChannelScalar,
+        M: LmsModel<T>
+);
+impl_serde_color!(
+    Lab<T, W>, "Lab", LabRepr<T = T> {
+        L: channel T, a: channel T, b: channel T
+    },
+    where
+        T: FreeChannelScalar,
+        W: UnitWhitePoint<T>
+);
+impl_serde_color!(
+    Luv<T, W>, "Luv", LuvRepr<T = T> {
+        L: channel T, u: channel T, v: channel T
+    },
+    where
+        T: FreeChannelScalar,
+        W: UnitWhitePoint<T>
+);
+impl_serde_color!(
+    Lchab<T, W, A>, "Lchab", LchabRepr<T = T, H = A::Scalar> {
+        L: channel T, chroma: channel T, hue: hue H
+    },
+    where
+        T: FreeChannelScalar,
+        W: UnitWhitePoint<T>,
+        A: AngularChannelScalar
+);
+impl_serde_color!(
+    Lchuv<T, W, A>, "Lchuv", LchuvRepr<T = T, H = A::Scalar> {
+        L: channel T, chroma: channel T, hue: hue H
+    },
+    where
+        T: FreeChannelScalar,
+        W: UnitWhitePoint<T>,
+        A: AngularChannelScalar
+);
+impl_serde_color!(
+    Oklab<T>, "Oklab", OklabRepr<T = T> {
+        L: channel T, a: channel T, b: channel T
+    },
+    where T: FreeChannelScalar
+);
+impl_serde_color!(
+    Oklch<T, A>, "Oklch", OklchRepr<T = T, H = A::Scalar> {
+        L: channel T, chroma: channel T, hue: hue H
+    },
+    where
+        T: FreeChannelScalar,
+        A: AngularChannelScalar
+);
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Rgi")]
+struct RgiRepr<T> {
+    red: T,
+    green: T,
+    intensity: T,
+}
+
+impl<T> Serialize for Rgi<T>
+where
+    T: PosNormalChannelScalar + Float + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RgiRepr {
+            red: self.red(),
+            green: self.green(),
+            intensity: self.intensity(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Rgi<T>
+where
+    T: PosNormalChannelScalar + Float + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let c = RgiRepr::deserialize(deserializer)?;
+        if !is_valid_ratio(c.red, c.green) {
+            return Err(de::Error::custom(
+                "rgi red and green must be non-negative and sum to at most 1",
+            ));
+        }
+        Ok(Rgi::new(c.red, c.green, c.intensity))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "XyY")]
+#[allow(non_snake_case)]
+struct XyYRepr<T> {
+    x: T,
+    y: T,
+    Y: T,
+}
+
+impl<T> Serialize for XyY<T>
+where
+    T: FreeChannelScalar + PosNormalChannelScalar + Float + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        XyYRepr {
+            x: self.x(),
+            y: self.y(),
+            Y: self.Y(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for XyY<T>
+where
+    T: FreeChannelScalar + PosNormalChannelScalar + Float + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let c = XyYRepr::deserialize(deserializer)?;
+        if !is_valid_ratio(c.x, c.y) {
+            return Err(de::Error::custom(
+                "xyY x and y must be non-negative and sum to at most 1",
+            ));
+        }
+        Ok(XyY::new(c.x, c.y, c.Y))
+    }
+}
+
+fn is_valid_ratio<T: Float>(a: T, b: T) -> bool {
+    a >= T::zero() && b >= T::zero() && a + b <= T::one()
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Alpha")]
+struct AlphaRepr<C, T> {
+    color: C,
+    alpha: T,
+}
+
+impl<T, C> Serialize for Alpha<T, C>
+where
+    T: PosNormalChannelScalar + Serialize,
+    C: Color + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        AlphaRepr {
+            color: self.color(),
+            alpha: self.alpha(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, T, C> Deserialize<'de> for Alpha<T, C>
+where
+    T: PosNormalChannelScalar + Deserialize<'de>,
+    C: Color + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let c = AlphaRepr::deserialize(deserializer)?;
+        Ok(Alpha::new(c.color, c.alpha))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "EncodedColor")]
+struct EncodedColorRepr<C, E> {
+    color: C,
+    encoding: E,
+}
+
+impl<C, E> Serialize for EncodedColor<C, E>
+where
+    C: Color + Serialize,
+    E: ColorEncoding + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        EncodedColorRepr {
+            color: self.color(),
+            encoding: self.encoding(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, C, E> Deserialize<'de> for EncodedColor<C, E>
+where
+    C: Color + EncodableColor + Deserialize<'de>,
+    E: ColorEncoding + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let c = EncodedColorRepr::deserialize(deserializer)?;
+        Ok(EncodedColor::new(c.color, c.encoding))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "ChromaticityCoordinates")]
+struct ChromaticityCoordinatesRepr<T> {
+    alpha: T,
+    beta: T,
+}
+
+impl<T> Serialize for ChromaticityCoordinates<T>
+where
+    T: Float + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ChromaticityCoordinatesRepr {
+            alpha: self.alpha,
+            beta: self.beta,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for ChromaticityCoordinates<T>
+where
+    T: Float + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let c = ChromaticityCoordinatesRepr::deserialize(deserializer)?;
+        Ok(ChromaticityCoordinates::new(c.alpha, c.beta))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "RgbPrimary")]
+struct RgbPrimaryRepr<T> {
+    x: T,
+    y: T,
+}
+
+impl<T> Serialize for RgbPrimary<T>
+where
+    T: PosNormalChannelScalar + Float + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (x, y) = self.clone().to_tuple();
+        RgbPrimaryRepr { x, y }.serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for RgbPrimary<T>
+where
+    T: PosNormalChannelScalar + Float + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let c = RgbPrimaryRepr::deserialize(deserializer)?;
+        Ok(RgbPrimary::new(c.x, c.y))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "EncodedColorSpace")]
+struct EncodedColorSpaceRepr<T, E>
+where
+    T: PosNormalChannelScalar + FreeChannelScalar + Float,
+{
+    red: RgbPrimary<T>,
+    green: RgbPrimary<T>,
+    blue: RgbPrimary<T>,
+    white_point: Xyz<T>,
+    encoding: E,
+}
+
+impl<T, E> Serialize for EncodedColorSpace<T, E>
+where
+    T: PosNormalChannelScalar + FreeChannelScalar + Float + Serialize,
+    E: ColorEncoding + Clone + PartialEq + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        EncodedColorSpaceRepr {
+            red: self.red_primary(),
+            green: self.green_primary(),
+            blue: self.blue_primary(),
+            white_point: self.white_point(),
+            encoding: EncodedColorSpace::encoding(self),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, T, E> Deserialize<'de> for EncodedColorSpace<T, E>
+where
+    T: PosNormalChannelScalar + FreeChannelScalar + Float + Deserialize<'de>,
+    E: ColorEncoding + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let c = EncodedColorSpaceRepr::deserialize(deserializer)?;
+        EncodedColorSpace::try_new(c.red, c.green, c.blue, c.white_point, c.encoding).ok_or_else(
+            || de::Error::custom("the primaries and white point don't form a valid color space"),
+        )
+    }
+}
+
+/// Accepts a unit struct, ignoring its name
+struct UnitVisitor;
+
+impl<'de> Visitor<'de> for UnitVisitor {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a unit struct")
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<(), E> {
+        Ok(())
+    }
+}
+
+macro_rules! impl_serde_unit {
+    ($($name:ident),*) => {
+        $(
+            impl Serialize for $name {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.serialize_unit_struct(stringify!($name))
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $name {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    deserializer.deserialize_unit_struct(stringify!($name), UnitVisitor)?;
+                    Ok($name::new())
+                }
+            }
+        )*
+    };
+    ($($name:ident<T>),*) => {
+        $(
+            impl<T> Serialize for $name<T> {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.serialize_unit_struct(stringify!($name))
+                }
+            }
+
+            impl<'de, T> Deserialize<'de> for $name<T> {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    deserializer.deserialize_unit_struct(stringify!($name), UnitVisitor)?;
+                    Ok($name::new())
+                }
+            }
+        )*
+    };
+}
+
+impl_serde_unit!(
+    SrgbEncoding,
+    LinearEncoding,
+    Rec709Encoding,
+    Rec2020Encoding,
+    PqEncoding,
+    HlgEncoding,
+    RommEncoding
+);
+impl_serde_unit!(
+    SRgb<T>,
+    ScRgb<T>,
+    Rec601_625<T>,
+    Rec601_525<T>,
+    Rec2020<T>,
+    ProPhotoRgb<T>,
+    Aces2065_1<T>,
+    AcesCg<T>
+);
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "GammaEncoding")]
+struct GammaEncodingRepr<T>(T);
+
+impl<T> Serialize for GammaEncoding<T>
+where
+    T: Float + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        GammaEncodingRepr(self.0).serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for GammaEncoding<T>
+where
+    T: Float + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let GammaEncodingRepr(gamma) = GammaEncodingRepr::deserialize(deserializer)?;
+        Ok(GammaEncoding(gamma))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::alpha::Rgba;
+    use crate::color_space::LinearColorSpace;
+    use crate::lms::Bradford;
+    use crate::white_point::D65;
+    use crate::ycbcr::JpegModel;
+    use angle::{Deg, Turns};
+    use serde::de::DeserializeOwned;
+    use std::fmt::Debug;
+
+    fn round_trip<C>(color: &C) -> String
+    where
+        C: Serialize + DeserializeOwned + PartialEq + Debug,
+    {
+        let json = serde_json::to_string(color).unwrap();
+        assert_eq!(&serde_json::from_str::<C>(&json).unwrap(), color);
+        json
+    }
+
+    #[test]
+    fn test_device_colors() {
+        assert_eq!(
+            round_trip(&Rgb::new(10u8, 20, 30)),
+            r#"{"red":10,"green":20,"blue":30}"#
+        );
+        assert_eq!(round_trip(&Luma::new(0.5f32)), r#"{"luma":0.5}"#);
+        assert_eq!(
+            round_trip(&Rgi::new(0.25f64, 0.5, 0.75)),
+            r#"{"red":0.25,"green":0.5,"intensity":0.75}"#
+        );
+        assert_eq!(
+            round_trip(&Hsv::new(Deg(120.0f64), 0.5, 1.0)),
+            r#"{"hue":120.0,"saturation":0.5,"value":1.0}"#
+        );
+        assert_eq!(
+            round_trip(&Hsl::new(Turns(0.25f32), 0.5, 0.25)),
+            r#"{"hue":0.25,"saturation":0.5,"lightness":0.25}"#
+        );
+        round_trip(&Hsi::new(Deg(10.0f64), 0.5, 0.25));
+        round_trip(&eHsi::new(Deg(10.0f64), 0.5, 0.25));
+        assert_eq!(
+            round_trip(&Hwb::new(Deg(300.0f64), 0.1, 0.2)),
+            r#"{"hue":300.0,"whiteness":0.1,"blackness":0.2}"#
+        );
+        assert_eq!(
+            round_trip(&YCbCr::<f32, JpegModel>::new(0.5, -0.25, 0.25)),
+            r#"{"luma":0.5,"cb":-0.25,"cr":0.25}"#
+        );
+        round_trip(&BareYCbCr::new(0.5f32, -0.25, 0.25));
+        // Out of range values aren't clamped
+        round_trip(&Rgb::new(1.5f32, -0.5, 0.0));
+
+        assert!(
+            serde_json::from_str::<Rgi<f64>>(r#"{"red":0.75,"green":0.5,"intensity":1}"#).is_err()
+        );
+        assert!(serde_json::from_str::<Rgb<u8>>(r#"{"red":256,"green":0,"blue":0}"#).is_err());
+        assert!(serde_json::from_str::<Rgb<f32>>(r#"{"red":1,"green":0}"#).is_err());
+    }
+
+    #[test]
+    fn test_cie_colors() {
+        assert_eq!(
+            round_trip(&Xyz::new(0.5f64, 1.0, 0.25)),
+            r#"{"x":0.5,"y":1.0,"z":0.25}"#
+        );
+        assert_eq!(
+            round_trip(&XyY::new(0.25f64, 0.5, 0.75)),
+            r#"{"x":0.25,"y":0.5,"Y":0.75}"#
+        );
+        assert!(serde_json::from_str::<XyY<f64>>(r#"{"x":0.75,"y":0.5,"Y":1}"#).is_err());
+        round_trip(&Lms::<f64, Bradford>::new(0.25, 0.5, 0.75));
+        assert_eq!(
+            round_trip(&Lab::<f64, D65>::new(50.0, -20.0, 10.0)),
+            r#"{"L":50.0,"a":-20.0,"b":10.0}"#
+        );
+        round_trip(&Luv::<f64, D65>::new(50.0, -20.0, 10.0));
+        assert_eq!(
+            round_trip(&Lchab::<f64, D65>::new(50.0, 20.0, Deg(90.0))),
+            r#"{"L":50.0,"chroma":20.0,"hue":90.0}"#
+        );
+        round_trip(&Lchuv::<f64, D65>::new(50.0, 20.0, Deg(90.0)));
+        round_trip(&Oklab::new(0.5f32, -0.1, 0.1));
+        round_trip(&Oklch::new(0.5f32, 0.1, Deg(45.0)));
+    }
+
+    #[test]
+    fn test_wrappers() {
+        assert_eq!(
+            round_trip(&Rgba::new(Rgb::new(0.5f32, 0.25, 1.0), 0.75)),
+            r#"{"color":{"red":0.5,"green":0.25,"blue":1.0},"alpha":0.75}"#
+        );
+        assert_eq!(
+            round_trip(&Rgb::new(0.5f32, 0.25, 1.0).srgb_encoded()),
+            r#"{"color":{"red":0.5,"green":0.25,"blue":1.0},"encoding":null}"#
+        );
+        assert_eq!(
+            round_trip(&Rgb::new(0.5f32, 0.25, 1.0).encoded_as(GammaEncoding(2.2f32))),
+            r#"{"color":{"red":0.5,"green":0.25,"blue":1.0},"encoding":2.2}"#
+        );
+        assert_eq!(
+            round_trip(&ChromaticityCoordinates::new(0.5f64, -0.25)),
+            r#"{"alpha":0.5,"beta":-0.25}"#
+        );
+        assert_eq!(round_trip(&SRgb::<f64>::new()), "null");
+        assert_eq!(round_trip(&Rec709Encoding::new()), "null");
+    }
+
+    #[test]
+    fn test_color_space() {
+        let srgb = SRgb::<f64>::new();
+        let space = LinearColorSpace::new(
+            srgb.red_primary(),
+            srgb.green_primary(),
+            srgb.blue_primary(),
+            srgb.white_point(),
+            LinearEncoding,
+        );
+        let json = round_trip(&space);
+        assert!(json.starts_with(r#"{"red":{"x":0.64,"y":0.33},"green":{"x":0.3,"y":0.6},"#));
+        assert!(json.ends_with(r#""encoding":null}"#));
+
+        let degenerate = concat!(
+            r#"{"red":{"x":0.3,"y":0.6},"green":{"x":0.3,"y":0.6},"#,
+            r#""blue":{"x":0.15,"y":0.06},"white_point":"#,
+            r#"{"x":0.95,"y":1.0,"z":1.09},"encoding":null}"#
+        );
+        assert!(serde_json::from_str::<LinearColorSpace<f64>>(degenerate).is_err());
+    }
+}
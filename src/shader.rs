@@ -0,0 +1,453 @@
+//! Generation of WGSL and GLSL source for color conversions
+//!
+//! Real-time renderers often do their color conversions on the GPU. The functions here write a
+//! conversion built with this crate as shader source, so the GPU performs the same transfer
+//! functions with the same constants and the same matrix as the CPU.
+//!
+//! [`encoding_source`](fn.encoding_source.html) writes the decoding and encoding functions of an
+//! encoding, while [`pipeline_source`](fn.pipeline_source.html) writes a complete conversion:
+//! decoding, a [`LinearPipeline`](../color_space/struct.LinearPipeline.html), and encoding.
+//! [`converter_source`](fn.converter_source.html) does the same for a
+//! [`Converter`](../color_space/struct.Converter.html). Every function generated takes and returns
+//! 32-bit floats, and their names all start with the name passed in, so that several conversions
+//! can live in the same shader.
+//!
+//! Encodings are written through the [`ShaderEncoding`](trait.ShaderEncoding.html) trait, which
+//! is implemented for every encoding in the [`encoding`](../encoding/index.html) module.
+//!
+//! This module is only available with the `shader` feature.
+//!
+//! ## Examples:
+//!
+//! ```rust
+//! # extern crate prisma;
+//!
+//! use prisma::color_space::LinearPipeline;
+//! use prisma::color_space::named::{DisplayP3, SRgb};
+//! use prisma::encoding::SrgbEncoding;
+//! use prisma::shader::{self, ShaderLanguage};
+//!
+//! let pipeline = LinearPipeline::<f32>::new()
+//!     .rgb_to_xyz(&SRgb::new())
+//!     .xyz_to_rgb(&DisplayP3::new());
+//! let source = shader::pipeline_source(
+//!     ShaderLanguage::Wgsl,
+//!     "srgb_to_p3",
+//!     &SrgbEncoding,
+//!     &pipeline,
+//!     &SrgbEncoding,
+//! );
+//! assert!(source.contains("fn srgb_to_p3(rgb: vec3<f32>) -> vec3<f32> {"));
+//! ```
+
+use crate::channel::{FreeChannelScalar, PosNormalChannelScalar};
+use crate::color_space::{ColorSpace, Converter, LinearPipeline};
+use crate::encoding::{
+    AdobeRgbEncoding, ColorEncoding, GammaEncoding, HlgEncoding, LinearEncoding, PqEncoding,
+    Rec2020Encoding, Rec709Encoding, RommEncoding, SrgbEncoding,
+};
+use crate::gamut::GamutMapping;
+use num_traits;
+use std::fmt::Write;
+
+/// A shading language that source can be generated for
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ShaderLanguage {
+    /// The WebGPU Shading Language
+    Wgsl,
+    /// The OpenGL Shading Language, version 3.30 or later, or GLSL ES 3.00
+    Glsl,
+}
+
+/// Collects the statements of a generated function
+///
+/// The function has a single `f32` argument named `x`, and every path through it must end in a
+/// return. Expressions are written with the functions common to WGSL and GLSL, such as `abs`,
+/// `sign`, `pow`, `exp`, `log`, `sqrt` and `max`, with constants formatted by
+/// [`literal`](fn.literal.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FunctionBody {
+    language: ShaderLanguage,
+    lines: Vec<String>,
+}
+
+impl FunctionBody {
+    fn new(language: ShaderLanguage) -> Self {
+        FunctionBody {
+            language,
+            lines: Vec::new(),
+        }
+    }
+
+    /// Returns the language the function is written in
+    pub fn language(&self) -> ShaderLanguage {
+        self.language
+    }
+    /// Bind the value of `expr` to a new local named `name`
+    pub fn bind(&mut self, name: &str, expr: &str) {
+        let line = match self.language {
+            ShaderLanguage::Wgsl => format!("let {} = {};", name, expr),
+            ShaderLanguage::Glsl => format!("float {} = {};", name, expr),
+        };
+        self.lines.push(line);
+    }
+    /// Return `expr` from the function if `condition` holds
+    pub fn return_if(&mut self, condition: &str, expr: &str) {
+        self.lines
+            .push(format!("if ({}) {{ return {}; }}", condition, expr));
+    }
+    /// Return `expr` from the function
+    pub fn ret(&mut self, expr: &str) {
+        self.lines.push(format!("return {};", expr));
+    }
+}
+
+/// An encoding whose transfer functions can be written as shader source
+pub trait ShaderEncoding {
+    /// Write the statements decoding the channel `x` to linear
+    fn write_decode(&self, body: &mut FunctionBody);
+    /// Write the statements encoding the linear channel `x`
+    fn write_encode(&self, body: &mut FunctionBody);
+}
+
+/// Format `val` as a floating point literal valid in every supported language
+pub fn literal(val: f64) -> String {
+    // `Debug` always includes a decimal point or an exponent, so the literal is never an integer
+    format!("{:?}", val)
+}
+
+fn write_function(out: &mut String, language: ShaderLanguage, name: &str, body: &FunctionBody) {
+    match language {
+        ShaderLanguage::Wgsl => writeln!(out, "fn {}(x: f32) -> f32 {{", name),
+        ShaderLanguage::Glsl => writeln!(out, "float {}(float x) {{", name),
+    }
+    .unwrap();
+    for line in body.lines.iter() {
+        writeln!(out, "    {}", line).unwrap();
+    }
+    out.push_str("}\n");
+}
+
+fn write_codec<SE, DE>(
+    out: &mut String,
+    language: ShaderLanguage,
+    name: &str,
+    decoder: &SE,
+    encoder: &DE,
+) where
+    SE: ShaderEncoding + ?Sized,
+    DE: ShaderEncoding + ?Sized,
+{
+    let mut decode = FunctionBody::new(language);
+    decoder.write_decode(&mut decode);
+    write_function(out, language, &format!("{}_decode", name), &decode);
+    out.push('\n');
+
+    let mut encode = FunctionBody::new(language);
+    encoder.write_encode(&mut encode);
+    write_function(out, language, &format!("{}_encode", name), &encode);
+}
+
+/// Generate the functions `<name>_decode` and `<name>_encode` for `encoding`
+///
+/// Both functions act on a single channel.
+pub fn encoding_source<E>(language: ShaderLanguage, name: &str, encoding: &E) -> String
+where
+    E: ShaderEncoding + ?Sized,
+{
+    let mut out = String::new();
+    write_codec(&mut out, language, name, encoding, encoding);
+    out
+}
+
+/// Generate a function `<name>` converting an RGB color with `pipeline`
+///
+/// The color is decoded with `source_encoding`, transformed by the pipeline's matrix and encoded
+/// with `dest_encoding`. The helper functions `<name>_decode` and `<name>_encode` are generated
+/// along with it.
+pub fn pipeline_source<T, SE, DE>(
+    language: ShaderLanguage,
+    name: &str,
+    source_encoding: &SE,
+    pipeline: &LinearPipeline<T>,
+    dest_encoding: &DE,
+) -> String
+where
+    T: num_traits::Float + FreeChannelScalar,
+    SE: ShaderEncoding + ?Sized,
+    DE: ShaderEncoding + ?Sized,
+{
+    let mut out = String::new();
+    write_codec(&mut out, language, name, source_encoding, dest_encoding);
+    out.push('\n');
+
+    // The matrix is stored by rows, while both languages construct matrices by columns
+    let m: Vec<String> = pipeline
+        .matrix()
+        .as_slice()
+        .iter()
+        .map(|&v| literal(num_traits::cast(v).unwrap()))
+        .collect();
+    let columns = [
+        m[0].as_str(),
+        &m[3],
+        &m[6],
+        &m[1],
+        &m[4],
+        &m[7],
+        &m[2],
+        &m[5],
+        &m[8],
+    ]
+    .join(", ");
+    let decode = |c: &str| format!("{}_decode(rgb.{})", name, c);
+    let encode = |c: &str| format!("{}_encode(transformed.{})", name, c);
+
+    let (signature, vec3, mat3, decl) = match language {
+        ShaderLanguage::Wgsl => (
+            format!("fn {}(rgb: vec3<f32>) -> vec3<f32> {{", name),
+            "vec3<f32>",
+            "mat3x3<f32>",
+            ["let", "let", "let"],
+        ),
+        ShaderLanguage::Glsl => (
+            format!("vec3 {}(vec3 rgb) {{", name),
+            "vec3",
+            "mat3",
+            ["vec3", "mat3", "vec3"],
+        ),
+    };
+    writeln!(out, "{}", signature).unwrap();
+    writeln!(
+        out,
+        "    {} decoded = {}({}, {}, {});",
+        decl[0],
+        vec3,
+        decode("x"),
+        decode("y"),
+        decode("z")
+    )
+    .unwrap();
+    writeln!(out, "    {} transform = {}({});", decl[1], mat3, columns).unwrap();
+    writeln!(out, "    {} transformed = transform * decoded;", decl[2]).unwrap();
+    writeln!(
+        out,
+        "    return {}({}, {}, {});",
+        vec3,
+        encode("x"),
+        encode("y"),
+        encode("z")
+    )
+    .unwrap();
+    out.push_str("}\n");
+    out
+}
+
+/// Generate a function `<name>` performing the conversion of `converter`
+///
+/// The converter's gamut mapping is not included, so colors outside of the destination gamut are
+/// returned unmapped.
+pub fn converter_source<T, S, D, SE, DE, M>(
+    language: ShaderLanguage,
+    name: &str,
+    converter: &Converter<T, S, D, SE, DE, M>,
+) -> String
+where
+    T: num_traits::Float + FreeChannelScalar + PosNormalChannelScalar,
+    S: ColorSpace<T>,
+    D: ColorSpace<T>,
+    SE: ColorEncoding + ShaderEncoding,
+    DE: ColorEncoding + ShaderEncoding,
+    M: GamutMapping<T>,
+{
+    pipeline_source(
+        language,
+        name,
+        converter.source_encoding(),
+        converter.pipeline(),
+        converter.dest_encoding(),
+    )
+}
+
+impl ShaderEncoding for LinearEncoding {
+    fn write_decode(&self, body: &mut FunctionBody) {
+        body.ret("x");
+    }
+    fn write_encode(&self, body: &mut FunctionBody) {
+        body.ret("x");
+    }
+}
+
+fn write_pow(body: &mut FunctionBody, exponent: f64) {
+    body.ret(&format!("sign(x) * pow(abs(x), {})", literal(exponent)));
+}
+
+impl<T> ShaderEncoding for GammaEncoding<T>
+where
+    T: num_traits::Float,
+{
+    fn write_decode(&self, body: &mut FunctionBody) {
+        write_pow(body, num_traits::cast(self.exponent()).unwrap());
+    }
+    fn write_encode(&self, body: &mut FunctionBody) {
+        let exponent: f64 = num_traits::cast(self.exponent()).unwrap();
+        write_pow(body, 1.0 / exponent);
+    }
+}
+
+impl ShaderEncoding for AdobeRgbEncoding {
+    fn write_decode(&self, body: &mut FunctionBody) {
+        write_pow(body, 563.0 / 256.0);
+    }
+    fn write_encode(&self, body: &mut FunctionBody) {
+        write_pow(body, 256.0 / 563.0);
+    }
+}
+
+impl ShaderEncoding for SrgbEncoding {
+    fn write_decode(&self, body: &mut FunctionBody) {
+        body.return_if("abs(x) < 0.04045", "x / 12.92");
+        body.ret("sign(x) * pow((abs(x) + 0.055) / 1.055, 2.4)");
+    }
+    fn write_encode(&self, body: &mut FunctionBody) {
+        body.return_if("abs(x) < 0.0031308", "12.92 * x");
+        body.ret(&format!(
+            "sign(x) * (1.055 * pow(abs(x), {}) - 0.055)",
+            literal(1.0 / 2.4)
+        ));
+    }
+}
+
+fn write_bt_decode(body: &mut FunctionBody, alpha: f64, threshold: f64) {
+    body.return_if(&format!("abs(x) < {}", literal(threshold)), "x / 4.5");
+    body.ret(&format!(
+        "sign(x) * pow((abs(x) + {}) / {}, {})",
+        literal(alpha - 1.0),
+        literal(alpha),
+        literal(1.0 / 0.45)
+    ));
+}
+
+fn write_bt_encode(body: &mut FunctionBody, alpha: f64, threshold: f64) {
+    body.return_if(&format!("abs(x) < {}", literal(threshold)), "4.5 * x");
+    body.ret(&format!(
+        "sign(x) * ({} * pow(abs(x), 0.45) - {})",
+        literal(alpha),
+        literal(alpha - 1.0)
+    ));
+}
+
+impl ShaderEncoding for Rec709Encoding {
+    fn write_decode(&self, body: &mut FunctionBody) {
+        write_bt_decode(body, 1.099, 0.081);
+    }
+    fn write_encode(&self, body: &mut FunctionBody) {
+        write_bt_encode(body, 1.099, 0.018);
+    }
+}
+
+impl ShaderEncoding for Rec2020Encoding {
+    fn write_decode(&self, body: &mut FunctionBody) {
+        write_bt_decode(body, 1.09929682680944, 4.5 * 0.018053968510807);
+    }
+    fn write_encode(&self, body: &mut FunctionBody) {
+        write_bt_encode(body, 1.09929682680944, 0.018053968510807);
+    }
+}
+
+impl ShaderEncoding for PqEncoding {
+    fn write_decode(&self, body: &mut FunctionBody) {
+        body.bind("e", &format!("pow(abs(x), {})", literal(1.0 / 78.84375)));
+        body.bind("num", "max(e - 0.8359375, 0.0)");
+        body.ret(&format!(
+            "sign(x) * pow(num / (18.8515625 - 18.6875 * e), {})",
+            literal(1.0 / 0.1593017578125)
+        ));
+    }
+    fn write_encode(&self, body: &mut FunctionBody) {
+        body.bind("y", "pow(abs(x), 0.1593017578125)");
+        body.ret("sign(x) * pow((0.8359375 + 18.8515625 * y) / (1.0 + 18.6875 * y), 78.84375)");
+    }
+}
+
+impl ShaderEncoding for HlgEncoding {
+    fn write_decode(&self, body: &mut FunctionBody) {
+        body.return_if("abs(x) <= 0.5", "sign(x) * x * x / 3.0");
+        body.ret("sign(x) * (exp((abs(x) - 0.55991073) / 0.17883277) + 0.28466892) / 12.0");
+    }
+    fn write_encode(&self, body: &mut FunctionBody) {
+        body.return_if(
+            &format!("abs(x) <= {}", literal(1.0 / 12.0)),
+            "sign(x) * sqrt(3.0 * abs(x))",
+        );
+        body.ret("sign(x) * (0.17883277 * log(12.0 * abs(x) - 0.28466892) + 0.55991073)");
+    }
+}
+
+impl ShaderEncoding for RommEncoding {
+    fn write_decode(&self, body: &mut FunctionBody) {
+        body.return_if("abs(x) < 0.03125", "x / 16.0");
+        write_pow(body, 1.8);
+    }
+    fn write_encode(&self, body: &mut FunctionBody) {
+        body.return_if("abs(x) < 0.001953125", "16.0 * x");
+        write_pow(body, 1.0 / 1.8);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::color_space::named::{Rec2020, SRgb};
+    use crate::linalg::Matrix3;
+
+    #[test]
+    fn test_encoding_source() {
+        let wgsl = encoding_source(ShaderLanguage::Wgsl, "srgb", &SrgbEncoding);
+        assert_eq!(
+            wgsl,
+            "fn srgb_decode(x: f32) -> f32 {\n    \
+             if (abs(x) < 0.04045) { return x / 12.92; }\n    \
+             return sign(x) * pow((abs(x) + 0.055) / 1.055, 2.4);\n\
+             }\n\n\
+             fn srgb_encode(x: f32) -> f32 {\n    \
+             if (abs(x) < 0.0031308) { return 12.92 * x; }\n    \
+             return sign(x) * (1.055 * pow(abs(x), 0.4166666666666667) - 0.055);\n\
+             }\n"
+        );
+
+        let glsl = encoding_source(ShaderLanguage::Glsl, "pq", &PqEncoding);
+        assert!(glsl.starts_with("float pq_decode(float x) {\n    float e = pow(abs(x), "));
+        let gamma = encoding_source(ShaderLanguage::Glsl, "g", &GammaEncoding(2.0f32));
+        assert!(gamma.contains("return sign(x) * pow(abs(x), 0.5);"));
+        assert_eq!(literal(1.0), "1.0");
+    }
+
+    #[test]
+    fn test_pipeline_source() {
+        let pipeline = LinearPipeline::new().then(Matrix3::new([
+            1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0f64,
+        ]));
+        let glsl = pipeline_source(
+            ShaderLanguage::Glsl,
+            "conv",
+            &LinearEncoding,
+            &pipeline,
+            &HlgEncoding,
+        );
+        assert!(glsl.contains("vec3 conv(vec3 rgb) {"));
+        assert!(
+            glsl.contains("mat3 transform = mat3(1.0, 4.0, 7.0, 2.0, 5.0, 8.0, 3.0, 6.0, 9.0);")
+        );
+        assert!(glsl.contains("float conv_encode(float x) {"));
+
+        let converter = Converter::builder(SRgb::<f64>::new(), Rec2020::new()).build();
+        let wgsl = converter_source(ShaderLanguage::Wgsl, "to_2020", &converter);
+        assert!(wgsl.contains("fn to_2020_decode(x: f32) -> f32 {"));
+        assert!(wgsl.contains("let transformed = transform * decoded;"));
+        assert!(wgsl.contains(
+            "return vec3<f32>(to_2020_encode(transformed.x), \
+             to_2020_encode(transformed.y), to_2020_encode(transformed.z));"
+        ));
+    }
+}
@@ -0,0 +1,121 @@
+//! Spectral power distributions of the CIE standard illuminants
+
+use super::Spd;
+use crate::channel::FreeChannelScalar;
+use num_traits::{cast, Float};
+
+impl<T> Spd<T>
+where
+    T: Float + FreeChannelScalar,
+{
+    /// Construct the spectrum of CIE standard illuminant A from 380nm to 780nm in steps of 5nm
+    ///
+    /// Illuminant A represents typical tungsten incandescent lighting. It is defined as the
+    /// spectrum of a blackbody radiator at about 2856K, normalized to 100 at 560nm.
+    pub fn cie_a() -> Self {
+        let c2 = 1.435e7;
+        let t = 2848.0;
+        let scale = 100.0 * ((c2 / (t * 560.0)).exp() - 1.0);
+        Spd::from_fn(
+            cast(380.0).unwrap(),
+            cast(780.0).unwrap(),
+            cast(5.0).unwrap(),
+            |w| {
+                let w: f64 = cast(w).unwrap();
+                cast(scale * (560.0 / w).powi(5) / ((c2 / (t * w)).exp() - 1.0)).unwrap()
+            },
+        )
+    }
+
+    /// Construct the spectrum of CIE standard illuminant D65 from 380nm to 780nm in steps of 5nm
+    ///
+    /// D65 represents average daylight and is the white point of sRGB and most other RGB spaces.
+    pub fn cie_d65() -> Self {
+        Self::from_table(&CIE_D65)
+    }
+
+    /// Construct the spectrum of CIE standard illuminant F2 from 380nm to 780nm in steps of 5nm
+    ///
+    /// F2 represents a cool white fluorescent lamp, the kind found in many offices and stores.
+    pub fn cie_f2() -> Self {
+        Self::from_table(&CIE_F2)
+    }
+
+    fn from_table(table: &[f64]) -> Self {
+        Spd::new(
+            cast(380.0).unwrap(),
+            cast(5.0).unwrap(),
+            table.iter().map(|&v| cast(v).unwrap()).collect(),
+        )
+    }
+}
+
+#[rustfmt::skip]
+const CIE_D65: [f64; 81] = [
+    49.9755, 52.3118, 54.6482, 68.7015, 82.7549, 87.1204, 91.486, 92.4589,
+    93.4318, 90.057, 86.6823, 95.7736, 104.865, 110.936, 117.008, 117.41,
+    117.812, 116.336, 114.861, 115.392, 115.923, 112.367, 108.811, 109.082,
+    109.354, 108.578, 107.802, 106.296, 104.79, 106.239, 107.689, 106.047,
+    104.405, 104.225, 104.046, 102.023, 100.0, 98.1671, 96.3342, 96.0611,
+    95.788, 92.2368, 88.6856, 89.3459, 90.0062, 89.8026, 89.5991, 88.6489,
+    87.6987, 85.4936, 83.2886, 83.4939, 83.6992, 81.863, 80.0268, 80.1207,
+    80.2146, 81.2462, 82.2778, 80.281, 78.2842, 74.0027, 69.7213, 70.6652,
+    71.6091, 72.979, 74.349, 67.9765, 61.604, 65.7448, 69.8856, 72.4863,
+    75.087, 69.3398, 63.5927, 55.0054, 46.4182, 56.6118, 66.8054, 65.0941,
+    63.3828,
+];
+
+#[rustfmt::skip]
+const CIE_F2: [f64; 81] = [
+    1.18, 1.48, 1.84, 2.15, 3.44, 15.69, 3.85, 3.74,
+    4.19, 4.62, 5.06, 34.98, 11.81, 6.27, 6.63, 6.93,
+    7.19, 7.40, 7.54, 7.62, 7.65, 7.62, 7.62, 7.45,
+    7.28, 7.15, 7.05, 7.04, 7.16, 7.47, 8.04, 8.88,
+    10.01, 24.88, 16.64, 14.59, 16.16, 17.56, 18.62, 21.47,
+    22.79, 19.29, 18.66, 17.73, 16.54, 15.21, 13.80, 12.36,
+    10.95, 9.65, 8.40, 7.32, 6.31, 5.43, 4.68, 4.02,
+    3.45, 2.96, 2.55, 2.19, 1.89, 1.64, 1.53, 1.27,
+    1.10, 0.99, 0.88, 0.76, 0.68, 0.61, 0.56, 0.54,
+    0.51, 0.47, 0.47, 0.43, 0.46, 0.47, 0.40, 0.33,
+    0.27,
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::spectrum::{Cie1931, Cie1964};
+    use crate::white_point::{deg_10, WhitePoint, A, D65, F2};
+    use approx::*;
+
+    #[test]
+    fn test_white_points() {
+        assert_relative_eq!(
+            Spd::<f64>::cie_a().to_white_point(&Cie1931),
+            A.get_xyz(),
+            epsilon = 1e-4
+        );
+        assert_relative_eq!(
+            Spd::<f64>::cie_d65().to_white_point(&Cie1931),
+            D65.get_xyz(),
+            epsilon = 1e-4
+        );
+        assert_relative_eq!(
+            Spd::<f64>::cie_f2().to_white_point(&Cie1931),
+            F2.get_xyz(),
+            epsilon = 1e-4
+        );
+        assert_relative_eq!(
+            Spd::<f64>::cie_d65().to_white_point(&Cie1964),
+            deg_10::D65.get_xyz(),
+            epsilon = 1e-3
+        );
+    }
+
+    #[test]
+    fn test_normalization() {
+        let a = Spd::<f32>::cie_a();
+        assert_eq!(a.values().len(), 81);
+        assert_relative_eq!(a.value_at(560.0), 100.0, epsilon = 1e-3);
+        assert_relative_eq!(Spd::<f64>::cie_d65().value_at(560.0), 100.0);
+    }
+}
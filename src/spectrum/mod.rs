@@ -0,0 +1,401 @@
+//! Spectral power distributions and their conversion to `Xyz`
+//!
+//! Every color stimulus is ultimately a distribution of power over the visible wavelengths. An
+//! observer's [color matching functions](trait.ColorMatchingFunctions.html) reduce this
+//! distribution to three `Xyz` tristimulus values. Working spectrally rather than in `Xyz` allows
+//! answering questions that tristimulus values cannot, such as how a surface looks under a
+//! different light source.
+//!
+//! Two workflows are supported:
+//!
+//! * **Emissive**: The spectrum of a light source is converted directly with
+//!   [`Spd::to_xyz`](struct.Spd.html#method.to_xyz), or normalized to a white point with
+//!   [`Spd::to_white_point`](struct.Spd.html#method.to_white_point).
+//! * **Reflective**: The reflectance spectrum of a surface is combined with the spectrum of the
+//!   light illuminating it with
+//!   [`Spd::to_reflected_xyz`](struct.Spd.html#method.to_reflected_xyz), or
+//!   [`Spd::to_reflected_lab`](struct.Spd.html#method.to_reflected_lab) for a result relative to
+//!   the white of the light.
+//!
+//! The spectra of the CIE standard illuminants A, D65 and F2 are built in, see
+//! [`Spd::cie_d65`](struct.Spd.html#method.cie_d65) and friends.
+//!
+//! ## Examples:
+//!
+//! ```rust
+//! # extern crate prisma;
+//!
+//! use prisma::spectrum::{Cie1931, Spd};
+//!
+//! // A surface reflecting mostly long wavelengths
+//! let reflectance = Spd::from_fn(380.0, 780.0, 5.0, |w| if w > 590.0 { 0.8 } else { 0.1 });
+//! let daylight = reflectance.to_reflected_lab(&Spd::cie_d65(), &Cie1931).unwrap();
+//! let store = reflectance.to_reflected_lab(&Spd::cie_f2(), &Cie1931).unwrap();
+//! assert!(daylight.a() > 0.0 && store.a() > 0.0);
+//! ```
+
+mod cmf;
+mod illuminant;
+
+pub use self::cmf::{Cie1931, Cie1964, ColorMatchingFunctions};
+
+use crate::channel::{FreeChannelScalar, PosNormalChannelScalar};
+use crate::lab::Lab;
+use crate::xyz::Xyz;
+use num_traits::{cast, Float};
+
+/// A spectral power distribution sampled at regular wavelength intervals
+///
+/// Wavelengths are in nanometers. Between samples the distribution is linearly interpolated,
+/// and outside of the sampled range it is zero. An `Spd` can hold the power of a light source or
+/// the reflectance of a surface, which is a factor in the range [0, 1] at each wavelength.
+///
+/// ## Examples:
+///
+/// ```rust
+/// #[macro_use] extern crate approx;
+/// # extern crate prisma;
+///
+/// use prisma::spectrum::{Cie1931, Spd};
+/// use prisma::Xyz;
+///
+/// let equal_energy = Spd::new(380.0, 5.0, vec![1.0; 81]);
+/// let white = equal_energy.to_white_point(&Cie1931);
+/// assert_relative_eq!(white, Xyz::new(1.0, 1.0, 1.0), epsilon=1e-4);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Spd<T> {
+    start: T,
+    step: T,
+    values: Vec<T>,
+}
+
+impl<T> Spd<T>
+where
+    T: Float + FreeChannelScalar,
+{
+    /// Construct a new `Spd` from samples starting at `start` and spaced by `step`
+    ///
+    /// Panics:
+    /// ========
+    /// `new` will panic if `step` is not positive or if `values` is empty.
+    pub fn new(start: T, step: T, values: Vec<T>) -> Self {
+        assert!(step > T::zero(), "Spd wavelength step must be positive");
+        assert!(!values.is_empty(), "Spd must have at least one sample");
+        Spd {
+            start,
+            step,
+            values,
+        }
+    }
+
+    /// Construct a new `Spd` by sampling `f` from `start` to `end` in increments of `step`
+    ///
+    /// `end` is included if it lies on a sample.
+    pub fn from_fn<F>(start: T, end: T, step: T, mut f: F) -> Self
+    where
+        F: FnMut(T) -> T,
+    {
+        assert!(step > T::zero(), "Spd wavelength step must be positive");
+        let count = ((end - start) / step + cast(1e-6).unwrap())
+            .floor()
+            .to_usize()
+            .unwrap_or(0)
+            + 1;
+        let values = (0..count)
+            .map(|i| f(start + step * cast(i).unwrap()))
+            .collect();
+        Spd::new(start, step, values)
+    }
+
+    /// Returns the wavelength of the first sample
+    pub fn start(&self) -> T {
+        self.start
+    }
+    /// Returns the wavelength of the last sample
+    pub fn end(&self) -> T {
+        self.start + self.step * cast(self.values.len() - 1).unwrap()
+    }
+    /// Returns the distance between samples
+    pub fn step(&self) -> T {
+        self.step
+    }
+    /// Returns the sampled values
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+    /// Returns a mutable reference to the sampled values
+    pub fn values_mut(&mut self) -> &mut [T] {
+        &mut self.values
+    }
+    /// Returns an iterator over `(wavelength, value)` pairs of the samples
+    pub fn samples<'a>(&'a self) -> impl Iterator<Item = (T, T)> + 'a {
+        let (start, step) = (self.start, self.step);
+        self.values
+            .iter()
+            .enumerate()
+            .map(move |(i, &v)| (start + step * cast(i).unwrap(), v))
+    }
+
+    /// Returns the value at `wavelength`, linearly interpolated between samples
+    ///
+    /// Returns zero outside of the sampled range, including for a NaN or infinite `wavelength`.
+    pub fn value_at(&self, wavelength: T) -> T {
+        let last = self.values.len() - 1;
+        let pos = (wavelength - self.start) / self.step;
+        if !pos.is_finite() || pos < T::zero() || pos > cast(last).unwrap() {
+            return T::zero();
+        }
+        let index = pos.floor().to_usize().unwrap().min(last);
+        if index == last {
+            return self.values[last];
+        }
+        let frac = pos - cast(index).unwrap();
+        let (lower, upper) = (self.values[index], self.values[index + 1]);
+        lower + (upper - lower) * frac
+    }
+
+    /// Returns a new `Spd` with every value multiplied by `factor`
+    pub fn scale(&self, factor: T) -> Self {
+        Spd {
+            start: self.start,
+            step: self.step,
+            values: self.values.iter().map(|&v| v * factor).collect(),
+        }
+    }
+
+    /// Returns the product of `self` and `other` at each of the samples of `self`
+    ///
+    /// Multiplying an illuminant by a reflectance gives the spectrum of the light reflected from
+    /// the surface.
+    pub fn multiply(&self, other: &Spd<T>) -> Self {
+        Spd {
+            start: self.start,
+            step: self.step,
+            values: self
+                .samples()
+                .map(|(wavelength, v)| v * other.value_at(wavelength))
+                .collect(),
+        }
+    }
+
+    /// Convert an emissive spectrum to `Xyz` as seen by the observer `cmf`
+    ///
+    /// The result is scaled so that an equal-energy spectrum with a value of 1 at every
+    /// wavelength has a `Y` of 1.
+    pub fn to_xyz<C>(&self, cmf: &C) -> Xyz<T>
+    where
+        C: ColorMatchingFunctions,
+    {
+        let (x, y, z) = self.integrate(cmf, |_| T::one());
+        let norm = Self::integrate_luminance(cmf, |_| T::one());
+        Xyz::new(x / norm, y / norm, z / norm)
+    }
+
+    /// Convert an emissive spectrum to a white point as seen by the observer `cmf`
+    ///
+    /// The result is scaled to have a `Y` of 1, so that it can be used directly as a white point.
+    /// Returns the zero color if the spectrum has no luminance.
+    pub fn to_white_point<C>(&self, cmf: &C) -> Xyz<T>
+    where
+        C: ColorMatchingFunctions,
+    {
+        let (x, y, z) = self.integrate(cmf, |_| T::one());
+        if y == T::zero() {
+            return Xyz::new(T::zero(), T::zero(), T::zero());
+        }
+        Xyz::new(x / y, T::one(), z / y)
+    }
+
+    /// Convert a reflectance spectrum to `Xyz` under the light of `illuminant`
+    ///
+    /// The result is relative to the white point of `illuminant`: a perfect reflector with a
+    /// reflectance of 1 at every wavelength yields the same value as
+    /// [`illuminant.to_white_point(cmf)`](#method.to_white_point), which has a `Y` of 1.
+    pub fn to_reflected_xyz<C>(&self, illuminant: &Spd<T>, cmf: &C) -> Xyz<T>
+    where
+        C: ColorMatchingFunctions,
+    {
+        let (x, y, z) = self.integrate(cmf, |wavelength| illuminant.value_at(wavelength));
+        let norm = Self::integrate_luminance(cmf, |wavelength| illuminant.value_at(wavelength));
+        if norm == T::zero() {
+            return Xyz::new(T::zero(), T::zero(), T::zero());
+        }
+        Xyz::new(x / norm, y / norm, z / norm)
+    }
+
+    /// Integrate `self` times `weight` against `cmf`, sampling at the wavelengths of `cmf`
+    fn integrate<C, F>(&self, cmf: &C, weight: F) -> (T, T, T)
+    where
+        C: ColorMatchingFunctions,
+        F: Fn(T) -> T,
+    {
+        let mut sum = (T::zero(), T::zero(), T::zero());
+        for (wavelength, [x, y, z]) in cmf_samples(cmf) {
+            let power = self.value_at(wavelength) * weight(wavelength);
+            sum.0 = sum.0 + power * cast(*x).unwrap();
+            sum.1 = sum.1 + power * cast(*y).unwrap();
+            sum.2 = sum.2 + power * cast(*z).unwrap();
+        }
+        sum
+    }
+
+    fn integrate_luminance<C, F>(cmf: &C, weight: F) -> T
+    where
+        C: ColorMatchingFunctions,
+        F: Fn(T) -> T,
+    {
+        cmf_samples(cmf).fold(T::zero(), |sum, (wavelength, [_, y, _])| {
+            sum + weight(wavelength) * cast(*y).unwrap()
+        })
+    }
+}
+
+impl<T> Spd<T>
+where
+    T: Float + FreeChannelScalar + PosNormalChannelScalar,
+{
+    /// Convert a reflectance spectrum to `Lab` under the light of `illuminant`
+    ///
+    /// The `Lab` value is relative to the white point of `illuminant`, so that it describes the
+    /// appearance of the surface to an observer adapted to that light. Returns `None` if
+    /// `illuminant` has no luminance.
+    pub fn to_reflected_lab<C>(&self, illuminant: &Spd<T>, cmf: &C) -> Option<Lab<T, Xyz<T>>>
+    where
+        C: ColorMatchingFunctions,
+    {
+        let white = illuminant.to_white_point(cmf);
+        if white.y() == T::zero() {
+            return None;
+        }
+        Some(Lab::from_xyz(
+            &self.to_reflected_xyz(illuminant, cmf),
+            white,
+        ))
+    }
+}
+
+fn cmf_samples<'a, T, C>(cmf: &'a C) -> impl Iterator<Item = (T, &'a [f64; 3])> + 'a
+where
+    T: Float,
+    C: ColorMatchingFunctions,
+{
+    let (start, step) = (cmf.start_wavelength(), cmf.wavelength_step());
+    cmf.table()
+        .iter()
+        .enumerate()
+        .map(move |(i, v)| (cast(start + step * i as f64).unwrap(), v))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::white_point::{WhitePoint, E};
+    use approx::*;
+
+    #[test]
+    fn test_construct() {
+        let spd = Spd::new(400.0, 10.0, vec![0.5, 1.0, 0.25]);
+        assert_relative_eq!(spd.start(), 400.0);
+        assert_relative_eq!(spd.end(), 420.0);
+        assert_relative_eq!(spd.step(), 10.0);
+        assert_eq!(spd.values(), &[0.5, 1.0, 0.25]);
+        assert_eq!(
+            spd.samples().collect::<Vec<_>>(),
+            vec![(400.0, 0.5), (410.0, 1.0), (420.0, 0.25)]
+        );
+
+        let spd2 = Spd::from_fn(400.0, 420.0, 10.0, |w| w / 10.0);
+        assert_eq!(spd2.values(), &[40.0, 41.0, 42.0]);
+        let spd3 = Spd::from_fn(400.0, 425.0, 10.0, |_| 1.0);
+        assert_relative_eq!(spd3.end(), 420.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bad_step() {
+        let _ = Spd::new(400.0, 0.0, vec![1.0]);
+    }
+
+    #[test]
+    fn test_value_at() {
+        let spd = Spd::new(400.0, 10.0, vec![0.5, 1.0, 0.25]);
+        assert_relative_eq!(spd.value_at(400.0), 0.5);
+        assert_relative_eq!(spd.value_at(405.0), 0.75);
+        assert_relative_eq!(spd.value_at(417.5), 0.4375);
+        assert_relative_eq!(spd.value_at(420.0), 0.25);
+        assert_relative_eq!(spd.value_at(399.0), 0.0);
+        assert_relative_eq!(spd.value_at(421.0), 0.0);
+        assert_relative_eq!(spd.value_at(f64::NAN), 0.0);
+        assert_relative_eq!(spd.value_at(f64::INFINITY), 0.0);
+
+        let single = Spd::new(555.0, 5.0, vec![2.0]);
+        assert_relative_eq!(single.value_at(555.0), 2.0);
+        assert_relative_eq!(single.value_at(556.0), 0.0);
+    }
+
+    #[test]
+    fn test_multiply() {
+        let a = Spd::new(400.0, 10.0, vec![2.0, 2.0, 2.0]);
+        let b = Spd::new(405.0, 10.0, vec![1.0, 3.0]);
+        assert_eq!(a.multiply(&b).values(), &[0.0, 4.0, 0.0]);
+        assert_eq!(a.scale(0.5).values(), &[1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_emissive() {
+        let equal_energy = Spd::from_fn(380.0, 780.0, 5.0, |_| 2.0);
+        assert_relative_eq!(
+            equal_energy.to_xyz(&Cie1931),
+            Xyz::new(2.0, 2.0, 2.0),
+            epsilon = 1e-4
+        );
+        assert_relative_eq!(
+            equal_energy.to_white_point(&Cie1931),
+            E.get_xyz(),
+            epsilon = 1e-4
+        );
+
+        let mono = Spd::new(555.0, 5.0, vec![1.0]);
+        let xyz = mono.to_white_point(&Cie1931);
+        assert_relative_eq!(xyz, Xyz::new(0.51205, 1.0, 0.00575), epsilon = 1e-9);
+
+        assert_relative_eq!(
+            equal_energy.to_white_point(&Cie1964),
+            E.get_xyz(),
+            epsilon = 1e-3
+        );
+
+        let dark = Spd::new(800.0, 5.0, vec![1.0]);
+        assert_eq!(dark.to_white_point(&Cie1931), Xyz::new(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_reflective() {
+        let illuminant = Spd::from_fn(380.0, 780.0, 5.0, |w| w / 500.0);
+        let perfect = Spd::new(300.0, 100.0, vec![1.0; 6]);
+        assert_relative_eq!(
+            perfect.to_reflected_xyz(&illuminant, &Cie1931),
+            illuminant.to_white_point(&Cie1931),
+            epsilon = 1e-9
+        );
+
+        let gray = Spd::new(380.0, 400.0, vec![0.5, 0.5]);
+        let xyz = gray.to_reflected_xyz(&illuminant, &Cie1931);
+        assert_relative_eq!(xyz.y(), 0.5, epsilon = 1e-9);
+
+        let lab = gray.to_reflected_lab(&illuminant, &Cie1931).unwrap();
+        assert_relative_eq!(lab.L(), 76.0693, epsilon = 1e-4);
+        assert_relative_eq!(lab.a(), 0.0, epsilon = 1e-9);
+        assert_relative_eq!(lab.b(), 0.0, epsilon = 1e-9);
+        let dark = Spd::new(800.0, 5.0, vec![1.0]);
+        assert_eq!(gray.to_reflected_lab(&dark, &Cie1931), None);
+
+        let reflected = illuminant.multiply(&gray);
+        assert_relative_eq!(
+            reflected.to_white_point(&Cie1931),
+            illuminant.to_white_point(&Cie1931),
+            epsilon = 1e-9
+        );
+    }
+}
@@ -0,0 +1,274 @@
+//! `proptest` strategies generating colors, enabled by the `proptest` feature
+//!
+//! Each function returns a [`Strategy`](https://docs.rs/proptest/1/proptest/strategy/trait.Strategy.html)
+//! producing valid colors of one type, for property testing code that consumes colors:
+//!
+//! * Device dependent colors have every channel in its normalized range, and hues within a
+//!   single period.
+//! * [`in_gamut`](fn.in_gamut.html) generates `Xyz` colors within the gamut of an RGB color space.
+//! * The CIE and Oklab colors are generated within the sRGB gamut, so they can always be
+//!   converted to sRGB without clipping. `Lab`, `Luv` and their polar forms are adapted to their
+//!   white point with the Bradford transform.
+//!
+//! Channels are generated as `f64` in [0, 1] and cast to the channel type, so integer channels
+//! cover their full range.
+//!
+//! ## Examples:
+//!
+//! ```rust
+//! # extern crate prisma;
+//! #[macro_use] extern crate proptest;
+//!
+//! use prisma::{Bounded, FromColor, Hsv, Rgb};
+//! use prisma::strategy;
+//!
+//! proptest! {
+//!     fn hsv_is_normalized(rgb in strategy::rgb::<f64>()) {
+//!         let hsv: Hsv<f64> = Hsv::from_color(&rgb);
+//!         prop_assert!(hsv.is_normalized());
+//!     }
+//! }
+//! # fn main() { hsv_is_normalized(); }
+//! ```
+
+use crate::alpha::Alpha;
+use crate::channel::{
+    AngularChannelScalar, ChannelFormatCast, FreeChannelScalar, PosNormalChannelScalar,
+};
+use crate::color::Color;
+use crate::color_space::named::SRgb;
+use crate::color_space::ColorSpace;
+use crate::convert::FromColor;
+use crate::hsl::Hsl;
+use crate::hsv::Hsv;
+use crate::hwb::{Hwb, HwbBoundedChannelTraits};
+use crate::lab::Lab;
+use crate::lchab::Lchab;
+use crate::lchuv::Lchuv;
+use crate::lms::{chromatic_adaptation_transform, Bradford};
+use crate::luma::Luma;
+use crate::luv::Luv;
+use crate::oklab::Oklab;
+use crate::oklch::Oklch;
+use crate::rgb::Rgb;
+use crate::white_point::{UnitWhitePoint, WhitePoint, D65};
+use crate::xyz::Xyz;
+use angle::{Angle, FromAngle, Rad};
+use num_traits::{cast, Float};
+use proptest::strategy::Strategy;
+use std::fmt::{Debug, Display};
+
+/// Returns a strategy generating a channel value in its normalized range
+pub fn channel<T>() -> impl Strategy<Value = T>
+where
+    T: Debug,
+    f64: ChannelFormatCast<T>,
+{
+    (0.0..=1.0f64).prop_map(ChannelFormatCast::cast)
+}
+
+/// Returns a strategy generating an angle within a single period
+pub fn hue<A>() -> impl Strategy<Value = A>
+where
+    A: AngularChannelScalar + Debug,
+{
+    (0.0..1.0f64).prop_map(|turns| {
+        AngularChannelScalar::normalize(A::new(cast::<_, A::Scalar>(turns).unwrap() * A::period()))
+    })
+}
+
+/// Returns a strategy generating `Rgb` colors
+pub fn rgb<T>() -> impl Strategy<Value = Rgb<T>>
+where
+    T: PosNormalChannelScalar + Debug,
+    f64: ChannelFormatCast<T>,
+{
+    (channel(), channel(), channel()).prop_map(|(r, g, b)| Rgb::new(r, g, b))
+}
+
+/// Returns a strategy generating `Luma` colors
+pub fn luma<T>() -> impl Strategy<Value = Luma<T>>
+where
+    T: PosNormalChannelScalar + Debug,
+    f64: ChannelFormatCast<T>,
+{
+    channel().prop_map(Luma::new)
+}
+
+/// Returns a strategy generating `Hsv` colors
+pub fn hsv<T, A>() -> impl Strategy<Value = Hsv<T, A>>
+where
+    T: PosNormalChannelScalar + Debug,
+    A: AngularChannelScalar + Debug,
+    f64: ChannelFormatCast<T>,
+{
+    (hue(), channel(), channel()).prop_map(|(h, s, v)| Hsv::new(h, s, v))
+}
+
+/// Returns a strategy generating `Hsl` colors
+pub fn hsl<T, A>() -> impl Strategy<Value = Hsl<T, A>>
+where
+    T: PosNormalChannelScalar + Debug,
+    A: AngularChannelScalar + Debug,
+    f64: ChannelFormatCast<T>,
+{
+    (hue(), channel(), channel()).prop_map(|(h, s, l)| Hsl::new(h, s, l))
+}
+
+/// Returns a strategy generating `Hwb` colors
+pub fn hwb<T, A>() -> impl Strategy<Value = Hwb<T, A>>
+where
+    T: HwbBoundedChannelTraits + Debug,
+    A: AngularChannelScalar + Debug,
+    f64: ChannelFormatCast<T>,
+{
+    (hue(), channel(), channel()).prop_map(|(h, w, b)| Hwb::new(h, w, b))
+}
+
+/// Returns a strategy generating colors from `color` with an alpha channel
+pub fn alpha<T, C, S>(color: S) -> impl Strategy<Value = Alpha<T, C>>
+where
+    T: PosNormalChannelScalar + Debug,
+    C: Color + Debug,
+    S: Strategy<Value = C>,
+    f64: ChannelFormatCast<T>,
+{
+    (color, channel()).prop_map(|(c, a)| Alpha::new(c, a))
+}
+
+/// Returns a strategy generating `Xyz` colors within the gamut of `space`
+///
+/// The colors are relative to the white point of the space.
+pub fn in_gamut<T, S>(space: S) -> impl Strategy<Value = Xyz<T>>
+where
+    T: FreeChannelScalar + PosNormalChannelScalar + Float + Debug,
+    S: ColorSpace<T> + Debug,
+    f64: ChannelFormatCast<T>,
+{
+    (channel(), channel(), channel()).prop_map(move |rgb| {
+        let (x, y, z) = space.apply_transform(rgb);
+        Xyz::new(x, y, z)
+    })
+}
+
+/// Returns a strategy generating `Oklab` colors within the sRGB gamut
+pub fn oklab<T>() -> impl Strategy<Value = Oklab<T>>
+where
+    T: FreeChannelScalar + PosNormalChannelScalar + Float + Debug,
+    f64: ChannelFormatCast<T>,
+{
+    in_gamut(SRgb::new()).prop_map(|xyz| Oklab::from_color(&xyz))
+}
+
+/// Returns a strategy generating `Oklch` colors within the sRGB gamut
+pub fn oklch<T, A>() -> impl Strategy<Value = Oklch<T, A>>
+where
+    T: FreeChannelScalar + PosNormalChannelScalar + Float + Debug,
+    A: AngularChannelScalar + FromAngle<Rad<T>> + Angle + Debug,
+    f64: ChannelFormatCast<T>,
+{
+    oklab().prop_map(|lab| Oklch::from_color(&lab))
+}
+
+/// Returns a strategy generating `Lab` colors within the sRGB gamut
+pub fn lab<T, W>() -> impl Strategy<Value = Lab<T, W>>
+where
+    T: FreeChannelScalar + PosNormalChannelScalar + Float + Debug,
+    W: UnitWhitePoint<T> + Debug,
+    f64: ChannelFormatCast<T>,
+{
+    srgb_relative_to::<T, W>().prop_map(|xyz| Lab::from_xyz(&xyz, W::default()))
+}
+
+/// Returns a strategy generating `Lchab` colors within the sRGB gamut
+pub fn lchab<T, W, A>() -> impl Strategy<Value = Lchab<T, W, A>>
+where
+    T: FreeChannelScalar + PosNormalChannelScalar + Float + Debug,
+    W: UnitWhitePoint<T> + Debug,
+    A: AngularChannelScalar + FromAngle<Rad<T>> + Angle + Debug,
+    f64: ChannelFormatCast<T>,
+{
+    lab().prop_map(|lab| Lchab::from_color(&lab))
+}
+
+/// Returns a strategy generating `Luv` colors within the sRGB gamut
+pub fn luv<T, W>() -> impl Strategy<Value = Luv<T, W>>
+where
+    T: FreeChannelScalar + PosNormalChannelScalar + Float + Display + Debug,
+    W: UnitWhitePoint<T> + Debug,
+    f64: ChannelFormatCast<T>,
+{
+    srgb_relative_to::<T, W>().prop_map(|xyz| Luv::from_xyz(&xyz, W::default()))
+}
+
+/// Returns a strategy generating `Lchuv` colors within the sRGB gamut
+pub fn lchuv<T, W, A>() -> impl Strategy<Value = Lchuv<T, W, A>>
+where
+    T: FreeChannelScalar + PosNormalChannelScalar + Float + Display + Debug,
+    W: UnitWhitePoint<T> + Debug,
+    A: AngularChannelScalar + FromAngle<Rad<T>> + Angle + Debug,
+    f64: ChannelFormatCast<T>,
+{
+    luv().prop_map(|luv| Lchuv::from_color(&luv))
+}
+
+/// Generates sRGB colors as `Xyz` adapted from D65 to the white point `W`
+fn srgb_relative_to<T, W>() -> impl Strategy<Value = Xyz<T>>
+where
+    T: FreeChannelScalar + PosNormalChannelScalar + Float + Debug,
+    W: UnitWhitePoint<T>,
+    f64: ChannelFormatCast<T>,
+{
+    let adapt =
+        chromatic_adaptation_transform::<T, Bradford>(&D65.get_xyz(), &W::default().get_xyz());
+    in_gamut(SRgb::new()).prop_map(move |xyz| {
+        let (x, y, z) = adapt.transform_vector(xyz.to_tuple());
+        Xyz::new(x, y, z)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::color::Bounded;
+    use crate::white_point::D50;
+    use angle::{Deg, Turns};
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_device_colors(
+            rgb in rgb::<u8>(),
+            hsv in hsv::<f32, Deg<f32>>(),
+            hsl in hsl::<f64, Turns<f64>>(),
+            hwb in hwb::<f64, Deg<f64>>(),
+            rgba in alpha::<f64, _, _>(rgb::<f64>()),
+        ) {
+            prop_assert!(rgb.is_normalized());
+            prop_assert!(hsv.is_normalized());
+            prop_assert!(hsl.is_normalized());
+            prop_assert!(hwb.is_normalized());
+            prop_assert!(rgba.is_normalized());
+        }
+
+        #[test]
+        fn test_cie_colors(
+            xyz in in_gamut::<f64, _>(SRgb::new()),
+            oklch in oklch::<f64, Deg<f64>>(),
+            lab in lab::<f64, D50>(),
+            lchuv in lchuv::<f64, D50, Deg<f64>>(),
+        ) {
+            let space = SRgb::<f64>::new();
+            let in_srgb = |xyz: &Xyz<f64>| {
+                let (r, g, b) = space.get_inverse_xyz_transform().transform_vector(xyz.to_tuple());
+                [r, g, b].iter().all(|c| (-1e-6..=1.0 + 1e-6).contains(c))
+            };
+            prop_assert!(in_srgb(&xyz));
+            let oklab = Oklab::from_color(&oklch);
+            prop_assert!(in_srgb(&Xyz::from_color(&oklab)));
+            prop_assert!(oklch.hue() >= Deg(0.0) && oklch.hue() < Deg(360.0));
+            prop_assert!(lab.L() >= -1e-9 && lab.L() <= 100.0 + 1e-9);
+            prop_assert!(lchuv.chroma() >= 0.0);
+        }
+    }
+}
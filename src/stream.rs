@@ -0,0 +1,287 @@
+//! Streaming conversion of colors in fixed size chunks
+//!
+//! The functions in the [`buffer`](../buffer/index.html) module need the whole image in memory,
+//! along with a second buffer for the result. For very large images, or for video arriving a
+//! frame or a row at a time, a [`ColorStream`](struct.ColorStream.html) converts colors as they are
+//! pushed into it and hands the results to a [`ColorSink`](trait.ColorSink.html) whenever a chunk
+//! fills up. Only one chunk of converted colors is ever held, no matter how many are pushed.
+//!
+//! A sink can be a `Vec`, a closure taking a slice of colors, or a [`WriteSink`](struct.WriteSink.html)
+//! writing the raw bytes of each chunk to an `io::Write`.
+//!
+//! ## Examples:
+//!
+//! ```rust
+//! # extern crate prisma;
+//!
+//! use prisma::Rgb;
+//! use prisma::stream::ColorStream;
+//!
+//! let mut chunks = Vec::new();
+//! let mut stream = ColorStream::new(2, |rgb: &Rgb<f32>| rgb.color_cast::<u8>(), |chunk: &[Rgb<u8>]| {
+//!     chunks.push(chunk.to_vec());
+//!     Ok::<(), ()>(())
+//! });
+//!
+//! stream.push(&[Rgb::new(1.0, 0.0, 0.0), Rgb::new(0.0, 1.0, 0.0)]).unwrap();
+//! stream.push(&[Rgb::new(0.0, 0.0, 1.0)]).unwrap();
+//! stream.finish().unwrap();
+//!
+//! assert_eq!(chunks.len(), 2);
+//! assert_eq!(chunks[1], vec![Rgb::new(0, 0, 255)]);
+//! ```
+
+use crate::bytes::{self, Pod};
+use crate::convert::FromColor;
+use std::convert::Infallible;
+use std::io;
+use std::marker::PhantomData;
+
+/// A destination for chunks of converted colors
+pub trait ColorSink<C> {
+    /// The error returned when a chunk could not be written
+    type Error;
+    /// Write a chunk of colors to the sink
+    fn write_colors(&mut self, colors: &[C]) -> Result<(), Self::Error>;
+}
+
+impl<C> ColorSink<C> for Vec<C>
+where
+    C: Clone,
+{
+    type Error = Infallible;
+    fn write_colors(&mut self, colors: &[C]) -> Result<(), Self::Error> {
+        self.extend_from_slice(colors);
+        Ok(())
+    }
+}
+
+impl<C, E, F> ColorSink<C> for F
+where
+    F: FnMut(&[C]) -> Result<(), E>,
+{
+    type Error = E;
+    fn write_colors(&mut self, colors: &[C]) -> Result<(), Self::Error> {
+        self(colors)
+    }
+}
+
+/// A sink writing the bytes of each chunk to an `io::Write`
+///
+/// The colors are written in memory order, as returned by
+/// [`bytes::as_bytes`](../bytes/fn.as_bytes.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WriteSink<W> {
+    writer: W,
+}
+
+impl<W> WriteSink<W>
+where
+    W: io::Write,
+{
+    /// Construct a `WriteSink` writing to `writer`
+    pub fn new(writer: W) -> Self {
+        WriteSink { writer }
+    }
+    /// Returns a reference to the underlying writer
+    pub fn writer(&self) -> &W {
+        &self.writer
+    }
+    /// Consume the sink, returning the underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<C, W> ColorSink<C> for WriteSink<W>
+where
+    C: Pod,
+    W: io::Write,
+{
+    type Error = io::Error;
+    fn write_colors(&mut self, colors: &[C]) -> Result<(), Self::Error> {
+        self.writer.write_all(bytes::as_bytes(colors))
+    }
+}
+
+/// Converts colors as they arrive, passing them to a sink one chunk at a time
+///
+/// Each color pushed is converted with `f` and held until `chunk_size` colors are ready, at which
+/// point the chunk is written to the sink. Call [`finish`](#method.finish) once every color has
+/// been pushed to write the final, partial chunk. Dropping a `ColorStream` without finishing it
+/// discards any colors not yet written.
+///
+/// If the sink returns an error the chunk is kept, and writing it is retried on the next call
+/// to `push` or `flush`. Colors following the full chunk in the failed call to `push` are not
+/// converted and must be pushed again.
+#[derive(Clone, Debug)]
+pub struct ColorStream<S, D, F, K> {
+    f: F,
+    sink: K,
+    chunk: Vec<D>,
+    chunk_size: usize,
+    _source: PhantomData<fn(&S)>,
+}
+
+impl<S, D, F, K> ColorStream<S, D, F, K>
+where
+    F: FnMut(&S) -> D,
+    K: ColorSink<D>,
+{
+    /// Construct a `ColorStream` converting with `f` and writing chunks of `chunk_size` colors
+    /// to `sink`
+    ///
+    /// # Panics
+    /// If `chunk_size` is zero.
+    pub fn new(chunk_size: usize, f: F, sink: K) -> Self {
+        assert!(chunk_size > 0, "chunk size must be greater than zero");
+        ColorStream {
+            f,
+            sink,
+            chunk: Vec::with_capacity(chunk_size),
+            chunk_size,
+            _source: PhantomData,
+        }
+    }
+
+    /// Returns the number of colors written to the sink at a time
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+    /// Returns the number of converted colors waiting to be written
+    pub fn pending(&self) -> usize {
+        self.chunk.len()
+    }
+    /// Returns a reference to the sink
+    pub fn sink(&self) -> &K {
+        &self.sink
+    }
+
+    /// Convert each color in `colors`, writing every chunk that fills to the sink
+    pub fn push(&mut self, colors: &[S]) -> Result<(), K::Error> {
+        for color in colors {
+            if self.chunk.len() >= self.chunk_size {
+                self.flush()?;
+            }
+            self.chunk.push((self.f)(color));
+        }
+        if self.chunk.len() >= self.chunk_size {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Write any pending colors to the sink, even if they do not fill a chunk
+    pub fn flush(&mut self) -> Result<(), K::Error> {
+        if !self.chunk.is_empty() {
+            self.sink.write_colors(&self.chunk)?;
+            self.chunk.clear();
+        }
+        Ok(())
+    }
+
+    /// Write any pending colors to the sink and return it
+    pub fn finish(mut self) -> Result<K, K::Error> {
+        self.flush()?;
+        Ok(self.sink)
+    }
+}
+
+/// Apply `f` to each color in `src`, writing the results to `sink` in chunks of `chunk_size`
+///
+/// # Panics
+/// If `chunk_size` is zero.
+pub fn map_chunked<S, D, F, K>(src: &[S], chunk_size: usize, f: F, sink: K) -> Result<K, K::Error>
+where
+    F: FnMut(&S) -> D,
+    K: ColorSink<D>,
+{
+    let mut stream = ColorStream::new(chunk_size, f, sink);
+    stream.push(src)?;
+    stream.finish()
+}
+
+/// Convert each color in `src` to the color model `D`, writing the results to `sink` in chunks
+/// of `chunk_size`
+///
+/// # Panics
+/// If `chunk_size` is zero.
+pub fn convert_chunked<S, D, K>(src: &[S], chunk_size: usize, sink: K) -> Result<K, K::Error>
+where
+    D: FromColor<S>,
+    K: ColorSink<D>,
+{
+    map_chunked(src, chunk_size, D::from_color, sink)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rgb::Rgb;
+    use crate::Hsv;
+    use angle::Deg;
+
+    #[test]
+    fn test_chunks() {
+        let mut sizes = Vec::new();
+        let mut stream = ColorStream::new(
+            3,
+            |c: &u32| c * 2,
+            |chunk: &[u32]| {
+                sizes.push(chunk.len());
+                Ok::<(), ()>(())
+            },
+        );
+        stream.push(&[1, 2]).unwrap();
+        assert_eq!(stream.pending(), 2);
+        stream.push(&[3, 4, 5, 6, 7]).unwrap();
+        assert_eq!(stream.pending(), 1);
+        stream.push(&[]).unwrap();
+        assert_eq!(stream.chunk_size(), 3);
+        assert!(stream.finish().is_ok());
+        assert_eq!(sizes, vec![3, 3, 1]);
+
+        let doubled = map_chunked(&[1u32, 2, 3, 4], 3, |c| c * 2, Vec::new()).unwrap();
+        assert_eq!(doubled, vec![2, 4, 6, 8]);
+    }
+
+    #[test]
+    fn test_sink_error() {
+        let mut fail = true;
+        let mut written = Vec::new();
+        let mut stream = ColorStream::new(
+            2,
+            |c: &u8| *c,
+            |chunk: &[u8]| {
+                if fail {
+                    fail = false;
+                    Err("full")
+                } else {
+                    written.extend_from_slice(chunk);
+                    Ok(())
+                }
+            },
+        );
+        assert_eq!(stream.push(&[1, 2, 3]), Err("full"));
+        assert_eq!(stream.pending(), 2);
+        stream.push(&[3, 4]).unwrap();
+        assert!(stream.finish().is_ok());
+        assert_eq!(written, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_write_sink() {
+        let pixels = vec![Rgb::new(1.0f32, 0.0, 0.0), Rgb::new(0.0, 0.0, 1.0)];
+        let sink = map_chunked(
+            &pixels,
+            1,
+            |c| c.color_cast::<u8>(),
+            WriteSink::new(Vec::new()),
+        )
+        .unwrap();
+        assert_eq!(sink.into_inner(), vec![255, 0, 0, 0, 0, 255]);
+
+        let hsv: Vec<Hsv<f32, Deg<f32>>> = convert_chunked(&pixels, 4, Vec::new()).unwrap();
+        assert_eq!(hsv[0], Hsv::new(Deg(0.0), 1.0, 1.0));
+    }
+}
@@ -22,8 +22,14 @@ pub struct LchabTag;
 pub struct LchuvTag;
 /// A tag type uniquely identifying the [`Lms`](../struct.Lms.html) type in generic contexts
 pub struct LmsTag;
+/// A tag type uniquely identifying the [`Luma`](../struct.Luma.html) type in generic contexts
+pub struct LumaTag;
 /// A tag type uniquely identifying the [`Luv`](../struct.Luv.html) type in generic contexts
 pub struct LuvTag;
+/// A tag type uniquely identifying the [`Oklab`](../struct.Oklab.html) type in generic contexts
+pub struct OklabTag;
+/// A tag type uniquely identifying the [`Oklch`](../struct.Oklch.html) type in generic contexts
+pub struct OklchTag;
 /// A tag type uniquely identifying the [`Rgb`](../struct.Rgb.html) type in generic contexts
 pub struct RgbTag;
 /// A tag type uniquely identifying the [`Rgi`](../struct.Rgi.html) type in generic contexts
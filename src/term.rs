@@ -0,0 +1,324 @@
+//! ANSI escape sequences for coloring terminal output
+//!
+//! Terminals select colors with SGR escape sequences. Most modern terminals accept 24-bit
+//! "truecolor" sequences, which give the exact color, while older ones only understand indices
+//! into the xterm 256-color palette. `Rgb` colors can be written in both forms with
+//! `to_ansi_truecolor` and `to_ansi_256`, as the foreground or the background color. Writing
+//! [`RESET`](constant.RESET.html) returns to the terminal's default colors.
+//!
+//! `to_ansi_256` rounds each channel to the color cube, which is fast but ignores the grayscale
+//! ramp and how colors look. [`AnsiPalette`](struct.AnsiPalette.html) instead finds the
+//! perceptually nearest entry of the 256-color palette, or of the 16 basic ANSI colors for
+//! terminals supporting nothing more, by comparing colors in `Oklab`.
+//!
+//! Colors are taken to be sRGB encoded, as terminals expect.
+//!
+//! This module is only available with the `term` feature.
+//!
+//! ## Examples:
+//!
+//! ```rust
+//! # extern crate prisma;
+//!
+//! use prisma::Rgb;
+//! use prisma::term::{Layer, RESET};
+//!
+//! let orange = Rgb::new(255u8, 128, 0);
+//! assert_eq!(orange.to_ansi_truecolor(Layer::Foreground), "\x1b[38;2;255;128;0m");
+//! assert_eq!(orange.to_ansi_256(Layer::Background), "\x1b[48;5;208m");
+//!
+//! println!("{}warning{}", orange.to_ansi_truecolor(Layer::Foreground), RESET);
+//! ```
+
+use crate::channel::{ChannelFormatCast, PosNormalChannelScalar};
+use crate::color::Color;
+use crate::color_space::named::SRgb;
+use crate::color_space::ColorSpace;
+use crate::convert::FromColor;
+use crate::distance::DeltaEOk;
+use crate::encoding::EncodableColor;
+use crate::oklab::Oklab;
+use crate::palette::PaletteIndex;
+use crate::rgb::Rgb;
+use crate::xyz::Xyz;
+
+/// The escape sequence that resets the foreground and background colors to the terminal's defaults
+pub const RESET: &str = "\x1b[0m";
+
+/// The levels of each channel in the 6×6×6 color cube of the xterm 256-color palette
+pub const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The 16 basic ANSI colors as xterm displays them by default
+///
+/// These are indices 0 through 15 of the 256-color palette. Unlike the rest of the palette, most
+/// terminals let the user change them, so the colors actually shown may differ.
+pub const ANSI_16: [Rgb<u8>; 16] = [
+    Rgb::new_const(0, 0, 0),
+    Rgb::new_const(205, 0, 0),
+    Rgb::new_const(0, 205, 0),
+    Rgb::new_const(205, 205, 0),
+    Rgb::new_const(0, 0, 238),
+    Rgb::new_const(205, 0, 205),
+    Rgb::new_const(0, 205, 205),
+    Rgb::new_const(229, 229, 229),
+    Rgb::new_const(127, 127, 127),
+    Rgb::new_const(255, 0, 0),
+    Rgb::new_const(0, 255, 0),
+    Rgb::new_const(255, 255, 0),
+    Rgb::new_const(92, 92, 255),
+    Rgb::new_const(255, 0, 255),
+    Rgb::new_const(0, 255, 255),
+    Rgb::new_const(255, 255, 255),
+];
+
+/// The first index of the grayscale ramp in the 256-color palette
+pub const GRAY_RAMP_START: u8 = 232;
+
+/// Which color of the terminal cell an escape sequence sets
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Layer {
+    /// The color of the text
+    Foreground,
+    /// The color behind the text
+    Background,
+}
+
+impl Layer {
+    /// Returns the SGR parameter selecting an extended color for the layer
+    fn sgr_code(self) -> u8 {
+        match self {
+            Layer::Foreground => 38,
+            Layer::Background => 48,
+        }
+    }
+}
+
+/// Returns the escape sequence setting `layer` to the basic ANSI color `index`
+///
+/// Indices 8 through 15 are the bright colors.
+///
+/// ## Panics
+///
+/// If `index` is greater than 15.
+pub fn ansi_16_escape(index: u8, layer: Layer) -> String {
+    assert!(index < 16, "ANSI color index {} is out of range", index);
+    let base = match layer {
+        Layer::Foreground => 30,
+        Layer::Background => 40,
+    };
+    if index < 8 {
+        format!("\x1b[{}m", base + index)
+    } else {
+        format!("\x1b[{}m", base + 60 + index - 8)
+    }
+}
+
+/// Returns the escape sequence setting `layer` to the xterm 256-color palette entry `index`
+pub fn ansi_256_escape(index: u8, layer: Layer) -> String {
+    format!("\x1b[{};5;{}m", layer.sgr_code(), index)
+}
+
+/// Returns the index of the entry of the xterm 6×6×6 color cube closest to `color`
+///
+/// Each channel is rounded to the nearest level of the cube independently. The cube entries are
+/// indices 16 through 231 of the palette.
+pub fn cube_index(color: &Rgb<u8>) -> u8 {
+    let level = |c: u8| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|&i| (i16::from(CUBE_LEVELS[i]) - i16::from(c)).abs())
+            .unwrap() as u8
+    };
+    16 + 36 * level(color.red()) + 6 * level(color.green()) + level(color.blue())
+}
+
+/// Returns the color of entry `index` of the xterm 256-color palette
+///
+/// Indices 0 through 15 are the [`ANSI_16`](constant.ANSI_16.html) colors, 16 through 231 the
+/// 6×6×6 color cube and 232 through 255 a ramp of 24 grays from 8 to 238.
+pub fn xterm_color(index: u8) -> Rgb<u8> {
+    match index {
+        0..=15 => ANSI_16[usize::from(index)],
+        16..=231 => {
+            let i = index - 16;
+            Rgb::new(
+                CUBE_LEVELS[usize::from(i / 36)],
+                CUBE_LEVELS[usize::from(i / 6 % 6)],
+                CUBE_LEVELS[usize::from(i % 6)],
+            )
+        }
+        _ => {
+            let level = 8 + 10 * (index - GRAY_RAMP_START);
+            Rgb::new(level, level, level)
+        }
+    }
+}
+
+/// Finds the perceptually nearest ANSI or xterm 256-color palette entry of colors
+///
+/// Colors are compared by their [`DeltaEOk`](../distance/struct.DeltaEOk.html) distance, which
+/// matches how close colors look far better than comparing `Rgb` channels. Building an
+/// `AnsiPalette` converts every palette entry to `Oklab`, so one should be kept around when
+/// mapping many colors.
+///
+/// ## Examples:
+///
+/// ```rust
+/// # extern crate prisma;
+///
+/// use prisma::Rgb;
+/// use prisma::term::{ansi_256_escape, AnsiPalette, Layer};
+///
+/// let palette = AnsiPalette::new();
+/// let gray = Rgb::new(100u8, 100, 100);
+/// // The grayscale ramp has a much closer gray than the color cube
+/// assert_eq!(palette.nearest_256(&gray), 241);
+/// assert_eq!(palette.nearest_16(&Rgb::new(0.9, 0.1, 0.1)), 9);
+///
+/// let escape = ansi_256_escape(palette.nearest_256(&gray), Layer::Foreground);
+/// assert_eq!(escape, "\x1b[38;5;241m");
+/// ```
+#[derive(Clone, Debug)]
+pub struct AnsiPalette {
+    ansi_16: PaletteIndex<Oklab<f64>, DeltaEOk>,
+    extended: PaletteIndex<Oklab<f64>, DeltaEOk>,
+}
+
+impl AnsiPalette {
+    /// Construct a new `AnsiPalette` for the default xterm colors
+    pub fn new() -> Self {
+        let ansi_16 = ANSI_16.iter().map(srgb_to_oklab).collect();
+        let extended = (16..=255).map(|i| srgb_to_oklab(&xterm_color(i))).collect();
+        AnsiPalette {
+            ansi_16: PaletteIndex::new(ansi_16, DeltaEOk),
+            extended: PaletteIndex::new(extended, DeltaEOk),
+        }
+    }
+
+    /// Returns the index of the basic ANSI color nearest to the sRGB encoded `color`
+    pub fn nearest_16<T>(&self, color: &Rgb<T>) -> u8
+    where
+        T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+    {
+        self.ansi_16.nearest(&srgb_to_oklab(color)).unwrap() as u8
+    }
+
+    /// Returns the index of the 256-color palette entry nearest to the sRGB encoded `color`
+    ///
+    /// Only the color cube and grayscale ramp are searched, as the colors of the first 16
+    /// entries depend on the terminal's settings.
+    pub fn nearest_256<T>(&self, color: &Rgb<T>) -> u8
+    where
+        T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+    {
+        16 + self.extended.nearest(&srgb_to_oklab(color)).unwrap() as u8
+    }
+}
+
+impl Default for AnsiPalette {
+    fn default() -> Self {
+        AnsiPalette::new()
+    }
+}
+
+fn srgb_to_oklab<T>(color: &Rgb<T>) -> Oklab<f64>
+where
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+{
+    let linear = color.color_cast::<f64>().srgb_encoded().decode();
+    let (x, y, z) = SRgb::new().apply_transform(linear.strip_encoding().to_tuple());
+    Oklab::from_color(&Xyz::new(x, y, z))
+}
+
+impl<T> Rgb<T>
+where
+    T: PosNormalChannelScalar + ChannelFormatCast<u8>,
+{
+    /// Returns the 24-bit truecolor escape sequence setting `layer` to the color
+    pub fn to_ansi_truecolor(&self, layer: Layer) -> String {
+        let color: Rgb<u8> = self.clone().color_cast();
+        format!(
+            "\x1b[{};2;{};{};{}m",
+            layer.sgr_code(),
+            color.red(),
+            color.green(),
+            color.blue()
+        )
+    }
+    /// Returns the 256-color escape sequence setting `layer` to the closest entry of the color
+    /// cube, as [`cube_index`](term/fn.cube_index.html)
+    pub fn to_ansi_256(&self, layer: Layer) -> String {
+        ansi_256_escape(cube_index(&self.clone().color_cast()), layer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_truecolor() {
+        let color = Rgb::new(12u8, 200, 255);
+        assert_eq!(
+            color.to_ansi_truecolor(Layer::Foreground),
+            "\x1b[38;2;12;200;255m"
+        );
+        assert_eq!(
+            color.to_ansi_truecolor(Layer::Background),
+            "\x1b[48;2;12;200;255m"
+        );
+        assert_eq!(
+            Rgb::new(1.0, 0.0, 1.0).to_ansi_truecolor(Layer::Foreground),
+            "\x1b[38;2;255;0;255m"
+        );
+    }
+
+    #[test]
+    fn test_ansi_256() {
+        assert_eq!(cube_index(&Rgb::new(0, 0, 0)), 16);
+        assert_eq!(cube_index(&Rgb::new(255, 255, 255)), 231);
+        assert_eq!(cube_index(&Rgb::new(255, 0, 0)), 196);
+        assert_eq!(cube_index(&Rgb::new(95, 135, 175)), 67);
+        assert_eq!(cube_index(&Rgb::new(40, 120, 200)), 32);
+        assert_eq!(
+            Rgb::new(0.0, 1.0, 0.0).to_ansi_256(Layer::Foreground),
+            "\x1b[38;5;46m"
+        );
+        assert_eq!(ansi_256_escape(232, Layer::Background), "\x1b[48;5;232m");
+    }
+
+    #[test]
+    fn test_xterm_color() {
+        assert_eq!(xterm_color(1), Rgb::new(205, 0, 0));
+        assert_eq!(xterm_color(16), Rgb::new(0, 0, 0));
+        assert_eq!(xterm_color(67), Rgb::new(95, 135, 175));
+        assert_eq!(xterm_color(231), Rgb::new(255, 255, 255));
+        assert_eq!(xterm_color(232), Rgb::new(8, 8, 8));
+        assert_eq!(xterm_color(255), Rgb::new(238, 238, 238));
+        for i in 16..=231 {
+            assert_eq!(cube_index(&xterm_color(i)), i);
+        }
+
+        assert_eq!(ansi_16_escape(1, Layer::Foreground), "\x1b[31m");
+        assert_eq!(ansi_16_escape(12, Layer::Foreground), "\x1b[94m");
+        assert_eq!(ansi_16_escape(0, Layer::Background), "\x1b[40m");
+        assert_eq!(ansi_16_escape(15, Layer::Background), "\x1b[107m");
+    }
+
+    #[test]
+    fn test_nearest() {
+        let palette = AnsiPalette::new();
+        for i in 16..=255 {
+            assert_eq!(palette.nearest_256(&xterm_color(i)), i);
+        }
+        for i in 0..16 {
+            assert_eq!(palette.nearest_16(&ANSI_16[i as usize]), i);
+        }
+
+        assert_eq!(palette.nearest_256(&Rgb::new(250u8, 250, 250)), 231);
+        assert_eq!(palette.nearest_256(&Rgb::new(0.02, 0.02, 0.02)), 232);
+        assert_eq!(palette.nearest_256(&Rgb::new(255u8, 140, 0)), 208);
+        assert_eq!(palette.nearest_16(&Rgb::new(20u8, 20, 30)), 0);
+        assert_eq!(palette.nearest_16(&Rgb::new(30u8, 180, 40)), 2);
+        assert_eq!(palette.nearest_16(&Rgb::new(210u8, 210, 210)), 7);
+    }
+}
@@ -0,0 +1,345 @@
+//! Detecting NaN and infinite channels in floating point colors
+//!
+//! Floating point channels can hold values that aren't colors at all. A NaN or infinite channel
+//! usually comes from a division by zero or an overflow somewhere upstream, and once it is in a
+//! color it propagates silently through every conversion, with NaN hues being the most common
+//! case. Hues that are subnormal are also reported, as they are always the remains of a
+//! computation that should have given zero and are very slow to compute with on many processors.
+//!
+//! Colors implementing [`Validate`](trait.Validate.html) can be checked with `is_valid`, or with
+//! `validate`, which returns an [`InvalidColorError`](struct.InvalidColorError.html) naming the
+//! offending channels.
+//!
+//! With the `debug-validate` feature enabled, the constructors of every color also check their
+//! channels with `debug_assert!`, catching invalid values where they are made in debug builds.
+//!
+//! ## Examples:
+//!
+//! ```rust
+//! # extern crate prisma;
+//! extern crate angular_units as angle;
+//!
+//! use prisma::{Hsv, Rgb};
+//! use prisma::validate::{InvalidKind, Validate};
+//! use angle::Deg;
+//!
+//! assert!(Rgb::new(0.5f32, 0.25, 1.0).is_valid());
+//!
+//! # if !cfg!(feature = "debug-validate") {
+//! let hsv = Hsv::new(Deg(f64::NAN), 0.5, f64::INFINITY);
+//! let err = hsv.validate().unwrap_err();
+//! assert_eq!(err.channels()[0].name, "hue");
+//! assert_eq!(err.channels()[0].kind, InvalidKind::NaN);
+//! assert_eq!(err.channels()[1].kind, InvalidKind::Infinite);
+//! assert_eq!(err.to_string(), "invalid color: hue is NaN, value is infinite");
+//! # }
+//! ```
+
+#![allow(non_snake_case)]
+
+use crate::alpha::Alpha;
+use crate::channel::{
+    AngularChannelScalar, FreeChannelScalar, NormalChannelScalar, PosNormalChannelScalar,
+};
+use crate::color::Color;
+use crate::ehsi::eHsi;
+use crate::hsi::Hsi;
+use crate::hsl::Hsl;
+use crate::hsv::Hsv;
+use crate::hwb::Hwb;
+use crate::lab::Lab;
+use crate::lchab::Lchab;
+use crate::lchuv::Lchuv;
+use crate::lms::{Lms, LmsModel};
+use crate::luma::Luma;
+use crate::luv::Luv;
+use crate::oklab::Oklab;
+use crate::oklch::Oklch;
+use crate::rgb::Rgb;
+use crate::rgi::Rgi;
+use crate::white_point::UnitWhitePoint;
+use crate::xyy::XyY;
+use crate::xyz::Xyz;
+use crate::ycbcr::{BareYCbCr, UnitModel, YCbCr, YCbCrModel};
+use angle::Angle;
+use num_traits::Float;
+use std::error::Error;
+use std::fmt;
+use std::num::FpCategory;
+
+/// The reason a channel is invalid
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum InvalidKind {
+    /// The channel is NaN
+    NaN,
+    /// The channel is positive or negative infinity
+    Infinite,
+    /// The channel is a subnormal hue
+    SubnormalHue,
+}
+
+impl fmt::Display for InvalidKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            InvalidKind::NaN => write!(f, "NaN"),
+            InvalidKind::Infinite => write!(f, "infinite"),
+            InvalidKind::SubnormalHue => write!(f, "subnormal"),
+        }
+    }
+}
+
+/// A channel that does not hold a valid value
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct InvalidChannel {
+    /// The index of the channel in the color
+    pub index: usize,
+    /// The name of the channel, such as `"hue"`
+    pub name: &'static str,
+    /// Why the channel is invalid
+    pub kind: InvalidKind,
+}
+
+/// An error returned when a color has invalid channels
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidColorError {
+    channels: Vec<InvalidChannel>,
+}
+
+impl InvalidColorError {
+    /// Construct an `InvalidColorError` from the channels that are invalid
+    pub fn new(channels: Vec<InvalidChannel>) -> Self {
+        InvalidColorError { channels }
+    }
+    /// Returns the channels that are invalid
+    pub fn channels(&self) -> &[InvalidChannel] {
+        &self.channels
+    }
+}
+
+impl fmt::Display for InvalidColorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid color:")?;
+        for (i, channel) in self.channels.iter().enumerate() {
+            let sep = if i == 0 { " " } else { ", " };
+            write!(f, "{}{} is {}", sep, channel.name, channel.kind)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for InvalidColorError {}
+
+/// A floating point color whose channels can be checked for NaN and infinite values
+pub trait Validate {
+    /// Return every channel that is NaN, infinite or a subnormal hue
+    ///
+    /// An empty `Vec` means the color is valid.
+    fn invalid_channels(&self) -> Vec<InvalidChannel>;
+
+    /// Returns true if every channel holds a valid value
+    fn is_valid(&self) -> bool {
+        self.invalid_channels().is_empty()
+    }
+
+    /// Returns an error naming the invalid channels, if there are any
+    fn validate(&self) -> Result<(), InvalidColorError> {
+        let channels = self.invalid_channels();
+        if channels.is_empty() {
+            Ok(())
+        } else {
+            Err(InvalidColorError::new(channels))
+        }
+    }
+}
+
+fn check_scalar<T: Float>(
+    out: &mut Vec<InvalidChannel>,
+    index: &mut usize,
+    name: &'static str,
+    value: T,
+) {
+    let kind = if value.is_nan() {
+        Some(InvalidKind::NaN)
+    } else if value.is_infinite() {
+        Some(InvalidKind::Infinite)
+    } else {
+        None
+    };
+    if let Some(kind) = kind {
+        out.push(InvalidChannel {
+            index: *index,
+            name,
+            kind,
+        });
+    }
+    *index += 1;
+}
+
+fn check_hue<A>(out: &mut Vec<InvalidChannel>, index: &mut usize, name: &'static str, value: A)
+where
+    A: AngularChannelScalar,
+    A::Scalar: Float,
+{
+    let value = value.scalar();
+    if value.classify() == FpCategory::Subnormal {
+        out.push(InvalidChannel {
+            index: *index,
+            name,
+            kind: InvalidKind::SubnormalHue,
+        });
+        *index += 1;
+    } else {
+        check_scalar(out, index, name, value);
+    }
+}
+
+macro_rules! impl_validate {
+    (<$($gen:ident),*> $name:ty where { $($bounds:tt)* } [$($kind:ident $chan:ident),*]) => {
+        impl<$($gen),*> Validate for $name
+        where
+            $($bounds)*
+        {
+            fn invalid_channels(&self) -> Vec<InvalidChannel> {
+                let mut out = Vec::new();
+                let mut index = 0;
+                $(impl_validate!(@check $kind, self, out, index, $chan);)*
+                out
+            }
+        }
+    };
+    (@check scalar, $self:ident, $out:ident, $index:ident, $chan:ident) => {
+        check_scalar(&mut $out, &mut $index, stringify!($chan), $self.$chan())
+    };
+    (@check hue, $self:ident, $out:ident, $index:ident, $chan:ident) => {
+        check_hue(&mut $out, &mut $index, stringify!($chan), $self.$chan())
+    };
+}
+
+impl_validate!(<T> Rgb<T> where { T: PosNormalChannelScalar + Float }
+    [scalar red, scalar green, scalar blue]);
+impl_validate!(<T> Luma<T> where { T: PosNormalChannelScalar + Float } [scalar luma]);
+impl_validate!(<T> Rgi<T> where { T: PosNormalChannelScalar + Float }
+    [scalar red, scalar green, scalar intensity]);
+impl_validate!(<T, A> Hsv<T, A>
+    where { T: PosNormalChannelScalar + Float, A: AngularChannelScalar, A::Scalar: Float }
+    [hue hue, scalar saturation, scalar value]);
+impl_validate!(<T, A> Hsl<T, A>
+    where { T: PosNormalChannelScalar + Float, A: AngularChannelScalar, A::Scalar: Float }
+    [hue hue, scalar saturation, scalar lightness]);
+impl_validate!(<T, A> Hwb<T, A>
+    where { T: PosNormalChannelScalar + Float, A: AngularChannelScalar, A::Scalar: Float }
+    [hue hue, scalar whiteness, scalar blackness]);
+impl_validate!(<T, A> Hsi<T, A>
+    where { T: PosNormalChannelScalar + Float, A: AngularChannelScalar + Angle<Scalar = T> }
+    [hue hue, scalar saturation, scalar intensity]);
+impl_validate!(<T, A> eHsi<T, A>
+    where { T: PosNormalChannelScalar + Float, A: AngularChannelScalar + Angle<Scalar = T> }
+    [hue hue, scalar saturation, scalar intensity]);
+impl_validate!(<T, M> YCbCr<T, M>
+    where {
+        T: NormalChannelScalar + PosNormalChannelScalar + Float,
+        M: YCbCrModel<T> + UnitModel<T>
+    }
+    [scalar luma, scalar cb, scalar cr]);
+impl_validate!(<T> BareYCbCr<T>
+    where { T: NormalChannelScalar + PosNormalChannelScalar + Float }
+    [scalar luma, scalar cb, scalar cr]);
+impl_validate!(<T> Xyz<T> where { T: FreeChannelScalar } [scalar x, scalar y, scalar z]);
+impl_validate!(<T> XyY<T> where { T: FreeChannelScalar + PosNormalChannelScalar }
+    [scalar x, scalar y, scalar Y]);
+impl_validate!(<T, W> Lab<T, W> where { T: FreeChannelScalar, W: UnitWhitePoint<T> }
+    [scalar L, scalar a, scalar b]);
+impl_validate!(<T, W> Luv<T, W> where { T: FreeChannelScalar, W: UnitWhitePoint<T> }
+    [scalar L, scalar u, scalar v]);
+impl_validate!(<T, W, A> Lchab<T, W, A>
+    where { T: FreeChannelScalar, W: UnitWhitePoint<T>, A: AngularChannelScalar, A::Scalar: Float }
+    [scalar L, scalar chroma, hue hue]);
+impl_validate!(<T, W, A> Lchuv<T, W, A>
+    where { T: FreeChannelScalar, W: UnitWhitePoint<T>, A: AngularChannelScalar, A::Scalar: Float }
+    [scalar L, scalar chroma, hue hue]);
+impl_validate!(<T> Oklab<T> where { T: FreeChannelScalar } [scalar L, scalar a, scalar b]);
+impl_validate!(<T, A> Oklch<T, A>
+    where { T: FreeChannelScalar, A: AngularChannelScalar, A::Scalar: Float }
+    [scalar L, scalar chroma, hue hue]);
+impl_validate!(<T, Model> Lms<T, Model> where { T: FreeChannelScalar, Model: LmsModel<T> }
+    [scalar l, scalar m, scalar s]);
+
+impl<T, InnerColor> Validate for Alpha<T, InnerColor>
+where
+    T: PosNormalChannelScalar + Float,
+    InnerColor: Color + Validate,
+{
+    fn invalid_channels(&self) -> Vec<InvalidChannel> {
+        let mut out = self.color().invalid_channels();
+        let mut index = InnerColor::num_channels() as usize;
+        check_scalar(&mut out, &mut index, "alpha", self.alpha());
+        out
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use angle::Deg;
+
+    #[test]
+    #[cfg(all(feature = "debug-validate", debug_assertions))]
+    #[should_panic(expected = "hue is NaN or infinite")]
+    fn test_debug_validate() {
+        Hsv::new(Deg(f64::NAN), 0.5, 0.5);
+    }
+
+    #[test]
+    #[cfg(all(feature = "debug-validate", debug_assertions))]
+    #[should_panic(expected = "color channel is NaN or infinite")]
+    fn test_debug_validate_rgb() {
+        Rgb::new(f32::NAN, 0.0, 0.0);
+    }
+
+    #[test]
+    #[cfg(not(feature = "debug-validate"))]
+    fn test_validate() {
+        use crate::alpha::Rgba;
+        use crate::white_point::D65;
+        use angle::Rad;
+
+        assert!(Rgb::new(0.0f64, 0.5, 1.0).is_valid());
+        assert!(Rgb::new(2.0f32, -1.0, 0.0).is_valid());
+        assert!(Lab::<f64, D65>::new(50.0, 20.0, -10.0).validate().is_ok());
+
+        let rgb = Rgb::new(f64::NAN, 0.5, f64::NEG_INFINITY);
+        assert_eq!(
+            rgb.invalid_channels(),
+            vec![
+                InvalidChannel {
+                    index: 0,
+                    name: "red",
+                    kind: InvalidKind::NaN,
+                },
+                InvalidChannel {
+                    index: 2,
+                    name: "blue",
+                    kind: InvalidKind::Infinite,
+                },
+            ]
+        );
+        assert!(!rgb.is_valid());
+
+        let lch = Lchab::<f64, D65, _>::new(50.0, 10.0, Deg(f64::MIN_POSITIVE / 2.0));
+        let err = lch.validate().unwrap_err();
+        assert_eq!(err.channels().len(), 1);
+        assert_eq!(err.channels()[0].index, 2);
+        assert_eq!(err.channels()[0].kind, InvalidKind::SubnormalHue);
+        assert_eq!(err.to_string(), "invalid color: hue is subnormal");
+
+        let rgba = Rgba::new(Rgb::new(0.1f32, 0.2, 0.3), f32::NAN);
+        let err = rgba.validate().unwrap_err();
+        assert_eq!(err.channels()[0].index, 3);
+        assert_eq!(err.channels()[0].name, "alpha");
+
+        let xyy = XyY::new(0.3, 0.3, f64::INFINITY);
+        assert_eq!(xyy.invalid_channels()[0].name, "Y");
+        let oklch = Oklch::new(0.5, 0.1, Rad(f64::NAN));
+        assert_eq!(oklch.invalid_channels()[0].name, "hue");
+    }
+}
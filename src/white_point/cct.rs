@@ -0,0 +1,131 @@
+//! White points computed from a correlated color temperature
+
+use crate::channel::{FreeChannelScalar, PosNormalChannelScalar};
+use crate::convert::FromColor;
+use crate::xyy::XyY;
+use crate::xyz::Xyz;
+use num_traits::{cast, Float};
+
+/// Compute the XYZ white point of a light source with the correlated color temperature `kelvin`
+///
+/// Below 5000K the white point lies on the Planckian locus, matching incandescent light sources.
+/// From 5000K up it lies on the CIE daylight locus, matching the D-series illuminants. The
+/// returned value is normalized to have a `Y` of 1 and can be used directly as a white point.
+///
+/// Returns `None` if `kelvin` lies outside of the range [1667, 25000] in which the loci are
+/// defined.
+///
+/// ## Examples:
+///
+/// ```rust
+/// #[macro_use] extern crate approx;
+/// # extern crate prisma;
+///
+/// use prisma::white_point::{from_cct, WhitePoint, D65};
+///
+/// let white = from_cct(6504.0).unwrap();
+/// assert_relative_eq!(white, D65.get_xyz(), epsilon=1e-3);
+/// ```
+pub fn from_cct<T>(kelvin: T) -> Option<Xyz<T>>
+where
+    T: Float + FreeChannelScalar + PosNormalChannelScalar,
+{
+    cct_chromaticity(kelvin).map(|xy| Xyz::from_color(&xy))
+}
+
+/// Compute the chromaticity of a light source with the correlated color temperature `kelvin`
+///
+/// See [`from_cct`](fn.from_cct.html) for details.
+pub fn cct_chromaticity<T>(kelvin: T) -> Option<XyY<T>>
+where
+    T: Float + FreeChannelScalar + PosNormalChannelScalar,
+{
+    let t: f64 = cast(kelvin).unwrap();
+    if !(1667.0..=25000.0).contains(&t) {
+        return None;
+    }
+
+    let (x, y) = if t < 5000.0 {
+        planckian_xy(t)
+    } else {
+        daylight_xy(t)
+    };
+    Some(XyY::new(cast(x).unwrap(), cast(y).unwrap(), T::one()))
+}
+
+/// The cubic spline approximation of the Planckian locus by Kim et al.
+fn planckian_xy(t: f64) -> (f64, f64) {
+    let (t2, t3) = (t * t, t * t * t);
+    let x = if t <= 4000.0 {
+        -0.266_123_9e9 / t3 - 0.234_358_9e6 / t2 + 0.877_695_6e3 / t + 0.179_910
+    } else {
+        -3.025_846_9e9 / t3 + 2.107_037_9e6 / t2 + 0.222_634_7e3 / t + 0.240_390
+    };
+    let (x2, x3) = (x * x, x * x * x);
+    let y = if t <= 2222.0 {
+        -1.106_381_4 * x3 - 1.348_110_20 * x2 + 2.185_558_32 * x - 0.202_196_83
+    } else if t <= 4000.0 {
+        -0.954_947_6 * x3 - 1.374_185_93 * x2 + 2.091_370_15 * x - 0.167_488_67
+    } else {
+        3.081_758_0 * x3 - 5.873_386_70 * x2 + 3.751_129_97 * x - 0.370_014_83
+    };
+    (x, y)
+}
+
+/// The CIE daylight locus
+fn daylight_xy(t: f64) -> (f64, f64) {
+    let (t2, t3) = (t * t, t * t * t);
+    let x = if t <= 7000.0 {
+        -4.6070e9 / t3 + 2.9678e6 / t2 + 0.09911e3 / t + 0.244_063
+    } else {
+        -2.0064e9 / t3 + 1.9018e6 / t2 + 0.24748e3 / t + 0.237_040
+    };
+    let y = -3.0 * x * x + 2.870 * x - 0.275;
+    (x, y)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::white_point::{WhitePoint, A, D50, D65, D75};
+    use approx::*;
+
+    #[test]
+    fn test_daylight() {
+        assert_relative_eq!(
+            cct_chromaticity(6504.0).unwrap(),
+            D65.get_xy_chromaticity(),
+            epsilon = 2e-4
+        );
+        assert_relative_eq!(
+            cct_chromaticity(5003.0).unwrap(),
+            D50.get_xy_chromaticity(),
+            epsilon = 2e-4
+        );
+        assert_relative_eq!(
+            cct_chromaticity(7504.0).unwrap(),
+            D75.get_xy_chromaticity(),
+            epsilon = 2e-4
+        );
+        assert_relative_eq!(from_cct(6504.0).unwrap(), D65.get_xyz(), epsilon = 1e-3);
+    }
+
+    #[test]
+    fn test_planckian() {
+        assert_relative_eq!(
+            cct_chromaticity(2856.0).unwrap(),
+            A.get_xy_chromaticity(),
+            epsilon = 1e-3
+        );
+        let warm = cct_chromaticity(2000.0f32).unwrap();
+        assert_relative_eq!(warm, XyY::new(0.5267, 0.4133, 1.0), epsilon = 1e-3);
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        assert_eq!(from_cct(1000.0f64), None);
+        assert_eq!(from_cct(30000.0f64), None);
+        assert!(from_cct(1667.0f64).is_some());
+        assert!(from_cct(25000.0f64).is_some());
+    }
+}
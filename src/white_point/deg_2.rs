@@ -525,3 +525,29 @@ where
     }
 }
 impl<T> UnitWhitePoint<T> for F12 where T: Float + FreeChannelScalar + PosNormalChannelScalar {}
+
+/// The Academy Color Encoding System white point, close to but not exactly on CIE D60.
+#[derive(Clone, Debug, PartialEq, Eq, Default, Copy)]
+pub struct Aces;
+impl<T> WhitePoint<T> for Aces
+where
+    T: Float + FreeChannelScalar + PosNormalChannelScalar,
+{
+    #[inline]
+    fn get_xyz(&self) -> Xyz<T> {
+        Xyz::new(
+            cast(0.9526460746).unwrap(),
+            cast(1.0).unwrap(),
+            cast(1.0088251844).unwrap(),
+        )
+    }
+    #[inline]
+    fn get_xy_chromaticity(&self) -> XyY<T> {
+        XyY::new(
+            cast(0.32168).unwrap(),
+            cast(0.33767).unwrap(),
+            cast(1.0).unwrap(),
+        )
+    }
+}
+impl<T> UnitWhitePoint<T> for Aces where T: Float + FreeChannelScalar + PosNormalChannelScalar {}
@@ -11,10 +11,18 @@
 //! needs to be considered. The 2 degree observer white points are re-exported. Note that the 2 and 10
 //! degree standard observers use fundamentally different color-matching functions, which means that
 //! they yield different XYZ spaces. It is not valid (without spectrographic data) to convert between
-//! a 2 degree standard observer XYZ space and a 10 degree standard observer XYZ space.
+//! a 2 degree standard observer XYZ space and a 10 degree standard observer XYZ space. The
+//! color matching functions of both observers are available as
+//! [`spectrum::Cie1931`](../spectrum/struct.Cie1931.html) and
+//! [`spectrum::Cie1964`](../spectrum/struct.Cie1964.html).
 //!
 //! The standard illuminants are slightly different between the two, so prisma provides two modules
 //! containing them `deg_2` and `deg_10`. If you don't know which to use, use `deg_2`.
+//!
+//! White points for arbitrary light sources can be computed from a correlated color temperature
+//! with [`from_cct`](fn.from_cct.html).
+use crate::channel::{FreeChannelScalar, PosNormalChannelScalar};
+use crate::convert::FromColor;
 use crate::xyy::XyY;
 use crate::xyz::Xyz;
 
@@ -41,7 +49,79 @@ where
     }
 }
 
+/// An `Xyz` value can be used directly as a white point
+///
+/// This allows white points that aren't one of the named illuminants, such as the white point
+/// of a color space or a measured white, to be used wherever a `WhitePoint` is expected.
+impl<T> WhitePoint<T> for Xyz<T>
+where
+    T: FreeChannelScalar + PosNormalChannelScalar + num_traits::Float,
+{
+    fn get_xyz(&self) -> Xyz<T> {
+        self.clone()
+    }
+    fn get_xy_chromaticity(&self) -> XyY<T> {
+        XyY::from_color(self)
+    }
+}
+
+mod cct;
 pub mod deg_10;
 pub mod deg_2;
 
+pub use self::cct::{cct_chromaticity, from_cct};
+
 pub use self::deg_2::*;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use approx::*;
+
+    // The XYZ values are integrated from tabulated spectra, while the chromaticities are the
+    // published CIE values, so the two only agree to about 3 decimal places for some illuminants.
+    fn check_consistent<W: WhitePoint<f64>>(wp: W) {
+        let xyz = wp.get_xyz();
+        let xy = wp.get_xy_chromaticity();
+        assert_relative_eq!(xyz.y(), 1.0);
+        assert_relative_eq!(XyY::from_color(&xyz), xy, epsilon = 5e-4);
+    }
+
+    #[test]
+    fn test_deg_2_consistency() {
+        check_consistent(deg_2::A);
+        check_consistent(deg_2::B);
+        check_consistent(deg_2::C);
+        check_consistent(deg_2::D50);
+        check_consistent(deg_2::D55);
+        check_consistent(deg_2::D65);
+        check_consistent(deg_2::D75);
+        check_consistent(deg_2::E);
+        check_consistent(deg_2::F2);
+        check_consistent(deg_2::F7);
+        check_consistent(deg_2::F11);
+        check_consistent(deg_2::Aces);
+    }
+
+    #[test]
+    fn test_deg_10_consistency() {
+        check_consistent(deg_10::A);
+        check_consistent(deg_10::B);
+        check_consistent(deg_10::C);
+        check_consistent(deg_10::D50);
+        check_consistent(deg_10::D55);
+        check_consistent(deg_10::D65);
+        check_consistent(deg_10::D75);
+        check_consistent(deg_10::E);
+        check_consistent(deg_10::F2);
+        check_consistent(deg_10::F7);
+        check_consistent(deg_10::F11);
+    }
+
+    #[test]
+    fn test_xyz_white_point() {
+        let white = Xyz::new(0.9642, 1.0, 0.8251);
+        assert_eq!(white.get_xyz(), white);
+        check_consistent(white);
+    }
+}
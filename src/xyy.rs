@@ -5,7 +5,10 @@ use crate::channel::{
     ChannelCast, ChannelFormatCast, ColorChannel, FreeChannel, FreeChannelScalar,
     PosNormalBoundedChannel, PosNormalChannelScalar,
 };
-use crate::color::{Bounded, Broadcast, Color, Flatten, FromTuple, HomogeneousColor, Lerp};
+use crate::color::{
+    Bounded, Broadcast, ChannelAccess, ChannelOrder, Color, Flatten, FromTuple, HomogeneousColor,
+    Lerp,
+};
 use crate::convert::FromColor;
 use crate::tags::XyYTag;
 use crate::xyz::Xyz;
@@ -219,6 +222,20 @@ where
     impl_color_homogeneous_color_square!(XyY<T> {x, y, Y});
 }
 
+impl<T> ChannelOrder for XyY<T>
+where
+    T: FreeChannelScalar + PosNormalChannelScalar + num_traits::Float,
+{
+    impl_color_channel_order!({x, y, Y});
+}
+
+impl<T> ChannelAccess for XyY<T>
+where
+    T: FreeChannelScalar + PosNormalChannelScalar + num_traits::Float,
+{
+    impl_color_channel_access_square!(T {x, y, Y});
+}
+
 impl<T> Broadcast for XyY<T>
 where
     T: FreeChannelScalar + PosNormalChannelScalar + num_traits::Float,
@@ -3,7 +3,10 @@
 use crate::channel::{
     ChannelCast, ChannelFormatCast, ColorChannel, FreeChannelScalar, PosFreeChannel,
 };
-use crate::color::{Bounded, Broadcast, Color, Flatten, FromTuple, HomogeneousColor, Lerp};
+use crate::color::{
+    Bounded, Broadcast, ChannelAccess, ChannelOrder, Color, Flatten, FromTuple, HomogeneousColor,
+    Lerp,
+};
 use crate::tags::XyzTag;
 #[cfg(feature = "approx")]
 use approx;
@@ -39,6 +42,10 @@ use std::slice;
 /// a simple shape. Many combinations of XYZ will correspond to no representable color and are therefore
 /// "imaginary" to humans.
 ///
+/// `Xyz` implements element-wise `+`, `-`, `*` and `/`, as do the other free channel colors
+/// `Lab`, `Luv`, `Lms` and `Oklab`. Since `Xyz` and `Lms` are linear in light, they can also be
+/// multiplied and divided by a scalar.
+///
 /// ## Standard Observer
 ///
 /// XYZ is actually a family of spaces, each constructed from a set of color matching functions. Currently
@@ -147,6 +154,23 @@ where
     impl_color_homogeneous_color_square!(Xyz<T> {x, y, z});
 }
 
+impl<T> ChannelOrder for Xyz<T>
+where
+    T: FreeChannelScalar,
+{
+    impl_color_channel_order!({x, y, z});
+}
+
+impl<T> ChannelAccess for Xyz<T>
+where
+    T: FreeChannelScalar,
+{
+    impl_color_channel_access_square!(T {x, y, z});
+}
+
+impl_color_elementwise_ops!(Xyz<T> {x, y, z} where { T: FreeChannelScalar });
+impl_color_scalar_ops!(Xyz<T> {x, y, z} where { T: FreeChannelScalar });
+
 impl<T> Broadcast for Xyz<T>
 where
     T: FreeChannelScalar,
@@ -286,4 +310,37 @@ mod test {
         assert_relative_eq!(c1.color_cast(), Xyz::new(0.5f32, 1.0, 0.8));
     }
 
+    #[test]
+    fn test_ops() {
+        use crate::lab::Lab;
+        use crate::white_point::D65;
+
+        let c1 = Xyz::new(0.5, 1.0, 0.25);
+        let c2 = Xyz::new(0.25, 0.5, 1.0);
+        assert_relative_eq!(c1 + c2, Xyz::new(0.75, 1.5, 1.25));
+        assert_relative_eq!(c1 - c2, Xyz::new(0.25, 0.5, -0.75));
+        assert_relative_eq!(c1 * c2, Xyz::new(0.125, 0.5, 0.25));
+        assert_relative_eq!(c1 / c2, Xyz::new(2.0, 2.0, 0.25));
+
+        let mut sum = Xyz::new(0.0, 0.0, 0.0);
+        for color in [c1, c2, c1].iter() {
+            sum += *color;
+        }
+        sum /= Xyz::new(3.0, 3.0, 3.0);
+        assert_relative_eq!(sum, Xyz::new(1.25 / 3.0, 2.5 / 3.0, 0.5));
+
+        let c3 = Lab::<f64, D65>::new(50.0, 20.0, -10.0);
+        let c4 = Lab::new(30.0, -5.0, 5.0);
+        assert_relative_eq!(c3 - c4, Lab::new(20.0, 25.0, -15.0));
+        assert_relative_eq!(c1 * 2.0, Xyz::new(1.0, 2.0, 0.5));
+        assert_relative_eq!(c1 / 4.0, Xyz::new(0.125, 0.25, 0.0625));
+        let mut c6 = c2;
+        c6 *= 0.5;
+        c6 += c1 * 0.5;
+        assert_relative_eq!(c6, Xyz::new(0.375, 0.75, 0.625));
+
+        let mut c5 = c3;
+        c5 *= Lab::new(0.5, 0.5, 0.5);
+        assert_relative_eq!(c5, Lab::new(25.0, 10.0, -5.0));
+    }
 }
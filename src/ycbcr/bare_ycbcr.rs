@@ -4,7 +4,10 @@ use crate::channel::{
     ChannelCast, ChannelFormatCast, ColorChannel, NormalBoundedChannel, NormalChannelScalar,
     PosNormalBoundedChannel, PosNormalChannelScalar,
 };
-use crate::color::{Bounded, Broadcast, Color, Flatten, FromTuple, HomogeneousColor, Invert, Lerp};
+use crate::color::{
+    Bounded, Broadcast, ChannelAccess, ChannelOrder, Color, Flatten, FromTuple, HomogeneousColor,
+    Invert, Lerp,
+};
 use crate::encoding::EncodableColor;
 #[cfg(feature = "approx")]
 use approx;
@@ -183,6 +186,20 @@ where
     impl_color_homogeneous_color_square!(BareYCbCr<T> {luma, cb, cr});
 }
 
+impl<T> ChannelOrder for BareYCbCr<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar,
+{
+    impl_color_channel_order!({luma, cb, cr});
+}
+
+impl<T> ChannelAccess for BareYCbCr<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar,
+{
+    impl_color_channel_access_square!(T {luma, cb, cr});
+}
+
 impl<T> Broadcast for BareYCbCr<T>
 where
     T: PosNormalChannelScalar + NormalChannelScalar,
@@ -261,12 +278,12 @@ where
     /// `model` is only used within the conversion, it is up to the user
     /// to remember which model any `BareYCbCr` is using.
     pub fn from_rgb_and_model<M: YCbCrModel<T>>(from: &Rgb<T>, model: &M) -> Self {
-        let transform = model.forward_transform();
-        let shift = model.shift();
+        let transform = model.forward_affine_transform();
 
-        let (y, cb, cr) = transform.transform_vector(from.clone().to_tuple());
+        let (r, g, b) = from.clone().to_tuple();
+        let (y, cb, cr) = transform * (to_f64(r), to_f64(g), to_f64(b));
 
-        BareYCbCr::new(y + shift.0, cb + shift.1, cr + shift.2)
+        BareYCbCr::new(from_f64(y), from_f64(cb), from_f64(cr))
     }
 
     /// Convert from YCbCr to Rgb.
@@ -283,23 +300,12 @@ where
         model: &M,
         out_of_gamut_mode: YCbCrOutOfGamutMode,
     ) -> Rgb<T> {
-        let transform = model.inverse_transform();
-        let shift = model.shift();
+        let transform = model.inverse_affine_transform();
 
         let (i1, i2, i3) = self.clone().to_tuple();
-        let shifted_color = (
-            num_traits::cast::<_, f64>(i1).unwrap() - num_traits::cast::<_, f64>(shift.0).unwrap(),
-            num_traits::cast::<_, f64>(i2).unwrap() - num_traits::cast::<_, f64>(shift.1).unwrap(),
-            num_traits::cast::<_, f64>(i3).unwrap() - num_traits::cast::<_, f64>(shift.2).unwrap(),
-        );
-
-        let (r, g, b) = transform.transform_vector(shifted_color);
+        let (r, g, b) = transform * (to_f64(i1), to_f64(i2), to_f64(i3));
 
-        let out = Rgb::new(
-            num_traits::cast(r).unwrap(),
-            num_traits::cast(g).unwrap(),
-            num_traits::cast(b).unwrap(),
-        );
+        let out = Rgb::new(from_f64(r), from_f64(g), from_f64(b));
 
         match out_of_gamut_mode {
             YCbCrOutOfGamutMode::Preserve => out,
@@ -307,3 +313,24 @@ where
         }
     }
 }
+
+fn to_f64<T: num_traits::NumCast>(value: T) -> f64 {
+    num_traits::cast(value).unwrap()
+}
+
+/// Convert a channel computed in `f64` back to `T`.
+///
+/// Integer channels are rounded to the nearest value and saturate at the ends of their range, so
+/// that negative chroma or out of gamut Rgb values do not fail to convert. Floating point channels
+/// are returned unchanged.
+fn from_f64<T>(value: f64) -> T
+where
+    T: PosNormalChannelScalar + num_traits::NumCast,
+{
+    let max = to_f64(<T as PosNormalChannelScalar>::max_bound());
+    if max > 1.0 {
+        num_traits::cast(value.round().max(0.0).min(max)).unwrap()
+    } else {
+        num_traits::cast(value).unwrap()
+    }
+}
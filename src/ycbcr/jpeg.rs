@@ -0,0 +1,131 @@
+//! Exact integer conversions between `Rgb<u8>` and 8-bit JPEG YCbCr.
+
+use crate::rgb::Rgb;
+use crate::ycbcr::YCbCrJpeg;
+
+const SCALE_BITS: u32 = 16;
+const ONE_HALF: i32 = 1 << (SCALE_BITS - 1);
+const CBCR_OFFSET: i32 = 128 << SCALE_BITS;
+
+/// Convert a constant to the 16.16 fixed point format used by libjpeg
+const fn fix(val: f64) -> i32 {
+    (val * (1 << SCALE_BITS) as f64 + 0.5) as i32
+}
+
+const Y_R: i32 = fix(0.29900);
+const Y_G: i32 = fix(0.58700);
+const Y_B: i32 = fix(0.11400);
+const CB_R: i32 = fix(0.16874);
+const CB_G: i32 = fix(0.33126);
+const CR_G: i32 = fix(0.41869);
+const CR_B: i32 = fix(0.08131);
+const HALF: i32 = fix(0.5);
+
+const R_CR: i32 = fix(1.40200);
+const B_CB: i32 = fix(1.77200);
+const G_CB: i32 = fix(0.34414);
+const G_CR: i32 = fix(0.71414);
+
+/// Convert an sRGB `Rgb<u8>` to JPEG YCbCr exactly as libjpeg does
+///
+/// JPEG stores YCbCr with the BT.601 weights in the full range, with neutral chroma at 128. The
+/// conversion uses libjpeg's 16-bit fixed point arithmetic, so the result matches the values
+/// written by libjpeg and its derivatives bit for bit.
+pub fn rgb_to_jpeg_ycbcr(rgb: &Rgb<u8>) -> YCbCrJpeg<u8> {
+    let r = i32::from(rgb.red());
+    let g = i32::from(rgb.green());
+    let b = i32::from(rgb.blue());
+
+    let y = (Y_R * r + Y_G * g + Y_B * b + ONE_HALF) >> SCALE_BITS;
+    let cb = (-CB_R * r - CB_G * g + HALF * b + CBCR_OFFSET + ONE_HALF - 1) >> SCALE_BITS;
+    let cr = (HALF * r - CR_G * g - CR_B * b + CBCR_OFFSET + ONE_HALF - 1) >> SCALE_BITS;
+
+    YCbCrJpeg::new(y as u8, cb as u8, cr as u8)
+}
+
+/// Convert JPEG YCbCr to an sRGB `Rgb<u8>` exactly as libjpeg does
+///
+/// This is the inverse of [`rgb_to_jpeg_ycbcr`](fn.rgb_to_jpeg_ycbcr.html), using libjpeg's
+/// fixed point arithmetic and clamping each channel to `[0, 255]`. Like libjpeg, converting an
+/// `Rgb<u8>` to YCbCr and back can change each channel by one.
+pub fn jpeg_ycbcr_to_rgb(ycbcr: &YCbCrJpeg<u8>) -> Rgb<u8> {
+    let y = i32::from(ycbcr.luma());
+    let cb = i32::from(ycbcr.cb()) - 128;
+    let cr = i32::from(ycbcr.cr()) - 128;
+
+    let r = y + ((R_CR * cr + ONE_HALF) >> SCALE_BITS);
+    let g = y + ((-G_CB * cb + ONE_HALF - G_CR * cr) >> SCALE_BITS);
+    let b = y + ((B_CB * cb + ONE_HALF) >> SCALE_BITS);
+
+    Rgb::new(
+        r.clamp(0, 255) as u8,
+        g.clamp(0, 255) as u8,
+        b.clamp(0, 255) as u8,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ycbcr::YCbCrOutOfGamutMode;
+
+    #[test]
+    fn test_known_values() {
+        let cases = [
+            ((0, 0, 0), (0, 128, 128)),
+            ((255, 255, 255), (255, 128, 128)),
+            ((255, 0, 0), (76, 85, 255)),
+            ((0, 255, 0), (150, 44, 21)),
+            ((0, 0, 255), (29, 255, 107)),
+            ((128, 64, 32), (79, 101, 163)),
+        ];
+        for &((r, g, b), (y, cb, cr)) in cases.iter() {
+            let ycbcr = rgb_to_jpeg_ycbcr(&Rgb::new(r, g, b));
+            assert_eq!(ycbcr, YCbCrJpeg::new(y, cb, cr));
+        }
+        assert_eq!(
+            jpeg_ycbcr_to_rgb(&YCbCrJpeg::new(76, 85, 255)),
+            Rgb::new(254, 0, 0)
+        );
+        assert_eq!(
+            jpeg_ycbcr_to_rgb(&YCbCrJpeg::new(0, 255, 255)),
+            Rgb::new(178, 0, 225)
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for r in (0..=255u8).step_by(5) {
+            for g in (0..=255u8).step_by(5) {
+                for b in (0..=255u8).step_by(5) {
+                    let rgb = Rgb::new(r, g, b);
+                    let back = jpeg_ycbcr_to_rgb(&rgb_to_jpeg_ycbcr(&rgb));
+                    assert!((i32::from(back.red()) - i32::from(r)).abs() <= 1);
+                    assert!((i32::from(back.green()) - i32::from(g)).abs() <= 1);
+                    assert!((i32::from(back.blue()) - i32::from(b)).abs() <= 1);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_matches_float() {
+        let rgb = Rgb::new(200u8, 120, 40);
+        let exact = rgb_to_jpeg_ycbcr(&rgb);
+        let float = YCbCrJpeg::from_rgb(&rgb.color_cast::<f64>());
+        assert!((f64::from(exact.luma()) - float.luma() * 255.0).abs() <= 0.5);
+        assert!((f64::from(exact.cb()) - 128.0 - float.cb() * 255.0).abs() <= 0.5);
+        assert!((f64::from(exact.cr()) - 128.0 - float.cr() * 255.0).abs() <= 0.5);
+
+        let decoded = YCbCrJpeg::new(
+            f64::from(exact.luma()) / 255.0,
+            (f64::from(exact.cb()) - 128.0) / 255.0,
+            (f64::from(exact.cr()) - 128.0) / 255.0,
+        )
+        .to_rgb(YCbCrOutOfGamutMode::Clip);
+        let back = jpeg_ycbcr_to_rgb(&exact);
+        assert!((f64::from(back.red()) - decoded.red() * 255.0).abs() <= 1.0);
+        assert!((f64::from(back.green()) - decoded.green() * 255.0).abs() <= 1.0);
+        assert!((f64::from(back.blue()) - decoded.blue() * 255.0).abs() <= 1.0);
+    }
+}
@@ -28,14 +28,65 @@
 //! set of colors. It is represented in this library by the type
 //! `type Yiq<T> = YCbCr<T, YiqModel>`, but provides some convenience methods to mask
 //! the fact that it shares an implementation with YCbCr.
+//!
+//! Video standards each define their own luma weights, and decoding with the wrong set shifts
+//! every hue. `Bt601Model` is used for standard definition video, `Bt709Model` for high
+//! definition and `Bt2020Model` for ultra high definition, in its non-constant luminance form.
+//! When the standard is only known at runtime, such as from the metadata of a video stream,
+//! [`YCbCrStandard`](enum.YCbCrStandard.html) selects between them. For any other RGB space,
+//! [`luma_coefficients`](fn.luma_coefficients.html) computes the weights from its primaries, and
+//! `CustomYCbCrModel::from_color_space` builds a model from them.
+//!
+//! Digital video stores each channel as an integer code value. Broadcast video uses the limited,
+//! or "studio swing", range, where 8-bit luma spans 16 to 235 and chroma 16 to 240, while JPEG and
+//! most computer graphics use the full range. [`YCbCr::quantize`](struct.YCbCr.html#method.quantize)
+//! produces a [`QuantizedYCbCr`](struct.QuantizedYCbCr.html) holding 8-bit, 10-bit, 12-bit or
+//! 16-bit code values, with the range, [`FullRange`](struct.FullRange.html) or
+//! [`LimitedRange`](struct.LimitedRange.html), as part of its type.
+//!
+//! Conversions between `Rgb` and `YCbCr` never clamp unless asked to, so RGB values below zero or
+//! above one, such as colors from a wider gamut, survive the round trip. This is the basis of
+//! xvYCC, which stores such colors in the footroom and headroom of the limited range with
+//! [`XvYccRange`](struct.XvYccRange.html).
+//!
+//! JPEG images store BT.601 YCbCr in the full range as 8-bit values, which is `YCbCrJpeg<u8>`.
+//! [`rgb_to_jpeg_ycbcr`](fn.rgb_to_jpeg_ycbcr.html) and
+//! [`jpeg_ycbcr_to_rgb`](fn.jpeg_ycbcr_to_rgb.html) convert them with libjpeg's fixed point
+//! arithmetic, giving the same values as JPEG encoders and decoders built on it.
+//!
+//! Video codecs store YCbCr as separate planes, usually with the chroma planes at a lower
+//! resolution than luma. [`YCbCrPlanes`](struct.YCbCrPlanes.html) splits an image of `YCbCr`
+//! colors into 4:4:4, 4:2:2 or 4:2:0 planes and merges them back, with a choice of
+//! [`ChromaSiting`](enum.ChromaSiting.html) and [`ChromaFilter`](enum.ChromaFilter.html).
+//! Floating point planes are quantized to code values with `YCbCrPlanes::quantize`, and
+//! `YCbCrPlanes::to_le_bytes` lays them out as the byte planes of an 8-bit or 10-bit video frame.
+//! [`quantize_luma`](fn.quantize_luma.html) and [`quantize_chroma`](fn.quantize_chroma.html) do the
+//! same for single samples.
+//!
+//! Converting a `YCbCr` value back to `Rgb` only recovers the encoded device values. To reach XYZ,
+//! pair the result with the color space the video was mastered in, such as
+//! [`Rec601_625`](../color_space/named/struct.Rec601_625.html) and
+//! [`Rec601_525`](../color_space/named/struct.Rec601_525.html) for standard definition content.
 
 mod bare_ycbcr;
+mod jpeg;
 mod model;
+mod planar;
+mod range;
 mod ycbcr;
 
 pub use self::bare_ycbcr::{BareYCbCr, YCbCrOutOfGamutMode};
+pub use self::jpeg::{jpeg_ycbcr_to_rgb, rgb_to_jpeg_ycbcr};
 pub use self::model::{
-    build_transform, Bt709Model, Canonicalize, CustomYCbCrModel, JpegModel, StandardShift,
-    UnitModel, YCbCrModel, YCbCrShift, YCbCrTransform, YiqModel,
+    build_transform, luma_coefficients, Bt2020Model, Bt601Model, Bt709Model, Canonicalize,
+    CustomYCbCrModel, JpegModel, StandardShift, UnitModel, YCbCrModel, YCbCrShift, YCbCrStandard,
+    YCbCrTransform, YiqModel,
+};
+pub use self::planar::{ChromaFilter, ChromaSiting, PlaneScalar, Subsampling, YCbCrPlanes};
+pub use self::range::{
+    dequantize_chroma, dequantize_luma, quantize_chroma, quantize_luma, CodeValue, FullRange,
+    LimitedRange, QuantizedYCbCr, XvYccRange, YCbCrRange,
+};
+pub use self::ycbcr::{
+    YCbCr, YCbCrBt2020, YCbCrBt601, YCbCrBt709, YCbCrCustom, YCbCrJpeg, YCbCrStandardized, Yiq,
 };
-pub use self::ycbcr::{YCbCr, YCbCrBt709, YCbCrCustom, YCbCrJpeg, Yiq};
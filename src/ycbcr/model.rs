@@ -5,8 +5,9 @@
 //! the most common standard models as well as a type for the creation of
 //! custom YCbCr models.
 
-use crate::channel::{NormalChannelScalar, PosNormalChannelScalar};
-use crate::linalg::Matrix3;
+use crate::channel::{NormalChannelScalar, PosNormalChannelScalar, U10, U12};
+use crate::color_space::ColorSpace;
+use crate::linalg::{Matrix3, Matrix3x4};
 use crate::ycbcr::YCbCr;
 use num_traits;
 
@@ -35,6 +36,30 @@ pub trait YCbCrModel<T>: Clone + PartialEq + YCbCrTransform {
     type Shift: YCbCrShift<T>;
     /// Return a shift to be added to each channel after conversion.
     fn shift(&self) -> (T, T, T);
+
+    /// Return the transformation from Rgb to YCbCr, followed by the shift.
+    fn forward_affine_transform(&self) -> Matrix3x4<f64>
+    where
+        T: num_traits::NumCast,
+    {
+        Matrix3x4::new(self.forward_transform(), shift_to_f64(self.shift()))
+    }
+    /// Return the removal of the shift, followed by the transformation from YCbCr to Rgb.
+    fn inverse_affine_transform(&self) -> Matrix3x4<f64>
+    where
+        T: num_traits::NumCast,
+    {
+        let (s1, s2, s3) = shift_to_f64(self.shift());
+        self.inverse_transform() * Matrix3x4::translation((-s1, -s2, -s3))
+    }
+}
+
+fn shift_to_f64<T: num_traits::NumCast>(shift: (T, T, T)) -> (f64, f64, f64) {
+    (
+        num_traits::cast(shift.0).unwrap(),
+        num_traits::cast(shift.1).unwrap(),
+        num_traits::cast(shift.2).unwrap(),
+    )
 }
 
 /// A YCbCrModel that can transform a color in its space to the "canonical representation".
@@ -82,15 +107,37 @@ pub struct CustomYCbCrModel {
 pub struct StandardShift<T>(pub T);
 
 /// A model for the YIQ color space.
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct YiqModel;
+/// A model for YUV using the BT.601 standard, used by standard definition video.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bt601Model;
 /// A model for YUV using the BT.709 standard.
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Bt709Model;
+/// A model for YUV using the non-constant luminance form of the BT.2020 standard, used by
+/// ultra high definition video.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bt2020Model;
 /// A model for YUV used by Jpeg images.
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct JpegModel;
 
+/// A standard YUV model chosen at runtime.
+///
+/// Each variant converts exactly as the matching unit model does. This is useful when the
+/// standard is only known once a video stream has been opened, at the cost of storing the
+/// variant in each color.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum YCbCrStandard {
+    /// BT.601, the same as `Bt601Model`
+    Bt601,
+    /// BT.709, the same as `Bt709Model`
+    Bt709,
+    /// BT.2020 non-constant luminance, the same as `Bt2020Model`
+    Bt2020,
+}
+
 /// Build a transformation matrix for conversion
 /// from Rgb to a YCbCr space
 /// with a specified set of weight values.
@@ -139,6 +186,23 @@ where
     Matrix3::new([kr, kg, kb, cb_r, cb_g, cb_b, cr_r, cr_g, cr_b])
 }
 
+/// Compute the red and blue weights, `Wr` and `Wb`, of luma for RGB in `space`.
+///
+/// Each weight is the relative luminance of the primary, found from the primaries and white
+/// point of `space`. The result can be passed to `build_transform` to get the YCbCr transform
+/// for any color space, including those without a published standard.
+pub fn luma_coefficients<T, S>(space: &S) -> (f64, f64)
+where
+    T: num_traits::Float,
+    S: ColorSpace<T>,
+{
+    let transform = space.get_xyz_transform();
+    let luminance = &transform.as_slice()[3..6];
+    let cast = |v: T| num_traits::cast::<_, f64>(v).unwrap();
+    let total = cast(luminance[0]) + cast(luminance[1]) + cast(luminance[2]);
+    (cast(luminance[0]) / total, cast(luminance[2]) / total)
+}
+
 impl CustomYCbCrModel {
     /// Construct a model from the forward and inverse transformations.
     ///
@@ -160,10 +224,22 @@ impl CustomYCbCrModel {
         let transform = build_transform(kr, kb);
         let inv_transform = transform
             .clone()
-            .inverse()
+            .try_inverse()
             .expect("Singular YCbCr transformation matrix");
         CustomYCbCrModel::new(transform, inv_transform)
     }
+
+    /// Build a custom model with the luma weights of the color space `space`.
+    ///
+    /// See `luma_coefficients` for how the weights are computed.
+    pub fn from_color_space<T, S>(space: &S) -> Self
+    where
+        T: num_traits::Float,
+        S: ColorSpace<T>,
+    {
+        let (kr, kb) = luma_coefficients(space);
+        CustomYCbCrModel::build_from_coefficients(kr, kb)
+    }
 }
 
 impl YCbCrTransform for CustomYCbCrModel {
@@ -234,6 +310,67 @@ where
     }
 }
 
+impl YCbCrTransform for Bt601Model {
+    fn forward_transform(&self) -> Matrix3<f64> {
+        Matrix3::new([
+            0.299,
+            0.587,
+            0.114,
+            -0.16873589164785552,
+            -0.3312641083521445,
+            0.5,
+            0.5,
+            -0.41868758915834525,
+            -0.08131241084165478,
+        ])
+    }
+    fn inverse_transform(&self) -> Matrix3<f64> {
+        Matrix3::new([
+            1.0,
+            0.0,
+            1.402,
+            1.0,
+            -0.34413628620102216,
+            -0.7141362862010221,
+            1.0,
+            1.772,
+            0.0,
+        ])
+    }
+}
+impl<T> YCbCrModel<T> for Bt601Model
+where
+    T: PosNormalChannelScalar + NormalChannelScalar,
+    StandardShift<T>: YCbCrShift<T>,
+{
+    type Shift = StandardShift<T>;
+    fn shift(&self) -> (T, T, T) {
+        Self::Shift::get_shift()
+    }
+}
+impl<T> UnitModel<T> for Bt601Model
+where
+    T: PosNormalChannelScalar + NormalChannelScalar,
+    StandardShift<T>: YCbCrShift<T>,
+{
+    fn unit_value() -> Self {
+        Bt601Model
+    }
+}
+impl<T> Canonicalize<T> for Bt601Model
+where
+    T: PosNormalChannelScalar + NormalChannelScalar + num_traits::NumCast,
+    StandardShift<T>: YCbCrShift<T>,
+{
+    fn to_canonical_representation(from: &YCbCr<T, Self>) -> (T, T, T) {
+        (
+            from.luma(),
+            from.cb() * num_traits::cast(0.436).unwrap(),
+            from.cr() * num_traits::cast(0.615).unwrap(),
+        )
+    }
+}
+
 impl YCbCrTransform for Bt709Model {
     fn forward_transform(&self) -> Matrix3<f64> {
         Matrix3::new([
@@ -295,6 +432,120 @@ where
     }
 }
 
+impl YCbCrTransform for Bt2020Model {
+    fn forward_transform(&self) -> Matrix3<f64> {
+        Matrix3::new([
+            0.2627,
+            0.678,
+            0.0593,
+            -0.13963006271925163,
+            -0.3603699372807484,
+            0.5,
+            0.5,
+            -0.45978570459785706,
+            -0.04021429540214295,
+        ])
+    }
+    fn inverse_transform(&self) -> Matrix3<f64> {
+        Matrix3::new([
+            1.0,
+            0.0,
+            1.4746,
+            1.0,
+            -0.16455312684365778,
+            -0.5713531268436578,
+            1.0,
+            1.8814,
+            0.0,
+        ])
+    }
+}
+impl<T> YCbCrModel<T> for Bt2020Model
+where
+    T: PosNormalChannelScalar + NormalChannelScalar,
+    StandardShift<T>: YCbCrShift<T>,
+{
+    type Shift = StandardShift<T>;
+    fn shift(&self) -> (T, T, T) {
+        Self::Shift::get_shift()
+    }
+}
+impl<T> UnitModel<T> for Bt2020Model
+where
+    T: PosNormalChannelScalar + NormalChannelScalar,
+    StandardShift<T>: YCbCrShift<T>,
+{
+    fn unit_value() -> Self {
+        Bt2020Model
+    }
+}
+impl<T> Canonicalize<T> for Bt2020Model
+where
+    T: PosNormalChannelScalar + NormalChannelScalar + num_traits::NumCast,
+    StandardShift<T>: YCbCrShift<T>,
+{
+    fn to_canonical_representation(from: &YCbCr<T, Self>) -> (T, T, T) {
+        (
+            from.luma(),
+            from.cb() * num_traits::cast(0.436).unwrap(),
+            from.cr() * num_traits::cast(0.615).unwrap(),
+        )
+    }
+}
+
+impl YCbCrStandard {
+    /// Returns the red and blue luma weights, `(kr, kb)`, defined by the standard.
+    ///
+    /// See `build_transform` for how these define the model.
+    pub fn coefficients(&self) -> (f64, f64) {
+        match *self {
+            YCbCrStandard::Bt601 => (0.299, 0.114),
+            YCbCrStandard::Bt709 => (0.2126, 0.0722),
+            YCbCrStandard::Bt2020 => (0.2627, 0.0593),
+        }
+    }
+}
+
+impl YCbCrTransform for YCbCrStandard {
+    fn forward_transform(&self) -> Matrix3<f64> {
+        match *self {
+            YCbCrStandard::Bt601 => Bt601Model.forward_transform(),
+            YCbCrStandard::Bt709 => Bt709Model.forward_transform(),
+            YCbCrStandard::Bt2020 => Bt2020Model.forward_transform(),
+        }
+    }
+    fn inverse_transform(&self) -> Matrix3<f64> {
+        match *self {
+            YCbCrStandard::Bt601 => Bt601Model.inverse_transform(),
+            YCbCrStandard::Bt709 => Bt709Model.inverse_transform(),
+            YCbCrStandard::Bt2020 => Bt2020Model.inverse_transform(),
+        }
+    }
+}
+impl<T> YCbCrModel<T> for YCbCrStandard
+where
+    T: PosNormalChannelScalar + NormalChannelScalar,
+    StandardShift<T>: YCbCrShift<T>,
+{
+    type Shift = StandardShift<T>;
+    fn shift(&self) -> (T, T, T) {
+        Self::Shift::get_shift()
+    }
+}
+impl<T> Canonicalize<T> for YCbCrStandard
+where
+    T: PosNormalChannelScalar + NormalChannelScalar + num_traits::NumCast,
+    StandardShift<T>: YCbCrShift<T>,
+{
+    fn to_canonical_representation(from: &YCbCr<T, Self>) -> (T, T, T) {
+        (
+            from.luma(),
+            from.cb() * num_traits::cast(0.436).unwrap(),
+            from.cr() * num_traits::cast(0.615).unwrap(),
+        )
+    }
+}
+
 impl YCbCrTransform for YiqModel {
     fn forward_transform(&self) -> Matrix3<f64> {
         Matrix3::new([
@@ -416,3 +667,17 @@ impl_standard_shift_int!(u16);
 impl_standard_shift_int!(u32);
 impl_standard_shift_float!(f32);
 impl_standard_shift_float!(f64);
+
+macro_rules! impl_standard_shift_code_value {
+    ($T:ident) => {
+        impl YCbCrShift<$T> for StandardShift<$T> {
+            fn get_shift() -> ($T, $T, $T) {
+                let half = $T::new(($T::MAX.value() >> 1) + 1);
+                ($T::new(0), half, half)
+            }
+        }
+    };
+}
+
+impl_standard_shift_code_value!(U10);
+impl_standard_shift_code_value!(U12);
@@ -0,0 +1,694 @@
+//! Planar YCbCr layouts with subsampled chroma.
+
+use crate::channel::{NormalChannelScalar, PosNormalChannelScalar, U10, U12};
+use crate::ycbcr::model::YCbCrModel;
+use crate::ycbcr::range::{self, CodeValue, YCbCrRange};
+use crate::ycbcr::YCbCr;
+use num_traits;
+
+/// The ratio at which the chroma planes of a planar image are sampled.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Subsampling {
+    /// 4:4:4, with chroma at the full resolution of luma.
+    Chroma444,
+    /// 4:2:2, with chroma at half the horizontal resolution of luma.
+    Chroma422,
+    /// 4:2:0, with chroma at half the horizontal and vertical resolution of luma.
+    Chroma420,
+}
+
+/// Where chroma samples sit relative to the luma samples they cover.
+///
+/// Only matters for the dimensions that are subsampled.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ChromaSiting {
+    /// Centered between luma samples in both dimensions, as in JPEG and H.261.
+    Center,
+    /// Co-sited with the left luma sample and centered vertically, as in MPEG-2, H.264 and most
+    /// 4:2:0 video.
+    Left,
+    /// Co-sited with the top left luma sample, as in BT.2020 and BT.2100 4:2:0 video.
+    TopLeft,
+}
+
+/// The filter used to resample chroma between the luma and chroma resolutions.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ChromaFilter {
+    /// Average the luma sized block covered by each chroma sample when downsampling, and repeat
+    /// each chroma sample over its block when upsampling.
+    Box,
+    /// Weight samples linearly by their distance from the chroma sample's siting, in both
+    /// directions.
+    Bilinear,
+}
+
+/// A channel scalar that chroma planes can be resampled in.
+///
+/// Resampling is done in `f64`. Integer channels are rounded to the nearest value when converted
+/// back.
+pub trait PlaneScalar: Copy {
+    /// Convert the channel to `f64` without rescaling it.
+    fn to_f64(self) -> f64;
+    /// Convert from `f64` without rescaling, rounding and clamping integer values.
+    fn from_f64(value: f64) -> Self;
+}
+
+/// A YCbCr image split into separate luma, Cb and Cr planes.
+///
+/// The chroma planes may be subsampled, in which case each is `ceil(width / 2)` samples wide,
+/// and for 4:2:0 `ceil(height / 2)` samples tall. Every plane is stored row by row with no
+/// padding. The model is not stored, and must be given again when merging the planes back into
+/// `YCbCr` colors.
+///
+/// ## Examples:
+///
+/// ```rust
+/// # extern crate prisma;
+///
+/// use prisma::ycbcr::{ChromaFilter, ChromaSiting, Subsampling, YCbCrJpeg, YCbCrPlanes, JpegModel};
+///
+/// let image = vec![
+///     YCbCrJpeg::new(0.2, 0.1, -0.1), YCbCrJpeg::new(0.4, 0.3, 0.1),
+///     YCbCrJpeg::new(0.6, 0.1, -0.1), YCbCrJpeg::new(0.8, 0.3, 0.1f64),
+/// ];
+/// let planes = YCbCrPlanes::split(
+///     &image, 2, 2, Subsampling::Chroma420, ChromaSiting::Center, ChromaFilter::Box,
+/// );
+/// assert_eq!(planes.luma(), &[0.2, 0.4, 0.6, 0.8][..]);
+/// assert_eq!(planes.cb().len(), 1);
+///
+/// let merged = planes.merge(JpegModel, ChromaFilter::Box);
+/// assert_eq!(merged[3].luma(), 0.8);
+/// assert_eq!(merged[3].cr(), merged[0].cr());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct YCbCrPlanes<T> {
+    width: usize,
+    height: usize,
+    subsampling: Subsampling,
+    siting: ChromaSiting,
+    luma: Vec<T>,
+    cb: Vec<T>,
+    cr: Vec<T>,
+}
+
+impl Subsampling {
+    /// Return the horizontal and vertical factors that chroma resolution is divided by.
+    pub fn factors(&self) -> (usize, usize) {
+        match *self {
+            Subsampling::Chroma444 => (1, 1),
+            Subsampling::Chroma422 => (2, 1),
+            Subsampling::Chroma420 => (2, 2),
+        }
+    }
+
+    /// Return the dimensions of the chroma planes for an image of `width` by `height`.
+    pub fn chroma_dimensions(&self, width: usize, height: usize) -> (usize, usize) {
+        let (fx, fy) = self.factors();
+        (width.div_ceil(fx), height.div_ceil(fy))
+    }
+}
+
+impl ChromaSiting {
+    /// Return the position of the first chroma sample in luma samples, horizontally and
+    /// vertically, for the subsampling factors `factors`.
+    fn offsets(&self, factors: (usize, usize)) -> (f64, f64) {
+        let centered = |factor: usize| (factor as f64 - 1.0) / 2.0;
+        match *self {
+            ChromaSiting::Center => (centered(factors.0), centered(factors.1)),
+            ChromaSiting::Left => (0.0, centered(factors.1)),
+            ChromaSiting::TopLeft => (0.0, 0.0),
+        }
+    }
+}
+
+impl<T> YCbCrPlanes<T>
+where
+    T: NormalChannelScalar + PosNormalChannelScalar + PlaneScalar,
+{
+    /// Split `colors`, an image of `width` by `height` stored row by row, into planes.
+    ///
+    /// The chroma planes are downsampled to `subsampling` with `filter`, placing each chroma
+    /// sample at `siting`.
+    ///
+    /// # Panics
+    /// If `colors` does not have `width * height` elements.
+    pub fn split<M>(
+        colors: &[YCbCr<T, M>],
+        width: usize,
+        height: usize,
+        subsampling: Subsampling,
+        siting: ChromaSiting,
+        filter: ChromaFilter,
+    ) -> Self
+    where
+        M: YCbCrModel<T>,
+    {
+        assert_eq!(
+            colors.len(),
+            width * height,
+            "image must have width * height colors"
+        );
+        let luma = colors.iter().map(|c| c.luma()).collect();
+        let cb: Vec<f64> = colors.iter().map(|c| c.cb().to_f64()).collect();
+        let cr: Vec<f64> = colors.iter().map(|c| c.cr().to_f64()).collect();
+
+        let factors = subsampling.factors();
+        let offsets = siting.offsets(factors);
+        let (chroma_width, _) = subsampling.chroma_dimensions(width, height);
+        let downsample = |plane: &[f64]| -> Vec<T> {
+            let rows = resample_rows(plane, width, |line| {
+                downsample_line(line, factors.0, offsets.0, filter)
+            });
+            resample_columns(&rows, chroma_width, |line| {
+                downsample_line(line, factors.1, offsets.1, filter)
+            })
+            .into_iter()
+            .map(T::from_f64)
+            .collect()
+        };
+
+        YCbCrPlanes {
+            width,
+            height,
+            subsampling,
+            siting,
+            luma,
+            cb: downsample(&cb),
+            cr: downsample(&cr),
+        }
+    }
+
+    /// Construct from existing planes.
+    ///
+    /// # Panics
+    /// If `luma` does not have `width * height` elements, or if `cb` or `cr` do not have the
+    /// number of elements given by `subsampling.chroma_dimensions(width, height)`.
+    pub fn from_planes(
+        width: usize,
+        height: usize,
+        subsampling: Subsampling,
+        siting: ChromaSiting,
+        luma: Vec<T>,
+        cb: Vec<T>,
+        cr: Vec<T>,
+    ) -> Self {
+        let (chroma_width, chroma_height) = subsampling.chroma_dimensions(width, height);
+        assert_eq!(luma.len(), width * height, "luma plane has the wrong size");
+        assert_eq!(
+            cb.len(),
+            chroma_width * chroma_height,
+            "Cb plane has the wrong size"
+        );
+        assert_eq!(
+            cr.len(),
+            chroma_width * chroma_height,
+            "Cr plane has the wrong size"
+        );
+        YCbCrPlanes {
+            width,
+            height,
+            subsampling,
+            siting,
+            luma,
+            cb,
+            cr,
+        }
+    }
+
+    /// Merge the planes back into `YCbCr` colors of `model`, stored row by row.
+    ///
+    /// The chroma planes are upsampled to the full resolution with `filter`.
+    pub fn merge<M>(&self, model: M, filter: ChromaFilter) -> Vec<YCbCr<T, M>>
+    where
+        M: YCbCrModel<T>,
+    {
+        let factors = self.subsampling.factors();
+        let offsets = self.siting.offsets(factors);
+        let (chroma_width, _) = self.chroma_dimensions();
+        let (width, height) = (self.width, self.height);
+        let upsample = |plane: &[T]| -> Vec<f64> {
+            let plane: Vec<f64> = plane.iter().map(|v| v.to_f64()).collect();
+            let rows = resample_rows(&plane, chroma_width, |line| {
+                upsample_line(line, width, factors.0, offsets.0, filter)
+            });
+            resample_columns(&rows, width, |line| {
+                upsample_line(line, height, factors.1, offsets.1, filter)
+            })
+        };
+        let cb = upsample(&self.cb);
+        let cr = upsample(&self.cr);
+
+        self.luma
+            .iter()
+            .zip(cb.into_iter().zip(cr))
+            .map(|(&luma, (cb, cr))| {
+                YCbCr::new_and_model(luma, T::from_f64(cb), T::from_f64(cr), model.clone())
+            })
+            .collect()
+    }
+
+    /// Return the width of the image in luma samples.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    /// Return the height of the image in luma samples.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    /// Return the dimensions of the chroma planes.
+    pub fn chroma_dimensions(&self) -> (usize, usize) {
+        self.subsampling.chroma_dimensions(self.width, self.height)
+    }
+    /// Return the subsampling of the chroma planes.
+    pub fn subsampling(&self) -> Subsampling {
+        self.subsampling
+    }
+    /// Return the siting of the chroma samples.
+    pub fn siting(&self) -> ChromaSiting {
+        self.siting
+    }
+    /// Return the luma plane.
+    pub fn luma(&self) -> &[T] {
+        &self.luma
+    }
+    /// Return the Cb plane.
+    pub fn cb(&self) -> &[T] {
+        &self.cb
+    }
+    /// Return the Cr plane.
+    pub fn cr(&self) -> &[T] {
+        &self.cr
+    }
+    /// Consume `self`, returning the luma, Cb and Cr planes.
+    pub fn into_planes(self) -> (Vec<T>, Vec<T>, Vec<T>) {
+        (self.luma, self.cb, self.cr)
+    }
+}
+
+impl<T> YCbCrPlanes<T>
+where
+    T: PlaneScalar + num_traits::Float,
+{
+    /// Quantize normalized planes to digital code values in the range `R`.
+    ///
+    /// Each sample is quantized with [`quantize_luma`](fn.quantize_luma.html) or
+    /// [`quantize_chroma`](fn.quantize_chroma.html), so the result depends only on the sample
+    /// values and not on the platform.
+    pub fn quantize<C, R>(&self) -> YCbCrPlanes<C>
+    where
+        C: CodeValue,
+        R: YCbCrRange,
+    {
+        let luma = |v: &T| range::quantize_luma::<C, R>(PlaneScalar::to_f64(*v));
+        let chroma = |v: &T| range::quantize_chroma::<C, R>(PlaneScalar::to_f64(*v));
+        YCbCrPlanes {
+            width: self.width,
+            height: self.height,
+            subsampling: self.subsampling,
+            siting: self.siting,
+            luma: self.luma.iter().map(luma).collect(),
+            cb: self.cb.iter().map(chroma).collect(),
+            cr: self.cr.iter().map(chroma).collect(),
+        }
+    }
+}
+
+impl<C> YCbCrPlanes<C>
+where
+    C: CodeValue,
+{
+    /// Convert planes of code values in the range `R` back to normalized values.
+    pub fn dequantize<T, R>(&self) -> YCbCrPlanes<T>
+    where
+        T: PlaneScalar + num_traits::Float,
+        R: YCbCrRange,
+    {
+        let luma = |v: &C| <T as PlaneScalar>::from_f64(range::dequantize_luma::<C, R>(*v));
+        let chroma = |v: &C| <T as PlaneScalar>::from_f64(range::dequantize_chroma::<C, R>(*v));
+        YCbCrPlanes {
+            width: self.width,
+            height: self.height,
+            subsampling: self.subsampling,
+            siting: self.siting,
+            luma: self.luma.iter().map(luma).collect(),
+            cb: self.cb.iter().map(chroma).collect(),
+            cr: self.cr.iter().map(chroma).collect(),
+        }
+    }
+
+    /// Serialize the planes to bytes, with the luma, Cb and Cr planes one after another.
+    ///
+    /// Code values of 8 bits or fewer take one byte each. Wider code values take two bytes,
+    /// little endian and in the least significant bits, which is the layout of formats such as
+    /// FFmpeg's `yuv420p10le`.
+    pub fn to_le_bytes(&self) -> Vec<u8> {
+        let width = if C::BITS <= 8 { 1 } else { 2 };
+        let len = self.luma.len() + self.cb.len() + self.cr.len();
+        let mut bytes = Vec::with_capacity(len * width);
+        for value in self.luma.iter().chain(&self.cb).chain(&self.cr) {
+            let code = value.code();
+            if width == 1 {
+                bytes.push(code as u8);
+            } else {
+                bytes.extend_from_slice(&(code as u16).to_le_bytes());
+            }
+        }
+        bytes
+    }
+}
+
+/// Apply `f` to each row of `plane`, which has rows of `width` samples.
+fn resample_rows<F>(plane: &[f64], width: usize, f: F) -> Vec<f64>
+where
+    F: Fn(&[f64]) -> Vec<f64>,
+{
+    if width == 0 {
+        return Vec::new();
+    }
+    plane.chunks(width).flat_map(f).collect()
+}
+
+/// Apply `f` to each column of `plane`, which has rows of `width` samples.
+fn resample_columns<F>(plane: &[f64], width: usize, f: F) -> Vec<f64>
+where
+    F: Fn(&[f64]) -> Vec<f64>,
+{
+    if width == 0 {
+        return Vec::new();
+    }
+    let columns: Vec<Vec<f64>> = (0..width)
+        .map(|x| {
+            let column: Vec<f64> = plane.iter().skip(x).step_by(width).cloned().collect();
+            f(&column)
+        })
+        .collect();
+    let height = columns[0].len();
+    (0..height)
+        .flat_map(|y| columns.iter().map(move |column| column[y]))
+        .collect()
+}
+
+fn downsample_line(line: &[f64], factor: usize, offset: f64, filter: ChromaFilter) -> Vec<f64> {
+    if factor == 1 {
+        return line.to_vec();
+    }
+    let len = line.len().div_ceil(factor);
+    (0..len)
+        .map(|i| match filter {
+            ChromaFilter::Box => {
+                let block = &line[i * factor..((i + 1) * factor).min(line.len())];
+                block.iter().sum::<f64>() / block.len() as f64
+            }
+            ChromaFilter::Bilinear => {
+                let center = (i * factor) as f64 + offset;
+                let radius = factor as f64;
+                let first = (center - radius).ceil() as isize;
+                let last = (center + radius).floor() as isize;
+                let (mut sum, mut total) = (0.0, 0.0);
+                for x in first..=last {
+                    let weight = 1.0 - (x as f64 - center).abs() / radius;
+                    if weight > 0.0 {
+                        let index = x.clamp(0, line.len() as isize - 1) as usize;
+                        sum += weight * line[index];
+                        total += weight;
+                    }
+                }
+                sum / total
+            }
+        })
+        .collect()
+}
+
+fn upsample_line(
+    line: &[f64],
+    len: usize,
+    factor: usize,
+    offset: f64,
+    filter: ChromaFilter,
+) -> Vec<f64> {
+    if factor == 1 {
+        return line.to_vec();
+    }
+    if line.is_empty() || len == 0 {
+        return Vec::new();
+    }
+    let last = line.len() - 1;
+    (0..len)
+        .map(|x| match filter {
+            ChromaFilter::Box => line[(x / factor).min(last)],
+            ChromaFilter::Bilinear => {
+                let pos = ((x as f64 - offset) / factor as f64).max(0.0);
+                let index = (pos.floor() as usize).min(last);
+                let next = (index + 1).min(last);
+                let t = pos - index as f64;
+                line[index] * (1.0 - t) + line[next] * t
+            }
+        })
+        .collect()
+}
+
+macro_rules! impl_plane_scalar_int {
+    ($T:ident) => {
+        impl PlaneScalar for $T {
+            fn to_f64(self) -> f64 {
+                f64::from(self)
+            }
+            fn from_f64(value: f64) -> Self {
+                value.round().clamp(0.0, f64::from($T::MAX)) as $T
+            }
+        }
+    };
+}
+
+macro_rules! impl_plane_scalar_code_value {
+    ($T:ident) => {
+        impl PlaneScalar for $T {
+            fn to_f64(self) -> f64 {
+                f64::from(self.value())
+            }
+            fn from_f64(value: f64) -> Self {
+                $T::new(value.round().clamp(0.0, f64::from($T::MAX.value())) as u16)
+            }
+        }
+    };
+}
+
+impl_plane_scalar_int!(u8);
+impl_plane_scalar_int!(u16);
+impl_plane_scalar_int!(u32);
+impl_plane_scalar_code_value!(U10);
+impl_plane_scalar_code_value!(U12);
+
+impl PlaneScalar for f32 {
+    fn to_f64(self) -> f64 {
+        f64::from(self)
+    }
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+}
+
+impl PlaneScalar for f64 {
+    fn to_f64(self) -> f64 {
+        self
+    }
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ycbcr::model::JpegModel;
+    use crate::ycbcr::range::LimitedRange;
+    use crate::ycbcr::YCbCrJpeg;
+    use approx::*;
+
+    fn gradient(width: usize, height: usize) -> Vec<YCbCrJpeg<f64>> {
+        (0..width * height)
+            .map(|i| {
+                let (x, y) = ((i % width) as f64, (i / width) as f64);
+                YCbCrJpeg::new(0.5, x / 10.0 - 0.2, y / 10.0 - 0.2)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_dimensions() {
+        assert_eq!(Subsampling::Chroma444.chroma_dimensions(5, 3), (5, 3));
+        assert_eq!(Subsampling::Chroma422.chroma_dimensions(5, 3), (3, 3));
+        assert_eq!(Subsampling::Chroma420.chroma_dimensions(5, 3), (3, 2));
+
+        let image = gradient(5, 3);
+        for &subsampling in [
+            Subsampling::Chroma444,
+            Subsampling::Chroma422,
+            Subsampling::Chroma420,
+        ]
+        .iter()
+        {
+            let planes = YCbCrPlanes::split(
+                &image,
+                5,
+                3,
+                subsampling,
+                ChromaSiting::Left,
+                ChromaFilter::Bilinear,
+            );
+            let (w, h) = planes.chroma_dimensions();
+            assert_eq!(planes.cb().len(), w * h);
+            assert_eq!(planes.cr().len(), w * h);
+            assert_eq!(planes.luma().len(), 15);
+            assert_eq!(planes.merge(JpegModel, ChromaFilter::Bilinear).len(), 15);
+        }
+
+        let planes = YCbCrPlanes::split(
+            &image,
+            5,
+            3,
+            Subsampling::Chroma444,
+            ChromaSiting::Center,
+            ChromaFilter::Box,
+        );
+        assert_eq!(planes.merge(JpegModel, ChromaFilter::Box), image);
+    }
+
+    #[test]
+    fn test_empty_image() {
+        for &(width, height) in [(4, 0), (0, 4), (0, 0)].iter() {
+            for &subsampling in [
+                Subsampling::Chroma444,
+                Subsampling::Chroma422,
+                Subsampling::Chroma420,
+            ]
+            .iter()
+            {
+                for &filter in [ChromaFilter::Box, ChromaFilter::Bilinear].iter() {
+                    let image: Vec<YCbCrJpeg<f64>> = Vec::new();
+                    let planes = YCbCrPlanes::split(
+                        &image,
+                        width,
+                        height,
+                        subsampling,
+                        ChromaSiting::Center,
+                        filter,
+                    );
+                    assert!(planes.cb().is_empty());
+                    assert!(planes.cr().is_empty());
+                    assert!(planes.merge(JpegModel, filter).is_empty());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_siting() {
+        let image = gradient(8, 8);
+        let center = YCbCrPlanes::split(
+            &image,
+            8,
+            8,
+            Subsampling::Chroma420,
+            ChromaSiting::Center,
+            ChromaFilter::Bilinear,
+        );
+        // A linear ramp is reproduced exactly at each chroma sample's position, away from edges
+        assert_relative_eq!(center.cb()[1], 0.05, epsilon = 1e-12);
+        assert_relative_eq!(center.cr()[4], 0.05, epsilon = 1e-12);
+        let boxed = YCbCrPlanes::split(
+            &image,
+            8,
+            8,
+            Subsampling::Chroma420,
+            ChromaSiting::Center,
+            ChromaFilter::Box,
+        );
+        assert_relative_eq!(boxed.cb()[1], 0.05, epsilon = 1e-12);
+
+        let cosited = YCbCrPlanes::split(
+            &image,
+            8,
+            8,
+            Subsampling::Chroma420,
+            ChromaSiting::TopLeft,
+            ChromaFilter::Bilinear,
+        );
+        assert_relative_eq!(cosited.cb()[1], 0.0, epsilon = 1e-12);
+        assert_relative_eq!(cosited.cr()[4], 0.0, epsilon = 1e-12);
+
+        let merged = cosited.merge(JpegModel, ChromaFilter::Bilinear);
+        assert_relative_eq!(merged[45].cb(), 0.3, epsilon = 1e-12);
+        assert_relative_eq!(merged[45].cr(), 0.3, epsilon = 1e-12);
+        assert_eq!(cosited.siting(), ChromaSiting::TopLeft);
+    }
+
+    #[test]
+    fn test_integer_planes() {
+        let image = vec![
+            YCbCrJpeg::new(10u8, 100, 200),
+            YCbCrJpeg::new(20, 101, 201),
+            YCbCrJpeg::new(30, 102, 202),
+        ];
+        let planes = YCbCrPlanes::split(
+            &image,
+            3,
+            1,
+            Subsampling::Chroma422,
+            ChromaSiting::Center,
+            ChromaFilter::Box,
+        );
+        assert_eq!(planes.cb(), &[101, 102][..]);
+        assert_eq!(planes.cr(), &[201, 202][..]);
+
+        let rebuilt = YCbCrPlanes::from_planes(
+            3,
+            1,
+            Subsampling::Chroma422,
+            ChromaSiting::Center,
+            vec![10, 20, 30],
+            vec![101, 102],
+            vec![201, 202],
+        );
+        assert_eq!(rebuilt, planes);
+        let merged = rebuilt.merge(JpegModel, ChromaFilter::Box);
+        assert_eq!(merged[1], YCbCrJpeg::new(20, 101, 201));
+        assert_eq!(merged[2], YCbCrJpeg::new(30, 102, 202));
+    }
+
+    #[test]
+    fn test_quantize_planes() {
+        let image = vec![
+            YCbCrJpeg::new(0.0f64, -0.5, 0.5),
+            YCbCrJpeg::new(1.0, 0.5, -0.5),
+            YCbCrJpeg::new(0.5, 0.0, 0.0),
+            YCbCrJpeg::new(0.5, 0.0, 0.0),
+        ];
+        let planes = YCbCrPlanes::split(
+            &image,
+            2,
+            2,
+            Subsampling::Chroma420,
+            ChromaSiting::Center,
+            ChromaFilter::Box,
+        );
+        let studio = planes.quantize::<u8, LimitedRange>();
+        assert_eq!(studio.luma(), &[16, 235, 126, 126][..]);
+        assert_eq!(studio.cb(), &[128][..]);
+        assert_eq!(studio.to_le_bytes(), vec![16, 235, 126, 126, 128, 128]);
+
+        let ten_bit = planes.quantize::<U10, LimitedRange>();
+        assert_eq!(ten_bit.luma()[1], U10::new(940));
+        assert_eq!(
+            ten_bit.to_le_bytes(),
+            vec![64, 0, 172, 3, 246, 1, 246, 1, 0, 2, 0, 2]
+        );
+
+        let back = ten_bit.dequantize::<f64, LimitedRange>();
+        for (a, b) in back.luma().iter().zip(planes.luma()) {
+            assert_relative_eq!(*a, *b, epsilon = 1.0 / 876.0);
+        }
+        assert_eq!(back.cb(), &[0.0][..]);
+    }
+}
@@ -0,0 +1,482 @@
+//! Full and limited ranges of digital YCbCr code values.
+
+use crate::channel::{NormalChannelScalar, PosNormalChannelScalar, U10, U12};
+use crate::linalg::{Matrix3, Matrix3x4};
+use crate::ycbcr::model::{JpegModel, YCbCrModel};
+use crate::ycbcr::YCbCr;
+use num_traits;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// An unsigned integer type that can hold digital YCbCr code values.
+pub trait CodeValue: Copy {
+    /// The number of bits in a code value.
+    const BITS: u32;
+    /// Construct from a code value, which must fit in `BITS` bits.
+    fn from_code(value: u32) -> Self;
+    /// Return the code value.
+    fn code(self) -> u32;
+}
+
+/// The range of digital code values that the channels of a YCbCr color are stored in.
+///
+/// For each channel, a normalized value `v` is stored as the code value `offset + v * scale`,
+/// rounded to the nearest integer. The offsets and scales depend on the bit depth.
+pub trait YCbCrRange: Copy + Clone + fmt::Debug + PartialEq + Default {
+    /// Return the `(offset, scale)` of the luma channel at a depth of `bits`.
+    fn luma_scale(bits: u32) -> (f64, f64);
+    /// Return the `(offset, scale)` of the two chroma channels at a depth of `bits`.
+    fn chroma_scale(bits: u32) -> (f64, f64);
+    /// Return the lowest and highest code values that may be stored at a depth of `bits`.
+    ///
+    /// Quantized values are clamped to these limits. By default, every code value may be used.
+    fn code_limits(bits: u32) -> (f64, f64) {
+        (0.0, max_code(bits))
+    }
+    /// Return the transform from normalized `(luma, cb, cr)` to code values at a depth of `bits`.
+    ///
+    /// The transform is not rounded or clamped to the code value limits. It can be combined with
+    /// the matrix of a model to go directly between Rgb and code values, or with the inverse
+    /// transform of another range to convert between ranges.
+    fn code_transform(bits: u32) -> Matrix3x4<f64> {
+        let (luma_offset, luma_scale) = Self::luma_scale(bits);
+        let (chroma_offset, chroma_scale) = Self::chroma_scale(bits);
+        Matrix3x4::new(
+            Matrix3::new([
+                luma_scale,
+                0.0,
+                0.0,
+                0.0,
+                chroma_scale,
+                0.0,
+                0.0,
+                0.0,
+                chroma_scale,
+            ]),
+            (luma_offset, chroma_offset, chroma_offset),
+        )
+    }
+}
+
+/// The full range of code values, as used by JPEG and most computer graphics.
+///
+/// Luma spans every code value, from 0 to 255 for 8-bit channels, and neutral chroma is
+/// half of the code value range, 128 for 8-bit channels.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub struct FullRange;
+
+/// The limited, or "studio swing", range of code values used by broadcast video.
+///
+/// For 8-bit channels, luma spans 16 to 235 and chroma spans 16 to 240, leaving footroom and
+/// headroom for filter overshoot. Deeper channels scale these values up, so 10-bit luma spans
+/// 64 to 940.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub struct LimitedRange;
+
+/// The extended range of xvYCC (IEC 61966-2-4).
+///
+/// xvYCC has the same nominal range as `LimitedRange`, but lets colors outside of the RGB gamut
+/// use the footroom and headroom. Negative and greater than one RGB values, encoded with the
+/// sign-symmetric BT.709 transfer function, give chroma past 16 and 240 for 8-bit channels.
+/// Only the lowest and highest code values, such as 0 and 255 for 8-bit channels, are reserved
+/// for synchronization and never produced.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub struct XvYccRange;
+
+impl YCbCrRange for FullRange {
+    fn luma_scale(bits: u32) -> (f64, f64) {
+        (0.0, max_code(bits))
+    }
+    fn chroma_scale(bits: u32) -> (f64, f64) {
+        (f64::from(1u32 << (bits - 1)), max_code(bits))
+    }
+}
+
+impl YCbCrRange for LimitedRange {
+    fn luma_scale(bits: u32) -> (f64, f64) {
+        let step = f64::from(1u32 << (bits - 8));
+        (16.0 * step, 219.0 * step)
+    }
+    fn chroma_scale(bits: u32) -> (f64, f64) {
+        let step = f64::from(1u32 << (bits - 8));
+        (128.0 * step, 224.0 * step)
+    }
+}
+
+impl YCbCrRange for XvYccRange {
+    fn luma_scale(bits: u32) -> (f64, f64) {
+        LimitedRange::luma_scale(bits)
+    }
+    fn chroma_scale(bits: u32) -> (f64, f64) {
+        LimitedRange::chroma_scale(bits)
+    }
+    fn code_limits(bits: u32) -> (f64, f64) {
+        let step = f64::from(1u32 << (bits - 8));
+        (step, max_code(bits) - step)
+    }
+}
+
+fn max_code(bits: u32) -> f64 {
+    f64::from(((1u64 << bits) - 1) as u32)
+}
+
+/// A YCbCr color stored as digital code values in the range `R`.
+///
+/// `QuantizedYCbCr` is produced by [`YCbCr::quantize`](struct.YCbCr.html#method.quantize), which
+/// scales the normalized channels of a `YCbCr` into the code values of the range `R`, and
+/// converted back with [`dequantize`](#method.dequantize). Both the model and the range are part
+/// of the type, so full range codes cannot be decoded as limited range ones, nor BT.709 codes as
+/// BT.601, without an explicit conversion.
+///
+/// The normalized chroma channels span `[-0.5, 0.5]`, as produced by the standard models.
+///
+/// ## Examples:
+///
+/// ```rust
+/// # extern crate prisma;
+///
+/// use prisma::Rgb;
+/// use prisma::ycbcr::{FullRange, LimitedRange, QuantizedYCbCr, YCbCrBt709, Bt709Model};
+///
+/// let white = YCbCrBt709::from_rgb(&Rgb::new(1.0, 1.0, 1.0));
+/// let studio: QuantizedYCbCr<u8, Bt709Model, LimitedRange> = white.quantize();
+/// assert_eq!(studio.to_tuple(), (235, 128, 128));
+///
+/// let full: QuantizedYCbCr<u8, Bt709Model, FullRange> = studio.convert_range();
+/// assert_eq!(full.to_tuple(), (255, 128, 128));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct QuantizedYCbCr<C, M = JpegModel, R = FullRange> {
+    luma: C,
+    cb: C,
+    cr: C,
+    model: M,
+    range: PhantomData<R>,
+}
+
+impl<C, M, R> QuantizedYCbCr<C, M, R>
+where
+    C: CodeValue,
+    R: YCbCrRange,
+{
+    /// Construct a `QuantizedYCbCr` from code values and a model.
+    pub fn new_and_model(luma: C, cb: C, cr: C, model: M) -> Self {
+        QuantizedYCbCr {
+            luma,
+            cb,
+            cr,
+            model,
+            range: PhantomData,
+        }
+    }
+
+    /// Get the luma (Y') code value.
+    pub fn luma(&self) -> C {
+        self.luma
+    }
+    /// Get the Cb code value.
+    pub fn cb(&self) -> C {
+        self.cb
+    }
+    /// Get the Cr code value.
+    pub fn cr(&self) -> C {
+        self.cr
+    }
+    /// Get a reference to the model of the color.
+    pub fn model(&self) -> &M {
+        &self.model
+    }
+    /// Return the code values as a tuple of `(luma, cb, cr)`.
+    pub fn to_tuple(&self) -> (C, C, C) {
+        (self.luma, self.cb, self.cr)
+    }
+
+    /// Convert the code values back to a normalized `YCbCr`.
+    pub fn dequantize<T>(&self) -> YCbCr<T, M>
+    where
+        T: NormalChannelScalar + PosNormalChannelScalar + num_traits::Float,
+        M: YCbCrModel<T>,
+    {
+        let (luma, cb, cr) = self.normalized();
+        YCbCr::new_and_model(
+            num_traits::cast(luma).unwrap(),
+            num_traits::cast(cb).unwrap(),
+            num_traits::cast(cr).unwrap(),
+            self.model.clone(),
+        )
+    }
+
+    /// Rescale the code values into the range `R2`.
+    pub fn convert_range<R2>(&self) -> QuantizedYCbCr<C, M, R2>
+    where
+        M: Clone,
+        R2: YCbCrRange,
+    {
+        let transform = R2::code_transform(C::BITS)
+            * R::code_transform(C::BITS)
+                .try_inverse()
+                .expect("Singular YCbCr range transform");
+        let codes = (
+            f64::from(self.luma.code()),
+            f64::from(self.cb.code()),
+            f64::from(self.cr.code()),
+        );
+        let (luma, cb, cr) = transform * codes;
+        QuantizedYCbCr::new_and_model(
+            clamp_code::<C, R2>(luma),
+            clamp_code::<C, R2>(cb),
+            clamp_code::<C, R2>(cr),
+            self.model.clone(),
+        )
+    }
+
+    fn normalized(&self) -> (f64, f64, f64) {
+        (
+            dequantize_luma::<C, R>(self.luma),
+            dequantize_chroma::<C, R>(self.cb),
+            dequantize_chroma::<C, R>(self.cr),
+        )
+    }
+}
+
+impl<T, M> YCbCr<T, M>
+where
+    T: NormalChannelScalar + PosNormalChannelScalar + num_traits::Float,
+    M: YCbCrModel<T>,
+{
+    /// Quantize the channels to code values in the range `R`.
+    ///
+    /// Each channel is rounded to the nearest code value. Values past the code value limits of
+    /// `R` are clamped to them.
+    pub fn quantize<C, R>(&self) -> QuantizedYCbCr<C, M, R>
+    where
+        C: CodeValue,
+        R: YCbCrRange,
+    {
+        let cast = |v: T| num_traits::cast::<_, f64>(v).unwrap();
+        QuantizedYCbCr::new_and_model(
+            quantize_luma::<C, R>(cast(self.luma())),
+            quantize_chroma::<C, R>(cast(self.cb())),
+            quantize_chroma::<C, R>(cast(self.cr())),
+            self.model().clone(),
+        )
+    }
+}
+
+/// Quantize a normalized luma value to a code value in the range `R`.
+///
+/// `value` is scaled by [`R::luma_scale`](trait.YCbCrRange.html#tymethod.luma_scale), rounded to
+/// the nearest code value with halves rounded up, and clamped to the code value limits of `R`.
+/// NaN becomes the lowest code value. For example, `1.0` is code value 235 in 8-bit
+/// `LimitedRange` and 940 in 10-bit.
+pub fn quantize_luma<C, R>(value: f64) -> C
+where
+    C: CodeValue,
+    R: YCbCrRange,
+{
+    let (offset, scale) = R::luma_scale(C::BITS);
+    quantize_channel::<C, R>(value, offset, scale)
+}
+
+/// Quantize a normalized chroma value in `[-0.5, 0.5]` to a code value in the range `R`.
+///
+/// Rounding and clamping are the same as for [`quantize_luma`](fn.quantize_luma.html). For
+/// example, `0.5` is code value 240 in 8-bit `LimitedRange` and 960 in 10-bit.
+pub fn quantize_chroma<C, R>(value: f64) -> C
+where
+    C: CodeValue,
+    R: YCbCrRange,
+{
+    let (offset, scale) = R::chroma_scale(C::BITS);
+    quantize_channel::<C, R>(value, offset, scale)
+}
+
+/// Convert a luma code value in the range `R` back to a normalized value.
+pub fn dequantize_luma<C, R>(code: C) -> f64
+where
+    C: CodeValue,
+    R: YCbCrRange,
+{
+    let (offset, scale) = R::luma_scale(C::BITS);
+    (f64::from(code.code()) - offset) / scale
+}
+
+/// Convert a chroma code value in the range `R` back to a normalized value in `[-0.5, 0.5]`.
+pub fn dequantize_chroma<C, R>(code: C) -> f64
+where
+    C: CodeValue,
+    R: YCbCrRange,
+{
+    let (offset, scale) = R::chroma_scale(C::BITS);
+    (f64::from(code.code()) - offset) / scale
+}
+
+fn quantize_channel<C, R>(value: f64, offset: f64, scale: f64) -> C
+where
+    C: CodeValue,
+    R: YCbCrRange,
+{
+    clamp_code::<C, R>(offset + value * scale)
+}
+
+fn clamp_code<C, R>(code: f64) -> C
+where
+    C: CodeValue,
+    R: YCbCrRange,
+{
+    let (min, max) = R::code_limits(C::BITS);
+    let code = code.round();
+    let code = if code.is_nan() {
+        min
+    } else {
+        code.clamp(min, max)
+    };
+    C::from_code(code as u32)
+}
+
+impl CodeValue for u8 {
+    const BITS: u32 = 8;
+    fn from_code(value: u32) -> Self {
+        value as u8
+    }
+    fn code(self) -> u32 {
+        u32::from(self)
+    }
+}
+
+impl CodeValue for u16 {
+    const BITS: u32 = 16;
+    fn from_code(value: u32) -> Self {
+        value as u16
+    }
+    fn code(self) -> u32 {
+        u32::from(self)
+    }
+}
+
+impl CodeValue for U10 {
+    const BITS: u32 = U10::BITS;
+    fn from_code(value: u32) -> Self {
+        U10::new(value as u16)
+    }
+    fn code(self) -> u32 {
+        u32::from(self.value())
+    }
+}
+
+impl CodeValue for U12 {
+    const BITS: u32 = U12::BITS;
+    fn from_code(value: u32) -> Self {
+        U12::new(value as u16)
+    }
+    fn code(self) -> u32 {
+        u32::from(self.value())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::color::Color;
+    use crate::rgb::Rgb;
+    use crate::ycbcr::model::{Bt2020Model, Bt709Model};
+    use crate::ycbcr::{YCbCrBt2020, YCbCrBt709, YCbCrJpeg};
+    use approx::*;
+
+    #[test]
+    fn test_ranges() {
+        let black = YCbCrJpeg::from_rgb(&Rgb::new(0.0, 0.0, 0.0));
+        let white = YCbCrJpeg::from_rgb(&Rgb::new(1.0, 1.0, 1.0));
+        let extreme = YCbCrJpeg::new(1.0, -0.5, 0.5);
+
+        let q: QuantizedYCbCr<u8, JpegModel, FullRange> = black.quantize();
+        assert_eq!(q.to_tuple(), (0, 128, 128));
+        let q: QuantizedYCbCr<u8, JpegModel, FullRange> = white.quantize();
+        assert_eq!(q.to_tuple(), (255, 128, 128));
+        let q: QuantizedYCbCr<u8, JpegModel, FullRange> = extreme.quantize();
+        assert_eq!(q.to_tuple(), (255, 1, 255));
+
+        let q: QuantizedYCbCr<u8, JpegModel, LimitedRange> = black.quantize();
+        assert_eq!(q.to_tuple(), (16, 128, 128));
+        let q: QuantizedYCbCr<u8, JpegModel, LimitedRange> = extreme.quantize();
+        assert_eq!(q.to_tuple(), (235, 16, 240));
+
+        let q: QuantizedYCbCr<U10, JpegModel, LimitedRange> = extreme.quantize();
+        assert_eq!(q.luma(), U10::new(940));
+        assert_eq!(q.cb(), U10::new(64));
+        assert_eq!(q.cr(), U10::new(960));
+        let q: QuantizedYCbCr<U10, JpegModel, FullRange> = white.quantize();
+        assert_eq!(q.to_tuple(), (U10::new(1023), U10::new(512), U10::new(512)));
+
+        let over = YCbCrJpeg::new(1.5, -1.0, 1.0);
+        let q: QuantizedYCbCr<u8, JpegModel, LimitedRange> = over.quantize();
+        assert_eq!(q.to_tuple(), (255, 0, 255));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let color = YCbCrBt2020::from_rgb(&Rgb::new(0.8f64, 0.3, 0.1));
+        let q: QuantizedYCbCr<U10, Bt2020Model, LimitedRange> = color.quantize();
+        let back: YCbCrBt2020<f64> = q.dequantize();
+        assert_relative_eq!(back, color, epsilon = 1.0 / 876.0);
+
+        let q: QuantizedYCbCr<u16, Bt2020Model, FullRange> = color.quantize();
+        assert_relative_eq!(q.dequantize::<f64>(), color, epsilon = 1e-4);
+
+        for code in 16..=235u8 {
+            let q =
+                QuantizedYCbCr::<u8, _, LimitedRange>::new_and_model(code, 128, 128, Bt709Model);
+            let full: QuantizedYCbCr<u8, Bt709Model, FullRange> = q.convert_range();
+            assert_eq!(full.convert_range::<LimitedRange>(), q);
+        }
+    }
+
+    #[test]
+    fn test_xvycc() {
+        use crate::encoding::{Rec709Encoding, TranscodableColor};
+        use crate::ycbcr::{YCbCrBt709, YCbCrOutOfGamutMode};
+
+        // A green outside of the BT.709 gamut, with a negative red channel
+        let linear = Rgb::new(-0.1f64, 0.8, 0.05);
+        let ycbcr = YCbCrBt709::from_rgb(&linear.encode_color(&Rec709Encoding));
+        let q: QuantizedYCbCr<u8, Bt709Model, XvYccRange> = ycbcr.quantize();
+        assert!(q.cb() < 16 || q.cr() < 16);
+        assert!(q.cb() >= 1 && q.cr() >= 1);
+
+        let back = q
+            .dequantize::<f64>()
+            .to_rgb(YCbCrOutOfGamutMode::Preserve)
+            .decode_color(&Rec709Encoding);
+        assert!(back.red() < 0.0);
+        assert_relative_eq!(back, linear, epsilon = 0.02);
+
+        let extreme = YCbCrBt709::new(1.5, -1.0, 1.0);
+        let q: QuantizedYCbCr<u8, Bt709Model, XvYccRange> = extreme.quantize();
+        assert_eq!(q.to_tuple(), (254, 1, 254));
+        let q: QuantizedYCbCr<U10, Bt709Model, XvYccRange> = extreme.quantize();
+        assert_eq!(q.to_tuple(), (U10::new(1019), U10::new(4), U10::new(1019)));
+    }
+
+    #[test]
+    fn test_code_transform() {
+        let transform = LimitedRange::code_transform(10)
+            * YCbCrModel::<f64>::forward_affine_transform(&Bt709Model);
+        let rgb = Rgb::new(0.8f64, 0.3, 0.1);
+        let q: QuantizedYCbCr<U10, Bt709Model, LimitedRange> =
+            YCbCrBt709::from_rgb(&rgb).quantize();
+        let (luma, cb, cr) = transform * rgb.to_tuple();
+        assert_eq!(U10::new(luma.round() as u16), q.luma());
+        assert_eq!(U10::new(cb.round() as u16), q.cb());
+        assert_eq!(U10::new(cr.round() as u16), q.cr());
+
+        let decode = YCbCrModel::<f64>::inverse_affine_transform(&Bt709Model)
+            * LimitedRange::code_transform(10).try_inverse().unwrap();
+        let (r, g, b) = decode * (luma, cb, cr);
+        assert_relative_eq!(Rgb::new(r, g, b), rgb, epsilon = 1e-12);
+
+        assert_eq!(
+            FullRange::code_transform(8) * (1.0, -0.5, 0.5),
+            (255.0, 0.5, 255.5)
+        );
+    }
+}
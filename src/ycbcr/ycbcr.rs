@@ -1,7 +1,10 @@
 //! Implements the core `YCbCr` struct and some convenience types.
 
 use crate::channel::{ChannelFormatCast, NormalChannelScalar, PosNormalChannelScalar};
-use crate::color::{Bounded, Broadcast, Color, Flatten, FromTuple, HomogeneousColor, Invert, Lerp};
+use crate::color::{
+    Bounded, Broadcast, ChannelAccess, ChannelOrder, Color, Flatten, FromTuple, HomogeneousColor,
+    Invert, Lerp,
+};
 use crate::convert::{FromColor, FromYCbCr};
 use crate::encoding::EncodableColor;
 use crate::rgb::Rgb;
@@ -13,7 +16,8 @@ use std::fmt;
 
 use crate::ycbcr::bare_ycbcr::{BareYCbCr, YCbCrOutOfGamutMode};
 use crate::ycbcr::model::{
-    Bt709Model, Canonicalize, CustomYCbCrModel, JpegModel, UnitModel, YCbCrModel, YiqModel,
+    Bt2020Model, Bt601Model, Bt709Model, Canonicalize, CustomYCbCrModel, JpegModel, UnitModel,
+    YCbCrModel, YCbCrStandard, YiqModel,
 };
 
 /// A color in the YCbCr family of color spaces.
@@ -23,13 +27,16 @@ use crate::ycbcr::model::{
 /// is stored with the color, and comes in two forms:
 ///
 /// * A unit struct, defining the model at compile time. These do not store any value and thus
-///   do not increase the size of a color instance. `JpegModel`, `Bt709Model` and `YiqModel`
-///   and of this type. These types implement the `UnitModel` trait, and do not need to be
+///   do not increase the size of a color instance. `JpegModel`, `Bt601Model`, `Bt709Model`,
+///   `Bt2020Model` and `YiqModel` are of this type. These types implement the `UnitModel` trait, and do not need to be
 ///   passed to most functions.
 /// * A type which stores its transformations in memory at runtime. For these models, it is
 ///   usually preferable to store a reference to the model in the color to minimize the size
 ///   impact. However, this will still increase the size of the type by one pointer size. Only
 ///   `CustomYCbCrModel` is of this type.
+///
+/// With integer channels and a unit model, `YCbCr` is `Eq`, `Hash` and `Ord`. Colors are ordered by
+/// luma, then cb, then cr.
 #[repr(C)]
 #[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Eq, Ord, Hash)]
 pub struct YCbCr<T, M = JpegModel> {
@@ -40,9 +47,19 @@ pub struct YCbCr<T, M = JpegModel> {
 /// A YCbCr color with a `YiqModel`.
 pub type Yiq<T> = YCbCr<T, YiqModel>;
 /// A YCbCr color with a `JpegModel`.
+///
+/// With `u8` channels this holds the full range, 8-bit values stored in JPEG files. Use
+/// [`rgb_to_jpeg_ycbcr`](fn.rgb_to_jpeg_ycbcr.html) and
+/// [`jpeg_ycbcr_to_rgb`](fn.jpeg_ycbcr_to_rgb.html) to convert them exactly as libjpeg does.
 pub type YCbCrJpeg<T> = YCbCr<T, JpegModel>;
+/// A YCbCr color with a `Bt601Model`.
+pub type YCbCrBt601<T> = YCbCr<T, Bt601Model>;
 /// A YCbCr color with a `Bt709Model`.
 pub type YCbCrBt709<T> = YCbCr<T, Bt709Model>;
+/// A YCbCr color with a `Bt2020Model`.
+pub type YCbCrBt2020<T> = YCbCr<T, Bt2020Model>;
+/// A YCbCr color with a standard chosen at runtime.
+pub type YCbCrStandardized<T> = YCbCr<T, YCbCrStandard>;
 /// A YCbCr color with a reference to a `CustomYCbCrModel`.
 pub type YCbCrCustom<'a, T> = YCbCr<T, &'a CustomYCbCrModel>;
 
@@ -277,6 +294,29 @@ where
     }
 }
 
+impl<T, M> ChannelOrder for YCbCr<T, M>
+where
+    T: NormalChannelScalar + PosNormalChannelScalar,
+    M: YCbCrModel<T>,
+{
+    fn channel_name(index: usize) -> Option<&'static str> {
+        BareYCbCr::<T>::channel_name(index)
+    }
+}
+
+impl<T, M> ChannelAccess for YCbCr<T, M>
+where
+    T: NormalChannelScalar + PosNormalChannelScalar,
+    M: YCbCrModel<T>,
+{
+    fn channel(&self, index: usize) -> Option<&T> {
+        self.ycbcr.channel(index)
+    }
+    fn channel_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.ycbcr.channel_mut(index)
+    }
+}
+
 impl<T, M> Broadcast for YCbCr<T, M>
 where
     T: NormalChannelScalar + PosNormalChannelScalar,
@@ -656,4 +696,103 @@ mod test {
             YCbCrJpeg::new(0.39215686f32, 0.56862745f32, -0.21568627f32)
         );
     }
+
+    #[test]
+    fn test_integer_channels() {
+        let blue = YCbCrJpeg::from_rgb(&Rgb::new(0u8, 0, 255));
+        assert_eq!(blue, YCbCrJpeg::new(29u8, 255, 107));
+        assert_eq!(
+            blue.to_rgb(YCbCrOutOfGamutMode::Clip),
+            Rgb::new(0u8, 0, 254)
+        );
+
+        let white = YCbCrBt709::from_rgb(&Rgb::new(u32::MAX, u32::MAX, u32::MAX));
+        assert_eq!(white.to_tuple(), (u32::MAX, 1 << 31, 1 << 31));
+
+        let c1 = Rgb::new(12000u16, 40000, 65000);
+        let y1 = YCbCrBt2020::from_rgb(&c1);
+        let float = YCbCrBt2020::from_rgb(&c1.color_cast::<f64>());
+        assert!((f64::from(y1.luma()) - float.luma() * 65535.0).abs() <= 0.5);
+        assert!((f64::from(y1.cb()) - 32768.0 - float.cb() * 65535.0).abs() <= 0.5);
+        let back = y1.to_rgb(YCbCrOutOfGamutMode::Clip);
+        assert!((i32::from(back.red()) - 12000).abs() <= 1);
+        assert!((i32::from(back.green()) - 40000).abs() <= 1);
+        assert!((i32::from(back.blue()) - 65000).abs() <= 1);
+    }
+
+    #[test]
+    fn test_standards() {
+        let white = Rgb::new(1.0, 1.0, 1.0);
+        let red = Rgb::new(1.0, 0.0, 0.0);
+        let standards = [
+            YCbCrStandard::Bt601,
+            YCbCrStandard::Bt709,
+            YCbCrStandard::Bt2020,
+        ];
+        for &standard in standards.iter() {
+            let (kr, kb) = standard.coefficients();
+            let expected = CustomYCbCrModel::build_from_coefficients(kr, kb);
+            assert_relative_eq!(
+                standard.forward_transform(),
+                expected.forward_transform(),
+                epsilon = 1e-12
+            );
+            assert_relative_eq!(
+                standard.inverse_transform(),
+                expected.inverse_transform(),
+                epsilon = 1e-12
+            );
+
+            let c1 = YCbCrStandardized::from_rgb_and_model(&white, standard);
+            assert_relative_eq!(c1.luma(), 1.0, epsilon = 1e-12);
+            assert_relative_eq!(c1.cb(), 0.0, epsilon = 1e-12);
+            assert_relative_eq!(c1.cr(), 0.0, epsilon = 1e-12);
+            let c2 = YCbCrStandardized::from_rgb_and_model(&red, standard);
+            assert_relative_eq!(c2.luma(), kr, epsilon = 1e-12);
+            assert_relative_eq!(c2.cr(), 0.5, epsilon = 1e-12);
+            assert_relative_eq!(
+                c2.to_rgb(YCbCrOutOfGamutMode::Preserve),
+                red,
+                epsilon = 1e-12
+            );
+        }
+
+        let color = Rgb::new(0.2f64, 0.6, 0.9);
+        let hd = YCbCrBt709::from_rgb(&color);
+        let uhd = YCbCrBt2020::from_rgb(&color);
+        assert_relative_eq!(
+            YCbCrStandardized::from_rgb_and_model(&color, YCbCrStandard::Bt709).strip_model(),
+            hd.strip_model()
+        );
+        assert_relative_eq!(
+            YCbCrStandardized::from_rgb_and_model(&color, YCbCrStandard::Bt2020).strip_model(),
+            uhd.strip_model()
+        );
+        assert!((hd.luma() - uhd.luma()).abs() > 1e-3);
+        assert_relative_eq!(
+            YCbCrBt601::from_rgb(&color).strip_model(),
+            YCbCrJpeg::from_rgb(&color).strip_model(),
+            epsilon = 1e-5
+        );
+    }
+
+    #[test]
+    fn test_from_color_space() {
+        use crate::color_space::named::{Rec2020, SRgb};
+
+        let (kr, kb) = luma_coefficients(&SRgb::<f64>::new());
+        assert_relative_eq!(kr, 0.2126, epsilon = 1e-4);
+        assert_relative_eq!(kb, 0.0722, epsilon = 1e-4);
+        let model = CustomYCbCrModel::from_color_space(&Rec2020::<f32>::new());
+        assert_relative_eq!(
+            model.forward_transform(),
+            Bt2020Model.forward_transform(),
+            epsilon = 1e-4
+        );
+        assert_relative_eq!(
+            model.inverse_transform(),
+            Bt2020Model.inverse_transform(),
+            epsilon = 1e-3
+        );
+    }
 }